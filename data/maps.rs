@@ -0,0 +1,62 @@
+//! UiMap subset for the C_Map API (UiMap.db2 IDs, names and parents).
+//! Hand-maintained: covers the cosmic/world/continent chain plus the
+//! capitals and starting zones addons most often look up.
+
+/// `Enum.UIMapType` values.
+pub mod map_type {
+    pub const COSMIC: i32 = 0;
+    pub const WORLD: i32 = 1;
+    pub const CONTINENT: i32 = 2;
+    pub const ZONE: i32 = 3;
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct UiMap {
+    pub map_id: i32,
+    pub name: &'static str,
+    pub map_type: i32,
+    /// 0 for the root (Cosmic) map.
+    pub parent_map_id: i32,
+}
+
+const fn map(map_id: i32, name: &'static str, map_type: i32, parent_map_id: i32) -> UiMap {
+    UiMap { map_id, name, map_type, parent_map_id }
+}
+
+use map_type::*;
+
+pub static UI_MAPS: &[UiMap] = &[
+    map(946, "Cosmic", COSMIC, 0),
+    map(947, "Azeroth", WORLD, 946),
+    map(12, "Kalimdor", CONTINENT, 947),
+    map(13, "Eastern Kingdoms", CONTINENT, 947),
+    map(113, "Northrend", CONTINENT, 947),
+    map(424, "Pandaria", CONTINENT, 947),
+    map(619, "Broken Isles", CONTINENT, 947),
+    map(875, "Zandalar", CONTINENT, 947),
+    map(876, "Kul Tiras", CONTINENT, 947),
+    map(1978, "Dragon Isles", CONTINENT, 947),
+    map(2274, "Khaz Algar", CONTINENT, 947),
+    map(1, "Durotar", ZONE, 12),
+    map(7, "Mulgore", ZONE, 12),
+    map(57, "Teldrassil", ZONE, 12),
+    map(85, "Orgrimmar", ZONE, 12),
+    map(88, "Thunder Bluff", ZONE, 12),
+    map(37, "Elwynn Forest", ZONE, 13),
+    map(27, "Dun Morogh", ZONE, 13),
+    map(84, "Stormwind City", ZONE, 13),
+    map(87, "Ironforge", ZONE, 13),
+    map(90, "Undercity", ZONE, 13),
+    map(110, "Silvermoon City", ZONE, 13),
+    map(125, "Dalaran", ZONE, 113),
+    map(2023, "Ohn'ahran Plains", ZONE, 1978),
+    map(2025, "Thaldraszus", ZONE, 1978),
+    map(2112, "Valdrakken", ZONE, 2025),
+    map(2248, "Isle of Dorn", ZONE, 2274),
+    map(2339, "Dornogal", ZONE, 2248),
+];
+
+/// Look up a map by UiMapID.
+pub fn get_map(map_id: i32) -> Option<&'static UiMap> {
+    UI_MAPS.iter().find(|m| m.map_id == map_id)
+}
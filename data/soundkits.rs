@@ -0,0 +1,62 @@
+//! SoundKit ID -> sound file table for common UI sounds.
+//!
+//! IDs come from `SOUNDKIT` in Blizzard_SharedXML/Mainline/SoundKitConstants.lua;
+//! file names are relative to the simulator's `sounds/` directory. Kits whose
+//! file isn't shipped still resolve, so `PlaySound` reports them as playable.
+//! Sorted by ID for binary search.
+
+pub static SOUNDKIT_FILES: &[(u32, &str)] = &[
+    (821, "igminimapopen.ogg"),            // IG_MINIMAP_OPEN
+    (822, "igminimapclose.ogg"),           // IG_MINIMAP_CLOSE
+    (823, "igminimapzoomin.ogg"),          // IG_MINIMAP_ZOOM_IN
+    (824, "igminimapzoomout.ogg"),         // IG_MINIMAP_ZOOM_OUT
+    (825, "igchatemotebutton.ogg"),        // IG_CHAT_EMOTE_BUTTON
+    (826, "igchatscrollup.ogg"),           // IG_CHAT_SCROLL_UP
+    (827, "igchatscrolldown.ogg"),         // IG_CHAT_SCROLL_DOWN
+    (828, "igchatbottom.ogg"),             // IG_CHAT_BOTTOM
+    (829, "igspellbookopen.ogg"),          // IG_SPELLBOOK_OPEN
+    (830, "igspellbookclose.ogg"),         // IG_SPELLBOOK_CLOSE
+    (834, "igabilityopen.ogg"),            // IG_ABILITY_OPEN
+    (835, "igabilityclose.ogg"),           // IG_ABILITY_CLOSE
+    (836, "igabilitypageturn.ogg"),        // IG_ABILITY_PAGE_TURN
+    (838, "igabilityicondrop.ogg"),        // IG_ABILITY_ICON_DROP
+    (839, "igcharacterinfoopen.ogg"),      // IG_CHARACTER_INFO_OPEN
+    (840, "igcharacterinfoclose.ogg"),     // IG_CHARACTER_INFO_CLOSE
+    (841, "igcharacterinfotab.ogg"),       // IG_CHARACTER_INFO_TAB
+    (844, "igquestlogopen.ogg"),           // IG_QUEST_LOG_OPEN
+    (845, "igquestlogclose.ogg"),          // IG_QUEST_LOG_CLOSE
+    (846, "igquestlogabandonquest.ogg"),   // IG_QUEST_LOG_ABANDON_QUEST
+    (850, "igmainmenuopen.ogg"),           // IG_MAINMENU_OPEN
+    (851, "igmainmenuclose.ogg"),          // IG_MAINMENU_CLOSE
+    (852, "igmainmenuoption.ogg"),         // IG_MAINMENU_OPTION
+    (853, "igmainmenulogout.ogg"),         // IG_MAINMENU_LOGOUT
+    (854, "igmainmenuquit.ogg"),           // IG_MAINMENU_QUIT
+    (855, "igmainmenucontinue.ogg"),       // IG_MAINMENU_CONTINUE
+    (856, "igmainmenuoptioncheckboxon.ogg"),  // IG_MAINMENU_OPTION_CHECKBOX_ON
+    (857, "igmainmenuoptioncheckboxoff.ogg"), // IG_MAINMENU_OPTION_CHECKBOX_OFF
+    (858, "igmainmenuoptioncheckboxoff.ogg"), // IG_MAINMENU_OPTION_FAER_TAB
+    (862, "igbackpackopen.ogg"),           // IG_BACKPACK_OPEN
+    (863, "igbackpackclose.ogg"),          // IG_BACKPACK_CLOSE
+    (864, "igbackpackcoinselect.ogg"),     // IG_BACKPACK_COIN_SELECT
+    (865, "igbackpackcoinok.ogg"),         // IG_BACKPACK_COIN_OK
+    (866, "igbackpackcoincancel.ogg"),     // IG_BACKPACK_COIN_CANCEL
+    (875, "igquestlistopen.ogg"),          // IG_QUEST_LIST_OPEN
+    (876, "igquestlistclose.ogg"),         // IG_QUEST_LIST_CLOSE
+    (877, "igquestlistselect.ogg"),        // IG_QUEST_LIST_SELECT
+    (878, "igquestlistcomplete.ogg"),      // IG_QUEST_LIST_COMPLETE
+    (879, "igquestcancel.ogg"),            // IG_QUEST_CANCEL
+    (891, "moneyframeopen.ogg"),           // MONEY_FRAME_OPEN
+    (892, "moneyframeclose.ogg"),          // MONEY_FRAME_CLOSE
+    (1115, "uchatscrollbutton.ogg"),       // U_CHAT_SCROLL_BUTTON
+    // UI_CLASS_TALENT_OPEN/CLOSE_WINDOW — fall back to the classic spellbook sounds
+    (207757, "igspellbookopen.ogg"),
+    (207758, "igspellbookclose.ogg"),
+];
+
+/// Sound file for a SoundKit ID, relative to the sound directory.
+pub fn soundkit_file(id: u32) -> Option<&'static str> {
+    SOUNDKIT_FILES
+        .binary_search_by_key(&id, |&(kit, _)| kit)
+        .ok()
+        .map(|i| SOUNDKIT_FILES[i].1)
+}
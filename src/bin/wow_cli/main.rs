@@ -1,16 +1,24 @@
 //! WoW UI Simulator CLI - thin client for a running wow-sim server.
 //!
-//! All commands except extract-textures, convert-texture, and generate require a running wow-sim
-//! instance.
+//! All commands except compare, extract-textures, convert-texture, generate, and clear-cache
+//! require a running wow-sim instance.
 //!
 //! Usage:
 //!   wow-cli lua                      # Interactive Lua REPL
 //!   wow-cli lua -e "print('hi')"     # Execute code and exit
+//!   wow-cli lua --eval "GetTime()"   # Evaluate an expression, print values as JSON
+//!   wow-cli lua --tail               # Stream print/error output until Ctrl+C
+//!   wow-cli reload-addon TomTom      # Re-run an addon's TOC in the running server
+//!   wow-cli save-vars                # Write SavedVariables from running server
 //!   wow-cli dump-tree                # Dump frame tree from running server
+//!   wow-cli dump-tree --json         # Same, as JSON with absolute rects
 //!   wow-cli screenshot -o out.webp   # Render screenshot via running server
+//!   wow-cli compare --baseline a.webp --candidate b.webp --out diff.webp
+//!                                    # Diff two screenshots (standalone)
 //!   wow-cli extract-textures         # Extract textures to WebP (standalone)
 //!   wow-cli convert-texture foo.BLP  # Convert single BLP to WebP (standalone)
 //!   wow-cli generate spells          # Regenerate data/spells.rs from CSVs
+//!   wow-cli clear-cache              # Delete cached Lua bytecode (standalone)
 
 mod csv_util;
 mod gen_atlas;
@@ -43,6 +51,14 @@ enum Commands {
         #[arg(short = 'e', long)]
         exec: Option<String>,
 
+        /// Evaluate an expression and print its return values as JSON
+        #[arg(long, conflicts_with = "exec")]
+        eval: Option<String>,
+
+        /// Stream print and error output from the server until interrupted
+        #[arg(long)]
+        tail: bool,
+
         /// Execute file and exit
         #[arg(short = 'f', long)]
         file: Option<PathBuf>,
@@ -52,6 +68,18 @@ enum Commands {
         list: bool,
     },
 
+    /// Re-run an addon's TOC in the running server and fire ADDON_LOADED
+    ReloadAddon {
+        /// Addon folder name
+        name: String,
+    },
+
+    /// Write all addons' SavedVariables back to storage (requires running server)
+    SaveVars,
+
+    /// Print the missing-API report (requires a server started with --api-coverage)
+    ApiCoverage,
+
     /// Dump the rendered frame tree (requires running server)
     DumpTree {
         /// Filter by frame name (substring match)
@@ -61,6 +89,21 @@ enum Commands {
         /// Show only visible frames
         #[arg(long)]
         visible_only: bool,
+
+        /// Print the tree as JSON with absolute rects
+        #[arg(long)]
+        json: bool,
+
+        /// List frames matching all predicates, e.g. "type=Button visible=true minwidth=100"
+        #[arg(long)]
+        query: Option<String>,
+    },
+
+    /// Print the rects of all named frames without rendering (requires running server)
+    Layout {
+        /// Print the rects as JSON
+        #[arg(long)]
+        json: bool,
     },
 
     /// Render UI to an image file (requires running server)
@@ -84,6 +127,33 @@ enum Commands {
         /// Crop the output image to WxH+X+Y (e.g., 700x150+400+650)
         #[arg(long, value_name = "WxH+X+Y")]
         crop: Option<String>,
+
+        /// Render only frames at or above this strata (e.g. DIALOG, TOOLTIP)
+        #[arg(long, value_name = "STRATA")]
+        min_strata: Option<String>,
+    },
+
+    /// Compare two screenshots and write a diff heatmap (standalone)
+    Compare {
+        /// Reference image
+        #[arg(long)]
+        baseline: PathBuf,
+
+        /// Image to check against the baseline
+        #[arg(long)]
+        candidate: PathBuf,
+
+        /// Diff heatmap output path (format from extension)
+        #[arg(long)]
+        out: Option<PathBuf>,
+
+        /// Maximum fraction of differing pixels (0.0-1.0) before exiting nonzero
+        #[arg(long, default_value_t = 0.0)]
+        threshold: f64,
+
+        /// Per-channel difference (0-255) below which pixels count as equal
+        #[arg(long, default_value_t = 0)]
+        tolerance: u8,
     },
 
     /// Extract textures referenced by addons to WebP format (standalone)
@@ -109,6 +179,10 @@ enum Commands {
         /// Output WebP file path (defaults to input with .webp extension)
         #[arg(short, long)]
         output: Option<PathBuf>,
+
+        /// Mip level to extract (0 = full size, each level halves)
+        #[arg(long, default_value_t = 0)]
+        mip: usize,
     },
 
     /// Generate static data files from WoW CSV exports (standalone)
@@ -116,6 +190,9 @@ enum Commands {
         #[command(subcommand)]
         what: GenerateTarget,
     },
+
+    /// Delete the Lua bytecode cache in ./.cache/lua-bytecode (standalone)
+    ClearCache,
 }
 
 #[derive(Subcommand)]
@@ -148,34 +225,58 @@ fn main() {
     let cli = Cli::parse();
 
     match cli.command {
-        Commands::Lua { exec, file, list } => {
+        Commands::Lua { exec, eval, tail, file, list } => {
             if list {
                 list_servers();
+            } else if tail {
+                tail_console();
             } else if let Some(code) = exec {
                 execute_and_exit(&code);
+            } else if let Some(code) = eval {
+                evaluate_and_exit(&code);
             } else if let Some(path) = file {
                 execute_file_and_exit(&path);
             } else {
                 run_repl();
             }
         }
-        Commands::DumpTree { filter, visible_only } => {
-            dump_tree(filter, visible_only);
+        Commands::ReloadAddon { name } => {
+            reload_addon(&name);
+        }
+        Commands::SaveVars => save_vars(),
+        Commands::ApiCoverage => api_coverage(),
+        Commands::DumpTree { filter, visible_only, json, query } => {
+            dump_tree(filter, visible_only, json, query);
         }
-        Commands::Screenshot { output, width, height, filter, crop } => {
-            take_screenshot(&output, width, height, filter, crop);
+        Commands::Layout { json } => layout(json),
+        Commands::Screenshot { output, width, height, filter, crop, min_strata } => {
+            take_screenshot(&output, width, height, filter, crop, min_strata);
+        }
+        Commands::Compare { baseline, candidate, out, threshold, tolerance } => {
+            compare_images(&baseline, &candidate, out.as_ref(), threshold, tolerance);
         }
         Commands::ExtractTextures { addons, interface, output } => {
             let (found, missing) =
                 wow_ui_sim::extract_textures::extract_textures(&addons, &interface, &output);
             println!("\nSummary: {} converted, {} missing", found, missing);
         }
-        Commands::ConvertTexture { input, output } => {
-            convert_texture(&input, output.as_ref());
+        Commands::ConvertTexture { input, output, mip } => {
+            convert_texture(&input, output.as_ref(), mip);
         }
         Commands::Generate { what } => {
             run_generator(what);
         }
+        Commands::ClearCache => clear_cache(),
+    }
+}
+
+fn clear_cache() {
+    match wow_ui_sim::loader::bytecode_cache::clear() {
+        Ok(removed) => println!("Removed {} cached bytecode file(s)", removed),
+        Err(e) => {
+            eprintln!("Error: failed to clear bytecode cache: {}", e);
+            std::process::exit(1);
+        }
     }
 }
 
@@ -254,6 +355,31 @@ fn execute_and_exit(code: &str) {
     }
 }
 
+/// Print each return value on its own line as compact JSON.
+fn evaluate_and_exit(code: &str) {
+    let socket = resolve_socket();
+    match client::eval(&socket, code) {
+        Ok(values) => {
+            for value in values {
+                println!("{}", value.to_json());
+            }
+        }
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+fn tail_console() {
+    let socket = resolve_socket();
+    eprintln!("Tailing {} (Ctrl+C to stop)", socket.display());
+    if let Err(e) = client::tail(&socket, |line| println!("{}", line)) {
+        eprintln!("Error: {}", e);
+        std::process::exit(1);
+    }
+}
+
 fn execute_file_and_exit(path: &PathBuf) {
     let code = match std::fs::read_to_string(path) {
         Ok(c) => c,
@@ -265,9 +391,42 @@ fn execute_file_and_exit(path: &PathBuf) {
     execute_and_exit(&code);
 }
 
-fn dump_tree(filter: Option<String>, visible_only: bool) {
+fn reload_addon(name: &str) {
+    let socket = resolve_socket();
+    match client::reload_addon(&socket, name) {
+        Ok(report) => println!("{}", report),
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+fn save_vars() {
+    let socket = resolve_socket();
+    match client::save_vars(&socket) {
+        Ok(msg) => println!("{}", msg),
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+fn api_coverage() {
     let socket = resolve_socket();
-    match client::dump_tree(&socket, filter, visible_only) {
+    match client::api_coverage(&socket) {
+        Ok(report) => print!("{}", report),
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+fn dump_tree(filter: Option<String>, visible_only: bool, json: bool, query: Option<String>) {
+    let socket = resolve_socket();
+    match client::dump_tree(&socket, filter, visible_only, json, query) {
         Ok(tree) => println!("{}", tree),
         Err(e) => {
             eprintln!("Error: {}", e);
@@ -276,13 +435,31 @@ fn dump_tree(filter: Option<String>, visible_only: bool) {
     }
 }
 
-fn take_screenshot(output: &PathBuf, width: u32, height: u32, filter: Option<String>, crop: Option<String>) {
+fn layout(json: bool) {
+    let socket = resolve_socket();
+    match client::layout(&socket, json) {
+        Ok(layout) => println!("{}", layout),
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+fn take_screenshot(
+    output: &PathBuf,
+    width: u32,
+    height: u32,
+    filter: Option<String>,
+    crop: Option<String>,
+    min_strata: Option<String>,
+) {
     let socket = resolve_socket();
     // Canonicalize output path so the server can write to the right location
     let abs_output = std::env::current_dir()
         .map(|cwd| cwd.join(output))
         .unwrap_or_else(|_| output.clone());
-    match client::screenshot(&socket, &abs_output.to_string_lossy(), width, height, filter, crop) {
+    match client::screenshot(&socket, &abs_output.to_string_lossy(), width, height, filter, crop, min_strata) {
         Ok(msg) => println!("{}", msg),
         Err(e) => {
             eprintln!("Error: {}", e);
@@ -291,33 +468,65 @@ fn take_screenshot(output: &PathBuf, width: u32, height: u32, filter: Option<Str
     }
 }
 
-fn convert_texture(input: &PathBuf, output: Option<&PathBuf>) {
-    use image_blp::{convert::blp_to_image, parser::load_blp};
-
-    let output_path = match output {
-        Some(p) => p.clone(),
-        None => input.with_extension("webp"),
+fn compare_images(
+    baseline: &PathBuf,
+    candidate: &PathBuf,
+    out: Option<&PathBuf>,
+    threshold: f64,
+    tolerance: u8,
+) {
+    let load = |path: &PathBuf| match image::open(path) {
+        Ok(img) => img.to_rgba8(),
+        Err(e) => {
+            eprintln!("Error loading {}: {}", path.display(), e);
+            std::process::exit(1);
+        }
     };
+    let (base_img, cand_img) = (load(baseline), load(candidate));
 
-    let blp = match load_blp(input) {
-        Ok(b) => b,
+    let result = match wow_ui_sim::render::diff::diff_images(&base_img, &cand_img, tolerance) {
+        Ok(r) => r,
         Err(e) => {
-            eprintln!("Error loading BLP {}: {}", input.display(), e);
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    if let Some(out) = out {
+        if let Err(e) = result.diff_image.save(out) {
+            eprintln!("Error saving {}: {}", out.display(), e);
             std::process::exit(1);
         }
+        println!("Diff image written to {}", out.display());
+    }
+
+    println!(
+        "{} of {} pixels differ ({:.4}%), max channel delta {}",
+        result.differing_pixels,
+        result.total_pixels,
+        result.fraction() * 100.0,
+        result.max_delta
+    );
+    if result.fraction() > threshold {
+        eprintln!("Difference exceeds threshold {}", threshold);
+        std::process::exit(1);
+    }
+}
+
+fn convert_texture(input: &PathBuf, output: Option<&PathBuf>, mip: usize) {
+    let output_path = match output {
+        Some(p) => p.clone(),
+        None => input.with_extension("webp"),
     };
 
-    let img = match blp_to_image(&blp, 0) {
-        Ok(i) => i,
+    let rgba = match wow_ui_sim::texture::convert_blp_mip(input, mip) {
+        Ok(img) => img,
         Err(e) => {
-            eprintln!("Error converting BLP: {}", e);
+            eprintln!("Error converting BLP {}: {}", input.display(), e);
             std::process::exit(1);
         }
     };
 
-    let mut rgba = img.to_rgba8();
-    // Fix 1-bit alpha: image-blp decodes 1-bit alpha as literal 0/1 byte values
-    wow_ui_sim::texture::fix_1bit_alpha(rgba.as_mut());
     if let Err(e) = rgba.save(&output_path) {
         eprintln!("Error saving {}: {}", output_path.display(), e);
         std::process::exit(1);
@@ -1,7 +1,9 @@
 //! Console Variable (CVar) storage.
 //!
 //! CVars are configuration values that addons can read/write.
-//! Defaults come from WoW's built-in cvars, overrides are persisted to disk.
+//! Defaults come from WoW's built-in cvars (`cvars.yaml`) plus anything
+//! registered at runtime via `RegisterCVar`; overrides are persisted to disk
+//! unless the storage is in-memory.
 
 use std::collections::HashMap;
 use std::path::PathBuf;
@@ -15,38 +17,70 @@ fn default_storage_path() -> PathBuf {
         .join("cvars.json")
 }
 
+/// Value type of a CVar, inferred from its default.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CVarType {
+    /// "0" / "1"
+    Boolean,
+    Number,
+    String,
+}
+
+impl CVarType {
+    fn infer(default: &str) -> Self {
+        match default {
+            "0" | "1" => Self::Boolean,
+            v if v.parse::<f64>().is_ok() => Self::Number,
+            _ => Self::String,
+        }
+    }
+}
+
+/// A registered CVar: its default value and type.
+#[derive(Debug, Clone)]
+pub struct CVarDefault {
+    pub value: String,
+    pub cvar_type: CVarType,
+}
+
+impl CVarDefault {
+    fn new(value: &str) -> Self {
+        Self { value: value.to_string(), cvar_type: CVarType::infer(value) }
+    }
+}
+
 /// CVar storage with defaults and overrides.
 pub struct CVarStorage {
-    /// Default values (lowercase key -> value)
-    defaults: HashMap<String, String>,
+    /// Registered CVars (lowercase key -> default). Unregistered names have no value.
+    defaults: RwLock<HashMap<String, CVarDefault>>,
     /// Runtime overrides (lowercase key -> value), persisted to disk.
     overrides: RwLock<HashMap<String, String>>,
-    /// Path to persist overrides.
-    storage_path: PathBuf,
+    /// Path to persist overrides; `None` keeps them in memory only.
+    storage_path: Option<PathBuf>,
 }
 
 impl CVarStorage {
     /// Create storage with defaults parsed from YAML, loading persisted overrides from disk.
     pub fn new() -> Self {
-        let path = default_storage_path();
-        let defaults = parse_cvar_yaml(include_str!("cvars.yaml"));
+        Self::with_path(default_storage_path())
+    }
+
+    /// Create storage persisted at a custom path (tests and isolated runs).
+    pub fn with_path(path: PathBuf) -> Self {
         let overrides = load_overrides(&path);
         Self {
-            defaults,
+            defaults: RwLock::new(builtin_defaults()),
             overrides: RwLock::new(overrides),
-            storage_path: path,
+            storage_path: Some(path),
         }
     }
 
-    /// Create storage with a custom path (for testing).
-    #[cfg(test)]
-    fn with_path(path: PathBuf) -> Self {
-        let defaults = parse_cvar_yaml(include_str!("cvars.yaml"));
-        let overrides = load_overrides(&path);
+    /// Create storage that starts from the defaults and never touches disk.
+    pub fn in_memory() -> Self {
         Self {
-            defaults,
-            overrides: RwLock::new(overrides),
-            storage_path: path,
+            defaults: RwLock::new(builtin_defaults()),
+            overrides: RwLock::new(HashMap::new()),
+            storage_path: None,
         }
     }
 
@@ -58,17 +92,23 @@ impl CVarStorage {
             return Some(value.clone());
         }
         // Fall back to defaults
-        self.defaults.get(&key).cloned()
+        self.get_default(&key)
     }
 
-    /// Get the default value for a CVar.
+    /// Get the registered default value for a CVar.
     pub fn get_default(&self, name: &str) -> Option<String> {
-        self.defaults.get(&name.to_lowercase()).cloned()
+        self.defaults.read().unwrap().get(&name.to_lowercase()).map(|d| d.value.clone())
     }
 
-    /// Get a CVar as a boolean ("1" = true, anything else = false).
+    /// Get the value type of a registered CVar.
+    pub fn cvar_type(&self, name: &str) -> Option<CVarType> {
+        self.defaults.read().unwrap().get(&name.to_lowercase()).map(|d| d.cvar_type)
+    }
+
+    /// Get a CVar as a boolean: "1" (or any non-zero number) is true,
+    /// "0", other strings and unknown CVars are false.
     pub fn get_bool(&self, name: &str) -> bool {
-        self.get(name).as_deref() == Some("1")
+        self.get(name).is_some_and(|v| v.parse::<f64>().is_ok_and(|n| n != 0.0))
     }
 
     /// Set a CVar value and persist to disk.
@@ -82,28 +122,25 @@ impl CVarStorage {
         true
     }
 
-    /// Register a new CVar with a default value.
+    /// Register a new CVar with a default value. Built-in CVars keep their default.
     pub fn register(&self, name: &str, default: Option<&str>) {
-        if let Some(value) = default {
-            let key = name.to_lowercase();
-            // Only set if not already in defaults
-            if !self.defaults.contains_key(&key) {
-                self.overrides
-                    .write()
-                    .unwrap()
-                    .insert(key, value.to_string());
-            }
-        }
+        let key = name.to_lowercase();
+        self.defaults
+            .write()
+            .unwrap()
+            .entry(key)
+            .or_insert_with(|| CVarDefault::new(default.unwrap_or("")));
     }
 
     /// Persist current overrides to disk.
     fn save(&self) {
-        if let Some(parent) = self.storage_path.parent() {
+        let Some(path) = &self.storage_path else { return };
+        if let Some(parent) = path.parent() {
             let _ = std::fs::create_dir_all(parent);
         }
         let overrides = self.overrides.read().unwrap();
         if let Ok(json) = serde_json::to_string_pretty(&*overrides) {
-            let _ = std::fs::write(&self.storage_path, json);
+            let _ = std::fs::write(path, json);
         }
     }
 }
@@ -114,6 +151,14 @@ impl Default for CVarStorage {
     }
 }
 
+/// Built-in CVar defaults from `cvars.yaml`, with types inferred from the values.
+fn builtin_defaults() -> HashMap<String, CVarDefault> {
+    parse_cvar_yaml(include_str!("cvars.yaml"))
+        .into_iter()
+        .map(|(k, v)| (k, CVarDefault::new(&v)))
+        .collect()
+}
+
 /// Load persisted overrides from disk.
 fn load_overrides(path: &PathBuf) -> HashMap<String, String> {
     match std::fs::read_to_string(path) {
@@ -165,7 +210,7 @@ mod tests {
 
     #[test]
     fn test_get_set() {
-        let storage = CVarStorage::new();
+        let storage = CVarStorage::in_memory();
         // Test default
         assert!(storage.get("nameplateShowEnemies").is_some());
         // Test override
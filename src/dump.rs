@@ -4,6 +4,7 @@
 //! the connected `wow-cli dump-tree` (via iced_app debug server).
 
 use crate::iced_app::layout::{anchor_position, compute_frame_rect};
+use crate::lua_api::globals::utf8_api::truncate_chars;
 use crate::widget::{Frame, WidgetRegistry, WidgetType};
 use crate::LayoutRect;
 use regex::RegexBuilder;
@@ -66,6 +67,44 @@ pub fn build_tree(
     lines
 }
 
+/// Build the frame tree as a JSON document (for `dump-tree --json`).
+///
+/// Layout: `{"screen": {"width", "height"}, "frames": [node...]}` where each
+/// node carries `name` (null for anonymous frames), `type`, absolute `rect`,
+/// `visible`, `strata`, `level`, `parentKey`, `anchors`, `text` and nested
+/// `children`. With `filter`/`filter_key`, the roots are the outermost frames
+/// whose display name matches, each with its full subtree.
+pub fn frame_tree_json(
+    widgets: &WidgetRegistry,
+    filter: Option<&str>,
+    filter_key: Option<&str>,
+    visible_only: bool,
+    screen_width: f32,
+    screen_height: f32,
+) -> serde_json::Value {
+    let mut roots = collect_root_frames(widgets);
+    roots.sort_by(|a, b| {
+        let na = a.1.as_deref().unwrap_or("");
+        let nb = b.1.as_deref().unwrap_or("");
+        na.cmp(nb)
+    });
+    let root_ids: Vec<u64> = match filter_key.or(filter) {
+        Some(pat) => {
+            let re = RegexBuilder::new(pat).case_insensitive(true).build()
+                .unwrap_or_else(|_| RegexBuilder::new(&regex::escape(pat)).case_insensitive(true).build().unwrap());
+            collect_key_matches(widgets, &roots, &re)
+        }
+        None => roots.iter().map(|(id, _)| *id).collect(),
+    };
+    let frames: Vec<serde_json::Value> = root_ids.into_iter()
+        .filter_map(|id| frame_json(widgets, id, visible_only, screen_width, screen_height))
+        .collect();
+    serde_json::json!({
+        "screen": { "width": screen_width, "height": screen_height },
+        "frames": frames,
+    })
+}
+
 /// Build a compact dump with warning flags (for debug server Dump command).
 pub fn build_warning_dump(
     widgets: &WidgetRegistry,
@@ -92,6 +131,187 @@ pub fn build_warning_dump(
     lines
 }
 
+/// Frames matching `query` in tree order, one line each with the computed
+/// rect (for `dump-tree --query`).
+pub fn build_query_results(
+    widgets: &WidgetRegistry,
+    query: &FrameQuery,
+    screen_width: f32,
+    screen_height: f32,
+) -> Vec<String> {
+    query_frames(widgets, query, screen_width, screen_height).into_iter()
+        .filter_map(|id| {
+            let frame = widgets.get(id)?;
+            let rect = compute_frame_rect(widgets, id, screen_width, screen_height);
+            Some(format!(
+                "{} [{}] ({:.0},{:.0} {}x{}) {}:{}",
+                resolve_display_name(widgets, frame, id),
+                frame.widget_type.as_str(),
+                rect.x, rect.y, rect.width as i32, rect.height as i32,
+                frame.frame_strata.as_str(), frame.frame_level,
+            ))
+        })
+        .collect()
+}
+
+/// Frames matching `query` as JSON: `{"screen", "frames": [{id, name, type,
+/// rect, visible, strata, level}]}` without children.
+pub fn query_results_json(
+    widgets: &WidgetRegistry,
+    query: &FrameQuery,
+    screen_width: f32,
+    screen_height: f32,
+) -> serde_json::Value {
+    let frames: Vec<serde_json::Value> = query_frames(widgets, query, screen_width, screen_height)
+        .into_iter()
+        .filter_map(|id| {
+            let frame = widgets.get(id)?;
+            let rect = compute_frame_rect(widgets, id, screen_width, screen_height);
+            Some(serde_json::json!({
+                "id": id,
+                "name": frame.name.as_deref().filter(|n| !is_generated_name(n)),
+                "type": frame.widget_type.as_str(),
+                "rect": { "x": rect.x, "y": rect.y, "width": rect.width, "height": rect.height },
+                "visible": frame.visible,
+                "strata": frame.frame_strata.as_str(),
+                "level": frame.frame_level,
+            }))
+        })
+        .collect();
+    serde_json::json!({
+        "screen": { "width": screen_width, "height": screen_height },
+        "frames": frames,
+    })
+}
+
+// ── Frame queries ───────────────────────────────────────────────────
+
+/// A frame query: whitespace-separated `key=value` predicates that must all
+/// hold, e.g. `type=Button visible=true under=ObjectiveTrackerFrame minwidth=100`.
+///
+/// Keys: `type` and `strata` (case-insensitive), `name` (regex on the display
+/// name), `visible` (the frame and all its ancestors shown), `under` (global
+/// name of any ancestor), `anchor` (name of a frame it is anchored to) and
+/// `minwidth`/`maxwidth`/`minheight`/`maxheight` (computed rect).
+#[derive(Debug, Default)]
+pub struct FrameQuery {
+    predicates: Vec<Predicate>,
+}
+
+#[derive(Debug)]
+enum Predicate {
+    Type(String),
+    Name(regex::Regex),
+    Visible(bool),
+    Strata(String),
+    Under(String),
+    Anchor(String),
+    MinWidth(f32),
+    MaxWidth(f32),
+    MinHeight(f32),
+    MaxHeight(f32),
+}
+
+impl FrameQuery {
+    /// Parse a query string. An empty query matches every frame.
+    pub fn parse(query: &str) -> Result<Self, String> {
+        let predicates = query.split_whitespace()
+            .map(parse_predicate)
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self { predicates })
+    }
+
+    /// Whether frame `id` satisfies every predicate.
+    pub fn matches(&self, widgets: &WidgetRegistry, id: u64, screen_width: f32, screen_height: f32) -> bool {
+        let Some(frame) = widgets.get(id) else { return false };
+        // Computed lazily: only size predicates need the rect.
+        let mut cached = None;
+        let mut rect = || *cached.get_or_insert_with(|| compute_frame_rect(widgets, id, screen_width, screen_height));
+        self.predicates.iter().all(|p| match p {
+            Predicate::Type(t) => frame.widget_type.as_str().eq_ignore_ascii_case(t),
+            Predicate::Name(re) => re.is_match(&resolve_display_name(widgets, frame, id)),
+            Predicate::Visible(v) => is_effectively_visible(widgets, frame) == *v,
+            Predicate::Strata(s) => frame.frame_strata.as_str().eq_ignore_ascii_case(s),
+            Predicate::Under(name) => has_named_ancestor(widgets, frame, name),
+            Predicate::Anchor(name) => is_anchored_to(widgets, frame, name),
+            Predicate::MinWidth(w) => rect().width >= *w,
+            Predicate::MaxWidth(w) => rect().width <= *w,
+            Predicate::MinHeight(h) => rect().height >= *h,
+            Predicate::MaxHeight(h) => rect().height <= *h,
+        })
+    }
+}
+
+fn parse_predicate(term: &str) -> Result<Predicate, String> {
+    let (key, value) = term.split_once('=')
+        .filter(|(_, v)| !v.is_empty())
+        .ok_or_else(|| format!("expected key=value, got '{term}'"))?;
+    let number = || value.parse::<f32>().map_err(|_| format!("{key}: '{value}' is not a number"));
+    Ok(match key.to_ascii_lowercase().as_str() {
+        "type" => Predicate::Type(value.to_string()),
+        "name" => Predicate::Name(RegexBuilder::new(value).case_insensitive(true).build()
+            .map_err(|e| format!("name: {e}"))?),
+        "visible" => match value {
+            "true" | "1" => Predicate::Visible(true),
+            "false" | "0" => Predicate::Visible(false),
+            _ => return Err(format!("visible: expected true or false, got '{value}'")),
+        },
+        "strata" => Predicate::Strata(value.to_string()),
+        "under" => Predicate::Under(value.to_string()),
+        "anchor" => Predicate::Anchor(value.to_string()),
+        "minwidth" => Predicate::MinWidth(number()?),
+        "maxwidth" => Predicate::MaxWidth(number()?),
+        "minheight" => Predicate::MinHeight(number()?),
+        "maxheight" => Predicate::MaxHeight(number()?),
+        _ => return Err(format!("unknown query key '{key}'")),
+    })
+}
+
+/// Ids of all frames matching `query`, parents before children, roots by name.
+pub fn query_frames(widgets: &WidgetRegistry, query: &FrameQuery, screen_width: f32, screen_height: f32) -> Vec<u64> {
+    let mut roots = collect_root_frames(widgets);
+    roots.sort_by(|a, b| a.1.as_deref().unwrap_or("").cmp(b.1.as_deref().unwrap_or("")));
+    let mut result = Vec::new();
+    let mut stack: Vec<u64> = roots.iter().rev().map(|(id, _)| *id).collect();
+    while let Some(id) = stack.pop() {
+        let Some(frame) = widgets.get(id) else { continue };
+        if query.matches(widgets, id, screen_width, screen_height) {
+            result.push(id);
+        }
+        stack.extend(frame.children.iter().rev());
+    }
+    result
+}
+
+fn is_effectively_visible(widgets: &WidgetRegistry, frame: &Frame) -> bool {
+    let mut current = Some(frame);
+    while let Some(f) = current {
+        if !f.visible {
+            return false;
+        }
+        current = f.parent_id.and_then(|pid| widgets.get(pid));
+    }
+    true
+}
+
+fn has_named_ancestor(widgets: &WidgetRegistry, frame: &Frame, name: &str) -> bool {
+    let mut current = frame.parent_id.and_then(|pid| widgets.get(pid));
+    while let Some(f) = current {
+        if f.name.as_deref() == Some(name) {
+            return true;
+        }
+        current = f.parent_id.and_then(|pid| widgets.get(pid));
+    }
+    false
+}
+
+fn is_anchored_to(widgets: &WidgetRegistry, frame: &Frame, name: &str) -> bool {
+    frame.anchors.iter().any(|a| match a.relative_to_id {
+        Some(rid) => widgets.get(rid as u64).and_then(|f| f.name.as_deref()) == Some(name),
+        None => a.relative_to.as_deref() == Some(name),
+    })
+}
+
 // ── Frame line formatting ───────────────────────────────────────────
 
 /// Emit a single frame line with computed rect, stored size, anchors, texture.
@@ -241,6 +461,52 @@ fn format_font_str(frame: &Frame) -> String {
     }
 }
 
+// ── JSON nodes ──────────────────────────────────────────────────────
+
+fn frame_json(
+    widgets: &WidgetRegistry,
+    id: u64,
+    visible_only: bool,
+    screen_width: f32,
+    screen_height: f32,
+) -> Option<serde_json::Value> {
+    let frame = widgets.get(id)?;
+    if visible_only && !frame.visible {
+        return None;
+    }
+    let rect = compute_frame_rect(widgets, id, screen_width, screen_height);
+    let anchors: Vec<serde_json::Value> = frame.anchors.iter().map(|a| {
+        let relative_to = a.relative_to_id
+            .and_then(|rid| widgets.get(rid as u64))
+            .and_then(|f| f.name.clone())
+            .filter(|n| !is_generated_name(n))
+            .or_else(|| a.relative_to.clone());
+        serde_json::json!({
+            "point": a.point.as_str(),
+            "relativeTo": relative_to,
+            "relativePoint": a.relative_point.as_str(),
+            "x": a.x_offset,
+            "y": a.y_offset,
+        })
+    }).collect();
+    let children: Vec<serde_json::Value> = frame.children.iter()
+        .filter_map(|&cid| frame_json(widgets, cid, visible_only, screen_width, screen_height))
+        .collect();
+    Some(serde_json::json!({
+        "id": id,
+        "name": frame.name.as_deref().filter(|n| !is_generated_name(n)),
+        "type": frame.widget_type.as_str(),
+        "rect": { "x": rect.x, "y": rect.y, "width": rect.width, "height": rect.height },
+        "visible": frame.visible,
+        "strata": frame.frame_strata.as_str(),
+        "level": frame.frame_level,
+        "parentKey": resolve_parent_key(widgets, frame, id),
+        "anchors": anchors,
+        "text": frame.text.as_deref().filter(|t| !t.is_empty()).map(strip_wow_escapes),
+        "children": children,
+    }))
+}
+
 // ── Tree traversal ──────────────────────────────────────────────────
 
 /// Emit a full subtree unconditionally (for filter_key matches).
@@ -371,28 +637,38 @@ fn collect_root_frames(widgets: &WidgetRegistry) -> Vec<(u64, Option<String>)> {
         .collect()
 }
 
+/// Internal names assigned to frames created without a global name.
+pub(crate) fn is_generated_name(name: &str) -> bool {
+    name.starts_with("__anon_")
+        || name.starts_with("__frame_")
+        || name.starts_with("__tex_")
+        || name.starts_with("__fs_")
+}
+
+/// The key under which the parent stores this frame, if any.
+fn resolve_parent_key(widgets: &WidgetRegistry, frame: &Frame, id: u64) -> Option<String> {
+    let parent = widgets.get(frame.parent_id?)?;
+    // A child can sit under several keys; pick the smallest so output is stable.
+    parent.children_keys.iter()
+        .filter(|&(_, &child_id)| child_id == id)
+        .map(|(key, _)| key)
+        .min()
+        .cloned()
+}
+
 /// Global name > parentKey > anonymous fallback.
 fn resolve_display_name(widgets: &WidgetRegistry, frame: &Frame, id: u64) -> String {
     if let Some(ref name) = frame.name
-        && !name.starts_with("__anon_")
-            && !name.starts_with("__frame_")
-            && !name.starts_with("__tex_")
-            && !name.starts_with("__fs_")
-        {
+        && !is_generated_name(name) {
             return name.clone();
         }
-    if let Some(parent_id) = frame.parent_id
-        && let Some(parent) = widgets.get(parent_id) {
-            for (key, &child_id) in &parent.children_keys {
-                if child_id == id {
-                    return format!(".{key}");
-                }
-            }
-        }
+    if let Some(key) = resolve_parent_key(widgets, frame, id) {
+        return format!(".{key}");
+    }
     // For anonymous frames with text, show a text preview
     if let Some(ref text) = frame.text {
-        if text.len() > 20 {
-            return format!("\"{}...\"", &text[..17]);
+        if text.chars().count() > 20 {
+            return format!("\"{}...\"", truncate_chars(text, 17));
         }
         return format!("\"{text}\"");
     }
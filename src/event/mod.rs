@@ -2,6 +2,8 @@
 
 use std::collections::HashMap;
 
+pub mod recorder;
+
 /// Common WoW events that addons typically use.
 pub mod events {
     pub const PLAYER_LOGIN: &str = "PLAYER_LOGIN";
@@ -37,8 +39,9 @@ pub struct Event {
     pub args: Vec<EventArg>,
 }
 
-/// Event argument types.
-#[derive(Debug, Clone)]
+/// Event argument types. Serialized untagged (`"x"`, `1.5`, `true`, `null`).
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(untagged)]
 pub enum EventArg {
     String(String),
     Number(f64),
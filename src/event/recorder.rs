@@ -0,0 +1,157 @@
+//! Event recording and replay.
+//!
+//! When enabled (`--record-events <path>` or `WowLuaEnv::start_event_recording`),
+//! every event dispatched to OnEvent handlers is logged with its arguments and
+//! the `SimClock` time. With a path, each event is also appended to the file
+//! as one JSON line, so the log survives however the process exits.
+//! `WowLuaEnv::replay_events` feeds a log back through `fire_event_with_args`
+//! to reproduce bugs that depend on a specific event order.
+
+use super::EventArg;
+use mlua::{Lua, Value};
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::{LineWriter, Write};
+use std::path::Path;
+
+/// One dispatched event.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecordedEvent {
+    /// `GetTime()` when the event fired.
+    pub time: f64,
+    pub event: String,
+    pub args: Vec<EventArg>,
+}
+
+/// Event log kept in memory and optionally mirrored to a JSON-lines file.
+#[derive(Debug, Default)]
+pub struct EventRecorder {
+    events: Vec<RecordedEvent>,
+    sink: Option<LineWriter<File>>,
+}
+
+impl EventRecorder {
+    /// Record in memory only.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record in memory and append each event to `path` (truncated first).
+    pub fn to_file(path: &Path) -> std::io::Result<Self> {
+        let file = File::create(path)?;
+        Ok(Self { events: Vec::new(), sink: Some(LineWriter::new(file)) })
+    }
+
+    pub fn record(&mut self, event: RecordedEvent) {
+        if let Some(sink) = &mut self.sink {
+            let line = serde_json::to_string(&event).unwrap_or_default();
+            if let Err(e) = writeln!(sink, "{line}") {
+                eprintln!("[record-events] write failed, recording to memory only: {e}");
+                self.sink = None;
+            }
+        }
+        self.events.push(event);
+    }
+
+    pub fn events(&self) -> &[RecordedEvent] {
+        &self.events
+    }
+}
+
+/// Read a JSON-lines event log written by `EventRecorder::to_file`.
+/// Errors name the 1-based line number.
+pub fn load_event_log(path: &Path) -> Result<Vec<RecordedEvent>, String> {
+    let source = std::fs::read_to_string(path).map_err(|e| format!("{}: {e}", path.display()))?;
+    source
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| serde_json::from_str(line).map_err(|e| format!("line {}: {e}", i + 1)))
+        .collect()
+}
+
+/// The part of a log recorded after the login sequence. A log captured with
+/// `--record-events` starts with the load-time ADDON_LOADED events and the
+/// startup events through PLAYER_LEAVING_WORLD, which a headless run has
+/// already fired itself. Logs without that marker are returned whole.
+pub fn events_after_startup(events: &[RecordedEvent]) -> &[RecordedEvent] {
+    match events.iter().position(|e| e.event == "PLAYER_LEAVING_WORLD") {
+        Some(end) => &events[end + 1..],
+        None => events,
+    }
+}
+
+impl EventArg {
+    /// Convert a dispatched Lua argument. Tables, functions and frames have no
+    /// portable form and record as nil.
+    pub fn from_lua(value: &Value) -> Self {
+        match value {
+            Value::String(s) => EventArg::String(s.to_string_lossy().to_string()),
+            Value::Integer(n) => EventArg::Number(*n as f64),
+            Value::Number(n) => EventArg::Number(*n),
+            Value::Boolean(b) => EventArg::Boolean(*b),
+            _ => EventArg::Nil,
+        }
+    }
+
+    pub fn to_lua(&self, lua: &Lua) -> mlua::Result<Value> {
+        Ok(match self {
+            EventArg::String(s) => Value::String(lua.create_string(s)?),
+            EventArg::Number(n) if n.fract() == 0.0 && n.abs() < i64::MAX as f64 => {
+                Value::Integer(*n as i64)
+            }
+            EventArg::Number(n) => Value::Number(*n),
+            EventArg::Boolean(b) => Value::Boolean(*b),
+            EventArg::Nil => Value::Nil,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn event_log_round_trips_through_json_lines() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("events.jsonl");
+        let events = vec![
+            RecordedEvent {
+                time: 1.0,
+                event: "ADDON_LOADED".into(),
+                args: vec![EventArg::String("MyAddon".into()), EventArg::Boolean(false)],
+            },
+            RecordedEvent { time: 1.5, event: "UNIT_HEALTH".into(), args: vec![EventArg::Nil, EventArg::Number(2.5)] },
+        ];
+        {
+            let mut recorder = EventRecorder::to_file(&path).unwrap();
+            for event in &events {
+                recorder.record(event.clone());
+            }
+            assert_eq!(recorder.events(), events.as_slice());
+        }
+        assert_eq!(load_event_log(&path).unwrap(), events);
+    }
+
+    #[test]
+    fn startup_prefix_is_skipped() {
+        let event = |name: &str| RecordedEvent { time: 0.0, event: name.into(), args: Vec::new() };
+        let events = vec![
+            event("ADDON_LOADED"),
+            event("PLAYER_LOGIN"),
+            event("PLAYER_LEAVING_WORLD"),
+            event("UNIT_HEALTH"),
+        ];
+        assert_eq!(events_after_startup(&events), &events[3..]);
+        assert_eq!(events_after_startup(&events[3..]), &events[3..]);
+    }
+
+    #[test]
+    fn bad_log_line_is_reported() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("events.jsonl");
+        std::fs::write(&path, "{\"time\":1.0,\"event\":\"A\",\"args\":[]}\nnot json\n").unwrap();
+        let err = load_event_log(&path).unwrap_err();
+        assert!(err.starts_with("line 2:"), "{err}");
+    }
+}
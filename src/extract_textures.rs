@@ -1,9 +1,9 @@
 //! Extract and convert textures referenced by addons to WebP format.
 
-use image_blp::{convert::blp_to_image, parser::load_blp};
 use regex::Regex;
 use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
 use walkdir::WalkDir;
 
 /// Extract all texture paths referenced in addon XML and Lua files.
@@ -97,18 +97,9 @@ pub fn convert_to_webp(src: &Path, dst: &Path) -> Result<(), Box<dyn std::error:
     let ext = src.extension().and_then(|e| e.to_str()).unwrap_or("");
 
     if ext.eq_ignore_ascii_case("blp") {
-        // BLP uses image 0.24, we use 0.25, so extract raw pixels
-        let blp = load_blp(src)?;
-        let blp_img = blp_to_image(&blp, 0)?;
-        let rgba = blp_img.to_rgba8();
-        let (width, height) = rgba.dimensions();
-
-        // Fix 1-bit alpha: image-blp decodes 1-bit alpha as literal 0/1 byte values
-        let mut pixels = rgba.into_raw();
-        crate::texture::fix_1bit_alpha(&mut pixels);
-        let img =
-            image::RgbaImage::from_raw(width, height, pixels).expect("invalid dimensions");
-        img.save(dst)?;
+        crate::texture::convert_blp_mip(src, 0)
+            .map_err(|e| e as Box<dyn std::error::Error>)?
+            .save(dst)?;
     } else {
         let img = image::open(src)?;
         let rgba = img.to_rgba8();
@@ -167,15 +158,72 @@ fn convert_texture_reference(
     }
 }
 
+/// Number of conversion workers: `WOW_SIM_MAX_CORES` when set (the same
+/// core limit wow-sim applies to itself), otherwise all available cores.
+pub fn worker_count() -> usize {
+    std::env::var("WOW_SIM_MAX_CORES")
+        .ok()
+        .and_then(|s| s.parse().ok())
+        .or_else(|| std::thread::available_parallelism().ok().map(|n| n.get()))
+        .unwrap_or(1)
+        .max(1)
+}
+
+/// Convert texture references (`interface/...` paths) using up to `workers`
+/// threads pulling from a shared queue.
+///
+/// Returns (found, missing) counts; totals don't depend on the worker count.
+pub fn convert_texture_references(
+    textures: &[String],
+    index: &HashMap<String, PathBuf>,
+    output_dir: &Path,
+    workers: usize,
+) -> (usize, usize) {
+    let next = AtomicUsize::new(0);
+    let found = AtomicUsize::new(0);
+    let missing = AtomicUsize::new(0);
+
+    let work = || loop {
+        let i = next.fetch_add(1, Ordering::Relaxed);
+        let Some(texture_path) = textures.get(i) else { break };
+        let lookup_key = texture_path
+            .strip_prefix("interface/")
+            .unwrap_or(texture_path);
+        let counter = if convert_texture_reference(lookup_key, index, output_dir) {
+            &found
+        } else {
+            &missing
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+    };
+
+    let workers = workers.clamp(1, textures.len().max(1));
+    if workers == 1 {
+        work();
+    } else {
+        std::thread::scope(|s| {
+            for _ in 0..workers {
+                s.spawn(work);
+            }
+        });
+    }
+
+    (found.into_inner(), missing.into_inner())
+}
+
 /// Extract textures referenced by addons and convert to WebP.
 ///
+/// References are collected first, then converted in parallel across
+/// [`worker_count`] threads.
+///
 /// Returns (found, missing) counts.
 pub fn extract_textures(
     addons_path: &Path,
     interface_path: &Path,
     output_dir: &Path,
 ) -> (usize, usize) {
-    let textures = collect_texture_references(addons_path);
+    let mut textures: Vec<String> = collect_texture_references(addons_path).into_iter().collect();
+    textures.sort();
 
     println!("Building file index...");
     let index = build_file_index(interface_path);
@@ -183,20 +231,7 @@ pub fn extract_textures(
 
     std::fs::create_dir_all(output_dir).expect("failed to create output directory");
 
-    let mut found = 0;
-    let mut missing = 0;
-
-    for texture_path in &textures {
-        let lookup_key = texture_path
-            .strip_prefix("interface/")
-            .unwrap_or(texture_path);
-
-        if convert_texture_reference(lookup_key, &index, output_dir) {
-            found += 1;
-        } else {
-            missing += 1;
-        }
-    }
-
-    (found, missing)
+    let workers = worker_count();
+    println!("Converting with {} worker(s)", workers);
+    convert_texture_references(&textures, &index, output_dir, workers)
 }
@@ -471,7 +471,7 @@ impl App {
 
 /// Check if any GCD or spell cooldowns are still active.
 fn has_active_cooldowns(state: &crate::lua_api::SimState) -> bool {
-    let now = state.start_time.elapsed().as_secs_f64();
+    let now = state.clock.now();
     if let Some((start, dur)) = state.gcd {
         if now < start + dur {
             return true;
@@ -482,12 +482,17 @@ fn has_active_cooldowns(state: &crate::lua_api::SimState) -> bool {
 
 impl Drop for App {
     fn drop(&mut self) {
-        if let Some(ref saved_vars) = self.saved_vars {
+        if let Some(ref saved_vars) = self.saved_vars
+            && saved_vars.save_on_exit()
+        {
             let env = self.env.borrow();
             match saved_vars.save_all(env.lua()) {
                 Ok(()) => eprintln!("[wow-sim] SavedVariables saved"),
                 Err(e) => eprintln!("[wow-sim] SavedVariables save error: {}", e),
             }
         }
+        if let Some(report) = self.env.borrow().api_coverage_report() {
+            eprint!("{report}");
+        }
     }
 }
@@ -0,0 +1,105 @@
+//! SetClipsChildren support — clips descendant quads to clipping ancestors.
+//!
+//! Clipping is done on the CPU while building the quad batch: frames that
+//! lie entirely outside the clip rect are skipped, and axis-aligned quads
+//! that straddle its edge are cropped with their UVs and colors interpolated.
+
+use iced::{Point, Rectangle, Size};
+
+use crate::render::QuadBatch;
+use crate::render::shader::QuadVertex;
+use crate::render::texture::UI_SCALE;
+
+/// Screen-space clip rect for `id`: the intersection of the layout rects of
+/// every ancestor with `clips_children` set. `None` means unclipped.
+///
+/// An empty intersection yields a zero-sized rect so everything is culled.
+pub(super) fn ancestor_clip_rect(registry: &crate::widget::WidgetRegistry, id: u64) -> Option<Rectangle> {
+    let mut clip: Option<Rectangle> = None;
+    let mut current = registry.get(id).and_then(|f| f.parent_id);
+    while let Some(pid) = current {
+        let Some(parent) = registry.get(pid) else { break };
+        if parent.clips_children
+            && let Some(r) = parent.layout_rect {
+                let rect = Rectangle::new(
+                    Point::new(r.x * UI_SCALE, r.y * UI_SCALE),
+                    Size::new(r.width * UI_SCALE, r.height * UI_SCALE),
+                );
+                clip = Some(match clip {
+                    Some(c) => c.intersection(&rect).unwrap_or(Rectangle::new(c.position(), Size::ZERO)),
+                    None => rect,
+                });
+            }
+        current = parent.parent_id;
+    }
+    clip
+}
+
+/// Whether `bounds` can produce any visible pixels inside `clip`.
+///
+/// Edges count as touching so zero-width FontStrings (auto-sized text)
+/// anchored inside the clip rect are not culled.
+pub(super) fn is_outside_clip(bounds: Rectangle, clip: Rectangle) -> bool {
+    clip.width <= 0.0
+        || clip.height <= 0.0
+        || bounds.x > clip.x + clip.width
+        || bounds.x + bounds.width < clip.x
+        || bounds.y > clip.y + clip.height
+        || bounds.y + bounds.height < clip.y
+}
+
+/// Crop every axis-aligned quad emitted after `vert_before` to `clip`.
+///
+/// Quads fully outside collapse to zero area. Non-axis-aligned quads
+/// (rotated lines) are left untouched.
+pub(super) fn clip_quads(batch: &mut QuadBatch, vert_before: usize, clip: Rectangle) {
+    let (cx0, cy0) = (clip.x, clip.y);
+    let (cx1, cy1) = (clip.x + clip.width, clip.y + clip.height);
+    for quad in batch.vertices[vert_before..].chunks_exact_mut(4) {
+        let [tl, tr, br, bl] = [quad[0], quad[1], quad[2], quad[3]];
+        let axis_aligned = tl.position[1] == tr.position[1]
+            && bl.position[1] == br.position[1]
+            && tl.position[0] == bl.position[0]
+            && tr.position[0] == br.position[0];
+        if !axis_aligned {
+            continue;
+        }
+        let (x0, x1) = (tl.position[0], tr.position[0]);
+        let (y0, y1) = (tl.position[1], bl.position[1]);
+        let inside = x0.min(x1) >= cx0 && x0.max(x1) <= cx1 && y0.min(y1) >= cy0 && y0.max(y1) <= cy1;
+        if inside {
+            continue;
+        }
+        let corners = [tl, tr, br, bl];
+        for v in quad.iter_mut() {
+            let px = v.position[0].clamp(cx0, cx1.max(cx0));
+            let py = v.position[1].clamp(cy0, cy1.max(cy0));
+            let s = if x1 != x0 { (px - x0) / (x1 - x0) } else { 0.0 };
+            let t = if y1 != y0 { (py - y0) / (y1 - y0) } else { 0.0 };
+            *v = bilerp_vertex(&corners, s, t, v);
+            v.position = [px, py];
+        }
+    }
+}
+
+/// Interpolate per-vertex attributes across a TL, TR, BR, BL quad at (s, t).
+fn bilerp_vertex(c: &[QuadVertex; 4], s: f32, t: f32, base: &QuadVertex) -> QuadVertex {
+    let lerp2 = |f: fn(&QuadVertex) -> [f32; 2]| -> [f32; 2] {
+        let top = lerp_arr(f(&c[0]), f(&c[1]), s);
+        let bottom = lerp_arr(f(&c[3]), f(&c[2]), s);
+        lerp_arr(top, bottom, t)
+    };
+    let top = lerp_arr(c[0].color, c[1].color, s);
+    let bottom = lerp_arr(c[3].color, c[2].color, s);
+    QuadVertex {
+        tex_coords: lerp2(|v| v.tex_coords),
+        local_uv: lerp2(|v| v.local_uv),
+        mask_tex_coords: lerp2(|v| v.mask_tex_coords),
+        color: lerp_arr(top, bottom, t),
+        ..*base
+    }
+}
+
+fn lerp_arr<const N: usize>(a: [f32; N], b: [f32; N], t: f32) -> [f32; N] {
+    std::array::from_fn(|i| a[i] + (b[i] - a[i]) * t)
+}
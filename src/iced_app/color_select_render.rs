@@ -0,0 +1,103 @@
+//! ColorSelect rendering: hue/saturation wheel, value bar and their selectors.
+
+use iced::{Point, Rectangle, Size};
+
+use crate::render::texture::UI_SCALE;
+use crate::render::QuadBatch;
+use crate::widget::{hsv_to_rgb, Frame, WidgetRegistry};
+
+/// Hue sectors the wheel is built from.
+const WHEEL_SEGMENTS: usize = 48;
+/// Side of the square marking the selected hue/saturation, in pixels.
+pub const WHEEL_THUMB_SIZE: f32 = 6.0;
+
+/// Draw the wheel and value bar of a ColorSelect with markers at its color.
+///
+/// The wheel and bar fill the rects of the textures given to
+/// SetColorWheelTexture/SetColorValueTexture; without them the wheel takes
+/// the left square of the frame and the bar its right edge. Hue 0 (red)
+/// points right and hue grows counter-clockwise; saturation grows outwards.
+pub fn emit_color_select(
+    batch: &mut QuadBatch,
+    f: &Frame,
+    bounds: Rectangle,
+    registry: &WidgetRegistry,
+    alpha: f32,
+) {
+    let (wheel, bar) = color_select_regions(f, bounds, registry);
+    let (h, s, v) = f.color_hsv;
+    emit_wheel(batch, wheel, v, alpha);
+    emit_value_bar(batch, bar, h, s, alpha);
+
+    let (cx, cy) = (wheel.x + wheel.width / 2.0, wheel.y + wheel.height / 2.0);
+    let radius = wheel.width.min(wheel.height) / 2.0 * s as f32;
+    let (sin, cos) = (h.to_radians() as f32).sin_cos();
+    emit_thumb(batch, cx + cos * radius, cy - sin * radius, WHEEL_THUMB_SIZE, WHEEL_THUMB_SIZE, alpha);
+    let value_y = bar.y + bar.height * (1.0 - v as f32);
+    emit_thumb(batch, bar.x + bar.width / 2.0, value_y, bar.width + 4.0, 2.0, alpha);
+}
+
+/// Wheel and value bar rects in screen pixels.
+fn color_select_regions(f: &Frame, bounds: Rectangle, registry: &WidgetRegistry) -> (Rectangle, Rectangle) {
+    let texture_rect = |id: Option<u64>| {
+        let r = registry.get(id?)?.layout_rect?;
+        Some(Rectangle::new(
+            Point::new(r.x * UI_SCALE, r.y * UI_SCALE),
+            Size::new(r.width * UI_SCALE, r.height * UI_SCALE),
+        ))
+    };
+    let bar_width = (bounds.height * 0.2).max(8.0);
+    let side = bounds.height.min(bounds.width - bar_width * 1.5).max(0.0);
+    let wheel = texture_rect(f.color_wheel_texture)
+        .unwrap_or_else(|| Rectangle::new(bounds.position(), Size::new(side, side)));
+    let bar = texture_rect(f.color_value_texture).unwrap_or_else(|| Rectangle::new(
+        Point::new(bounds.x + bounds.width - bar_width, bounds.y),
+        Size::new(bar_width, bounds.height),
+    ));
+    (wheel, bar)
+}
+
+/// A fan of hue sectors: white (grey at lower value) in the middle, fully
+/// saturated at the rim. Each sector is a quad with two corners at the center.
+fn emit_wheel(batch: &mut QuadBatch, wheel: Rectangle, value: f64, alpha: f32) {
+    let (cx, cy) = (wheel.x + wheel.width / 2.0, wheel.y + wheel.height / 2.0);
+    let radius = wheel.width.min(wheel.height) / 2.0;
+    let center_color = rgba(hsv_to_rgb(0.0, 0.0, value), alpha);
+    let rim = |i: usize| {
+        let hue = i as f64 * 360.0 / WHEEL_SEGMENTS as f64;
+        let (sin, cos) = (hue.to_radians() as f32).sin_cos();
+        ([cx + cos * radius, cy - sin * radius], rgba(hsv_to_rgb(hue, 1.0, value), alpha))
+    };
+    for i in 0..WHEEL_SEGMENTS {
+        let start = batch.vertices.len();
+        batch.push_solid(wheel, center_color);
+        let ((p0, c0), (p1, c1)) = (rim(i), rim(i + 1));
+        let corners = [([cx, cy], center_color), (p0, c0), (p1, c1), ([cx, cy], center_color)];
+        for (v, (pos, color)) in batch.vertices[start..].iter_mut().zip(corners) {
+            v.position = pos;
+            v.color = color;
+        }
+    }
+}
+
+/// Vertical bar from the current hue/saturation at full value (top) to black.
+fn emit_value_bar(batch: &mut QuadBatch, bar: Rectangle, hue: f64, saturation: f64, alpha: f32) {
+    let start = batch.vertices.len();
+    let top = rgba(hsv_to_rgb(hue, saturation, 1.0), alpha);
+    batch.push_solid(bar, top);
+    // Vertex order is TL, TR, BR, BL.
+    for v in &mut batch.vertices[start + 2..start + 4] {
+        v.color = [0.0, 0.0, 0.0, alpha];
+    }
+}
+
+/// White marker centered on (x, y) with a dark outline.
+fn emit_thumb(batch: &mut QuadBatch, x: f32, y: f32, width: f32, height: f32, alpha: f32) {
+    let rect = |w: f32, h: f32| Rectangle::new(Point::new(x - w / 2.0, y - h / 2.0), Size::new(w, h));
+    batch.push_solid(rect(width + 2.0, height + 2.0), [0.0, 0.0, 0.0, alpha]);
+    batch.push_solid(rect(width, height), [1.0, 1.0, 1.0, alpha]);
+}
+
+fn rgba((r, g, b): (f64, f64, f64), alpha: f32) -> [f32; 4] {
+    [r as f32, g as f32, b as f32, alpha]
+}
@@ -57,6 +57,9 @@ pub type IntraStrataKey = (i32, std::cmp::Reverse<u64>, u8, i32, i32, u8, std::c
 /// early) must render above the bar background.
 /// FontStrings (type_flag=1) render above Textures (type_flag=0) in the same
 /// draw layer per WoW rules.
+///
+/// Frame levels are taken from `render_frame_level`, so a child never sorts
+/// below its parent even if given a lower explicit level.
 pub fn intra_strata_sort_key(
     f: &crate::widget::Frame,
     id: u64,
@@ -64,12 +67,27 @@ pub fn intra_strata_sort_key(
 ) -> IntraStrataKey {
     if matches!(f.widget_type, WidgetType::Texture | WidgetType::FontString | WidgetType::Line) {
         let (parent_level, parent_id) = f.parent_id
-            .and_then(|pid| registry.get(pid).map(|p| (p.frame_level, pid)))
+            .and_then(|pid| registry.get(pid).map(|p| (render_frame_level(p, registry), pid)))
             .unwrap_or((f.frame_level, id));
         let type_flag = if f.widget_type == WidgetType::FontString { 1u8 } else { 0u8 };
         (parent_level, std::cmp::Reverse(parent_id), 1, f.draw_layer as i32, f.draw_sub_layer, type_flag, std::cmp::Reverse(id))
     } else {
-        (f.frame_level, std::cmp::Reverse(id), 0, 0, 0, 0, std::cmp::Reverse(0))
+        (render_frame_level(f, registry), std::cmp::Reverse(id), 0, 0, 0, 0, std::cmp::Reverse(0))
+    }
+}
+
+/// Frame level used for draw ordering: the frame's own level, clamped to at
+/// least one above its parent's render level when both share a strata.
+///
+/// WoW draws children above their parent regardless of `SetFrameLevel`, so a
+/// child explicitly set below (or equal to) its parent still renders on top
+/// of the parent's regions. A child in a different strata keeps its own level.
+pub fn render_frame_level(f: &crate::widget::Frame, registry: &crate::widget::WidgetRegistry) -> i32 {
+    match f.parent_id.and_then(|pid| registry.get(pid)) {
+        Some(p) if p.frame_strata == f.frame_strata => {
+            f.frame_level.max(render_frame_level(p, registry) + 1)
+        }
+        _ => f.frame_level,
     }
 }
 
@@ -87,7 +105,7 @@ pub fn collect_hittable_frames(
         for &id in bucket {
             let Some(f) = registry.get(id) else { continue };
             let Some(rect) = f.layout_rect else { continue };
-            if f.visible && f.effective_alpha > 0.0 && f.mouse_enabled
+            if f.visible && f.effective_alpha > 0.0 && f.is_mouse_hittable()
                 && !f.name.as_deref().is_some_and(|n| HIT_TEST_EXCLUDED.contains(&n))
             {
                 hittable.push((id, f.frame_strata, f.frame_level, rect));
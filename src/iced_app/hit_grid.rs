@@ -53,12 +53,18 @@ impl HitGrid {
     /// Returns the frame with the highest strata/level whose rect contains
     /// the point, or `None`.
     pub fn topmost_at(&self, pos: Point) -> Option<u64> {
+        self.topmost_at_where(pos, |_| true)
+    }
+
+    /// Like `topmost_at`, but skips frames rejected by `accepts` so input
+    /// falls through to the frame below (e.g. click-disabled frames).
+    pub fn topmost_at_where(&self, pos: Point, accepts: impl Fn(u64) -> bool) -> Option<u64> {
         let col = ((pos.x / CELL_SIZE) as usize).min(self.cols.saturating_sub(1));
         let row = ((pos.y / CELL_SIZE) as usize).min(self.rows.saturating_sub(1));
         let cell = &self.cells[row * self.cols + col];
         // Reverse: highest strata/level is last in the sorted order.
         cell.iter().rev().find(|&&id| {
-            self.rects.get(&id).is_some_and(|r| r.contains(pos))
+            self.rects.get(&id).is_some_and(|r| r.contains(pos)) && accepts(id)
         }).copied()
     }
 
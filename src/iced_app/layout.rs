@@ -2,6 +2,7 @@
 
 use std::collections::HashMap;
 
+use crate::lua_api::layout::{enter_layout, leave_layout};
 use crate::widget::{AnchorPoint, LineAnchor, WidgetType, WidgetRegistry};
 use crate::LayoutRect;
 
@@ -169,6 +170,10 @@ fn resolve_single_anchor(
 
 /// Compute frame rect with memoization. Each frame is computed at most once
 /// per cache lifetime; parent results are reused by siblings.
+///
+/// A frame whose parent/anchor chain loops back to itself (which SetPoint's
+/// cycle check cannot see when the loop goes through a parent) resolves to
+/// an empty rect at the screen center instead of recursing forever.
 pub fn compute_frame_rect_cached(
     registry: &WidgetRegistry,
     id: u64,
@@ -199,6 +204,26 @@ pub fn compute_frame_rect_cached(
         return result;
     }
 
+    if !enter_layout(registry, id) {
+        let rect = LayoutRect { x: screen_width / 2.0, y: screen_height / 2.0, width: 0.0, height: 0.0 };
+        return CachedFrameLayout { rect, eff_scale: frame.effective_scale };
+    }
+    let result = layout_frame(registry, frame, id, screen_width, screen_height, cache);
+    leave_layout();
+    cache.insert(id, result);
+    result
+}
+
+/// Uncached layout of one frame; its parent and anchor targets go through
+/// `compute_frame_rect_cached`.
+fn layout_frame(
+    registry: &WidgetRegistry,
+    frame: &crate::widget::Frame,
+    id: u64,
+    screen_width: f32,
+    screen_height: f32,
+    cache: &mut LayoutCache,
+) -> CachedFrameLayout {
     // Compute parent layout (cache hit for siblings)
     let parent_rect = if let Some(parent_id) = frame.parent_id {
         compute_frame_rect_cached(registry, parent_id, screen_width, screen_height, cache).rect
@@ -224,8 +249,25 @@ pub fn compute_frame_rect_cached(
         )
     };
 
+    if let Some((x, y)) = slider_thumb_position(registry, frame, id, parent_rect, &rect) {
+        rect.x = x;
+        rect.y = y;
+    }
     rect.x += frame.anim_offset_x;
     rect.y += frame.anim_offset_y;
+    if let Some((h, v)) = scroll_offset_for_child(registry, frame, id) {
+        // Scrolling moves the scroll child (and so its subtree) up/left.
+        rect.x -= h;
+        rect.y -= v;
+    }
+    if frame.anim_scale_x != 1.0 || frame.anim_scale_y != 1.0 {
+        // Scale animations grow/shrink around the frame's center.
+        let (cx, cy) = (rect.x + rect.width * 0.5, rect.y + rect.height * 0.5);
+        rect.width *= frame.anim_scale_x;
+        rect.height *= frame.anim_scale_y;
+        rect.x = cx - rect.width * 0.5;
+        rect.y = cy - rect.height * 0.5;
+    }
 
     if frame.widget_type == WidgetType::Line {
         if let (Some(start), Some(end)) = (&frame.line_start, &frame.line_end) {
@@ -242,9 +284,47 @@ pub fn compute_frame_rect_cached(
         clamp_rect_to_screen(&mut rect, screen_width, screen_height);
     }
 
-    let result = CachedFrameLayout { rect, eff_scale: scale };
-    cache.insert(id, result);
-    result
+    CachedFrameLayout { rect, eff_scale: scale }
+}
+
+/// Scroll offset (horizontal, vertical) in screen units when `frame` is the
+/// scroll child of its parent ScrollFrame.
+fn scroll_offset_for_child(registry: &WidgetRegistry, frame: &crate::widget::Frame, id: u64) -> Option<(f32, f32)> {
+    let parent = registry.get(frame.parent_id?)?;
+    if parent.scroll_child_id != Some(id) {
+        return None;
+    }
+    let scale = parent.effective_scale;
+    Some((parent.scroll_horizontal as f32 * scale, parent.scroll_vertical as f32 * scale))
+}
+
+/// Top-left of `frame` when it is the thumb of its parent Slider: centered
+/// across the track and moved along it in proportion to the value, staying
+/// inside the slider at both ends. Vertical sliders have their minimum at the top.
+fn slider_thumb_position(
+    registry: &WidgetRegistry,
+    frame: &crate::widget::Frame,
+    id: u64,
+    slider_rect: LayoutRect,
+    rect: &LayoutRect,
+) -> Option<(f32, f32)> {
+    let slider = registry.get(frame.parent_id?)?;
+    if slider.widget_type != WidgetType::Slider || slider.slider_thumb_id != Some(id) {
+        return None;
+    }
+    let range = slider.slider_max - slider.slider_min;
+    let frac = if range > 0.0 {
+        ((slider.slider_value - slider.slider_min) / range).clamp(0.0, 1.0) as f32
+    } else {
+        0.0
+    };
+    let centered_x = slider_rect.x + (slider_rect.width - rect.width) / 2.0;
+    let centered_y = slider_rect.y + (slider_rect.height - rect.height) / 2.0;
+    Some(if slider.slider_orientation == "VERTICAL" {
+        (centered_x, slider_rect.y + frac * (slider_rect.height - rect.height))
+    } else {
+        (slider_rect.x + frac * (slider_rect.width - rect.width), centered_y)
+    })
 }
 
 /// Compute frame rect with anchor resolution (uncached).
@@ -268,7 +348,7 @@ pub fn compute_frame_rect(
 /// a small window), the top-left corner is kept on-screen and the frame is
 /// allowed to extend past the bottom/right edges — matching WoW behavior
 /// where oversized frames clip rather than getting pushed to y=0.
-fn clamp_rect_to_screen(rect: &mut LayoutRect, screen_w: f32, screen_h: f32) {
+pub(crate) fn clamp_rect_to_screen(rect: &mut LayoutRect, screen_w: f32, screen_h: f32) {
     // Pull right/bottom edges on-screen, but only when the frame fits
     if rect.width <= screen_w && rect.x + rect.width > screen_w {
         rect.x = screen_w - rect.width;
@@ -44,6 +44,19 @@ pub fn apply_mask_texture(
     let mask_uvs = compute_mask_uvs_from_rects(
         mask_screen, icon_bounds, tl, tr, tt, tb,
     );
+    push_mask(batch, vert_before, mask_path, mask_uvs);
+}
+
+/// Apply a `SetMask` file to recently emitted quads. The mask is stretched
+/// over the texture's own bounds, so each quad samples the whole mask.
+pub fn apply_mask_file(batch: &mut QuadBatch, vert_before: usize, mask_path: &str) {
+    if batch.vertices.len() == vert_before { return; }
+    push_mask(batch, vert_before, mask_path, [[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 1.0]]);
+}
+
+/// Tag quads from `vert_before` on as pending-mask and queue the mask texture.
+fn push_mask(batch: &mut QuadBatch, vert_before: usize, mask_path: &str, mask_uvs: [[f32; 2]; 4]) {
+    let count = batch.vertices.len() - vert_before;
     for i in (vert_before..batch.vertices.len()).step_by(4) {
         let end = (i + 4).min(batch.vertices.len());
         for (j, v) in batch.vertices[i..end].iter_mut().enumerate() {
@@ -52,7 +65,7 @@ pub fn apply_mask_texture(
         }
     }
     batch.mask_texture_requests.push(TextureRequest {
-        path: mask_path.clone(), vertex_start: vert_before as u32, vertex_count: count as u32,
+        path: mask_path.to_string(), vertex_start: vert_before as u32, vertex_count: count as u32,
     });
 }
 
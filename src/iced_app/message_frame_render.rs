@@ -4,7 +4,7 @@ use iced::Rectangle;
 
 use crate::lua_api::message_frame::MessageFrameData;
 use crate::render::font::WowFontSystem;
-use crate::render::glyph::{emit_text_quads, measure_text_height, GlyphAtlas};
+use crate::render::glyph::{emit_text_quads, measure_text_height, GlyphAtlas, TextLinkRect};
 use crate::render::shader::GLYPH_ATLAS_TEX_INDEX;
 use crate::render::QuadBatch;
 use crate::widget::TextJustify;
@@ -13,7 +13,8 @@ use crate::widget::TextJustify;
 ///
 /// Messages are word-wrapped to the frame width. We work backwards from
 /// the most recent message, measuring each one's wrapped height, until
-/// the available vertical space is exhausted.
+/// the available vertical space is exhausted. Returns where the visible
+/// messages' hyperlinks were drawn.
 #[allow(clippy::too_many_arguments)]
 pub fn emit_message_frame_text(
     batch: &mut QuadBatch,
@@ -25,23 +26,24 @@ pub fn emit_message_frame_text(
     mf_map: &std::collections::HashMap<u64, MessageFrameData>,
     alpha: f32,
     elapsed_secs: f64,
-) {
-    let Some(data) = mf_map.get(&id) else { return };
+) -> Vec<TextLinkRect> {
+    let mut links = Vec::new();
+    let Some(data) = mf_map.get(&id) else { return links };
     if data.messages.is_empty() || bounds.width <= 0.0 || bounds.height <= 0.0 {
-        return;
+        return links;
     }
 
     let total = data.messages.len();
     let scroll = data.scroll_offset.max(0) as usize;
     let end = total.saturating_sub(scroll);
     if end == 0 {
-        return;
+        return links;
     }
 
     // Pre-measure wrapped heights from newest to oldest, stopping when
     // we've filled the available vertical space.
     let measured = measure_visible_messages(
-        font_sys, glyph_atlas, f, &data.messages[..end], bounds.width, bounds.height,
+        font_sys, glyph_atlas, f, &data.messages, end, bounds.width, bounds.height,
     );
 
     // Render bottom-aligned: walk measured messages from oldest to newest
@@ -52,11 +54,12 @@ pub fn emit_message_frame_text(
         if msg_alpha <= 0.0 {
             continue;
         }
-        render_message(
+        links.extend(render_message(
             batch, font_sys, glyph_atlas, f, bounds,
             &data.messages[msg_idx], y, height, alpha * msg_alpha,
-        );
+        ));
     }
+    links
 }
 
 /// Compute fade alpha for a single message.
@@ -80,20 +83,21 @@ fn message_fade_alpha(data: &MessageFrameData, msg: &crate::lua_api::message_fra
     t.powf(data.fade_power) as f32
 }
 
-/// Measure messages from newest to oldest, returning (index, height) pairs
-/// in newest-first order, until available height is filled.
+/// Measure messages before `end` from newest to oldest, returning
+/// (index, height) pairs in newest-first order, until available height is filled.
 fn measure_visible_messages(
     font_sys: &mut WowFontSystem,
     glyph_atlas: &mut GlyphAtlas,
     f: &crate::widget::Frame,
-    messages: &[crate::lua_api::message_frame::Message],
+    messages: &std::collections::VecDeque<crate::lua_api::message_frame::Message>,
+    end: usize,
     width: f32,
     available_height: f32,
 ) -> Vec<(usize, f32)> {
     let mut result = Vec::new();
     let mut used_height = 0.0;
 
-    for i in (0..messages.len()).rev() {
+    for i in (0..end).rev() {
         let h = measure_text_height(
             font_sys, glyph_atlas, &messages[i].text,
             f.font.as_deref(), f.font_size, width, true,
@@ -122,7 +126,7 @@ fn render_message(
     y: f32,
     height: f32,
     alpha: f32,
-) {
+) -> Vec<TextLinkRect> {
     let line_bounds = Rectangle {
         x: bounds.x,
         y,
@@ -141,5 +145,5 @@ fn render_message(
         shadow, (1.0, 1.0),
         f.font_outline,
         true, 0, // word_wrap=true, no line limit
-    );
+    )
 }
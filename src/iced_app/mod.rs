@@ -11,6 +11,8 @@
 
 mod app;
 mod button_vis;
+mod clipping;
+mod color_select_render;
 pub mod frame_collect;
 mod hit_grid;
 mod keybinds;
@@ -20,6 +22,7 @@ mod nine_slice;
 mod masking;
 mod quad_builders;
 mod render;
+mod simple_html_render;
 mod statusbar;
 mod state;
 mod tiling;
@@ -42,7 +45,10 @@ use crate::saved_variables::SavedVariablesManager;
 // Re-export public types
 pub use app::App;
 pub use layout::{anchor_position, compute_frame_rect, compute_frame_rect_cached, frame_position_from_anchor, CachedFrameLayout, LayoutCache};
-pub use render::{build_quad_batch_for_registry, build_hittable_rects};
+pub use render::{
+    build_quad_batch_at_time, build_quad_batch_for_registry, build_hittable_rects,
+    strata_buckets_from,
+};
 pub use state::{CanvasMessage, InspectorState};
 pub use styles::palette;
 
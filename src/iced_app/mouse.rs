@@ -12,7 +12,8 @@ impl App {
         self.mouse_position = Some(pos);
         {
             let env = self.env.borrow();
-            env.state().borrow_mut().mouse_position = Some((pos.x, pos.y));
+            let resized = env.state().borrow_mut().set_mouse_position(pos.x, pos.y);
+            crate::lua_api::script_helpers::fire_size_changed(env.lua(), resized);
         }
 
         // Check drag threshold while mouse is held down.
@@ -27,24 +28,27 @@ impl App {
             }
         }
 
-        let new_hovered = self.hit_test(pos);
+        let new_hovered = self.hover_test(pos);
         if new_hovered == self.hovered_frame {
-            return;
-        }
-
-        // Update hovered_frame in both iced_app and SimState BEFORE firing events,
-        // so IsMouseMotionFocus() / GetMouseFocus() return correct values in OnEnter.
-        let old_hovered = self.hovered_frame;
-        self.hovered_frame = new_hovered;
-        {
+            let link_changed = self.env.borrow().update_hovered_hyperlink(new_hovered, pos.x, pos.y);
+            if !link_changed.unwrap_or(false) {
+                return;
+            }
+        } else {
+            // Update hovered_frame in both iced_app and SimState BEFORE firing events,
+            // so IsMouseMotionFocus() / GetMouseFocus() return correct values in OnEnter.
+            let old_hovered = self.hovered_frame;
+            self.hovered_frame = new_hovered;
             let env = self.env.borrow();
             env.state().borrow_mut().hovered_frame = new_hovered;
+            let _ = env.update_hovered_hyperlink(None, pos.x, pos.y);
             if let Some(old_id) = old_hovered {
                 let _ = env.fire_script_handler(old_id, "OnLeave", vec![]);
             }
             if let Some(new_id) = new_hovered {
                 let _ = env.fire_script_handler(new_id, "OnEnter", vec![]);
             }
+            let _ = env.update_hovered_hyperlink(new_hovered, pos.x, pos.y);
         }
         // OnEnter/OnLeave scripts may show/hide tooltips or change widget state.
         // Apply incremental HitGrid updates before the next hit_test.
@@ -77,6 +81,7 @@ impl App {
         self.mouse_down_pos = Some(pos);
         self.dragging = false;
         self.pressed_frame = Some(frame_id);
+        self.raise_toplevel_on_click(frame_id);
 
         {
             let env = self.env.borrow();
@@ -86,6 +91,19 @@ impl App {
         self.invalidate();
     }
 
+    /// Bring the clicked frame's toplevel ancestor to the front. The hit grid
+    /// is rebuilt on the next render since hit order follows frame levels.
+    fn raise_toplevel_on_click(&self, frame_id: u64) {
+        let raised = {
+            let env = self.env.borrow();
+            let mut state = env.state().borrow_mut();
+            state.raise_toplevel_ancestor(frame_id)
+        };
+        if raised {
+            *self.cached_hittable.borrow_mut() = None;
+        }
+    }
+
     pub(super) fn handle_mouse_up(&mut self, pos: Point) {
         let was_dragging = self.dragging;
         let drag_source = self.mouse_down_frame;
@@ -111,7 +129,7 @@ impl App {
                     mlua::Value::String(env.lua().create_string("LeftButton").unwrap());
 
                 if self.mouse_down_frame == Some(frame_id) {
-                    self.toggle_checkbutton_if_needed(frame_id, &env);
+                    env.toggle_checkbutton_if_needed(frame_id);
 
                     let down_val = mlua::Value::Boolean(false);
                     let _ = env.fire_script_handler(
@@ -127,6 +145,7 @@ impl App {
                         "PostClick",
                         vec![button_val.clone(), down_val],
                     );
+                    let _ = env.click_hyperlink(frame_id, pos.x, pos.y, "LeftButton");
                 }
 
                 let _ = env.fire_script_handler(frame_id, "OnMouseUp", vec![button_val]);
@@ -141,6 +160,7 @@ impl App {
         let Some(frame_id) = self.hit_test(pos) else { return };
         if !self.is_frame_enabled(frame_id) { return }
         self.right_mouse_down_frame = Some(frame_id);
+        self.raise_toplevel_on_click(frame_id);
         {
             let env = self.env.borrow();
             let button_val = mlua::Value::String(env.lua().create_string("RightButton").unwrap());
@@ -180,6 +200,7 @@ impl App {
                         frame_id, "PostClick",
                         vec![button_val.clone(), down_val],
                     );
+                    let _ = env.click_hyperlink(frame_id, pos.x, pos.y, "RightButton");
                 }
 
                 let _ = env.fire_script_handler(frame_id, "OnMouseUp", vec![button_val]);
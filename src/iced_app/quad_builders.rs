@@ -3,27 +3,25 @@
 use iced::{Point, Rectangle, Size};
 
 use crate::render::font::WowFontSystem;
-use crate::render::glyph::{emit_text_quads, GlyphAtlas};
+use crate::render::glyph::{emit_text_quads, GlyphAtlas, TextLinkRect};
 use crate::render::shader::GLYPH_ATLAS_TEX_INDEX;
+use crate::render::texture::UI_SCALE;
 use crate::render::{BlendMode, QuadBatch};
+use crate::texture::TextureManager;
 use crate::widget::{TextJustify, WidgetType};
 
+use super::color_select_render::emit_color_select;
 use super::message_frame_render::emit_message_frame_text;
+use super::simple_html_render::emit_simple_html;
 use super::statusbar::StatusBarFill;
 use super::tiling::emit_tiled_texture;
-use super::masking::apply_mask_texture;
+use super::masking::{apply_mask_file, apply_mask_texture};
 use super::tooltip::TooltipRenderData;
 
 /// Build quads for a Frame widget (backdrop).
 pub fn build_frame_quads(batch: &mut QuadBatch, bounds: Rectangle, f: &crate::widget::Frame, alpha: f32) {
     if f.backdrop.enabled {
-        let bg = &f.backdrop.bg_color;
-        batch.push_solid(bounds, [bg.r, bg.g, bg.b, bg.a * alpha]);
-
-        if f.backdrop.edge_size > 0.0 {
-            let bc = &f.backdrop.border_color;
-            batch.push_border(bounds, f.backdrop.edge_size.max(1.0), [bc.r, bc.g, bc.b, bc.a * alpha]);
-        }
+        emit_backdrop(batch, bounds, &f.backdrop, f.effective_scale * crate::render::texture::UI_SCALE, alpha);
     }
 
     if f.nine_slice_layout.is_some() {
@@ -31,6 +29,44 @@ pub fn build_frame_quads(batch: &mut QuadBatch, bounds: Rectangle, f: &crate::wi
     }
 }
 
+/// Render a `SetBackdrop`/legacy `<Backdrop>`: the background inside the
+/// insets (tiled or stretched, tinted by the backdrop color), then the edge
+/// file's 8 edge/corner pieces tinted by the border color.
+///
+/// Backdrop sizes are in frame units; `scale` converts them to screen pixels.
+fn emit_backdrop(
+    batch: &mut QuadBatch,
+    bounds: Rectangle,
+    backdrop: &crate::widget::Backdrop,
+    scale: f32,
+    alpha: f32,
+) {
+    if let Some(bg_file) = &backdrop.bg_file {
+        let (left, right, top, bottom) = backdrop.insets;
+        let bg_bounds = Rectangle::new(
+            Point::new(bounds.x + left * scale, bounds.y + top * scale),
+            Size::new(
+                (bounds.width - (left + right) * scale).max(0.0),
+                (bounds.height - (top + bottom) * scale).max(0.0),
+            ),
+        );
+        let c = &backdrop.bg_color;
+        let color = [c.r, c.g, c.b, c.a * alpha];
+        let tile = backdrop.tile_size * scale;
+        if backdrop.tile && tile > 0.0 {
+            batch.push_tiled_path(bg_bounds, tile, tile, bg_file, color);
+        } else {
+            batch.push_textured_path(bg_bounds, bg_file, color, BlendMode::Alpha);
+        }
+    }
+
+    if let Some(edge_file) = &backdrop.edge_file {
+        let c = &backdrop.border_color;
+        let color = [c.r, c.g, c.b, c.a * alpha];
+        batch.push_backdrop_edges(bounds, backdrop.edge_size * scale, edge_file, color);
+    }
+}
+
 /// Build quads for a Button widget.
 pub fn build_button_quads(
     batch: &mut QuadBatch,
@@ -107,7 +143,15 @@ pub(super) fn emit_button_highlight(batch: &mut QuadBatch, bounds: Rectangle, f:
 }
 
 /// Build quads for a Texture widget, optionally clipped by a StatusBar fill.
-pub fn build_texture_quads(batch: &mut QuadBatch, bounds: Rectangle, f: &crate::widget::Frame, bar_fill: Option<&StatusBarFill>, alpha: f32) {
+/// `tex_mgr` supplies native texture sizes for tiled textures.
+pub fn build_texture_quads(
+    batch: &mut QuadBatch,
+    bounds: Rectangle,
+    f: &crate::widget::Frame,
+    bar_fill: Option<&StatusBarFill>,
+    alpha: f32,
+    tex_mgr: Option<&mut TextureManager>,
+) {
     if let Some(ref ns) = f.nine_slice_atlas {
         super::nine_slice::emit_nine_slice_atlas(batch, bounds, ns, alpha);
         return;
@@ -130,7 +174,17 @@ pub fn build_texture_quads(batch: &mut QuadBatch, bounds: Rectangle, f: &crate::
 
     if let Some(color) = f.color_texture {
         let fill_bounds = apply_bar_fill(bounds, bar_fill);
-        batch.push_solid(fill_bounds, [color.r * tint[0], color.g * tint[1], color.b * tint[2], color.a * alpha]);
+        let vert_before = batch.vertices.len();
+        batch.push_solid(fill_bounds, [color.r * tint[0], color.g * tint[1], color.b * tint[2], color.a * tint[3]]);
+        if f.rotation != 0.0 {
+            apply_quad_rotation(batch, vert_before, fill_bounds, f.rotation, f.rotation_pivot);
+        }
+        if f.desaturated {
+            apply_desaturate_flag(batch, vert_before);
+        }
+        if let Some(gradient) = &f.gradient {
+            apply_gradient(batch, vert_before, fill_bounds, gradient);
+        }
         return;
     }
 
@@ -159,7 +213,10 @@ pub fn build_texture_quads(batch: &mut QuadBatch, bounds: Rectangle, f: &crate::
     } else if let Some((left, right, top, bottom)) = effective_uvs {
         let uvs = Rectangle::new(Point::new(left, top), Size::new(right - left, bottom - top));
         if f.horiz_tile || f.vert_tile {
-            emit_tiled_texture(batch, fill_bounds, &uvs, &effective_path, f, alpha);
+            emit_tiled_texture(batch, fill_bounds, &uvs, &effective_path, f, alpha, tex_mgr);
+        } else if let Some(raw) = f.tex_coords_quad.filter(|_| bar_fill.is_none()) {
+            let corners = quad_corner_uvs(&raw, (left, right, top, bottom));
+            batch.push_textured_path_uv4(fill_bounds, corners, &effective_path, tint, f.blend_mode);
         } else {
             batch.push_textured_path_uv(fill_bounds, uvs, &effective_path, tint, f.blend_mode);
         }
@@ -168,11 +225,14 @@ pub fn build_texture_quads(batch: &mut QuadBatch, bounds: Rectangle, f: &crate::
     }
 
     if f.rotation != 0.0 {
-        apply_uv_rotation(batch, vert_before, f.rotation);
+        apply_quad_rotation(batch, vert_before, fill_bounds, f.rotation, f.rotation_pivot);
     }
     if f.desaturated {
         apply_desaturate_flag(batch, vert_before);
     }
+    if let Some(gradient) = &f.gradient {
+        apply_gradient(batch, vert_before, fill_bounds, gradient);
+    }
 }
 
 /// Render an atlas texture as 3 horizontal slices (left cap, stretched middle, right cap).
@@ -223,6 +283,11 @@ fn emit_three_slice_h_atlas(
 /// Apply StatusBar fill clipping to bounds.
 fn apply_bar_fill(bounds: Rectangle, bar_fill: Option<&StatusBarFill>) -> Rectangle {
     let Some(fill) = bar_fill else { return bounds };
+    if fill.vertical {
+        let fill_height = bounds.height * fill.fraction;
+        let y = if fill.reverse { bounds.y } else { bounds.y + bounds.height - fill_height };
+        return Rectangle::new(Point::new(bounds.x, y), Size::new(bounds.width, fill_height));
+    }
     let fill_width = bounds.width * fill.fraction;
     if fill.reverse {
         Rectangle::new(
@@ -284,6 +349,24 @@ fn remap_atlas_crop(
     (crop_key, remapped_uvs)
 }
 
+/// Map raw 8-arg SetTexCoord corners into `uvs`, returning [TL, TR, BR, BL].
+///
+/// `uvs` is the corners' bounding box after atlas and crop remapping, so each
+/// corner keeps its relative position and rotated or flipped art survives.
+fn quad_corner_uvs(raw: &[f32; 8], uvs: (f32, f32, f32, f32)) -> [[f32; 2]; 4] {
+    let (left, right, top, bottom) = uvs;
+    let (us, vs) = ([raw[0], raw[2], raw[4], raw[6]], [raw[1], raw[3], raw[5], raw[7]]);
+    let (u_min, u_max) = (us.iter().copied().fold(f32::MAX, f32::min), us.iter().copied().fold(f32::MIN, f32::max));
+    let (v_min, v_max) = (vs.iter().copied().fold(f32::MAX, f32::min), vs.iter().copied().fold(f32::MIN, f32::max));
+    let map = |u: f32, v: f32| {
+        let su = if u_max > u_min { (u - u_min) / (u_max - u_min) } else { 0.0 };
+        let sv = if v_max > v_min { (v - v_min) / (v_max - v_min) } else { 0.0 };
+        [left + su * (right - left), top + sv * (bottom - top)]
+    };
+    // Raw order is UL, LL, UR, LR.
+    [map(raw[0], raw[1]), map(raw[4], raw[5]), map(raw[6], raw[7]), map(raw[2], raw[3])]
+}
+
 /// Apply StatusBar fill clipping to bounds and UV coordinates.
 fn apply_bar_fill_with_uvs(
     bounds: Rectangle,
@@ -293,6 +376,15 @@ fn apply_bar_fill_with_uvs(
     let Some(fill) = bar_fill else { return (bounds, tex_coords) };
     let fill_bounds = apply_bar_fill(bounds, bar_fill);
     let (uv_left, uv_right, uv_top, uv_bottom) = tex_coords.unwrap_or((0.0, 1.0, 0.0, 1.0));
+    if fill.vertical {
+        let uv_range = uv_bottom - uv_top;
+        let fill_uvs = if fill.reverse {
+            (uv_left, uv_right, uv_top, uv_top + uv_range * fill.fraction)
+        } else {
+            (uv_left, uv_right, uv_bottom - uv_range * fill.fraction, uv_bottom)
+        };
+        return (fill_bounds, Some(fill_uvs));
+    }
     let uv_range = uv_right - uv_left;
     let fill_uvs = if fill.reverse {
         (uv_left + uv_range * (1.0 - fill.fraction), uv_right, uv_top, uv_bottom)
@@ -302,24 +394,24 @@ fn apply_bar_fill_with_uvs(
     (fill_bounds, Some(fill_uvs))
 }
 
-/// Rotate texture UV coordinates around their center for vertices added after `vert_before`.
-fn apply_uv_rotation(batch: &mut QuadBatch, vert_before: usize, radians: f32) {
-    let verts = &mut batch.vertices[vert_before..];
-    if verts.len() < 4 {
-        return;
-    }
+/// Rotate the corners of vertices added after `vert_before` counter-clockwise
+/// by `radians` around `pivot`, given in normalized coordinates of `bounds`.
+fn apply_quad_rotation(
+    batch: &mut QuadBatch,
+    vert_before: usize,
+    bounds: Rectangle,
+    radians: f32,
+    pivot: (f32, f32),
+) {
+    let cx = bounds.x + bounds.width * pivot.0;
+    let cy = bounds.y + bounds.height * pivot.1;
     let (sin_r, cos_r) = radians.sin_cos();
-    for chunk in verts.chunks_exact_mut(4) {
-        let cx = (chunk[0].tex_coords[0] + chunk[1].tex_coords[0]
-            + chunk[2].tex_coords[0] + chunk[3].tex_coords[0]) * 0.25;
-        let cy = (chunk[0].tex_coords[1] + chunk[1].tex_coords[1]
-            + chunk[2].tex_coords[1] + chunk[3].tex_coords[1]) * 0.25;
-        for v in chunk.iter_mut() {
-            let du = v.tex_coords[0] - cx;
-            let dv = v.tex_coords[1] - cy;
-            v.tex_coords[0] = cx + du * cos_r - dv * sin_r;
-            v.tex_coords[1] = cy + du * sin_r + dv * cos_r;
-        }
+    for v in &mut batch.vertices[vert_before..] {
+        // Screen y points down, so a counter-clockwise turn negates sin for y.
+        let dx = v.position[0] - cx;
+        let dy = v.position[1] - cy;
+        v.position[0] = cx + dx * cos_r + dy * sin_r;
+        v.position[1] = cy - dx * sin_r + dy * cos_r;
     }
 }
 
@@ -331,6 +423,28 @@ fn apply_desaturate_flag(batch: &mut QuadBatch, vert_before: usize) {
     }
 }
 
+/// Multiply vertices added after `vert_before` by a two-color gradient.
+///
+/// Each vertex's position within `bounds` picks the interpolated color, so
+/// sliced or tiled textures still get one continuous gradient.
+fn apply_gradient(batch: &mut QuadBatch, vert_before: usize, bounds: Rectangle, gradient: &crate::widget::Gradient) {
+    use crate::widget::GradientOrientation;
+    let (min, max) = (gradient.min_color, gradient.max_color);
+    for v in &mut batch.vertices[vert_before..] {
+        let t = match gradient.orientation {
+            GradientOrientation::Horizontal if bounds.width > 0.0 => (v.position[0] - bounds.x) / bounds.width,
+            // Screen y grows downward; minColor sits on the bottom edge.
+            GradientOrientation::Vertical if bounds.height > 0.0 => (bounds.y + bounds.height - v.position[1]) / bounds.height,
+            _ => 0.0,
+        }
+        .clamp(0.0, 1.0);
+        v.color[0] *= min.r + (max.r - min.r) * t;
+        v.color[1] *= min.g + (max.g - min.g) * t;
+        v.color[2] *= min.b + (max.b - min.b) * t;
+        v.color[3] *= min.a + (max.a - min.a) * t;
+    }
+}
+
 /// Build quads for a Minimap widget - map texture clipped to a circle.
 pub fn build_minimap_quads(batch: &mut QuadBatch, bounds: Rectangle, _f: &crate::widget::Frame, alpha: f32) {
     use crate::render::shader::FLAG_CIRCLE_CLIP;
@@ -389,6 +503,22 @@ fn emit_widget_text_quads(
     );
 }
 
+/// Record the hyperlinks a frame just drew in the batch, converted back to
+/// UI units, for hyperlink hit testing.
+fn record_hyperlinks(batch: &mut QuadBatch, id: u64, links: Vec<TextLinkRect>) {
+    let regions = links.into_iter().map(|l| crate::lua_api::HyperlinkRegion {
+        link: l.link,
+        text: l.text,
+        rect: crate::LayoutRect {
+            x: l.rect.x / UI_SCALE,
+            y: l.rect.y / UI_SCALE,
+            width: l.rect.width / UI_SCALE,
+            height: l.rect.height / UI_SCALE,
+        },
+    }).collect();
+    batch.hyperlinks.push((id, regions));
+}
+
 /// Check if a button is visually pressed (mouse or Lua SetButtonState).
 fn is_button_pressed(f: &crate::widget::Frame, id: u64, pressed_frame: Option<u64>) -> bool {
     pressed_frame == Some(id) || f.button_state == 1
@@ -408,6 +538,7 @@ pub fn emit_frame_quads(
     pressed_frame: Option<u64>,
     hovered_frame: Option<u64>,
     text_ctx: &mut Option<(&mut WowFontSystem, &mut GlyphAtlas)>,
+    tex_mgr: &mut Option<&mut TextureManager>,
     message_frames: Option<&std::collections::HashMap<u64, crate::lua_api::message_frame::MessageFrameData>>,
     tooltip_data: Option<&std::collections::HashMap<u64, TooltipRenderData>>,
     registry: &crate::widget::WidgetRegistry,
@@ -420,9 +551,15 @@ pub fn emit_frame_quads(
             build_frame_quads(batch, bounds, f, eff_alpha);
             if let Some((fs, ga)) = text_ctx
                 && let Some(mf_map) = message_frames {
-                    emit_message_frame_text(batch, fs, ga, f, id, bounds, mf_map, eff_alpha, elapsed_secs);
+                    let links = emit_message_frame_text(batch, fs, ga, f, id, bounds, mf_map, eff_alpha, elapsed_secs);
+                    record_hyperlinks(batch, id, links);
                 }
         }
+        WidgetType::SimpleHTML => {
+            build_frame_quads(batch, bounds, f, eff_alpha);
+            let links = emit_simple_html(batch, text_ctx, f, bounds, eff_alpha);
+            record_hyperlinks(batch, id, links);
+        }
         WidgetType::GameTooltip => {
             super::tooltip::build_tooltip_quads(batch, bounds, f, tooltip_data, id, text_ctx, eff_alpha);
         }
@@ -438,9 +575,11 @@ pub fn emit_frame_quads(
         WidgetType::Texture => {
             if !f.is_mask {
                 let vert_before = batch.vertices.len();
-                build_texture_quads(batch, bounds, f, bar_fill, eff_alpha);
+                build_texture_quads(batch, bounds, f, bar_fill, eff_alpha, tex_mgr.as_deref_mut());
                 if !f.mask_textures.is_empty() {
                     apply_mask_texture(batch, vert_before, bounds, &f.mask_textures, registry);
+                } else if let Some(mask_path) = &f.mask_file {
+                    apply_mask_file(batch, vert_before, mask_path);
                 }
             }
         }
@@ -470,6 +609,10 @@ pub fn emit_frame_quads(
         WidgetType::Line => {
             build_line_quads(batch, f, registry, eff_alpha);
         }
+        WidgetType::ColorSelect => {
+            build_frame_quads(batch, bounds, f, eff_alpha);
+            emit_color_select(batch, f, bounds, registry, eff_alpha);
+        }
         _ => {}
     }
 }
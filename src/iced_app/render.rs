@@ -8,9 +8,11 @@ use crate::render::font::WowFontSystem;
 use crate::render::glyph::GlyphAtlas;
 use crate::render::texture::UI_SCALE;
 use crate::render::{GpuTextureData, QuadBatch, WowUiPrimitive, load_texture_or_crop};
+use crate::texture::TextureManager;
 use crate::widget::{WidgetType};
 
 use super::app::App;
+use super::clipping::{ancestor_clip_rect, clip_quads, is_outside_clip};
 use super::frame_collect::{CollectedFrames, collect_subtree_ids, collect_hittable_frames};
 use super::quad_builders::{build_texture_quads, emit_button_highlight, emit_frame_quads};
 use super::statusbar::collect_statusbar_fills;
@@ -174,6 +176,7 @@ fn emit_single_strata(
     pressed_frame: Option<u64>,
     hovered_frame: Option<u64>,
     text_ctx: &mut Option<(&mut WowFontSystem, &mut GlyphAtlas)>,
+    tex_mgr: &mut Option<&mut TextureManager>,
     message_frames: Option<&std::collections::HashMap<u64, crate::lua_api::message_frame::MessageFrameData>>,
     tooltip_data: Option<&std::collections::HashMap<u64, TooltipRenderData>>,
     elapsed_secs: f64,
@@ -217,8 +220,17 @@ fn emit_single_strata(
             Point::new(rect.x * UI_SCALE, rect.y * UI_SCALE),
             Size::new(rect.width * UI_SCALE, rect.height * UI_SCALE),
         );
+        let clip = ancestor_clip_rect(registry, id);
+        if let Some(clip) = clip
+            && !is_line && is_outside_clip(bounds, clip) {
+                continue;
+            }
         let bar_fill = statusbar_fills.get(&id);
-        emit_frame_quads(batch, id, f, bounds, bar_fill, pressed_frame, hovered_frame, text_ctx, message_frames, tooltip_data, registry, elapsed_secs, eff_alpha);
+        let vert_before = batch.vertices.len();
+        emit_frame_quads(batch, id, f, bounds, bar_fill, pressed_frame, hovered_frame, text_ctx, tex_mgr, message_frames, tooltip_data, registry, elapsed_secs, eff_alpha);
+        if let Some(clip) = clip {
+            clip_quads(batch, vert_before, clip);
+        }
     }
 }
 
@@ -227,26 +239,69 @@ fn emit_single_strata(
 /// When `text_ctx` is provided, FontString and button/editbox/checkbox text is
 /// rendered as glyph quads interleaved with texture quads (correct draw order).
 /// When `None`, text is skipped (legacy behavior for callers without fonts).
+/// Tiled textures without an atlas size repeat at the frame size; use
+/// [`build_quad_batch_at_time`] with a texture manager for native-size tiles.
 #[allow(clippy::too_many_arguments)]
 pub fn build_quad_batch_for_registry(
+    registry: &crate::widget::WidgetRegistry,
+    screen_size: (f32, f32),
+    root_name: Option<&str>,
+    pressed_frame: Option<u64>,
+    hovered_frame: Option<u64>,
+    text_ctx: Option<(&mut WowFontSystem, &mut GlyphAtlas)>,
+    message_frames: Option<&std::collections::HashMap<u64, crate::lua_api::message_frame::MessageFrameData>>,
+    tooltip_data: Option<&std::collections::HashMap<u64, TooltipRenderData>>,
+    strata_buckets: &Vec<Vec<u64>>,
+) -> QuadBatch {
+    build_quad_batch_at_time(
+        registry, screen_size, root_name, pressed_frame, hovered_frame,
+        text_ctx, None, message_frames, tooltip_data,
+        strata_buckets, 0.0,
+    )
+}
+
+/// Like [`build_quad_batch_for_registry`], but evaluates time-dependent
+/// overlays (cooldown swipes) at `elapsed_secs` on the simulator clock.
+///
+/// Screenshots pass `SimState::clock.now()` so a cooldown started
+/// with `SetCooldown(GetTime(), duration)` renders at its current angle.
+/// `tex_mgr` decodes the textures of tiled regions so they repeat at their
+/// native size.
+#[allow(clippy::too_many_arguments)]
+pub fn build_quad_batch_at_time(
     registry: &crate::widget::WidgetRegistry,
     screen_size: (f32, f32),
     root_name: Option<&str>,
     pressed_frame: Option<u64>,
     hovered_frame: Option<u64>,
     mut text_ctx: Option<(&mut WowFontSystem, &mut GlyphAtlas)>,
+    mut tex_mgr: Option<&mut TextureManager>,
     message_frames: Option<&std::collections::HashMap<u64, crate::lua_api::message_frame::MessageFrameData>>,
     tooltip_data: Option<&std::collections::HashMap<u64, TooltipRenderData>>,
     strata_buckets: &Vec<Vec<u64>>,
+    elapsed_secs: f64,
 ) -> QuadBatch {
     let (batch, _collected) = build_quad_batch_with_cache(
         registry, screen_size, root_name, pressed_frame, hovered_frame,
-        &mut text_ctx, message_frames, tooltip_data,
-        strata_buckets, 0.0,
+        &mut text_ctx, &mut tex_mgr, message_frames, tooltip_data,
+        strata_buckets, elapsed_secs,
     );
     batch
 }
 
+/// Keep only the strata buckets at or above `min_strata`, for screenshots of
+/// dialogs and tooltips without the UI underneath.
+pub fn strata_buckets_from(
+    strata_buckets: &[Vec<u64>],
+    min_strata: crate::widget::FrameStrata,
+) -> Vec<Vec<u64>> {
+    strata_buckets
+        .iter()
+        .enumerate()
+        .map(|(i, bucket)| if i >= min_strata.as_index() { bucket.clone() } else { Vec::new() })
+        .collect()
+}
+
 /// Scale hittable layout rects to screen coordinates, applying hit rect insets.
 pub fn build_hittable_rects(
     collected: &CollectedFrames,
@@ -276,6 +331,7 @@ pub fn build_quad_batch_with_cache(
     pressed_frame: Option<u64>,
     hovered_frame: Option<u64>,
     text_ctx: &mut Option<(&mut WowFontSystem, &mut GlyphAtlas)>,
+    tex_mgr: &mut Option<&mut TextureManager>,
     message_frames: Option<&std::collections::HashMap<u64, crate::lua_api::message_frame::MessageFrameData>>,
     tooltip_data: Option<&std::collections::HashMap<u64, TooltipRenderData>>,
     strata_buckets: &[Vec<u64>],
@@ -301,7 +357,7 @@ pub fn build_quad_batch_with_cache(
         emit_single_strata(
             &mut batch, bucket, registry,
             &visible_ids, pressed_frame, hovered_frame,
-            text_ctx, message_frames, tooltip_data, elapsed_secs,
+            text_ctx, tex_mgr, message_frames, tooltip_data, elapsed_secs,
         );
     }
     (batch, collected)
@@ -370,14 +426,17 @@ impl App {
         };
 
         let state = env.state().borrow();
-        let elapsed_secs = state.start_time.elapsed().as_secs_f64();
+        let elapsed_secs = state.clock.now();
         let tooltip_data = super::tooltip::collect_tooltip_data(&state);
         let mut glyph_atlas = self.glyph_atlas.borrow_mut();
         glyph_atlas.advance_generation();
         let mut text_ctx: Option<(&mut WowFontSystem, &mut GlyphAtlas)> =
             Some((&mut font_sys, &mut glyph_atlas));
+        let mut tex_mgr_ref = self.texture_manager.borrow_mut();
+        let mut tex_mgr: Option<&mut TextureManager> = Some(&mut *tex_mgr_ref);
 
         let mut strata_cache = self.cached_strata_quads.borrow_mut();
+        let mut hyperlinks = Vec::new();
 
         for i in 0..FrameStrata::COUNT {
             if dirty & (1 << i) == 0 && strata_cache[i].is_some() {
@@ -399,13 +458,15 @@ impl App {
                     &mut batch, bucket, &state.widgets,
                     &None,
                     self.pressed_frame, None,
-                    &mut text_ctx, Some(&state.message_frames),
+                    &mut text_ctx, &mut tex_mgr, Some(&state.message_frames),
                     Some(&tooltip_data), elapsed_secs,
                 );
             }
+            hyperlinks.append(&mut batch.hyperlinks);
             strata_cache[i] = Some(Arc::new(batch));
         }
         drop(strata_cache);
+        drop(tex_mgr_ref);
 
         // Build hittable grid on first render.
         if self.cached_hittable.borrow().is_none() {
@@ -419,6 +480,7 @@ impl App {
 
         let mut state = env.state().borrow_mut();
         state.strata_buckets = Some(strata_buckets);
+        state.set_hyperlink_regions(hyperlinks);
     }
 
     /// Load textures not yet uploaded to the GPU atlas.
@@ -488,7 +550,7 @@ impl App {
                         Point::new(ht_rect.x * UI_SCALE, ht_rect.y * UI_SCALE),
                         Size::new(ht_rect.width * UI_SCALE, ht_rect.height * UI_SCALE),
                     );
-                    build_texture_quads(quads, ht_bounds, ht, None, ht.alpha);
+                    build_texture_quads(quads, ht_bounds, ht, None, ht.alpha, None);
                 }
             }
     }
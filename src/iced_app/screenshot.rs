@@ -7,7 +7,8 @@ use crate::render::headless::render_to_image;
 use crate::render::GlyphAtlas;
 
 use super::app::App;
-use super::render::build_quad_batch_for_registry;
+use super::render::{build_quad_batch_at_time, strata_buckets_from};
+use crate::widget::FrameStrata;
 
 impl App {
     /// Render a screenshot from the live app state and save to disk.
@@ -18,8 +19,14 @@ impl App {
         height: u32,
         filter: Option<&str>,
         crop: Option<&str>,
+        min_strata: Option<&str>,
     ) -> LuaResponse {
         let output_path = Path::new(output).with_extension("webp");
+        let min_strata = match min_strata.map(|s| (s, FrameStrata::from_str(s))) {
+            Some((s, None)) => return LuaResponse::Error(format!("Unknown strata '{}'", s)),
+            Some((_, strata)) => strata,
+            None => None,
+        };
 
         let mut glyph_atlas = GlyphAtlas::new();
         let batch = {
@@ -29,21 +36,30 @@ impl App {
                 let mut state = env.state().borrow_mut();
                 super::tooltip::update_tooltip_sizes(&mut state, &mut fs);
                 let _ = state.get_strata_buckets();
-                state.strata_buckets.as_ref().unwrap().clone()
+                let buckets = state.strata_buckets.as_ref().unwrap();
+                match min_strata {
+                    Some(min) => strata_buckets_from(buckets, min),
+                    None => buckets.clone(),
+                }
             };
             let state = env.state().borrow();
             let tooltip_data = super::tooltip::collect_tooltip_data(&state);
-            build_quad_batch_for_registry(
+            let mut batch = build_quad_batch_at_time(
                 &state.widgets,
                 (width as f32, height as f32),
                 filter,
                 self.pressed_frame,
                 self.hovered_frame,
                 Some((&mut fs, &mut glyph_atlas)),
+                Some(&mut *self.texture_manager.borrow_mut()),
                 Some(&state.message_frames),
                 Some(&tooltip_data),
                 &buckets,
-            )
+                state.clock.now(),
+            );
+            drop(state);
+            env.state().borrow_mut().set_hyperlink_regions(std::mem::take(&mut batch.hyperlinks));
+            batch
         };
 
         let glyph_data = if glyph_atlas.is_dirty() {
@@ -0,0 +1,73 @@
+//! SimpleHTML block rendering.
+
+use iced::Rectangle;
+
+use crate::lua_api::simple_html::{estimate_text_height, image_block_size, stack_html_blocks};
+use crate::render::font::WowFontSystem;
+use crate::render::glyph::{emit_text_quads, measure_text_height, GlyphAtlas, TextLinkRect};
+use crate::render::shader::GLYPH_ATLAS_TEX_INDEX;
+use crate::render::{BlendMode, QuadBatch};
+use crate::widget::{HtmlBlock, TextJustify};
+
+/// Render a SimpleHTML frame's blocks stacked top-to-bottom within bounds.
+///
+/// Paragraphs and headers wrap to the frame width with their own font and
+/// justification; `<img>` blocks become textured quads. Without a font
+/// context text is skipped but images are still placed, using estimated
+/// text heights for the blocks above them. Returns where the blocks'
+/// hyperlinks were drawn.
+pub fn emit_simple_html(
+    batch: &mut QuadBatch,
+    text_ctx: &mut Option<(&mut WowFontSystem, &mut GlyphAtlas)>,
+    f: &crate::widget::Frame,
+    bounds: Rectangle,
+    alpha: f32,
+) -> Vec<TextLinkRect> {
+    let mut links = Vec::new();
+    if f.html_blocks.is_empty() || bounds.width <= 0.0 {
+        return links;
+    }
+    let offsets = stack_html_blocks(&f.html_blocks, bounds.width, |text, font, size, width| {
+        match text_ctx {
+            Some((fs, ga)) => measure_text_height(fs, ga, text, font, size, width, true),
+            None => estimate_text_height(text, size, width),
+        }
+    });
+
+    for (block, &(top, height)) in f.html_blocks.iter().zip(&offsets) {
+        let y = bounds.y + top;
+        match block {
+            HtmlBlock::Text { text, font, font_size, color, justify_h, shadow_color, shadow_offset } => {
+                let Some((fs, ga)) = text_ctx else { continue };
+                let shadow = (shadow_color.a > 0.0)
+                    .then(|| [shadow_color.r, shadow_color.g, shadow_color.b, shadow_color.a * alpha]);
+                links.extend(emit_text_quads(
+                    batch, fs, ga, text,
+                    Rectangle { x: bounds.x, y, width: bounds.width, height },
+                    font.as_deref(), *font_size,
+                    [color.r, color.g, color.b, color.a * alpha],
+                    *justify_h, TextJustify::Left, // top-aligned within its slot
+                    GLYPH_ATLAS_TEX_INDEX,
+                    shadow, *shadow_offset,
+                    f.font_outline,
+                    true, 0,
+                ));
+            }
+            HtmlBlock::Image { path, width, height, justify_h } => {
+                let (w, h) = image_block_size(*width, *height, bounds.width);
+                let x = match justify_h {
+                    TextJustify::Left => bounds.x,
+                    TextJustify::Center => bounds.x + (bounds.width - w) / 2.0,
+                    TextJustify::Right => bounds.x + bounds.width - w,
+                };
+                batch.push_textured_path(
+                    Rectangle { x, y, width: w, height: h },
+                    path,
+                    [1.0, 1.0, 1.0, alpha],
+                    BlendMode::Alpha,
+                );
+            }
+        }
+    }
+    links
+}
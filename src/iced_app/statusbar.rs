@@ -7,6 +7,8 @@ use crate::widget::{Color, WidgetType};
 pub(super) struct StatusBarFill {
     pub fraction: f32,
     pub reverse: bool,
+    /// VERTICAL orientation: fill grows from the bottom (top when reversed).
+    pub vertical: bool,
     pub color: Option<Color>,
 }
 
@@ -37,6 +39,7 @@ pub(super) fn collect_statusbar_fills(
         fills.insert(bar_id, StatusBarFill {
             fraction: fraction.clamp(0.0, 1.0),
             reverse: frame.statusbar_reverse_fill,
+            vertical: frame.statusbar_orientation == "VERTICAL",
             color: frame.statusbar_color,
         });
     }
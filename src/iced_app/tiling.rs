@@ -2,6 +2,7 @@
 
 use iced::{Point, Rectangle, Size};
 use crate::render::{BlendMode, QuadBatch};
+use crate::texture::TextureManager;
 
 #[derive(Debug, Clone, Copy)]
 enum TileDir {
@@ -23,13 +24,44 @@ struct UvRepeatInfo {
     rotated: bool,
 }
 
-/// Compute tile dimensions from frame size or UV region as fallback.
-fn tile_dimensions(f: &crate::widget::Frame, uv_w: f32, uv_h: f32) -> (f32, f32) {
+/// Compute tile dimensions: the texture's native size when known, else the
+/// frame size, else the UV region as a last resort.
+fn tile_dimensions(
+    f: &crate::widget::Frame,
+    tex_path: &str,
+    uv_w: f32,
+    uv_h: f32,
+    tex_mgr: Option<&mut TextureManager>,
+) -> (f32, f32) {
+    if let Some((w, h)) = native_tile_size(f, tex_path, uv_w, uv_h, tex_mgr) {
+        return (w.max(1.0), h.max(1.0));
+    }
     let tile_w = if f.width > 1.0 { f.width } else { (uv_w * 128.0).max(8.0) };
     let tile_h = if f.height > 1.0 { f.height } else { (uv_h * 128.0).max(8.0) };
     (tile_w, tile_h)
 }
 
+/// One tile at native resolution: the atlas entry's size, or the file's size
+/// (decoded through `tex_mgr`) scaled by the UV region shown. UV ranges
+/// beyond 1.0 already encode a repeat count, so those keep the frame-size
+/// fallback.
+fn native_tile_size(
+    f: &crate::widget::Frame,
+    tex_path: &str,
+    uv_w: f32,
+    uv_h: f32,
+    tex_mgr: Option<&mut TextureManager>,
+) -> Option<(f32, f32)> {
+    if uv_w > 1.0 || uv_h > 1.0 {
+        return None;
+    }
+    if let Some(lookup) = f.atlas.as_deref().and_then(crate::atlas::get_atlas_info) {
+        return Some((lookup.width() as f32, lookup.height() as f32));
+    }
+    let (w, h) = tex_mgr?.native_size(tex_path)?;
+    Some((w as f32 * uv_w, h as f32 * uv_h))
+}
+
 /// Analyze raw 8-arg SetTexCoord values to determine tiling parameters.
 ///
 /// BackdropTemplateMixin encodes repeat counts as UV values >1.0.
@@ -93,6 +125,7 @@ pub(super) fn emit_tiled_texture(
     tex_path: &str,
     f: &crate::widget::Frame,
     alpha: f32,
+    tex_mgr: Option<&mut TextureManager>,
 ) {
     // Check for UV-based repeat tiling (BackdropTemplateMixin pattern):
     // 8-arg SetTexCoord with values >1.0 encoding repeat counts.
@@ -104,7 +137,7 @@ pub(super) fn emit_tiled_texture(
     }
 
     let (left, right, top, bottom) = (uvs.x, uvs.x + uvs.width, uvs.y, uvs.y + uvs.height);
-    let (tile_w, tile_h) = tile_dimensions(f, right - left, bottom - top);
+    let (tile_w, tile_h) = tile_dimensions(f, tex_path, right - left, bottom - top, tex_mgr);
     let tint = frame_tint(f, alpha);
 
     if f.horiz_tile && !f.vert_tile {
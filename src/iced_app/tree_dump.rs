@@ -13,12 +13,41 @@ impl App {
     }
 
     /// Build a frame tree dump with computed layout rects (for connected dump-tree).
-    pub(crate) fn build_frame_tree_dump(&self, filter: Option<&str>, visible_only: bool) -> String {
+    pub(crate) fn build_frame_tree_dump(&self, filter: Option<&str>, visible_only: bool, json: bool) -> String {
         let env = self.env.borrow();
         let state = env.state().borrow();
         let screen_width = self.screen_size.get().width;
         let screen_height = self.screen_size.get().height;
+        if json {
+            let tree = crate::dump::frame_tree_json(&state.widgets, filter, None, visible_only, screen_width, screen_height);
+            return serde_json::to_string_pretty(&tree).unwrap_or_default();
+        }
         let lines = crate::dump::build_tree(&state.widgets, filter, None, visible_only, screen_width, screen_height);
         if lines.is_empty() { "No frames found".to_string() } else { lines.join("\n") }
     }
+
+    /// Named frame rects without rendering (for `wow-cli layout`).
+    pub(crate) fn build_layout_dump(&self, json: bool) -> String {
+        let env = self.env.borrow();
+        let snapshot = crate::layout_snapshot::LayoutSnapshot::capture(&mut env.state().borrow_mut());
+        if json {
+            return serde_json::to_string_pretty(&snapshot.to_json()).unwrap_or_default();
+        }
+        snapshot.lines().join("\n")
+    }
+
+    /// List frames matching a `dump::FrameQuery` string (for `dump-tree --query`).
+    pub(crate) fn build_frame_query_dump(&self, query: &str, json: bool) -> Result<String, String> {
+        let query = crate::dump::FrameQuery::parse(query).map_err(|e| format!("Invalid query: {e}"))?;
+        let env = self.env.borrow();
+        let state = env.state().borrow();
+        let screen_width = self.screen_size.get().width;
+        let screen_height = self.screen_size.get().height;
+        if json {
+            let result = crate::dump::query_results_json(&state.widgets, &query, screen_width, screen_height);
+            return Ok(serde_json::to_string_pretty(&result).unwrap_or_default());
+        }
+        let lines = crate::dump::build_query_results(&state.widgets, &query, screen_width, screen_height);
+        Ok(if lines.is_empty() { "No frames found".to_string() } else { lines.join("\n") })
+    }
 }
@@ -406,7 +406,7 @@ impl App {
             while let Some(id) = stack.pop() {
                 let Some(f) = registry.get(id) else { continue };
                 if became_visible {
-                    if f.visible && f.effective_alpha > 0.0 && f.mouse_enabled
+                    if f.visible && f.effective_alpha > 0.0 && f.is_mouse_hittable()
                         && !f.name.as_deref().is_some_and(|n| {
                             super::frame_collect::HIT_TEST_EXCLUDED.contains(&n)
                         })
@@ -488,52 +488,6 @@ impl App {
         }
     }
 
-    /// Toggle CheckButton checked state before OnClick (WoW behavior).
-    /// Skip action bar buttons — they manage checked state via UpdateState().
-    pub(super) fn toggle_checkbutton_if_needed(&self, frame_id: u64, env: &WowLuaEnv) {
-        let mut state = env.state().borrow_mut();
-        let is_checkbutton = state
-            .widgets
-            .get(frame_id)
-            .map(|f| f.widget_type == crate::widget::WidgetType::CheckButton)
-            .unwrap_or(false);
-        if !is_checkbutton {
-            return;
-        }
-        // Action bar buttons registered via SetActionUIButton manage their own
-        // checked state through UpdateState() — don't auto-toggle them.
-        let is_action_button = state.action_ui_buttons.iter().any(|(id, _)| *id == frame_id);
-        if is_action_button {
-            return;
-        }
-
-        let old_checked = state
-            .widgets
-            .get(frame_id)
-            .and_then(|f| f.attributes.get("__checked"))
-            .and_then(|v| {
-                if let crate::widget::AttributeValue::Boolean(b) = v {
-                    Some(*b)
-                } else {
-                    None
-                }
-            })
-            .unwrap_or(false);
-        let new_checked = !old_checked;
-
-        if let Some(frame) = state.widgets.get_mut(frame_id) {
-            frame.attributes.insert(
-                "__checked".to_string(),
-                crate::widget::AttributeValue::Boolean(new_checked),
-            );
-        }
-        let tex_id = state.widgets.get(frame_id)
-            .and_then(|f| f.children_keys.get("CheckedTexture").copied());
-        if let Some(tex_id) = tex_id {
-            state.set_frame_visible(tex_id, new_checked);
-        }
-    }
-
     /// Sync the iced canvas size to SimState and UIParent/WorldFrame dimensions.
     /// Called from the render path when the window is resized by the window manager.
     pub(crate) fn sync_screen_size_to_state(&self, size: iced::Size) {
@@ -564,7 +518,7 @@ fn extract_completed_cast(
 ) -> Option<(u32, u32)> {
     let mut s = state.borrow_mut();
     let c = s.casting.as_ref()?;
-    let now = s.start_time.elapsed().as_secs_f64();
+    let now = s.clock.now();
     if now < c.end_time {
         return None;
     }
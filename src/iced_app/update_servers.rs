@@ -89,6 +89,55 @@ impl App {
         }
     }
 
+    /// Reload one addon from the REPL server and describe the outcome.
+    fn reload_addon_command(&self, name: &str) -> LuaResponse {
+        let env = self.env.borrow();
+        let result =
+            crate::lua_api::globals::addon_api::reload_addon(env.lua(), env.state(), name);
+        match result {
+            Ok(result) => {
+                let mut lines = vec![format!(
+                    "Reloaded {}: {} Lua, {} XML ({:.1?})",
+                    result.name,
+                    result.lua_files,
+                    result.xml_files,
+                    result.timing.total()
+                )];
+                lines.extend(result.warnings.iter().map(|w| format!("warning: {}", w)));
+                lines.push(
+                    "Note: old frames were hidden and unregistered and old timers cancelled; \
+                     globals, hooks and saved variables from the previous load remain."
+                        .to_string(),
+                );
+                LuaResponse::Output(lines.join("\n"))
+            }
+            Err(e) => LuaResponse::Error(e),
+        }
+    }
+
+    /// Write SavedVariables back to storage on request from the REPL server.
+    fn save_vars_command(&self) -> LuaResponse {
+        let Some(ref saved_vars) = self.saved_vars else {
+            return LuaResponse::Error("SavedVariables are disabled (--no-saved-vars)".into());
+        };
+        match saved_vars.save(&self.env.borrow()) {
+            Ok(count) => LuaResponse::Output(format!(
+                "Saved SavedVariables for {} addon(s) to {}",
+                count,
+                saved_vars.storage_dir().display()
+            )),
+            Err(e) => LuaResponse::Error(format!("SavedVariables save error: {}", e)),
+        }
+    }
+
+    /// Missing-API report for the REPL server.
+    fn api_coverage_command(&self) -> LuaResponse {
+        match self.env.borrow().api_coverage_report() {
+            Some(report) => LuaResponse::Output(report),
+            None => LuaResponse::Error("API coverage is off (start with --api-coverage)".into()),
+        }
+    }
+
     pub(crate) fn process_lua_commands(&mut self) {
         let commands: Vec<_> = self
             .lua_rx
@@ -104,13 +153,44 @@ impl App {
                     self.drain_console();
                     self.mark_all_strata_dirty();
                 }
+                LuaCommand::Eval { code, respond } => {
+                    let response = crate::lua_server::eval_chunk(self.env.borrow().lua(), &code);
+                    let _ = respond.send(response);
+                    self.drain_console();
+                    self.mark_all_strata_dirty();
+                }
+                LuaCommand::Tail { subscriber } => {
+                    let env = self.env.borrow();
+                    env.state().borrow_mut().console_subscribers.push(subscriber);
+                }
                 LuaCommand::DumpTree {
                     filter,
                     visible_only,
+                    json,
+                    query,
                     respond,
                 } => {
-                    let tree = self.build_frame_tree_dump(filter.as_deref(), visible_only);
-                    let _ = respond.send(LuaResponse::Tree(tree));
+                    let response = match query {
+                        Some(query) => self.build_frame_query_dump(&query, json)
+                            .map_or_else(LuaResponse::Error, LuaResponse::Tree),
+                        None => LuaResponse::Tree(self.build_frame_tree_dump(filter.as_deref(), visible_only, json)),
+                    };
+                    let _ = respond.send(response);
+                }
+                LuaCommand::Layout { json, respond } => {
+                    let _ = respond.send(LuaResponse::Tree(self.build_layout_dump(json)));
+                }
+                LuaCommand::ReloadAddon { name, respond } => {
+                    let response = self.reload_addon_command(&name);
+                    let _ = respond.send(response);
+                    self.drain_console();
+                    self.mark_all_strata_dirty();
+                }
+                LuaCommand::SaveVars { respond } => {
+                    let _ = respond.send(self.save_vars_command());
+                }
+                LuaCommand::ApiCoverage { respond } => {
+                    let _ = respond.send(self.api_coverage_command());
                 }
                 LuaCommand::Screenshot {
                     output,
@@ -118,9 +198,17 @@ impl App {
                     height,
                     filter,
                     crop,
+                    min_strata,
                     respond,
                 } => {
-                    let result = self.render_screenshot(&output, width, height, filter.as_deref(), crop.as_deref());
+                    let result = self.render_screenshot(
+                        &output,
+                        width,
+                        height,
+                        filter.as_deref(),
+                        crop.as_deref(),
+                        min_strata.as_deref(),
+                    );
                     let _ = respond.send(result);
                 }
             }
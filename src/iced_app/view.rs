@@ -335,8 +335,8 @@ impl App {
                     visible
                 );
 
-                let display = if display.len() > 30 {
-                    format!("{}...", &display[..27])
+                let display = if display.chars().count() > 30 {
+                    format!("{}...", crate::lua_api::globals::utf8_api::truncate_chars(&display, 27))
                 } else {
                     display
                 };
@@ -571,27 +571,39 @@ impl App {
             .into()
     }
 
+    /// Hit test for clicks: the frame under the cursor that accepts mouse clicks.
+    pub(crate) fn hit_test(&self, pos: iced::Point) -> Option<u64> {
+        self.hit_test_where(pos, crate::widget::Frame::accepts_mouse_clicks)
+    }
+
+    /// Hit test for hover: the frame under the cursor that accepts mouse motion.
+    pub(crate) fn hover_test(&self, pos: iced::Point) -> Option<u64> {
+        self.hit_test_where(pos, crate::widget::Frame::accepts_mouse_motion)
+    }
+
     /// Hit test to find frame under cursor (uses cached rects from render pass).
     ///
     /// After finding the topmost frame at the cursor position (highest strata/level),
     /// drills down through child frames to find the deepest mouse-enabled descendant.
     /// This matches WoW behavior where child frames always receive clicks over parents,
-    /// regardless of the parent's frame level.
-    pub(crate) fn hit_test(&self, pos: iced::Point) -> Option<u64> {
+    /// regardless of the parent's frame level. Frames rejected by `accepts` are
+    /// transparent to the query.
+    fn hit_test_where(&self, pos: iced::Point, accepts: fn(&crate::widget::Frame) -> bool) -> Option<u64> {
         let cache = self.cached_hittable.borrow();
         let grid = cache.as_ref()?;
+        let env = self.env.borrow();
+        let state = env.state().borrow();
+        let accepts_id = |id: u64| state.widgets.get(id).is_some_and(accepts);
 
         // Phase 1: Grid lookup — O(1) cell + O(k) scan within cell.
-        let initial_id = grid.topmost_at(pos)?;
+        let initial_id = grid.topmost_at_where(pos, accepts_id)?;
 
         // Phase 2: Drill down through children to deepest mouse-enabled descendant.
-        let env = self.env.borrow();
-        let state = env.state().borrow();
         let mut current = initial_id;
         loop {
             let Some(frame) = state.widgets.get(current) else { break };
             let child_hit = frame.children.iter().rev().find(|&&cid| {
-                grid.contains(cid, pos)
+                grid.contains(cid, pos) && accepts_id(cid)
             });
             match child_hit {
                 Some(&cid) => current = cid,
@@ -0,0 +1,219 @@
+//! Headless input scripts for reproducible interaction tests.
+//!
+//! A script is plain text with one command per line, run after startup by
+//! `dump-tree`/`screenshot --input-script @file`:
+//!
+//! ```text
+//! # open the game menu, then close it again
+//! click MainMenuMicroButton
+//! hover CharacterMicroButton
+//! scroll ChatFrame1 -1
+//! type hello
+//! key RETURN
+//! wait 500
+//! ```
+//!
+//! Frame arguments are global names, optionally followed by `.Key` parent
+//! keys (`GameMenuFrame.Header`). Mouse commands aim at the frame's center
+//! and go through hit testing (mouse_dispatch.rs), so a covered frame is not
+//! clicked — whatever is on top receives the input, as in the client.
+
+use crate::lua_api::WowLuaEnv;
+
+/// One line of an input script.
+#[derive(Debug, Clone, PartialEq)]
+pub enum InputCommand {
+    /// `click <frame> [button]` — press and release at the frame's center.
+    Click { frame: String, button: String },
+    /// `hover <frame>` — move the cursor to the frame's center.
+    Hover { frame: String },
+    /// `key <KEY>` — a WoW key name (`ESCAPE`, `ENTER`/`RETURN`, `A`, ...).
+    Key { key: String },
+    /// `type <text>` — one key press per character of the rest of the line.
+    Type { text: String },
+    /// `scroll <frame> <delta>` — mouse wheel at the frame's center.
+    Scroll { frame: String, delta: f32 },
+    /// `wait <ms>` — advance the simulated clock and drain timers.
+    Wait { ms: u64 },
+}
+
+/// Parse an input script. Blank lines and `#` comments are skipped; errors
+/// name the 1-based line number.
+pub fn parse_input_script(source: &str) -> Result<Vec<InputCommand>, String> {
+    source
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| {
+            let line = line.trim();
+            !line.is_empty() && !line.starts_with('#')
+        })
+        .map(|(i, line)| parse_line(line.trim()).map_err(|e| format!("line {}: {}", i + 1, e)))
+        .collect()
+}
+
+/// Turn an inline `--input-script` value into script lines: unescaped `;`
+/// separates commands and `\;` is a literal semicolon (e.g. `type a\;b`).
+pub fn inline_script_lines(inline: &str) -> String {
+    let mut lines = String::with_capacity(inline.len());
+    let mut chars = inline.chars().peekable();
+    while let Some(ch) = chars.next() {
+        match ch {
+            '\\' if chars.peek() == Some(&';') => lines.push(chars.next().unwrap()),
+            ';' => lines.push('\n'),
+            _ => lines.push(ch),
+        }
+    }
+    lines
+}
+
+fn parse_line(line: &str) -> Result<InputCommand, String> {
+    let (cmd, rest) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+    let rest = rest.trim();
+    let args: Vec<&str> = rest.split_whitespace().collect();
+    let frame_arg = || {
+        args.first()
+            .map(|s| s.to_string())
+            .ok_or_else(|| format!("'{cmd}' needs a frame name"))
+    };
+    match cmd.to_ascii_lowercase().as_str() {
+        "click" => Ok(InputCommand::Click {
+            frame: frame_arg()?,
+            button: args.get(1).unwrap_or(&"LeftButton").to_string(),
+        }),
+        "hover" => Ok(InputCommand::Hover { frame: frame_arg()? }),
+        "key" => match args.as_slice() {
+            [key] => Ok(InputCommand::Key { key: normalize_key(key) }),
+            _ => Err("'key' needs exactly one key name".to_string()),
+        },
+        "type" if !rest.is_empty() => Ok(InputCommand::Type { text: rest.to_string() }),
+        "type" => Err("'type' needs text".to_string()),
+        "scroll" => {
+            let delta = args
+                .get(1)
+                .and_then(|d| d.parse().ok())
+                .ok_or_else(|| "'scroll' needs a frame name and a numeric delta".to_string())?;
+            Ok(InputCommand::Scroll { frame: frame_arg()?, delta })
+        }
+        "wait" => {
+            let ms = args
+                .first()
+                .and_then(|ms| ms.parse().ok())
+                .ok_or_else(|| "'wait' needs milliseconds".to_string())?;
+            Ok(InputCommand::Wait { ms })
+        }
+        _ => Err(format!("unknown command '{cmd}'")),
+    }
+}
+
+/// Uppercase a key name; `RETURN` is accepted as an alias for `ENTER`.
+fn normalize_key(key: &str) -> String {
+    match key.to_ascii_uppercase().as_str() {
+        "RETURN" => "ENTER".to_string(),
+        upper => upper.to_string(),
+    }
+}
+
+/// Run parsed commands in order, draining timers after each so deferred UI
+/// (C_Timer callbacks, on-demand addon loads) settles before the next one.
+pub fn run_input_script(env: &WowLuaEnv, commands: &[InputCommand]) -> Result<(), String> {
+    for command in commands {
+        tracing::debug!("[Input] {:?}", command);
+        run_command(env, command)?;
+        crate::startup::process_pending_timers(env);
+    }
+    Ok(())
+}
+
+fn run_command(env: &WowLuaEnv, command: &InputCommand) -> Result<(), String> {
+    let lua_err = |e: crate::Error| format!("{:?}: {}", command, e);
+    match command {
+        InputCommand::Click { frame, button } => {
+            let (x, y) = frame_center(env, frame)?;
+            env.send_mouse_move(x, y).map_err(lua_err)?;
+            env.send_mouse_down(x, y, button).map_err(lua_err)?;
+            env.send_mouse_up(x, y, button).map_err(lua_err)
+        }
+        InputCommand::Hover { frame } => {
+            let (x, y) = frame_center(env, frame)?;
+            env.send_mouse_move(x, y).map_err(lua_err)
+        }
+        InputCommand::Scroll { frame, delta } => {
+            let (x, y) = frame_center(env, frame)?;
+            env.send_mouse_move(x, y).map_err(lua_err)?;
+            env.send_mouse_wheel(x, y, *delta).map_err(lua_err)?;
+            Ok(())
+        }
+        InputCommand::Key { key } => {
+            let text = (key.chars().count() == 1).then(|| key.to_lowercase());
+            env.send_key_press(key, text.as_deref()).map_err(lua_err)
+        }
+        InputCommand::Type { text } => {
+            for ch in text.chars() {
+                let key = if ch == ' ' { "SPACE".to_string() } else { ch.to_uppercase().collect() };
+                env.send_key_press(&key, Some(&ch.to_string())).map_err(lua_err)?;
+            }
+            Ok(())
+        }
+        InputCommand::Wait { ms } => {
+            crate::startup::apply_delay(env, Some(*ms));
+            Ok(())
+        }
+    }
+}
+
+/// Center of a frame's layout rect, resolving `Name.Key.Key` paths.
+fn frame_center(env: &WowLuaEnv, path: &str) -> Result<(f32, f32), String> {
+    let mut state = env.state().borrow_mut();
+    state.ensure_layout_rects();
+    let mut parts = path.split('.');
+    let name = parts.next().unwrap_or_default();
+    let mut id = state
+        .widgets
+        .get_id_by_name(name)
+        .ok_or_else(|| format!("no frame named '{name}'"))?;
+    for key in parts {
+        id = state
+            .widgets
+            .get(id)
+            .and_then(|f| f.children_keys.get(key).copied())
+            .ok_or_else(|| format!("'{path}': no child key '{key}'"))?;
+    }
+    let rect = state
+        .widgets
+        .get(id)
+        .and_then(|f| f.layout_rect)
+        .ok_or_else(|| format!("'{path}' has no layout (never shown or anchored?)"))?;
+    Ok((rect.x + rect.width / 2.0, rect.y + rect.height / 2.0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_commands_skipping_comments_and_blanks() {
+        let script = "# open menu\n\nclick MainMenuMicroButton\nclick Foo RightButton\n\
+                      hover Bar.Close\nkey return\ntype hello world\nscroll ChatFrame1 -1\nwait 250\n";
+        let commands = parse_input_script(script).unwrap();
+        assert_eq!(
+            commands,
+            vec![
+                InputCommand::Click { frame: "MainMenuMicroButton".into(), button: "LeftButton".into() },
+                InputCommand::Click { frame: "Foo".into(), button: "RightButton".into() },
+                InputCommand::Hover { frame: "Bar.Close".into() },
+                InputCommand::Key { key: "ENTER".into() },
+                InputCommand::Type { text: "hello world".into() },
+                InputCommand::Scroll { frame: "ChatFrame1".into(), delta: -1.0 },
+                InputCommand::Wait { ms: 250 },
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_errors_name_the_line() {
+        let err = parse_input_script("click Foo\n\nscroll Foo up\n").unwrap_err();
+        assert!(err.starts_with("line 3:"), "{err}");
+        let err = parse_input_script("jump Foo").unwrap_err();
+        assert!(err.contains("unknown command 'jump'"), "{err}");
+    }
+}
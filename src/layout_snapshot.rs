@@ -0,0 +1,80 @@
+//! Layout-only headless runs (`wow-cli layout --json`).
+//!
+//! `compute_layout` runs the same startup sequence as `dump-tree` and
+//! resolves every frame rect, but never builds a quad batch or touches a
+//! `TextureManager`, so CI can assert on geometry without the render cost.
+
+use crate::LayoutRect;
+use crate::dump::is_generated_name;
+use crate::iced_app::layout::compute_frame_rect;
+use crate::lua_api::{SimState, WowLuaEnv};
+use std::collections::BTreeMap;
+
+/// Resolved rects of all named frames, in screen units with a top-left origin.
+#[derive(Debug, Clone, Default)]
+pub struct LayoutSnapshot {
+    pub screen_width: f32,
+    pub screen_height: f32,
+    pub frames: BTreeMap<String, LayoutRect>,
+}
+
+impl LayoutSnapshot {
+    /// Resolve pending layout and collect the rect of every named frame at
+    /// the state's current screen size.
+    pub fn capture(state: &mut SimState) -> Self {
+        state.ensure_layout_rects();
+        let (sw, sh) = (state.screen_width, state.screen_height);
+        let widgets = &state.widgets;
+        let frames = widgets
+            .named_frames()
+            .filter(|(_, name)| !is_generated_name(name))
+            .map(|(id, name)| {
+                let rect = widgets
+                    .get(id)
+                    .and_then(|f| f.layout_rect)
+                    .unwrap_or_else(|| compute_frame_rect(widgets, id, sw, sh));
+                (name.clone(), rect)
+            })
+            .collect();
+        Self { screen_width: sw, screen_height: sh, frames }
+    }
+
+    /// Rect of the frame named `name`.
+    pub fn get(&self, name: &str) -> Option<&LayoutRect> {
+        self.frames.get(name)
+    }
+
+    /// One `Name (x,y WxH)` line per frame, sorted by name.
+    pub fn lines(&self) -> Vec<String> {
+        self.frames
+            .iter()
+            .map(|(name, r)| format!("{name} ({:.0},{:.0} {}x{})", r.x, r.y, r.width as i32, r.height as i32))
+            .collect()
+    }
+
+    /// `{"screen": {"width", "height"}, "frames": {name: {x, y, width, height}}}`,
+    /// with frames sorted by name.
+    pub fn to_json(&self) -> serde_json::Value {
+        let frames: serde_json::Map<String, serde_json::Value> = self
+            .frames
+            .iter()
+            .map(|(name, r)| {
+                let rect = serde_json::json!({ "x": r.x, "y": r.y, "width": r.width, "height": r.height });
+                (name.clone(), rect)
+            })
+            .collect();
+        serde_json::json!({
+            "screen": { "width": self.screen_width, "height": self.screen_height },
+            "frames": frames,
+        })
+    }
+}
+
+/// Run the headless startup sequence (login events, timers, OnUpdate ticks)
+/// at `width`x`height` and snapshot the resulting layout.
+pub fn compute_layout(env: &WowLuaEnv, width: u32, height: u32) -> LayoutSnapshot {
+    env.set_screen_size(width as f32, height as f32);
+    crate::startup::fire_headless_startup(env);
+    crate::startup::settle_headless_startup(env);
+    LayoutSnapshot::capture(&mut env.state().borrow_mut())
+}
@@ -12,8 +12,12 @@ pub mod atlas;
 pub mod global_strings;
 #[path = "../data/items.rs"]
 pub mod items;
+#[path = "../data/maps.rs"]
+pub mod maps;
 #[path = "../data/manifest_interface_data.rs"]
 pub mod manifest_interface_data;
+#[path = "../data/soundkits.rs"]
+pub mod soundkits;
 #[path = "../data/spells.rs"]
 pub mod spells;
 #[path = "../data/spell_power.rs"]
@@ -28,6 +32,8 @@ pub mod error;
 pub mod event;
 pub mod extract_textures;
 pub mod iced_app;
+pub mod input;
+pub mod layout_snapshot;
 pub mod loader;
 pub mod lua_api;
 pub mod lua_server;
@@ -37,6 +43,7 @@ pub mod sound;
 pub mod startup;
 pub mod texture;
 pub mod toc;
+pub mod watch;
 pub mod widget;
 pub mod xml;
 
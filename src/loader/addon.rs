@@ -4,11 +4,13 @@ use crate::lua_api::LoaderEnv;
 use crate::saved_variables::SavedVariablesManager;
 use crate::toc::TocFile;
 use mlua::Table;
-use std::path::Path;
+use std::cell::RefCell;
+use std::path::{Path, PathBuf};
 use std::time::Instant;
 
 use super::error::LoadError;
 use super::lua_file::load_lua_file;
+use super::profile::{WidgetCounts, with_profile};
 use super::xml_file::load_xml_file;
 use super::{LoadResult, LoadTiming};
 
@@ -18,6 +20,22 @@ pub struct AddonContext<'a> {
     pub table: Table,
     /// Addon root directory for fallback path resolution
     pub addon_root: &'a Path,
+    /// XML files being loaded, outermost first, to stop include cycles.
+    pub include_stack: RefCell<Vec<PathBuf>>,
+    /// Failed `<Include>`/`<Script file>` loads; the including file carries on.
+    pub warnings: RefCell<Vec<String>>,
+}
+
+impl<'a> AddonContext<'a> {
+    pub fn new(name: &'a str, table: Table, addon_root: &'a Path) -> Self {
+        Self {
+            name,
+            table,
+            addon_root,
+            include_stack: RefCell::new(Vec::new()),
+            warnings: RefCell::new(Vec::new()),
+        }
+    }
 }
 
 /// Initialize saved variables for an addon (WTF first, then JSON fallback).
@@ -27,29 +45,21 @@ fn init_saved_variables(
     folder_name: &str,
     mgr: &mut SavedVariablesManager,
 ) -> Vec<String> {
-    let mut warnings = Vec::new();
-    match mgr.load_wtf_for_addon(env.lua(), folder_name) {
-        Ok(count) if count > 0 => {
-            tracing::debug!("Loaded {} WTF SavedVariables file(s) for {}", count, toc.name);
-        }
-        Ok(_) => {
-            let saved_vars = toc.saved_variables();
-            let saved_vars_per_char = toc.saved_variables_per_character();
-            if (!saved_vars.is_empty() || !saved_vars_per_char.is_empty())
-                && let Err(e) =
-                    mgr.init_for_addon(env.lua(), folder_name, &saved_vars, &saved_vars_per_char)
-                {
-                    warnings.push(format!(
-                        "Failed to initialize saved variables for {}: {}",
-                        folder_name, e
-                    ));
-                }
-        }
-        Err(e) => {
-            warnings.push(format!(
-                "Failed to load WTF SavedVariables for {}: {}",
+    let (wtf_files, mut warnings) = mgr.load_wtf_for_addon(env.lua(), folder_name);
+    if wtf_files > 0 {
+        tracing::debug!("Loaded {} WTF SavedVariables file(s) for {}", wtf_files, toc.name);
+    }
+    // Registers the variables for saving, and loads simulator storage for any
+    // the WTF files didn't set.
+    let saved_vars = &toc.saved_variables;
+    let saved_vars_per_char = &toc.saved_variables_per_character;
+    if !saved_vars.is_empty() || !saved_vars_per_char.is_empty() {
+        match mgr.init_for_addon(env.lua(), folder_name, saved_vars, saved_vars_per_char) {
+            Ok(w) => warnings.extend(w),
+            Err(e) => warnings.push(format!(
+                "Failed to initialize saved variables for {}: {}",
                 folder_name, e
-            ));
+            )),
         }
     }
     warnings
@@ -94,14 +104,22 @@ pub fn load_addon_internal(
         env.state().borrow_mut().loading_addon_index = idx.map(|i| i as u16);
     }
 
-    let ctx = AddonContext {
-        name: folder_name,
-        table: addon_table,
-        addon_root: &toc.addon_dir,
-    };
+    let ctx = AddonContext::new(folder_name, addon_table, &toc.addon_dir);
+
+    // Collect script errors (OnLoad handlers etc.) raised while this addon
+    // loads. An addon loaded from inside another's load keeps its own list.
+    let outer_script_errors = env.state().borrow_mut().script_errors.replace(Vec::new());
 
     let overlay_dir = Path::new("Interface/AddOns").join(folder_name);
 
+    // Only measured when --profile is on; one borrow per addon otherwise.
+    let profile_start = {
+        let s = env.state().borrow();
+        s.load_profile
+            .is_some()
+            .then(|| (Instant::now(), WidgetCounts::of(&s.widgets)))
+    };
+
     for (file_rel, file) in toc.files.iter().zip(toc.file_paths()) {
         // Check local overlay first (./Interface/AddOns/{addon}/{file})
         let file = {
@@ -109,6 +127,7 @@ pub fn load_addon_internal(
             if overlay.exists() { overlay } else { file }
         };
         let ext = file.extension().and_then(|e| e.to_str()).unwrap_or("");
+        let file_start = profile_start.map(|_| Instant::now());
         match ext {
             "lua" => {
                 match load_lua_file(env, &file, &ctx, &mut result.timing) {
@@ -128,11 +147,25 @@ pub fn load_addon_internal(
                 result.warnings.push(format!("{}: unknown file type", file.display()));
             }
         }
+        result.warnings.append(&mut ctx.warnings.borrow_mut());
+        result.warnings.extend(crate::lua_api::take_anchor_cycle_warnings());
+        if let Some(errors) = env.state().borrow_mut().script_errors.as_mut() {
+            result.warnings.extend(errors.drain(..).map(|e| format!("{}: {}", file.display(), e)));
+        }
+        if let Some(start) = file_start {
+            with_profile(env.state(), |p| p.record_file(folder_name, &file, start.elapsed()));
+        }
+    }
+
+    if let Some((start, before)) = profile_start {
+        let created = WidgetCounts::of(&env.state().borrow().widgets).since(before);
+        with_profile(env.state(), |p| p.record_addon(folder_name, start.elapsed(), created));
     }
 
     // Clear loading_addon_index so runtime-created frames don't get attributed
     // to this addon after it finishes loading.
     env.state().borrow_mut().loading_addon_index = None;
+    env.state().borrow_mut().script_errors = outer_script_errors;
 
     Ok(result)
 }
@@ -5,27 +5,44 @@
 //! loads directly into the VM with no parsing overhead.
 //!
 //! Cache entries are invalidated automatically when file content changes
-//! (different hash). Invalid bytecode (Lua version change, corruption)
-//! triggers automatic recompilation via the fallback path.
+//! (different hash, and the length is part of the key too). Modification
+//! times are deliberately not part of the key: content is authoritative, and
+//! a git checkout rewrites mtimes without changing a byte. Invalid bytecode
+//! (Lua version change, corruption) triggers automatic recompilation via the
+//! fallback path.
 //!
-//! Disable with `WOW_SIM_NO_BYTECODE_CACHE=1`.
+//! Entries live under a versioned directory (`.cache/lua-bytecode/v2`); bump
+//! `FORMAT_VERSION` when the key or file layout changes so old entries are
+//! never read. `clear()` removes every version (`wow-cli clear-cache`).
+//!
+//! Disable with `--no-bytecode-cache` or `WOW_SIM_NO_BYTECODE_CACHE=1`.
 
 use std::collections::hash_map::DefaultHasher;
 use std::hash::{Hash, Hasher};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::OnceLock;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+const CACHE_ROOT: &str = ".cache/lua-bytecode";
+const FORMAT_VERSION: u32 = 2;
 
-const CACHE_DIR: &str = ".cache/lua-bytecode";
+static FORCE_DISABLED: AtomicBool = AtomicBool::new(false);
 
-/// Check if bytecode caching is disabled via environment variable.
-/// Result is cached after first check.
+/// Turn the cache off for the rest of the process (`--no-bytecode-cache`).
+pub fn disable() {
+    FORCE_DISABLED.store(true, Ordering::Relaxed);
+}
+
+/// Check if bytecode caching is disabled via `disable()` or the environment
+/// variable. The environment lookup is cached after first check.
 pub fn is_disabled() -> bool {
     static DISABLED: OnceLock<bool> = OnceLock::new();
-    *DISABLED.get_or_init(|| {
-        std::env::var("WOW_SIM_NO_BYTECODE_CACHE")
-            .map(|v| v == "1" || v.eq_ignore_ascii_case("true"))
-            .unwrap_or(false)
-    })
+    FORCE_DISABLED.load(Ordering::Relaxed)
+        || *DISABLED.get_or_init(|| {
+            std::env::var("WOW_SIM_NO_BYTECODE_CACHE")
+                .map(|v| v == "1" || v.eq_ignore_ascii_case("true"))
+                .unwrap_or(false)
+        })
 }
 
 /// Compute a cache key from file content and chunk name.
@@ -41,7 +58,38 @@ pub fn content_hash(bytes: &[u8], chunk_name: &str) -> u64 {
 }
 
 fn cache_path(hash: u64) -> PathBuf {
-    PathBuf::from(CACHE_DIR).join(format!("{hash:016x}.luac"))
+    Path::new(CACHE_ROOT)
+        .join(format!("v{FORMAT_VERSION}"))
+        .join(format!("{hash:016x}.luac"))
+}
+
+/// Delete the cache, including directories left by older format versions.
+/// Returns the number of cached chunks removed.
+pub fn clear() -> std::io::Result<usize> {
+    let root = Path::new(CACHE_ROOT);
+    if !root.exists() {
+        return Ok(0);
+    }
+    let removed = count_entries(root);
+    std::fs::remove_dir_all(root)?;
+    Ok(removed)
+}
+
+fn count_entries(dir: &Path) -> usize {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return 0;
+    };
+    entries
+        .flatten()
+        .map(|e| {
+            let path = e.path();
+            if path.is_dir() {
+                count_entries(&path)
+            } else {
+                usize::from(path.extension().is_some_and(|ext| ext == "luac"))
+            }
+        })
+        .sum()
 }
 
 /// Load cached bytecode for the given content hash.
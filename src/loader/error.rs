@@ -7,6 +7,8 @@ pub enum LoadError {
     Toc(std::io::Error),
     Xml(crate::xml::XmlLoadError),
     Lua(String),
+    /// `<Include>` cycle or nesting past the depth limit.
+    Include(String),
 }
 
 impl From<std::io::Error> for LoadError {
@@ -28,6 +30,7 @@ impl std::fmt::Display for LoadError {
             LoadError::Toc(e) => write!(f, "TOC error: {}", e),
             LoadError::Xml(e) => write!(f, "XML error: {}", e),
             LoadError::Lua(e) => write!(f, "Lua error: {}", e),
+            LoadError::Include(e) => write!(f, "Include error: {}", e),
         }
     }
 }
@@ -46,6 +46,9 @@ pub fn resolve_path_case_insensitive(base: &Path, path: &str) -> Option<PathBuf>
     }
 }
 
+/// Prefix of file paths given from the game root rather than the addon.
+const ADDONS_PREFIX: &str = "interface/addons/";
+
 /// Resolve a path relative to xml_dir, with fallback to addon_root.
 /// Some addons use paths relative to addon root instead of the XML file location.
 /// Uses case-insensitive matching for compatibility with WoW (Windows/macOS).
@@ -74,6 +77,14 @@ pub fn resolve_path_with_fallback(xml_dir: &Path, addon_root: &Path, file: &str)
         return resolved;
     }
 
+    // Game-root paths ("Interface\AddOns\Name\file.xml") resolve from the AddOns directory
+    if normalized.get(..ADDONS_PREFIX.len()).is_some_and(|p| p.eq_ignore_ascii_case(ADDONS_PREFIX))
+        && let Some(addons_dir) = addon_root.parent()
+        && let Some(resolved) = resolve_path_case_insensitive(addons_dir, &normalized[ADDONS_PREFIX.len()..])
+    {
+        return resolved;
+    }
+
     // Return primary path (will result in error with correct path)
     primary
 }
@@ -288,17 +299,45 @@ pub fn append_script_handler(
 ) {
     let Some(new_handler) = build_handler_expr(handler_name, script) else { return };
 
+    if let Some(order @ ("precall" | "postcall")) = script.intrinsic_order.as_deref() {
+        emit_intrinsic_handler(code, target, handler_name, &new_handler, order);
+        return;
+    }
+
     match script.inherit.as_deref() {
         Some("prepend") => emit_chained_handler(code, target, handler_name, &new_handler, false),
         Some("append") => emit_chained_handler(code, target, handler_name, &new_handler, true),
         _ => {
+            // A plain override still keeps any intrinsicOrder handler from the
+            // intrinsic base, placed before or after it.
             code.push_str(&format!(
-                "\n        {target}:SetScript(\"{handler_name}\", {new_handler})\n        "
+                "\n        {target}:SetScript(\"{handler_name}\", __compose_intrinsic_script({target}, \"{handler_name}\", {new_handler}))\n        "
             ));
         }
     }
 }
 
+/// Emit an intrinsic handler (`intrinsicOrder="precall"`/`"postcall"`): it is
+/// set now and recorded so handlers set later by inheriting frames run after
+/// (precall) or before (postcall) it instead of replacing it.
+fn emit_intrinsic_handler(
+    code: &mut String,
+    target: &str,
+    handler_name: &str,
+    new_handler: &str,
+    order: &str,
+) {
+    code.push_str(&format!(
+        r#"
+        do
+            local __new = {new_handler}
+            __set_intrinsic_script({target}, "{handler_name}", __new, "{order}")
+            {target}:SetScript("{handler_name}", __new)
+        end
+        "#
+    ));
+}
+
 /// Emit a chained handler that wraps the existing handler (new_first=true → new runs first).
 /// WoW semantics: "prepend"/"append" describe the INHERITED handler's position:
 ///   inherit="prepend" → inherited (old) runs first, instance (new) second → new_first=false
@@ -318,8 +357,8 @@ fn emit_chained_handler(
             local __new = {new_handler}
             if __old then
                 {target}:SetScript("{handler_name}", function(self, ...)
-                    local __ok1, __err1 = pcall({first}, self, ...)
-                    local __ok2, __err2 = pcall({second}, self, ...)
+                    local __ok1, __err1 = xpcall({first}, __script_traceback, self, ...)
+                    local __ok2, __err2 = xpcall({second}, __script_traceback, self, ...)
                     if not __ok1 then
                         local name = self.GetName and self:GetName() or "?"
                         __report_script_error("[script:{handler_name}] " .. name .. ": " .. tostring(__err1))
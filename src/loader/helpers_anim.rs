@@ -111,7 +111,9 @@ fn resolve_animation_element(
         crate::xml::AnimationElement::TextureCoordTranslation(a) => {
             Some(("TextureCoordTranslation", a))
         }
-        crate::xml::AnimationElement::Animation(a) => Some(("Animation", a)),
+        crate::xml::AnimationElement::Animation(a) => {
+            Some((a.anim_type.as_deref().unwrap_or("Animation"), a))
+        }
         _ => None,
     }
 }
@@ -193,11 +195,16 @@ fn generate_animation_code(
     emit_str_call(&mut code, "__anim", "SetSmoothing", anim.smoothing.as_deref());
     emit_num_call(&mut code, "__anim", "SetFromAlpha", anim.from_alpha);
     emit_num_call(&mut code, "__anim", "SetToAlpha", anim.to_alpha);
-    emit_pair_call(&mut code, "__anim", "SetOffset", anim.offset_x, anim.offset_y, 0.0);
+    let (offset_x, offset_y) = match &anim.offset {
+        Some(offset) if anim.offset_x.is_none() && anim.offset_y.is_none() => (offset.x, offset.y),
+        _ => (anim.offset_x, anim.offset_y),
+    };
+    emit_pair_call(&mut code, "__anim", "SetOffset", offset_x, offset_y, 0.0);
     emit_pair_call(&mut code, "__anim", "SetScale", anim.scale_x, anim.scale_y, 1.0);
     emit_pair_call(&mut code, "__anim", "SetScaleFrom", anim.from_scale_x, anim.from_scale_y, 1.0);
     emit_pair_call(&mut code, "__anim", "SetScaleTo", anim.to_scale_x, anim.to_scale_y, 1.0);
     emit_num_call(&mut code, "__anim", "SetDegrees", anim.degrees);
+    emit_num_call(&mut code, "__anim", "SetRadians", anim.radians);
     emit_str_call(&mut code, "__anim", "SetChildKey", anim.child_key.as_deref());
     emit_str_call(&mut code, "__anim", "SetTargetName", anim.target.as_deref());
     emit_str_call(&mut code, "__anim", "SetTargetKey", anim.target_key.as_deref());
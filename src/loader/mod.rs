@@ -1,13 +1,14 @@
 //! Addon loader - loads addons from TOC files.
 
 mod addon;
-pub(crate) mod bytecode_cache;
+pub mod bytecode_cache;
 mod button;
 mod error;
 pub(crate) mod helpers;
 pub(crate) mod helpers_anim;
 mod lua_file;
 pub(crate) mod precompiled;
+pub mod profile;
 mod xml_file;
 mod xml_fontstring;
 mod xml_frame;
@@ -17,7 +18,7 @@ mod xml_texture;
 
 use crate::lua_api::LoaderEnv;
 use crate::saved_variables::SavedVariablesManager;
-use crate::toc::TocFile;
+use crate::toc::{TocFile, TocFlavor};
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::time::Duration;
@@ -26,37 +27,46 @@ pub use error::LoadError;
 pub use xml_frame::create_frame_from_xml;
 
 /// Find the TOC file for an addon directory.
-/// Prefers Mainline variant, then exact name match, then any non-Classic TOC.
+/// Prefers the simulated client's flavor (Mainline unless `WOW_SIM_INTERFACE`
+/// says otherwise), then exact name match, then any TOC not meant for another flavor.
 pub fn find_toc_file(addon_dir: &Path) -> Option<PathBuf> {
+    find_toc_file_for_flavor(addon_dir, TocFlavor::current())
+}
+
+/// Find the TOC file for `flavor` in an addon directory.
+///
+/// Prefers `<Name>_<Suffix>.toc` / `<Name>-<Suffix>.toc` for the flavor's
+/// suffixes, then `<Name>.toc`, then the first (by file name) TOC that is
+/// not suffixed for a different flavor. Deterministic regardless of
+/// directory iteration order.
+pub fn find_toc_file_for_flavor(addon_dir: &Path, flavor: TocFlavor) -> Option<PathBuf> {
     let addon_name = addon_dir.file_name()?.to_str()?;
-    let toc_variants = [
-        format!("{}_Mainline.toc", addon_name),
-        format!("{}.toc", addon_name),
-    ];
-    for variant in &toc_variants {
-        let toc_path = addon_dir.join(variant);
-        if toc_path.exists() {
-            return Some(toc_path);
-        }
-    }
-    // Fallback: find any .toc file (skip Classic/TBC/etc.)
-    if let Ok(entries) = std::fs::read_dir(addon_dir) {
-        for entry in entries.flatten() {
-            let path = entry.path();
-            if path.extension().map(|e| e == "toc").unwrap_or(false) {
-                let name = path.file_name().unwrap().to_str().unwrap();
-                if !name.contains("_Cata")
-                    && !name.contains("_Wrath")
-                    && !name.contains("_TBC")
-                    && !name.contains("_Vanilla")
-                    && !name.contains("_Mists")
-                {
-                    return Some(path);
-                }
+    let mut tocs: Vec<PathBuf> = std::fs::read_dir(addon_dir)
+        .ok()?
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| p.extension().is_some_and(|e| e.eq_ignore_ascii_case("toc")))
+        .collect();
+    tocs.sort();
+    let stem_of = |p: &PathBuf| p.file_stem().and_then(|s| s.to_str()).map(str::to_string);
+
+    for suffix in flavor.suffixes() {
+        for sep in ['_', '-'] {
+            let wanted = format!("{addon_name}{sep}{suffix}");
+            if let Some(p) = tocs.iter().find(|p| stem_of(p).is_some_and(|s| s.eq_ignore_ascii_case(&wanted))) {
+                return Some(p.clone());
             }
         }
     }
-    None
+    if let Some(p) = tocs.iter().find(|p| stem_of(p).is_some_and(|s| s.eq_ignore_ascii_case(addon_name))) {
+        return Some(p.clone());
+    }
+    // Fallback: any TOC not meant for another flavor
+    tocs.into_iter().find(|p| {
+        stem_of(p)
+            .and_then(|s| TocFlavor::from_toc_stem(&s))
+            .is_none_or(|f| f == flavor)
+    })
 }
 
 /// Result of loading an addon.
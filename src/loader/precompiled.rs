@@ -45,14 +45,14 @@ fn compile_fire_onload(lua: &Lua) -> mlua::Result<Function> {
         local frame = _G[...]
         if not frame then return end
         if type(frame.OnLoad_Intrinsic) == "function" then
-            local ok, err = pcall(frame.OnLoad_Intrinsic, frame)
+            local ok, err = xpcall(frame.OnLoad_Intrinsic, __script_traceback, frame)
             if not ok then
                 __report_script_error("[OnLoad_Intrinsic] " .. tostring(err))
             end
         end
         local handler = frame:GetScript("OnLoad")
         if handler then
-            local ok, err = pcall(handler, frame)
+            local ok, err = xpcall(handler, __script_traceback, frame)
             if not ok then
                 local name = frame.GetName and frame:GetName() or "?"
                 __report_script_error("[OnLoad] " .. name .. ": " .. tostring(err))
@@ -68,14 +68,14 @@ fn compile_fire_onshow(lua: &Lua) -> mlua::Result<Function> {
         if frame:IsVisible() then
             local handler = frame:GetScript("OnShow")
             if handler then
-                local ok, err = pcall(handler, frame)
+                local ok, err = xpcall(handler, __script_traceback, frame)
                 if not ok then
                     local name = frame.GetName and frame:GetName() or "?"
                     __report_script_error("[OnShow] " .. name .. ": " .. tostring(err))
                 end
             end
             if type(frame.OnShow_Intrinsic) == "function" then
-                local ok, err = pcall(frame.OnShow_Intrinsic, frame)
+                local ok, err = xpcall(frame.OnShow_Intrinsic, __script_traceback, frame)
                 if not ok then
                     __report_script_error("[OnShow_Intrinsic] " .. tostring(err))
                 end
@@ -0,0 +1,165 @@
+//! Opt-in load profiler (`--profile`).
+//!
+//! While `SimState::load_profile` is set, `load_addon_internal` records the
+//! time of every TOC file and the widgets each addon created, and
+//! `apply_templates_from_registry` records per-template costs. With profiling
+//! off each hook is a single `Option` check.
+
+use crate::lua_api::SimState;
+use crate::widget::{WidgetRegistry, WidgetType};
+use serde::Serialize;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::path::Path;
+use std::time::Duration;
+
+/// Collected load costs, reported by `report()` or serialized as JSON.
+#[derive(Debug, Default, Serialize)]
+pub struct LoadProfile {
+    pub addons: Vec<AddonCost>,
+    pub files: Vec<FileCost>,
+    /// Template name → cost. Times include nested templates applied to
+    /// children, so a wrapper template also carries its children's cost.
+    pub templates: HashMap<String, TemplateCost>,
+}
+
+/// One addon: total load time and the widgets created while it loaded.
+#[derive(Debug, Clone, Serialize)]
+pub struct AddonCost {
+    pub name: String,
+    pub time_ms: f64,
+    pub created: WidgetCounts,
+}
+
+/// One TOC file (XML times include the Lua scripts it pulls in).
+#[derive(Debug, Clone, Serialize)]
+pub struct FileCost {
+    pub addon: String,
+    pub path: String,
+    pub time_ms: f64,
+}
+
+#[derive(Debug, Default, Clone, Serialize)]
+pub struct TemplateCost {
+    pub applications: u32,
+    pub time_ms: f64,
+}
+
+/// Widget counts by kind; everything that is not a texture, line or font
+/// string counts as a frame.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize)]
+pub struct WidgetCounts {
+    pub frames: usize,
+    pub textures: usize,
+    pub font_strings: usize,
+}
+
+impl WidgetCounts {
+    pub fn of(widgets: &WidgetRegistry) -> Self {
+        let mut counts = Self::default();
+        for id in widgets.iter_ids() {
+            match widgets.get(id).map(|f| f.widget_type) {
+                Some(WidgetType::Texture | WidgetType::Line) => counts.textures += 1,
+                Some(WidgetType::FontString) => counts.font_strings += 1,
+                Some(_) => counts.frames += 1,
+                None => {}
+            }
+        }
+        counts
+    }
+
+    /// Widgets added since `before`.
+    pub fn since(self, before: Self) -> Self {
+        Self {
+            frames: self.frames.saturating_sub(before.frames),
+            textures: self.textures.saturating_sub(before.textures),
+            font_strings: self.font_strings.saturating_sub(before.font_strings),
+        }
+    }
+}
+
+impl LoadProfile {
+    pub fn record_file(&mut self, addon: &str, path: &Path, elapsed: Duration) {
+        self.files.push(FileCost {
+            addon: addon.to_string(),
+            path: path.display().to_string(),
+            time_ms: ms(elapsed),
+        });
+    }
+
+    pub fn record_template(&mut self, name: &str, elapsed: Duration) {
+        let cost = self.templates.entry(name.to_string()).or_default();
+        cost.applications += 1;
+        cost.time_ms += ms(elapsed);
+    }
+
+    pub fn record_addon(&mut self, name: &str, elapsed: Duration, created: WidgetCounts) {
+        self.addons.push(AddonCost { name: name.to_string(), time_ms: ms(elapsed), created });
+    }
+
+    /// Text report of the `top` slowest addons, files and templates.
+    pub fn report(&self, top: usize) -> String {
+        let mut out = String::from("=== Load profile ===\n");
+
+        let mut addons: Vec<&AddonCost> = self.addons.iter().collect();
+        addons.sort_by(|a, b| b.time_ms.total_cmp(&a.time_ms));
+        out.push_str("Slowest addons:\n");
+        for a in addons.iter().take(top) {
+            let c = a.created;
+            let _ = writeln!(
+                out,
+                "  {:>9.1}ms  {}  ({} frames, {} textures, {} font strings)",
+                a.time_ms, a.name, c.frames, c.textures, c.font_strings
+            );
+        }
+
+        let mut files: Vec<&FileCost> = self.files.iter().collect();
+        files.sort_by(|a, b| b.time_ms.total_cmp(&a.time_ms));
+        out.push_str("Slowest files:\n");
+        for f in files.iter().take(top) {
+            let _ = writeln!(out, "  {:>9.1}ms  {}  [{}]", f.time_ms, f.path, f.addon);
+        }
+
+        let mut templates: Vec<(&String, &TemplateCost)> = self.templates.iter().collect();
+        templates.sort_by(|a, b| b.1.time_ms.total_cmp(&a.1.time_ms).then(a.0.cmp(b.0)));
+        out.push_str("Most expensive templates (including nested templates):\n");
+        for (name, t) in templates.iter().take(top) {
+            let _ = writeln!(out, "  {:>9.1}ms  x{:<5} {}", t.time_ms, t.applications, name);
+        }
+        out
+    }
+}
+
+/// Run `f` on the profile if profiling is enabled.
+pub(crate) fn with_profile(state: &RefCell<SimState>, f: impl FnOnce(&mut LoadProfile)) {
+    if let Some(profile) = state.borrow_mut().load_profile.as_mut() {
+        f(profile);
+    }
+}
+
+fn ms(d: Duration) -> f64 {
+    d.as_secs_f64() * 1000.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn report_sorts_slowest_first_and_truncates() {
+        let mut profile = LoadProfile::default();
+        let counts = WidgetCounts { frames: 3, textures: 5, font_strings: 1 };
+        profile.record_addon("Fast", Duration::from_millis(2), counts);
+        profile.record_addon("Slow", Duration::from_millis(40), counts);
+        profile.record_file("Slow", Path::new("Slow/Options.xml"), Duration::from_millis(30));
+        profile.record_template("RowTemplate", Duration::from_millis(4));
+        profile.record_template("RowTemplate", Duration::from_millis(6));
+
+        let report = profile.report(1);
+        assert!(report.contains("Slow  (3 frames, 5 textures, 1 font strings)"), "{report}");
+        assert!(!report.contains("Fast"), "{report}");
+        assert!(report.contains("Slow/Options.xml"), "{report}");
+        assert!(report.contains("10.0ms  x2     RowTemplate"), "{report}");
+    }
+}
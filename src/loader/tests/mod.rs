@@ -48,11 +48,7 @@ fn load_test_xml(dir_suffix: &str, xml_content: &str) -> TestCtx {
     std::fs::write(&xml_path, xml_content).unwrap();
 
     let addon_table = env.create_addon_table().unwrap();
-    let ctx = AddonContext {
-        name: "TestAddon",
-        table: addon_table,
-        addon_root: &temp_dir,
-    };
+    let ctx = AddonContext::new("TestAddon", addon_table, &temp_dir);
     load_xml_file(&env.loader_env(), &xml_path, &ctx, &mut LoadTiming::default()).unwrap();
 
     TestCtx { env, temp_dir }
@@ -67,11 +63,7 @@ fn load_test_lua(dir_suffix: &str, lua_content: &str) -> (TestCtx, mlua::Table)
     std::fs::write(&lua_path, lua_content).unwrap();
 
     let addon_table = env.create_addon_table().unwrap();
-    let ctx = AddonContext {
-        name: "TestAddon",
-        table: addon_table.clone(),
-        addon_root: &temp_dir,
-    };
+    let ctx = AddonContext::new("TestAddon", addon_table.clone(), &temp_dir);
     load_lua_file(&env.loader_env(), &lua_path, &ctx, &mut LoadTiming::default()).unwrap();
 
     (TestCtx { env, temp_dir }, addon_table)
@@ -86,11 +78,7 @@ fn test_load_lua_file() {
     std::fs::write(&lua_path, "TEST_VAR = 42").unwrap();
 
     let addon_table = env.create_addon_table().unwrap();
-    let ctx = AddonContext {
-        name: "TestAddon",
-        table: addon_table,
-        addon_root: &temp_dir,
-    };
+    let ctx = AddonContext::new("TestAddon", addon_table, &temp_dir);
     load_lua_file(&env.loader_env(), &lua_path, &ctx, &mut LoadTiming::default()).unwrap();
 
     let value: i32 = env.eval("return TEST_VAR").unwrap();
@@ -170,7 +158,7 @@ fn test_xml_scripts_function_attribute() {
     </Ui>"#).unwrap();
 
     let addon_table = env.create_addon_table().unwrap();
-    let ctx = AddonContext { name: "TestAddon", table: addon_table, addon_root: &temp_dir };
+    let ctx = AddonContext::new("TestAddon", addon_table, &temp_dir);
     load_xml_file(&env.loader_env(), &xml_path, &ctx, &mut LoadTiming::default()).unwrap();
 
     let handler_set: bool = env.eval("return FuncTestFrame:GetScript('OnLoad') == MyGlobalOnLoad").unwrap();
@@ -191,7 +179,7 @@ fn test_xml_scripts_method_attribute() {
     </Ui>"#).unwrap();
 
     let addon_table = env.create_addon_table().unwrap();
-    let ctx = AddonContext { name: "TestAddon", table: addon_table, addon_root: &temp_dir };
+    let ctx = AddonContext::new("TestAddon", addon_table, &temp_dir);
     load_xml_file(&env.loader_env(), &xml_path, &ctx, &mut LoadTiming::default()).unwrap();
 
     env.exec(r#"
@@ -223,7 +211,7 @@ fn test_xml_keyvalues() {
     </Ui>"#).unwrap();
 
     let addon_table = env.create_addon_table().unwrap();
-    let ctx = AddonContext { name: "TestAddon", table: addon_table, addon_root: &temp_dir };
+    let ctx = AddonContext::new("TestAddon", addon_table, &temp_dir);
     load_xml_file(&env.loader_env(), &xml_path, &ctx, &mut LoadTiming::default()).unwrap();
 
     assert_eq!(env.eval::<String>("return KeyValueFrame.myString").unwrap(), "hello");
@@ -248,7 +236,7 @@ fn test_xml_keyvalue_global_type_resolves_global_string() {
     </Ui>"#).unwrap();
 
     let addon_table = env.create_addon_table().unwrap();
-    let ctx = AddonContext { name: "TestAddon", table: addon_table, addon_root: &temp_dir };
+    let ctx = AddonContext::new("TestAddon", addon_table, &temp_dir);
     load_xml_file(&env.loader_env(), &xml_path, &ctx, &mut LoadTiming::default()).unwrap();
 
     let val: String = env.eval("return KeyValueGlobalFrame.instructionText").unwrap();
@@ -274,7 +262,7 @@ fn test_xml_anchors_with_offset() {
     </Ui>"#).unwrap();
 
     let addon_table = env.create_addon_table().unwrap();
-    let ctx = AddonContext { name: "TestAddon", table: addon_table, addon_root: &temp_dir };
+    let ctx = AddonContext::new("TestAddon", addon_table, &temp_dir);
     load_xml_file(&env.loader_env(), &xml_path, &ctx, &mut LoadTiming::default()).unwrap();
 
     let point_info: String = env.eval(r#"
@@ -299,7 +287,7 @@ fn test_xml_size_with_absdimension() {
     </Ui>"#).unwrap();
 
     let addon_table = env.create_addon_table().unwrap();
-    let ctx = AddonContext { name: "TestAddon", table: addon_table, addon_root: &temp_dir };
+    let ctx = AddonContext::new("TestAddon", addon_table, &temp_dir);
     load_xml_file(&env.loader_env(), &xml_path, &ctx, &mut LoadTiming::default()).unwrap();
 
     assert_eq!(env.eval::<f64>("return AbsSizeFrame:GetWidth()").unwrap(), 150.0);
@@ -364,7 +352,7 @@ fn test_xml_texture_color() {
     </Ui>"#).unwrap();
 
     let addon_table = env.create_addon_table().unwrap();
-    let ctx = AddonContext { name: "TestAddon", table: addon_table, addon_root: &temp_dir };
+    let ctx = AddonContext::new("TestAddon", addon_table, &temp_dir);
     load_xml_file(&env.loader_env(), &xml_path, &ctx, &mut LoadTiming::default()).unwrap();
 
     assert!(env.eval::<bool>("return ColorTexFrame.bg ~= nil").unwrap(), "bg should exist");
@@ -386,7 +374,7 @@ fn test_xml_virtual_frames_skipped() {
     </Ui>"#).unwrap();
 
     let addon_table = env.create_addon_table().unwrap();
-    let ctx = AddonContext { name: "TestAddon", table: addon_table, addon_root: &temp_dir };
+    let ctx = AddonContext::new("TestAddon", addon_table, &temp_dir);
     load_xml_file(&env.loader_env(), &xml_path, &ctx, &mut LoadTiming::default()).unwrap();
 
     assert!(!env.eval::<bool>("return VirtualTemplate ~= nil").unwrap(), "VirtualTemplate should NOT exist");
@@ -417,6 +405,84 @@ fn test_xml_multiple_anchors() {
         local point, _, relPoint, x, y = MultiAnchorFrame:GetPoint(2)
         return string.format("%s,%s,%d,%d", point, relPoint, x, y)
     "#, "BOTTOMRIGHT,BOTTOMRIGHT,-10,10");
+
+    // Size comes from the inset span against the 1600x1200 UIParent.
+    let (w, h): (f64, f64) = t.env.eval("return MultiAnchorFrame:GetWidth(), MultiAnchorFrame:GetHeight()").unwrap();
+    assert_eq!((w, h), (1580.0, 1180.0));
+    let rect: (f64, f64, f64, f64) = t.env.eval("return MultiAnchorFrame:GetRect()").unwrap();
+    assert_eq!(rect, (10.0, 10.0, 1580.0, 1180.0));
+}
+
+#[test]
+fn test_xml_relative_key_sibling_anchor() {
+    let t = load_test_xml(
+        "test-relkey-sibling",
+        r#"<Ui>
+            <Frame name="RelKeyContainer" parent="UIParent">
+                <Size x="400" y="300"/>
+                <Anchors><Anchor point="TOPLEFT" x="100" y="-50"/></Anchors>
+                <Frames>
+                    <Frame parentKey="Header">
+                        <Size x="400" y="40"/>
+                        <Anchors><Anchor point="TOPLEFT"/></Anchors>
+                    </Frame>
+                    <Frame name="RelKeyBody">
+                        <Size x="400" y="100"/>
+                        <Anchors>
+                            <Anchor point="TOPLEFT" relativeKey="$parent.Header" relativePoint="BOTTOMLEFT" y="-5"/>
+                        </Anchors>
+                    </Frame>
+                </Frames>
+            </Frame>
+        </Ui>"#,
+    );
+
+    // Container top = 1200 - 50; Header bottom = top - 40; Body top = Header bottom - 5.
+    let (left, top): (f64, f64) = t.env.eval("return RelKeyBody:GetLeft(), RelKeyBody:GetTop()").unwrap();
+    assert_eq!(left, 100.0);
+    assert_eq!(top, 1105.0);
+    let (_, bottom, _, height): (f64, f64, f64, f64) = t.env.eval("return RelKeyBody:GetRect()").unwrap();
+    assert_eq!(height, 100.0);
+    assert_eq!(bottom, 1005.0);
+}
+
+#[test]
+fn test_xml_relative_key_declared_later() {
+    let t = load_test_xml(
+        "test-relkey-deferred",
+        r#"<Ui>
+            <Frame name="RelKeyLateContainer" parent="UIParent">
+                <Size x="400" y="300"/>
+                <Anchors><Anchor point="TOPLEFT" x="100" y="-50"/></Anchors>
+                <Frames>
+                    <Frame name="RelKeyLateBody">
+                        <Size x="400" y="100"/>
+                        <Anchors>
+                            <Anchor point="TOPLEFT" relativeKey="$parent.Header" relativePoint="BOTTOMLEFT" y="-5"/>
+                        </Anchors>
+                    </Frame>
+                    <Frame parentKey="Header">
+                        <Size x="400" y="40"/>
+                        <Anchors><Anchor point="TOPLEFT"/></Anchors>
+                    </Frame>
+                </Frames>
+            </Frame>
+        </Ui>"#,
+    );
+
+    // The anchor is bound once Header exists, not left on the parent rect.
+    t.assert_lua_true(
+        "return select(2, RelKeyLateBody:GetPoint(1)) == RelKeyLateContainer.Header",
+        "deferred relativeKey anchor should bind to Header",
+    );
+    let top: f64 = t.env.eval("return RelKeyLateBody:GetTop()").unwrap();
+    assert_eq!(top, 1105.0);
+
+    t.env.state().borrow_mut().ensure_layout_rects();
+    let state = t.env.state().borrow();
+    let id = state.widgets.get_id_by_name("RelKeyLateBody").unwrap();
+    let rect = state.widgets.get(id).unwrap().layout_rect.unwrap();
+    assert_eq!(rect.y, 95.0, "layout_rect should use the Header's bottom edge");
 }
 
 #[test]
@@ -477,11 +543,7 @@ fn load_test_lua_files(
     std::fs::create_dir_all(&temp_dir).unwrap();
 
     let addon_table = env.create_addon_table().unwrap();
-    let ctx = AddonContext {
-        name: addon_name,
-        table: addon_table.clone(),
-        addon_root: &temp_dir,
-    };
+    let ctx = AddonContext::new(addon_name, addon_table.clone(), &temp_dir);
 
     for (filename, content) in files {
         let path = temp_dir.join(filename);
@@ -697,4 +759,75 @@ fn test_xml_hit_rect_insets() {
         "XML HitRectInsets should be applied: got {:?}", insets);
 }
 
+/// Write `files` (relative path, content) under a fresh temp dir and load the first as XML.
+fn load_xml_tree(dir_suffix: &str, files: &[(&str, &str)]) -> (TestCtx, usize, Vec<String>) {
+    let env = WowLuaEnv::new().unwrap();
+    let temp_dir = std::env::temp_dir().join(format!("wow-sim-{}", dir_suffix));
+    let _ = std::fs::remove_dir_all(&temp_dir);
+    for (rel, content) in files {
+        let path = temp_dir.join(rel);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, content).unwrap();
+    }
+
+    let addon_table = env.create_addon_table().unwrap();
+    let ctx = AddonContext::new("TestAddon", addon_table, &temp_dir);
+    let count = load_xml_file(&env.loader_env(), &temp_dir.join(files[0].0), &ctx, &mut LoadTiming::default()).unwrap();
+    let warnings = ctx.warnings.take();
+    drop(ctx);
+    (TestCtx { env, temp_dir }, count, warnings)
+}
+
+#[test]
+fn test_xml_nested_includes_resolve_relative_paths() {
+    let (t, count, warnings) = load_xml_tree(
+        "test-nested-includes",
+        &[
+            ("a.xml", r#"<Ui>
+                <Include file="Sub\B.xml"/>
+                <Script>LOAD_ORDER = (LOAD_ORDER or "") .. "a"</Script>
+            </Ui>"#),
+            ("sub/b.xml", r#"<Ui><Script file="..\Lib\C.lua"/></Ui>"#),
+            ("lib/c.lua", r#"LOAD_ORDER = (LOAD_ORDER or "") .. "c""#),
+        ],
+    );
+
+    assert!(warnings.is_empty(), "unexpected warnings: {warnings:?}");
+    assert_eq!(count, 2);
+    t.assert_lua_str("return LOAD_ORDER", "ca");
+}
+
+#[test]
+fn test_xml_include_cycle_is_reported_not_followed() {
+    let (t, _, warnings) = load_xml_tree(
+        "test-include-cycle",
+        &[
+            ("a.xml", r#"<Ui><Include file="b.xml"/></Ui>"#),
+            ("b.xml", r#"<Ui>
+                <Include file="a.xml"/>
+                <Script>B_LOADS = (B_LOADS or 0) + 1</Script>
+            </Ui>"#),
+        ],
+    );
+
+    assert_eq!(warnings.len(), 1);
+    assert!(warnings[0].contains("include cycle"), "{}", warnings[0]);
+    assert_eq!(t.env.eval::<i32>("return B_LOADS").unwrap(), 1);
+}
+
+#[test]
+fn test_xml_missing_include_does_not_drop_the_rest() {
+    let (t, _, warnings) = load_xml_tree(
+        "test-missing-include",
+        &[("a.xml", r#"<Ui>
+            <Script file="Missing.lua"/>
+            <Frame name="AfterMissingInclude" parent="UIParent"/>
+        </Ui>"#)],
+    );
+
+    assert_eq!(warnings.len(), 1, "{warnings:?}");
+    assert!(warnings[0].contains("Missing.lua"));
+    t.assert_lua_true("return AfterMissingInclude ~= nil", "frame after the missing script should load");
+}
+
 mod global_frame_access;
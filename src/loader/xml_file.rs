@@ -12,13 +12,45 @@ use super::lua_file::load_lua_file;
 use super::xml_frame::create_frame_from_xml;
 use super::LoadTiming;
 
+/// Deepest `<Include>` nesting followed before giving up on a branch.
+const MAX_INCLUDE_DEPTH: usize = 32;
+
 /// Load an XML file, processing its elements.
 /// Returns the number of Lua files loaded from Script elements.
+///
+/// Fails without loading anything when the file is already being loaded
+/// further up the include chain (a cycle) or the chain is too deep.
 pub fn load_xml_file(
     env: &LoaderEnv<'_>,
     path: &Path,
     ctx: &AddonContext,
     timing: &mut LoadTiming,
+) -> Result<usize, LoadError> {
+    let key = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+    {
+        let stack = ctx.include_stack.borrow();
+        if stack.contains(&key) {
+            let chain: Vec<String> = stack.iter().chain([&key]).map(|p| p.display().to_string()).collect();
+            return Err(LoadError::Include(format!("include cycle: {}", chain.join(" -> "))));
+        }
+        if stack.len() >= MAX_INCLUDE_DEPTH {
+            return Err(LoadError::Include(format!(
+                "includes nested deeper than {MAX_INCLUDE_DEPTH} files at {}",
+                path.display()
+            )));
+        }
+    }
+    ctx.include_stack.borrow_mut().push(key);
+    let result = load_xml_elements(env, path, ctx, timing);
+    ctx.include_stack.borrow_mut().pop();
+    result
+}
+
+fn load_xml_elements(
+    env: &LoaderEnv<'_>,
+    path: &Path,
+    ctx: &AddonContext,
+    timing: &mut LoadTiming,
 ) -> Result<usize, LoadError> {
     let xml_start = Instant::now();
     let ui = parse_xml_file(path)?;
@@ -95,8 +127,7 @@ fn process_script(
 ) -> Result<usize, LoadError> {
     if let Some(file) = &s.file {
         let script_path = resolve_path_with_fallback(xml_dir, ctx.addon_root, file);
-        load_lua_file(env, &script_path, ctx, timing)?;
-        Ok(1)
+        Ok(nested_load(ctx, &script_path, load_lua_file(env, &script_path, ctx, timing).map(|()| 1)))
     } else if let Some(inline) = &s.inline {
         let table_clone = ctx.table.clone();
         let lua_start = Instant::now();
@@ -118,12 +149,21 @@ fn process_include(
     timing: &mut LoadTiming,
 ) -> Result<usize, LoadError> {
     let include_path = resolve_path_with_fallback(xml_dir, ctx.addon_root, &i.file);
-    if i.file.ends_with(".lua") {
-        load_lua_file(env, &include_path, ctx, timing)?;
-        Ok(1)
+    let result = if i.file.to_lowercase().ends_with(".lua") {
+        load_lua_file(env, &include_path, ctx, timing).map(|()| 1)
     } else {
         load_xml_file(env, &include_path, ctx, timing)
-    }
+    };
+    Ok(nested_load(ctx, &include_path, result))
+}
+
+/// Record a failed nested load as a warning so the including file keeps
+/// loading its remaining elements, as the client does.
+fn nested_load(ctx: &AddonContext, path: &Path, result: Result<usize, LoadError>) -> usize {
+    result.unwrap_or_else(|e| {
+        ctx.warnings.borrow_mut().push(format!("{}: {}", path.display(), e));
+        0
+    })
 }
 
 /// Extract the FrameXml data, widget type, and optional intrinsic name from an XmlElement.
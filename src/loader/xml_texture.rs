@@ -93,6 +93,10 @@ fn generate_texture_visual_code(texture: &crate::xml::TextureXml) -> String {
         code.push_str("\n        tex:SetVertTile(true)\n        ");
     }
 
+    if let Some(mask) = &texture.mask {
+        code.push_str(&format!("\n        tex:SetMask(\"{}\")\n        ", escape_lua_string(mask)));
+    }
+
     if texture.set_all_points == Some(true) {
         code.push_str("\n        tex:SetAllPoints(true)\n        ");
     }
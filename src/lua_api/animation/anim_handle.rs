@@ -6,7 +6,7 @@ use mlua::{MultiValue, UserData, UserDataMethods, Value};
 use std::cell::RefCell;
 use std::rc::Rc;
 
-use super::{extract_number, AnimGroupHandle, AnimationType, Smoothing};
+use super::{extract_number, resolve_anim_target, AnimGroupHandle, AnimationType, Smoothing};
 
 /// Userdata handle for an individual Animation.
 #[derive(Clone)]
@@ -222,7 +222,7 @@ impl AnimHandle {
         });
     }
 
-    /// Register rotation methods: SetDegrees, SetOrigin.
+    /// Register rotation methods: SetDegrees, SetRadians, SetOrigin.
     fn add_rotation_methods<M: UserDataMethods<Self>>(methods: &mut M) {
         methods.add_method("SetDegrees", |_, this, degrees: f64| {
             let mut state = this.state.borrow_mut();
@@ -233,6 +233,15 @@ impl AnimHandle {
             Ok(())
         });
 
+        methods.add_method("SetRadians", |_, this, radians: f64| {
+            let mut state = this.state.borrow_mut();
+            if let Some(group) = state.animation_groups.get_mut(&this.group_id)
+                && let Some(anim) = group.animations.get_mut(this.anim_index) {
+                    anim.degrees = radians.to_degrees();
+                }
+            Ok(())
+        });
+
         methods.add_method("SetOrigin", |_, _this, _args: MultiValue| {
             Ok(()) // Store only, no visual effect
         });
@@ -377,11 +386,7 @@ impl AnimHandle {
             let owner_id = group.owner_frame_id;
             let child_key = group.animations.get(this.anim_index)
                 .and_then(|a| a.child_key.clone());
-            let target_id = match &child_key {
-                Some(key) => state.widgets.get(owner_id)
-                    .and_then(|owner| owner.children_keys.get(key.as_str()).copied()),
-                None => Some(owner_id),
-            };
+            let target_id = resolve_anim_target(&state.widgets, owner_id, child_key.as_deref());
             let Some(id) = target_id else { return Ok(Value::Nil) };
             Ok(frame_lud(id))
         });
@@ -394,7 +399,15 @@ impl AnimHandle {
                 }
             Ok(())
         });
-        methods.add_method("SetTargetKey", |_, _this, _key: String| Ok(()));
+        // targetKey paths resolve like childKey ("$parent.Icon" etc.).
+        methods.add_method("SetTargetKey", |_, this, key: String| {
+            let mut state = this.state.borrow_mut();
+            if let Some(group) = state.animation_groups.get_mut(&this.group_id)
+                && let Some(anim) = group.animations.get_mut(this.anim_index) {
+                    anim.child_key = Some(key);
+                }
+            Ok(())
+        });
         methods.add_method("SetTargetName", |_, _this, _name: String| Ok(()));
         methods.add_method("SetTargetParent", |_, _this, ()| Ok(()));
     }
@@ -6,18 +6,9 @@ use mlua::{Lua, MultiValue, UserData, UserDataMethods, Value};
 use std::cell::RefCell;
 use std::rc::Rc;
 
-use super::{AnimHandle, AnimState, AnimationType, LoopType};
+use super::{resolve_anim_target, AnimGroupState, AnimHandle, AnimState, AnimationType, LoopType};
 use super::tick::apply_flipbook_for_group;
 
-/// Resolve a child_key to a frame ID via the owner's children_keys.
-fn resolve_child(state: &SimState, owner_id: u64, child_key: &Option<String>) -> Option<u64> {
-    match child_key {
-        Some(key) => state.widgets.get(owner_id)
-            .and_then(|owner| owner.children_keys.get(key.as_str()).copied()),
-        None => Some(owner_id),
-    }
-}
-
 /// Start (or restart) playback: reset elapsed, save pre-animation alphas.
 fn start_group_playback(state: &mut SimState, group_id: u64, reverse: bool) {
     // Collect alpha targets to save before mutating the group.
@@ -26,7 +17,7 @@ fn start_group_playback(state: &mut SimState, group_id: u64, reverse: bool) {
             let owner_id = group.owner_frame_id;
             group.animations.iter()
                 .filter(|a| a.anim_type == AnimationType::Alpha)
-                .filter_map(|a| resolve_child(state, owner_id, &a.child_key))
+                .filter_map(|a| resolve_anim_target(&state.widgets, owner_id, a.child_key.as_deref()))
                 .collect::<std::collections::HashSet<_>>()
                 .into_iter()
                 .filter_map(|id| state.widgets.get(id).map(|f| (id, f.alpha)))
@@ -50,38 +41,73 @@ fn start_group_playback(state: &mut SimState, group_id: u64, reverse: bool) {
     }
 }
 
-/// Stop a group: restore pre-animation alphas (unless setToFinalAlpha),
-/// clear translation offsets, mark finished.
+/// Alpha each Alpha-animation target ends on: the last animation (by order)
+/// touching it, read from its end in the group's play direction.
+fn final_alphas(state: &SimState, group: &AnimGroupState) -> Vec<(u64, f32)> {
+    let mut anims: Vec<&AnimState> = group.animations.iter()
+        .filter(|a| a.anim_type == AnimationType::Alpha)
+        .collect();
+    anims.sort_by_key(|a| a.order);
+    if group.reverse {
+        anims.reverse();
+    }
+    let mut finals: Vec<(u64, f32)> = Vec::new();
+    for anim in anims {
+        let Some(id) = resolve_anim_target(&state.widgets, group.owner_frame_id, anim.child_key.as_deref()) else {
+            continue;
+        };
+        let alpha = (if group.reverse { anim.from_alpha } else { anim.to_alpha }) as f32;
+        match finals.iter_mut().find(|(t, _)| *t == id) {
+            Some(entry) => entry.1 = alpha,
+            None => finals.push((id, alpha)),
+        }
+    }
+    finals
+}
+
+/// Stop a group: snap alpha targets to their final alpha when setToFinalAlpha
+/// is set, otherwise restore the pre-animation alphas; clear translation
+/// offsets, mark finished.
 pub(super) fn stop_group(state: &mut SimState, group_id: u64) {
     // Collect restoration data before mutating.
-    let restore: Option<(bool, Vec<(u64, f32)>, Vec<u64>)> =
+    let restore: Option<(Vec<(u64, f32)>, Vec<u64>)> =
         state.animation_groups.get(&group_id).map(|group| {
-            let keep_alpha = group.set_to_final_alpha;
-            let saved = group.saved_alphas.iter().map(|(&id, &a)| (id, a)).collect();
+            let saved = if group.set_to_final_alpha {
+                final_alphas(state, group)
+            } else {
+                group.saved_alphas.iter().map(|(&id, &a)| (id, a)).collect()
+            };
             let owner_id = group.owner_frame_id;
             let translation_targets: Vec<u64> = group.animations.iter()
-                .filter(|a| a.anim_type == AnimationType::Translation)
-                .filter_map(|a| resolve_child(state, owner_id, &a.child_key))
+                .filter(|a| matches!(a.anim_type, AnimationType::Translation | AnimationType::Scale))
+                .filter_map(|a| resolve_anim_target(&state.widgets, owner_id, a.child_key.as_deref()))
                 .collect();
-            (keep_alpha, saved, translation_targets)
+            (saved, translation_targets)
         });
 
-    if let Some((keep_alpha, saved_alphas, translation_targets)) = restore {
-        // Restore alphas if not keeping final values
-        if !keep_alpha {
-            for (id, alpha) in &saved_alphas {
-                if let Some(frame) = state.widgets.get_mut_visual(*id) {
-                    frame.alpha = *alpha;
-                }
+    if let Some((alphas, translation_targets)) = restore {
+        for (id, alpha) in &alphas {
+            if let Some(frame) = state.widgets.get_mut_visual(*id) {
+                frame.alpha = *alpha;
             }
+            let parent_eff = state.widgets.get(*id)
+                .and_then(|f| f.parent_id)
+                .and_then(|pid| state.widgets.get(pid))
+                .map(|p| p.effective_alpha)
+                .unwrap_or(1.0);
+            state.widgets.propagate_effective_alpha(*id, parent_eff);
         }
-        // Always clear translation offsets (they don't persist)
+        // Always clear translation offsets and scale (they don't persist)
         for id in &translation_targets {
-            let had_offset = state.widgets.get(*id)
-                .is_some_and(|f| f.anim_offset_x != 0.0 || f.anim_offset_y != 0.0);
+            let had_offset = state.widgets.get(*id).is_some_and(|f| {
+                f.anim_offset_x != 0.0 || f.anim_offset_y != 0.0
+                    || f.anim_scale_x != 1.0 || f.anim_scale_y != 1.0
+            });
             if let Some(frame) = state.widgets.get_mut_visual(*id) {
                 frame.anim_offset_x = 0.0;
                 frame.anim_offset_y = 0.0;
+                frame.anim_scale_x = 1.0;
+                frame.anim_scale_y = 1.0;
             }
             if had_offset {
                 state.invalidate_layout(*id);
@@ -166,14 +192,12 @@ impl AnimGroupHandle {
         methods.add_method("Finish", |_, this, ()| {
             let mut state = this.state.borrow_mut();
             if let Some(group) = state.animation_groups.get_mut(&this.group_id) {
-                group.playing = false;
-                group.paused = false;
-                group.finished = true;
                 for anim in &mut group.animations {
                     anim.elapsed = anim.total_time();
                 }
                 group.elapsed = group.total_duration();
             }
+            stop_group(&mut state, this.group_id);
             Ok(())
         });
     }
@@ -20,6 +20,34 @@ pub(crate) fn extract_number(args: &[Value], index: usize) -> Option<f64> {
     })
 }
 
+/// Resolve an animation's childKey/targetKey to the frame it drives.
+///
+/// `None` targets the owner frame. Keys are parentKey paths relative to the
+/// owner: `"Icon"`, `"Border.Glow"`, or `$parent`-relative forms such as
+/// `"$parent.Icon"` / `"$parent.$parent.Title"`, where the first `$parent`
+/// is the owner itself (the animation group's parent) and each further one
+/// walks up a level.
+pub(crate) fn resolve_anim_target(
+    widgets: &crate::widget::WidgetRegistry,
+    owner_id: u64,
+    key: Option<&str>,
+) -> Option<u64> {
+    let Some(key) = key else { return Some(owner_id) };
+    let mut segments = key.split('.').peekable();
+    if segments.peek() == Some(&"$parent") {
+        segments.next();
+    }
+    let mut current = owner_id;
+    for segment in segments {
+        let frame = widgets.get(current)?;
+        current = match segment {
+            "$parent" => frame.parent_id?,
+            key => frame.children_keys.get(key).copied()?,
+        };
+    }
+    Some(current)
+}
+
 /// Animation type (Alpha, Translation, etc.)
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum AnimationType {
@@ -264,12 +292,13 @@ impl AnimGroupState {
         }
     }
 
-    /// Whether any child animation produces visual effects (alpha, translation, flipbook).
+    /// Whether any child animation produces visual effects (alpha, translation, scale, flipbook).
     /// Groups containing only generic `Animation` (timers) don't modify frame state.
     pub fn has_visual_effects(&self) -> bool {
         self.animations.iter().any(|a| matches!(
             a.anim_type,
-            AnimationType::Alpha | AnimationType::Translation | AnimationType::FlipBook
+            AnimationType::Alpha | AnimationType::Translation | AnimationType::Scale
+                | AnimationType::FlipBook
         ))
     }
 
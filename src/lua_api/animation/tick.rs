@@ -6,11 +6,11 @@ use std::cell::RefCell;
 use std::collections::HashMap;
 use std::rc::Rc;
 
-use super::{AnimGroupHandle, AnimGroupState, AnimationType, LoopType};
+use super::{resolve_anim_target, AnimGroupHandle, AnimGroupState, AnimationType, LoopType};
 use super::group_handle::stop_group;
 
 /// Advance all playing animation groups by `delta` seconds.
-/// Applies alpha and translation animations to target frames and fires script callbacks.
+/// Applies alpha, translation and scale animations to target frames and fires script callbacks.
 pub fn tick_animation_groups(state_rc: &Rc<RefCell<SimState>>, lua: &Lua, delta: f64) -> mlua::Result<()> {
     let playing_ids: Vec<u64> = {
         let state = state_rc.borrow();
@@ -30,15 +30,22 @@ pub fn tick_animation_groups(state_rc: &Rc<RefCell<SimState>>, lua: &Lua, delta:
 }
 
 /// Per-target animation effects collected during a tick.
-#[derive(Default)]
 struct TargetEffects {
     alpha: Option<f32>,
     offset_x: f32,
     offset_y: f32,
+    scale_x: f32,
+    scale_y: f32,
     /// FlipBook: (rows, columns, frames, progress) to compute UV sub-region.
     flipbook: Option<(u32, u32, u32, f64)>,
 }
 
+impl Default for TargetEffects {
+    fn default() -> Self {
+        Self { alpha: None, offset_x: 0.0, offset_y: 0.0, scale_x: 1.0, scale_y: 1.0, flipbook: None }
+    }
+}
+
 /// Advance a single animation group: update elapsed times, compute effects, handle finish.
 fn advance_group(
     state_rc: &Rc<RefCell<SimState>>,
@@ -55,15 +62,17 @@ fn advance_group(
 
         group.elapsed += delta * group.speed_multiplier;
 
-        let effects = collect_effects(group);
         let group_finished = group.elapsed >= group.total_duration();
         let owner_id = group.owner_frame_id;
 
+        // Wrap looping groups before sampling so the tick shows where the
+        // new cycle is rather than the end of the old one.
         let scripts_to_fire = if group_finished {
             handle_group_finish(group, lua)
         } else {
             Vec::new()
         };
+        let effects = collect_effects(group);
 
         (group_finished, effects, scripts_to_fire, owner_id)
     };
@@ -139,6 +148,11 @@ fn apply_anim_to_entry(anim: &super::AnimState, progress: f64, entry: &mut Targe
             entry.offset_x = (anim.offset_x * progress) as f32;
             entry.offset_y = (anim.offset_y * progress) as f32;
         }
+        AnimationType::Scale => {
+            let (sx, sy) = anim_scale_at(anim, progress);
+            entry.scale_x = sx as f32;
+            entry.scale_y = sy as f32;
+        }
         AnimationType::FlipBook => {
             entry.flipbook = Some((
                 anim.flip_book_rows,
@@ -147,10 +161,25 @@ fn apply_anim_to_entry(anim: &super::AnimState, progress: f64, entry: &mut Targe
                 progress,
             ));
         }
-        _ => {} // Scale, Rotation, etc. not yet implemented
+        _ => {} // Rotation, etc. not yet implemented
     }
 }
 
+/// Interpolated scale for a Scale animation.
+///
+/// `SetScaleFrom`/`SetScaleTo` take precedence; otherwise `SetScale` scales
+/// from 1.0 to the given factors.
+fn anim_scale_at(anim: &super::AnimState, progress: f64) -> (f64, f64) {
+    let has_from_to = anim.from_scale_x != 1.0 || anim.from_scale_y != 1.0
+        || anim.to_scale_x != 1.0 || anim.to_scale_y != 1.0;
+    let (from, to) = if has_from_to {
+        ((anim.from_scale_x, anim.from_scale_y), (anim.to_scale_x, anim.to_scale_y))
+    } else {
+        ((1.0, 1.0), (anim.scale_x, anim.scale_y))
+    };
+    (from.0 + (to.0 - from.0) * progress, from.1 + (to.1 - from.1) * progress)
+}
+
 /// Resolve child_key to frame ID and apply effects to widget state.
 ///
 /// Uses `get_mut` to avoid setting `render_dirty` every tick.
@@ -165,11 +194,7 @@ fn apply_effects(
     effects: &HashMap<Option<String>, TargetEffects>,
 ) {
     for (child_key, fx) in effects {
-        let target_id = match child_key {
-            Some(key) => state.widgets.get(owner_frame_id)
-                .and_then(|owner| owner.children_keys.get(key.as_str()).copied()),
-            None => Some(owner_frame_id),
-        };
+        let target_id = resolve_anim_target(&state.widgets, owner_frame_id, child_key.as_deref());
         let Some(id) = target_id else { continue };
         let alpha_changed = fx.alpha.is_some_and(|a| {
             state.widgets.get(id).map(|f| f.alpha != a).unwrap_or(false)
@@ -179,9 +204,13 @@ fn apply_effects(
             frame.alpha = alpha;
         }
         let offset_changed = frame.anim_offset_x != fx.offset_x
-            || frame.anim_offset_y != fx.offset_y;
+            || frame.anim_offset_y != fx.offset_y
+            || frame.anim_scale_x != fx.scale_x
+            || frame.anim_scale_y != fx.scale_y;
         frame.anim_offset_x = fx.offset_x;
         frame.anim_offset_y = fx.offset_y;
+        frame.anim_scale_x = fx.scale_x;
+        frame.anim_scale_y = fx.scale_y;
         if let Some((rows, cols, frames, progress)) = fx.flipbook {
             apply_flipbook_uv(frame, rows, cols, frames, progress);
         }
@@ -253,11 +282,7 @@ pub(super) fn apply_flipbook_for_group(state: &mut SimState, group_id: u64) {
     };
 
     for (child_key, rows, cols, frames, progress) in flipbook_data {
-        let target_id = match &child_key {
-            Some(key) => state.widgets.get(owner_id)
-                .and_then(|owner| owner.children_keys.get(key.as_str()).copied()),
-            None => Some(owner_id),
-        };
+        let target_id = resolve_anim_target(&state.widgets, owner_id, child_key.as_deref());
         if let Some(id) = target_id {
             if let Some(frame) = state.widgets.get_mut(id) {
                 apply_flipbook_uv(frame, rows, cols, frames, progress);
@@ -281,17 +306,13 @@ fn handle_group_finish(group: &mut AnimGroupState, lua: &Lua) -> Vec<mlua::Funct
                 }
         }
         LoopType::Repeat => {
-            group.elapsed -= total_dur;
-            for anim in &mut group.animations {
-                anim.elapsed = 0.0;
-            }
+            wrap_elapsed(group, total_dur);
             collect_loop_script(&mut scripts, group, lua);
         }
         LoopType::Bounce => {
-            group.elapsed -= total_dur;
-            group.reverse = !group.reverse;
-            for anim in &mut group.animations {
-                anim.elapsed = 0.0;
+            // Every completed pass flips the direction.
+            if wrap_elapsed(group, total_dur) % 2 == 1 {
+                group.reverse = !group.reverse;
             }
             collect_loop_script(&mut scripts, group, lua);
         }
@@ -300,6 +321,19 @@ fn handle_group_finish(group: &mut AnimGroupState, lua: &Lua) -> Vec<mlua::Funct
     scripts
 }
 
+/// Fold a looping group's elapsed time back into its first cycle.
+///
+/// Returns how many full cycles were skipped, so a long tick over a short
+/// group lands on the right pass.
+fn wrap_elapsed(group: &mut AnimGroupState, total_dur: f64) -> u64 {
+    let cycles = if total_dur > 0.0 { (group.elapsed / total_dur).floor() } else { 1.0 };
+    group.elapsed = if total_dur > 0.0 { group.elapsed - cycles * total_dur } else { 0.0 };
+    for anim in &mut group.animations {
+        anim.elapsed = 0.0;
+    }
+    cycles as u64
+}
+
 /// Collect OnLoop script if present.
 fn collect_loop_script(
     scripts: &mut Vec<mlua::Function>,
@@ -0,0 +1,146 @@
+//! API coverage instrumentation (`--api-coverage`).
+//!
+//! Records Lua lookups that fall through to nil: unknown globals, unknown
+//! `C_*` namespace members and PascalCase frame methods. Each entry keeps a
+//! call count and the first addon seen using it, so the report ranks the
+//! most-wanted missing APIs across an addon set.
+//!
+//! Unknown `C_*` namespaces resolve to a proxy whose members are no-op
+//! functions, so one missing namespace doesn't stop an addon from reaching
+//! the next missing API.
+
+use super::diagnostics::current_addon;
+use super::frame::get_sim_state;
+use mlua::{Lua, Result, Table, Value};
+use std::collections::HashMap;
+
+/// One missing API: how often it was looked up and by whom first.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MissingApi {
+    pub count: u64,
+    pub example_addon: String,
+}
+
+/// Missing API lookups, keyed by name (`Foo`, `C_Foo.Bar`, `Button:Baz`).
+#[derive(Debug, Default)]
+pub struct ApiCoverage {
+    missing: HashMap<String, MissingApi>,
+}
+
+impl ApiCoverage {
+    /// Count one lookup of `name` from `addon`.
+    pub fn record(&mut self, name: &str, addon: &str) {
+        self.missing
+            .entry(name.to_string())
+            .or_insert_with(|| MissingApi { count: 0, example_addon: addon.to_string() })
+            .count += 1;
+    }
+
+    /// Entry for `name`, if it was recorded.
+    pub fn get(&self, name: &str) -> Option<&MissingApi> {
+        self.missing.get(name)
+    }
+
+    /// Entries by descending count, then name.
+    pub fn ranked(&self) -> Vec<(&str, &MissingApi)> {
+        let mut entries: Vec<_> = self.missing.iter().map(|(k, v)| (k.as_str(), v)).collect();
+        entries.sort_by(|a, b| b.1.count.cmp(&a.1.count).then(a.0.cmp(b.0)));
+        entries
+    }
+
+    /// Ranked "most-wanted missing API" table.
+    pub fn report(&self) -> String {
+        let mut out = format!("Missing APIs ({} distinct):\n", self.missing.len());
+        for (name, api) in self.ranked() {
+            out.push_str(&format!("{:>8}  {name}  ({})\n", api.count, api.example_addon));
+        }
+        out
+    }
+}
+
+/// Start recording: enable the collector and hook `_G` and the existing
+/// `C_*` namespaces.
+pub fn enable(lua: &Lua) -> Result<()> {
+    get_sim_state(lua).borrow_mut().api_coverage = Some(ApiCoverage::default());
+
+    let globals = lua.globals();
+    for pair in globals.pairs::<Value, Value>() {
+        let (Value::String(key), Value::Table(ns)) = pair? else { continue };
+        let key = key.to_string_lossy();
+        if key.starts_with("C_") && ns.metatable().is_none() {
+            ns.set_metatable(Some(namespace_metatable(lua, &key, false)?));
+        }
+    }
+
+    let mt = lua.create_table()?;
+    mt.set(
+        "__index",
+        lua.create_function(|lua, (_g, key): (Table, Value)| {
+            let Value::String(key) = key else { return Ok(Value::Nil) };
+            let key = key.to_string_lossy();
+            if key.starts_with("C_") {
+                // Cache the proxy so its members are counted, not the namespace.
+                let proxy = lua.create_table()?;
+                proxy.set_metatable(Some(namespace_metatable(lua, &key, true)?));
+                lua.globals().raw_set(key.as_str(), proxy.clone())?;
+                return Ok(Value::Table(proxy));
+            }
+            record_missing(lua, &key);
+            Ok(Value::Nil)
+        })?,
+    )?;
+    globals.set_metatable(Some(mt));
+    Ok(())
+}
+
+/// Metatable recording `namespace.key` misses. Members of an unknown
+/// namespace (`stub_members`) are no-op functions; known ones stay nil.
+fn namespace_metatable(lua: &Lua, namespace: &str, stub_members: bool) -> Result<Table> {
+    let namespace = namespace.to_string();
+    let mt = lua.create_table()?;
+    mt.set(
+        "__index",
+        lua.create_function(move |lua, (_t, key): (Table, Value)| {
+            let Value::String(key) = key else { return Ok(Value::Nil) };
+            record_missing(lua, &format!("{namespace}.{}", key.to_string_lossy()));
+            if stub_members {
+                Ok(Value::Function(lua.create_function(|_, _: mlua::MultiValue| Ok(()))?))
+            } else {
+                Ok(Value::Nil)
+            }
+        })?,
+    )?;
+    Ok(mt)
+}
+
+/// Record a frame method lookup that found nothing. Only PascalCase keys
+/// count: lowercase keys are addon fields probed with `if self.foo`.
+pub fn record_missing_method(lua: &Lua, frame_id: u64, key: &str) {
+    if !key.starts_with(|c: char| c.is_ascii_uppercase()) {
+        return;
+    }
+    let state_rc = get_sim_state(lua);
+    let Ok(state) = state_rc.try_borrow() else { return };
+    if state.api_coverage.is_none() {
+        return;
+    }
+    let widget_type = state.widgets.get(frame_id).map(|f| f.widget_type.as_str());
+    let name = format!("{}:{key}", widget_type.unwrap_or("Frame"));
+    drop(state);
+    record_missing(lua, &name);
+}
+
+/// Count a miss for `name` against the addon currently running, if
+/// recording is on. Skipped when the state is already borrowed.
+fn record_missing(lua: &Lua, name: &str) {
+    let state_rc = get_sim_state(lua);
+    if !state_rc.try_borrow().is_ok_and(|s| s.api_coverage.is_some()) {
+        return;
+    }
+    let addon = current_addon(lua);
+    if let Ok(mut state) = state_rc.try_borrow_mut()
+        && let Some(coverage) = state.api_coverage.as_mut()
+    {
+        coverage.record(name, &addon);
+    }
+}
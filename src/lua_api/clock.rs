@@ -0,0 +1,40 @@
+//! Virtual simulation clock backing `GetTime()` and C_Timer scheduling.
+//!
+//! Time only moves when the clock is advanced: the GUI advances it by the
+//! real frame delta on every OnUpdate, headless runs advance it by a fixed
+//! step per tick. This keeps timer ordering and timer-driven UI reproducible
+//! regardless of machine speed.
+
+/// Simulated time in seconds, as reported by `GetTime()`.
+#[derive(Debug, Clone, Copy)]
+pub struct SimClock {
+    now: f64,
+}
+
+impl SimClock {
+    /// Time reported at UI load. Non-zero because addons treat a cooldown
+    /// `start` of 0 as "no cooldown" (real `GetTime()` is system uptime).
+    pub const START_SECS: f64 = 1.0;
+
+    pub fn new() -> Self {
+        Self { now: Self::START_SECS }
+    }
+
+    /// Current simulated time in seconds (what `GetTime()` returns).
+    pub fn now(&self) -> f64 {
+        self.now
+    }
+
+    /// Move the clock forward. Negative or non-finite deltas are ignored.
+    pub fn advance(&mut self, secs: f64) {
+        if secs.is_finite() && secs > 0.0 {
+            self.now += secs;
+        }
+    }
+}
+
+impl Default for SimClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -1,7 +1,10 @@
-//! Diagnostic dump functions for OnUpdate handlers and frame trees.
+//! Diagnostic dump functions for OnUpdate handlers and frame trees, and
+//! attribution of runtime calls to the addon making them.
 
+use super::frame::get_sim_state;
 use super::layout::{compute_frame_rect, get_parent_depth, LayoutRect};
 use super::state::SimState;
+use mlua::{Lua, Table};
 
 /// Dump all frame positions for debugging.
 /// Returns a formatted string similar to iced-debug output.
@@ -75,3 +78,29 @@ fn format_frame_entry(
     }
 }
 
+/// The addon being loaded, else the innermost addon file on the Lua stack.
+pub(crate) fn current_addon(lua: &Lua) -> String {
+    let state_rc = get_sim_state(lua);
+    if let Ok(state) = state_rc.try_borrow()
+        && let Some(addon) = state.loading_addon_index.and_then(|i| state.addons.get(i as usize))
+    {
+        return addon.folder_name.clone();
+    }
+    let traceback = lua
+        .globals()
+        .raw_get::<Table>("debug")
+        .and_then(|debug| debug.raw_get::<mlua::Function>("traceback"))
+        .and_then(|tb| tb.call::<String>(("", 1)));
+    traceback.ok().as_deref().and_then(addon_from_traceback).unwrap_or_else(|| "?".to_string())
+}
+
+/// First addon folder named in a traceback (`.../AddOns/<name>/...` or
+/// `.../BlizzardUI/<name>/...`).
+fn addon_from_traceback(traceback: &str) -> Option<String> {
+    traceback.lines().find_map(|line| {
+        let rest = ["AddOns/", "BlizzardUI/"].iter().find_map(|dir| {
+            line.find(dir).map(|pos| &line[pos + dir.len()..])
+        })?;
+        rest.split('/').next().filter(|name| !name.is_empty()).map(str::to_string)
+    })
+}
@@ -34,6 +34,7 @@ impl WowLuaEnv {
         // This is safe for our simulator since we control the Lua code
         let lua = unsafe { Lua::unsafe_new() };
         let state = Rc::new(RefCell::new(SimState::default()));
+        super::layout::reset_anchor_cycle_guard();
 
         // Create all built-in frames
         {
@@ -51,6 +52,14 @@ impl WowLuaEnv {
         // Precompile Lua helper functions for the XML loader
         crate::loader::precompiled::init(&lua)?;
 
+        // Lua 5.1's math.random wraps the process-wide C rand(), so reseed
+        // it per environment to make seeded runs independent of earlier ones.
+        if let Some(seed) = super::game_data::random_seed() {
+            let math: mlua::Table = lua.globals().get("math")?;
+            let randomseed: mlua::Function = math.get("randomseed")?;
+            randomseed.call::<()>((seed & 0x7fff_ffff) as i64)?;
+        }
+
         Ok(Self {
             lua,
             state,
@@ -58,6 +67,15 @@ impl WowLuaEnv {
         })
     }
 
+    /// Switch to the user's persisted CVars (`cvars.json`) and apply their
+    /// `useUiScale`/`uiScale` from the start, not only on SetCVar. A new env
+    /// keeps CVars in memory, so tests never see or write the user's settings.
+    pub fn use_persisted_cvars(&self) {
+        let mut state = self.state.borrow_mut();
+        state.cvars = crate::cvars::CVarStorage::new();
+        state.apply_ui_scale_cvars();
+    }
+
     /// Execute Lua code.
     pub fn exec(&self, code: &str) -> Result<()> {
         self.lua.load(code).exec()?;
@@ -108,6 +126,17 @@ impl WowLuaEnv {
         Ok(result)
     }
 
+    /// Start recording lookups of missing APIs (`--api-coverage`).
+    pub fn enable_api_coverage(&self) -> Result<()> {
+        super::api_coverage::enable(&self.lua)?;
+        Ok(())
+    }
+
+    /// Ranked missing-API report, if recording was enabled.
+    pub fn api_coverage_report(&self) -> Option<String> {
+        self.state.borrow().api_coverage.as_ref().map(|c| c.report())
+    }
+
     /// Apply post-load workarounds for Blizzard code that depends on
     /// unimplemented engine features (AnimationGroups, EditMode, etc.).
     /// Must be called after all addons are loaded and before firing events.
@@ -132,9 +161,11 @@ impl WowLuaEnv {
     pub fn fire_event_with_args(&self, event: &str, args: &[Value]) -> Result<()> {
         use super::script_helpers::{call_error_handler, get_frame_ref, get_script};
 
+        let unit = super::script_helpers::event_unit_arg(args);
         let listeners = {
-            let state = self.state.borrow();
-            state.widgets.get_event_listeners(event)
+            let mut state = self.state.borrow_mut();
+            state.record_event(event, args);
+            state.widgets.get_unit_event_listeners(event, unit.as_deref())
         };
 
         for widget_id in listeners {
@@ -163,6 +194,38 @@ impl WowLuaEnv {
         Ok(())
     }
 
+    /// Start recording dispatched events (see `crate::event::recorder`),
+    /// mirroring them to `path` as JSON lines when given.
+    pub fn start_event_recording(&self, path: Option<&std::path::Path>) -> std::io::Result<()> {
+        use crate::event::recorder::EventRecorder;
+        let recorder = match path {
+            Some(path) => EventRecorder::to_file(path)?,
+            None => EventRecorder::new(),
+        };
+        self.state.borrow_mut().event_recorder = Some(recorder);
+        Ok(())
+    }
+
+    /// Fire a recorded event log in order. The clock is advanced to each
+    /// event's recorded time first (running timers that come due), so
+    /// timer-driven state between events is reproduced too.
+    pub fn replay_events(&self, events: &[crate::event::recorder::RecordedEvent]) -> Result<()> {
+        for recorded in events {
+            let delta = recorded.time - self.state.borrow().clock.now();
+            if delta > 0.0 {
+                self.state.borrow_mut().clock.advance(delta);
+                self.process_timers()?;
+            }
+            let args = recorded
+                .args
+                .iter()
+                .map(|arg| arg.to_lua(&self.lua))
+                .collect::<mlua::Result<Vec<_>>>()?;
+            self.fire_event_with_args(&recorded.event, &args)?;
+        }
+        Ok(())
+    }
+
     /// Fire a script handler for a specific widget.
     /// handler_name is like "OnClick", "OnEnter", etc.
     /// extra_args are passed after the frame (self) argument.
@@ -173,19 +236,7 @@ impl WowLuaEnv {
         handler_name: &str,
         extra_args: Vec<Value>,
     ) -> Result<()> {
-        use super::script_helpers::{call_error_handler, get_script};
-
-        if let Some(handler) = get_script(&self.lua, widget_id, handler_name) {
-            let frame = super::frame::frame_lud(widget_id);
-
-            let mut call_args = vec![frame];
-            call_args.extend(extra_args);
-
-            if let Err(e) = handler.call::<()>(MultiValue::from_vec(call_args)) {
-                call_error_handler(&self.lua, &e.to_string());
-            }
-        }
-
+        super::script_helpers::fire_script(&self.lua, widget_id, handler_name, extra_args);
         Ok(())
     }
 
@@ -196,11 +247,14 @@ impl WowLuaEnv {
 
     /// Simulate a left-click on a frame by ID.
     ///
-    /// Handles EditBox focus management (focus/unfocus), then fires
-    /// OnMouseDown, OnClick, and OnMouseUp in sequence.
+    /// Handles EditBox focus management (focus/unfocus) and raises the
+    /// nearest toplevel frame, then fires OnMouseDown, OnClick, and OnMouseUp
+    /// in sequence.
     pub fn send_click(&self, frame_id: u64) -> Result<()> {
         use crate::widget::WidgetType;
 
+        self.state.borrow_mut().raise_toplevel_ancestor(frame_id);
+
         let is_editbox = self
             .state
             .borrow()
@@ -236,6 +290,10 @@ impl WowLuaEnv {
 
     /// Dispatch a slash command (e.g., "/wa options").
     /// Returns Ok(true) if a handler was found and called, Ok(false) if no handler matched.
+    ///
+    /// Tokens resolve through `hash_SlashCmdList` (filled by Blizzard's chat
+    /// edit box) and then the `slash_commands` index, which is rebuilt from
+    /// `SlashCmdList` only when a token is not found.
     pub fn dispatch_slash_command(&self, input: &str) -> Result<bool> {
         let input = input.trim();
         if !input.starts_with('/') {
@@ -247,42 +305,63 @@ impl WowLuaEnv {
             Some(pos) => (&input[..pos], input[pos + 1..].trim()),
             None => (input, ""),
         };
-        let cmd_lower = cmd.to_lowercase();
+        let token = cmd.to_uppercase();
 
-        // Scan globals for SLASH_* variables to find a matching command
-        let globals = self.lua.globals();
-        let slash_cmd_list: mlua::Table = globals.get("SlashCmdList")?;
+        let handler = match self.hashed_slash_handler(&token)? {
+            Some(handler) => Some(handler),
+            None => self.indexed_slash_handler(&token)?,
+        };
+        let Some(handler) = handler else {
+            return Ok(false);
+        };
+        handler.call::<()>(self.lua.create_string(msg)?)?;
+        Ok(true)
+    }
 
-        // Iterate through all globals looking for SLASH_* patterns
-        for pair in globals.pairs::<String, Value>() {
-            let (key, value) = pair?;
+    /// Handler already imported into Blizzard's `hash_SlashCmdList`.
+    fn hashed_slash_handler(&self, token: &str) -> Result<Option<mlua::Function>> {
+        let hash: Option<mlua::Table> = self.lua.globals().get("hash_SlashCmdList")?;
+        match hash {
+            Some(hash) => Ok(hash.get(token)?),
+            None => Ok(None),
+        }
+    }
 
-            // Look for SLASH_NAME1, SLASH_NAME2, etc.
-            if !key.starts_with("SLASH_") {
-                continue;
+    /// Handler via the `slash_commands` index, rebuilding it once on a miss.
+    fn indexed_slash_handler(&self, token: &str) -> Result<Option<mlua::Function>> {
+        let slash_cmd_list: mlua::Table = self.lua.globals().get("SlashCmdList")?;
+        let cached = self.state.borrow().slash_commands.get(token).cloned();
+        let name = match cached {
+            Some(name) => name,
+            None => {
+                self.rebuild_slash_index(&slash_cmd_list)?;
+                match self.state.borrow().slash_commands.get(token) {
+                    Some(name) => name.clone(),
+                    None => return Ok(None),
+                }
             }
+        };
+        // A key may have been unregistered since the index was built.
+        Ok(slash_cmd_list.get::<Option<mlua::Function>>(name).unwrap_or(None))
+    }
 
-            // Extract the command name (e.g., "SLASH_WEAKAURAS1" -> "WEAKAURAS")
-            let suffix = &key[6..]; // Skip "SLASH_"
-            let name = suffix.trim_end_matches(|c: char| c.is_ascii_digit());
-            if name.is_empty() {
-                continue;
+    /// Index `SLASH_<KEY>1..n` for every key in `SlashCmdList`, the same walk
+    /// Blizzard's ImportListToHash does.
+    fn rebuild_slash_index(&self, slash_cmd_list: &mlua::Table) -> Result<()> {
+        let globals = self.lua.globals();
+        let mut index = std::collections::HashMap::new();
+        for pair in slash_cmd_list.pairs::<Value, Value>() {
+            let (Value::String(key), _) = pair? else { continue };
+            let key = key.to_str()?.to_string();
+            for i in 1.. {
+                let Some(tag) = globals.get::<Option<String>>(format!("SLASH_{key}{i}"))? else {
+                    break;
+                };
+                index.insert(tag.to_uppercase(), key.clone());
             }
-
-            // Check if this SLASH_ variable matches our command
-            if let Value::String(slash_str) = value
-                && slash_str.to_str()?.to_lowercase() == cmd_lower {
-                    // Found a match! Look up the handler in SlashCmdList
-                    let handler: Option<mlua::Function> = slash_cmd_list.get(name).ok();
-                    if let Some(handler) = handler {
-                        let msg_value = self.lua.create_string(msg)?;
-                        handler.call::<()>(msg_value)?;
-                        return Ok(true);
-                    }
-                }
         }
-
-        Ok(false)
+        self.state.borrow_mut().slash_commands = index;
+        Ok(())
     }
 
     /// Get access to the Lua state.
@@ -310,19 +389,7 @@ impl WowLuaEnv {
 
     /// Update screen dimensions in SimState and resize UIParent/WorldFrame to match.
     pub fn set_screen_size(&self, width: f32, height: f32) {
-        let mut state = self.state.borrow_mut();
-        state.screen_width = width;
-        state.screen_height = height;
-        // Screen resize invalidates all cached layout rects and strata buckets.
-        state.strata_buckets = None;
-        state.widgets.clear_all_layout_rects();
-        for name in &["UIParent", "WorldFrame"] {
-            if let Some(id) = state.widgets.get_id_by_name(name)
-                && let Some(frame) = state.widgets.get_mut_visual(id) {
-                    frame.width = width;
-                    frame.height = height;
-                }
-        }
+        self.state.borrow_mut().set_screen_size(width, height);
     }
 
     /// Register an addon in the addon list.
@@ -332,7 +399,6 @@ impl WowLuaEnv {
 
     /// Scan an addons directory and register all found addons (metadata only, no loading).
     pub fn scan_and_register_addons(&self, addons_path: &std::path::Path) {
-        use crate::toc::TocFile;
         let entries = match std::fs::read_dir(addons_path) {
             Ok(e) => e,
             Err(_) => return,
@@ -350,21 +416,7 @@ impl WowLuaEnv {
             // Find TOC file
             let toc_path = crate::loader::find_toc_file(&path);
             let Some(toc_path) = toc_path else { continue };
-            let toc = TocFile::from_file(&toc_path).ok();
-            let (title, notes, load_on_demand) = toc
-                .as_ref()
-                .map(|t| {
-                    let title = t.metadata.get("Title").cloned().unwrap_or_else(|| name.clone());
-                    let notes = t.metadata.get("Notes").cloned().unwrap_or_default();
-                    let lod = t.metadata.get("LoadOnDemand").map(|v| v == "1").unwrap_or(false);
-                    (title, notes, lod)
-                })
-                .unwrap_or_else(|| (name.clone(), String::new(), false));
-            addons.push(AddonInfo {
-                folder_name: name, title, notes,
-                enabled: true, loaded: false, load_on_demand,
-                load_time_secs: 0.0, ..Default::default()
-            });
+            addons.push(AddonInfo::from_toc(&name, &toc_path));
         }
         addons.sort_by(|a, b| a.folder_name.to_lowercase().cmp(&b.folder_name.to_lowercase()));
         let mut state = self.state.borrow_mut();
@@ -386,7 +438,7 @@ impl WowLuaEnv {
     ) -> Result<u64> {
         let id = next_timer_id();
         let callback_key = self.lua.create_registry_value(callback)?;
-        let fire_at = Instant::now() + std::time::Duration::from_secs_f64(seconds);
+        let fire_at = self.state.borrow().clock.now() + seconds;
 
         let owner_addon = self.state.borrow().loading_addon_index;
         let timer = PendingTimer {
@@ -437,6 +489,17 @@ impl WowLuaEnv {
         true
     }
 
+    /// Whether the timer's Lua handle was cancelled. A ticker that calls
+    /// `self:Cancel()` from its own callback is not in `timers` at that point,
+    /// so only the handle records it.
+    fn handle_cancelled(&self, timer: &PendingTimer) -> bool {
+        timer
+            .handle_key
+            .as_ref()
+            .and_then(|k| self.lua.registry_value::<mlua::Table>(k).ok())
+            .is_some_and(|h| h.get::<bool>("_cancelled").unwrap_or(false))
+    }
+
     /// Check if a ticker should repeat and decrement its remaining count.
     fn ticker_should_repeat(timer: &mut PendingTimer) -> bool {
         match &mut timer.remaining {
@@ -449,64 +512,68 @@ impl WowLuaEnv {
         }
     }
 
-    /// Process any timers that are ready to fire.
+    /// Process any timers that are ready to fire at the current `SimClock` time.
+    ///
+    /// Due timers fire in `fire_at` order (ties broken by creation order), so a
+    /// 0.1s callback always runs before a 0.2s one even when both become due in
+    /// the same pass. Timers created by a callback wait for the next pass, so
+    /// a `C_Timer.After(0, f)` that reschedules itself can't loop forever.
     /// Returns the number of callbacks invoked.
     pub fn process_timers(&self) -> Result<usize> {
-        let now = Instant::now();
+        let (now, last_id) = {
+            let state = self.state.borrow();
+            (state.clock.now(), state.timers.iter().map(|t| t.id).max().unwrap_or(0))
+        };
         let mut fired = 0;
         let mut to_reschedule = Vec::new();
 
-        let mut state = self.state.borrow_mut();
-        let mut i = 0;
-        while i < state.timers.len() {
-            if state.timers[i].cancelled {
-                self.cleanup_timer(state.timers.remove(i).unwrap());
+        while let Some(mut timer) = self.take_next_due_timer(now, last_id) {
+            let timer_addon = timer.owner_addon;
+            let cb_start = Instant::now();
+            if !self.fire_timer_callback(&timer) {
+                self.cleanup_timer(timer);
                 continue;
             }
-
-            if state.timers[i].fire_at <= now {
-                let mut timer = state.timers.remove(i).unwrap();
-                let timer_addon = timer.owner_addon;
-                // Drop state borrow before calling Lua callback
-                drop(state);
-
-                let cb_start = Instant::now();
-                if self.fire_timer_callback(&timer) {
-                    let elapsed_ms = cb_start.elapsed().as_secs_f64() * 1000.0;
-                    fired += 1;
-                    state = self.state.borrow_mut();
-                    if let Some(idx) = timer_addon {
-                        if let Some(addon) = state.addons.get_mut(idx as usize) {
-                            addon.runtime.current_frame_ms += elapsed_ms;
-                        }
-                    }
-
-                    if let Some(interval) = timer.interval {
-                        if Self::ticker_should_repeat(&mut timer) {
-                            timer.fire_at = now + interval;
-                            to_reschedule.push(timer);
-                        } else {
-                            self.cleanup_timer(timer);
-                        }
-                    } else {
-                        self.cleanup_timer(timer);
-                    }
-                } else {
-                    self.cleanup_timer(timer);
-                    state = self.state.borrow_mut();
+            let elapsed_ms = cb_start.elapsed().as_secs_f64() * 1000.0;
+            fired += 1;
+            if let Some(idx) = timer_addon {
+                let mut state = self.state.borrow_mut();
+                if let Some(addon) = state.addons.get_mut(idx as usize) {
+                    addon.runtime.current_frame_ms += elapsed_ms;
                 }
-                continue;
             }
-            i += 1;
-        }
 
-        for timer in to_reschedule {
-            state.timers.push_back(timer);
+            match timer.interval {
+                Some(interval)
+                    if !self.handle_cancelled(&timer) && Self::ticker_should_repeat(&mut timer) =>
+                {
+                    timer.fire_at += interval.as_secs_f64();
+                    to_reschedule.push(timer);
+                }
+                _ => self.cleanup_timer(timer),
+            }
         }
 
+        self.state.borrow_mut().timers.extend(to_reschedule);
         Ok(fired)
     }
 
+    /// Remove and return the earliest timer due at `now` with an ID up to
+    /// `last_id`, dropping cancelled timers along the way. The state borrow is
+    /// released before returning so the caller can run the Lua callback.
+    fn take_next_due_timer(&self, now: f64, last_id: u64) -> Option<PendingTimer> {
+        let mut state = self.state.borrow_mut();
+        while let Some(i) = state.timers.iter().position(|t| t.cancelled) {
+            let cancelled = state.timers.remove(i).unwrap();
+            self.cleanup_timer(cancelled);
+        }
+        let idx = state.timers.iter().enumerate()
+            .filter(|(_, t)| t.fire_at <= now && t.id <= last_id)
+            .min_by(|(_, a), (_, b)| a.fire_at.total_cmp(&b.fire_at).then(a.id.cmp(&b.id)))
+            .map(|(i, _)| i)?;
+        state.timers.remove(idx)
+    }
+
     /// Check if there are any pending timers.
     pub fn has_pending_timers(&self) -> bool {
         !self.state.borrow().timers.is_empty()
@@ -515,10 +582,15 @@ impl WowLuaEnv {
 
     /// Fire OnUpdate handlers for all frames that have them registered,
     /// then tick animation groups.
-    /// `elapsed` is the time in seconds since the last frame.
+    /// `elapsed` is the time in seconds since the last frame; the `SimClock`
+    /// advances by the same amount.
     pub fn fire_on_update(&self, elapsed: f64) -> Result<()> {
         use super::script_helpers::{call_error_handler, get_frame_ref, get_script};
 
+        // The frame's time passes before its handlers run, so GetTime() inside
+        // OnUpdate already reflects `elapsed`.
+        self.state.borrow_mut().clock.advance(elapsed);
+
         let frame_ids = self.get_visible_on_update_frames();
 
         if !frame_ids.is_empty() {
@@ -691,18 +763,12 @@ impl WowLuaEnv {
     /// Get the time until the next timer fires, if any.
     pub fn next_timer_delay(&self) -> Option<std::time::Duration> {
         let state = self.state.borrow();
-        let now = Instant::now();
+        let now = state.clock.now();
         state
             .timers
             .iter()
             .filter(|t| !t.cancelled)
-            .map(|t| {
-                if t.fire_at > now {
-                    t.fire_at - now
-                } else {
-                    std::time::Duration::ZERO
-                }
-            })
+            .map(|t| std::time::Duration::from_secs_f64((t.fire_at - now).max(0.0)))
             .min()
     }
 
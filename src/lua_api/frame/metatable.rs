@@ -2,7 +2,9 @@
 //!
 //! Sets up a shared metatable for all LightUserData values (frames):
 //! - `__index` = Rust fn that does rawget on methods_table, then falls back
-//!   to children_keys / custom fields / numeric index / Lower/Raise/Clear
+//!   to children_keys / custom fields / fallback methods (e.g. backdrop) /
+//!   numeric index / Lower/Raise/Clear. Frames with `hooksecurefunc` method
+//!   hooks check their custom fields first so the hook wrapper wins.
 //! - `__newindex` = Rust fn (children_keys sync + __frame_fields storage)
 //! - `__len` = Rust fn (children count)
 //! - No `__eq` needed: same ID = same pointer = Lua `==` works natively.
@@ -10,6 +12,22 @@
 use super::handle::{extract_frame_id, frame_lud, get_sim_state, lud_to_id};
 use crate::widget::WidgetType;
 use mlua::{LightUserData, Lua, Value};
+use std::cell::Cell;
+use std::rc::Rc;
+
+/// Set once any frame method has been wrapped by `hooksecurefunc`; until
+/// then `__index` skips the per-frame `has_method_hooks` check.
+struct FrameMethodHooksActive(Rc<Cell<bool>>);
+
+/// Shared "some frame has hooked methods" flag, created on first use.
+pub(crate) fn frame_method_hooks_active(lua: &Lua) -> Rc<Cell<bool>> {
+    if let Some(active) = lua.app_data_ref::<FrameMethodHooksActive>() {
+        return Rc::clone(&active.0);
+    }
+    let active = Rc::new(Cell::new(false));
+    lua.set_app_data(FrameMethodHooksActive(Rc::clone(&active)));
+    active
+}
 
 /// Build and install the shared LightUserData metatable for frames.
 pub fn setup_frame_metatable(lua: &Lua) -> mlua::Result<()> {
@@ -21,9 +39,13 @@ pub fn setup_frame_metatable(lua: &Lua) -> mlua::Result<()> {
     // Store methods_table in registry for getmetatable() and populate_method_index()
     lua.set_named_registry_value("__frame_methods_table", methods_table.clone())?;
 
+    // Methods that mixins may override, looked up after custom fields
+    let fallback_methods = lua.create_table()?;
+    super::methods::register_fallback_methods(lua, &fallback_methods)?;
+
     // Build the frame metatable
     let frame_mt = lua.create_table()?;
-    frame_mt.set("__index", create_index(lua, methods_table)?)?;
+    frame_mt.set("__index", create_index(lua, methods_table, fallback_methods)?)?;
     frame_mt.set("__newindex", create_newindex(lua)?)?;
     frame_mt.set("__len", create_len(lua)?)?;
 
@@ -34,7 +56,12 @@ pub fn setup_frame_metatable(lua: &Lua) -> mlua::Result<()> {
 }
 
 /// __index: method lookup via rawget on methods_table, then fallback.
-fn create_index(lua: &Lua, methods_table: mlua::Table) -> mlua::Result<mlua::Function> {
+fn create_index(
+    lua: &Lua,
+    methods_table: mlua::Table,
+    fallback_methods: mlua::Table,
+) -> mlua::Result<mlua::Function> {
+    let hooks_active = frame_method_hooks_active(lua);
     lua.create_function(move |lua, (ud, key): (LightUserData, Value)| {
         let frame_id = lud_to_id(ud);
 
@@ -51,6 +78,12 @@ fn create_index(lua: &Lua, methods_table: mlua::Table) -> mlua::Result<mlua::Fun
         // Fast path: rawget on methods_table
         let method: Value = methods_table.raw_get(key_str.as_str())?;
         if method != Value::Nil {
+            if hooks_active.get()
+                && frame_has_method_hooks(lua, frame_id)
+                && let Some(wrapper) = lookup_custom_field(lua, frame_id, &key_str)
+            {
+                return Ok(wrapper);
+            }
             return Ok(method);
         }
 
@@ -64,11 +97,18 @@ fn create_index(lua: &Lua, methods_table: mlua::Table) -> mlua::Result<mlua::Fun
             return Ok(value);
         }
 
+        // Mixin-overridable methods (backdrop)
+        let method: Value = fallback_methods.raw_get(key_str.as_str())?;
+        if method != Value::Nil {
+            return Ok(method);
+        }
+
         // Fallback methods (Clear for Cooldown, Lower, Raise)
         if let Some(func) = lookup_fallback_method(lua, frame_id, &key_str)? {
             return Ok(func);
         }
 
+        crate::lua_api::api_coverage::record_missing_method(lua, frame_id, &key_str);
         Ok(Value::Nil)
     })
 }
@@ -92,6 +132,8 @@ fn create_newindex(lua: &Lua) -> mlua::Result<mlua::Function> {
                     parent_frame.children.push(child_id);
                 }
             }
+            // A new key may be the target of a pending `$parent.Key` anchor.
+            state.bind_deferred_anchors_for_key(&key);
         } else {
             // Non-frame value — remove stale children_keys entry
             let mut state = state_rc.borrow_mut();
@@ -147,6 +189,13 @@ fn lookup_child_by_key(lua: &Lua, frame_id: u64, key: &str) -> mlua::Result<Opti
     Ok(None)
 }
 
+/// Whether `hooksecurefunc` wrapped one of this frame's methods.
+fn frame_has_method_hooks(lua: &Lua, frame_id: u64) -> bool {
+    let state_rc = get_sim_state(lua);
+    let state = state_rc.borrow();
+    state.widgets.get(frame_id).is_some_and(|f| f.has_method_hooks)
+}
+
 /// Look up a value from the __frame_fields Lua table (stored in registry).
 fn lookup_custom_field(lua: &Lua, frame_id: u64, key: &str) -> Option<Value> {
     let fields_table = crate::lua_api::script_helpers::get_frame_fields_table(lua)?;
@@ -184,9 +233,7 @@ fn lookup_fallback_method(lua: &Lua, frame_id: u64, key: &str) -> mlua::Result<O
                 let id = lud_to_id(ud);
                 let state_rc = get_sim_state(lua);
                 let mut state = state_rc.borrow_mut();
-                if let Some(frame) = state.widgets.get_mut_visual(id) {
-                    frame.frame_level = (frame.frame_level - 1).max(0);
-                }
+                state.lower_frame(id);
                 Ok(())
             },
         )?)));
@@ -91,13 +91,18 @@ fn parse_set_point_2_or_3(
     }
 }
 
-/// Parse SetPoint with 4+ arguments (full form with relativeTo, relativePoint, x, y).
+/// Parse SetPoint with 4+ arguments: the full form (relativeTo, relativePoint,
+/// x, y) or the short form (relativeTo, x, y) where relativePoint = point.
 fn parse_set_point_full(
     lua: &Lua,
     args: &[Value],
     point: crate::widget::AnchorPoint,
 ) -> (Option<usize>, crate::widget::AnchorPoint, f32, f32) {
     let rel_to = args.get(1).and_then(|v| get_frame_id(lua, v));
+    if let Some(x) = args.get(2).and_then(get_number) {
+        let y = args.get(3).and_then(get_number).unwrap_or(0.0);
+        return (rel_to, point, x, y);
+    }
     let rel_point_str = args.get(2).and_then(|v| {
         if let Value::String(s) = v {
             Some(s.to_string_lossy().to_string())
@@ -207,7 +212,7 @@ fn add_clear_and_adjust_methods(lua: &Lua, methods: &mlua::Table) -> mlua::Resul
                 frame.clear_all_points();
             }
             state.widgets.mark_rect_dirty(id);
-            state.invalidate_layout(id);
+            state.invalidate_layout_with_dependents(id);
         }
         Ok(())
     })?)?;
@@ -231,7 +236,7 @@ fn add_clear_and_adjust_methods(lua: &Lua, methods: &mlua::Table) -> mlua::Resul
                 frame.anchors.retain(|a| a.point != point);
             }
             state.widgets.mark_rect_dirty(id);
-            state.invalidate_layout(id);
+            state.invalidate_layout_with_dependents(id);
         }
         Ok(())
     })?)?;
@@ -261,13 +266,14 @@ fn add_clear_and_adjust_methods(lua: &Lua, methods: &mlua::Table) -> mlua::Resul
     Ok(())
 }
 
-/// SetAllPoints(relativeTo) - sets TOPLEFT and BOTTOMRIGHT to fill a relative frame.
+/// SetAllPoints(relativeTo) - replaces all anchors with TOPLEFT and BOTTOMRIGHT
+/// filling `relativeTo` (a frame or global frame name; nil/true = parent).
 fn add_set_all_points_method(lua: &Lua, methods: &mlua::Table) -> mlua::Result<()> {
     methods.set("SetAllPoints", lua.create_function(|lua, (ud, arg): (LightUserData, Option<Value>)| {
         let id = lud_to_id(ud);
         let (should_set, relative_to_id) = match &arg {
             Some(Value::Boolean(false)) => (false, None),
-            Some(Value::LightUserData(lud)) => (true, Some(lud_to_id(*lud) as usize)),
+            Some(v @ (Value::LightUserData(_) | Value::String(_))) => (true, get_frame_id(lua, v)),
             _ => (true, None),
         };
 
@@ -318,7 +324,7 @@ fn apply_set_all_points(
         );
     }
     state.widgets.mark_rect_dirty(id);
-    state.invalidate_layout(id);
+    state.invalidate_layout_with_dependents(id);
 }
 
 /// GetPoint, GetNumPoints, GetPointByName - querying anchor points.
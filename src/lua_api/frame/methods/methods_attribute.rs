@@ -32,8 +32,11 @@ fn add_get_set_attribute_methods(lua: &Lua, methods: &mlua::Table) -> mlua::Resu
         "SetAttribute",
         lua.create_function(|lua, (ud, name, value): (LightUserData, String, Value)| {
             let id = lud_to_id(ud);
+            let previous = get_attribute_value(lua, id, std::slice::from_ref(&name))?;
             set_attribute_value(lua, id, &name, &value)?;
-            fire_on_attribute_changed(lua, id, &name, value)?;
+            if !same_attribute_value(&previous, &value) {
+                fire_on_attribute_changed(lua, id, &name, value)?;
+            }
             Ok(())
         })?,
     )?;
@@ -51,6 +54,7 @@ fn add_get_set_attribute_methods(lua: &Lua, methods: &mlua::Table) -> mlua::Resu
         "ClearAttributes",
         lua.create_function(|lua, ud: LightUserData| {
             let id = lud_to_id(ud);
+            clear_table_attributes(lua, id)?;
             let state_rc = get_sim_state(lua);
             let mut state = state_rc.borrow_mut();
             if let Some(frame) = state.widgets.get_mut(id) {
@@ -105,8 +109,7 @@ fn build_attribute_keys(args: &mlua::MultiValue) -> Vec<String> {
 
 /// Look up an attribute, trying each key in order until one is found.
 fn get_attribute_value(lua: &Lua, id: u64, keys: &[String]) -> mlua::Result<Value> {
-    let table_attrs: Option<mlua::Table> =
-        lua.globals().get("__frame_table_attributes").ok();
+    let table_attrs = table_attributes(lua);
     let state_rc = get_sim_state(lua);
     let state = state_rc.borrow();
     let frame = state.widgets.get(id);
@@ -114,7 +117,7 @@ fn get_attribute_value(lua: &Lua, id: u64, keys: &[String]) -> mlua::Result<Valu
     for key in keys {
         // Check table attributes stored in Lua
         if let Some(attrs) = &table_attrs {
-            let lua_key = format!("{}_{}", id, key);
+            let lua_key = table_attribute_key(id, key);
             let table_val: Value = attrs.get(lua_key.as_str()).unwrap_or(Value::Nil);
             if !matches!(table_val, Value::Nil) {
                 return Ok(table_val);
@@ -140,11 +143,34 @@ fn attribute_to_value(lua: &Lua, attr: &AttributeValue) -> mlua::Result<Value> {
     }
 }
 
+/// Registry key of the Lua-side store for table/function/userdata attributes.
+/// Entries are keyed by `"{frame_id}_{name}"` (see `table_attribute_key`).
+const TABLE_ATTRIBUTES_KEY: &str = "__frame_table_attributes";
+
+fn table_attribute_key(id: u64, name: &str) -> String {
+    format!("{}_{}", id, name)
+}
+
+/// The Lua-side attribute table, if any table attribute has been stored yet.
+fn table_attributes(lua: &Lua) -> Option<mlua::Table> {
+    lua.named_registry_value(TABLE_ATTRIBUTES_KEY).ok()
+}
+
 /// Store the attribute value in Lua (tables) or Rust (simple types).
+///
+/// The two stores are exclusive per name: writing one clears the other so a
+/// stale table value never shadows a newer string/number (or vice versa).
 fn set_attribute_value(lua: &Lua, id: u64, name: &str, value: &Value) -> mlua::Result<()> {
     if matches!(value, Value::Table(_) | Value::UserData(_) | Value::LightUserData(_) | Value::Function(_)) {
         store_table_attribute(lua, id, name, value)?;
+        let state_rc = get_sim_state(lua);
+        if let Some(frame) = state_rc.borrow_mut().widgets.get_mut(id) {
+            frame.attributes.remove(name);
+        }
     } else {
+        if let Some(attrs) = table_attributes(lua) {
+            attrs.set(table_attribute_key(id, name), Value::Nil)?;
+        }
         store_simple_attribute(lua, id, name, value)?;
     }
     Ok(())
@@ -152,26 +178,28 @@ fn set_attribute_value(lua: &Lua, id: u64, name: &str, value: &Value) -> mlua::R
 
 /// Store a complex Lua value (table/userdata/function) in the Lua-side attribute table.
 fn store_table_attribute(lua: &Lua, id: u64, name: &str, value: &Value) -> mlua::Result<()> {
-    let table_attrs: mlua::Table = lua
-        .globals()
-        .get("__frame_table_attributes")
-        .unwrap_or_else(|_| {
-            let t = lua.create_table().unwrap();
-            lua.globals().set("__frame_table_attributes", t.clone()).ok();
+    let table_attrs = match table_attributes(lua) {
+        Some(t) => t,
+        None => {
+            let t = lua.create_table()?;
+            lua.set_named_registry_value(TABLE_ATTRIBUTES_KEY, &t)?;
             t
-        });
-    let key = format!("{}_{}", id, name);
-    table_attrs.set(key, value.clone())?;
+        }
+    };
+    table_attrs.set(table_attribute_key(id, name), value.clone())?;
     Ok(())
 }
 
-/// Store a simple value (string/number/bool/nil) in the Rust-side attribute map.
+/// Store a simple value (string/number/bool) in the Rust-side attribute map; nil removes it.
 fn store_simple_attribute(lua: &Lua, id: u64, name: &str, value: &Value) -> mlua::Result<()> {
     let state_rc = get_sim_state(lua);
     let mut state = state_rc.borrow_mut();
     if let Some(frame) = state.widgets.get_mut(id) {
         let attr = match value {
-            Value::Nil => AttributeValue::Nil,
+            Value::Nil => {
+                frame.attributes.remove(name);
+                return Ok(());
+            }
             Value::Boolean(b) => AttributeValue::Boolean(*b),
             Value::Integer(i) => AttributeValue::Number(*i as f64),
             Value::Number(n) => AttributeValue::Number(*n),
@@ -180,22 +208,54 @@ fn store_simple_attribute(lua: &Lua, id: u64, name: &str, value: &Value) -> mlua
             }
             _ => AttributeValue::Nil,
         };
-        if matches!(attr, AttributeValue::Nil) && matches!(value, Value::Nil) {
-            frame.attributes.remove(name);
-            // Also remove from table attributes if it exists there
-            if let Ok(table_attrs) =
-                lua.globals().get::<mlua::Table>("__frame_table_attributes")
-            {
-                let key = format!("{}_{}", id, name);
-                table_attrs.set(key, Value::Nil).ok();
-            }
-        } else {
-            frame.attributes.insert(name.to_string(), attr);
+        frame.attributes.insert(name.to_string(), attr);
+    }
+    Ok(())
+}
+
+/// Drop every Lua-side table attribute belonging to frame `id`.
+fn clear_table_attributes(lua: &Lua, id: u64) -> mlua::Result<()> {
+    let Some(attrs) = table_attributes(lua) else {
+        return Ok(());
+    };
+    let prefix = table_attribute_key(id, "");
+    let mut stale = Vec::new();
+    for pair in attrs.pairs::<String, Value>() {
+        let (key, _) = pair?;
+        if key.starts_with(&prefix) {
+            stale.push(key);
         }
     }
+    for key in stale {
+        attrs.set(key, Value::Nil)?;
+    }
     Ok(())
 }
 
+/// Whether a SetAttribute leaves the stored value unchanged (Lua raw equality).
+fn same_attribute_value(old: &Value, new: &Value) -> bool {
+    match (old, new) {
+        (Value::Nil, Value::Nil) => true,
+        (Value::Boolean(a), Value::Boolean(b)) => a == b,
+        (Value::String(a), Value::String(b)) => *a.as_bytes() == *b.as_bytes(),
+        (Value::Number(_) | Value::Integer(_), Value::Number(_) | Value::Integer(_)) => {
+            value_as_f64(old) == value_as_f64(new)
+        }
+        (Value::Table(_) | Value::Function(_) | Value::UserData(_) | Value::LightUserData(_), _) => {
+            old.to_pointer() == new.to_pointer()
+        }
+        _ => false,
+    }
+}
+
+fn value_as_f64(v: &Value) -> Option<f64> {
+    match v {
+        Value::Number(n) => Some(*n),
+        Value::Integer(i) => Some(*i as f64),
+        _ => None,
+    }
+}
+
 /// Fire OnAttributeChanged script handler if one exists.
 fn fire_on_attribute_changed(lua: &Lua, id: u64, name: &str, value: Value) -> mlua::Result<()> {
     use crate::lua_api::script_helpers::{call_error_handler, get_script};
@@ -284,7 +344,7 @@ fn add_clip_children_methods(lua: &Lua, methods: &mlua::Table) -> mlua::Result<(
             let id = lud_to_id(ud);
             let state_rc = get_sim_state(lua);
             let mut state = state_rc.borrow_mut();
-            if let Some(frame) = state.widgets.get_mut(id) {
+            if let Some(frame) = state.widgets.get_mut_visual(id) {
                 frame.clips_children = clips.unwrap_or(false);
             }
             Ok(())
@@ -1,18 +1,152 @@
-//! Backdrop methods.
+//! Backdrop fallback methods.
 //!
-//! In modern WoW (post-9.0), backdrop rendering is handled entirely by
+//! In modern WoW (post-9.0), backdrop rendering is handled by
 //! BackdropTemplateMixin in Lua, which creates child Texture widgets for
-//! nine-slice pieces. No Rust methods are needed — the mixin defines
-//! SetBackdrop, ApplyBackdrop, SetBackdropColor, SetBackdropBorderColor, etc.
+//! nine-slice pieces. These methods must therefore never live in the shared
+//! methods table: Rust methods take priority over `__index` lookups and would
+//! shadow the mixin, preventing it from creating the nine-slice children.
 //!
-//! Previously this file had Rust implementations of these methods, but they
-//! shadowed the Lua mixin methods (Rust `add_method` takes priority over
-//! `__index` lookups), preventing the mixin from creating the nine-slice
-//! child textures.
+//! Instead they are registered as fallbacks, consulted by `__index` only after
+//! custom fields. Frames with the mixin get its methods; plain frames (legacy
+//! `<Backdrop>` XML, addons calling `SetBackdrop` without the template) store
+//! the backdrop on `Frame::backdrop`, which the renderer draws directly.
 
-use mlua::Lua;
+use super::widget_tooltip::val_to_f32;
+use crate::lua_api::frame::handle::{get_sim_state, lud_to_id};
+use crate::widget::{Backdrop, Color};
+use mlua::{LightUserData, Lua, Result, Value};
 
-/// No-op: all backdrop methods are handled by BackdropTemplateMixin in Lua.
-pub fn add_backdrop_methods(_lua: &Lua, _methods: &mlua::Table) -> mlua::Result<()> {
+/// Register backdrop methods into the fallback methods table.
+pub fn add_backdrop_methods(lua: &Lua, fallbacks: &mlua::Table) -> Result<()> {
+    add_set_backdrop(lua, fallbacks)?;
+    add_get_backdrop(lua, fallbacks)?;
+    add_backdrop_color_methods(lua, fallbacks)?;
     Ok(())
 }
+
+/// Parse a `backdropInfo` table (bgFile, edgeFile, tile, tileSize, edgeSize, insets).
+fn backdrop_from_table(info: &mlua::Table) -> Result<Backdrop> {
+    let insets = match info.get::<Option<mlua::Table>>("insets")? {
+        Some(t) => (
+            t.get::<Option<f32>>("left")?.unwrap_or(0.0),
+            t.get::<Option<f32>>("right")?.unwrap_or(0.0),
+            t.get::<Option<f32>>("top")?.unwrap_or(0.0),
+            t.get::<Option<f32>>("bottom")?.unwrap_or(0.0),
+        ),
+        None => (0.0, 0.0, 0.0, 0.0),
+    };
+    Ok(Backdrop {
+        enabled: true,
+        bg_file: info.get("bgFile")?,
+        edge_file: info.get("edgeFile")?,
+        bg_color: Color::new(1.0, 1.0, 1.0, 1.0),
+        border_color: Color::new(1.0, 1.0, 1.0, 1.0),
+        edge_size: info.get::<Option<f32>>("edgeSize")?.unwrap_or(0.0),
+        tile: info.get::<Option<bool>>("tile")?.unwrap_or(false),
+        tile_size: info.get::<Option<f32>>("tileSize")?.unwrap_or(0.0),
+        insets,
+    })
+}
+
+/// SetBackdrop(info) — set or clear (nil) the backdrop. Colors reset to white.
+fn add_set_backdrop(lua: &Lua, methods: &mlua::Table) -> Result<()> {
+    methods.set("SetBackdrop", lua.create_function(|lua, (ud, info): (LightUserData, Option<mlua::Table>)| {
+        let id = lud_to_id(ud);
+        let backdrop = match info {
+            Some(t) => backdrop_from_table(&t)?,
+            None => Backdrop::default(),
+        };
+        let state_rc = get_sim_state(lua);
+        let mut state = state_rc.borrow_mut();
+        if let Some(frame) = state.widgets.get_mut_visual(id) {
+            frame.backdrop = backdrop;
+        }
+        Ok(())
+    })?)?;
+    Ok(())
+}
+
+/// GetBackdrop() — the backdropInfo table, or nil when no backdrop is set.
+fn add_get_backdrop(lua: &Lua, methods: &mlua::Table) -> Result<()> {
+    methods.set("GetBackdrop", lua.create_function(|lua, ud: LightUserData| {
+        let id = lud_to_id(ud);
+        let backdrop = {
+            let state_rc = get_sim_state(lua);
+            let state = state_rc.borrow();
+            match state.widgets.get(id) {
+                Some(f) if f.backdrop.enabled => f.backdrop.clone(),
+                _ => return Ok(Value::Nil),
+            }
+        };
+        let info = lua.create_table()?;
+        info.set("bgFile", backdrop.bg_file)?;
+        info.set("edgeFile", backdrop.edge_file)?;
+        info.set("tile", backdrop.tile)?;
+        info.set("tileSize", backdrop.tile_size)?;
+        info.set("edgeSize", backdrop.edge_size)?;
+        let (left, right, top, bottom) = backdrop.insets;
+        let insets = lua.create_table()?;
+        insets.set("left", left)?;
+        insets.set("right", right)?;
+        insets.set("top", top)?;
+        insets.set("bottom", bottom)?;
+        info.set("insets", insets)?;
+        Ok(Value::Table(info))
+    })?)?;
+    Ok(())
+}
+
+fn add_backdrop_color_methods(lua: &Lua, methods: &mlua::Table) -> Result<()> {
+    methods.set("SetBackdropColor", lua.create_function(|lua, (ud, args): (LightUserData, mlua::MultiValue)| {
+        let color = color_from_args(args);
+        let state_rc = get_sim_state(lua);
+        let mut state = state_rc.borrow_mut();
+        if let Some(frame) = state.widgets.get_mut_visual(lud_to_id(ud)) {
+            frame.backdrop.bg_color = color;
+        }
+        Ok(())
+    })?)?;
+
+    methods.set("GetBackdropColor", lua.create_function(|lua, ud: LightUserData| {
+        let state_rc = get_sim_state(lua);
+        let state = state_rc.borrow();
+        let frame = state.widgets.get(lud_to_id(ud)).filter(|f| f.backdrop.enabled);
+        Ok(color_values(frame.map(|f| &f.backdrop.bg_color)))
+    })?)?;
+
+    methods.set("SetBackdropBorderColor", lua.create_function(|lua, (ud, args): (LightUserData, mlua::MultiValue)| {
+        let color = color_from_args(args);
+        let state_rc = get_sim_state(lua);
+        let mut state = state_rc.borrow_mut();
+        if let Some(frame) = state.widgets.get_mut_visual(lud_to_id(ud)) {
+            frame.backdrop.border_color = color;
+        }
+        Ok(())
+    })?)?;
+
+    methods.set("GetBackdropBorderColor", lua.create_function(|lua, ud: LightUserData| {
+        let state_rc = get_sim_state(lua);
+        let state = state_rc.borrow();
+        let frame = state.widgets.get(lud_to_id(ud)).filter(|f| f.backdrop.enabled);
+        Ok(color_values(frame.map(|f| &f.backdrop.border_color)))
+    })?)?;
+    Ok(())
+}
+
+/// Read `r, g, b[, a]` call arguments; missing components default to 1.
+fn color_from_args(args: mlua::MultiValue) -> Color {
+    let mut it = args.into_iter();
+    let r = val_to_f32(it.next(), 1.0);
+    let g = val_to_f32(it.next(), 1.0);
+    let b = val_to_f32(it.next(), 1.0);
+    let a = val_to_f32(it.next(), 1.0);
+    Color::new(r, g, b, a)
+}
+
+/// Color as four return values, or four nils when there is no backdrop.
+fn color_values(c: Option<&Color>) -> (Option<f32>, Option<f32>, Option<f32>, Option<f32>) {
+    match c {
+        Some(c) => (Some(c.r), Some(c.g), Some(c.b), Some(c.a)),
+        None => (None, None, None, None),
+    }
+}
@@ -2,7 +2,6 @@
 
 use super::methods_helpers::{calculate_frame_height, calculate_frame_width};
 use crate::lua_api::frame::handle::{frame_lud, get_sim_state, lud_to_id};
-use crate::lua_api::layout::compute_frame_rect;
 use crate::lua_api::SimState;
 use mlua::{LightUserData, Lua, Value};
 
@@ -77,7 +76,7 @@ fn add_identity_methods(lua: &Lua, methods: &mlua::Table) -> mlua::Result<()> {
             .get(id)
             .map(|f| f.widget_type)
             .unwrap_or(WidgetType::Frame);
-        Ok(widget_type_is_a(wt, &type_name))
+        Ok(wt.is_object_type(&type_name))
     })?)?;
 
     Ok(())
@@ -153,6 +152,7 @@ fn add_size_setters(lua: &Lua, methods: &mlua::Table) -> mlua::Result<()> {
         let mut state = state_rc.borrow_mut();
         if let Some(frame) = state.widgets.get_mut_visual(id) {
             frame.height = height;
+            frame.has_fixed_height = height > 0.0;
         }
         state.widgets.mark_rect_dirty(id);
         state.invalidate_layout_with_dependents(id);
@@ -177,6 +177,69 @@ fn effective_scale(widgets: &crate::widget::WidgetRegistry, id: u64) -> f32 {
     scale
 }
 
+/// Frame rect in WoW coordinates: bottom-left origin, in the frame's own
+/// (effective-scale) units.
+#[derive(Clone, Copy)]
+struct WowRect {
+    left: f32,
+    bottom: f32,
+    width: f32,
+    height: f32,
+}
+
+/// Resolve pending layout and return the frame's computed screen-space rect.
+///
+/// Forces a recompute when the frame's rect is dirty, like GetSize does.
+fn resolved_layout_rect(state: &mut SimState, id: u64) -> crate::LayoutRect {
+    state.resolve_rect_if_dirty(id);
+    state.ensure_layout_rects();
+    let (sw, sh) = screen_dims(state);
+    state
+        .widgets
+        .get(id)
+        .and_then(|f| f.layout_rect)
+        .unwrap_or_else(|| crate::iced_app::compute_frame_rect(&state.widgets, id, sw, sh))
+}
+
+/// The frame's layout rect converted to WoW coordinates.
+fn frame_wow_rect(lua: &Lua, id: u64) -> WowRect {
+    let state_rc = get_sim_state(lua);
+    let mut state = state_rc.borrow_mut();
+    let rect = resolved_layout_rect(&mut state, id);
+    let scale = effective_scale(&state.widgets, id).max(f32::EPSILON);
+    let sh = state.screen_height;
+    WowRect {
+        left: rect.x / scale,
+        bottom: (sh - rect.y - rect.height) / scale,
+        width: rect.width / scale,
+        height: rect.height / scale,
+    }
+}
+
+/// Union of the screen-space rects of a frame and its shown descendants.
+fn bounds_layout_rect(state: &mut SimState, id: u64) -> crate::LayoutRect {
+    let rect = resolved_layout_rect(state, id);
+    let (mut min_x, mut min_y) = (rect.x, rect.y);
+    let (mut max_x, mut max_y) = (rect.x + rect.width, rect.y + rect.height);
+    let mut stack: Vec<u64> = state.widgets.get(id).map(|f| f.children.clone()).unwrap_or_default();
+    while let Some(child_id) = stack.pop() {
+        let Some(child) = state.widgets.get(child_id) else { continue };
+        if !child.visible {
+            continue;
+        }
+        stack.extend(child.children.iter().copied());
+        let Some(r) = child.layout_rect else { continue };
+        if r.width <= 0.0 || r.height <= 0.0 {
+            continue;
+        }
+        min_x = min_x.min(r.x);
+        min_y = min_y.min(r.y);
+        max_x = max_x.max(r.x + r.width);
+        max_y = max_y.max(r.y + r.height);
+    }
+    crate::LayoutRect { x: min_x, y: min_y, width: max_x - min_x, height: max_y - min_y }
+}
+
 /// Rect/position methods
 fn add_rect_methods(lua: &Lua, methods: &mlua::Table) -> mlua::Result<()> {
     add_rect_full_methods(lua, methods)?;
@@ -184,38 +247,37 @@ fn add_rect_methods(lua: &Lua, methods: &mlua::Table) -> mlua::Result<()> {
     Ok(())
 }
 
-/// GetRect, GetScaledRect, GetBounds
+/// GetRect, GetScaledRect, GetBounds, GetBoundsRect
 fn add_rect_full_methods(lua: &Lua, methods: &mlua::Table) -> mlua::Result<()> {
     methods.set("GetRect", lua.create_function(|lua, ud: LightUserData| {
-        let id = lud_to_id(ud);
-        let state_rc = get_sim_state(lua);
-        let state = state_rc.borrow();
-        let (screen_width, screen_height) = screen_dims(&state);
-        let rect = compute_frame_rect(&state.widgets, id, screen_width, screen_height);
-        let bottom = screen_height - rect.y - rect.height;
-        Ok((rect.x, bottom, rect.width, rect.height))
+        let r = frame_wow_rect(lua, lud_to_id(ud));
+        Ok((r.left, r.bottom, r.width, r.height))
     })?)?;
 
+    // Screen pixels, i.e. the layout rect with effective scale applied.
     methods.set("GetScaledRect", lua.create_function(|lua, ud: LightUserData| {
         let id = lud_to_id(ud);
         let state_rc = get_sim_state(lua);
-        let state = state_rc.borrow();
-        let (screen_width, screen_height) = screen_dims(&state);
-        let rect = compute_frame_rect(&state.widgets, id, screen_width, screen_height);
-        let scale = effective_scale(&state.widgets, id);
-        let left = rect.x * scale;
-        let bottom = (screen_height - rect.y - rect.height) * scale;
-        Ok((left, bottom, rect.width * scale, rect.height * scale))
+        let mut state = state_rc.borrow_mut();
+        let rect = resolved_layout_rect(&mut state, id);
+        let bottom = state.screen_height - rect.y - rect.height;
+        Ok((rect.x, bottom, rect.width, rect.height))
     })?)?;
 
     methods.set("GetBounds", lua.create_function(|lua, ud: LightUserData| {
+        let r = frame_wow_rect(lua, lud_to_id(ud));
+        Ok((r.left, r.bottom, r.width, r.height))
+    })?)?;
+
+    // Bounding box of the frame and its shown children and regions.
+    methods.set("GetBoundsRect", lua.create_function(|lua, ud: LightUserData| {
         let id = lud_to_id(ud);
         let state_rc = get_sim_state(lua);
-        let state = state_rc.borrow();
-        let (screen_width, screen_height) = screen_dims(&state);
-        let rect = compute_frame_rect(&state.widgets, id, screen_width, screen_height);
-        let bottom = screen_height - rect.y - rect.height;
-        Ok((rect.x, bottom, rect.width, rect.height))
+        let mut state = state_rc.borrow_mut();
+        let rect = bounds_layout_rect(&mut state, id);
+        let scale = effective_scale(&state.widgets, id).max(f32::EPSILON);
+        let bottom = state.screen_height - rect.y - rect.height;
+        Ok((rect.x / scale, bottom / scale, rect.width / scale, rect.height / scale))
     })?)?;
 
     Ok(())
@@ -224,126 +286,160 @@ fn add_rect_full_methods(lua: &Lua, methods: &mlua::Table) -> mlua::Result<()> {
 /// GetLeft, GetRight, GetTop, GetBottom, GetCenter
 fn add_rect_edge_methods(lua: &Lua, methods: &mlua::Table) -> mlua::Result<()> {
     methods.set("GetLeft", lua.create_function(|lua, ud: LightUserData| {
-        let id = lud_to_id(ud);
-        let state_rc = get_sim_state(lua);
-        let state = state_rc.borrow();
-        let (sw, sh) = screen_dims(&state);
-        let rect = compute_frame_rect(&state.widgets, id, sw, sh);
-        Ok(rect.x)
+        Ok(frame_wow_rect(lua, lud_to_id(ud)).left)
     })?)?;
 
     methods.set("GetRight", lua.create_function(|lua, ud: LightUserData| {
-        let id = lud_to_id(ud);
-        let state_rc = get_sim_state(lua);
-        let state = state_rc.borrow();
-        let (sw, sh) = screen_dims(&state);
-        let rect = compute_frame_rect(&state.widgets, id, sw, sh);
-        Ok(rect.x + rect.width)
+        let r = frame_wow_rect(lua, lud_to_id(ud));
+        Ok(r.left + r.width)
     })?)?;
 
     methods.set("GetTop", lua.create_function(|lua, ud: LightUserData| {
-        let id = lud_to_id(ud);
-        let state_rc = get_sim_state(lua);
-        let state = state_rc.borrow();
-        let (sw, sh) = screen_dims(&state);
-        let rect = compute_frame_rect(&state.widgets, id, sw, sh);
-        Ok(sh - rect.y)
+        let r = frame_wow_rect(lua, lud_to_id(ud));
+        Ok(r.bottom + r.height)
     })?)?;
 
     methods.set("GetBottom", lua.create_function(|lua, ud: LightUserData| {
-        let id = lud_to_id(ud);
-        let state_rc = get_sim_state(lua);
-        let state = state_rc.borrow();
-        let (sw, sh) = screen_dims(&state);
-        let rect = compute_frame_rect(&state.widgets, id, sw, sh);
-        Ok(sh - rect.y - rect.height)
+        Ok(frame_wow_rect(lua, lud_to_id(ud)).bottom)
     })?)?;
 
     methods.set("GetCenter", lua.create_function(|lua, ud: LightUserData| {
-        let id = lud_to_id(ud);
-        let state_rc = get_sim_state(lua);
-        let state = state_rc.borrow();
-        let (sw, sh) = screen_dims(&state);
-        let rect = compute_frame_rect(&state.widgets, id, sw, sh);
-        let cx = rect.x + rect.width / 2.0;
-        let cy = sh - rect.y - rect.height / 2.0;
-        Ok((cx, cy))
+        let r = frame_wow_rect(lua, lud_to_id(ud));
+        Ok((r.left + r.width / 2.0, r.bottom + r.height / 2.0))
     })?)?;
 
     Ok(())
 }
 
+/// Whether a frame and every ancestor are shown (WoW `IsVisible`).
+pub(crate) fn is_effectively_visible(widgets: &crate::widget::WidgetRegistry, id: u64) -> bool {
+    let mut cur = id;
+    loop {
+        match widgets.get(cur) {
+            Some(f) if f.visible => match f.parent_id {
+                Some(pid) => cur = pid,
+                None => return true,
+            },
+            _ => return false,
+        }
+    }
+}
+
+/// Whether a frame's parent chain is visible (true for parentless frames).
+fn parent_chain_visible(widgets: &crate::widget::WidgetRegistry, id: u64) -> bool {
+    match widgets.get(id).and_then(|f| f.parent_id) {
+        Some(pid) => is_effectively_visible(widgets, pid),
+        None => true,
+    }
+}
+
 /// Fire OnShow on a frame and recursively on its visible children.
 pub(crate) fn fire_on_show_recursive(
     lua: &Lua,
     id: u64,
 ) -> mlua::Result<()> {
-    if let Some(handler) = crate::lua_api::script_helpers::get_script(lua, id, "OnShow") {
-        let frame_val = frame_lud(id);
-        if let Err(e) = handler.call::<()>(frame_val) {
+    fire_visibility_script(lua, id, "OnShow")
+}
+
+/// Fire OnHide on a frame and recursively on its shown children.
+pub(crate) fn fire_on_hide_recursive(
+    lua: &Lua,
+    id: u64,
+) -> mlua::Result<()> {
+    fire_visibility_script(lua, id, "OnHide")
+}
+
+/// Fire `script` top-down on `id` and the shown descendants it affects.
+///
+/// The affected frames are collected before any handler runs, so a handler
+/// that shows or hides a child doesn't make it fire twice: a child shown by
+/// its parent's OnShow already got OnShow from its own Show(), and one hidden
+/// there got OnHide and is skipped here. Each frame fires only while its
+/// visibility still matches the transition.
+fn fire_visibility_script(lua: &Lua, id: u64, script: &str) -> mlua::Result<()> {
+    let showing = script == "OnShow";
+    let state_rc = get_sim_state(lua);
+    let affected = {
+        let st = state_rc.borrow();
+        let mut order = Vec::new();
+        let mut stack = vec![id];
+        while let Some(fid) = stack.pop() {
+            order.push(fid);
+            if let Some(f) = st.widgets.get(fid) {
+                stack.extend(f.children.iter().rev()
+                    .filter(|&&cid| st.widgets.get(cid).is_some_and(|c| c.visible)));
+            }
+        }
+        order
+    };
+
+    for fid in affected {
+        let still_matches = is_effectively_visible(&state_rc.borrow().widgets, fid) == showing;
+        if !still_matches {
+            continue;
+        }
+        if let Some(handler) = crate::lua_api::script_helpers::get_script(lua, fid, script)
+            && let Err(e) = handler.call::<()>(frame_lud(fid))
+        {
             crate::lua_api::script_helpers::call_error_handler(lua, &e.to_string());
         }
     }
+    Ok(())
+}
 
-    let children: Vec<u64> = {
-        let state_rc = get_sim_state(lua);
-        let st = state_rc.borrow();
-        st.widgets
-            .get(id)
-            .map(|f| {
-                f.children
-                    .iter()
-                    .filter(|&&cid| st.widgets.get(cid).map(|c| c.visible).unwrap_or(false))
-                    .copied()
-                    .collect()
-            })
-            .unwrap_or_default()
+/// Show a frame. OnShow fires (on it and its shown descendants) only when
+/// this makes the frame visible, i.e. it was hidden and its parents are visible.
+pub(crate) fn show_frame(lua: &Lua, id: u64) -> mlua::Result<()> {
+    let state_rc = get_sim_state(lua);
+    let (was_hidden, parent_visible) = {
+        let state = state_rc.borrow();
+        let was_hidden = state.widgets.get(id).map(|f| !f.visible).unwrap_or(false);
+        (was_hidden, parent_chain_visible(&state.widgets, id))
     };
-
-    for child_id in children {
-        fire_on_show_recursive(lua, child_id)?;
+    if !was_hidden {
+        return Ok(());
+    }
+    state_rc.borrow_mut().set_frame_visible(id, true);
+    if parent_visible {
+        fire_on_show_recursive(lua, id)?;
     }
+    Ok(())
+}
 
+/// Hide a frame. OnHide fires (on it and its shown descendants) only when
+/// the frame was actually visible before.
+pub(crate) fn hide_frame(lua: &Lua, id: u64) -> mlua::Result<()> {
+    let state_rc = get_sim_state(lua);
+    let (was_shown, was_visible) = {
+        let state = state_rc.borrow();
+        let was_shown = state.widgets.get(id).map(|f| f.visible).unwrap_or(false);
+        (was_shown, is_effectively_visible(&state.widgets, id))
+    };
+    if !was_shown {
+        return Ok(());
+    }
+    state_rc.borrow_mut().set_frame_visible(id, false);
+    if was_visible {
+        fire_on_hide_recursive(lua, id)?;
+    }
     Ok(())
 }
 
 /// Visibility methods: Show, Hide, IsVisible, IsShown, SetShown
 fn add_visibility_methods(lua: &Lua, methods: &mlua::Table) -> mlua::Result<()> {
     methods.set("Show", lua.create_function(|lua, ud: LightUserData| {
-        let id = lud_to_id(ud);
-        let state_rc = get_sim_state(lua);
-        let was_hidden = {
-            let state = state_rc.borrow();
-            state.widgets.get(id).map(|f| !f.visible).unwrap_or(false)
-        };
-        state_rc.borrow_mut().set_frame_visible(id, true);
-        if was_hidden {
-            fire_on_show_recursive(lua, id)?;
-        }
-        Ok(())
+        show_frame(lua, lud_to_id(ud))
     })?)?;
 
     methods.set("Hide", lua.create_function(|lua, ud: LightUserData| {
-        let id = lud_to_id(ud);
-        let state_rc = get_sim_state(lua);
-        state_rc.borrow_mut().set_frame_visible(id, false);
-        Ok(())
+        hide_frame(lua, lud_to_id(ud))
     })?)?;
 
     methods.set("IsVisible", lua.create_function(|lua, ud: LightUserData| {
         let id = lud_to_id(ud);
         let state_rc = get_sim_state(lua);
         let state = state_rc.borrow();
-        let mut cur = id;
-        loop {
-            match state.widgets.get(cur) {
-                Some(f) if f.visible => match f.parent_id {
-                    Some(pid) => cur = pid,
-                    None => return Ok(true),
-                },
-                _ => return Ok(false),
-            }
-        }
+        Ok(is_effectively_visible(&state.widgets, id))
     })?)?;
 
     methods.set("IsShown", lua.create_function(|lua, ud: LightUserData| {
@@ -356,9 +452,7 @@ fn add_visibility_methods(lua: &Lua, methods: &mlua::Table) -> mlua::Result<()>
 
     methods.set("SetShown", lua.create_function(|lua, (ud, shown): (LightUserData, bool)| {
         let id = lud_to_id(ud);
-        let state_rc = get_sim_state(lua);
-        state_rc.borrow_mut().set_frame_visible(id, shown);
-        Ok(())
+        if shown { show_frame(lua, id) } else { hide_frame(lua, id) }
     })?)?;
 
     Ok(())
@@ -422,7 +516,7 @@ fn add_alpha_methods(lua: &Lua, methods: &mlua::Table) -> mlua::Result<()> {
         let id = lud_to_id(ud);
         let state_rc = get_sim_state(lua);
         let state = state_rc.borrow();
-        Ok(state.widgets.get(id).map(|f| f.effective_alpha).unwrap_or(1.0))
+        Ok(state.widgets.alpha_through_parents(id))
     })?)?;
 
     methods.set("SetAlphaFromBoolean", lua.create_function(|lua, (ud, flag): (LightUserData, bool)| {
@@ -511,6 +605,7 @@ fn add_level_methods(lua: &Lua, methods: &mlua::Table) -> mlua::Result<()> {
             frame.has_fixed_frame_level = true;
         }
         super::methods_hierarchy::propagate_strata_level_pub(&mut state.widgets, id);
+        state.strata_buckets = None;
         Ok(())
     })?)?;
 
@@ -561,11 +656,16 @@ fn add_mouse_input_methods(lua: &Lua, methods: &mlua::Table) -> mlua::Result<()>
     methods.set("GetMapID", lua.create_function(|_, _ud: LightUserData| Ok(0))?)?;
     methods.set("SetMapID", lua.create_function(|_, (_ud, _map_id): (LightUserData, i32)| Ok(()))?)?;
 
+    // EnableMouse toggles both clicks and motion; SetMouseClickEnabled /
+    // SetMouseMotionEnabled adjust them individually.
     methods.set("EnableMouse", lua.create_function(|lua, (ud, enable): (LightUserData, bool)| {
         let id = lud_to_id(ud);
         let state_rc = get_sim_state(lua);
         let mut state = state_rc.borrow_mut();
-        if let Some(frame) = state.widgets.get_mut(id) { frame.mouse_enabled = enable; }
+        if let Some(frame) = state.widgets.get_mut(id) {
+            frame.mouse_enabled = enable;
+            frame.mouse_motion_enabled = enable;
+        }
         Ok(())
     })?)?;
 
@@ -573,7 +673,7 @@ fn add_mouse_input_methods(lua: &Lua, methods: &mlua::Table) -> mlua::Result<()>
         let id = lud_to_id(ud);
         let state_rc = get_sim_state(lua);
         let state = state_rc.borrow();
-        Ok(state.widgets.get(id).map(|f| f.mouse_enabled).unwrap_or(false))
+        Ok(state.widgets.get(id).map(|f| f.is_mouse_hittable()).unwrap_or(false))
     })?)?;
 
     methods.set("EnableMouseWheel", lua.create_function(|_, (_ud, _enable): (LightUserData, bool)| Ok(()))?)?;
@@ -699,7 +799,21 @@ fn add_region_query_methods(lua: &Lua, methods: &mlua::Table) -> mlua::Result<()
     })?)?;
 
     methods.set("IsObjectLoaded", lua.create_function(|_, _ud: LightUserData| Ok(true))?)?;
-    methods.set("IsMouseOver", lua.create_function(|_, (_ud, _args): (LightUserData, mlua::MultiValue)| Ok(true))?)?;
+    // IsMouseOver([top, bottom, left, right]) - offsets move each edge in WoW
+    // coordinates (positive top/right grow the rect up/right).
+    methods.set("IsMouseOver", lua.create_function(|lua, (ud, top, bottom, left, right): (LightUserData, Option<f32>, Option<f32>, Option<f32>, Option<f32>)| {
+        let id = lud_to_id(ud);
+        let state_rc = get_sim_state(lua);
+        let mut state = state_rc.borrow_mut();
+        let Some((mx, my)) = state.mouse_position else { return Ok(false) };
+        let rect = resolved_layout_rect(&mut state, id);
+        let scale = effective_scale(&state.widgets, id);
+        let min_x = rect.x + left.unwrap_or(0.0) * scale;
+        let max_x = rect.x + rect.width + right.unwrap_or(0.0) * scale;
+        let min_y = rect.y - top.unwrap_or(0.0) * scale;
+        let max_y = rect.y + rect.height - bottom.unwrap_or(0.0) * scale;
+        Ok(mx >= min_x && mx <= max_x && my >= min_y && my <= max_y)
+    })?)?;
 
     methods.set("IsMouseMotionFocus", lua.create_function(|lua, ud: LightUserData| {
         let id = lud_to_id(ud);
@@ -716,19 +830,3 @@ fn add_region_query_methods(lua: &Lua, methods: &mlua::Table) -> mlua::Result<()
 
     Ok(())
 }
-
-/// Check if a widget type is or inherits from the given type name.
-fn widget_type_is_a(wt: crate::widget::WidgetType, type_name: &str) -> bool {
-    use crate::widget::WidgetType;
-    if wt.as_str().eq_ignore_ascii_case(type_name) {
-        return true;
-    }
-    match type_name.to_ascii_lowercase().as_str() {
-        "region" => true,
-        "frame" => !matches!(wt, WidgetType::FontString | WidgetType::Texture | WidgetType::Line),
-        "texture" => matches!(wt, WidgetType::Texture | WidgetType::Line),
-        "line" => matches!(wt, WidgetType::Line),
-        "button" => matches!(wt, WidgetType::Button | WidgetType::CheckButton),
-        _ => false,
-    }
-}
@@ -59,15 +59,17 @@ fn register_child_widget(
         state.widgets.register(child);
         state.widgets.add_child(parent_id, child_id);
 
-        // Inherit strata and level from parent (regions render in parent's context)
+        // Inherit strata, level and effective scale from parent (regions
+        // render in parent's context and are sized in its scaled space)
         let parent_props = state
             .widgets
             .get(parent_id)
-            .map(|p| (p.frame_strata, p.frame_level));
-        if let Some((parent_strata, parent_level)) = parent_props {
+            .map(|p| (p.frame_strata, p.frame_level, p.effective_scale));
+        if let Some((parent_strata, parent_level, parent_eff_scale)) = parent_props {
             if let Some(f) = state.widgets.get_mut_visual(child_id) {
                 f.frame_strata = parent_strata;
                 f.frame_level = parent_level + 1;
+                f.effective_scale = parent_eff_scale * f.scale;
             }
         }
     }
@@ -23,14 +23,26 @@ fn add_event_register_methods(lua: &Lua, methods: &mlua::Table) -> mlua::Result<
         Ok(())
     })?)?;
 
-    // Some addons pass a callback function as the last argument (non-standard)
+    // RegisterUnitEvent(event, unit1 [, unit2, ...]). Non-string args are ignored
+    // (some addons pass a callback function as the last argument, non-standard).
     methods.set("RegisterUnitEvent", lua.create_function(
-        |lua, (ud, event, _args): (LightUserData, String, mlua::Variadic<Value>)| {
+        |lua, (ud, event, args): (LightUserData, String, mlua::Variadic<Value>)| {
             let id = lud_to_id(ud);
+            let units: Vec<String> = args
+                .iter()
+                .filter_map(|v| match v {
+                    Value::String(s) => s.to_str().ok().map(|s| s.to_string()),
+                    _ => None,
+                })
+                .collect();
             let state_rc = get_sim_state(lua);
             let mut state = state_rc.borrow_mut();
             if let Some(frame) = state.widgets.get_mut(id) {
-                frame.register_event(&event);
+                if units.is_empty() {
+                    frame.register_event(&event);
+                } else {
+                    frame.register_unit_event(&event, units);
+                }
             }
             Ok(())
         },
@@ -51,7 +63,7 @@ fn add_event_register_methods(lua: &Lua, methods: &mlua::Table) -> mlua::Result<
         let state_rc = get_sim_state(lua);
         let mut state = state_rc.borrow_mut();
         if let Some(frame) = state.widgets.get_mut(id) {
-            frame.registered_events.clear();
+            frame.unregister_all_events();
         }
         Ok(())
     })?)?;
@@ -71,14 +83,19 @@ fn add_event_register_methods(lua: &Lua, methods: &mlua::Table) -> mlua::Result<
 
 /// IsEventRegistered, RegisterEventCallback
 fn add_event_query_methods(lua: &Lua, methods: &mlua::Table) -> mlua::Result<()> {
+    // IsEventRegistered(event) -> isRegistered, unit1, unit2, ... (RegisterUnitEvent units)
     methods.set("IsEventRegistered", lua.create_function(|lua, (ud, event): (LightUserData, String)| {
         let id = lud_to_id(ud);
         let state_rc = get_sim_state(lua);
         let state = state_rc.borrow();
-        if let Some(frame) = state.widgets.get(id) {
-            return Ok(frame.register_all_events || frame.registered_events.contains(&event));
+        let Some(frame) = state.widgets.get(id).filter(|f| f.is_registered_for_event(&event)) else {
+            return Ok(mlua::MultiValue::from_vec(vec![Value::Boolean(false)]));
+        };
+        let mut ret = vec![Value::Boolean(true)];
+        for unit in frame.unit_event_filters.get(&event).into_iter().flatten() {
+            ret.push(Value::String(lua.create_string(unit)?));
         }
-        Ok(false)
+        Ok(mlua::MultiValue::from_vec(ret))
     })?)?;
 
     // RegisterEventCallback(event, callbackContainer) - callback-based event registration
@@ -19,8 +19,11 @@ fn add_parent_methods(lua: &Lua, methods: &mlua::Table) -> mlua::Result<()> {
         let id = lud_to_id(ud);
         let state_rc = get_sim_state(lua);
         let state = state_rc.borrow();
+        // Top-level frames (UIParent, WorldFrame) and regions whose parent is
+        // gone have no parent: nil, not an error.
         if let Some(frame) = state.widgets.get(id)
             && let Some(parent_id) = frame.parent_id
+            && state.widgets.get(parent_id).is_some()
         {
             // Return the canonical LightUserData for the parent.
             return Ok(frame_lud(parent_id));
@@ -91,17 +94,45 @@ fn reparent_widget(widgets: &mut WidgetRegistry, child_id: u64, new_parent_id: O
 
 /// SetParentKey, GetParentKey
 fn add_parent_key_methods(lua: &Lua, methods: &mlua::Table) -> mlua::Result<()> {
-    methods.set("SetParentKey", lua.create_function(|lua, (ud, key): (LightUserData, String)| {
-        let id = lud_to_id(ud);
-        let state_rc = get_sim_state(lua);
-        let mut state = state_rc.borrow_mut();
-        let parent_id = state.widgets.get(id).and_then(|f| f.parent_id);
-        if let Some(pid) = parent_id
-            && let Some(parent) = state.widgets.get_mut_visual(pid) {
-                parent.children_keys.insert(key, id);
+    // SetParentKey(key [, clearOtherKeys]): `parent[key] = self`.
+    methods.set("SetParentKey", lua.create_function(
+        |lua, (ud, key, clear_other_keys): (LightUserData, String, Option<bool>)| {
+            let id = lud_to_id(ud);
+            let state_rc = get_sim_state(lua);
+            let mut cleared = Vec::new();
+            let parent_id = {
+                let mut state = state_rc.borrow_mut();
+                let parent_id = state.widgets.get(id).and_then(|f| f.parent_id);
+                if let Some(pid) = parent_id
+                    && let Some(parent) = state.widgets.get_mut_visual(pid) {
+                        if clear_other_keys.unwrap_or(false) {
+                            parent.children_keys.retain(|k, &mut cid| {
+                                let other = cid == id && *k != key;
+                                if other {
+                                    cleared.push(k.clone());
+                                }
+                                !other
+                            });
+                        }
+                        parent.children_keys.insert(key.clone(), id);
+                    }
+                parent_id
+            };
+            // Keep the Lua-side fields in step so `parent.key` and rawget-style
+            // walks over the frame fields both see the child, and the keys
+            // cleared above no longer do.
+            if let Some(pid) = parent_id {
+                let fields = crate::lua_api::script_helpers::get_or_create_frame_fields(lua, pid);
+                for old_key in cleared {
+                    if fields.raw_get::<Value>(old_key.as_str())? == frame_lud(id) {
+                        fields.raw_set(old_key, Value::Nil)?;
+                    }
+                }
+                fields.set(key, frame_lud(id))?;
             }
-        Ok(())
-    })?)?;
+            Ok(())
+        },
+    )?)?;
 
     methods.set("GetParentKey", lua.create_function(|lua, ud: LightUserData| {
         let id = lud_to_id(ud);
@@ -128,27 +159,14 @@ fn add_children_frame_methods(lua: &Lua, methods: &mlua::Table) -> mlua::Result<
         let id = lud_to_id(ud);
         let state_rc = get_sim_state(lua);
         let state = state_rc.borrow();
-        let count = state
-            .widgets
-            .get(id)
-            .map(|f| f.children.len())
-            .unwrap_or(0);
-        Ok(count as i32)
+        Ok(child_frame_ids(&state.widgets, id).len() as i32)
     })?)?;
 
     methods.set("GetChildren", lua.create_function(|lua, ud: LightUserData| {
         let id = lud_to_id(ud);
         let state_rc = get_sim_state(lua);
-        let state = state_rc.borrow();
-        let mut result = mlua::MultiValue::new();
-        if let Some(frame) = state.widgets.get(id) {
-            let children = frame.children.clone();
-            drop(state);
-            for child_id in children {
-                result.push_back(frame_lud(child_id));
-            }
-        }
-        Ok(result)
+        let children = child_frame_ids(&state_rc.borrow().widgets, id);
+        Ok(children.into_iter().map(frame_lud).collect::<mlua::MultiValue>())
     })?)?;
 
     Ok(())
@@ -157,42 +175,17 @@ fn add_children_frame_methods(lua: &Lua, methods: &mlua::Table) -> mlua::Result<
 /// GetNumRegions, GetRegions, GetAdditionalRegions
 fn add_children_region_methods(lua: &Lua, methods: &mlua::Table) -> mlua::Result<()> {
     methods.set("GetNumRegions", lua.create_function(|lua, ud: LightUserData| {
-        use crate::widget::WidgetType;
         let id = lud_to_id(ud);
         let state_rc = get_sim_state(lua);
         let state = state_rc.borrow();
-        let count = state.widgets.get(id).map(|f| {
-            f.children.iter().filter(|&&cid| {
-                state.widgets.get(cid).map(|c| {
-                    matches!(c.widget_type, WidgetType::Texture | WidgetType::FontString | WidgetType::Line)
-                }).unwrap_or(false)
-            }).count()
-        }).unwrap_or(0);
-        Ok(count as i32)
+        Ok(region_ids(&state.widgets, id).len() as i32)
     })?)?;
 
     methods.set("GetRegions", lua.create_function(|lua, ud: LightUserData| {
-        use crate::widget::WidgetType;
         let id = lud_to_id(ud);
         let state_rc = get_sim_state(lua);
-        let state = state_rc.borrow();
-        let mut result = mlua::MultiValue::new();
-        if let Some(frame) = state.widgets.get(id) {
-            let children = frame.children.clone();
-            drop(state);
-            for child_id in children {
-                let is_region = {
-                    let state = state_rc.borrow();
-                    state.widgets.get(child_id).map(|f| {
-                        matches!(f.widget_type, WidgetType::Texture | WidgetType::FontString | WidgetType::Line)
-                    }).unwrap_or(false)
-                };
-                if is_region {
-                    result.push_back(frame_lud(child_id));
-                }
-            }
-        }
-        Ok(result)
+        let regions = region_ids(&state_rc.borrow().widgets, id);
+        Ok(regions.into_iter().map(frame_lud).collect::<mlua::MultiValue>())
     })?)?;
 
     methods.set("GetAdditionalRegions", lua.create_function(
@@ -202,6 +195,32 @@ fn add_children_region_methods(lua: &Lua, methods: &mlua::Table) -> mlua::Result
     Ok(())
 }
 
+/// Child frames of `id` (regions excluded), in creation order.
+fn child_frame_ids(widgets: &WidgetRegistry, id: u64) -> Vec<u64> {
+    let Some(frame) = widgets.get(id) else { return Vec::new() };
+    frame
+        .children
+        .iter()
+        .copied()
+        .filter(|&cid| widgets.get(cid).is_some_and(|c| !c.widget_type.is_region()))
+        .collect()
+}
+
+/// Regions of `id` in draw order: by draw layer, then sublevel, then
+/// creation order.
+fn region_ids(widgets: &WidgetRegistry, id: u64) -> Vec<u64> {
+    let Some(frame) = widgets.get(id) else { return Vec::new() };
+    let mut regions: Vec<_> = frame
+        .children
+        .iter()
+        .filter_map(|&cid| widgets.get(cid))
+        .filter(|c| c.widget_type.is_region())
+        .map(|c| (c.draw_layer, c.draw_sub_layer, c.id))
+        .collect();
+    regions.sort_by_key(|&(layer, sub_layer, _)| (layer, sub_layer));
+    regions.into_iter().map(|(_, _, cid)| cid).collect()
+}
+
 /// Public wrapper for propagation, used by SetFrameLevel in methods_core.
 pub fn propagate_strata_level_pub(widgets: &mut WidgetRegistry, root_id: u64) {
     propagate_strata_level(widgets, root_id);
@@ -3,7 +3,7 @@
 use super::super::super::handle::{get_sim_state, lud_to_id};
 use super::{is_simple_html, is_text_type, val_to_f32, val_to_f64};
 use crate::loader::helpers::lua_global_ref;
-use crate::lua_api::simple_html::TextStyle;
+use crate::lua_api::simple_html::{TextStyle, refresh_html_blocks};
 use mlua::{LightUserData, Lua, Value};
 
 /// Add title, border, portrait, and shadow methods.
@@ -200,6 +200,7 @@ fn set_shadow_offset_html(lua: &Lua, id: u64, type_str: &str, args_vec: &[Value]
         let style = data.text_styles.entry(type_str.to_string()).or_insert_with(TextStyle::default);
         style.shadow_offset = (x as f32, y as f32);
     }
+    refresh_html_blocks(&mut state, id);
     Ok(())
 }
 
@@ -273,6 +274,7 @@ fn set_shadow_color_html(lua: &Lua, id: u64, type_str: &str, args_vec: &[Value])
         let style = data.text_styles.entry(type_str.to_string()).or_insert_with(TextStyle::default);
         style.shadow_color = (r, g, b, a);
     }
+    refresh_html_blocks(&mut state, id);
     Ok(())
 }
 
@@ -68,7 +68,7 @@ fn measure_text_width(lua: &Lua, id: u64) -> mlua::Result<f64> {
         let mut fs = fs_rc.borrow_mut();
         Ok(fs.measure_text_width(&text, font_path.as_deref(), font_size) as f64)
     } else {
-        Ok(text.len() as f64 * 7.0)
+        Ok(crate::lua_api::globals::utf8_api::utf8_len(text.as_bytes()) as f64 * 7.0)
     }
 }
 
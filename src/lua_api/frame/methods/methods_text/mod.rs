@@ -4,7 +4,7 @@ mod decor;
 mod measure;
 
 use crate::lua_api::frame::handle::{frame_lud, get_sim_state, lud_to_id};
-use crate::lua_api::simple_html::TextStyle;
+use crate::lua_api::simple_html::{TextStyle, refresh_html_blocks};
 use crate::widget::WidgetType;
 use mlua::{LightUserData, Lua, Value};
 
@@ -95,35 +95,18 @@ fn add_text_get_set_methods(lua: &Lua, methods: &mlua::Table) -> mlua::Result<()
     Ok(())
 }
 
-/// Auto-size a FontString's width to match its text content.
-///
-/// Skips if the FontString has word-wrap with an explicit width constraint.
+/// Auto-size a FontString to its text content (see `measure_and_apply_sizes`).
 fn auto_size_fontstring(
     lua: &Lua,
     state_rc: &std::rc::Rc<std::cell::RefCell<crate::lua_api::SimState>>,
     id: u64,
 ) {
-    let measure_info = {
+    let measure = {
         let state = state_rc.borrow();
-        state.widgets.get(id).and_then(|f| {
-            if f.widget_type != WidgetType::FontString { return None; }
-            if f.word_wrap && f.width > 0.0 { return None; }
-            let text = f.text.as_ref()?.clone();
-            Some((text, f.font.clone(), f.font_size))
-        })
+        state.widgets.get(id).and_then(|f| fontstring_measure(id, f))
     };
-    if let Some((text, font, font_size)) = measure_info {
-        if let Some(fs_rc) = lua.app_data_ref::<std::rc::Rc<std::cell::RefCell<crate::render::font::WowFontSystem>>>() {
-            let mut fs = fs_rc.borrow_mut();
-            let width = fs.measure_text_width(&text, font.as_deref(), font_size);
-            let mut state = state_rc.borrow_mut();
-            let changed = state.widgets.get(id).map(|f| f.width != width).unwrap_or(false);
-            if changed {
-                if let Some(frame) = state.widgets.get_mut_visual(id) {
-                    frame.width = width;
-                }
-            }
-        }
+    if let Some(measure) = measure {
+        measure_and_apply_sizes(lua, state_rc, &[measure]);
     }
 }
 
@@ -131,6 +114,7 @@ fn auto_size_fontstring(
 /// Tooltip: clears lines and sets first line with optional color/wrap.
 /// SimpleHTML: strips HTML tags before storing.
 /// Button: propagates text to the child Text FontString.
+/// EditBox: clamps to SetMaxLetters, moves the cursor, fires OnTextChanged.
 /// FontString: auto-sizes height and width to fit content.
 fn handle_set_text(lua: &Lua, id: u64, args: mlua::MultiValue) -> mlua::Result<()> {
     let mut args_iter = args.into_iter();
@@ -148,13 +132,20 @@ fn handle_set_text(lua: &Lua, id: u64, args: mlua::MultiValue) -> mlua::Result<(
         update_tooltip_line(&mut state, id, text, &mut args_iter);
     }
 
-    let (text_child_id, is_html) = {
+    let (text_child_id, is_html, is_editbox) = {
         let f = state.widgets.get(id);
         let child = f.and_then(|f| f.children_keys.get("Text").copied());
         let html = state.simple_htmls.contains_key(&id);
-        (child, html)
+        let editbox = f.is_some_and(|f| f.widget_type == WidgetType::EditBox);
+        (child, html, editbox)
     };
 
+    if is_editbox {
+        drop(state);
+        return set_editbox_text(lua, id, text_str.as_deref().unwrap_or(""));
+    }
+
+    let html_source = if is_html { text_str.clone() } else { None };
     let store_text = text_str.map(|t| {
         if is_html {
             super::widget_tooltip::strip_html_tags(&t)
@@ -164,55 +155,113 @@ fn handle_set_text(lua: &Lua, id: u64, args: mlua::MultiValue) -> mlua::Result<(
     });
 
     set_text_on_frame(&mut state, id, store_text.clone());
+    if is_html {
+        if let Some(data) = state.simple_htmls.get_mut(&id) {
+            data.source = html_source.unwrap_or_default();
+        }
+        refresh_html_blocks(&mut state, id);
+    }
 
     // For Buttons, also set text on the Text fontstring child
     if let Some(text_id) = text_child_id {
         set_text_on_frame(&mut state, text_id, store_text);
     }
 
-    let ids_to_measure = collect_fontstring_measure_ids(&state, id, text_child_id);
+    let to_measure = collect_fontstring_measures(&state, id, text_child_id);
     drop(state);
 
-    measure_and_apply_widths(lua, &state_rc, &ids_to_measure);
+    measure_and_apply_sizes(lua, &state_rc, &to_measure);
+    Ok(())
+}
+
+/// EditBox SetText: clamp to max letters, move the cursor to the end and
+/// fire OnTextChanged(self, false) when the text actually changed.
+fn set_editbox_text(lua: &Lua, id: u64, text: &str) -> mlua::Result<()> {
+    let changed = {
+        let state_rc = get_sim_state(lua);
+        let mut state = state_rc.borrow_mut();
+        let old = state.widgets.get(id).map(|f| f.text.clone().unwrap_or_default());
+        match state.widgets.get_mut_visual(id) {
+            Some(frame) => {
+                frame.editbox_set_text(text);
+                old.as_deref() != frame.text.as_deref()
+            }
+            None => false,
+        }
+    };
+    if changed {
+        super::widget_editbox::fire_text_changed(lua, id, false)?;
+    }
     Ok(())
 }
 
-/// Collect FontString IDs that need width measurement after text changes.
-fn collect_fontstring_measure_ids(
+/// A FontString's text and font, captured for measurement outside the state borrow.
+struct FontStringMeasure {
+    id: u64,
+    text: String,
+    font: Option<String>,
+    font_size: f32,
+    /// Set for word-wrapped FontStrings with a fixed width.
+    wrap_width: Option<f32>,
+    /// Height was set explicitly, so wrapping must not change it.
+    fixed_height: bool,
+}
+
+fn fontstring_measure(id: u64, f: &crate::widget::Frame) -> Option<FontStringMeasure> {
+    if f.widget_type != WidgetType::FontString { return None; }
+    Some(FontStringMeasure {
+        id,
+        text: f.text.as_ref()?.clone(),
+        font: f.font.clone(),
+        font_size: f.font_size,
+        wrap_width: (f.word_wrap && f.width > 0.0).then_some(f.width),
+        fixed_height: f.has_fixed_height,
+    })
+}
+
+/// Collect FontStrings that need measurement after text changes.
+fn collect_fontstring_measures(
     state: &std::cell::RefMut<'_, crate::lua_api::SimState>,
     id: u64,
     text_child_id: Option<u64>,
-) -> Vec<(u64, String, Option<String>, f32)> {
+) -> Vec<FontStringMeasure> {
     [Some(id), text_child_id]
         .into_iter()
         .flatten()
-        .filter_map(|fid| {
-            let f = state.widgets.get(fid)?;
-            if f.widget_type != WidgetType::FontString { return None; }
-            if f.word_wrap && f.width > 0.0 { return None; }
-            let text = f.text.as_ref()?.clone();
-            Some((fid, text, f.font.clone(), f.font_size))
-        })
+        .filter_map(|fid| fontstring_measure(fid, state.widgets.get(fid)?))
         .collect()
 }
 
-/// Measure text widths and apply to frames that changed.
-fn measure_and_apply_widths(
+/// Measure text and apply the result to frames that changed.
+///
+/// Word-wrapped FontStrings with a fixed width keep it and take the wrapped
+/// text height, unless their height was set explicitly; all others take the
+/// single-line text width.
+fn measure_and_apply_sizes(
     lua: &Lua,
     state_rc: &std::rc::Rc<std::cell::RefCell<crate::lua_api::SimState>>,
-    ids_to_measure: &[(u64, String, Option<String>, f32)],
+    to_measure: &[FontStringMeasure],
 ) {
-    if ids_to_measure.is_empty() { return; }
+    if to_measure.is_empty() { return; }
     if let Some(fs_rc) = lua.app_data_ref::<std::rc::Rc<std::cell::RefCell<crate::render::font::WowFontSystem>>>() {
         let mut fs = fs_rc.borrow_mut();
         let mut state = state_rc.borrow_mut();
-        for (fid, text, font, font_size) in ids_to_measure {
-            let width = fs.measure_text_width(text, font.as_deref(), *font_size);
-            let changed = state.widgets.get(*fid).map(|f| f.width != width).unwrap_or(false);
-            if changed {
-                if let Some(frame) = state.widgets.get_mut_visual(*fid) {
-                    frame.width = width;
-                }
+        for m in to_measure {
+            let font = m.font.as_deref();
+            let current_height = state.widgets.get(m.id).map_or(0.0, |f| f.height);
+            let (width, height) = match m.wrap_width {
+                Some(wrap) if m.fixed_height => (wrap, current_height),
+                Some(wrap) => match fs.measure_text_wrapped(&m.text, font, m.font_size, wrap) {
+                    (_, _, 0) => (wrap, current_height),
+                    (_, height, _) => (wrap, height),
+                },
+                None => (fs.measure_text_width(&m.text, font, m.font_size), current_height),
+            };
+            let changed = state.widgets.get(m.id)
+                .is_some_and(|f| f.width != width || f.height != height);
+            if changed && let Some(frame) = state.widgets.get_mut_visual(m.id) {
+                frame.width = width;
+                frame.height = height;
             }
         }
     }
@@ -339,6 +388,7 @@ fn set_font_for_text_type(
             style.font_size = s;
         }
     }
+    refresh_html_blocks(&mut state, id);
     Ok(true)
 }
 
@@ -483,10 +533,27 @@ fn add_font_object_extra_methods(lua: &Lua, methods: &mlua::Table) -> mlua::Resu
         },
     )?)?;
 
-    // GetNumLines() - return number of visible text lines
-    methods.set("GetNumLines", lua.create_function(
-        |_lua, _ud: LightUserData| Ok(1_i32),
-    )?)?;
+    // GetNumLines() - number of displayed lines, after wrapping and SetMaxLines
+    methods.set("GetNumLines", lua.create_function(|lua, ud: LightUserData| {
+        let id = lud_to_id(ud);
+        let (measure, max_lines) = {
+            let state_rc = get_sim_state(lua);
+            let state = state_rc.borrow();
+            match state.widgets.get(id) {
+                Some(f) => (fontstring_measure(id, f), f.max_lines),
+                None => return Ok(1_i32),
+            }
+        };
+        let Some(m) = measure else { return Ok(1) };
+        let Some(fs_rc) = lua.app_data_ref::<std::rc::Rc<std::cell::RefCell<crate::render::font::WowFontSystem>>>() else {
+            return Ok(1);
+        };
+        let (_, _, lines) = fs_rc.borrow_mut().measure_text_wrapped(
+            &m.text, m.font.as_deref(), m.font_size, m.wrap_width.unwrap_or(0.0),
+        );
+        let lines = if max_lines > 0 { lines.min(max_lines as usize) } else { lines };
+        Ok(lines.max(1) as i32)
+    })?)?;
 
     Ok(())
 }
@@ -602,6 +669,7 @@ fn set_text_color_html(lua: &Lua, id: u64, args: &[Value], type_str: String) {
         let style = data.text_styles.entry(type_str).or_insert_with(TextStyle::default);
         style.text_color = (r, g, b, a);
     }
+    refresh_html_blocks(&mut state, id);
 }
 
 /// Apply SetTextColor for standard FontString/Frame widgets.
@@ -695,6 +763,7 @@ fn add_justification_methods(lua: &Lua, methods: &mlua::Table) -> mlua::Result<(
                             let style = data.text_styles.entry(type_str).or_insert_with(TextStyle::default);
                             style.justify_h = justify;
                         }
+                        refresh_html_blocks(&mut state, id);
                     }
                     return Ok(());
                 }
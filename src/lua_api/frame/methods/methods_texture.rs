@@ -17,6 +17,7 @@ pub fn add_texture_methods(lua: &Lua, methods: &mlua::Table) -> mlua::Result<()>
     add_tex_coord_methods(lua, methods)?;
     add_mask_methods(lua, methods)?;
     add_rotation_methods(lua, methods)?;
+    add_gradient_methods(lua, methods)?;
     add_draw_layer_methods(lua, methods)?;
     add_visual_methods(lua, methods)?;
     Ok(())
@@ -39,6 +40,7 @@ fn add_texture_path_methods(lua: &Lua, methods: &mlua::Table) -> mlua::Result<()
         let state_rc = get_sim_state(lua);
         let mut state = state_rc.borrow_mut();
         if let Some(frame) = state.widgets.get_mut_visual(id) {
+            clear_atlas(frame);
             frame.texture = path;
             if let Some(h) = horiz_tile { frame.horiz_tile = h; }
             if let Some(v) = vert_tile { frame.vert_tile = v; }
@@ -62,6 +64,7 @@ fn add_texture_path_methods(lua: &Lua, methods: &mlua::Table) -> mlua::Result<()
         let state_rc = get_sim_state(lua);
         let mut state = state_rc.borrow_mut();
         if let Some(frame) = state.widgets.get_mut_visual(id) {
+            clear_atlas(frame);
             frame.color_texture =
                 Some(crate::widget::Color::new(r, g, b, a.unwrap_or(1.0)));
             // Clear file texture when setting color texture
@@ -73,6 +76,17 @@ fn add_texture_path_methods(lua: &Lua, methods: &mlua::Table) -> mlua::Result<()
     Ok(())
 }
 
+/// Forget a previous SetAtlas (name and the UVs it applied) so GetAtlas
+/// reports nil once a plain texture or color replaces it.
+fn clear_atlas(frame: &mut crate::widget::Frame) {
+    if frame.atlas.take().is_some() {
+        frame.atlas_tex_coords = None;
+        frame.tex_coords = None;
+        frame.tex_coords_quad = None;
+        frame.nine_slice_atlas = None;
+    }
+}
+
 /// SetHorizTile, GetHorizTile, SetVertTile, GetVertTile.
 fn add_tiling_methods(lua: &Lua, methods: &mlua::Table) -> mlua::Result<()> {
     methods.set("SetHorizTile", lua.create_function(|lua, (ud, tile): (LightUserData, bool)| {
@@ -339,6 +353,7 @@ fn apply_atlas_to_frame(
         );
         frame.atlas_tex_coords = Some(atlas_uvs);
         frame.tex_coords = Some(atlas_uvs);
+        frame.tex_coords_quad = None;
         frame.horiz_tile = atlas_info.tiles_horizontally;
         frame.vert_tile = atlas_info.tiles_vertically;
         frame.atlas = Some(atlas_name.to_string());
@@ -494,11 +509,16 @@ fn add_tex_coord_methods(lua: &Lua, methods: &mlua::Table) -> mlua::Result<()> {
         let id = lud_to_id(ud);
         let state_rc = get_sim_state(lua);
         let state = state_rc.borrow();
-        if let Some(frame) = state.widgets.get(id)
-            && let Some((left, right, top, bottom)) = frame.tex_coords {
+        if let Some(frame) = state.widgets.get(id) {
+            // 8-arg form: return the corners as given (may be rotated/flipped).
+            if let Some(q) = frame.tex_coords_quad {
+                return Ok((q[0], q[1], q[2], q[3], q[4], q[5], q[6], q[7]));
+            }
+            if let Some((left, right, top, bottom)) = frame.tex_coords {
                 // Return 8 values: UL, LL, UR, LR corners
                 return Ok((left, top, left, bottom, right, top, right, bottom));
             }
+        }
         // Default: full texture
         Ok((0.0_f32, 0.0_f32, 0.0_f32, 1.0_f32, 1.0_f32, 0.0_f32, 1.0_f32, 1.0_f32))
     })?)?;
@@ -592,7 +612,7 @@ fn remap_tex_coords(
     }
 }
 
-/// AddMaskTexture, RemoveMaskTexture, GetNumMaskTextures, GetMaskTexture.
+/// AddMaskTexture, RemoveMaskTexture, GetNumMaskTextures, GetMaskTexture, SetMask.
 fn add_mask_methods(lua: &Lua, methods: &mlua::Table) -> mlua::Result<()> {
     methods.set("AddMaskTexture", lua.create_function(|lua, (ud, mask): (LightUserData, Value)| {
         let id = lud_to_id(ud);
@@ -629,23 +649,36 @@ fn add_mask_methods(lua: &Lua, methods: &mlua::Table) -> mlua::Result<()> {
         Ok(state.widgets.get(id).map_or(0, |f| f.mask_textures.len()))
     })?)?;
 
-    methods.set("GetMaskTexture", lua.create_function(|_, (_ud, _index): (LightUserData, i32)| Ok(Value::Nil))?)?;
-
-    Ok(())
-}
+    methods.set("GetMaskTexture", lua.create_function(|lua, (ud, index): (LightUserData, i64)| {
+        let id = lud_to_id(ud);
+        let state_rc = get_sim_state(lua);
+        let state = state_rc.borrow();
+        let mask_id = state.widgets.get(id)
+            .and_then(|f| f.mask_textures.get(usize::try_from(index - 1).ok()?).copied());
+        Ok(mask_id.map_or(Value::Nil, frame_lud))
+    })?)?;
 
-/// SetRotation, GetRotation - rotate texture UVs around center.
-fn add_rotation_methods(lua: &Lua, methods: &mlua::Table) -> mlua::Result<()> {
-    methods.set("SetRotation", lua.create_function(|lua, (ud, radians): (LightUserData, f64)| {
+    // SetMask(file): legacy file mask over the whole texture; nil or "" clears it.
+    methods.set("SetMask", lua.create_function(|lua, (ud, path): (LightUserData, Option<String>)| {
         let id = lud_to_id(ud);
         let state_rc = get_sim_state(lua);
         let mut state = state_rc.borrow_mut();
         if let Some(frame) = state.widgets.get_mut_visual(id) {
-            frame.rotation = radians as f32;
+            frame.mask_file = path.filter(|p| !p.is_empty());
         }
         Ok(())
     })?)?;
 
+    Ok(())
+}
+
+/// SetRotation, GetRotation - rotate the texture quad around a pivot.
+fn add_rotation_methods(lua: &Lua, methods: &mlua::Table) -> mlua::Result<()> {
+    methods.set("SetRotation", lua.create_function(|lua, (ud, args): (LightUserData, mlua::MultiValue)| {
+        set_rotation(lua, lud_to_id(ud), &args);
+        Ok(())
+    })?)?;
+
     methods.set("GetRotation", lua.create_function(|lua, ud: LightUserData| {
         let id = lud_to_id(ud);
         let state_rc = get_sim_state(lua);
@@ -660,10 +693,104 @@ fn add_rotation_methods(lua: &Lua, methods: &mlua::Table) -> mlua::Result<()> {
     Ok(())
 }
 
-/// SetGradient, SetDrawLayer, GetDrawLayer.
-fn add_draw_layer_methods(lua: &Lua, methods: &mlua::Table) -> mlua::Result<()> {
-    methods.set("SetGradient", lua.create_function(|_, (_ud, _args): (LightUserData, mlua::MultiValue)| Ok(()))?)?;
+/// Apply `SetRotation(radians[, pivot])` to frame `id`. The pivot is either a
+/// vector table (`CreateVector2D(x, y)`) or two numbers, in normalized frame
+/// coordinates; it defaults to the center.
+pub(super) fn set_rotation(lua: &Lua, id: u64, args: &mlua::MultiValue) {
+    let number = |v: Option<&Value>| match v {
+        Some(Value::Number(n)) => Some(*n as f32),
+        Some(Value::Integer(n)) => Some(*n as f32),
+        _ => None,
+    };
+    let radians = number(args.front()).unwrap_or(0.0);
+    let pivot = match args.get(1) {
+        Some(Value::Table(t)) => t.get::<f32>("x").ok().zip(t.get::<f32>("y").ok()),
+        other => number(other).zip(number(args.get(2))),
+    };
+    let state_rc = get_sim_state(lua);
+    let mut state = state_rc.borrow_mut();
+    if let Some(frame) = state.widgets.get_mut_visual(id) {
+        frame.rotation = radians;
+        frame.rotation_pivot = pivot.unwrap_or((0.5, 0.5));
+    }
+}
 
+/// SetGradient, SetGradientAlpha - two-color vertex gradient.
+fn add_gradient_methods(lua: &Lua, methods: &mlua::Table) -> mlua::Result<()> {
+    // Modern form: SetGradient(orientation, minColor, maxColor) with color tables.
+    // Legacy form: SetGradient(orientation, r1, g1, b1, r2, g2, b2).
+    methods.set("SetGradient", lua.create_function(|lua, (ud, args): (LightUserData, mlua::MultiValue)| {
+        let args: Vec<Value> = args.into_iter().collect();
+        let gradient = parse_gradient_args(&args, false);
+        set_gradient(lua, lud_to_id(ud), gradient);
+        Ok(())
+    })?)?;
+
+    // Legacy: SetGradientAlpha(orientation, r1, g1, b1, a1, r2, g2, b2, a2).
+    methods.set("SetGradientAlpha", lua.create_function(|lua, (ud, args): (LightUserData, mlua::MultiValue)| {
+        let args: Vec<Value> = args.into_iter().collect();
+        let gradient = parse_gradient_args(&args, true);
+        set_gradient(lua, lud_to_id(ud), gradient);
+        Ok(())
+    })?)?;
+
+    Ok(())
+}
+
+fn set_gradient(lua: &Lua, id: u64, gradient: Option<crate::widget::Gradient>) {
+    let Some(gradient) = gradient else { return };
+    let state_rc = get_sim_state(lua);
+    let mut state = state_rc.borrow_mut();
+    if let Some(frame) = state.widgets.get_mut_visual(id) {
+        frame.gradient = Some(gradient);
+    }
+}
+
+/// Parse gradient args after `self`: orientation followed by either two color
+/// tables or flat RGB(A) components (`with_alpha` selects RGBA for the legacy form).
+fn parse_gradient_args(args: &[Value], with_alpha: bool) -> Option<crate::widget::Gradient> {
+    use crate::widget::{Color, Gradient, GradientOrientation};
+    let orientation = match args.first() {
+        Some(Value::String(s)) => GradientOrientation::from_str(&s.to_string_lossy())?,
+        _ => return None,
+    };
+    let (min_color, max_color) = match (args.get(1), args.get(2)) {
+        (Some(Value::Table(min)), Some(Value::Table(max))) => {
+            (color_from_table(min), color_from_table(max))
+        }
+        _ => {
+            let n = if with_alpha { 4 } else { 3 };
+            let comps: Vec<f32> = args[1..].iter().take(n * 2).filter_map(value_as_f32).collect();
+            if comps.len() < n * 2 {
+                return None;
+            }
+            let alpha = |i: usize| if with_alpha { comps[i] } else { 1.0 };
+            (
+                Color::new(comps[0], comps[1], comps[2], alpha(3)),
+                Color::new(comps[n], comps[n + 1], comps[n + 2], alpha(n + 3)),
+            )
+        }
+    };
+    Some(Gradient { orientation, min_color, max_color })
+}
+
+/// Read a `CreateColor`-style table (`r`, `g`, `b`, optional `a`).
+fn color_from_table(t: &mlua::Table) -> crate::widget::Color {
+    let get = |k: &str, default: f32| t.get::<Option<f32>>(k).ok().flatten().unwrap_or(default);
+    crate::widget::Color::new(get("r", 1.0), get("g", 1.0), get("b", 1.0), get("a", 1.0))
+}
+
+/// Convert a Lua Value to f32, or None if it's not a number.
+fn value_as_f32(v: &Value) -> Option<f32> {
+    match v {
+        Value::Number(n) => Some(*n as f32),
+        Value::Integer(n) => Some(*n as f32),
+        _ => None,
+    }
+}
+
+/// SetDrawLayer, GetDrawLayer.
+fn add_draw_layer_methods(lua: &Lua, methods: &mlua::Table) -> mlua::Result<()> {
     methods.set("SetDrawLayer", lua.create_function(|lua, (ud, args): (LightUserData, mlua::MultiValue)| {
         use crate::widget::DrawLayer;
         let id = lud_to_id(ud);
@@ -27,7 +27,7 @@ mod widget_scroll;
 mod widget_slider;
 mod widget_tooltip;
 
-pub(crate) use methods_core::fire_on_show_recursive;
+pub(crate) use methods_core::{hide_frame, show_frame};
 
 /// Register all ~200 frame methods into the shared methods table.
 pub fn register_all_methods(lua: &mlua::Lua, methods: &mlua::Table) -> mlua::Result<()> {
@@ -38,7 +38,6 @@ pub fn register_all_methods(lua: &mlua::Lua, methods: &mlua::Table) -> mlua::Res
     methods_event::add_event_methods(lua, methods)?;
     methods_script::add_script_methods(lua, methods)?;
     methods_attribute::add_attribute_methods(lua, methods)?;
-    methods_backdrop::add_backdrop_methods(lua, methods)?;
     methods_create::add_create_methods(lua, methods)?;
     methods_texture::add_texture_methods(lua, methods)?;
     methods_text::add_text_methods(lua, methods)?;
@@ -47,3 +46,10 @@ pub fn register_all_methods(lua: &mlua::Lua, methods: &mlua::Table) -> mlua::Res
     methods_line::add_line_methods(lua, methods)?;
     Ok(())
 }
+
+/// Register methods that Lua mixins may override (consulted by `__index`
+/// after custom fields, so mixin-provided methods win).
+pub fn register_fallback_methods(lua: &mlua::Lua, fallbacks: &mlua::Table) -> mlua::Result<()> {
+    methods_backdrop::add_backdrop_methods(lua, fallbacks)?;
+    Ok(())
+}
@@ -32,8 +32,14 @@ fn add_cooldown_set_methods(lua: &Lua, methods: &mlua::Table) -> mlua::Result<()
         let state_rc = get_sim_state(lua);
         let mut state = state_rc.borrow_mut();
         if let Some(frame) = state.widgets.get_mut_visual(id) {
-            frame.cooldown_start = start;
-            frame.cooldown_duration = duration;
+            // SetCooldown(0, 0) (or any non-positive duration) clears the swipe.
+            if duration > 0.0 {
+                frame.cooldown_start = start;
+                frame.cooldown_duration = duration;
+            } else {
+                frame.cooldown_start = 0.0;
+                frame.cooldown_duration = 0.0;
+            }
         }
         Ok(())
     })?)?;
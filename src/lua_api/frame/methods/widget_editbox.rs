@@ -79,7 +79,8 @@ fn add_editbox_cursor_methods(lua: &Lua, methods: &mlua::Table) -> mlua::Result<
         let state_rc = get_sim_state(lua);
         let mut state = state_rc.borrow_mut();
         if let Some(frame) = state.widgets.get_mut_visual(id) {
-            frame.editbox_cursor_pos = pos;
+            let len = frame.text.as_ref().map(|t| t.chars().count()).unwrap_or(0);
+            frame.editbox_cursor_pos = pos.clamp(0, len as i32);
         }
         Ok(())
     })?)?;
@@ -96,14 +97,13 @@ fn add_editbox_cursor_methods(lua: &Lua, methods: &mlua::Table) -> mlua::Result<
     methods.set("Insert", lua.create_function(|lua, (ud, text): (LightUserData, String)| {
         let id = lud_to_id(ud);
         let state_rc = get_sim_state(lua);
-        let mut state = state_rc.borrow_mut();
-        if let Some(frame) = state.widgets.get_mut_visual(id) {
-            let current = frame.text.get_or_insert_with(String::new);
-            let pos = (frame.editbox_cursor_pos as usize).min(current.len());
-            current.insert_str(pos, &text);
-            frame.editbox_cursor_pos = (pos + text.len()) as i32;
+        let inserted = state_rc.borrow_mut().widgets.get_mut_visual(id)
+            .map(|frame| frame.editbox_insert(&text))
+            .unwrap_or_default();
+        if inserted.is_empty() {
+            return Ok(());
         }
-        Ok(())
+        fire_text_changed(lua, id, false)
     })?)?;
 
     methods.set("GetNumLetters", lua.create_function(|lua, ud: LightUserData| {
@@ -120,6 +120,17 @@ fn add_editbox_cursor_methods(lua: &Lua, methods: &mlua::Table) -> mlua::Result<
     Ok(())
 }
 
+/// Fire OnTextChanged(self, userInput). `userInput` is false for changes
+/// made from Lua (SetText, Insert) and true for typed keys.
+pub(super) fn fire_text_changed(lua: &Lua, frame_id: u64, user_input: bool) -> mlua::Result<()> {
+    if let Some(func) = crate::lua_api::script_helpers::get_script(lua, frame_id, "OnTextChanged")
+        && let Some(frame_ud) = crate::lua_api::script_helpers::get_frame_ref(lua, frame_id)
+            && let Err(e) = func.call::<()>((frame_ud, user_input)) {
+                crate::lua_api::script_helpers::call_error_handler(lua, &e.to_string());
+            }
+    Ok(())
+}
+
 fn add_editbox_number_methods(lua: &Lua, methods: &mlua::Table) -> mlua::Result<()> {
     methods.set("SetNumber", lua.create_function(|lua, (ud, n): (LightUserData, f64)| {
         let id = lud_to_id(ud);
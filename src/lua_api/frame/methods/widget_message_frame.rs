@@ -58,6 +58,7 @@ fn add_message_frame_add_methods(lua: &Lua, methods: &mlua::Table) -> mlua::Resu
             data.messages.clear();
             data.scroll_offset = 0;
         }
+        state.hyperlink_regions.remove(&id);
         Ok(())
     })?)?;
 
@@ -82,7 +83,7 @@ fn add_message_frame_count_methods(lua: &Lua, methods: &mlua::Table) -> mlua::Re
         let data = state.message_frames.entry(id)
             .or_insert_with(crate::lua_api::message_frame::MessageFrameData::default);
         data.max_lines = max_lines.max(1) as usize;
-        data.messages.truncate(data.max_lines);
+        data.trim_to_max_lines();
         Ok(())
     })?)?;
 
@@ -212,22 +213,50 @@ fn add_message_frame_insert_methods(lua: &Lua, methods: &mlua::Table) -> mlua::R
 }
 
 fn add_message_frame_scroll_methods(lua: &Lua, methods: &mlua::Table) -> mlua::Result<()> {
-    // Scroll methods (no-ops for now, no visual scrolling)
-    methods.set("ScrollUp", lua.create_function(|_, _ud: LightUserData| Ok(()))?)?;
-    methods.set("ScrollDown", lua.create_function(|_, _ud: LightUserData| Ok(()))?)?;
-    methods.set("PageUp", lua.create_function(|_, _ud: LightUserData| Ok(()))?)?;
-    methods.set("PageDown", lua.create_function(|_, _ud: LightUserData| Ok(()))?)?;
-    methods.set("ScrollToTop", lua.create_function(|_, _ud: LightUserData| Ok(()))?)?;
-    methods.set("ScrollToBottom", lua.create_function(|_, _ud: LightUserData| Ok(()))?)?;
-    methods.set("AtTop", lua.create_function(|_, _ud: LightUserData| Ok(true))?)?;
-    methods.set("AtBottom", lua.create_function(|_, _ud: LightUserData| Ok(true))?)?;
+    // Scrolling moves `scroll_offset` through the scrollback, one message per
+    // step; a page is PAGE_MESSAGES messages (the renderer shows what fits).
+    const PAGE_MESSAGES: i32 = 10;
+    let scroll_fns: [(&str, fn(&mut crate::lua_api::message_frame::MessageFrameData)); 6] = [
+        ("ScrollUp", |d| d.scroll_by(1)),
+        ("ScrollDown", |d| d.scroll_by(-1)),
+        ("PageUp", |d| d.scroll_by(PAGE_MESSAGES)),
+        ("PageDown", |d| d.scroll_by(-PAGE_MESSAGES)),
+        ("ScrollToTop", |d| d.scroll_offset = d.max_scroll_range()),
+        ("ScrollToBottom", |d| d.scroll_offset = 0),
+    ];
+    for (name, apply) in scroll_fns {
+        methods.set(name, lua.create_function(move |lua, ud: LightUserData| {
+            let id = lud_to_id(ud);
+            let state_rc = get_sim_state(lua);
+            let mut state = state_rc.borrow_mut();
+            if let Some(data) = state.message_frames.get_mut(&id)
+                && data.scroll_allowed {
+                    apply(data);
+                    state.hyperlink_regions.remove(&id);
+                }
+            Ok(())
+        })?)?;
+    }
+
+    methods.set("AtTop", lua.create_function(|lua, ud: LightUserData| {
+        let state_rc = get_sim_state(lua);
+        let state = state_rc.borrow();
+        Ok(state.message_frames.get(&lud_to_id(ud))
+            .is_none_or(|d| d.scroll_offset >= d.max_scroll_range()))
+    })?)?;
+    methods.set("AtBottom", lua.create_function(|lua, ud: LightUserData| {
+        let state_rc = get_sim_state(lua);
+        let state = state_rc.borrow();
+        Ok(state.message_frames.get(&lud_to_id(ud)).is_none_or(|d| d.scroll_offset == 0))
+    })?)?;
 
     methods.set("SetScrollOffset", lua.create_function(|lua, (ud, offset): (LightUserData, i32)| {
         let id = lud_to_id(ud);
         let state_rc = get_sim_state(lua);
         let mut state = state_rc.borrow_mut();
         if let Some(data) = state.message_frames.get_mut(&id) {
-            data.scroll_offset = offset;
+            data.scroll_offset = offset.clamp(0, data.max_scroll_range());
+            state.hyperlink_regions.remove(&id);
         }
         Ok(())
     })?)?;
@@ -242,7 +271,11 @@ fn add_message_frame_scroll_methods(lua: &Lua, methods: &mlua::Table) -> mlua::R
         Ok(offset)
     })?)?;
 
-    methods.set("GetMaxScrollRange", lua.create_function(|_, _ud: LightUserData| Ok(0_i32))?)?;
+    methods.set("GetMaxScrollRange", lua.create_function(|lua, ud: LightUserData| {
+        let state_rc = get_sim_state(lua);
+        let state = state_rc.borrow();
+        Ok(state.message_frames.get(&lud_to_id(ud)).map_or(0, |d| d.max_scroll_range()))
+    })?)?;
 
     methods.set("SetScrollAllowed", lua.create_function(|lua, (ud, allowed): (LightUserData, bool)| {
         let id = lud_to_id(ud);
@@ -356,10 +389,12 @@ fn add_message_core(state: &mut SimState, id: u64, args: mlua::MultiValue, log:
     if log {
         log_message(state, id, &text);
     }
-    let timestamp = state.start_time.elapsed().as_secs_f64();
+    let timestamp = state.clock.now();
     let data = state.message_frames.entry(id).or_default();
     insert_message(data, text, r, g, b, a, message_id, timestamp);
-    truncate_messages(data);
+    data.trim_to_max_lines();
+    // The new message scrolls the drawn lines; their links moved with them.
+    state.hyperlink_regions.remove(&id);
 }
 
 fn backfill_message(state: &mut SimState, id: u64, args: mlua::MultiValue) {
@@ -370,15 +405,16 @@ fn backfill_message(state: &mut SimState, id: u64, args: mlua::MultiValue) {
     };
     let (r, g, b, a) = extract_rgba(&args_vec, 1);
     log_message(state, id, &text);
-    let timestamp = state.start_time.elapsed().as_secs_f64();
+    let timestamp = state.clock.now();
     let data = state.message_frames.entry(id)
         .or_insert_with(crate::lua_api::message_frame::MessageFrameData::default);
-    data.messages.insert(0, crate::lua_api::message_frame::Message {
+    data.messages.push_front(crate::lua_api::message_frame::Message {
         text, r, g, b, a, message_id: None, timestamp,
     });
     if data.messages.len() > data.max_lines {
-        data.messages.pop();
+        data.messages.pop_back();
     }
+    state.hyperlink_regions.remove(&id);
 }
 
 fn insert_message(
@@ -390,19 +426,9 @@ fn insert_message(
         text, r, g, b, a, message_id, timestamp,
     };
     if data.insert_mode == "TOP" {
-        data.messages.insert(0, msg);
+        data.messages.push_front(msg);
     } else {
-        data.messages.push(msg);
-    }
-}
-
-fn truncate_messages(data: &mut crate::lua_api::message_frame::MessageFrameData) {
-    if data.messages.len() > data.max_lines {
-        if data.insert_mode == "TOP" {
-            data.messages.pop();
-        } else {
-            data.messages.remove(0);
-        }
+        data.messages.push_back(msg);
     }
 }
 
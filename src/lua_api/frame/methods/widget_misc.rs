@@ -1,12 +1,15 @@
 //! Miscellaneous widget methods: ColorSelect, drag/move/resize, SimpleHTML, and stubs.
 
-use crate::lua_api::frame::handle::{get_sim_state, lud_to_id};
-use crate::widget::AttributeValue;
+use crate::lua_api::frame::handle::{extract_frame_id, frame_lud, get_sim_state, lud_to_id};
+use crate::widget::{hsv_to_rgb, rgb_to_hsv};
 use mlua::{LightUserData, Lua, Value};
+use std::cell::RefCell;
+use std::rc::Rc;
 
 pub fn add_colorselect_methods(lua: &Lua, methods: &mlua::Table) -> mlua::Result<()> {
     add_colorselect_rgb_methods(lua, methods)?;
     add_colorselect_hsv_methods(lua, methods)?;
+    add_colorselect_texture_methods(lua, methods)?;
     Ok(())
 }
 
@@ -66,6 +69,9 @@ fn add_simplehtml_hyperlink_methods(lua: &Lua, methods: &mlua::Table) -> mlua::R
         if let Some(data) = state.simple_htmls.get_mut(&id) {
             data.hyperlinks_enabled = enabled;
         }
+        if let Some(data) = state.message_frames.get_mut(&id) {
+            data.hyperlinks_enabled = enabled;
+        }
         Ok(())
     })?)?;
 
@@ -73,12 +79,7 @@ fn add_simplehtml_hyperlink_methods(lua: &Lua, methods: &mlua::Table) -> mlua::R
     methods.set("GetHyperlinksEnabled", lua.create_function(|lua, ud: LightUserData| {
         let id = lud_to_id(ud);
         let state_rc = get_sim_state(lua);
-        let state = state_rc.borrow();
-        let enabled = state
-            .simple_htmls
-            .get(&id)
-            .map(|d| d.hyperlinks_enabled)
-            .unwrap_or(true);
+        let enabled = state_rc.borrow().hyperlinks_enabled(id).unwrap_or(true);
         Ok(enabled)
     })?)?;
 
@@ -86,25 +87,26 @@ fn add_simplehtml_hyperlink_methods(lua: &Lua, methods: &mlua::Table) -> mlua::R
 }
 
 fn add_simplehtml_content_methods(lua: &Lua, methods: &mlua::Table) -> mlua::Result<()> {
-    // GetContentHeight() - estimate based on text length and font size
+    // GetContentHeight() - height of the stacked HTML blocks wrapped to the laid-out width
     methods.set("GetContentHeight", lua.create_function(|lua, ud: LightUserData| {
+        use crate::lua_api::simple_html::{estimate_text_height, html_content_height, stack_html_blocks};
+        use crate::render::font::WowFontSystem;
+
         let id = lud_to_id(ud);
         let state_rc = get_sim_state(lua);
-        let state = state_rc.borrow();
-        let frame = match state.widgets.get(id) {
-            Some(f) => f,
-            None => return Ok(0.0_f64),
-        };
-        let text = match &frame.text {
-            Some(t) if !t.is_empty() => t,
-            _ => return Ok(0.0_f64),
-        };
-        let font_size = frame.font_size.max(12.0) as f64;
-        let line_height = font_size * 1.2;
-        let width = frame.width.max(200.0) as f64;
-        let chars_per_line = (width / (font_size * 0.6)).max(1.0);
-        let estimated_lines = (text.len() as f64 / chars_per_line).ceil().max(1.0);
-        Ok(estimated_lines * line_height)
+        let mut state = state_rc.borrow_mut();
+        state.resolve_rect_if_dirty(id);
+        let Some(frame) = state.widgets.get(id) else { return Ok(0.0_f64) };
+        // Anchored SimpleHTML frames often have no explicit width.
+        let wrap_width = frame.layout_rect.map_or(frame.width, |r| r.width);
+        let font_sys = lua.app_data_ref::<Rc<RefCell<WowFontSystem>>>();
+        let offsets = stack_html_blocks(&frame.html_blocks, wrap_width, |text, font, size, width| {
+            match &font_sys {
+                Some(fs) => fs.borrow_mut().measure_text_height(text, font, size, (width > 0.0).then_some(width)),
+                None => estimate_text_height(text, size, width),
+            }
+        });
+        Ok(html_content_height(&offsets) as f64)
     })?)?;
 
     // GetTextData() - return empty table (no HTML parsing yet)
@@ -121,32 +123,13 @@ fn add_simplehtml_content_methods(lua: &Lua, methods: &mlua::Table) -> mlua::Res
 fn add_colorselect_rgb_methods(lua: &Lua, methods: &mlua::Table) -> mlua::Result<()> {
     // SetColorRGB(r, g, b) - Set the RGB color
     methods.set("SetColorRGB", lua.create_function(|lua, (ud, r, g, b): (LightUserData, f64, f64, f64)| {
-        let id = lud_to_id(ud);
-        let state_rc = get_sim_state(lua);
-        let mut state = state_rc.borrow_mut();
-        if let Some(frame) = state.widgets.get_mut_visual(id) {
-            frame.attributes.insert("colorR".to_string(), AttributeValue::Number(r));
-            frame.attributes.insert("colorG".to_string(), AttributeValue::Number(g));
-            frame.attributes.insert("colorB".to_string(), AttributeValue::Number(b));
-        }
-        Ok(())
+        set_color_hsv(lua, lud_to_id(ud), rgb_to_hsv(r, g, b))
     })?)?;
 
     // GetColorRGB() - Get the RGB color
     methods.set("GetColorRGB", lua.create_function(|lua, ud: LightUserData| {
-        let id = lud_to_id(ud);
-        let state_rc = get_sim_state(lua);
-        let state = state_rc.borrow();
-        if let Some(frame) = state.widgets.get(id) {
-            let get_num = |key: &str| -> f64 {
-                match frame.attributes.get(key) {
-                    Some(AttributeValue::Number(n)) => *n,
-                    _ => 1.0,
-                }
-            };
-            return Ok((get_num("colorR"), get_num("colorG"), get_num("colorB")));
-        }
-        Ok((1.0, 1.0, 1.0))
+        let (h, s, v) = color_hsv(lua, lud_to_id(ud));
+        Ok(hsv_to_rgb(h, s, v))
     })?)?;
 
     Ok(())
@@ -155,70 +138,94 @@ fn add_colorselect_rgb_methods(lua: &Lua, methods: &mlua::Table) -> mlua::Result
 fn add_colorselect_hsv_methods(lua: &Lua, methods: &mlua::Table) -> mlua::Result<()> {
     // SetColorHSV(h, s, v) - Set the HSV color
     methods.set("SetColorHSV", lua.create_function(|lua, (ud, h, s, v): (LightUserData, f64, f64, f64)| {
-        let id = lud_to_id(ud);
-        let (r, g, b) = hsv_to_rgb(h, s, v);
-        let state_rc = get_sim_state(lua);
-        let mut state = state_rc.borrow_mut();
-        if let Some(frame) = state.widgets.get_mut_visual(id) {
-            frame.attributes.insert("colorR".to_string(), AttributeValue::Number(r));
-            frame.attributes.insert("colorG".to_string(), AttributeValue::Number(g));
-            frame.attributes.insert("colorB".to_string(), AttributeValue::Number(b));
-            frame.attributes.insert("colorH".to_string(), AttributeValue::Number(h % 360.0));
-            frame.attributes.insert("colorS".to_string(), AttributeValue::Number(s));
-            frame.attributes.insert("colorV".to_string(), AttributeValue::Number(v));
-        }
-        Ok(())
+        set_color_hsv(lua, lud_to_id(ud), (h.rem_euclid(360.0), s, v))
     })?)?;
 
     // GetColorHSV() - Get the HSV color
     methods.set("GetColorHSV", lua.create_function(|lua, ud: LightUserData| {
-        let id = lud_to_id(ud);
+        Ok(color_hsv(lua, lud_to_id(ud)))
+    })?)?;
+
+    Ok(())
+}
+
+/// SetColorWheelTexture/SetColorValueTexture and their getters: the textures
+/// whose rects the wheel and value bar are drawn into.
+fn add_colorselect_texture_methods(lua: &Lua, methods: &mlua::Table) -> mlua::Result<()> {
+    type Set = fn(&mut crate::widget::Frame, Option<u64>);
+    type Get = fn(&crate::widget::Frame) -> Option<u64>;
+    let slots: [(&str, &str, Set, Get); 2] = [
+        ("SetColorWheelTexture", "GetColorWheelTexture", |f, t| f.color_wheel_texture = t, |f| f.color_wheel_texture),
+        ("SetColorValueTexture", "GetColorValueTexture", |f, t| f.color_value_texture = t, |f| f.color_value_texture),
+    ];
+    for (setter, getter, set, get) in slots {
+        methods.set(setter, lua.create_function(move |lua, (ud, texture): (LightUserData, Value)| {
+            let id = lud_to_id(ud);
+            let state_rc = get_sim_state(lua);
+            let mut state = state_rc.borrow_mut();
+            if let Some(frame) = state.widgets.get_mut_visual(id) {
+                set(frame, extract_frame_id(&texture));
+            }
+            Ok(())
+        })?)?;
+        methods.set(getter, lua.create_function(move |lua, ud: LightUserData| {
+            let id = lud_to_id(ud);
+            let state_rc = get_sim_state(lua);
+            let texture = state_rc.borrow().widgets.get(id).and_then(get);
+            Ok(texture.map(frame_lud).unwrap_or(Value::Nil))
+        })?)?;
+    }
+    Ok(())
+}
+
+fn color_hsv(lua: &Lua, id: u64) -> (f64, f64, f64) {
+    let state_rc = get_sim_state(lua);
+    let state = state_rc.borrow();
+    state.widgets.get(id).map(|f| f.color_hsv).unwrap_or((0.0, 0.0, 1.0))
+}
+
+/// Store the selected color and fire OnColorSelect(r, g, b) when it changed.
+fn set_color_hsv(lua: &Lua, id: u64, hsv: (f64, f64, f64)) -> mlua::Result<()> {
+    let changed = {
         let state_rc = get_sim_state(lua);
-        let state = state_rc.borrow();
-        if let Some(frame) = state.widgets.get(id) {
-            let get_num = |key: &str| -> Option<f64> {
-                match frame.attributes.get(key) {
-                    Some(AttributeValue::Number(n)) => Some(*n),
-                    _ => None,
-                }
-            };
-            if let (Some(h), Some(s), Some(v)) =
-                (get_num("colorH"), get_num("colorS"), get_num("colorV"))
-            {
-                return Ok((h, s, v));
+        let mut state = state_rc.borrow_mut();
+        match state.widgets.get_mut_visual(id) {
+            Some(frame) if frame.color_hsv != hsv => {
+                frame.color_hsv = hsv;
+                true
             }
-            let r: f64 = get_num("colorR").unwrap_or(1.0);
-            let g: f64 = get_num("colorG").unwrap_or(1.0);
-            let b: f64 = get_num("colorB").unwrap_or(1.0);
-            return Ok(rgb_to_hsv(r, g, b));
+            _ => false,
         }
-        Ok((0.0, 0.0, 1.0))
-    })?)?;
-
+    };
+    if changed && let Some(handler) = crate::lua_api::script_helpers::get_script(lua, id, "OnColorSelect") {
+        let (r, g, b) = hsv_to_rgb(hsv.0, hsv.1, hsv.2);
+        if let Err(e) = handler.call::<()>((frame_lud(id), r, g, b)) {
+            crate::lua_api::script_helpers::call_error_handler(lua, &e.to_string());
+        }
+    }
     Ok(())
 }
 
 // --- Drag/Move/Resize ---
 
 fn add_drag_move_methods(lua: &Lua, methods: &mlua::Table) -> mlua::Result<()> {
+    // StartMoving() - the frame follows the cursor until StopMovingOrSizing().
     methods.set("StartMoving", lua.create_function(|lua, ud: LightUserData| {
         let id = lud_to_id(ud);
         let state_rc = get_sim_state(lua);
-        if let Ok(mut s) = state_rc.try_borrow_mut()
-            && let Some(frame) = s.widgets.get_mut_visual(id)
-                && frame.movable {
-                    frame.is_moving = true;
-                }
+        if let Ok(mut s) = state_rc.try_borrow_mut() {
+            s.start_moving(id);
+        }
         Ok(())
     })?)?;
 
     methods.set("StopMovingOrSizing", lua.create_function(|lua, ud: LightUserData| {
         let id = lud_to_id(ud);
         let state_rc = get_sim_state(lua);
-        if let Ok(mut s) = state_rc.try_borrow_mut()
-            && let Some(frame) = s.widgets.get_mut_visual(id) {
-                frame.is_moving = false;
-            }
+        if let Ok(mut s) = state_rc.try_borrow_mut() {
+            s.stop_moving(id);
+            s.stop_sizing(id);
+        }
         Ok(())
     })?)?;
 
@@ -297,15 +304,49 @@ fn add_drag_clamp_methods(lua: &Lua, methods: &mlua::Table) -> mlua::Result<()>
     Ok(())
 }
 
+/// Apply `update` to a frame's (minWidth, minHeight, maxWidth, maxHeight).
+fn update_resize_bounds(lua: &Lua, ud: LightUserData, update: impl FnOnce(&mut (f32, f32, f32, f32))) {
+    let state_rc = get_sim_state(lua);
+    if let Ok(mut s) = state_rc.try_borrow_mut()
+        && let Some(frame) = s.widgets.get_mut(lud_to_id(ud)) {
+            update(&mut frame.resize_bounds);
+        }
+}
+
 fn add_drag_resize_methods(lua: &Lua, methods: &mlua::Table) -> mlua::Result<()> {
-    methods.set("SetResizeBounds", lua.create_function(|_, (_ud, _args): (LightUserData, mlua::MultiValue)| Ok(()))?)?;
-    methods.set("GetResizeBounds", lua.create_function(|_, _ud: LightUserData| {
-        Ok((0.0_f32, 0.0_f32, 0.0_f32, 0.0_f32))
+    // SetResizeBounds(minWidth, minHeight[, maxWidth, maxHeight]) - max 0 or omitted is unbounded.
+    methods.set("SetResizeBounds", lua.create_function(
+        |lua, (ud, min_w, min_h, max_w, max_h): (LightUserData, f32, f32, Option<f32>, Option<f32>)| {
+            update_resize_bounds(lua, ud, |b| *b = (min_w, min_h, max_w.unwrap_or(0.0), max_h.unwrap_or(0.0)));
+            Ok(())
+        },
+    )?)?;
+    methods.set("GetResizeBounds", lua.create_function(|lua, ud: LightUserData| {
+        let state_rc = get_sim_state(lua);
+        let s = state_rc.borrow();
+        Ok(s.widgets.get(lud_to_id(ud)).map(|f| f.resize_bounds).unwrap_or_default())
     })?)?;
     // Legacy resize bound methods (deprecated in favor of SetResizeBounds)
-    methods.set("SetMinResize", lua.create_function(|_, (_ud, _w, _h): (LightUserData, f32, f32)| Ok(()))?)?;
-    methods.set("SetMaxResize", lua.create_function(|_, (_ud, _w, _h): (LightUserData, f32, f32)| Ok(()))?)?;
-    methods.set("StartSizing", lua.create_function(|_, (_ud, _point): (LightUserData, Option<String>)| Ok(()))?)?;
+    methods.set("SetMinResize", lua.create_function(|lua, (ud, w, h): (LightUserData, f32, f32)| {
+        update_resize_bounds(lua, ud, |b| { b.0 = w; b.1 = h; });
+        Ok(())
+    })?)?;
+    methods.set("SetMaxResize", lua.create_function(|lua, (ud, w, h): (LightUserData, f32, f32)| {
+        update_resize_bounds(lua, ud, |b| { b.2 = w; b.3 = h; });
+        Ok(())
+    })?)?;
+    // StartSizing([point]) - the dragged edge or corner (default BOTTOMRIGHT) follows
+    // the cursor until StopMovingOrSizing().
+    methods.set("StartSizing", lua.create_function(|lua, (ud, point): (LightUserData, Option<String>)| {
+        let point = point
+            .and_then(|p| crate::widget::AnchorPoint::from_str(&p))
+            .unwrap_or(crate::widget::AnchorPoint::BottomRight);
+        let state_rc = get_sim_state(lua);
+        if let Ok(mut s) = state_rc.try_borrow_mut() {
+            s.start_sizing(lud_to_id(ud), point);
+        }
+        Ok(())
+    })?)?;
     methods.set("RegisterForDrag", lua.create_function(|_, (_ud, _args): (LightUserData, mlua::MultiValue)| Ok(()))?)?;
     methods.set("SetUserPlaced", lua.create_function(|_, (_ud, _user_placed): (LightUserData, bool)| Ok(()))?)?;
     methods.set("IsUserPlaced", lua.create_function(|_, _ud: LightUserData| Ok(false))?)?;
@@ -383,51 +424,3 @@ fn add_misc_stubs_mixin(lua: &Lua, methods: &mlua::Table) -> mlua::Result<()> {
 
     Ok(())
 }
-
-// --- Color conversion helpers ---
-
-/// Convert HSV to RGB.
-fn hsv_to_rgb(h: f64, s: f64, v: f64) -> (f64, f64, f64) {
-    let h = h % 360.0;
-    let c = v * s;
-    let x = c * (1.0 - ((h / 60.0) % 2.0 - 1.0).abs());
-    let m = v - c;
-
-    let (r1, g1, b1) = if h < 60.0 {
-        (c, x, 0.0)
-    } else if h < 120.0 {
-        (x, c, 0.0)
-    } else if h < 180.0 {
-        (0.0, c, x)
-    } else if h < 240.0 {
-        (0.0, x, c)
-    } else if h < 300.0 {
-        (x, 0.0, c)
-    } else {
-        (c, 0.0, x)
-    };
-
-    (r1 + m, g1 + m, b1 + m)
-}
-
-/// Convert RGB to HSV.
-fn rgb_to_hsv(r: f64, g: f64, b: f64) -> (f64, f64, f64) {
-    let max = r.max(g).max(b);
-    let min = r.min(g).min(b);
-    let delta = max - min;
-
-    let v = max;
-    let s = if max == 0.0 { 0.0 } else { delta / max };
-    let h = if delta == 0.0 {
-        0.0
-    } else if max == r {
-        60.0 * (((g - b) / delta) % 6.0)
-    } else if max == g {
-        60.0 * ((b - r) / delta + 2.0)
-    } else {
-        60.0 * ((r - g) / delta + 4.0)
-    };
-    let h = if h < 0.0 { h + 360.0 } else { h };
-
-    (h, s, v)
-}
@@ -1,7 +1,7 @@
 //! Model and ModelScene widget method stubs.
 
-use crate::lua_api::frame::handle::{get_sim_state, lud_to_id};
-use mlua::{IntoLuaMulti, LightUserData, Lua, Result, Value};
+use crate::lua_api::frame::handle::lud_to_id;
+use mlua::{IntoLuaMulti, LightUserData, Lua, Result};
 
 pub fn add_model_methods(lua: &Lua, methods: &mlua::Table) -> Result<()> {
     add_model_transform_methods(lua, methods)?;
@@ -32,21 +32,14 @@ fn add_model_transform_methods(lua: &Lua, methods: &mlua::Table) -> Result<()> {
 
     // Mixin override: ModelScenelRotateButtonMixin defines SetRotation(direction)
     // Falls through to Texture:SetRotation(radians) when no mixin override exists.
-    methods.set("SetRotation", lua.create_function(|lua, (ud, radians): (LightUserData, Value)| {
+    methods.set("SetRotation", lua.create_function(|lua, (ud, args): (LightUserData, mlua::MultiValue)| {
         let id = lud_to_id(ud);
         if let Some((func, frame_ud)) = super::methods_helpers::get_mixin_override(lua, id, "SetRotation") {
-            return func.call::<()>((frame_ud, radians));
-        }
-        let rad_f64 = match radians {
-            Value::Number(n) => n,
-            Value::Integer(n) => n as f64,
-            _ => 0.0,
-        };
-        let state_rc = get_sim_state(lua);
-        let mut state = state_rc.borrow_mut();
-        if let Some(frame) = state.widgets.get_mut_visual(id) {
-            frame.rotation = rad_f64 as f32;
+            let mut call_args = vec![frame_ud];
+            call_args.extend(args);
+            return func.call::<()>(mlua::MultiValue::from_vec(call_args));
         }
+        super::methods_texture::set_rotation(lua, id, &args);
         Ok(())
     })?)?;
 
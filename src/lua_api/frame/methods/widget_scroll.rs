@@ -29,6 +29,7 @@ fn add_scrollframe_child_methods(lua: &Lua, methods: &mlua::Table) -> mlua::Resu
         if let Some(frame) = state.widgets.get_mut_visual(id) {
             frame.scroll_child_id = child_id;
         }
+        relayout_scroll_child(&mut state, id);
         Ok(())
     })?)?;
 
@@ -57,6 +58,7 @@ fn add_scrollframe_offset_methods(lua: &Lua, methods: &mlua::Table) -> mlua::Res
         if let Some(frame) = state.widgets.get_mut_visual(id) {
             frame.scroll_horizontal = offset;
         }
+        relayout_scroll_child(&mut state, id);
         Ok(())
     })?)?;
 
@@ -75,6 +77,7 @@ fn add_scrollframe_offset_methods(lua: &Lua, methods: &mlua::Table) -> mlua::Res
             if let Some(frame) = state.widgets.get_mut_visual(id) {
                 frame.scroll_vertical = offset;
             }
+            relayout_scroll_child(&mut state, id);
         }
         fire_tooltip_script(lua, id, "OnScrollRangeChanged")?;
         Ok(())
@@ -123,3 +126,10 @@ fn add_scrollframe_range_methods(lua: &Lua, methods: &mlua::Table) -> mlua::Resu
 
     Ok(())
 }
+
+/// Recompute the scroll child's layout so the new scroll offset moves its subtree.
+fn relayout_scroll_child(state: &mut crate::lua_api::SimState, id: u64) {
+    if let Some(child_id) = state.widgets.get(id).and_then(|f| f.scroll_child_id) {
+        state.invalidate_layout(child_id);
+    }
+}
@@ -79,6 +79,9 @@ pub fn add_shared_value_methods(lua: &Lua, methods: &mlua::Table) -> Result<()>
 
 // --- Slider methods ---
 
+/// Size given to a thumb created from a texture path (UI-SliderBar-Button-Horizontal is 32x32).
+const DEFAULT_THUMB_SIZE: f32 = 32.0;
+
 fn add_slider_step_methods(lua: &Lua, methods: &mlua::Table) -> Result<()> {
     methods.set("SetValueStep", lua.create_function(|lua, (ud, step): (LightUserData, f64)| {
         let id = lud_to_id(ud);
@@ -94,7 +97,7 @@ fn add_slider_step_methods(lua: &Lua, methods: &mlua::Table) -> Result<()> {
         let id = lud_to_id(ud);
         let state_rc = get_sim_state(lua);
         let state = state_rc.borrow();
-        let step = state.widgets.get(id).map(|f| f.slider_step).unwrap_or(1.0);
+        let step = state.widgets.get(id).map(|f| f.slider_step).unwrap_or(0.0);
         Ok(step)
     })?)?;
 
@@ -113,8 +116,14 @@ fn add_slider_orientation_methods(lua: &Lua, methods: &mlua::Table) -> Result<()
             let state_rc = get_sim_state(lua);
             let mut state = state_rc.borrow_mut();
             if let Some(frame) = state.widgets.get_mut_visual(id) {
-                frame.slider_orientation = s.to_str().map(|s| s.to_uppercase()).unwrap_or_else(|_| "HORIZONTAL".to_string());
+                let orientation = s.to_str().map(|s| s.to_uppercase()).unwrap_or_else(|_| "HORIZONTAL".to_string());
+                if frame.widget_type == WidgetType::StatusBar {
+                    frame.statusbar_orientation = orientation;
+                } else {
+                    frame.slider_orientation = orientation;
+                }
             }
+            relayout_slider_thumb(&mut state, id);
         }
         Ok(())
     })?)?;
@@ -124,7 +133,10 @@ fn add_slider_orientation_methods(lua: &Lua, methods: &mlua::Table) -> Result<()
         let state_rc = get_sim_state(lua);
         let state = state_rc.borrow();
         let orientation = state.widgets.get(id)
-            .map(|f| f.slider_orientation.clone())
+            .map(|f| match f.widget_type {
+                WidgetType::StatusBar => f.statusbar_orientation.clone(),
+                _ => f.slider_orientation.clone(),
+            })
             .unwrap_or_else(|| "HORIZONTAL".to_string());
         Ok(orientation)
     })?)?;
@@ -133,10 +145,45 @@ fn add_slider_orientation_methods(lua: &Lua, methods: &mlua::Table) -> Result<()
 }
 
 fn add_slider_thumb_methods(lua: &Lua, methods: &mlua::Table) -> Result<()> {
-    methods.set("SetThumbTexture", lua.create_function(|_, (_ud, _args): (LightUserData, mlua::MultiValue)| Ok(()))?)?;
+    // SetThumbTexture(texture) or SetThumbTexture(path)
+    methods.set("SetThumbTexture", lua.create_function(|lua, (ud, texture): (LightUserData, Value)| {
+        let id = lud_to_id(ud);
+        let state_rc = get_sim_state(lua);
+        let mut state = state_rc.borrow_mut();
+        let thumb_id = match &texture {
+            Value::LightUserData(lud) => lud_to_id(*lud),
+            Value::String(s) => {
+                let thumb_id = super::methods_helpers::get_or_create_button_texture(&mut state, id, "ThumbTexture");
+                if let Some(thumb) = state.widgets.get_mut_visual(thumb_id) {
+                    thumb.texture = Some(s.to_string_lossy().to_string());
+                    thumb.anchors.clear();
+                    if thumb.width == 0.0 && thumb.height == 0.0 {
+                        thumb.width = DEFAULT_THUMB_SIZE;
+                        thumb.height = DEFAULT_THUMB_SIZE;
+                    }
+                }
+                thumb_id
+            }
+            _ => return Ok(()),
+        };
+        if let Some(frame) = state.widgets.get_mut_visual(id) {
+            frame.slider_thumb_id = Some(thumb_id);
+            frame.children_keys.insert("ThumbTexture".to_string(), thumb_id);
+        }
+        relayout_slider_thumb(&mut state, id);
+        Ok(())
+    })?)?;
 
     methods.set("GetThumbTexture", lua.create_function(|lua, ud: LightUserData| {
         let id = lud_to_id(ud);
+        let thumb_id = {
+            let state_rc = get_sim_state(lua);
+            let state = state_rc.borrow();
+            state.widgets.get(id).and_then(|f| f.slider_thumb_id)
+        };
+        if let Some(thumb_id) = thumb_id {
+            return Ok(frame_lud(thumb_id));
+        }
         get_or_create_child_texture(lua, id, "ThumbTexture")
     })?)?;
 
@@ -179,6 +226,7 @@ fn add_slider_drag_methods(lua: &Lua, methods: &mlua::Table) -> Result<()> {
 
 fn add_statusbar_texture_methods(lua: &Lua, methods: &mlua::Table) -> Result<()> {
     add_set_statusbar_texture(lua, methods)?;
+    add_set_statusbar_atlas(lua, methods)?;
     add_get_statusbar_texture(lua, methods)?;
     methods.set("SetRotatesTexture", lua.create_function(|_, (_ud, _rotates): (LightUserData, bool)| Ok(()))?)?;
     Ok(())
@@ -211,6 +259,18 @@ fn add_set_statusbar_texture(lua: &Lua, methods: &mlua::Table) -> Result<()> {
     Ok(())
 }
 
+/// SetStatusBarAtlas(atlas): same as SetStatusBarTexture with an atlas name.
+fn add_set_statusbar_atlas(lua: &Lua, methods: &mlua::Table) -> Result<()> {
+    methods.set("SetStatusBarAtlas", lua.create_function(|lua, (ud, atlas): (LightUserData, String)| {
+        let id = lud_to_id(ud);
+        let state_rc = get_sim_state(lua);
+        let mut state = state_rc.borrow_mut();
+        apply_statusbar_texture_path(&mut state, id, &atlas);
+        Ok(())
+    })?)?;
+    Ok(())
+}
+
 fn add_get_statusbar_texture(lua: &Lua, methods: &mlua::Table) -> Result<()> {
     methods.set("GetStatusBarTexture", lua.create_function(|lua, ud: LightUserData| {
         let id = lud_to_id(ud);
@@ -278,15 +338,20 @@ fn add_statusbar_fill_methods(lua: &Lua, methods: &mlua::Table) -> Result<()> {
         Ok(())
     })?)?;
 
+    methods.set("GetReverseFill", lua.create_function(|lua, ud: LightUserData| {
+        let id = lud_to_id(ud);
+        let state_rc = get_sim_state(lua);
+        let state = state_rc.borrow();
+        Ok(state.widgets.get(id).map(|f| f.statusbar_reverse_fill).unwrap_or(false))
+    })?)?;
+
     Ok(())
 }
 
 fn add_statusbar_desaturate_methods(lua: &Lua, methods: &mlua::Table) -> Result<()> {
     methods.set("SetStatusBarDesaturated", lua.create_function(|_, (_ud, _desat): (LightUserData, bool)| Ok(()))?)?;
     methods.set("GetStatusBarDesaturated", lua.create_function(|_, _ud: LightUserData| Ok(false))?)?;
-    methods.set("SetStatusBarAtlas", lua.create_function(|_, (_ud, _atlas): (LightUserData, String)| Ok(()))?)?;
     methods.set("GetFillStyle", lua.create_function(|_, _ud: LightUserData| Ok("STANDARD"))?)?;
-    methods.set("GetReverseFill", lua.create_function(|_, _ud: LightUserData| Ok(false))?)?;
     methods.set("GetRotatesTexture", lua.create_function(|_, _ud: LightUserData| Ok(false))?)?;
     Ok(())
 }
@@ -332,15 +397,22 @@ fn add_shared_set_min_max(lua: &Lua, methods: &mlua::Table) -> Result<()> {
     methods.set("SetMinMaxValues", lua.create_function(|lua, (ud, args): (LightUserData, mlua::MultiValue)| {
         let id = lud_to_id(ud);
         let (min, max) = parse_min_max_args(args);
-        let state_rc = get_sim_state(lua);
-        let mut state = state_rc.borrow_mut();
-        if !min_max_changed(&state, id, min, max) {
-            return Ok(());
-        }
-        if let Some(frame) = state.widgets.get_mut_visual(id) {
-            apply_min_max(frame, min, max);
+        let clamped = {
+            let state_rc = get_sim_state(lua);
+            let mut state = state_rc.borrow_mut();
+            if !min_max_changed(&state, id, min, max) {
+                return Ok(());
+            }
+            let Some(frame) = state.widgets.get_mut_visual(id) else { return Ok(()) };
+            let clamped = apply_min_max(frame, min, max);
+            relayout_slider_thumb(&mut state, id);
+            clamped
+        };
+        // Narrowing the range can move the value, which the client reports.
+        match clamped {
+            Some(value) => fire_value_changed(lua, id, value),
+            None => Ok(()),
         }
-        Ok(())
     })?)?;
     Ok(())
 }
@@ -365,19 +437,38 @@ fn add_shared_get_min_max(lua: &Lua, methods: &mlua::Table) -> Result<()> {
 // --- Helper functions ---
 
 fn set_slider_value(lua: &mlua::Lua, id: u64, value: f64) -> mlua::Result<()> {
-    let clamped = {
+    let snapped = {
         let state_rc = get_sim_state(lua);
         let mut state = state_rc.borrow_mut();
         let Some(frame) = state.widgets.get(id) else { return Ok(()) };
-        let clamped = value.clamp(frame.slider_min, frame.slider_max);
-        if clamped == frame.slider_value {
+        let snapped = snap_slider_value(frame, value);
+        if snapped == frame.slider_value {
             return Ok(());
         }
         let frame = state.widgets.get_mut_visual(id).unwrap();
-        frame.slider_value = clamped;
-        clamped
+        frame.slider_value = snapped;
+        relayout_slider_thumb(&mut state, id);
+        snapped
     };
-    fire_value_changed(lua, id, clamped)
+    fire_value_changed(lua, id, snapped)
+}
+
+/// Clamp `value` to the slider range and round it to the nearest step above the minimum.
+fn snap_slider_value(frame: &crate::widget::Frame, value: f64) -> f64 {
+    let (min, max, step) = (frame.slider_min, frame.slider_max, frame.slider_step);
+    let clamped = value.clamp(min, max);
+    if step > 0.0 {
+        (min + ((clamped - min) / step).round() * step).min(max)
+    } else {
+        clamped
+    }
+}
+
+/// Recompute the thumb rect after the value, range or orientation changed.
+fn relayout_slider_thumb(state: &mut crate::lua_api::SimState, id: u64) {
+    if let Some(thumb_id) = state.widgets.get(id).and_then(|f| f.slider_thumb_id) {
+        state.invalidate_layout(thumb_id);
+    }
 }
 
 fn set_statusbar_value(lua: &mlua::Lua, id: u64, value: f64) -> mlua::Result<()> {
@@ -429,20 +520,27 @@ fn min_max_changed(state: &crate::lua_api::SimState, id: u64, min: f64, max: f64
     }).unwrap_or(false)
 }
 
-fn apply_min_max(frame: &mut crate::widget::Frame, min: f64, max: f64) {
-    match frame.widget_type {
+/// Store the new range, clamping the value into it. Returns the value when clamping changed it.
+fn apply_min_max(frame: &mut crate::widget::Frame, min: f64, max: f64) -> Option<f64> {
+    let value = match frame.widget_type {
         WidgetType::Slider => {
             frame.slider_min = min;
             frame.slider_max = max;
-            frame.slider_value = frame.slider_value.clamp(min, max);
+            &mut frame.slider_value
         }
         WidgetType::StatusBar => {
             frame.statusbar_min = min;
             frame.statusbar_max = max;
-            frame.statusbar_value = frame.statusbar_value.clamp(min, max);
+            &mut frame.statusbar_value
         }
-        _ => {}
+        _ => return None,
+    };
+    let clamped = value.clamp(min, max);
+    if clamped == *value {
+        return None;
     }
+    *value = clamped;
+    Some(clamped)
 }
 
 /// Apply a texture path string to a StatusBar's bar child, creating it if needed.
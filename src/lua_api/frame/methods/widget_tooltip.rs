@@ -52,6 +52,7 @@ fn add_tooltip_owner_methods(lua: &Lua, methods: &mlua::Table) -> Result<()> {
             let mut state = state_rc.borrow_mut();
             if let Some(td) = state.tooltips.get_mut(&id) {
                 td.lines.clear();
+                td.item_id = None;
             }
         }
         fire_tooltip_script(lua, id, "OnTooltipCleared")?;
@@ -78,6 +79,7 @@ fn set_owner_impl(lua: &Lua, id: u64, args: mlua::MultiValue) -> Result<()> {
         let mut state = state_rc.borrow_mut();
         if let Some(td) = state.tooltips.get_mut(&id) {
             td.lines.clear();
+            td.item_id = None;
             td.owner_id = owner_id;
             td.anchor_type = anchor.clone();
         }
@@ -174,11 +176,17 @@ fn add_tooltip_data_query_stubs(lua: &Lua, methods: &mlua::Table) -> Result<()>
     // SetSpellByID(spellID) - Set tooltip to show spell info (no game data)
     methods.set("SetSpellByID", lua.create_function(|_, (_ud, _spell_id): (LightUserData, i32)| Ok(()))?)?;
 
-    // SetItemByID(itemID) - Set tooltip to show item info (no game data)
-    methods.set("SetItemByID", lua.create_function(|_, (_ud, _item_id): (LightUserData, i32)| Ok(()))?)?;
+    // SetItemByID(itemID) - Fill the tooltip from the embedded item table
+    methods.set("SetItemByID", lua.create_function(|lua, (ud, item_id): (LightUserData, i32)| {
+        set_item_impl(lua, lud_to_id(ud), item_id.max(0) as u32)
+    })?)?;
 
-    // SetHyperlink(link) - Set tooltip from a hyperlink (no game data)
-    methods.set("SetHyperlink", lua.create_function(|_, (_ud, _link): (LightUserData, String)| Ok(()))?)?;
+    // SetHyperlink(link) - Accepts "item:12345[:...]" or a full |Hitem:...|h link.
+    // Non-item links just clear the tooltip (no spell/quest data yet).
+    methods.set("SetHyperlink", lua.create_function(|lua, (ud, link): (LightUserData, String)| {
+        let item_id = crate::lua_api::globals::item_api::parse_item_id_from_link(&link);
+        set_item_impl(lua, lud_to_id(ud), item_id.max(0) as u32)
+    })?)?;
 
     // SetUnitBuff/Debuff/Aura stubs (no game data)
     methods.set("SetUnitBuff", lua.create_function(|_, (_ud, _args): (LightUserData, mlua::MultiValue)| Ok(()))?)?;
@@ -214,9 +222,22 @@ fn add_tooltip_query_methods(lua: &Lua, methods: &mlua::Table) -> Result<()> {
         Ok((None, None))
     })?)?;
 
-    // GetItem() - Get the item this tooltip is showing info for (no game data)
-    methods.set("GetItem", lua.create_function(|_, _ud: LightUserData| -> Result<(Option<String>, Option<String>)> {
-        Ok((None, None))
+    // GetItem() - (name, link, itemID) of the item set by SetItemByID/SetHyperlink
+    methods.set("GetItem", lua.create_function(|lua, ud: LightUserData| -> Result<(Option<String>, Option<String>, Option<u32>)> {
+        let id = lud_to_id(ud);
+        let state_rc = get_sim_state(lua);
+        let state = state_rc.borrow();
+        let item = state.tooltips.get(&id)
+            .and_then(|td| td.item_id)
+            .and_then(|item_id| crate::items::get_item(item_id).map(|info| (item_id, info)));
+        Ok(match item {
+            Some((item_id, info)) => {
+                let (_, hex) = quality_rgb_hex(info.quality);
+                let link = format!("|c{}|Hitem:{}::::::::80:::::|h[{}]|h|r", hex, item_id, info.name);
+                (Some(info.name.to_string()), Some(link), Some(item_id))
+            }
+            None => (None, None, None),
+        })
     })?)?;
 
     add_tooltip_minwidth_methods(lua, methods)?;
@@ -376,6 +397,70 @@ fn add_tooltip_state_methods(lua: &Lua, methods: &mlua::Table) -> Result<()> {
     Ok(())
 }
 
+// --- Item content ---
+
+/// Replace the tooltip lines with an item's name (quality colored), binding
+/// and item level, then fire OnTooltipSetItem. Unknown items leave it empty.
+fn set_item_impl(lua: &Lua, id: u64, item_id: u32) -> Result<()> {
+    let info = crate::items::get_item(item_id);
+    {
+        let state_rc = get_sim_state(lua);
+        let mut state = state_rc.borrow_mut();
+        let Some(td) = state.tooltips.get_mut(&id) else { return Ok(()) };
+        td.lines.clear();
+        td.item_id = info.map(|_| item_id);
+        if let Some(info) = info {
+            td.lines.extend(item_tooltip_lines(info));
+        }
+    }
+    if info.is_some() {
+        fire_tooltip_script(lua, id, "OnTooltipSetItem")?;
+    }
+    Ok(())
+}
+
+fn item_tooltip_lines(info: &crate::items::ItemInfo) -> Vec<TooltipLine> {
+    const WHITE: (f32, f32, f32) = (1.0, 1.0, 1.0);
+    const GOLD: (f32, f32, f32) = (1.0, 0.82, 0.0);
+    let line = |text: String, color| TooltipLine {
+        left_text: text,
+        left_color: color,
+        right_text: None,
+        right_color: WHITE,
+        wrap: false,
+    };
+
+    let (rgb, _) = quality_rgb_hex(info.quality);
+    let mut lines = vec![line(info.name.to_string(), rgb)];
+    // Only equippable items (inventory_type != 0) show an item level.
+    if info.inventory_type != 0 {
+        let fmt = crate::global_strings::get_global_string("ITEM_LEVEL").unwrap_or("Item Level %d");
+        lines.push(line(fmt.replace("%d", &info.item_level.to_string()), GOLD));
+    }
+    let binding = match info.bonding {
+        1 => Some("ITEM_BIND_ON_PICKUP"),
+        2 => Some("ITEM_BIND_ON_EQUIP"),
+        3 => Some("ITEM_BIND_ON_USE"),
+        4 => Some("ITEM_BIND_QUEST"),
+        _ => None,
+    };
+    if let Some(text) = binding.and_then(crate::global_strings::get_global_string) {
+        lines.push(line(text.to_string(), WHITE));
+    }
+    lines
+}
+
+/// Quality color as (rgb, "ffRRGGBB") from ITEM_QUALITY_COLORS; unknown
+/// qualities fall back to Common (white).
+fn quality_rgb_hex(quality: u8) -> ((f32, f32, f32), &'static str) {
+    use crate::lua_api::globals::strings::string_data::game_enums::ITEM_QUALITY_COLORS_DATA;
+    ITEM_QUALITY_COLORS_DATA
+        .iter()
+        .find(|(idx, ..)| *idx == quality as i32)
+        .map(|&(_, r, g, b, hex)| ((r as f32, g as f32, b as f32), hex))
+        .unwrap_or(((1.0, 1.0, 1.0), "ffffffff"))
+}
+
 // --- Positioning ---
 
 /// Set anchors on the tooltip frame based on anchor_type from SetOwner.
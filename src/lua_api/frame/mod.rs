@@ -5,5 +5,5 @@ pub(crate) mod metatable;
 pub(crate) mod methods;
 
 pub use handle::{extract_frame_id, frame_lud, get_sim_state, lud_to_id};
-pub(crate) use methods::fire_on_show_recursive;
+pub(crate) use methods::{hide_frame, show_frame};
 pub(crate) use methods::methods_hierarchy::propagate_strata_level_pub;
@@ -4,6 +4,41 @@
 //! generators used by `SimState::default()`.
 
 use std::collections::HashMap;
+use std::sync::Mutex;
+
+/// Seed set by `--seed`; `None` means randomized defaults use the wall clock.
+static RANDOM_SEED: Mutex<Option<u64>> = Mutex::new(None);
+
+/// Make randomized defaults (player name, buffs, XP) and Lua's `math.random`
+/// reproducible in environments created after this call. `None` restores
+/// wall-clock randomness.
+pub fn set_random_seed(seed: Option<u64>) {
+    *RANDOM_SEED.lock().unwrap_or_else(|e| e.into_inner()) = seed;
+}
+
+/// The seed set by `set_random_seed`, if any.
+pub fn random_seed() -> Option<u64> {
+    *RANDOM_SEED.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Entropy for one randomized default. With a seed, `salt` keeps the
+/// defaults independent of each other; without one, the wall clock's
+/// sub-second nanoseconds are used.
+pub fn default_entropy(salt: u64) -> usize {
+    match random_seed() {
+        Some(seed) => {
+            // splitmix64 finalizer
+            let mut z = seed ^ salt.wrapping_mul(0x9E37_79B9_7F4A_7C15);
+            z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+            ((z ^ (z >> 31)) >> 32) as usize
+        }
+        None => std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.subsec_nanos() as usize)
+            .unwrap_or(0),
+    }
+}
 
 /// Information about the current target.
 #[derive(Clone)]
@@ -25,7 +60,7 @@ pub struct TargetInfo {
 
 /// A simulated party member.
 pub struct PartyMember {
-    pub name: &'static str,
+    pub name: String,
     /// 1-based class index into CLASS_DATA.
     pub class_index: i32,
     pub level: i32,
@@ -41,6 +76,84 @@ pub struct PartyMember {
     pub dead_since: Option<std::time::Instant>,
 }
 
+/// Copy of the numeric state of one unit, as read by UnitHealth/UnitPower/etc.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct UnitStats {
+    pub class_index: i32,
+    pub level: i32,
+    pub health: i32,
+    pub health_max: i32,
+    pub power: i32,
+    pub power_max: i32,
+    pub power_type: i32,
+    pub power_type_name: &'static str,
+}
+
+/// Mutable view of one unit's fields, wherever they live in `SimState`.
+pub struct UnitFieldsMut<'a> {
+    pub name: &'a mut String,
+    pub class_index: &'a mut i32,
+    pub level: &'a mut i32,
+    pub health: &'a mut i32,
+    pub health_max: &'a mut i32,
+    pub power: &'a mut i32,
+    pub power_max: &'a mut i32,
+}
+
+impl TargetInfo {
+    pub fn stats(&self) -> UnitStats {
+        UnitStats {
+            class_index: self.class_index,
+            level: self.level,
+            health: self.health,
+            health_max: self.health_max,
+            power: self.power,
+            power_max: self.power_max,
+            power_type: self.power_type,
+            power_type_name: self.power_type_name,
+        }
+    }
+
+    pub fn fields_mut(&mut self) -> UnitFieldsMut<'_> {
+        UnitFieldsMut {
+            name: &mut self.name,
+            class_index: &mut self.class_index,
+            level: &mut self.level,
+            health: &mut self.health,
+            health_max: &mut self.health_max,
+            power: &mut self.power,
+            power_max: &mut self.power_max,
+        }
+    }
+}
+
+impl PartyMember {
+    pub fn stats(&self) -> UnitStats {
+        UnitStats {
+            class_index: self.class_index,
+            level: self.level,
+            health: self.health,
+            health_max: self.health_max,
+            power: self.power,
+            power_max: self.power_max,
+            power_type: self.power_type,
+            power_type_name: self.power_type_name,
+        }
+    }
+
+    pub fn fields_mut(&mut self) -> UnitFieldsMut<'_> {
+        UnitFieldsMut {
+            name: &mut self.name,
+            class_index: &mut self.class_index,
+            level: &mut self.level,
+            health: &mut self.health,
+            health_max: &mut self.health_max,
+            power: &mut self.power,
+            power_max: &mut self.power_max,
+        }
+    }
+}
+
 /// A simulated aura (buff or debuff).
 #[derive(Clone)]
 pub struct AuraInfo {
@@ -218,7 +331,7 @@ pub const XP_LEVELS: &[(&str, f64)] = &[
     ("100%", 1.0),
 ];
 
-/// Pick a random WoW-style player name using the current time as a seed.
+/// Pick a random WoW-style player name (see `default_entropy`).
 pub fn random_player_name() -> String {
     const NAMES: &[&str] = &[
         "Arthas", "Jaina", "Thrall", "Varian", "Anduin",
@@ -226,11 +339,7 @@ pub fn random_player_name() -> String {
         "Genn", "Baine", "Rokhan", "Thalyssra", "Alleria",
         "Turalyon", "Calia", "Lothraxion", "Velen", "Yrel",
     ];
-    let nanos = std::time::SystemTime::now()
-        .duration_since(std::time::UNIX_EPOCH)
-        .map(|d| d.subsec_nanos() as usize)
-        .unwrap_or(0);
-    NAMES[nanos % NAMES.len()].to_string()
+    NAMES[default_entropy(1) % NAMES.len()].to_string()
 }
 
 /// Default party member definitions: (name, class_index, health_max, power, power_max, power_type, power_type_name).
@@ -250,7 +359,7 @@ pub fn default_party() -> Vec<PartyMember> {
         .iter()
         .map(|&(name, class_index, health_max, power, power_max, power_type, power_type_name)| {
             PartyMember {
-                name,
+                name: name.to_string(),
                 class_index,
                 level: 80,
                 health: health_max,
@@ -274,6 +383,7 @@ const ENEMY_NPC: (&str, i32, i32, i32, i32, i32, i32, &str) =
 pub fn build_target_info(unit_id: &str, state: &super::state::SimState) -> Option<TargetInfo> {
     match unit_id {
         "player" => Some(build_player_target(state)),
+        "pet" => state.pet.clone(),
         u if u.starts_with("party") => build_party_target(u, state),
         "enemy1" => Some(build_enemy_target()),
         _ => None,
@@ -285,11 +395,11 @@ fn build_player_target(state: &super::state::SimState) -> TargetInfo {
         unit_id: "player".into(),
         name: state.player_name.clone(),
         class_index: state.player_class_index,
-        level: 80,
+        level: state.player_level,
         health: state.player_health,
         health_max: state.player_health_max,
-        power: 50_000,
-        power_max: 100_000,
+        power: state.player_power,
+        power_max: state.player_power_max,
         power_type: 0,
         power_type_name: "MANA",
         is_player: true,
@@ -298,15 +408,20 @@ fn build_player_target(state: &super::state::SimState) -> TargetInfo {
     }
 }
 
-fn build_party_target(unit_id: &str, state: &super::state::SimState) -> Option<TargetInfo> {
-    let idx = unit_id.strip_prefix("party")?
-        .parse::<usize>().ok()
+/// Parse a "partyN" unit ID and return the 0-based index if valid.
+pub fn parse_party_index(unit: &str) -> Option<usize> {
+    unit.strip_prefix("party")
+        .and_then(|n| n.parse::<usize>().ok())
         .filter(|&n| n >= 1)
-        .map(|n| n - 1)?;
+        .map(|n| n - 1)
+}
+
+fn build_party_target(unit_id: &str, state: &super::state::SimState) -> Option<TargetInfo> {
+    let idx = parse_party_index(unit_id)?;
     let m = state.party_members.get(idx)?;
     Some(TargetInfo {
         unit_id: unit_id.into(),
-        name: m.name.into(),
+        name: m.name.clone(),
         class_index: m.class_index,
         level: m.level,
         health: m.health,
@@ -341,6 +456,42 @@ fn build_enemy_target() -> TargetInfo {
     }
 }
 
+/// The player's default pet (UnitExists("pet") is true unless dismissed).
+pub fn default_pet() -> TargetInfo {
+    TargetInfo {
+        unit_id: "pet".into(),
+        name: "Misha".into(),
+        class_index: 1,
+        level: 80,
+        health: 100_000,
+        health_max: 100_000,
+        power: 100,
+        power_max: 100,
+        power_type: 2,
+        power_type_name: "FOCUS",
+        is_player: false,
+        is_enemy: false,
+        guid: "Pet-0000-00000002".into(),
+    }
+}
+
+/// A party member joining through `SetUnitState("partyN", {exists = true})`.
+pub fn new_party_member(index: usize) -> PartyMember {
+    PartyMember {
+        name: format!("Member{index}"),
+        class_index: 1,
+        level: 80,
+        health: 100_000,
+        health_max: 100_000,
+        power: 0,
+        power_max: 100,
+        power_type: 1,
+        power_type_name: "RAGE",
+        is_leader: false,
+        dead_since: None,
+    }
+}
+
 /// Randomly damage party members, auto-resurrect after 30s dead.
 ///
 /// `damage_pct` controls the intensity (fraction of max HP per tick).
@@ -402,10 +553,7 @@ pub fn default_player_buffs() -> Vec<AuraInfo> {
     if std::env::var("WOW_SIM_NO_BUFFS").is_ok() {
         return Vec::new();
     }
-    let nanos = std::time::SystemTime::now()
-        .duration_since(std::time::UNIX_EPOCH)
-        .map(|d| d.subsec_nanos() as usize)
-        .unwrap_or(0);
+    let nanos = default_entropy(2);
     let count = 4 + (nanos % 3); // 4, 5, or 6 buffs
     let mut indices: Vec<usize> = (0..BUFF_POOL.len()).collect();
     // Simple shuffle using nanos
@@ -468,3 +616,196 @@ pub fn default_action_bars() -> HashMap<u32, u32> {
     ];
     prot_paladin_bar.iter().copied().collect()
 }
+
+/// An item stack in a bag or equipment slot.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ItemStack {
+    pub item_id: u32,
+    pub count: i32,
+}
+
+/// The simulated bags (0 = backpack, 1-4 = equipped bags) and equipped items.
+#[derive(Debug, Clone, Default)]
+pub struct Bags {
+    /// (bag, slot) → item. Slots are 1-based.
+    pub contents: HashMap<(i32, i32), ItemStack>,
+    /// Inventory slot (1-19 gear, 20-23 bags) → equipped item.
+    pub equipped: HashMap<i32, ItemStack>,
+}
+
+impl Bags {
+    pub fn get(&self, bag: i32, slot: i32) -> Option<ItemStack> {
+        self.contents.get(&(bag, slot)).copied()
+    }
+
+    /// Put `item` in a bag slot, or empty the slot with `None`.
+    pub fn set(&mut self, bag: i32, slot: i32, item: Option<ItemStack>) {
+        match item {
+            Some(item) => self.contents.insert((bag, slot), item),
+            None => self.contents.remove(&(bag, slot)),
+        };
+    }
+
+    /// Number of occupied slots in `bag`.
+    pub fn num_items(&self, bag: i32) -> i32 {
+        self.contents.keys().filter(|&&(b, _)| b == bag).count() as i32
+    }
+
+    pub fn equipped(&self, slot: i32) -> Option<ItemStack> {
+        self.equipped.get(&slot).copied()
+    }
+
+    /// Equip `item` in an inventory slot, or empty it with `None`.
+    pub fn set_equipped(&mut self, slot: i32, item: Option<ItemStack>) {
+        match item {
+            Some(item) => self.equipped.insert(slot, item),
+            None => self.equipped.remove(&slot),
+        };
+    }
+}
+
+/// Backpack slots holding a mock Hearthstone.
+const MOCK_BACKPACK_SLOTS: &[i32] = &[1, 3, 5, 7, 10, 14];
+
+/// A backpack with six Hearthstones and nothing equipped.
+pub fn default_bags() -> Bags {
+    let hearthstone = ItemStack { item_id: 6948, count: 1 };
+    let contents = MOCK_BACKPACK_SLOTS.iter().map(|&slot| ((0, slot), hearthstone)).collect();
+    Bags { contents, equipped: HashMap::new() }
+}
+
+/// One quest objective (GetQuestLogLeaderBoard / C_QuestLog.GetQuestObjectives).
+#[derive(Debug, Clone)]
+pub struct QuestObjective {
+    pub text: String,
+    /// "item", "monster", "object", "event", "progressbar", ...
+    pub kind: String,
+    pub fulfilled: i32,
+    pub required: i32,
+    pub finished: bool,
+}
+
+/// A quest in the simulated quest log.
+#[derive(Debug, Clone)]
+pub struct QuestLogEntry {
+    pub quest_id: i32,
+    pub title: String,
+    pub level: i32,
+    pub objectives: Vec<QuestObjective>,
+    /// Ready for turn-in (C_QuestLog.IsComplete).
+    pub is_complete: bool,
+    /// Shown in the ObjectiveTracker (C_QuestLog.GetNumQuestWatches).
+    pub is_watched: bool,
+}
+
+impl QuestLogEntry {
+    /// Mark the quest (in)complete; completing also fulfills every objective.
+    pub fn set_complete(&mut self, complete: bool) {
+        self.is_complete = complete;
+        if complete {
+            for obj in &mut self.objectives {
+                obj.fulfilled = obj.required;
+                obj.finished = true;
+            }
+        }
+    }
+}
+
+/// The simulated quest log. Log indices are 1-based positions in `quests`.
+#[derive(Debug, Clone, Default)]
+pub struct QuestLog {
+    pub quests: Vec<QuestLogEntry>,
+    /// Whether GetNumQuestLogEntries reports the quests. The default mock
+    /// quests are only watched: listing them drives QuestMapFrame's quest
+    /// button templates, which need child hierarchies the simulator doesn't
+    /// build. Quests added through `add` replace the mocks and are listed.
+    pub listed: bool,
+}
+
+impl QuestLog {
+    pub fn get(&self, quest_id: i32) -> Option<&QuestLogEntry> {
+        self.quests.iter().find(|q| q.quest_id == quest_id)
+    }
+
+    pub fn get_mut(&mut self, quest_id: i32) -> Option<&mut QuestLogEntry> {
+        self.quests.iter_mut().find(|q| q.quest_id == quest_id)
+    }
+
+    pub fn by_log_index(&self, log_index: i32) -> Option<&QuestLogEntry> {
+        usize::try_from(log_index).ok()?.checked_sub(1).and_then(|i| self.quests.get(i))
+    }
+
+    pub fn log_index(&self, quest_id: i32) -> Option<i32> {
+        self.quests.iter().position(|q| q.quest_id == quest_id).map(|i| i as i32 + 1)
+    }
+
+    pub fn watched(&self) -> impl Iterator<Item = &QuestLogEntry> {
+        self.quests.iter().filter(|q| q.is_watched)
+    }
+
+    /// Number of listed entries (0 while only the mock quests are present).
+    pub fn num_entries(&self) -> i32 {
+        if self.listed { self.quests.len() as i32 } else { 0 }
+    }
+
+    /// Add a quest, replacing one with the same ID. The first added quest
+    /// drops the mock quests.
+    pub fn add(&mut self, quest: QuestLogEntry) {
+        if !self.listed {
+            self.quests.clear();
+            self.listed = true;
+        }
+        match self.get_mut(quest.quest_id) {
+            Some(existing) => *existing = quest,
+            None => self.quests.push(quest),
+        }
+    }
+
+    /// Empty the log; it stays listed so GetNumQuestLogEntries reports 0.
+    pub fn clear(&mut self) {
+        self.quests.clear();
+        self.listed = true;
+    }
+}
+
+/// Mock quest definitions: (questID, title, objectives as (text, type, fulfilled, required)).
+type MockQuest = (i32, &'static str, &'static [(&'static str, &'static str, i32, i32)]);
+
+const MOCK_QUESTS: &[MockQuest] = &[
+    (80000, "The Lost Expedition", &[
+        ("Ironforge Relics collected: 3/5", "item", 3, 5),
+        ("Explore the Old Quarry", "event", 0, 1),
+    ]),
+    (80001, "Defending the Gates", &[
+        ("Stormwind Guards defended: 7/10", "monster", 7, 10),
+    ]),
+    (80002, "Supply Run", &[
+        ("Supplies gathered: 5/5", "item", 5, 5),
+        ("Deliver to Quartermaster", "event", 0, 1),
+    ]),
+];
+
+/// Three watched (but unlisted) mock quests for the ObjectiveTracker.
+pub fn default_quest_log() -> QuestLog {
+    let quests = MOCK_QUESTS
+        .iter()
+        .map(|&(quest_id, title, objectives)| QuestLogEntry {
+            quest_id,
+            title: title.to_string(),
+            level: 80,
+            objectives: objectives
+                .iter()
+                .map(|&(text, kind, fulfilled, required)| QuestObjective {
+                    text: text.to_string(),
+                    kind: kind.to_string(),
+                    fulfilled,
+                    required,
+                    finished: fulfilled >= required,
+                })
+                .collect(),
+            is_complete: false,
+            is_watched: true,
+        })
+        .collect();
+    QuestLog { quests, listed: false }
+}
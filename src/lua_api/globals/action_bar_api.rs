@@ -110,7 +110,7 @@ fn register_action_cooldown(lua: &Lua, state: &Rc<RefCell<SimState>>) -> Result<
 
 /// Look up the active cooldown for an action bar slot.
 fn action_cooldown_times(state: &SimState, slot: u32) -> (f64, f64) {
-    let now = state.start_time.elapsed().as_secs_f64();
+    let now = state.clock.now();
     let spell_id = match state.action_bars.get(&slot) {
         Some(&id) => id,
         None => return (0.0, 0.0),
@@ -201,7 +201,7 @@ pub fn start_cooldowns(
         spell_cooldown_duration, spell_triggers_gcd,
     };
 
-    let now = state.borrow().start_time.elapsed().as_secs_f64();
+    let now = state.borrow().clock.now();
     {
         let mut s = state.borrow_mut();
         if spell_triggers_gcd(spell_id) {
@@ -242,7 +242,7 @@ pub fn start_cast(
         let mut s = state.borrow_mut();
         let cast_id = s.next_cast_id;
         s.next_cast_id += 1;
-        let now = s.start_time.elapsed().as_secs_f64();
+        let now = s.clock.now();
         s.casting = Some(CastingState {
             spell_id,
             spell_name: spell_name.clone(),
@@ -492,7 +492,7 @@ fn register_c_action_bar_cooldowns(
         let s = st.borrow();
         let (start, dur) = slot_from_value(&slot)
             .and_then(|n| s.action_bars.get(&n).map(|&id| {
-                spell_cooldown_times(&s, id, s.start_time.elapsed().as_secs_f64())
+                spell_cooldown_times(&s, id, s.clock.now())
             }))
             .unwrap_or((0.0, 0.0));
         let info = lua.create_table()?;
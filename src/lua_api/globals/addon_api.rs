@@ -78,8 +78,8 @@ fn addon_info_tuple(lua: &Lua, addon: Option<&AddonInfo>) -> Result<(Value, Valu
             Value::String(lua.create_string(&addon.folder_name)?),
             Value::String(lua.create_string(&addon.title)?),
             Value::String(lua.create_string(&addon.notes)?),
-            Value::Boolean(true),
-            Value::Nil,
+            Value::Boolean(addon.enabled),
+            if addon.enabled { Value::Nil } else { Value::String(lua.create_string("DISABLED")?) },
             Value::String(lua.create_string("INSECURE")?),
             Value::Boolean(false),
         ))
@@ -534,33 +534,34 @@ fn load_addon_runtime(
     state: &Rc<RefCell<SimState>>,
     addon_name: &str,
 ) -> Result<(bool, Value)> {
-    // Already loaded? Return early.
-    {
-        let s = state.borrow();
-        if s.addons.iter().any(|a| a.folder_name == addon_name && a.loaded) {
-            return Ok((true, Value::Nil));
-        }
+    // Already loaded? Return early. Addon names are case-insensitive.
+    if is_addon_loaded(state, addon_name) {
+        return Ok((true, Value::Nil));
     }
 
     // Search addon_base_paths for the addon directory and its TOC file.
-    let toc_path = find_addon_toc(state, addon_name);
-
-    let toc_path = match toc_path {
+    let toc_path = match find_addon_toc(state, addon_name) {
         Some(p) => p,
         None => {
             let reason = lua.create_string("MISSING")?;
             return Ok((false, Value::String(reason)));
         }
     };
+    // Register and announce the addon under its on-disk folder name.
+    let folder_name = toc_path
+        .parent()
+        .and_then(|dir| dir.file_name())
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_else(|| addon_name.to_string());
+    let addon_name = folder_name.as_str();
+    if is_addon_loaded(state, addon_name) {
+        return Ok((true, Value::Nil));
+    }
 
     // Parse TOC to check dependencies, then load them first.
     if let Ok(toc) = crate::toc::TocFile::from_file(&toc_path) {
         for dep in toc.dependencies() {
-            let already_loaded = {
-                let s = state.borrow();
-                s.addons.iter().any(|a| a.folder_name == dep && a.loaded)
-            };
-            if !already_loaded {
+            if !is_addon_loaded(state, &dep) {
                 let _ = load_addon_runtime(lua, state, &dep);
             }
         }
@@ -580,7 +581,7 @@ fn load_addon_runtime(
                     result.timing.total()
                 );
             }
-            register_loaded_addon(state, addon_name, load_time_secs);
+            register_loaded_addon(state, addon_name, &toc_path, load_time_secs);
             fire_addon_loaded(&loader_env, addon_name);
             Ok((true, Value::Nil))
         }
@@ -592,23 +593,98 @@ fn load_addon_runtime(
     }
 }
 
-/// Search addon_base_paths for an addon's TOC file.
+/// Re-run an addon's TOC against the live environment and fire `ADDON_LOADED`.
+///
+/// Frames created by the previous load are hidden and lose their event
+/// registrations, and its pending timers are cancelled. Globals, hooks and
+/// mixins it defined are not removed; the re-executed files overwrite them.
+pub fn reload_addon(
+    lua: &Lua,
+    state: &Rc<RefCell<SimState>>,
+    addon_name: &str,
+) -> std::result::Result<crate::loader::LoadResult, String> {
+    let toc_path = find_addon_toc(state, addon_name)
+        .ok_or_else(|| format!("Addon '{}' not found in addon paths", addon_name))?;
+    // Addon names are case-insensitive; register under the on-disk folder name.
+    let folder_name = toc_path
+        .parent()
+        .and_then(|dir| dir.file_name())
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_else(|| addon_name.to_string());
+    let addon_name = folder_name.as_str();
+
+    let idx = state.borrow().addons.iter().position(|a| a.folder_name.eq_ignore_ascii_case(addon_name));
+    if let Some(idx) = idx {
+        detach_addon(state, idx as u16);
+    }
+
+    let loader_env = crate::lua_api::LoaderEnv::new(lua, Rc::clone(state));
+    let result = crate::loader::load_addon(&loader_env, &toc_path).map_err(|e| e.to_string())?;
+    register_loaded_addon(state, addon_name, &toc_path, result.timing.total().as_secs_f64());
+    fire_addon_loaded(&loader_env, addon_name);
+    Ok(result)
+}
+
+/// Disconnect what an addon's previous load left running: hide its frames,
+/// drop their event registrations and cancel its timers.
+fn detach_addon(state: &Rc<RefCell<SimState>>, addon_idx: u16) {
+    let mut s = state.borrow_mut();
+    let owned: Vec<u64> = s
+        .widgets
+        .iter_ids()
+        .filter(|&id| s.widgets.get(id).is_some_and(|f| f.owner_addon == Some(addon_idx)))
+        .collect();
+    for id in owned {
+        if let Some(frame) = s.widgets.get_mut(id) {
+            frame.unregister_all_events();
+        }
+        s.set_frame_visible(id, false);
+    }
+    for timer in s.timers.iter_mut().filter(|t| t.owner_addon == Some(addon_idx)) {
+        timer.cancelled = true;
+    }
+}
+
+/// Whether an addon with this folder name (case-insensitive) is loaded.
+fn is_addon_loaded(state: &Rc<RefCell<SimState>>, addon_name: &str) -> bool {
+    state
+        .borrow()
+        .addons
+        .iter()
+        .any(|a| a.loaded && a.folder_name.eq_ignore_ascii_case(addon_name))
+}
+
+/// Search addon_base_paths for an addon's TOC file. The folder name is
+/// matched exactly first, then case-insensitively.
 fn find_addon_toc(state: &Rc<RefCell<SimState>>, addon_name: &str) -> Option<std::path::PathBuf> {
     let s = state.borrow();
     s.addon_base_paths
         .iter()
-        .map(|base| base.join(addon_name))
-        .find_map(|dir| {
-            if dir.is_dir() {
-                crate::loader::find_toc_file(&dir)
-            } else {
-                None
-            }
-        })
+        .filter_map(|base| find_addon_dir(base, addon_name))
+        .find_map(|dir| crate::loader::find_toc_file(&dir))
+}
+
+/// The directory for `addon_name` under `base`, if any.
+fn find_addon_dir(base: &std::path::Path, addon_name: &str) -> Option<std::path::PathBuf> {
+    let exact = base.join(addon_name);
+    if exact.is_dir() {
+        return Some(exact);
+    }
+    std::fs::read_dir(base)
+        .ok()?
+        .filter_map(|entry| entry.ok())
+        .find(|entry| entry.file_name().to_string_lossy().eq_ignore_ascii_case(addon_name))
+        .map(|entry| entry.path())
+        .filter(|path| path.is_dir())
 }
 
 /// Register a newly loaded addon in SimState.
-fn register_loaded_addon(state: &Rc<RefCell<SimState>>, name: &str, load_time_secs: f64) {
+fn register_loaded_addon(
+    state: &Rc<RefCell<SimState>>,
+    name: &str,
+    toc_path: &std::path::Path,
+    load_time_secs: f64,
+) {
     let mut s = state.borrow_mut();
     // Update existing entry or create new one.
     if let Some(existing) = s.addons.iter_mut().find(|a| a.folder_name == name) {
@@ -616,10 +692,8 @@ fn register_loaded_addon(state: &Rc<RefCell<SimState>>, name: &str, load_time_se
         existing.load_time_secs = load_time_secs;
     } else {
         s.addons.push(AddonInfo {
-            folder_name: name.to_string(),
-            title: name.to_string(),
-            enabled: true, loaded: true, load_on_demand: true,
-            load_time_secs, ..Default::default()
+            loaded: true, load_on_demand: true, load_time_secs,
+            ..AddonInfo::from_toc(name, toc_path)
         });
     }
 }
@@ -645,7 +719,7 @@ fn find_addon_by_value<'a>(addons: &'a [AddonInfo], value: &Value) -> Option<&'a
         Value::Integer(idx) => addons.get((*idx - 1) as usize),
         Value::String(s) => {
             let name = s.to_string_lossy();
-            addons.iter().find(|a| a.folder_name == *name)
+            addons.iter().find(|a| a.folder_name.eq_ignore_ascii_case(&name))
         }
         _ => None,
     }
@@ -662,7 +736,7 @@ fn set_addon_enabled(state: &Rc<RefCell<SimState>>, addon: &Value, enabled: bool
         }
         Value::String(s) => {
             let name = s.to_string_lossy();
-            if let Some(a) = state.addons.iter_mut().find(|a| a.folder_name == *name) {
+            if let Some(a) = state.addons.iter_mut().find(|a| a.folder_name.eq_ignore_ascii_case(&name)) {
                 a.enabled = enabled;
             }
         }
@@ -671,6 +745,9 @@ fn set_addon_enabled(state: &Rc<RefCell<SimState>>, addon: &Value, enabled: bool
 }
 
 /// Resolve addon metadata for a given addon name and field.
+///
+/// Registered addons answer from their TOC; `Title`, `Group`, `Version` and
+/// `X-Flavor` have fallbacks when the TOC doesn't set them.
 fn resolve_metadata(
     state: &Rc<RefCell<SimState>>,
     lua: &Lua,
@@ -681,27 +758,17 @@ fn resolve_metadata(
     let addon_info = state
         .addons
         .iter()
-        .find(|a| a.folder_name == addon_name || a.title == addon_name);
-
-    let value = if let Some(info) = addon_info {
-        match field {
-            "Version" => "@project-version@",
-            "X-Flavor" => "Mainline",
-            "Title" => &info.title,
-            "Notes" => &info.notes,
-            "Author" => "",
-            "Group" => &info.folder_name,
-            _ => "",
-        }
-    } else {
-        match field {
-            "Version" => "@project-version@",
-            "X-Flavor" => "Mainline",
-            "Title" => addon_name,
-            "Group" => addon_name,
-            _ => "",
-        }
-    };
+        .find(|a| a.folder_name.eq_ignore_ascii_case(addon_name) || a.title == addon_name);
+
+    let from_toc = addon_info.and_then(|info| info.metadata_field(field));
+    let value = from_toc.unwrap_or_else(|| match field.to_ascii_lowercase().as_str() {
+        "version" => "@project-version@",
+        "x-flavor" => "Mainline",
+        "title" => addon_info.map_or(addon_name, |info| info.title.as_str()),
+        "notes" => addon_info.map_or("", |info| info.notes.as_str()),
+        "group" => addon_info.map_or(addon_name, |info| info.folder_name.as_str()),
+        _ => "",
+    });
 
     if value.is_empty() {
         Ok(Value::Nil)
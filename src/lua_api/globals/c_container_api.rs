@@ -1,14 +1,22 @@
 //! C_Container namespace and legacy container global functions.
 //!
-//! Provides mock inventory data for bag slots and the full C_Container API.
+//! Bag contents come from `SimState::bags`, which starts with six Hearthstones
+//! in the backpack. The simulator utilities SetContainerItem, ClearContainers
+//! and SetInventoryItem change it (from --exec-lua or tests) and fire the
+//! matching BAG_UPDATE / PLAYER_EQUIPMENT_CHANGED events.
 
+use crate::lua_api::SimState;
+use crate::lua_api::state::ItemStack;
 use mlua::{Lua, Result, Value};
+use std::cell::RefCell;
+use std::rc::Rc;
 
 /// Register C_Container namespace, C_NewItems, and legacy container globals.
-pub fn register_c_container_api(lua: &Lua) -> Result<()> {
-    register_c_container(lua)?;
+pub fn register_c_container_api(lua: &Lua, state: Rc<RefCell<SimState>>) -> Result<()> {
+    register_c_container(lua, &state)?;
     register_c_new_items(lua)?;
-    register_container_globals(lua)?;
+    register_container_globals(lua, &state)?;
+    register_bag_sim_utilities(lua, state)?;
     Ok(())
 }
 
@@ -31,37 +39,24 @@ pub(super) fn bag_slot_count(bag: i32) -> i32 {
     }
 }
 
-/// Mock inventory: (bag, slot) → (item_id, stack_count).
-fn mock_bag_item(bag: i32, slot: i32) -> Option<(u32, i32)> {
-    match (bag, slot) {
-        (0, 1) => Some((6948, 1)),   // Hearthstone
-        (0, 3) => Some((6948, 1)),   // Hearthstone
-        (0, 5) => Some((6948, 1)),   // Hearthstone
-        (0, 7) => Some((6948, 1)),   // Hearthstone
-        (0, 10) => Some((6948, 1)),  // Hearthstone
-        (0, 14) => Some((6948, 1)),  // Hearthstone
-        _ => None,
-    }
+/// Item hyperlink colored by the item's quality.
+pub(super) fn item_link(item_id: u32) -> String {
+    let (name, quality) = crate::items::get_item(item_id)
+        .map(|item| (item.name, item.quality))
+        .unwrap_or(("Unknown", 1));
+    let color = super::c_item_api::quality_color(quality);
+    format!("|cff{}|Hitem:{}::::::::80:::::|h[{}]|h|r", color, item_id, name)
 }
 
 /// Build the `containerInfo` table returned by `C_Container.GetContainerItemInfo`.
-fn build_container_item_info(lua: &Lua, item_id: u32, stack_count: i32) -> Result<Value> {
-    let (name, quality) = if let Some(item) = crate::items::get_item(item_id) {
-        (item.name, item.quality)
-    } else {
-        ("Unknown", 1u8)
-    };
-    let color = super::c_item_api::quality_color(quality);
-    let link = format!(
-        "|cff{}|Hitem:{}::::::::80:::::|h[{}]|h|r",
-        color, item_id, name
-    );
+fn build_container_item_info(lua: &Lua, item: ItemStack) -> Result<Value> {
+    let quality = crate::items::get_item(item.item_id).map(|i| i.quality).unwrap_or(1);
     let t = lua.create_table()?;
-    t.set("itemID", item_id)?;
+    t.set("itemID", item.item_id)?;
     t.set("iconFileID", 134400)?;
-    t.set("stackCount", stack_count)?;
+    t.set("stackCount", item.count)?;
     t.set("quality", quality as i32)?;
-    t.set("hyperlink", lua.create_string(&link)?)?;
+    t.set("hyperlink", lua.create_string(item_link(item.item_id))?)?;
     t.set("isLocked", false)?;
     t.set("isBound", false)?;
     t.set("isFiltered", false)?;
@@ -71,43 +66,44 @@ fn build_container_item_info(lua: &Lua, item_id: u32, stack_count: i32) -> Resul
     Ok(Value::Table(t))
 }
 
+/// The item in a bag slot.
+fn bag_item(state: &Rc<RefCell<SimState>>, bag: i32, slot: i32) -> Option<ItemStack> {
+    state.borrow().bags.get(bag, slot)
+}
+
 /// Register C_Container item query methods.
-fn register_c_container_item_methods(lua: &Lua, t: &mlua::Table) -> Result<()> {
+fn register_c_container_item_methods(lua: &Lua, t: &mlua::Table, state: &Rc<RefCell<SimState>>) -> Result<()> {
+    let st = Rc::clone(state);
     t.set(
         "GetContainerItemID",
-        lua.create_function(|_, (bag, slot): (i32, i32)| {
-            Ok(mock_bag_item(bag, slot).map(|(id, _)| id as i64))
+        lua.create_function(move |_, (bag, slot): (i32, i32)| {
+            Ok(bag_item(&st, bag, slot).map(|item| item.item_id as i64))
         })?,
     )?;
+    let st = Rc::clone(state);
     t.set(
         "GetContainerItemLink",
-        lua.create_function(|lua, (bag, slot): (i32, i32)| {
-            let Some((item_id, _)) = mock_bag_item(bag, slot) else {
+        lua.create_function(move |lua, (bag, slot): (i32, i32)| {
+            let Some(item) = bag_item(&st, bag, slot) else {
                 return Ok(Value::Nil);
             };
-            let name = crate::items::get_item(item_id)
-                .map(|i| i.name)
-                .unwrap_or("Unknown");
-            let link = format!(
-                "|cffffffff|Hitem:{}::::::::80:::::|h[{}]|h|r",
-                item_id, name
-            );
-            Ok(Value::String(lua.create_string(&link)?))
+            Ok(Value::String(lua.create_string(item_link(item.item_id))?))
         })?,
     )?;
-    register_c_container_info_methods(lua, t)?;
+    register_c_container_info_methods(lua, t, state)?;
     Ok(())
 }
 
 /// Register GetContainerItemInfo, QuestInfo, and Cooldown.
-fn register_c_container_info_methods(lua: &Lua, t: &mlua::Table) -> Result<()> {
+fn register_c_container_info_methods(lua: &Lua, t: &mlua::Table, state: &Rc<RefCell<SimState>>) -> Result<()> {
+    let st = Rc::clone(state);
     t.set(
         "GetContainerItemInfo",
-        lua.create_function(|lua, (bag, slot): (i32, i32)| {
-            let Some((item_id, stack_count)) = mock_bag_item(bag, slot) else {
+        lua.create_function(move |lua, (bag, slot): (i32, i32)| {
+            let Some(item) = bag_item(&st, bag, slot) else {
                 return Ok(Value::Nil);
             };
-            build_container_item_info(lua, item_id, stack_count)
+            build_container_item_info(lua, item)
         })?,
     )?;
     t.set(
@@ -128,7 +124,7 @@ fn register_c_container_info_methods(lua: &Lua, t: &mlua::Table) -> Result<()> {
 }
 
 /// Register C_Container stub methods used by ContainerFrame.lua.
-fn register_c_container_stubs(lua: &Lua, t: &mlua::Table) -> Result<()> {
+fn register_c_container_stubs(lua: &Lua, t: &mlua::Table, state: &Rc<RefCell<SimState>>) -> Result<()> {
     t.set("IsContainerFiltered", lua.create_function(|_, _bag: i32| Ok(false))?)?;
     t.set(
         "GetBagName",
@@ -144,9 +140,10 @@ fn register_c_container_stubs(lua: &Lua, t: &mlua::Table) -> Result<()> {
         "ContainerIDToInventoryID",
         lua.create_function(|_, bag: i32| Ok(if bag > 0 { 19 + bag } else { 0 }))?,
     )?;
+    let st = Rc::clone(state);
     t.set(
         "HasContainerItem",
-        lua.create_function(|_, (bag, slot): (i32, i32)| Ok(mock_bag_item(bag, slot).is_some()))?,
+        lua.create_function(move |_, (bag, slot): (i32, i32)| Ok(bag_item(&st, bag, slot).is_some()))?,
     )?;
     t.set("GetBagSlotFlag", lua.create_function(|_, _args: mlua::MultiValue| Ok(false))?)?;
     t.set("SetBagSlotFlag", lua.create_function(|_, _args: mlua::MultiValue| Ok(()))?)?;
@@ -160,29 +157,30 @@ fn register_c_container_stubs(lua: &Lua, t: &mlua::Table) -> Result<()> {
     t.set("SplitContainerItem", lua.create_function(|_, _: mlua::MultiValue| Ok(()))?)?;
     t.set("IsBattlePayItem", lua.create_function(|_, _args: mlua::MultiValue| Ok(false))?)?;
     t.set("SetBagPortraitTexture", lua.create_function(|_, _args: mlua::MultiValue| Ok(()))?)?;
-    t.set("GetContainerNumFreeSlots", lua.create_function(|_, bag: i32| {
-        Ok((bag_slot_count(bag), 0i32))
+    let st = Rc::clone(state);
+    t.set("GetContainerNumFreeSlots", lua.create_function(move |_, bag: i32| {
+        Ok((bag_slot_count(bag) - st.borrow().bags.num_items(bag), 0i32))
     })?)?;
     Ok(())
 }
 
 /// Register the C_Container namespace.
-fn register_c_container(lua: &Lua) -> Result<()> {
+fn register_c_container(lua: &Lua, state: &Rc<RefCell<SimState>>) -> Result<()> {
     let c_container = lua.create_table()?;
 
     c_container.set(
         "GetContainerNumSlots",
         lua.create_function(|_, bag: i32| Ok(bag_slot_count(bag)))?,
     )?;
-    register_c_container_item_methods(lua, &c_container)?;
-    register_c_container_stubs(lua, &c_container)?;
+    register_c_container_item_methods(lua, &c_container, state)?;
+    register_c_container_stubs(lua, &c_container, state)?;
 
     lua.globals().set("C_Container", c_container)?;
     Ok(())
 }
 
 /// Register legacy global container functions (GetContainerNumSlots, etc.).
-fn register_container_globals(lua: &Lua) -> Result<()> {
+fn register_container_globals(lua: &Lua, state: &Rc<RefCell<SimState>>) -> Result<()> {
     let globals = lua.globals();
 
     globals.set(
@@ -193,28 +191,72 @@ fn register_container_globals(lua: &Lua) -> Result<()> {
         "IsInventoryItemProfessionBag",
         lua.create_function(|_, (_unit, _slot): (Value, Value)| Ok(false))?,
     )?;
+    let st = Rc::clone(state);
     globals.set(
         "GetContainerItemID",
-        lua.create_function(|_, (bag, slot): (i32, i32)| {
-            Ok(mock_bag_item(bag, slot).map(|(id, _)| id as i64))
+        lua.create_function(move |_, (bag, slot): (i32, i32)| {
+            Ok(bag_item(&st, bag, slot).map(|item| item.item_id as i64))
         })?,
     )?;
+    let st = Rc::clone(state);
     globals.set(
         "GetContainerItemLink",
-        lua.create_function(|lua, (bag, slot): (i32, i32)| {
-            let Some((item_id, _)) = mock_bag_item(bag, slot) else {
+        lua.create_function(move |lua, (bag, slot): (i32, i32)| {
+            let Some(item) = bag_item(&st, bag, slot) else {
                 return Ok(Value::Nil);
             };
-            let name = crate::items::get_item(item_id)
-                .map(|i| i.name)
-                .unwrap_or("Unknown");
-            let link = format!(
-                "|cffffffff|Hitem:{}::::::::80:::::|h[{}]|h|r",
-                item_id, name
-            );
-            Ok(Value::String(lua.create_string(&link)?))
+            Ok(Value::String(lua.create_string(item_link(item.item_id))?))
         })?,
     )?;
 
     Ok(())
 }
+
+/// Simulator utilities that fill the bag model: SetContainerItem(bag, slot,
+/// itemID[, count]) (a nil itemID empties the slot), ClearContainers() and
+/// SetInventoryItem(slot, itemID).
+fn register_bag_sim_utilities(lua: &Lua, state: Rc<RefCell<SimState>>) -> Result<()> {
+    let g = lua.globals();
+    let st = Rc::clone(&state);
+    g.set("SetContainerItem", lua.create_function(
+        move |lua, (bag, slot, item_id, count): (i32, i32, Option<u32>, Option<i32>)| {
+            if !(1..=bag_slot_count(bag)).contains(&slot) {
+                return Err(mlua::Error::runtime(format!(
+                    "SetContainerItem: bag {bag} has no slot {slot}"
+                )));
+            }
+            let item = item_id.map(|item_id| ItemStack { item_id, count: count.unwrap_or(1) });
+            st.borrow_mut().bags.set(bag, slot, item);
+            fire_bag_update(lua, &[bag])
+        },
+    )?)?;
+    let st = Rc::clone(&state);
+    g.set("ClearContainers", lua.create_function(move |lua, ()| {
+        let bags: Vec<i32> = {
+            let mut s = st.borrow_mut();
+            let mut bags: Vec<i32> = s.bags.contents.keys().map(|&(bag, _)| bag).collect();
+            bags.sort_unstable();
+            bags.dedup();
+            s.bags.contents.clear();
+            bags
+        };
+        fire_bag_update(lua, &bags)
+    })?)?;
+    g.set("SetInventoryItem", lua.create_function(move |lua, (slot, item_id): (i32, Option<u32>)| {
+        let item = item_id.map(|item_id| ItemStack { item_id, count: 1 });
+        state.borrow_mut().bags.set_equipped(slot, item);
+        let fire: mlua::Function = lua.globals().get("FireEvent")?;
+        // The second argument is true when the slot became empty.
+        fire.call::<()>((lua.create_string("PLAYER_EQUIPMENT_CHANGED")?, slot, item.is_none()))
+    })?)?;
+    Ok(())
+}
+
+/// Fire BAG_UPDATE for each changed bag, then BAG_UPDATE_DELAYED.
+fn fire_bag_update(lua: &Lua, bags: &[i32]) -> Result<()> {
+    let fire: mlua::Function = lua.globals().get("FireEvent")?;
+    for &bag in bags {
+        fire.call::<()>((lua.create_string("BAG_UPDATE")?, bag))?;
+    }
+    fire.call::<()>(lua.create_string("BAG_UPDATE_DELAYED")?)
+}
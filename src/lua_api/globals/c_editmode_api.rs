@@ -3,23 +3,38 @@
 //! Provides the minimum API needed for `EditModeManagerFrame:UpdateLayoutInfo()`
 //! to initialize and fire `EDIT_MODE_LAYOUTS_UPDATED`, which unblocks action bar
 //! positioning via `UpdateBottomActionBarPositions()`.
+//!
+//! Saved layouts and the active layout index live in a registry table, so
+//! `SaveLayouts`/`SetActiveLayout` round-trip through `GetLayouts`.
+
+use mlua::{Lua, Result, Table, Value};
 
-use mlua::{Lua, Result, Value};
+/// Registry key of the stored `{ activeLayout, layouts }` table.
+const LAYOUTS_KEY: &str = "__edit_mode_layouts";
+
+/// `Enum.EditModeLayoutType.Preset`: preset layouts are never saved.
+const LAYOUT_TYPE_PRESET: i64 = 0;
 
 /// Register the C_EditMode namespace.
 pub fn register_c_editmode_api(lua: &Lua) -> Result<()> {
     let t = lua.create_table()?;
 
-    // GetLayouts: returns { activeLayout = 1, layouts = {} }
-    // Empty layouts table — presets are prepended automatically by UpdateLayoutInfo.
+    // Start with preset layout 1 active and no saved layouts; presets are
+    // prepended by UpdateLayoutInfo.
+    let stored = lua.create_table()?;
+    stored.set("activeLayout", 1)?;
+    stored.set("layouts", lua.create_table()?)?;
+    lua.set_named_registry_value(LAYOUTS_KEY, stored)?;
+
+    // GetLayouts: a copy, since UpdateLayoutInfo rewrites the layouts field.
+    t.set("GetLayouts", lua.create_function(|lua, ()| get_layouts(lua))?)?;
     t.set(
-        "GetLayouts",
-        lua.create_function(|lua, ()| {
-            let info = lua.create_table()?;
-            info.set("activeLayout", 1)?;
-            info.set("layouts", lua.create_table()?)?;
-            Ok(info)
-        })?,
+        "SaveLayouts",
+        lua.create_function(|lua, info: Table| save_layouts(lua, &info))?,
+    )?;
+    t.set(
+        "SetActiveLayout",
+        lua.create_function(|lua, index: i64| set_active_layout(lua, index))?,
     )?;
 
     // GetAccountSettings: returns array of { setting, value } entries for enums 0–32.
@@ -39,8 +54,6 @@ pub fn register_c_editmode_api(lua: &Lua) -> Result<()> {
 
     // No-op stubs
     for name in [
-        "SaveLayouts",
-        "SetActiveLayout",
         "SetAccountSetting",
         "OnEditModeExit",
         "OnLayoutAdded",
@@ -71,6 +84,60 @@ pub fn register_c_editmode_api(lua: &Lua) -> Result<()> {
     Ok(())
 }
 
+/// A copy of the stored layout info.
+fn get_layouts(lua: &Lua) -> Result<Table> {
+    let stored: Table = lua.named_registry_value(LAYOUTS_KEY)?;
+    copy_table(lua, &stored)
+}
+
+/// `SaveLayouts(layoutInfo)`: keep the non-preset layouts and the active index.
+fn save_layouts(lua: &Lua, info: &Table) -> Result<()> {
+    let stored: Table = lua.named_registry_value(LAYOUTS_KEY)?;
+    if let Ok(active) = info.get::<i64>("activeLayout") {
+        stored.set("activeLayout", active)?;
+    }
+    let saved = lua.create_table()?;
+    if let Ok(layouts) = info.get::<Table>("layouts") {
+        for layout in layouts.sequence_values::<Table>() {
+            let layout = layout?;
+            if layout.get::<Option<i64>>("layoutType")? != Some(LAYOUT_TYPE_PRESET) {
+                saved.push(copy_table(lua, &layout)?)?;
+            }
+        }
+    }
+    stored.set("layouts", saved)
+}
+
+/// `SetActiveLayout(index)`: the client answers a change with
+/// `EDIT_MODE_LAYOUTS_UPDATED`, which EditModeManagerFrame applies.
+fn set_active_layout(lua: &Lua, index: i64) -> Result<()> {
+    let stored: Table = lua.named_registry_value(LAYOUTS_KEY)?;
+    if stored.get::<i64>("activeLayout")? == index {
+        return Ok(());
+    }
+    stored.set("activeLayout", index)?;
+    let fire: mlua::Function = lua.globals().get("FireEvent")?;
+    fire.call::<()>((
+        lua.create_string("EDIT_MODE_LAYOUTS_UPDATED")?,
+        get_layouts(lua)?,
+        false,
+    ))
+}
+
+/// Deep copy of a layout table (layouts are plain acyclic data).
+fn copy_table(lua: &Lua, t: &Table) -> Result<Table> {
+    let out = lua.create_table()?;
+    for pair in t.pairs::<Value, Value>() {
+        let (k, v) = pair?;
+        let v = match v {
+            Value::Table(inner) => Value::Table(copy_table(lua, &inner)?),
+            other => other,
+        };
+        out.raw_set(k, v)?;
+    }
+    Ok(out)
+}
+
 /// Default value for an account setting enum index.
 /// Enum values 0–32 map to EditMode account settings. Most "Show*" = 1 (visible).
 fn account_setting_default(setting: i32) -> i32 {
@@ -2,16 +2,19 @@
 //!
 //! Contains item information, container, encoding utilities, and inventory slot functions.
 
+use crate::lua_api::SimState;
 use mlua::{Lua, Result, Value};
+use std::cell::RefCell;
+use std::rc::Rc;
 
 /// Register item-related C_* namespaces and global functions.
-pub fn register_c_item_api(lua: &Lua) -> Result<()> {
+pub fn register_c_item_api(lua: &Lua, state: Rc<RefCell<SimState>>) -> Result<()> {
     register_c_item(lua)?;
-    super::c_container_api::register_c_container_api(lua)?;
+    super::c_container_api::register_c_container_api(lua, Rc::clone(&state))?;
     register_c_encoding_util(lua)?;
     register_legacy_item_globals(lua)?;
     register_spell_globals(lua)?;
-    register_inventory_globals(lua)?;
+    register_inventory_globals(lua, &state)?;
     Ok(())
 }
 
@@ -28,34 +31,8 @@ fn register_c_item(lua: &Lua) -> Result<()> {
 
 /// C_Item methods: GetItemInfo, GetItemInfoInstant, GetItemIDForItemInfo.
 fn register_c_item_info_methods(lua: &Lua, t: &mlua::Table) -> Result<()> {
-    t.set("GetItemInfo", make_c_item_get_item_info(lua)?)?;
-    t.set(
-        "GetItemInfoInstant",
-        lua.create_function(|lua, item_id: Value| {
-            let id = parse_item_id_from_value(&item_id);
-            if id == 0 {
-                return Ok(mlua::MultiValue::new());
-            }
-            let (class_name, subclass_name) =
-                if let Some(item) = crate::items::get_item(id as u32) {
-                    (
-                        item_class_from_inv_type(item.inventory_type),
-                        inv_type_to_subclass(item.inventory_type),
-                    )
-                } else {
-                    ("Miscellaneous", "Junk")
-                };
-            Ok(mlua::MultiValue::from_vec(vec![
-                Value::Integer(id as i64),
-                Value::String(lua.create_string(class_name)?),
-                Value::String(lua.create_string(subclass_name)?),
-                Value::String(lua.create_string("")?),
-                Value::Integer(134400),
-                Value::Integer(15),
-                Value::Integer(0),
-            ]))
-        })?,
-    )?;
+    t.set("GetItemInfo", make_get_item_info(lua)?)?;
+    t.set("GetItemInfoInstant", make_get_item_info_instant(lua)?)?;
     t.set(
         "GetItemIDForItemInfo",
         lua.create_function(|_, item_id: Value| {
@@ -66,45 +43,85 @@ fn register_c_item_info_methods(lua: &Lua, t: &mlua::Table) -> Result<()> {
     Ok(())
 }
 
-/// Build the C_Item.GetItemInfo closure (returns a table of item properties).
-fn make_c_item_get_item_info(lua: &Lua) -> Result<mlua::Function> {
+/// Look up an item by ID or link in the embedded item table.
+fn lookup_item(item: &Value) -> Option<(i32, &'static crate::items::ItemInfo)> {
+    let id = parse_item_id_from_value(item);
+    if id <= 0 {
+        return None;
+    }
+    crate::items::get_item(id as u32).map(|info| (id, info))
+}
+
+/// Build the GetItemInfo closure shared by the global and C_Item versions.
+///
+/// Returns the 17 positional values (name, link, quality, itemLevel, minLevel,
+/// type, subType, stackCount, equipLoc, texture, sellPrice, classID,
+/// subclassID, bindType, expacID, setID, isCraftingReagent), or nothing for
+/// unknown items.
+fn make_get_item_info(lua: &Lua) -> Result<mlua::Function> {
     lua.create_function(|lua, item_id: Value| {
-        let id = parse_item_id_from_value(&item_id);
-        if id == 0 {
-            return Ok(Value::Nil);
-        }
-        let Some(item) = crate::items::get_item(id as u32) else {
-            return Ok(Value::Nil);
+        let Some((id, item)) = lookup_item(&item_id) else {
+            return Ok(mlua::MultiValue::new());
         };
-        let color = quality_color(item.quality);
+        let (class_id, subclass_id) = item_class_ids(item.inventory_type);
         let link = format!(
             "|cff{}|Hitem:{}::::::::80:::::|h[{}]|h|r",
-            color, id, item.name
+            quality_color(item.quality), id, item.name
         );
-        let result = lua.create_table()?;
-        result.set("itemName", item.name)?;
-        result.set("itemLink", lua.create_string(&link)?)?;
-        result.set("itemQuality", item.quality as i32)?;
-        result.set("itemLevel", item.item_level as i32)?;
-        result.set("itemMinLevel", item.required_level as i32)?;
-        result.set("itemType", item_class_from_inv_type(item.inventory_type))?;
-        result.set("itemSubType", "")?;
-        result.set("itemStackCount", item.stackable as i32)?;
-        result.set("itemEquipLoc", inv_type_to_equip_loc(item.inventory_type))?;
-        result.set("itemTexture", 134400)?;
-        result.set("sellPrice", item.sell_price as i64)?;
-        result.set("classID", 15)?;
-        result.set("subclassID", 0)?;
-        result.set("bindType", item.bonding as i32)?;
-        result.set("expacID", item.expansion_id as i32)?;
-        result.set("isCraftingReagent", false)?;
-        Ok(Value::Table(result))
+        Ok(mlua::MultiValue::from_vec(vec![
+            Value::String(lua.create_string(item.name)?),
+            Value::String(lua.create_string(&link)?),
+            Value::Integer(item.quality as i64),
+            Value::Integer(item.item_level as i64),
+            Value::Integer(item.required_level as i64),
+            Value::String(lua.create_string(item_class_name(class_id))?),
+            Value::String(lua.create_string(item_subclass_name(class_id, subclass_id))?),
+            Value::Integer(item.stackable as i64),
+            Value::String(lua.create_string(inv_type_to_equip_loc(item.inventory_type))?),
+            Value::Integer(UNKNOWN_ITEM_ICON),
+            Value::Integer(item.sell_price as i64),
+            Value::Integer(class_id as i64),
+            Value::Integer(subclass_id as i64),
+            Value::Integer(item.bonding as i64),
+            Value::Integer(item.expansion_id as i64),
+            Value::Nil,
+            Value::Boolean(false),
+        ]))
+    })
+}
+
+/// Build the GetItemInfoInstant closure shared by the global and C_Item versions.
+///
+/// Returns (itemID, type, subType, equipLoc, icon, classID, subclassID), or
+/// nothing for unknown items.
+fn make_get_item_info_instant(lua: &Lua) -> Result<mlua::Function> {
+    lua.create_function(|lua, item_id: Value| {
+        let Some((id, item)) = lookup_item(&item_id) else {
+            return Ok(mlua::MultiValue::new());
+        };
+        let (class_id, subclass_id) = item_class_ids(item.inventory_type);
+        Ok(mlua::MultiValue::from_vec(vec![
+            Value::Integer(id as i64),
+            Value::String(lua.create_string(item_class_name(class_id))?),
+            Value::String(lua.create_string(item_subclass_name(class_id, subclass_id))?),
+            Value::String(lua.create_string(inv_type_to_equip_loc(item.inventory_type))?),
+            Value::Integer(UNKNOWN_ITEM_ICON),
+            Value::Integer(class_id as i64),
+            Value::Integer(subclass_id as i64),
+        ]))
+    })
+}
+
+/// Build the GetItemIcon closure: the icon fileID for known items, nil otherwise.
+fn make_get_item_icon(lua: &Lua) -> Result<mlua::Function> {
+    lua.create_function(|_, item_id: Value| {
+        Ok(lookup_item(&item_id).map(|_| UNKNOWN_ITEM_ICON))
     })
 }
 
 /// C_Item query methods: icon, subclass, count, class, spec, name, level.
 fn register_c_item_query_methods(lua: &Lua, t: &mlua::Table) -> Result<()> {
-    t.set("GetItemIconByID", lua.create_function(|_, _id: i32| Ok(134400i32))?)?;
+    t.set("GetItemIconByID", make_get_item_icon(lua)?)?;
     t.set(
         "GetItemSubClassInfo",
         lua.create_function(|lua, (class_id, subclass_id): (i32, i32)| {
@@ -214,10 +231,12 @@ fn register_c_encoding_util(lua: &Lua) -> Result<()> {
     Ok(())
 }
 
-/// Register legacy global item functions (GetItemInfo, GetItemID, etc.).
+/// Register legacy global item functions (GetItemInfo, GetItemIcon, GetItemID, etc.).
 fn register_legacy_item_globals(lua: &Lua) -> Result<()> {
     let globals = lua.globals();
-    globals.set("GetItemInfo", make_legacy_get_item_info(lua)?)?;
+    globals.set("GetItemInfo", make_get_item_info(lua)?)?;
+    globals.set("GetItemInfoInstant", make_get_item_info_instant(lua)?)?;
+    globals.set("GetItemIcon", make_get_item_icon(lua)?)?;
     globals.set(
         "GetItemID",
         lua.create_function(|_, item_link: Option<String>| {
@@ -245,43 +264,6 @@ fn register_legacy_item_stubs(lua: &Lua) -> Result<()> {
     Ok(())
 }
 
-/// Build the legacy global GetItemInfo closure (returns 17 positional values).
-fn make_legacy_get_item_info(lua: &Lua) -> Result<mlua::Function> {
-    lua.create_function(|lua, item_id: Value| {
-        let id = parse_item_id_from_value(&item_id);
-        if id == 0 {
-            return Ok(mlua::MultiValue::new());
-        }
-        let Some(item) = crate::items::get_item(id as u32) else {
-            return Ok(mlua::MultiValue::new());
-        };
-        let color = quality_color(item.quality);
-        let link = format!(
-            "|cff{}|Hitem:{}::::::::80:::::|h[{}]|h|r",
-            color, id, item.name
-        );
-        Ok(mlua::MultiValue::from_vec(vec![
-            Value::String(lua.create_string(item.name)?),
-            Value::String(lua.create_string(&link)?),
-            Value::Integer(item.quality as i64),
-            Value::Integer(item.item_level as i64),
-            Value::Integer(item.required_level as i64),
-            Value::String(lua.create_string(item_class_from_inv_type(item.inventory_type))?),
-            Value::String(lua.create_string("")?),
-            Value::Integer(item.stackable as i64),
-            Value::String(lua.create_string(inv_type_to_equip_loc(item.inventory_type))?),
-            Value::Integer(134400),
-            Value::Integer(item.sell_price as i64),
-            Value::Integer(15),
-            Value::Integer(0),
-            Value::Integer(item.bonding as i64),
-            Value::Integer(item.expansion_id as i64),
-            Value::Nil,
-            Value::Boolean(false),
-        ]))
-    })
-}
-
 /// Register spell-related global functions.
 fn register_spell_globals(lua: &Lua) -> Result<()> {
     register_spell_query_globals(lua)?;
@@ -327,15 +309,10 @@ fn register_spell_query_globals(lua: &Lua) -> Result<()> {
     Ok(())
 }
 
-/// Spell stub globals: cooldown, known checks, chat.
+/// Spell stub globals: known checks, chat.
 fn register_spell_stub_globals(lua: &Lua) -> Result<()> {
     let globals = lua.globals();
 
-    globals.set(
-        "GetSpellCooldown",
-        lua.create_function(|_, _spell_id: Value| Ok((0.0_f64, 0.0_f64, 1, 1.0_f64)))?,
-    )?;
-
     globals.set(
         "IsSpellKnown",
         lua.create_function(|_, args: mlua::MultiValue| {
@@ -383,8 +360,20 @@ fn register_spell_stub_globals(lua: &Lua) -> Result<()> {
     Ok(())
 }
 
+/// The player's equipped item in an inventory slot; other units have none.
+fn equipped_item(
+    state: &Rc<RefCell<SimState>>,
+    unit: &str,
+    slot: i32,
+) -> Option<crate::lua_api::state::ItemStack> {
+    if !unit.eq_ignore_ascii_case("player") {
+        return None;
+    }
+    state.borrow().bags.equipped(slot)
+}
+
 /// Register inventory slot functions.
-fn register_inventory_globals(lua: &Lua) -> Result<()> {
+fn register_inventory_globals(lua: &Lua, state: &Rc<RefCell<SimState>>) -> Result<()> {
     let globals = lua.globals();
 
     // Returns (slotID, textureFileDataID) — WoW returns the slot's background icon as a fileDataID.
@@ -397,17 +386,31 @@ fn register_inventory_globals(lua: &Lua) -> Result<()> {
             ))
         })?,
     )?;
+    let st = Rc::clone(state);
     globals.set(
         "GetInventoryItemLink",
-        lua.create_function(|_, (_unit, _slot): (String, i32)| Ok(Value::Nil))?,
+        lua.create_function(move |lua, (unit, slot): (String, i32)| {
+            let Some(item) = equipped_item(&st, &unit, slot) else {
+                return Ok(Value::Nil);
+            };
+            let link = super::c_container_api::item_link(item.item_id);
+            Ok(Value::String(lua.create_string(link)?))
+        })?,
     )?;
+    let st = Rc::clone(state);
     globals.set(
         "GetInventoryItemID",
-        lua.create_function(|_, (_unit, _slot): (String, i32)| Ok(Value::Nil))?,
+        lua.create_function(move |_, (unit, slot): (String, i32)| {
+            Ok(equipped_item(&st, &unit, slot).map(|item| item.item_id as i64))
+        })?,
     )?;
+    let st = Rc::clone(state);
     globals.set(
         "GetInventoryItemTexture",
-        lua.create_function(|lua, (_unit, slot): (String, i32)| {
+        lua.create_function(move |lua, (unit, slot): (String, i32)| {
+            if equipped_item(&st, &unit, slot).is_some() {
+                return Ok(Value::Integer(UNKNOWN_ITEM_ICON));
+            }
             // Slots 20-23 are bag slots; simulate 4 equipped bags.
             if (20..=24).contains(&slot) {
                 Ok(Value::String(
@@ -418,9 +421,12 @@ fn register_inventory_globals(lua: &Lua) -> Result<()> {
             }
         })?,
     )?;
+    let st = Rc::clone(state);
     globals.set(
         "GetInventoryItemCount",
-        lua.create_function(|_, (_unit, _slot): (String, i32)| Ok(0))?,
+        lua.create_function(move |_, (unit, slot): (String, i32)| {
+            Ok(equipped_item(&st, &unit, slot).map_or(0, |item| item.count))
+        })?,
     )?;
     globals.set(
         "GetInventoryItemBroken",
@@ -519,7 +525,7 @@ fn item_class_name_extended(class_id: i32) -> &'static str {
     }
 }
 
-/// Map item subclass to name for weapon/armor classes.
+/// Map item subclass to name for weapon/armor/miscellaneous classes.
 fn item_subclass_name(class_id: i32, subclass_id: i32) -> &'static str {
     match (class_id, subclass_id) {
         (2, 0) => "One-Handed Axes",
@@ -546,6 +552,7 @@ fn item_subclass_name(class_id: i32, subclass_id: i32) -> &'static str {
         (4, 3) => "Mail",
         (4, 4) => "Plate",
         (4, 6) => "Shield",
+        (15, 0) => "Junk",
         _ => "Unknown",
     }
 }
@@ -565,33 +572,21 @@ pub(super) fn quality_color(quality: u8) -> &'static str {
     }
 }
 
-/// Map inventory type to a rough item class name.
-fn item_class_from_inv_type(inv_type: u8) -> &'static str {
-    match inv_type {
-        13 | 15 | 17 | 21 | 22 | 25 | 26 => "Weapon",
-        1..=12 | 14 | 16 | 23 => "Armor",
-        _ => "Miscellaneous",
-    }
-}
+/// Icon fileID reported for every item (INV_Misc_QuestionMark); the embedded
+/// item table carries no icon column.
+const UNKNOWN_ITEM_ICON: i64 = 134400;
 
-/// Map inventory type to a rough subclass name.
-fn inv_type_to_subclass(inv_type: u8) -> &'static str {
+/// Map inventory type to a rough (classID, subclassID) pair.
+///
+/// The embedded item table has no class column, so weapons and armor are
+/// recognised by equip slot and everything else is Miscellaneous/Junk.
+fn item_class_ids(inv_type: u8) -> (i32, i32) {
     match inv_type {
-        1 => "Head",
-        2 => "Neck",
-        3 => "Shoulder",
-        4 => "Shirt",
-        5 => "Chest",
-        6 => "Waist",
-        7 => "Legs",
-        8 => "Feet",
-        9 => "Wrist",
-        10 => "Hands",
-        11 => "Finger",
-        12 => "Trinket",
-        14 => "Shield",
-        16 => "Back",
-        _ => "Junk",
+        25 => (2, 16),
+        13 | 15 | 17 | 21 | 22 | 26 => (2, 14),
+        14 => (4, 6),
+        1..=12 | 16 | 19 | 20 | 23 => (4, 0),
+        _ => (15, 0),
     }
 }
 
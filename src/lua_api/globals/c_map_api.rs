@@ -2,6 +2,7 @@
 //!
 //! Contains map, exploration, navigation, and location-related API functions.
 
+use crate::maps::{UiMap, UI_MAPS, get_map};
 use mlua::{Lua, Result, Value};
 
 /// Register C_Map namespace and map-related functions.
@@ -9,6 +10,7 @@ pub fn register_c_map_api(lua: &Lua) -> Result<()> {
     let globals = lua.globals();
 
     globals.set("C_Map", register_c_map(lua)?)?;
+    globals.set("SimSetPlayerLocation", lua.create_function(set_player_location)?)?;
     register_zone_text_functions(lua)?;
     globals.set("UiMapPoint", register_ui_map_point(lua)?)?;
     globals.set("C_MapExplorationInfo", register_c_map_exploration(lua)?)?;
@@ -30,28 +32,20 @@ fn register_c_map(lua: &Lua) -> Result<mlua::Table> {
             Ok(Value::String(lua.create_string(format!("Area_{}", area_id))?))
         })?,
     )?;
-    t.set(
-        "GetMapInfo",
-        lua.create_function(|lua, map_id: i32| {
-            let info = lua.create_table()?;
-            info.set("mapID", map_id)?;
-            info.set("name", format!("Map_{}", map_id))?;
-            info.set("mapType", 3)?;
-            info.set("parentMapID", 0)?;
-            Ok(Value::Table(info))
-        })?,
-    )?;
-    t.set(
-        "GetBestMapForUnit",
-        lua.create_function(|_, _unit: String| Ok(Value::Integer(1)))?,
-    )?;
+    t.set("GetMapInfo", lua.create_function(|lua, map_id: i32| {
+        match get_map(map_id) {
+            Some(m) => Ok(Value::Table(map_info_table(lua, m)?)),
+            None => Ok(Value::Nil),
+        }
+    })?)?;
+    t.set("GetBestMapForUnit", lua.create_function(|lua, unit: String| {
+        if !unit_is_with_player(&unit) {
+            return Ok(None);
+        }
+        Ok(Some(player_location(lua).0))
+    })?)?;
     t.set("GetPlayerMapPosition", lua.create_function(create_player_map_position)?)?;
-    t.set(
-        "GetMapChildrenInfo",
-        lua.create_function(|lua, (_map_id, _map_type, _all_descendants): (i32, Option<i32>, Option<bool>)| {
-            lua.create_table()
-        })?,
-    )?;
+    t.set("GetMapChildrenInfo", lua.create_function(create_map_children_info)?)?;
     t.set("GetWorldPosFromMapPos", lua.create_function(create_world_pos_from_map_pos)?)?;
     t.set(
         "GetMapWorldSize",
@@ -69,13 +63,73 @@ fn register_c_map(lua: &Lua) -> Result<mlua::Table> {
     Ok(t)
 }
 
-fn create_player_map_position(lua: &Lua, (_map_id, _unit): (i32, String)) -> Result<Value> {
+/// Player's (mapID, x, y) from `SimState::player_location`.
+fn player_location(lua: &Lua) -> (i32, f64, f64) {
+    crate::lua_api::frame::get_sim_state(lua).borrow().player_location
+}
+
+/// SimSetPlayerLocation(mapID, [x], [y]) - sim-only: move the simulated
+/// player (x/y default to the map center). Lasts for this run only.
+fn set_player_location(lua: &Lua, (map_id, x, y): (i32, Option<f64>, Option<f64>)) -> Result<()> {
+    if get_map(map_id).is_none() {
+        return Err(mlua::Error::RuntimeError(format!("SimSetPlayerLocation: unknown map {map_id}")));
+    }
+    let location = (map_id, x.unwrap_or(0.5), y.unwrap_or(0.5));
+    crate::lua_api::frame::get_sim_state(lua).borrow_mut().player_location = location;
+    Ok(())
+}
+
+/// The sim keeps the player and party together, so they share a map.
+fn unit_is_with_player(unit: &str) -> bool {
+    let unit = unit.to_lowercase();
+    unit == "player" || unit.starts_with("party")
+}
+
+fn map_info_table(lua: &Lua, m: &UiMap) -> Result<mlua::Table> {
+    let info = lua.create_table()?;
+    info.set("mapID", m.map_id)?;
+    info.set("name", m.name)?;
+    info.set("mapType", m.map_type)?;
+    info.set("parentMapID", m.parent_map_id)?;
+    info.set("flags", 0)?;
+    Ok(info)
+}
+
+/// Position on `map_id`, or nil when the unit isn't on that map.
+/// Returned as a table with x/y fields and a GetXY method (Vector2D shape).
+fn create_player_map_position(lua: &Lua, (map_id, unit): (i32, String)) -> Result<Value> {
+    let (player_map, x, y) = player_location(lua);
+    if map_id != player_map || !unit_is_with_player(&unit) {
+        return Ok(Value::Nil);
+    }
     let pos = lua.create_table()?;
-    pos.set("x", 0.5)?;
-    pos.set("y", 0.5)?;
+    pos.set("x", x)?;
+    pos.set("y", y)?;
+    pos.set("GetXY", lua.create_function(move |_, _: Value| Ok((x, y)))?)?;
     Ok(Value::Table(pos))
 }
 
+/// Direct children of `map_id` (all descendants when `all_descendants`),
+/// optionally filtered by map type.
+fn create_map_children_info(
+    lua: &Lua,
+    (map_id, map_type, all_descendants): (i32, Option<i32>, Option<bool>),
+) -> Result<mlua::Table> {
+    let result = lua.create_table()?;
+    let mut parents = vec![map_id];
+    while let Some(parent) = parents.pop() {
+        for m in UI_MAPS.iter().filter(|m| m.parent_map_id == parent) {
+            if map_type.is_none_or(|t| t == m.map_type) {
+                result.push(map_info_table(lua, m)?)?;
+            }
+            if all_descendants.unwrap_or(false) {
+                parents.push(m.map_id);
+            }
+        }
+    }
+    Ok(result)
+}
+
 fn create_world_pos_from_map_pos(lua: &Lua, (map_id, pos): (i32, Value)) -> Result<(i32, mlua::Table)> {
     let (x, y) = if let Value::Table(ref t) = pos {
         let x: f64 = t.get("x").unwrap_or(0.5);
@@ -99,13 +153,17 @@ fn create_world_pos_from_map_pos(lua: &Lua, (map_id, pos): (i32, Value)) -> Resu
 /// Zone text functions (GetRealZoneText, GetZoneText, etc.).
 fn register_zone_text_functions(lua: &Lua) -> Result<()> {
     let globals = lua.globals();
-    globals.set("GetRealZoneText", lua.create_function(|_, ()| Ok("Stormwind City"))?)?;
-    globals.set("GetZoneText", lua.create_function(|_, ()| Ok("Stormwind City"))?)?;
+    globals.set("GetRealZoneText", lua.create_function(|lua, ()| Ok(player_zone_name(lua)))?)?;
+    globals.set("GetZoneText", lua.create_function(|lua, ()| Ok(player_zone_name(lua)))?)?;
     globals.set("GetSubZoneText", lua.create_function(|_, ()| Ok("Trade District"))?)?;
     globals.set("GetMinimapZoneText", lua.create_function(|_, ()| Ok("Trade District"))?)?;
     Ok(())
 }
 
+fn player_zone_name(lua: &Lua) -> &'static str {
+    get_map(player_location(lua).0).map(|m| m.name).unwrap_or("Stormwind City")
+}
+
 /// UiMapPoint - map point creation helper.
 fn register_ui_map_point(lua: &Lua) -> Result<mlua::Table> {
     let t = lua.create_table()?;
@@ -1,37 +1,39 @@
 //! C_Quest namespaces and quest-related API functions.
 //!
 //! Contains quest log, task quests, quest info, and quest line API functions.
-//! Mock quest data provides 3 watched quests for the ObjectiveTracker.
+//! C_QuestLog and the quest leaderboard globals read `SimState::quest_log`,
+//! which starts with 3 watched mock quests for the ObjectiveTracker. The
+//! simulator utilities AddQuestToLog, SetQuestComplete and ClearQuestLog
+//! replace them (from --exec-lua or tests) and fire QUEST_LOG_UPDATE.
 
-use mlua::{Lua, Result, Value};
-
-/// Mock quest definitions: (questID, logIndex, title).
-const MOCK_QUESTS: &[(i32, i32, &str)] = &[
-    (80000, 1, "The Lost Expedition"),
-    (80001, 2, "Defending the Gates"),
-    (80002, 3, "Supply Run"),
-];
+use crate::lua_api::SimState;
+use crate::lua_api::state::{QuestLogEntry, QuestObjective};
+use mlua::{IntoLuaMulti, Lua, MultiValue, Result, Table, Value};
+use std::cell::RefCell;
+use std::rc::Rc;
 
 /// Register quest-related C_* namespaces.
-pub fn register_c_quest_api(lua: &Lua) -> Result<()> {
+pub fn register_c_quest_api(lua: &Lua, state: Rc<RefCell<SimState>>) -> Result<()> {
     let globals = lua.globals();
-    globals.set("C_QuestLog", register_c_quest_log(lua)?)?;
+    globals.set("C_QuestLog", register_c_quest_log(lua, &state)?)?;
     globals.set("C_TaskQuest", register_c_task_quest(lua)?)?;
     globals.set("C_QuestInfoSystem", register_c_quest_info_system(lua)?)?;
     globals.set("C_QuestLine", register_c_quest_line(lua)?)?;
     globals.set("C_QuestOffer", register_c_quest_offer(lua)?)?;
     globals.set("C_QuestSession", register_c_quest_session(lua)?)?;
+    register_quest_leaderboard_functions(lua, &state)?;
+    register_quest_log_sim_utilities(lua, state)?;
     Ok(())
 }
 
 /// C_QuestLog namespace - quest log utilities.
-fn register_c_quest_log(lua: &Lua) -> Result<mlua::Table> {
+fn register_c_quest_log(lua: &Lua, state: &Rc<RefCell<SimState>>) -> Result<mlua::Table> {
     let t = lua.create_table()?;
-    register_quest_log_queries(lua, &t)?;
-    register_quest_log_info(lua, &t)?;
+    register_quest_log_queries(lua, &t, state)?;
+    register_quest_log_info(lua, &t, state)?;
     register_quest_log_requests(lua, &t)?;
-    register_quest_log_watch(lua, &t)?;
-    register_quest_log_status(lua, &t)?;
+    register_quest_log_watch(lua, &t, state)?;
+    register_quest_log_status(lua, &t, state)?;
     t.set("HasActiveThreats", lua.create_function(|_, ()| Ok(false))?)?;
     t.set("GetBountySetInfoForMapID", lua.create_function(|_, _map_id: i32| Ok(Value::Nil))?)?;
     t.set("GetBountiesForMapID", lua.create_function(|lua, _map_id: i32| lua.create_table())?)?;
@@ -40,19 +42,40 @@ fn register_c_quest_log(lua: &Lua) -> Result<mlua::Table> {
 }
 
 /// Quest log query methods (counts, GetInfo, objectives).
-fn register_quest_log_queries(lua: &Lua, t: &mlua::Table) -> Result<()> {
-    // Return 0 quests in the log index — avoids triggering quest button
-    // template display code that requires full child frame hierarchies.
-    // Quest data is still available via GetTitleForQuestID, IsOnQuest, etc.
-    t.set("GetNumQuestLogEntries", lua.create_function(|_, ()| Ok((0i32, 0i32)))?)?;
-    t.set("GetInfo", lua.create_function(|lua, idx: i32| create_quest_info(lua, idx))?)?;
-    t.set("GetQuestIDForLogIndex", lua.create_function(|_, idx: i32| {
-        Ok(MOCK_QUESTS.iter().find(|q| q.1 == idx).map_or(0, |q| q.0))
-    })?)?;
-    t.set("GetLogIndexForQuestID", lua.create_function(|_, quest_id: i32| {
-        Ok(MOCK_QUESTS.iter().find(|q| q.0 == quest_id).map(|q| q.1))
-    })?)?;
-    t.set("GetQuestObjectives", lua.create_function(|lua, _id: i32| lua.create_table())?)?;
+fn register_quest_log_queries(lua: &Lua, t: &mlua::Table, state: &Rc<RefCell<SimState>>) -> Result<()> {
+    // Returns (numShownEntries, numQuests); both 0 while only the mock quests
+    // are present (see `QuestLog::listed`).
+    let st = Rc::clone(state);
+    t.set("GetNumQuestLogEntries", lua.create_function(move |_, ()| {
+        let n = st.borrow().quest_log.num_entries();
+        Ok((n, n))
+    })?)?;
+    let st = Rc::clone(state);
+    t.set("GetInfo", lua.create_function(move |lua, idx: i32| {
+        let s = st.borrow();
+        match s.quest_log.by_log_index(idx) {
+            Some(quest) => Ok(Value::Table(create_quest_info(lua, quest, idx)?)),
+            None => Ok(Value::Nil),
+        }
+    })?)?;
+    let st = Rc::clone(state);
+    t.set("GetQuestIDForLogIndex", lua.create_function(move |_, idx: i32| {
+        Ok(st.borrow().quest_log.by_log_index(idx).map_or(0, |q| q.quest_id))
+    })?)?;
+    let st = Rc::clone(state);
+    t.set("GetLogIndexForQuestID", lua.create_function(move |_, quest_id: i32| {
+        Ok(st.borrow().quest_log.log_index(quest_id))
+    })?)?;
+    let st = Rc::clone(state);
+    t.set("GetQuestObjectives", lua.create_function(move |lua, quest_id: i32| {
+        let list = lua.create_table()?;
+        if let Some(quest) = st.borrow().quest_log.get(quest_id) {
+            for (i, obj) in quest.objectives.iter().enumerate() {
+                list.raw_set(i + 1, create_objective_info(lua, obj)?)?;
+            }
+        }
+        Ok(list)
+    })?)?;
     t.set("GetMaxNumQuestsCanAccept", lua.create_function(|_, ()| Ok(35i32))?)?;
     t.set("GetMaxNumQuests", lua.create_function(|_, ()| Ok(35i32))?)?;
     t.set("SetMapForQuestPOIs", lua.create_function(|_, _map_id: i32| Ok(()))?)?;
@@ -63,20 +86,17 @@ fn register_quest_log_queries(lua: &Lua, t: &mlua::Table) -> Result<()> {
     Ok(())
 }
 
-/// Create a quest info table for a given log index.
-fn create_quest_info(lua: &Lua, idx: i32) -> Result<Value> {
-    let quest = MOCK_QUESTS.iter().find(|q| q.1 == idx);
-    let Some(&(quest_id, _, title)) = quest else {
-        return Ok(Value::Nil);
-    };
+/// Create a QuestInfo table (C_QuestLog.GetInfo) for a quest at `idx`.
+fn create_quest_info(lua: &Lua, quest: &QuestLogEntry, idx: i32) -> Result<Table> {
     let info = lua.create_table()?;
-    info.set("title", title)?;
+    info.set("title", quest.title.as_str())?;
     info.set("questLogIndex", idx)?;
-    info.set("questID", quest_id)?;
+    info.set("questID", quest.quest_id)?;
     info.set("campaignID", 0)?;
-    info.set("level", 80)?;
-    info.set("difficultyLevel", 80)?;
+    info.set("level", quest.level)?;
+    info.set("difficultyLevel", quest.level)?;
     info.set("suggestedGroup", 0)?;
+    info.set("frequency", 0)?;
     info.set("isHeader", false)?;
     info.set("isCollapsed", false)?;
     info.set("isTask", false)?;
@@ -89,8 +109,19 @@ fn create_quest_info(lua: &Lua, idx: i32) -> Result<Value> {
     info.set("overridesSortOrder", false)?;
     info.set("startEvent", false)?;
     info.set("isScaling", false)?;
-    info.set("readyForTranslation", false)?;
-    Ok(Value::Table(info))
+    info.set("readyForTranslation", true)?;
+    Ok(info)
+}
+
+/// Create a QuestObjectiveInfo table (C_QuestLog.GetQuestObjectives).
+fn create_objective_info(lua: &Lua, obj: &QuestObjective) -> Result<Table> {
+    let info = lua.create_table()?;
+    info.set("text", obj.text.as_str())?;
+    info.set("type", obj.kind.as_str())?;
+    info.set("finished", obj.finished)?;
+    info.set("numFulfilled", obj.fulfilled)?;
+    info.set("numRequired", obj.required)?;
+    Ok(info)
 }
 
 /// Quest data request stubs (async data loading).
@@ -102,9 +133,11 @@ fn register_quest_log_requests(lua: &Lua, t: &mlua::Table) -> Result<()> {
 }
 
 /// Quest log info methods (titles, tags).
-fn register_quest_log_info(lua: &Lua, t: &mlua::Table) -> Result<()> {
-    t.set("GetTitleForQuestID", lua.create_function(|lua, id: i32| {
-        let title = MOCK_QUESTS.iter().find(|q| q.0 == id).map_or("Quest", |q| q.2);
+fn register_quest_log_info(lua: &Lua, t: &mlua::Table, state: &Rc<RefCell<SimState>>) -> Result<()> {
+    let st = Rc::clone(state);
+    t.set("GetTitleForQuestID", lua.create_function(move |lua, id: i32| {
+        let s = st.borrow();
+        let title = s.quest_log.get(id).map_or("Quest", |q| q.title.as_str());
         Ok(Value::String(lua.create_string(title)?))
     })?)?;
     t.set("GetQuestTagInfo", lua.create_function(|lua, _id: i32| {
@@ -122,28 +155,56 @@ fn register_quest_log_info(lua: &Lua, t: &mlua::Table) -> Result<()> {
 }
 
 /// Quest watch list methods (tracked quests for ObjectiveTracker).
-fn register_quest_log_watch(lua: &Lua, t: &mlua::Table) -> Result<()> {
-    let num_watches = MOCK_QUESTS.len() as i32;
-    t.set("GetNumQuestWatches", lua.create_function(move |_, ()| Ok(num_watches))?)?;
-    t.set("GetQuestIDForQuestWatchIndex", lua.create_function(|_, idx: i32| {
-        Ok(MOCK_QUESTS.get((idx - 1) as usize).map(|q| q.0))
-    })?)?;
-    t.set("AddQuestWatch", lua.create_function(|_, _id: i32| Ok(()))?)?;
-    t.set("RemoveQuestWatch", lua.create_function(|_, _id: i32| Ok(()))?)?;
+fn register_quest_log_watch(lua: &Lua, t: &mlua::Table, state: &Rc<RefCell<SimState>>) -> Result<()> {
+    let st = Rc::clone(state);
+    t.set("GetNumQuestWatches", lua.create_function(move |_, ()| {
+        Ok(st.borrow().quest_log.watched().count() as i32)
+    })?)?;
+    let st = Rc::clone(state);
+    t.set("GetQuestIDForQuestWatchIndex", lua.create_function(move |_, idx: i32| {
+        let index = usize::try_from(idx).ok().and_then(|i| i.checked_sub(1));
+        Ok(index.and_then(|i| st.borrow().quest_log.watched().nth(i).map(|q| q.quest_id)))
+    })?)?;
+    let st = Rc::clone(state);
+    t.set("AddQuestWatch", lua.create_function(move |_, id: i32| {
+        Ok(set_watched(&st, id, true))
+    })?)?;
+    let st = Rc::clone(state);
+    t.set("RemoveQuestWatch", lua.create_function(move |_, id: i32| {
+        Ok(set_watched(&st, id, false))
+    })?)?;
     t.set("SortQuestWatches", lua.create_function(|_, ()| Ok(()))?)?;
     t.set("GetNumWorldQuestWatches", lua.create_function(|_, ()| Ok(0i32))?)?;
     t.set("GetQuestIDForWorldQuestWatchIndex", lua.create_function(|_, _idx: i32| Ok(Value::Nil))?)?;
     Ok(())
 }
 
+/// Set a quest's watched flag; returns whether the quest is in the log.
+fn set_watched(state: &Rc<RefCell<SimState>>, quest_id: i32, watched: bool) -> bool {
+    match state.borrow_mut().quest_log.get_mut(quest_id) {
+        Some(quest) => {
+            quest.is_watched = watched;
+            true
+        }
+        None => false,
+    }
+}
+
 /// Quest status check methods.
-fn register_quest_log_status(lua: &Lua, t: &mlua::Table) -> Result<()> {
+fn register_quest_log_status(lua: &Lua, t: &mlua::Table, state: &Rc<RefCell<SimState>>) -> Result<()> {
     t.set("IsQuestFlaggedCompleted", lua.create_function(|_, _id: i32| Ok(false))?)?;
-    t.set("IsComplete", lua.create_function(|_, _id: i32| Ok(false))?)?;
-    t.set("IsOnQuest", lua.create_function(|_, id: i32| {
-        Ok(MOCK_QUESTS.iter().any(|q| q.0 == id))
+    let st = Rc::clone(state);
+    t.set("IsComplete", lua.create_function(move |_, id: i32| {
+        Ok(st.borrow().quest_log.get(id).is_some_and(|q| q.is_complete))
+    })?)?;
+    let st = Rc::clone(state);
+    t.set("IsOnQuest", lua.create_function(move |_, id: i32| {
+        Ok(st.borrow().quest_log.get(id).is_some())
+    })?)?;
+    let st = Rc::clone(state);
+    t.set("ReadyForTurnIn", lua.create_function(move |_, id: i32| {
+        Ok(st.borrow().quest_log.get(id).is_some_and(|q| q.is_complete))
     })?)?;
-    t.set("ReadyForTurnIn", lua.create_function(|_, _id: i32| Ok(false))?)?;
     t.set("IsFailed", lua.create_function(|_, _id: i32| Ok(false))?)?;
     t.set("IsPushableQuest", lua.create_function(|_, _id: i32| Ok(false))?)?;
     t.set("IsQuestDisabledForSession", lua.create_function(|_, _id: i32| Ok(false))?)?;
@@ -158,6 +219,125 @@ fn register_quest_log_status(lua: &Lua, t: &mlua::Table) -> Result<()> {
     Ok(())
 }
 
+/// Legacy objective globals used by the ObjectiveTracker:
+/// GetNumQuestLeaderBoards / GetQuestLogLeaderBoard (by log index) and
+/// GetQuestObjectiveInfo (by quest ID).
+fn register_quest_leaderboard_functions(lua: &Lua, state: &Rc<RefCell<SimState>>) -> Result<()> {
+    let g = lua.globals();
+    let st = Rc::clone(state);
+    g.set("GetNumQuestLeaderBoards", lua.create_function(move |_, log_idx: Option<i32>| {
+        let s = st.borrow();
+        Ok(log_idx.and_then(|i| s.quest_log.by_log_index(i)).map_or(0, |q| q.objectives.len() as i32))
+    })?)?;
+    let st = Rc::clone(state);
+    g.set(
+        "GetQuestLogLeaderBoard",
+        lua.create_function(move |lua, (obj_idx, log_idx, _suppress): (i32, Option<i32>, Option<bool>)| {
+            let s = st.borrow();
+            let obj = log_idx
+                .and_then(|i| s.quest_log.by_log_index(i))
+                .and_then(|q| nth_objective(q, obj_idx));
+            match obj {
+                Some(o) => (o.text.as_str(), o.kind.as_str(), o.finished).into_lua_multi(lua),
+                None => Ok(MultiValue::new()),
+            }
+        })?,
+    )?;
+    let st = Rc::clone(state);
+    g.set(
+        "GetQuestObjectiveInfo",
+        lua.create_function(move |lua, (quest_id, obj_idx, _show_completed): (i32, i32, Option<bool>)| {
+            let s = st.borrow();
+            match s.quest_log.get(quest_id).and_then(|q| nth_objective(q, obj_idx)) {
+                Some(o) => (o.text.as_str(), o.kind.as_str(), o.finished, o.fulfilled, o.required)
+                    .into_lua_multi(lua),
+                None => Ok(MultiValue::new()),
+            }
+        })?,
+    )?;
+    Ok(())
+}
+
+/// 1-based objective lookup.
+fn nth_objective(quest: &QuestLogEntry, obj_idx: i32) -> Option<&QuestObjective> {
+    usize::try_from(obj_idx).ok()?.checked_sub(1).and_then(|i| quest.objectives.get(i))
+}
+
+/// Simulator utilities for driving the quest log from Lua:
+///
+/// - `AddQuestToLog{questID=, title=, level=, watched=, objectives={{text=, type=, numFulfilled=, numRequired=}}}`
+///   (the first call drops the mock quests; an existing questID is replaced)
+/// - `SetQuestComplete(questID[, complete])` — complete also fulfills every objective
+/// - `ClearQuestLog()`
+///
+/// Each fires QUEST_LOG_UPDATE so trackers refresh.
+fn register_quest_log_sim_utilities(lua: &Lua, state: Rc<RefCell<SimState>>) -> Result<()> {
+    let g = lua.globals();
+    let st = Rc::clone(&state);
+    g.set("AddQuestToLog", lua.create_function(move |lua, info: Table| {
+        let quest = quest_from_table(&info)?;
+        st.borrow_mut().quest_log.add(quest);
+        fire_quest_log_update(lua)
+    })?)?;
+    let st = Rc::clone(&state);
+    g.set("SetQuestComplete", lua.create_function(move |lua, (quest_id, complete): (i32, Option<bool>)| {
+        let found = match st.borrow_mut().quest_log.get_mut(quest_id) {
+            Some(quest) => {
+                quest.set_complete(complete.unwrap_or(true));
+                true
+            }
+            None => false,
+        };
+        if found {
+            fire_quest_log_update(lua)?;
+        }
+        Ok(found)
+    })?)?;
+    g.set("ClearQuestLog", lua.create_function(move |lua, ()| {
+        state.borrow_mut().quest_log.clear();
+        fire_quest_log_update(lua)
+    })?)?;
+    Ok(())
+}
+
+/// Build a quest from an AddQuestToLog table; `questID` and `title` are required.
+fn quest_from_table(info: &Table) -> Result<QuestLogEntry> {
+    let quest_id: i32 = info.get("questID")?;
+    let title: String = info.get("title")?;
+    let mut objectives = Vec::new();
+    if let Some(list) = info.get::<Option<Table>>("objectives")? {
+        for obj in list.sequence_values::<Table>() {
+            let obj = obj?;
+            let required: i32 = obj.get::<Option<i32>>("numRequired")?.unwrap_or(1);
+            let fulfilled: i32 = obj.get::<Option<i32>>("numFulfilled")?.unwrap_or(0);
+            objectives.push(QuestObjective {
+                text: obj.get::<Option<String>>("text")?.unwrap_or_default(),
+                kind: obj.get::<Option<String>>("type")?.unwrap_or_else(|| "event".to_string()),
+                fulfilled,
+                required,
+                finished: obj.get::<Option<bool>>("finished")?.unwrap_or(fulfilled >= required),
+            });
+        }
+    }
+    let mut quest = QuestLogEntry {
+        quest_id,
+        title,
+        level: info.get::<Option<i32>>("level")?.unwrap_or(80),
+        objectives,
+        is_complete: false,
+        is_watched: info.get::<Option<bool>>("watched")?.unwrap_or(true),
+    };
+    if info.get::<Option<bool>>("isComplete")?.unwrap_or(false) {
+        quest.set_complete(true);
+    }
+    Ok(quest)
+}
+
+fn fire_quest_log_update(lua: &Lua) -> Result<()> {
+    let fire: mlua::Function = lua.globals().get("FireEvent")?;
+    fire.call::<()>(lua.create_string("QUEST_LOG_UPDATE")?)
+}
+
 /// C_TaskQuest namespace - world quest/task utilities.
 fn register_c_task_quest(lua: &Lua) -> Result<mlua::Table> {
     let t = lua.create_table()?;
@@ -228,6 +228,7 @@ fn register_c_texture(lua: &Lua) -> Result<mlua::Table> {
                 info.set("topTexCoord", lookup.info.top_tex_coord)?;
                 info.set("bottomTexCoord", lookup.info.bottom_tex_coord)?;
                 info.set("file", lookup.info.file)?;
+                info.set("filename", lookup.info.file)?;
                 info.set("tilesHorizontally", lookup.info.tiles_horizontally)?;
                 info.set("tilesVertically", lookup.info.tiles_vertically)?;
                 Ok(Value::Table(info))
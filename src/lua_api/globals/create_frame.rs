@@ -74,6 +74,7 @@ pub fn create_frame_function(lua: &Lua, state: Rc<RefCell<SimState>>) -> Result<
 
         // Apply user-specified templates from the registry
         if let Some(ref tmpl) = template {
+            report_missing_templates(lua, &state_clone, tmpl);
             apply_templates_from_registry(lua, &state_clone, &ref_name, tmpl);
 
             // If any template in the chain defines parentArray, insert this frame
@@ -102,6 +103,35 @@ pub fn create_frame_function(lua: &Lua, state: Rc<RefCell<SimState>>) -> Result<
     Ok(create_frame)
 }
 
+/// Warn about templates in `template_names` (or anything they inherit) that
+/// aren't registered, naming the addon that asked for them. Like WoW's
+/// "Couldn't find inherited node", this doesn't stop the frame from being
+/// created from the rest of the chain. Each template is reported once.
+fn report_missing_templates(lua: &Lua, state: &Rc<RefCell<SimState>>, template_names: &str) {
+    let missing = crate::xml::find_missing_templates(template_names);
+    if missing.is_empty() {
+        return;
+    }
+    let addon = crate::lua_api::diagnostics::current_addon(lua);
+    let mut state = state.borrow_mut();
+    for template in missing {
+        if !state.reported_missing_templates.insert(template.name.clone()) {
+            continue;
+        }
+        let msg = match &template.required_by {
+            Some(parent) => format!(
+                "CreateFrame(): Couldn't find inherited node \"{}\" (inherited by \"{parent}\", from {addon})",
+                template.name
+            ),
+            None => format!("CreateFrame(): Couldn't find inherited node \"{}\" (from {addon})", template.name),
+        };
+        eprintln!("[wow-sim] {msg}");
+        if let Some(errors) = state.script_errors.as_mut() {
+            errors.push(msg);
+        }
+    }
+}
+
 /// Parse the arguments to CreateFrame: (frameType, name, parent, template, id).
 #[allow(clippy::type_complexity)]
 fn parse_create_frame_args(
@@ -195,6 +225,7 @@ fn register_new_frame(
         .and_then(|n| state.widgets.get_id_by_name(n));
     if let Some(old_id) = old_same_name {
         orphan_old_frame(&mut state.widgets, old_id);
+        state.hyperlink_regions.remove(&old_id);
     }
 
     state.widgets.register(frame);
@@ -297,6 +328,7 @@ fn create_widget_type_defaults(state: &mut SimState, frame_id: u64, widget_type:
         WidgetType::EditBox => {
             if let Some(frame) = state.widgets.get_mut_visual(frame_id) {
                 frame.mouse_enabled = true;
+                frame.mouse_motion_enabled = true;
             }
         }
         _ => {}
@@ -307,6 +339,7 @@ fn create_widget_type_defaults(state: &mut SimState, frame_id: u64, widget_type:
 fn create_button_defaults(state: &mut SimState, frame_id: u64) {
     if let Some(frame) = state.widgets.get_mut_visual(frame_id) {
         frame.mouse_enabled = true;
+        frame.mouse_motion_enabled = true;
     }
 
     let normal_id = create_child_widget(state, WidgetType::Texture, frame_id);
@@ -375,6 +408,7 @@ fn create_slider_defaults(state: &mut SimState, frame_id: u64) {
         slider.children_keys.insert("High".to_string(), high_id);
         slider.children_keys.insert("Text".to_string(), text_id);
         slider.children_keys.insert("ThumbTexture".to_string(), thumb_id);
+        slider.slider_thumb_id = Some(thumb_id);
     }
 }
 
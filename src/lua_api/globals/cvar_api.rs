@@ -1,8 +1,11 @@
 //! CVar WoW API functions.
 //!
-//! Provides access to configuration variables (CVars).
+//! Provides access to configuration variables (CVars). Values come from the
+//! `cvars` registry: unknown CVars read as nil, registered ones fall back to
+//! their default, and every successful set fires `CVAR_UPDATE`.
 
 use super::super::SimState;
+use crate::cvars::CVarType;
 use mlua::{Lua, Result, Value};
 use std::cell::RefCell;
 use std::rc::Rc;
@@ -18,36 +21,11 @@ pub fn register_cvar_api(lua: &Lua, state: Rc<RefCell<SimState>>) -> Result<()>
 fn register_c_cvar_namespace(lua: &Lua, state: &Rc<RefCell<SimState>>) -> Result<()> {
     let t = lua.create_table()?;
 
-    let s = Rc::clone(state);
-    t.set("GetCVar", lua.create_function(move |lua, cvar: String| {
-        let state = s.borrow();
-        match state.cvars.get(&cvar) {
-            Some(value) => Ok(Value::String(lua.create_string(&value)?)),
-            None => Ok(Value::Nil),
-        }
-    })?)?;
-
-    let s = Rc::clone(state);
-    t.set("SetCVar", lua.create_function(move |_, (cvar, value): (String, Option<String>)| {
-        let state = s.borrow();
-        state.cvars.set(&cvar, value.as_deref().unwrap_or(""));
-        Ok(true)
-    })?)?;
-
-    let s = Rc::clone(state);
-    t.set("GetCVarBool", lua.create_function(move |_, cvar: String| {
-        let state = s.borrow();
-        Ok(state.cvars.get_bool(&cvar))
-    })?)?;
-
-    let s = Rc::clone(state);
-    t.set("GetCVarDefault", lua.create_function(move |lua, cvar: String| {
-        let state = s.borrow();
-        match state.cvars.get(&cvar) {
-            Some(value) => Ok(Value::String(lua.create_string(&value)?)),
-            None => Ok(Value::Nil),
-        }
-    })?)?;
+    t.set("GetCVar", create_get_cvar(lua, state)?)?;
+    t.set("SetCVar", create_set_cvar(lua, state)?)?;
+    t.set("GetCVarBool", create_get_cvar_bool(lua, state)?)?;
+    t.set("GetCVarDefault", create_get_cvar_default(lua, state)?)?;
+    t.set("RegisterCVar", create_register_cvar(lua, state)?)?;
 
     t.set("GetCVarBitfield", lua.create_function(|_, (_name, _index): (String, Option<i32>)| {
         Ok(false)
@@ -57,37 +35,114 @@ fn register_c_cvar_namespace(lua: &Lua, state: &Rc<RefCell<SimState>>) -> Result
         Ok(true)
     })?)?;
 
-    t.set("RegisterCVar", lua.create_function(|_, (_name, _value): (String, Option<String>)| {
-        Ok(())
-    })?)?;
-
     t.set("ResetTestCVars", lua.create_function(|_, ()| Ok(()))?)?;
 
     lua.globals().set("C_CVar", t)?;
     Ok(())
 }
 
-/// Register GetCVar and SetCVar functions.
+/// Register the legacy global GetCVar/SetCVar/GetCVarBool/GetCVarDefault/RegisterCVar.
 fn register_cvar_functions(lua: &Lua, state: &Rc<RefCell<SimState>>) -> Result<()> {
     let globals = lua.globals();
+    globals.set("GetCVar", create_get_cvar(lua, state)?)?;
+    globals.set("SetCVar", create_set_cvar(lua, state)?)?;
+    globals.set("GetCVarBool", create_get_cvar_bool(lua, state)?)?;
+    globals.set("GetCVarDefault", create_get_cvar_default(lua, state)?)?;
+    globals.set("RegisterCVar", create_register_cvar(lua, state)?)?;
+    Ok(())
+}
+
+fn create_get_cvar(lua: &Lua, state: &Rc<RefCell<SimState>>) -> Result<mlua::Function> {
+    let s = Rc::clone(state);
+    lua.create_function(move |lua, cvar: String| {
+        let value = s.borrow().cvars.get(&cvar);
+        optional_string(lua, value)
+    })
+}
 
-    let state_for_getcvar = Rc::clone(state);
-    let get_cvar = lua.create_function(move |lua, cvar: String| {
-        let state = state_for_getcvar.borrow();
-        match state.cvars.get(&cvar) {
-            Some(value) => Ok(Value::String(lua.create_string(&value)?)),
-            None => Ok(Value::Nil),
+/// `SetCVar(name, value)`: stores the value and fires `CVAR_UPDATE(name, value)`.
+/// Booleans are stored as "1"/"0", matching the client.
+fn create_set_cvar(lua: &Lua, state: &Rc<RefCell<SimState>>) -> Result<mlua::Function> {
+    let s = Rc::clone(state);
+    lua.create_function(move |lua, (cvar, value): (String, Value)| {
+        let (value, scale_changed) = {
+            let mut state = s.borrow_mut();
+            let value = cvar_value_string(&value, state.cvars.cvar_type(&cvar));
+            state.cvars.set(&cvar, &value);
+            let is_scale_cvar = cvar.eq_ignore_ascii_case("uiScale")
+                || cvar.eq_ignore_ascii_case("useUiScale");
+            (value, is_scale_cvar && state.apply_ui_scale_cvars())
+        };
+        let fire: mlua::Function = lua.globals().get("FireEvent")?;
+        fire.call::<()>((
+            lua.create_string("CVAR_UPDATE")?,
+            lua.create_string(&cvar)?,
+            lua.create_string(&value)?,
+        ))?;
+        if scale_changed {
+            fire.call::<()>(lua.create_string("UI_SCALE_CHANGED")?)?;
+        }
+        Ok(true)
+    })
+}
+
+/// `GetCVarBool(name)`: "1" is true, "0" false, nil for unknown CVars.
+fn create_get_cvar_bool(lua: &Lua, state: &Rc<RefCell<SimState>>) -> Result<mlua::Function> {
+    let s = Rc::clone(state);
+    lua.create_function(move |_, cvar: String| {
+        let state = s.borrow();
+        if state.cvars.get(&cvar).is_none() {
+            return Ok(None);
         }
-    })?;
-    globals.set("GetCVar", get_cvar)?;
+        Ok(Some(state.cvars.get_bool(&cvar)))
+    })
+}
 
-    let state_for_setcvar = Rc::clone(state);
-    let set_cvar = lua.create_function(move |_, (cvar, value): (String, String)| {
-        let state = state_for_setcvar.borrow();
-        Ok(state.cvars.set(&cvar, &value))
-    })?;
-    globals.set("SetCVar", set_cvar)?;
+fn create_get_cvar_default(lua: &Lua, state: &Rc<RefCell<SimState>>) -> Result<mlua::Function> {
+    let s = Rc::clone(state);
+    lua.create_function(move |lua, cvar: String| {
+        let value = s.borrow().cvars.get_default(&cvar);
+        optional_string(lua, value)
+    })
+}
 
-    Ok(())
+/// `RegisterCVar(name, default)`: adds an addon CVar to the registry.
+fn create_register_cvar(lua: &Lua, state: &Rc<RefCell<SimState>>) -> Result<mlua::Function> {
+    let s = Rc::clone(state);
+    lua.create_function(move |_, (cvar, default): (String, Value)| {
+        let state = s.borrow();
+        let default = match default {
+            Value::Nil => None,
+            v => Some(cvar_value_string(&v, None)),
+        };
+        state.cvars.register(&cvar, default.as_deref());
+        Ok(())
+    })
 }
 
+/// Convert a Lua value passed to SetCVar into the stored string.
+fn cvar_value_string(value: &Value, cvar_type: Option<CVarType>) -> String {
+    match value {
+        Value::Nil => String::new(),
+        Value::Boolean(b) => if *b { "1" } else { "0" }.to_string(),
+        Value::Integer(n) => n.to_string(),
+        Value::Number(n) if n.fract() == 0.0 => (*n as i64).to_string(),
+        Value::Number(n) => n.to_string(),
+        Value::String(s) => {
+            let s = s.to_string_lossy();
+            match (cvar_type, s.as_str()) {
+                (Some(CVarType::Boolean), "true") => "1".to_string(),
+                (Some(CVarType::Boolean), "false") => "0".to_string(),
+                _ => s,
+            }
+        }
+        _ => String::new(),
+    }
+}
+
+fn optional_string(lua: &Lua, value: Option<String>) -> Result<Value> {
+    match value {
+        Some(value) => Ok(Value::String(lua.create_string(&value)?)),
+        None => Ok(Value::Nil),
+    }
+}
@@ -241,7 +241,10 @@ fn setup_alert_frame(lua: &Lua) -> Result<()> {
 }
 
 /// Set up EditModeManagerFrame with AccountSettings child frame.
-fn setup_edit_mode_manager(_lua: &Lua, state: &Rc<RefCell<SimState>>) -> Result<()> {
+///
+/// Until Blizzard_EditMode replaces the placeholder, it also answers the
+/// system-frame registration calls addons make from `OnSystemLoad`.
+fn setup_edit_mode_manager(lua: &Lua, state: &Rc<RefCell<SimState>>) -> Result<()> {
     // AccountSettings is a child frame with parentKey="AccountSettings"
     let emm_id = state.borrow().widgets.get_id_by_name("EditModeManagerFrame");
     if let Some(parent_id) = emm_id {
@@ -257,7 +260,36 @@ fn setup_edit_mode_manager(_lua: &Lua, state: &Rc<RefCell<SimState>>) -> Result<
             }
         }
     }
-    Ok(())
+    lua.load(
+        r#"
+        local emm = EditModeManagerFrame
+        emm.registeredSystemFrames = {}
+        function emm:RegisterSystemFrame(systemFrame)
+            table.insert(self.registeredSystemFrames, systemFrame)
+        end
+        function emm:GetRegisteredSystemFrame(system, systemIndex)
+            for _, frame in ipairs(self.registeredSystemFrames) do
+                if frame.system == system and frame.systemIndex == systemIndex then
+                    return frame
+                end
+            end
+        end
+        function emm:IsInitialized() return self.layoutInfo ~= nil end
+        function emm:IsEditModeActive() return false end
+        function emm:GetActiveLayoutInfo()
+            local info = self.layoutInfo
+            return info and info.layouts[info.activeLayout]
+        end
+
+        if not EditModeSystemMixin then
+            EditModeSystemMixin = {}
+            function EditModeSystemMixin:OnSystemLoad()
+                EditModeManagerFrame:RegisterSystemFrame(self)
+            end
+        end
+        "#,
+    )
+    .exec()
 }
 
 /// Register unit frame globals: BuffFrame, DebuffFrame.
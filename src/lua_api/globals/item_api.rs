@@ -27,10 +27,10 @@ pub fn register_item_api(lua: &Lua) -> Result<()> {
 }
 
 /// Parse item ID from a WoW item link string.
-fn parse_item_id_from_link(link: &str) -> i32 {
+pub(crate) fn parse_item_id_from_link(link: &str) -> i32 {
     link.split("item:")
         .nth(1)
-        .and_then(|s| s.split(':').next())
+        .and_then(|s| s.split([':', '|']).next())
         .and_then(|s| s.parse::<i32>().ok())
         .unwrap_or(0)
 }
@@ -1,5 +1,7 @@
 //! Locale, region, and build info WoW API functions.
 
+use crate::lua_api::frame::get_sim_state;
+use crate::toc::{client_interface, TocFlavor};
 use mlua::{Lua, Result, Value};
 
 /// Register locale, region, and build-related global functions.
@@ -14,42 +16,91 @@ pub fn register_locale_api(lua: &Lua) -> Result<()> {
     Ok(())
 }
 
-/// Register `GetBuildInfo()` - game version info.
+/// Register `GetBuildInfo()` - game version info - and the `WOW_PROJECT_*` constants.
+///
+/// Both follow [`client_interface`], so setting `WOW_SIM_INTERFACE=11507`
+/// simulates a Classic Era client.
 fn register_build_info(lua: &Lua) -> Result<()> {
     let globals = lua.globals();
 
     let get_build_info = lua.create_function(|lua, ()| {
+        let interface = client_interface();
+        let version = version_string(interface);
         Ok(mlua::MultiValue::from_vec(vec![
-            Value::String(lua.create_string("11.0.7")?),
+            Value::String(lua.create_string(&version)?),
             Value::String(lua.create_string("58238")?),
             Value::String(lua.create_string("Jan 7 2025")?),
-            Value::Integer(110007),
-            Value::String(lua.create_string("11.0.7")?),
+            Value::Integer(interface as i64),
+            Value::String(lua.create_string(&version)?),
             Value::String(lua.create_string("Release")?),
         ]))
     })?;
     globals.set("GetBuildInfo", get_build_info)?;
 
+    globals.set("WOW_PROJECT_MAINLINE", TocFlavor::Mainline.project_id())?;
+    globals.set("WOW_PROJECT_CLASSIC", TocFlavor::Vanilla.project_id())?;
+    globals.set("WOW_PROJECT_BURNING_CRUSADE_CLASSIC", TocFlavor::Tbc.project_id())?;
+    globals.set("WOW_PROJECT_WRATH_CLASSIC", TocFlavor::Wrath.project_id())?;
+    globals.set("WOW_PROJECT_CATACLYSM_CLASSIC", TocFlavor::Cata.project_id())?;
+    globals.set("WOW_PROJECT_MISTS_CLASSIC", TocFlavor::Mists.project_id())?;
+    globals.set("WOW_PROJECT_ID", TocFlavor::current().project_id())?;
+
     Ok(())
 }
 
-/// Register realm-related functions.
+/// "11.0.7" for interface 110007, "1.15.7" for 11507.
+fn version_string(interface: u32) -> String {
+    format!("{}.{}.{}", interface / 10000, interface / 100 % 100, interface % 100)
+}
+
+/// Register realm-related functions and `Ambiguate`.
 fn register_realm_functions(lua: &Lua) -> Result<()> {
     let globals = lua.globals();
 
     globals.set(
         "GetRealmName",
-        lua.create_function(|lua, ()| Ok(Value::String(lua.create_string("SimulatedRealm")?)))?,
+        lua.create_function(|lua, ()| {
+            let realm = get_sim_state(lua).borrow().realm_name.clone();
+            Ok(Value::String(lua.create_string(realm)?))
+        })?,
     )?;
     globals.set(
         "GetNormalizedRealmName",
-        lua.create_function(|lua, ()| Ok(Value::String(lua.create_string("SimulatedRealm")?)))?,
+        lua.create_function(|lua, ()| {
+            let realm = normalize_realm_name(&get_sim_state(lua).borrow().realm_name);
+            Ok(Value::String(lua.create_string(realm)?))
+        })?,
     )?;
     globals.set("GetRealmID", lua.create_function(|_, ()| Ok(1i32))?)?;
+    globals.set(
+        "Ambiguate",
+        lua.create_function(|lua, (name, context): (String, Option<String>)| {
+            let realm = normalize_realm_name(&get_sim_state(lua).borrow().realm_name);
+            let display = ambiguate(&name, context.as_deref().unwrap_or("none"), &realm);
+            Ok(Value::String(lua.create_string(display)?))
+        })?,
+    )?;
 
     Ok(())
 }
 
+/// Realm name as used in "Name-Realm" strings: "Burning Blade" -> "BurningBlade".
+pub fn normalize_realm_name(realm: &str) -> String {
+    realm.chars().filter(|c| !c.is_whitespace() && *c != '-').collect()
+}
+
+/// `Ambiguate(fullName, context)`: "short" and "all" always drop the realm;
+/// "none", "guild" and "mail" drop it only for the player's own realm.
+pub fn ambiguate<'a>(full_name: &'a str, context: &str, own_realm: &str) -> &'a str {
+    let Some((name, realm)) = full_name.split_once('-') else { return full_name };
+    let same_realm = realm.eq_ignore_ascii_case(own_realm);
+    match context {
+        "short" | "all" => name,
+        _ if same_realm => name,
+        _ => full_name,
+    }
+}
+
 /// Register locale and region functions.
 fn register_locale_and_region(lua: &Lua) -> Result<()> {
     let globals = lua.globals();
@@ -89,7 +140,11 @@ fn register_client_type_checks(lua: &Lua) -> Result<()> {
 fn register_expansion_functions(lua: &Lua) -> Result<()> {
     let globals = lua.globals();
 
-    globals.set("GetExpansionLevel", lua.create_function(|_, ()| Ok(10))?)?;
+    globals.set("GetExpansionLevel", lua.create_function(|_, ()| Ok(TocFlavor::current().expansion_level()))?)?;
+    globals.set(
+        "GetClassicExpansionLevel",
+        lua.create_function(|_, ()| Ok(TocFlavor::current().expansion_level()))?,
+    )?;
     globals.set("GetMaxLevelForPlayerExpansion", lua.create_function(|_, ()| Ok(80))?)?;
     globals.set("GetMaxPlayerLevel", lua.create_function(|_, ()| Ok(80))?)?;
     globals.set(
@@ -137,7 +192,7 @@ fn register_expansion_constants(lua: &Lua) -> Result<()> {
     globals.set("LE_EXPANSION_SHADOWLANDS", 8)?;
     globals.set("LE_EXPANSION_DRAGONFLIGHT", 9)?;
     globals.set("LE_EXPANSION_WAR_WITHIN", 10)?;
-    globals.set("LE_EXPANSION_LEVEL_CURRENT", 10)?;
+    globals.set("LE_EXPANSION_LEVEL_CURRENT", TocFlavor::current().expansion_level())?;
 
     Ok(())
 }
@@ -5,6 +5,7 @@
 //! - `locale_api` - Locale, region, and build info functions
 //! - `create_frame` - CreateFrame function implementation
 //! - `unit_api` - Unit information functions (UnitName, UnitClass, etc.)
+//! - `unit_state_api` - SetUnitState simulator utility for the unit model
 //! - `timer_api` - C_Timer namespace for timer management
 //! - `enum_api` - Enum table with game enumerations
 //! - `c_map_api` - C_Map and map/location related namespaces
@@ -15,6 +16,7 @@
 //! - `dropdown_api` - UIDropDownMenu system
 //! - `strings` - UI string constants (ERR_*, localization, font codes, etc.)
 //! - `utility_api` - Table manipulation (wipe, tinsert, tContains), string utilities, secure functions
+//! - `utf8_api` - UTF-8 aware string helpers (strlenutf8, string.utf8sub, strtrim)
 //! - `font_api` - Font object creation (CreateFont, CreateFontFamily, standard fonts)
 //! - `settings_api` - Settings namespace for addon configuration UI
 //! - `mixin_api` - UI mixins (POIButtonMixin, MapCanvasPinMixin, Menu, MenuUtil)
@@ -74,8 +76,10 @@ pub mod tooltip_api;
 pub mod unit_api;
 pub mod unit_combat_api;
 pub mod unit_health_power_api;
+pub mod unit_state_api;
 pub mod security_api;
 pub mod utility_api;
+pub mod utf8_api;
 
 // Re-export for backwards compatibility
 pub use strings::register_all_ui_strings;
@@ -4,6 +4,9 @@
 //! secureexecuterange, SecureHandler stubs, state/attribute driver stubs,
 //! and SecureCmdOptionParse.
 
+use crate::lua_api::frame::{get_sim_state, lud_to_id};
+use crate::lua_api::frame::metatable::frame_method_hooks_active;
+use crate::lua_api::script_helpers::call_error_handler;
 use mlua::{Lua, Result, Value};
 
 /// Register all security-related API functions.
@@ -50,26 +53,25 @@ pub fn register_security_functions(lua: &Lua) -> Result<()> {
 }
 
 /// securecall/securecallfunction implementation.
-/// Accepts a function or a string name (resolved from _G).
+/// Accepts a function or a string name (resolved from _G). Errors go to the
+/// error handler and the call returns nothing, as in the client.
 fn securecall_impl(lua: &Lua, args: mlua::MultiValue) -> Result<mlua::MultiValue> {
     let mut args_iter = args.into_iter();
     let func_or_name = args_iter.next().unwrap_or(Value::Nil);
     let remaining = mlua::MultiValue::from_vec(args_iter.collect());
-    match func_or_name {
-        Value::Function(f) => f.call::<mlua::MultiValue>(remaining),
-        Value::String(s) => {
-            let name = s.to_str()?;
-            match lua.globals().get::<Value>(name)? {
-                Value::Function(f) => f.call::<mlua::MultiValue>(remaining),
-                _ => Ok(mlua::MultiValue::new()),
-            }
-        }
-        _ => Ok(mlua::MultiValue::new()),
-    }
+    let func = match func_or_name {
+        Value::Function(f) => f,
+        Value::String(name) => match lua.globals().get::<Value>(name)? {
+            Value::Function(f) => f,
+            _ => return Ok(mlua::MultiValue::new()),
+        },
+        _ => return Ok(mlua::MultiValue::new()),
+    };
+    Ok(call_reporting_errors(lua, &func, remaining))
 }
 
 /// securecallmethod(object, methodName, ...) → object:methodName(...)
-fn securecallmethod_impl(_lua: &Lua, args: mlua::MultiValue) -> Result<mlua::MultiValue> {
+fn securecallmethod_impl(lua: &Lua, args: mlua::MultiValue) -> Result<mlua::MultiValue> {
     let mut it = args.into_iter();
     let obj = match it.next() {
         Some(Value::Table(t)) => t,
@@ -84,52 +86,64 @@ fn securecallmethod_impl(_lua: &Lua, args: mlua::MultiValue) -> Result<mlua::Mul
         Value::Function(f) => {
             let mut call_args = vec![Value::Table(obj)];
             call_args.extend(remaining);
-            f.call::<mlua::MultiValue>(mlua::MultiValue::from_iter(call_args))
+            Ok(call_reporting_errors(lua, &f, mlua::MultiValue::from_iter(call_args)))
         }
         _ => Ok(mlua::MultiValue::new()),
     }
 }
 
+/// Call `func`, routing an error to the WoW error handler instead of the caller.
+fn call_reporting_errors(lua: &Lua, func: &mlua::Function, args: mlua::MultiValue) -> mlua::MultiValue {
+    func.call::<mlua::MultiValue>(args).unwrap_or_else(|e| {
+        call_error_handler(lua, &e.to_string());
+        mlua::MultiValue::new()
+    })
+}
+
 /// hooksecurefunc(name, hook) or hooksecurefunc(table, name, hook).
+///
+/// Replaces `table[name]` with a wrapper that runs the original, then the hook
+/// with the same arguments, and returns the original's results. The target
+/// may be a frame handle; its hooked method then shadows the shared one.
 fn register_hooksecurefunc(lua: &Lua) -> Result<()> {
+    // Index through metamethods so frame handles resolve like `t[k]` in Lua.
+    let access: mlua::Table = lua
+        .load("return { get = function(t, k) return t[k] end, set = function(t, k, v) t[k] = v end }")
+        .eval()?;
+    let get: mlua::Function = access.get("get")?;
+    let set: mlua::Function = access.get("set")?;
+    let hooks_active = frame_method_hooks_active(lua);
     lua.globals().set(
         "hooksecurefunc",
-        lua.create_function(|lua, args: mlua::MultiValue| {
-            let args: Vec<Value> = args.into_iter().collect();
-
-            let (table, name, hook) = if args.len() == 2 {
-                let name = if let Value::String(s) = &args[0] {
-                    s.to_string_lossy().to_string()
-                } else {
-                    String::new()
-                };
-                (lua.globals(), name, args[1].clone())
-            } else if args.len() >= 3 {
-                let table = if let Value::Table(t) = &args[0] {
-                    t.clone()
-                } else {
-                    lua.globals()
-                };
-                let name = if let Value::String(s) = &args[1] {
-                    s.to_string_lossy().to_string()
-                } else {
-                    String::new()
-                };
-                (table, name, args[2].clone())
-            } else {
+        lua.create_function(move |lua, args: mlua::MultiValue| {
+            let mut args: Vec<Value> = args.into_iter().collect();
+            if matches!(args.first(), Some(Value::String(_))) {
+                args.insert(0, Value::Table(lua.globals()));
+            }
+            let [target, Value::String(name), Value::Function(hook), ..] = args.as_slice() else {
                 return Ok(());
             };
-
-            let original: Value = table.get::<Value>(name.as_str())?;
-            if let (Value::Function(orig_fn), Value::Function(hook_fn)) = (original, hook) {
-                let wrapper = lua.create_function(move |_, args: mlua::MultiValue| {
-                    let result = orig_fn.call::<mlua::MultiValue>(args.clone())?;
-                    let _ = hook_fn.call::<mlua::MultiValue>(args);
-                    Ok(result)
-                })?;
-                table.set(name.as_str(), wrapper)?;
+            // Hooking something that isn't a function is a no-op rather than an
+            // error, so addons probing APIs the simulator lacks keep loading.
+            let Value::Function(original) = get.call::<Value>((target.clone(), name.clone()))? else {
+                return Ok(());
+            };
+            let hook = hook.clone();
+            let wrapper = lua.create_function(move |lua, args: mlua::MultiValue| {
+                let result = original.call::<mlua::MultiValue>(args.clone())?;
+                if let Err(e) = hook.call::<()>(args) {
+                    call_error_handler(lua, &e.to_string());
+                }
+                Ok(result)
+            })?;
+            set.call::<()>((target.clone(), name.clone(), wrapper))?;
+            if let Value::LightUserData(ud) = target {
+                let state_rc = get_sim_state(lua);
+                if let Some(frame) = state_rc.borrow_mut().widgets.get_mut(lud_to_id(*ud)) {
+                    frame.has_method_hooks = true;
+                }
+                hooks_active.set(true);
             }
-
             Ok(())
         })?,
     )?;
@@ -2,51 +2,90 @@
 
 use super::super::SimState;
 use mlua::{Lua, Result, Value};
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
 use std::rc::Rc;
 
 /// Register all sound-related globals and the C_Sound namespace.
 pub fn register_sound_api(lua: &Lua, state: Rc<RefCell<SimState>>) -> Result<()> {
     let g = lua.globals();
+    let next_fake = Rc::new(Cell::new(FAKE_HANDLE_BASE));
 
-    g.set("PlaySound", create_play_sound(lua, Rc::clone(&state))?)?;
-    g.set("PlaySoundFile", create_play_sound_file(lua, Rc::clone(&state))?)?;
+    g.set("PlaySound", create_play_sound(lua, Rc::clone(&state), Rc::clone(&next_fake))?)?;
+    g.set("PlaySoundFile", create_play_sound_file(lua, Rc::clone(&state), Rc::clone(&next_fake))?)?;
     g.set("StopSound", create_stop_sound(lua, Rc::clone(&state))?)?;
 
-    register_c_sound(lua, state)?;
+    register_c_sound(lua, state, next_fake)?;
     register_game_message_info(lua)?;
     Ok(())
 }
 
+/// Channels accepted by PlaySound/PlaySoundFile (case-insensitive).
+const SOUND_CHANNELS: &[&str] = &["Master", "SFX", "Music", "Ambience", "Dialog", "Talking Head"];
+
+/// Handles for sounds that resolved but didn't reach the audio device (no
+/// device, `sound` feature off, file not shipped). Kept well above the real
+/// handle range so `StopSound`/`IsPlaying` treat them as already finished.
+const FAKE_HANDLE_BASE: u32 = 0x4000_0000;
+
+fn is_valid_channel(channel: Option<&str>) -> bool {
+    channel.is_none_or(|c| SOUND_CHANNELS.iter().any(|known| known.eq_ignore_ascii_case(c)))
+}
+
+fn number_arg(v: &Value) -> Option<u32> {
+    match v {
+        Value::Integer(n) => Some(*n as u32),
+        Value::Number(n) => Some(*n as u32),
+        _ => None,
+    }
+}
+
+/// Play `path` through the SoundManager if there is one. Sounds that cannot be
+/// heard still report willPlay with a fake handle, as the client would.
+fn play_resolved(state: &RefCell<SimState>, next_fake: &Cell<u32>, path: &str) -> (bool, Value) {
+    let handle = state
+        .borrow_mut()
+        .sound_manager
+        .as_mut()
+        .and_then(|mgr| mgr.play_sound_file(path))
+        .unwrap_or_else(|| {
+            let h = next_fake.get();
+            next_fake.set(h + 1);
+            h
+        });
+    (true, Value::Integer(handle as i64))
+}
+
 /// PlaySound(soundKitID, [channel]) -> willPlay, soundHandle
-fn create_play_sound(lua: &Lua, state: Rc<RefCell<SimState>>) -> Result<mlua::Function> {
-    lua.create_function(move |_, (id, _channel): (Value, Option<String>)| {
-        let soundkit_id = match &id {
-            Value::Integer(n) => *n as u32,
-            Value::Number(n) => *n as u32,
-            _ => return Ok((false, Value::Nil)),
-        };
-        let mut st = state.borrow_mut();
-        let handle = st.sound_manager.as_mut().and_then(|mgr| mgr.play_sound(soundkit_id));
-        match handle {
-            Some(h) => Ok((true, Value::Integer(h as i64))),
-            None => Ok((false, Value::Nil)),
+///
+/// The kit ID is resolved to a file through the embedded SoundKit table;
+/// unknown kits and invalid channels don't play.
+fn create_play_sound(
+    lua: &Lua,
+    state: Rc<RefCell<SimState>>,
+    next_fake: Rc<Cell<u32>>,
+) -> Result<mlua::Function> {
+    lua.create_function(move |_, (id, channel): (Value, Option<String>)| {
+        let file = number_arg(&id).and_then(crate::soundkits::soundkit_file);
+        match file {
+            Some(file) if is_valid_channel(channel.as_deref()) => {
+                Ok(play_resolved(&state, &next_fake, file))
+            }
+            _ => Ok((false, Value::Nil)),
         }
     })
 }
 
 /// PlaySoundFile(path_or_id, [channel]) -> willPlay, soundHandle
-fn create_play_sound_file(lua: &Lua, state: Rc<RefCell<SimState>>) -> Result<mlua::Function> {
-    lua.create_function(move |_, (path_or_id, _channel): (Value, Option<String>)| {
+fn create_play_sound_file(
+    lua: &Lua,
+    state: Rc<RefCell<SimState>>,
+    next_fake: Rc<Cell<u32>>,
+) -> Result<mlua::Function> {
+    lua.create_function(move |_, (path_or_id, channel): (Value, Option<String>)| {
         match &path_or_id {
-            Value::String(s) => {
+            Value::String(s) if is_valid_channel(channel.as_deref()) => {
                 let path = s.to_string_lossy().to_string();
-                let mut st = state.borrow_mut();
-                let handle = st.sound_manager.as_mut().and_then(|mgr| mgr.play_sound_file(&path));
-                match handle {
-                    Some(h) => Ok((true, Value::Integer(h as i64))),
-                    None => Ok((false, Value::Nil)),
-                }
+                Ok(play_resolved(&state, &next_fake, &path))
             }
             // Numeric FileDataID — not supported
             _ => Ok((false, Value::Nil)),
@@ -72,12 +111,12 @@ fn create_stop_sound(lua: &Lua, state: Rc<RefCell<SimState>>) -> Result<mlua::Fu
 
 /// Register C_Sound namespace with IsPlaying and stub methods.
 /// Also includes PlaySound/PlaySoundFile since Sound.lua reassigns globals from C_Sound.
-fn register_c_sound(lua: &Lua, state: Rc<RefCell<SimState>>) -> Result<()> {
+fn register_c_sound(lua: &Lua, state: Rc<RefCell<SimState>>, next_fake: Rc<Cell<u32>>) -> Result<()> {
     let snd = lua.create_table()?;
 
     // C_Sound.PlaySound — Sound.lua does `PlaySound = C_Sound.PlaySound`
-    snd.set("PlaySound", create_play_sound(lua, Rc::clone(&state))?)?;
-    snd.set("PlaySoundFile", create_play_sound_file(lua, Rc::clone(&state))?)?;
+    snd.set("PlaySound", create_play_sound(lua, Rc::clone(&state), Rc::clone(&next_fake))?)?;
+    snd.set("PlaySoundFile", create_play_sound_file(lua, Rc::clone(&state), next_fake)?)?;
 
     let st = Rc::clone(&state);
     snd.set("IsPlaying", lua.create_function(move |_, handle: Value| {
@@ -16,6 +16,7 @@ pub fn register_spell_api(lua: &Lua, state: Rc<RefCell<SimState>>) -> Result<()>
     globals.set("C_SpellBook", register_c_spell_book(lua, Rc::clone(&state))?)?;
     globals.set("C_Spell", register_c_spell(lua, Rc::clone(&state))?)?;
     globals.set("C_Traits", super::traits_api::register_c_traits(lua, Rc::clone(&state))?)?;
+    register_spell_info_globals(lua, Rc::clone(&state))?;
     register_cast_globals(lua, state)?;
 
     Ok(())
@@ -196,6 +197,51 @@ pub fn fire_ui_error(lua: &Lua, message: &str) -> mlua::Result<()> {
     ))
 }
 
+/// Legacy spell info globals: GetSpellInfo, GetSpellCooldown, and the
+/// simulator-only SetSpellCooldown used by tests to drive cooldown displays.
+fn register_spell_info_globals(lua: &Lua, state: Rc<RefCell<SimState>>) -> Result<()> {
+    use crate::lua_api::game_data::SpellCooldownState;
+
+    let g = lua.globals();
+
+    g.set("GetSpellInfo", lua.create_function(create_legacy_spell_info)?)?;
+
+    let st = Rc::clone(&state);
+    g.set(
+        "GetSpellCooldown",
+        lua.create_function(move |_, spell: Value| {
+            let Some(spell_id) = resolve_spell_id(&spell) else {
+                return Ok((0.0_f64, 0.0_f64, 1, 1.0_f64));
+            };
+            let s = st.borrow();
+            let now = s.clock.now();
+            let (start, duration) =
+                super::action_bar_api::spell_cooldown_times(&s, spell_id, now);
+            Ok((start, duration, 1, 1.0_f64))
+        })?,
+    )?;
+
+    // SetSpellCooldown(spellID, start, duration) — simulator utility; a
+    // non-positive duration clears the cooldown.
+    g.set(
+        "SetSpellCooldown",
+        lua.create_function(move |lua, (spell_id, start, duration): (u32, f64, f64)| {
+            {
+                let mut s = state.borrow_mut();
+                if duration > 0.0 {
+                    s.spell_cooldowns.insert(spell_id, SpellCooldownState { start, duration });
+                } else {
+                    s.spell_cooldowns.remove(&spell_id);
+                }
+            }
+            let fire: mlua::Function = lua.globals().get("FireEvent")?;
+            fire.call::<()>(lua.create_string("SPELL_UPDATE_COOLDOWN")?)
+        })?,
+    )?;
+
+    Ok(())
+}
+
 /// CastSpellByID / CastSpellByName globals (used by SecureTemplates SECURE_ACTIONS["spell"]).
 fn register_cast_globals(lua: &Lua, state: Rc<RefCell<SimState>>) -> Result<()> {
     let g = lua.globals();
@@ -332,7 +378,7 @@ fn register_c_spell(lua: &Lua, state: Rc<RefCell<SimState>>) -> Result<mlua::Tab
     let st = Rc::clone(&state);
     t.set("GetSpellCooldown", lua.create_function(move |lua, spell_id: i32| {
         let s = st.borrow();
-        let now = s.start_time.elapsed().as_secs_f64();
+        let now = s.clock.now();
         let (start, duration) = super::action_bar_api::spell_cooldown_times(
             &s, spell_id as u32, now,
         );
@@ -418,22 +464,63 @@ pub fn spell_cast_time(spell_id: i32) -> i32 {
     }
 }
 
-fn create_spell_info(lua: &Lua, spell_id: i32) -> Result<Value> {
+/// Resolve a spell ID or spell name (looked up in the spellbook) to a known spell ID.
+fn resolve_spell_id(spell: &Value) -> Option<u32> {
+    let id = match spell {
+        Value::Integer(n) => *n as u32,
+        Value::Number(n) => *n as u32,
+        Value::String(s) => {
+            let s = s.to_str().ok()?;
+            match s.parse::<u32>() {
+                Ok(n) => n,
+                Err(_) => spellbook_data::find_spell_by_name(&s)?,
+            }
+        }
+        _ => return None,
+    };
+    crate::spells::get_spell(id).map(|_| id)
+}
+
+/// C_Spell.GetSpellInfo: SpellInfo table, or nil for unknown spells.
+fn create_spell_info(lua: &Lua, spell: Value) -> Result<Value> {
+    let Some(spell_id) = resolve_spell_id(&spell) else {
+        return Ok(Value::Nil);
+    };
+    let Some(data) = crate::spells::get_spell(spell_id) else {
+        return Ok(Value::Nil);
+    };
     let info = lua.create_table()?;
-    if let Some(spell) = crate::spells::get_spell(spell_id as u32) {
-        info.set("name", spell.name)?;
-        info.set("iconID", spell.icon_file_data_id as i64)?;
-    } else {
-        info.set("name", format!("Spell {}", spell_id))?;
-        info.set("iconID", 136243)?;
-    }
-    info.set("spellID", spell_id)?;
-    info.set("castTime", spell_cast_time(spell_id))?;
+    info.set("name", data.name)?;
+    info.set("iconID", data.icon_file_data_id as i64)?;
+    info.set("originalIconID", data.icon_file_data_id as i64)?;
+    info.set("spellID", spell_id as i64)?;
+    info.set("castTime", spell_cast_time(spell_id as i32))?;
     info.set("minRange", 0)?;
     info.set("maxRange", 0)?;
     Ok(Value::Table(info))
 }
 
+/// Legacy GetSpellInfo: name, rank, icon, castTime, minRange, maxRange,
+/// spellID, originalIcon — or nothing for unknown spells.
+fn create_legacy_spell_info(lua: &Lua, spell: Value) -> Result<mlua::MultiValue> {
+    let Some(spell_id) = resolve_spell_id(&spell) else {
+        return Ok(mlua::MultiValue::new());
+    };
+    let Some(data) = crate::spells::get_spell(spell_id) else {
+        return Ok(mlua::MultiValue::new());
+    };
+    Ok(mlua::MultiValue::from_vec(vec![
+        Value::String(lua.create_string(data.name)?),
+        Value::Nil,
+        Value::Integer(data.icon_file_data_id as i64),
+        Value::Integer(spell_cast_time(spell_id as i32) as i64),
+        Value::Integer(0),
+        Value::Integer(0),
+        Value::Integer(spell_id as i64),
+        Value::Integer(data.icon_file_data_id as i64),
+    ]))
+}
+
 fn create_spell_charges(lua: &Lua, _spell_id: i32) -> Result<Value> {
     let info = lua.create_table()?;
     info.set("currentCharges", 0)?;
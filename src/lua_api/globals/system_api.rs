@@ -24,6 +24,7 @@ pub fn register_system_api(lua: &Lua, state: Rc<RefCell<SimState>>) -> Result<()
     register_xpcall(lua)?;
     register_slash_cmd_list(lua)?;
     register_fire_event(lua, Rc::clone(&state))?;
+    register_event_log(lua, Rc::clone(&state))?;
     register_reload_ui(lua, Rc::clone(&state))?;
     register_build_type_checks(lua)?;
     register_battlenet_stubs(lua)?;
@@ -35,7 +36,7 @@ pub fn register_system_api(lua: &Lua, state: Rc<RefCell<SimState>>) -> Result<()
     register_input_state_stubs(lua, &state)?;
     register_screen_size_functions(lua, &state)?;
     register_request_time_played(lua, Rc::clone(&state))?;
-    register_cursor_position(lua)?;
+    register_cursor_position(lua, &state)?;
     register_localization_stubs(lua)?;
     register_ui_object_stubs(lua, state)?;
     Ok(())
@@ -157,9 +158,11 @@ fn register_fire_event(lua: &Lua, state: Rc<RefCell<SimState>>) -> Result<()> {
 
         let event_args: Vec<Value> = args_iter.collect();
 
+        let unit = crate::lua_api::script_helpers::event_unit_arg(&event_args);
         let listeners = {
-            let state = state.borrow();
-            state.widgets.get_event_listeners(&event_name)
+            let mut state = state.borrow_mut();
+            state.record_event(&event_name, &event_args);
+            state.widgets.get_unit_event_listeners(&event_name, unit.as_deref())
         };
 
         for widget_id in listeners {
@@ -181,6 +184,32 @@ fn register_fire_event(lua: &Lua, state: Rc<RefCell<SimState>>) -> Result<()> {
     Ok(())
 }
 
+/// Register `GetEventLog()` - simulator utility returning the recorded events
+/// as `{ time = t, event = "NAME", args = { ... }, n = #args }` entries
+/// (empty when recording is off). `n` keeps trailing nil arguments countable.
+fn register_event_log(lua: &Lua, state: Rc<RefCell<SimState>>) -> Result<()> {
+    let get_event_log = lua.create_function(move |lua, ()| {
+        let log = lua.create_table()?;
+        let state = state.borrow();
+        let Some(recorder) = &state.event_recorder else { return Ok(log) };
+        for (i, recorded) in recorder.events().iter().enumerate() {
+            let entry = lua.create_table()?;
+            entry.set("time", recorded.time)?;
+            entry.set("event", recorded.event.as_str())?;
+            let args = lua.create_table()?;
+            for (j, arg) in recorded.args.iter().enumerate() {
+                args.set(j + 1, arg.to_lua(lua)?)?;
+            }
+            entry.set("args", args)?;
+            entry.set("n", recorded.args.len())?;
+            log.set(i + 1, entry)?;
+        }
+        Ok(log)
+    })?;
+    lua.globals().set("GetEventLog", get_event_log)?;
+    Ok(())
+}
+
 /// Register `ReloadUI()` - reload the interface by firing startup events again.
 fn register_reload_ui(lua: &Lua, state: Rc<RefCell<SimState>>) -> Result<()> {
     let reload_ui = lua.create_function(move |lua, ()| {
@@ -215,7 +244,7 @@ fn register_reload_ui(lua: &Lua, state: Rc<RefCell<SimState>>) -> Result<()> {
             Ok(vec![Value::String(event_str)])
         })?;
 
-        state.borrow_mut().console_output.push("UI Reloaded".to_string());
+        state.borrow_mut().push_console("UI Reloaded".to_string());
         Ok(())
     })?;
     lua.globals().set("ReloadUI", reload_ui)?;
@@ -232,10 +261,12 @@ fn fire_event_to_listeners<F>(
 where
     F: Fn(&Lua) -> Result<Vec<Value>>,
 {
-    let listeners = {
-        let state = state.borrow();
-        state.widgets.get_event_listeners(event_name)
-    };
+    if state.borrow().event_recorder.is_some() {
+        let recorded = build_extra_args(lua)?;
+        // The built args start with the event name itself.
+        state.borrow_mut().record_event(event_name, recorded.get(1..).unwrap_or_default());
+    }
+    let listeners = state.borrow().widgets.get_event_listeners(event_name);
     for widget_id in listeners {
         if let Some(handler) = crate::lua_api::script_helpers::get_script(lua, widget_id, "OnEvent")
             && let Some(frame) = crate::lua_api::script_helpers::get_frame_ref(lua, widget_id) {
@@ -363,11 +394,11 @@ fn register_c_widget(lua: &Lua) -> Result<mlua::Table> {
     Ok(t)
 }
 
-/// Register `GetTime()` - returns seconds since UI load.
+/// Register `GetTime()` - returns seconds since UI load on the virtual `SimClock`.
 fn register_time_functions(lua: &Lua, state: &Rc<RefCell<SimState>>) -> Result<()> {
     let st = Rc::clone(state);
     let get_time = lua.create_function(move |_, ()| {
-        Ok(st.borrow().start_time.elapsed().as_secs_f64())
+        Ok(st.borrow().clock.now())
     })?;
     lua.globals().set("GetTime", get_time)?;
     Ok(())
@@ -476,15 +507,7 @@ fn register_screen_size_functions(lua: &Lua, state: &Rc<RefCell<SimState>>) -> R
     })?)?;
     let st = Rc::clone(state);
     globals.set("SetScreenSize", lua.create_function(move |_, (w, h): (f32, f32)| {
-        let mut s = st.borrow_mut();
-        s.screen_width = w;
-        s.screen_height = h;
-        s.strata_buckets = None;
-        s.widgets.clear_all_layout_rects();
-        for name in ["UIParent", "WorldFrame"] {
-            if let Some(id) = s.widgets.get_id_by_name(name)
-                && let Some(f) = s.widgets.get_mut_visual(id) { f.width = w; f.height = h; }
-        }
+        st.borrow_mut().set_screen_size(w, h);
         Ok(())
     })?)?;
     Ok(())
@@ -502,7 +525,8 @@ fn register_request_time_played(lua: &Lua, state: Rc<RefCell<SimState>>) -> Resu
         let level_played = 3 * 24 * 3600; // 3 days in seconds
 
         let listeners = {
-            let s = state.borrow();
+            let mut s = state.borrow_mut();
+            s.record_event("TIME_PLAYED_MSG", &[Value::Integer(total_played), Value::Integer(level_played)]);
             s.widgets.get_event_listeners("TIME_PLAYED_MSG")
         };
 
@@ -527,14 +551,38 @@ fn register_request_time_played(lua: &Lua, state: Rc<RefCell<SimState>>) -> Resu
     Ok(())
 }
 
-/// Register `GetCursorPosition()` - returns cursor screen coordinates.
+/// Register `GetCursorPosition()` and the simulator utility `SetCursorPosition(x, y)`.
 ///
-/// Returns simulated cursor at screen center. The MinimapButton addon uses this
-/// for drag positioning.
-fn register_cursor_position(lua: &Lua) -> Result<()> {
+/// Both use WoW's convention: unscaled screen coordinates with a bottom-left
+/// origin, so addons divide by `UIParent:GetEffectiveScale()` to get UI
+/// units. The virtual cursor is `SimState::mouse_position` (top-left origin),
+/// moved by the GUI and `send_mouse_move`; before any mouse input it sits at
+/// the screen center. `SetCursorPosition` drags a frame started with
+/// StartMoving and resizes one started with StartSizing (firing
+/// OnSizeChanged), but does not fire OnEnter/OnLeave.
+fn register_cursor_position(lua: &Lua, state: &Rc<RefCell<SimState>>) -> Result<()> {
+    let st = Rc::clone(state);
     lua.globals().set(
         "GetCursorPosition",
-        lua.create_function(|_, ()| Ok((512.0_f64, 384.0_f64)))?,
+        lua.create_function(move |_, ()| {
+            let s = st.borrow();
+            let (x, y) = s.mouse_position
+                .unwrap_or((s.screen_width / 2.0, s.screen_height / 2.0));
+            Ok((x as f64, (s.screen_height - y) as f64))
+        })?,
+    )?;
+    let st = Rc::clone(state);
+    lua.globals().set(
+        "SetCursorPosition",
+        lua.create_function(move |lua, (x, y): (f32, f32)| {
+            let resized = {
+                let mut s = st.borrow_mut();
+                let y = s.screen_height - y;
+                s.set_mouse_position(x, y)
+            };
+            crate::lua_api::script_helpers::fire_size_changed(lua, resized);
+            Ok(())
+        })?,
     )?;
     Ok(())
 }
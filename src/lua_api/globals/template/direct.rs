@@ -5,7 +5,7 @@
 //! call. Used during template application and XML frame loading.
 
 use crate::lua_api::SimState;
-use crate::widget::{AnchorPoint, FrameStrata};
+use crate::widget::{AnchorPoint, Backdrop, Color, FrameStrata};
 use crate::xml::{AnchorXml, FrameXml};
 use std::cell::RefCell;
 use std::rc::Rc;
@@ -46,6 +46,7 @@ pub fn set_size_partial(
         (None, Some(h)) => {
             if let Some(frame) = s.widgets.get_mut_visual(frame_id) {
                 frame.height = h;
+                frame.has_fixed_height = h > 0.0;
             }
         }
         (None, None) => return,
@@ -85,9 +86,15 @@ fn set_single_anchor(
 
     let (offset_x, offset_y) = anchor_offset(anchor);
 
-    // Resolve relative_to target
-    let relative_to_id =
-        resolve_relative_to(state, frame_id, anchor.relative_to.as_deref(), frame_name);
+    // Resolve relative_to target. A relativeKey/relativeTo whose target doesn't
+    // exist yet is stored by name and bound later by `bind_deferred_anchors`.
+    let (relative_to_id, deferred) = match anchor.relative_key.as_deref() {
+        Some(key) => match state.widgets.resolve_relative_key(frame_id, key) {
+            Some(id) => (Some(id), None),
+            None => (None, Some(key.to_string())),
+        },
+        None => resolve_relative_to(state, frame_id, anchor.relative_to.as_deref(), frame_name),
+    };
 
     // Cycle detection
     if let Some(rel_id) = relative_to_id {
@@ -113,34 +120,48 @@ fn set_single_anchor(
     }
 
     // Set the anchor
+    let is_deferred = deferred.is_some();
     if let Some(frame) = state.widgets.get_mut_visual(frame_id) {
-        frame.set_point(
-            point,
-            relative_to_id.map(|id| id as usize),
-            relative_point,
-            offset_x,
-            offset_y,
-        );
+        if is_deferred {
+            frame.set_point_with_name(point, deferred, relative_point, offset_x, offset_y);
+        } else {
+            frame.set_point(
+                point,
+                relative_to_id.map(|id| id as usize),
+                relative_point,
+                offset_x,
+                offset_y,
+            );
+        }
+    }
+    if is_deferred {
+        state.widgets.defer_anchor_resolution(frame_id);
     }
 
     state.widgets.mark_rect_dirty(frame_id);
     state.invalidate_layout_with_dependents(frame_id);
 }
 
-/// Resolve the relative_to target for an anchor, returning the target frame ID.
+/// Resolve the relative_to target for an anchor.
+///
+/// Returns the target frame ID, or the name to defer on when the named
+/// frame hasn't been created yet.
 fn resolve_relative_to(
     state: &SimState,
     frame_id: u64,
     relative_to: Option<&str>,
     frame_name: &str,
-) -> Option<u64> {
+) -> (Option<u64>, Option<String>) {
     match relative_to {
-        Some(rel) if rel == "$parent" => state.widgets.get(frame_id).and_then(|f| f.parent_id),
+        Some(rel) if rel == "$parent" => (state.widgets.get(frame_id).and_then(|f| f.parent_id), None),
         Some(rel) => {
             let resolved = rel.replace("$parent", frame_name);
-            state.widgets.get_id_by_name(&resolved)
+            match state.widgets.get_id_by_name(&resolved) {
+                Some(id) => (Some(id), None),
+                None => (None, Some(resolved)),
+            }
         }
-        None => state.widgets.get(frame_id).and_then(|f| f.parent_id),
+        None => (state.widgets.get(frame_id).and_then(|f| f.parent_id), None),
     }
 }
 
@@ -249,6 +270,7 @@ pub fn enable_mouse(state: &Rc<RefCell<SimState>>, frame_id: u64, enable: bool)
     let mut s = state.borrow_mut();
     if let Some(frame) = s.widgets.get_mut(frame_id) {
         frame.mouse_enabled = enable;
+        frame.mouse_motion_enabled = enable;
     }
 }
 
@@ -493,6 +515,48 @@ pub fn apply_xml_hit_rect_insets(
     }
 }
 
+/// Apply a legacy `<Backdrop>` element; the instance's element wins over the
+/// template chain's, and the last template in the chain wins among templates.
+pub fn apply_xml_backdrop(
+    state: &Rc<RefCell<SimState>>,
+    frame_id: u64,
+    frame: &FrameXml,
+    inherits: &str,
+) {
+    let mut backdrop = frame.backdrop().cloned();
+    if backdrop.is_none() && !inherits.is_empty() {
+        for entry in &crate::xml::get_template_chain(inherits) {
+            if let Some(b) = entry.frame.backdrop() {
+                backdrop = Some(b.clone());
+            }
+        }
+    }
+    let Some(xml) = backdrop else { return };
+    let xml_color = |c: Option<&crate::xml::ColorXml>| {
+        c.map(|c| Color::new(
+            c.r.unwrap_or(1.0),
+            c.g.unwrap_or(1.0),
+            c.b.unwrap_or(1.0),
+            c.a.unwrap_or(1.0),
+        ))
+        .unwrap_or(Color::new(1.0, 1.0, 1.0, 1.0))
+    };
+    let mut s = state.borrow_mut();
+    if let Some(f) = s.widgets.get_mut_visual(frame_id) {
+        f.backdrop = Backdrop {
+            enabled: true,
+            bg_file: xml.bg_file.clone(),
+            edge_file: xml.edge_file.clone(),
+            bg_color: xml_color(xml.color.as_ref()),
+            border_color: xml_color(xml.border_color.as_ref()),
+            edge_size: xml.edge_size.as_ref().and_then(|v| v.value()).unwrap_or(0.0),
+            tile: xml.tile.unwrap_or(false),
+            tile_size: xml.tile_size.as_ref().and_then(|v| v.value()).unwrap_or(0.0),
+            insets: xml.background_insets.as_ref().map(|i| i.values()).unwrap_or_default(),
+        };
+    }
+}
+
 /// Resolve and apply clampedToScreen from template chain + instance XML.
 pub fn apply_xml_clamped_to_screen(
     state: &Rc<RefCell<SimState>>,
@@ -513,6 +577,39 @@ pub fn apply_xml_clamped_to_screen(
     }
 }
 
+/// Resolve and apply `resizable` and `<ResizeBounds>` from template chain +
+/// instance XML; the instance wins, then the last template that sets them.
+pub fn apply_xml_resizing(
+    state: &Rc<RefCell<SimState>>,
+    frame_id: u64,
+    frame: &FrameXml,
+    inherits: &str,
+) {
+    let mut resizable = None;
+    let mut bounds = None;
+    if !inherits.is_empty() {
+        for entry in &crate::xml::get_template_chain(inherits) {
+            resizable = entry.frame.resizable.or(resizable);
+            bounds = entry.frame.resize_bounds().cloned().or(bounds);
+        }
+    }
+    let resizable = frame.resizable.or(resizable);
+    let bounds = frame.resize_bounds().cloned().or(bounds);
+    let mut s = state.borrow_mut();
+    let Some(f) = s.widgets.get_mut(frame_id) else { return };
+    if let Some(r) = resizable {
+        f.resizable = r;
+    }
+    if let Some(b) = bounds {
+        f.resize_bounds = (
+            b.min_width.unwrap_or(0.0),
+            b.min_height.unwrap_or(0.0),
+            b.max_width.unwrap_or(0.0),
+            b.max_height.unwrap_or(0.0),
+        );
+    }
+}
+
 /// Resolve and apply setAllPoints from template chain + instance XML.
 pub fn apply_xml_set_all_points(
     state: &Rc<RefCell<SimState>>,
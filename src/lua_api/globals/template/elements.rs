@@ -210,6 +210,9 @@ fn append_texture_properties(code: &mut String, texture: &crate::xml::TextureXml
     if texture.vert_tile == Some(true) {
         code.push_str(&format!("            {}:SetVertTile(true)\n", var));
     }
+    if let Some(mask) = &texture.mask {
+        code.push_str(&format!("            {}:SetMask(\"{}\")\n", var, escape_lua_string(mask)));
+    }
     if let Some(a) = texture.alpha {
         code.push_str(&format!("            {}:SetAlpha({})\n", var, a));
     }
@@ -252,14 +255,15 @@ fn append_anchors_and_parent_refs(
 /// Create a fontstring from template XML.
 ///
 /// `subst_parent` is the name used for `$parent` substitution (propagated
-/// through anonymous frames).
+/// through anonymous frames). Returns the global name the fontstring was
+/// created under.
 pub(super) fn create_fontstring_from_template(
     lua: &Lua,
     fontstring: &crate::xml::FontStringXml,
     parent_name: &str,
     subst_parent: &str,
     draw_layer: &str,
-) {
+) -> String {
     let child_name = fontstring
         .name
         .as_ref()
@@ -309,6 +313,7 @@ pub(super) fn create_fontstring_from_template(
 
     code.push_str("        end\n");
     let _ = lua.load(&code).exec();
+    child_name
 }
 
 /// Append size and text setters for a fontstring.
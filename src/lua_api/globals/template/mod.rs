@@ -8,11 +8,13 @@ mod elements;
 
 use crate::loader::helpers::generate_set_point_code;
 use crate::loader::helpers_anim::generate_animation_group_code;
+use crate::loader::profile::with_profile;
 use crate::lua_api::SimState;
 use crate::xml::{get_template_chain, FrameElement, FrameXml, TemplateEntry};
 use mlua::Lua;
 use std::cell::RefCell;
 use std::rc::Rc;
+use std::time::Instant;
 
 /// Extract the FrameXml, widget type, and optional intrinsic name from a FrameElement.
 fn frame_element_type(element: &FrameElement) -> Option<(&FrameXml, &'static str, Option<&'static str>)> {
@@ -80,9 +82,14 @@ pub fn apply_templates_from_registry(
         return;
     }
 
+    let profiling = state.borrow().load_profile.is_some();
     let mut all_child_names = Vec::new();
     for entry in &chain {
+        let start = profiling.then(Instant::now);
         let child_names = apply_single_template(lua, state, frame_name, entry);
+        if let Some(start) = start {
+            with_profile(state, |p| p.record_template(&entry.name, start.elapsed()));
+        }
         all_child_names.extend(child_names);
     }
 
@@ -164,6 +171,7 @@ fn apply_single_template(
         direct::set_anchors(state, fid, template, frame_name);
         direct::set_all_points(state, fid, template);
         direct::set_hidden(state, fid, template);
+        direct::apply_xml_backdrop(state, fid, template, "");
     }
 
     // Apply layers (textures and fontstrings)
@@ -332,14 +340,14 @@ pub(crate) fn fire_on_load(lua: &Lua, frame_name: &str) {
         local frame = {frame_ref}
         if frame then
             if type(frame.OnLoad_Intrinsic) == "function" then
-                local ok, err = pcall(frame.OnLoad_Intrinsic, frame)
+                local ok, err = xpcall(frame.OnLoad_Intrinsic, __script_traceback, frame)
                 if not ok then
                     return tostring(err)
                 end
             end
             local handler = frame:GetScript("OnLoad")
             if handler then
-                local ok, err = pcall(handler, frame)
+                local ok, err = xpcall(handler, __script_traceback, frame)
                 if not ok then
                     return tostring(err)
                 end
@@ -348,7 +356,10 @@ pub(crate) fn fire_on_load(lua: &Lua, frame_name: &str) {
         "#
     );
     match lua.load(&code).eval::<Option<String>>() {
-        Ok(Some(err)) => eprintln!("[fire_on_load] {} error: {}", frame_name, err),
+        Ok(Some(err)) => crate::lua_api::script_helpers::call_error_handler(
+            lua,
+            &format!("[OnLoad] {frame_name}: {err}"),
+        ),
         Err(e) => eprintln!("[fire_on_load] {} eval error: {}", frame_name, e),
         _ => {}
     }
@@ -641,7 +652,7 @@ fn apply_inline_button_textures(lua: &Lua, frame: &crate::xml::FrameXml, frame_n
 /// Create ButtonText fontstring from template.
 fn apply_button_text(lua: &Lua, frame: &crate::xml::FrameXml, frame_name: &str, subst_parent: &str) {
     let Some(fs) = frame.button_text() else { return };
-    elements::create_fontstring_from_template(lua, fs, frame_name, subst_parent, "OVERLAY");
+    let fs_name = elements::create_fontstring_from_template(lua, fs, frame_name, subst_parent, "OVERLAY");
     // Only apply SetAllPoints when the ButtonText has no explicit anchors.
     // Templates like ChatTabTemplate define explicit anchors (e.g. CENTER 0 -5)
     // that would be wiped by SetAllPoints.
@@ -649,7 +660,7 @@ fn apply_button_text(lua: &Lua, frame: &crate::xml::FrameXml, frame_name: &str,
     let text_ref = if let Some(ref pk) = fs.parent_key {
         format!("p[\"{}\"]", escape_lua_string(pk))
     } else {
-        "select(p:GetNumRegions(), p:GetRegions())".to_string()
+        lua_global_ref(&fs_name)
     };
     let set_all_points = if has_anchors { "" } else { "if t then t:SetAllPoints(p) end " };
     let code = format!(
@@ -6,7 +6,7 @@ use super::super::{next_timer_id, PendingTimer, SimState};
 use mlua::{Lua, Result};
 use std::cell::RefCell;
 use std::rc::Rc;
-use std::time::{Duration, Instant};
+use std::time::Duration;
 
 /// Register C_Timer namespace and timer-related functions.
 pub fn register_timer_api(lua: &Lua, state: Rc<RefCell<SimState>>) -> Result<()> {
@@ -25,7 +25,7 @@ fn create_timer_after(lua: &Lua, state: Rc<RefCell<SimState>>) -> Result<mlua::F
     lua.create_function(move |lua, (seconds, callback): (f64, mlua::Function)| {
         let id = next_timer_id();
         let callback_key = lua.create_registry_value(callback)?;
-        let fire_at = Instant::now() + Duration::from_secs_f64(seconds);
+        let fire_at = state.borrow().clock.now() + seconds;
         let owner_addon = state.borrow().loading_addon_index;
 
         let timer = PendingTimer {
@@ -45,7 +45,7 @@ fn create_new_ticker(lua: &Lua, state: Rc<RefCell<SimState>>) -> Result<mlua::Fu
         move |lua, (seconds, callback, iterations): (f64, mlua::Function, Option<i32>)| {
             let id = next_timer_id();
             let callback_key = lua.create_registry_value(callback)?;
-            let fire_at = Instant::now() + Duration::from_secs_f64(seconds);
+            let fire_at = state.borrow().clock.now() + seconds;
             let interval = Duration::from_secs_f64(seconds);
             let owner_addon = state.borrow().loading_addon_index;
 
@@ -69,7 +69,7 @@ fn create_new_timer(lua: &Lua, state: Rc<RefCell<SimState>>) -> Result<mlua::Fun
     lua.create_function(move |lua, (seconds, callback): (f64, mlua::Function)| {
         let id = next_timer_id();
         let callback_key = lua.create_registry_value(callback)?;
-        let fire_at = Instant::now() + Duration::from_secs_f64(seconds);
+        let fire_at = state.borrow().clock.now() + seconds;
         let owner_addon = state.borrow().loading_addon_index;
 
         let timer_handle = create_timer_handle(lua, id, &state)?;
@@ -4,6 +4,7 @@
 //! health, power, auras, and other unit state.
 
 use crate::lua_api::SimState;
+pub use crate::lua_api::state::parse_party_index;
 use mlua::{Lua, MultiValue, Result, Value};
 use std::cell::RefCell;
 use std::rc::Rc;
@@ -34,30 +35,18 @@ fn class_info_by_index(index: i32) -> (&'static str, &'static str) {
         .unwrap_or(("Unknown", "UNKNOWN"))
 }
 
-/// Resolve a unit name, checking target, party members, and player name.
+/// Look up a 1-based class index by file token ("MAGE").
+pub(super) fn class_index_by_file(file: &str) -> Option<i32> {
+    CLASS_DATA.iter().find(|(_, _, f)| *f == file).map(|(i, _, _)| *i)
+}
+
+/// Resolve a unit name from the unit model.
 /// Returns owned String to avoid borrow lifetime issues in closures.
 fn resolve_unit_name_with_party(unit: &str, state: &SimState) -> String {
-    if unit == "player" {
-        return state.player_name.clone();
-    }
-    if unit == "target" {
-        return state.current_target.as_ref()
-            .map(|t| t.name.clone())
-            .unwrap_or_else(|| "Unknown".to_string());
+    if let Some(name) = state.unit_name(unit) {
+        return name.to_string();
     }
-    if let Some(idx) = parse_party_index(unit)
-        && let Some(m) = state.party_members.get(idx) {
-            return m.name.to_string();
-        }
-    "SimUnit".to_string()
-}
-
-/// Parse a "partyN" unit ID and return the 0-based index if valid.
-pub fn parse_party_index(unit: &str) -> Option<usize> {
-    unit.strip_prefix("party")
-        .and_then(|n| n.parse::<usize>().ok())
-        .filter(|&n| n >= 1)
-        .map(|n| n - 1)
+    if unit == "target" { "Unknown".to_string() } else { "SimUnit".to_string() }
 }
 
 /// Register unit-related global functions.
@@ -77,6 +66,7 @@ pub fn register_unit_api(lua: &Lua, state: Rc<RefCell<SimState>>) -> Result<()>
     register_xp_functions(lua)?;
     register_pvp_vehicle_functions(lua, state.clone())?;
     register_misc_unit_functions(lua, state.clone())?;
+    super::unit_state_api::register_unit_state_api(lua, state.clone())?;
     super::targeting_api::register_targeting_functions(lua, state)?;
     super::unit_combat_api::register_unit_combat_stat_functions(lua)?;
     Ok(())
@@ -141,10 +131,17 @@ fn register_unit_guid(lua: &Lua, state: Rc<RefCell<SimState>>) -> Result<()> {
             if unit == "player" {
                 return Ok(Value::String(lua.create_string("Player-0000-00000001")?));
             }
-            if unit == "target" {
+            let modelled = {
                 let s = state.borrow();
-                let guid = s.current_target.as_ref().map(|t| t.guid.clone())
-                    .unwrap_or_else(|| "Creature-0000-00000000".into());
+                match unit.as_str() {
+                    "target" => s.current_target.as_ref(),
+                    "focus" => s.current_focus.as_ref(),
+                    "pet" => s.pet.as_ref(),
+                    _ => None,
+                }
+                .map(|t| t.guid.clone())
+            };
+            if let Some(guid) = modelled {
                 return Ok(Value::String(lua.create_string(&guid)?));
             }
             if let Some(idx) = parse_party_index(&unit)
@@ -157,7 +154,7 @@ fn register_unit_guid(lua: &Lua, state: Rc<RefCell<SimState>>) -> Result<()> {
     )
 }
 
-/// Register UnitLevel and UnitExists with party/target awareness.
+/// Register UnitLevel and UnitExists against the unit model.
 fn register_unit_level_exists(lua: &Lua, state: Rc<RefCell<SimState>>) -> Result<()> {
     let globals = lua.globals();
 
@@ -166,17 +163,10 @@ fn register_unit_level_exists(lua: &Lua, state: Rc<RefCell<SimState>>) -> Result
         "UnitLevel",
         lua.create_function(move |_, unit: Option<String>| {
             let Some(unit) = unit else { return Ok(0) };
-            if unit == "target" {
-                let s = st.borrow();
-                return Ok(s.current_target.as_ref().map(|t| t.level).unwrap_or(1));
-            }
-            if let Some(idx) = parse_party_index(&unit) {
-                let s = st.borrow();
-                if let Some(m) = s.party_members.get(idx) {
-                    return Ok(m.level);
-                }
+            if let Some(stats) = st.borrow().unit_stats(&unit) {
+                return Ok(stats.level);
             }
-            Ok(80)
+            Ok(if unit == "target" { 1 } else { 80 })
         })?,
     )?;
 
@@ -184,16 +174,7 @@ fn register_unit_level_exists(lua: &Lua, state: Rc<RefCell<SimState>>) -> Result
         "UnitExists",
         lua.create_function(move |_, unit: Option<String>| {
             let Some(unit) = unit else { return Ok(false) };
-            if matches!(unit.as_str(), "player" | "pet") {
-                return Ok(true);
-            }
-            if unit == "target" {
-                return Ok(state.borrow().current_target.is_some());
-            }
-            if let Some(idx) = parse_party_index(&unit) {
-                return Ok(idx < state.borrow().party_members.len());
-            }
-            Ok(false)
+            Ok(state.borrow().unit_stats(&unit).is_some())
         })?,
     )
 }
@@ -212,28 +193,14 @@ fn register_unit_class(lua: &Lua, state: Rc<RefCell<SimState>>) -> Result<()> {
         "UnitClass",
         lua.create_function(move |lua, unit: Option<String>| {
             let unit = unit.unwrap_or_default();
-            let (name, file, idx) = if unit == "target" {
-                let s = state.borrow();
-                if let Some(t) = &s.current_target {
-                    let (n, f) = class_info_by_index(t.class_index);
-                    (n, f, t.class_index)
-                } else {
-                    ("Warrior", "WARRIOR", 1)
-                }
-            } else if let Some(i) = parse_party_index(&unit) {
-                let s = state.borrow();
-                if let Some(m) = s.party_members.get(i) {
-                    let (n, f) = class_info_by_index(m.class_index);
-                    (n, f, m.class_index)
-                } else {
-                    ("Warrior", "WARRIOR", 1)
-                }
-            } else {
-                // Player
-                let s = state.borrow();
-                let (n, f) = class_info_by_index(s.player_class_index);
-                (n, f, s.player_class_index)
+            let s = state.borrow();
+            let idx = match s.unit_stats(&unit) {
+                Some(stats) => stats.class_index,
+                None if unit == "target" || parse_party_index(&unit).is_some() => 1,
+                // Unmodelled unit tokens report the player's class.
+                None => s.player_class_index,
             };
+            let (name, file) = class_info_by_index(idx);
             Ok(MultiValue::from_vec(vec![
                 Value::String(lua.create_string(name)?),
                 Value::String(lua.create_string(file)?),
@@ -250,9 +217,12 @@ fn register_class_lookup_functions(lua: &Lua, state: Rc<RefCell<SimState>>) -> R
 
     globals.set(
         "UnitClassBase",
-        lua.create_function(move |lua, _unit: Option<String>| {
+        lua.create_function(move |lua, unit: Option<String>| {
             let s = state.borrow();
-            let (_, file) = class_info_by_index(s.player_class_index);
+            let idx = unit
+                .and_then(|u| s.unit_stats(&u))
+                .map_or(s.player_class_index, |stats| stats.class_index);
+            let (_, file) = class_info_by_index(idx);
             Ok(Value::String(lua.create_string(file)?))
         })?,
     )?;
@@ -312,10 +282,12 @@ fn register_name_functions(lua: &Lua, state: Rc<RefCell<SimState>>) -> Result<()
         "UnitFullName",
         lua.create_function(move |lua, unit: Option<String>| {
             let unit = unit.unwrap_or_default();
-            let name = resolve_unit_name_with_party(&unit, &st.borrow());
+            let state = st.borrow();
+            let name = resolve_unit_name_with_party(&unit, &state);
+            let realm = super::locale_api::normalize_realm_name(&state.realm_name);
             Ok(MultiValue::from_vec(vec![
                 Value::String(lua.create_string(name)?),
-                Value::String(lua.create_string("SimRealm")?),
+                Value::String(lua.create_string(realm)?),
             ]))
         })?,
     )?;
@@ -622,11 +594,7 @@ fn register_xp_functions(lua: &Lua) -> Result<()> {
     let globals = lua.globals();
     // Random XP so the bar displays something visible at startup.
     let xp_max = 89_750i32;
-    let nanos = std::time::SystemTime::now()
-        .duration_since(std::time::UNIX_EPOCH)
-        .unwrap_or_default()
-        .subsec_nanos();
-    let xp_current = (nanos % xp_max as u32) as i32;
+    let xp_current = (crate::lua_api::game_data::default_entropy(3) % xp_max as usize) as i32;
     globals.set("UnitXP", lua.create_function(move |_, _unit: Option<String>| Ok(xp_current))?)?;
     globals.set("UnitXPMax", lua.create_function(move |_, _unit: Option<String>| Ok(xp_max))?)?;
     globals.set("UnitTrialXP", lua.create_function(|_, _unit: Option<String>| Ok(0i32))?)?;
@@ -1,7 +1,7 @@
 //! Unit health, power, and heal/absorb API functions.
 
-use super::unit_api::parse_party_index;
 use crate::lua_api::SimState;
+use crate::lua_api::state::UnitStats;
 use mlua::{Lua, Result, Value};
 use std::cell::RefCell;
 use std::rc::Rc;
@@ -15,63 +15,61 @@ pub fn register_health_power_functions(lua: &Lua, state: Rc<RefCell<SimState>>)
     register_heal_absorb_stubs(lua)
 }
 
-/// Register UnitHealth, UnitHealthMax with party and target awareness.
+/// Register UnitHealth, UnitHealthMax against the unit model.
 fn register_health_functions(lua: &Lua, state: Rc<RefCell<SimState>>) -> Result<()> {
     let globals = lua.globals();
     let st = state.clone();
     globals.set(
         "UnitHealth",
         lua.create_function(move |_, unit: Value| {
-            if let Value::String(s) = &unit {
-                let u = s.to_string_lossy();
-                if u == "player" {
-                    return Ok(st.borrow().player_health);
-                }
-                if u == "target" {
-                    let st = st.borrow();
-                    return Ok(st.current_target.as_ref().map(|t| t.health).unwrap_or(0));
-                }
-                if let Some(idx) = parse_party_index(&u) {
-                    let st = st.borrow();
-                    if let Some(m) = st.party_members.get(idx) {
-                        return Ok(m.health);
-                    }
-                }
-            }
-            Ok(100_000i32)
+            Ok(unit_stat(&st.borrow(), &unit, |s| s.health, 100_000))
         })?,
     )?;
     globals.set(
         "UnitHealthMax",
         lua.create_function(move |_, unit: Value| {
-            if let Value::String(s) = &unit {
-                let u = s.to_string_lossy();
-                if u == "player" {
-                    return Ok(state.borrow().player_health_max);
-                }
-                if u == "target" {
-                    let st = state.borrow();
-                    return Ok(st.current_target.as_ref().map(|t| t.health_max).unwrap_or(0));
-                }
-                if let Some(idx) = parse_party_index(&u) {
-                    let st = state.borrow();
-                    if let Some(m) = st.party_members.get(idx) {
-                        return Ok(m.health_max);
-                    }
-                }
-            }
-            Ok(100_000i32)
+            Ok(unit_stat(&state.borrow(), &unit, |s| s.health_max, 100_000))
         })?,
     )?;
     Ok(())
 }
 
-/// Register UnitPower, UnitPowerMax with party and target awareness.
+/// Read one stat of a modelled unit. A missing target/focus/pet reads 0;
+/// unmodelled unit tokens (nameplates, mouseover, ...) read `default`.
+fn unit_stat(state: &SimState, unit: &Value, stat: impl Fn(&UnitStats) -> i32, default: i32) -> i32 {
+    match modelled_unit_stats(state, unit) {
+        Some(stats) => stat(&stats),
+        None if is_absent_unit(unit) => 0,
+        None => default,
+    }
+}
+
+fn modelled_unit_stats(state: &SimState, unit: &Value) -> Option<UnitStats> {
+    let Value::String(s) = unit else { return None };
+    state.unit_stats(&s.to_string_lossy())
+}
+
+/// A target/focus/pet token that is not currently set (checked after
+/// `modelled_unit_stats` returned None).
+fn is_absent_unit(unit: &Value) -> bool {
+    matches!(unit, Value::String(s) if matches!(s.to_string_lossy().as_str(), "target" | "focus" | "pet"))
+}
+
+/// Parse the optional Enum.PowerType argument of UnitPower/UnitPowerMax.
+fn power_type_arg(args: &[Value]) -> Option<i64> {
+    args.get(1).and_then(|v| match v {
+        Value::Integer(i) => Some(*i),
+        Value::Number(n) => Some(*n as i64),
+        _ => None,
+    })
+}
+
+/// Register UnitPower, UnitPowerMax against the unit model.
 ///
 /// The optional second argument is the power type (Enum.PowerType).
-/// When absent or matching the unit's primary power type (Mana=0), returns
-/// the primary power values.  For secondary resource types (HolyPower,
-/// ComboPoints, etc.) returns 0 / small-cap values.
+/// When absent or matching the unit's primary power type, returns the
+/// primary power values.  Other types return 0 current power; secondary
+/// resources (HolyPower, ComboPoints, etc.) report small caps.
 fn register_power_functions(lua: &Lua, state: Rc<RefCell<SimState>>) -> Result<()> {
     let globals = lua.globals();
     let st = state.clone();
@@ -79,26 +77,17 @@ fn register_power_functions(lua: &Lua, state: Rc<RefCell<SimState>>) -> Result<(
         "UnitPower",
         lua.create_function(move |_, args: mlua::MultiValue| {
             let args = args.into_vec();
-            let power_type = args.get(1).and_then(|v| match v {
-                Value::Integer(i) => Some(*i),
-                Value::Number(n) => Some(*n as i64),
-                _ => None,
-            });
-            if let Some(Value::String(s)) = args.first() {
-                let u = s.to_string_lossy();
-                if u == "target" {
-                    let st = st.borrow();
-                    return Ok(st.current_target.as_ref().map(|t| t.power).unwrap_or(0));
-                }
-                if let Some(idx) = parse_party_index(&u) {
-                    let st = st.borrow();
-                    if let Some(m) = st.party_members.get(idx) {
-                        return Ok(m.power);
-                    }
-                }
+            let power_type = power_type_arg(&args);
+            let unit = args.first().cloned().unwrap_or(Value::Nil);
+            let st = st.borrow();
+            if let Some(stats) = modelled_unit_stats(&st, &unit) {
+                return Ok(match power_type {
+                    Some(pt) if pt != stats.power_type as i64 => 0,
+                    _ => stats.power,
+                });
             }
-            // Secondary resource types return 0 current power.
-            if is_secondary_power_type(power_type) {
+            // Absent units and secondary resource types return 0 current power.
+            if is_absent_unit(&unit) || is_secondary_power_type(power_type) {
                 return Ok(0i32);
             }
             Ok(50_000i32)
@@ -108,23 +97,19 @@ fn register_power_functions(lua: &Lua, state: Rc<RefCell<SimState>>) -> Result<(
         "UnitPowerMax",
         lua.create_function(move |_, args: mlua::MultiValue| {
             let args = args.into_vec();
-            let power_type = args.get(1).and_then(|v| match v {
-                Value::Integer(i) => Some(*i),
-                Value::Number(n) => Some(*n as i64),
-                _ => None,
-            });
-            if let Some(Value::String(s)) = args.first() {
-                let u = s.to_string_lossy();
-                if u == "target" {
-                    let st = state.borrow();
-                    return Ok(st.current_target.as_ref().map(|t| t.power_max).unwrap_or(0));
-                }
-                if let Some(idx) = parse_party_index(&u) {
-                    let st = state.borrow();
-                    if let Some(m) = st.party_members.get(idx) {
-                        return Ok(m.power_max);
+            let power_type = power_type_arg(&args);
+            let unit = args.first().cloned().unwrap_or(Value::Nil);
+            let st = state.borrow();
+            if let Some(stats) = modelled_unit_stats(&st, &unit) {
+                return Ok(match power_type {
+                    Some(pt) if pt != stats.power_type as i64 => {
+                        if is_secondary_power_type(Some(pt)) { secondary_power_max(pt) } else { 0 }
                     }
-                }
+                    _ => stats.power_max,
+                });
+            }
+            if is_absent_unit(&unit) {
+                return Ok(0i32);
             }
             // Secondary resource types have small caps (holy power, combo points, etc.)
             if is_secondary_power_type(power_type) {
@@ -153,27 +138,14 @@ fn secondary_power_max(power_type: i64) -> i32 {
     }
 }
 
-/// Register UnitPowerType with party and target awareness.
+/// Register UnitPowerType against the unit model.
 fn register_power_type_function(lua: &Lua, state: Rc<RefCell<SimState>>) -> Result<()> {
     lua.globals().set(
         "UnitPowerType",
         lua.create_function(move |lua, unit: Value| {
-            if let Value::String(s) = &unit {
-                let u = s.to_string_lossy();
-                if u == "target" {
-                    let st = state.borrow();
-                    if let Some(t) = &st.current_target {
-                        return Ok((t.power_type, Value::String(lua.create_string(t.power_type_name)?)));
-                    }
-                }
-                if let Some(idx) = parse_party_index(&u) {
-                    let st = state.borrow();
-                    if let Some(m) = st.party_members.get(idx) {
-                        return Ok((m.power_type, Value::String(lua.create_string(m.power_type_name)?)));
-                    }
-                }
-            }
-            Ok((0i32, Value::String(lua.create_string("MANA")?)))
+            let (power_type, token) = modelled_unit_stats(&state.borrow(), &unit)
+                .map_or((0, "MANA"), |s| (s.power_type, s.power_type_name));
+            Ok((power_type, Value::String(lua.create_string(token)?)))
         })?,
     )
 }
@@ -0,0 +1,192 @@
+//! SetUnitState: simulator utility that edits the unit model.
+//!
+//! `SetUnitState(unit, {health=, healthMax=, power=, powerMax=, name=,
+//! class=, level=, exists=})` updates the player, target, focus, pet or a
+//! party member and fires the events the client would (UNIT_HEALTH,
+//! UNIT_POWER_UPDATE, ...) so unit frames refresh. Meant for --exec-lua and
+//! tests.
+
+use super::unit_api::class_index_by_file;
+use crate::lua_api::SimState;
+use crate::lua_api::game_data::{default_pet, new_party_member};
+use crate::lua_api::state::{build_target_info, parse_party_index, UnitStats};
+use mlua::{Lua, Result, Table, Value};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// Register the SetUnitState simulator utility.
+pub fn register_unit_state_api(lua: &Lua, state: Rc<RefCell<SimState>>) -> Result<()> {
+    lua.globals().set(
+        "SetUnitState",
+        lua.create_function(move |lua, (unit, fields): (String, Table)| {
+            let events = set_unit_state(&mut state.borrow_mut(), &unit, &fields)?;
+            fire_events(lua, &events)
+        })?,
+    )
+}
+
+/// An event to fire once the state borrow is released: (name, args).
+type PendingEvent = (&'static str, Vec<String>);
+
+/// Apply `fields` to `unit` and return the events describing the change.
+fn set_unit_state(state: &mut SimState, unit: &str, fields: &Table) -> Result<Vec<PendingEvent>> {
+    let mut events = Vec::new();
+    match fields.get::<Option<bool>>("exists")? {
+        Some(false) => {
+            if remove_unit(state, unit)? {
+                events.push(presence_event(unit));
+            }
+            return Ok(events);
+        }
+        Some(true) => {
+            if add_unit(state, unit)? {
+                events.push(presence_event(unit));
+            }
+        }
+        None => {}
+    }
+
+    let before = state.unit_stats(unit);
+    let before_name = state.unit_name(unit).map(str::to_string);
+    let Some(f) = state.unit_fields_mut(unit) else {
+        return Err(mlua::Error::runtime(format!(
+            "SetUnitState: unit '{unit}' does not exist (pass exists = true to add it)"
+        )));
+    };
+    if let Some(name) = fields.get::<Option<String>>("name")? {
+        *f.name = name;
+    }
+    if let Some(class) = class_field(fields)? {
+        *f.class_index = class;
+    }
+    if let Some(level) = fields.get::<Option<i32>>("level")? {
+        *f.level = level;
+    }
+    if let Some(max) = fields.get::<Option<i32>>("healthMax")? {
+        *f.health_max = max.max(1);
+    }
+    if let Some(health) = fields.get::<Option<i32>>("health")? {
+        *f.health = health;
+    }
+    *f.health = (*f.health).min(*f.health_max).max(0);
+    if let Some(max) = fields.get::<Option<i32>>("powerMax")? {
+        *f.power_max = max.max(0);
+    }
+    if let Some(power) = fields.get::<Option<i32>>("power")? {
+        *f.power = power;
+    }
+    *f.power = (*f.power).min(*f.power_max).max(0);
+
+    if let (Some(before), Some(after)) = (before, state.unit_stats(unit)) {
+        events.extend(change_events(unit, &before, &after));
+    }
+    if before_name.as_deref() != state.unit_name(unit) {
+        events.push(("UNIT_NAME_UPDATE", vec![unit.to_string()]));
+    }
+    Ok(events)
+}
+
+/// `class` as a 1-based index or a class file token ("MAGE").
+fn class_field(fields: &Table) -> Result<Option<i32>> {
+    match fields.get::<Value>("class")? {
+        Value::Nil => Ok(None),
+        Value::Integer(i) => Ok(Some(i as i32)),
+        Value::Number(n) => Ok(Some(n as i32)),
+        Value::String(s) => {
+            let file = s.to_string_lossy();
+            class_index_by_file(&file)
+                .map(Some)
+                .ok_or_else(|| mlua::Error::runtime(format!("SetUnitState: unknown class '{file}'")))
+        }
+        other => Err(mlua::Error::runtime(format!(
+            "SetUnitState: class must be a number or string, got {}",
+            other.type_name()
+        ))),
+    }
+}
+
+/// Events for stat differences, in the order the client sends them.
+fn change_events(unit: &str, before: &UnitStats, after: &UnitStats) -> Vec<PendingEvent> {
+    let unit_arg = || vec![unit.to_string()];
+    let power_args = || vec![unit.to_string(), after.power_type_name.to_string()];
+    let mut events = Vec::new();
+    if before.health_max != after.health_max {
+        events.push(("UNIT_MAXHEALTH", unit_arg()));
+    }
+    if before.health != after.health {
+        events.push(("UNIT_HEALTH", unit_arg()));
+    }
+    if before.power_max != after.power_max {
+        events.push(("UNIT_MAXPOWER", power_args()));
+    }
+    if before.power != after.power {
+        events.push(("UNIT_POWER_UPDATE", power_args()));
+    }
+    if before.level != after.level {
+        events.push(("UNIT_LEVEL", unit_arg()));
+    }
+    events
+}
+
+/// Event announcing that `unit` appeared or disappeared.
+fn presence_event(unit: &str) -> PendingEvent {
+    match unit {
+        "target" => ("PLAYER_TARGET_CHANGED", Vec::new()),
+        "focus" => ("PLAYER_FOCUS_CHANGED", Vec::new()),
+        "pet" => ("UNIT_PET", vec!["player".to_string()]),
+        _ => ("GROUP_ROSTER_UPDATE", Vec::new()),
+    }
+}
+
+/// Create `unit` if absent: target/focus start as the enemy NPC, the pet as
+/// the default pet, and party slots may only be appended. Returns whether
+/// the unit was added.
+fn add_unit(state: &mut SimState, unit: &str) -> Result<bool> {
+    if state.unit_stats(unit).is_some() {
+        return Ok(false);
+    }
+    match unit {
+        "target" => state.current_target = build_target_info("enemy1", state),
+        "focus" => state.current_focus = build_target_info("enemy1", state),
+        "pet" => state.pet = Some(default_pet()),
+        u => match parse_party_index(u) {
+            Some(idx) if idx == state.party_members.len() && idx < 4 => {
+                state.party_members.push(new_party_member(idx + 1));
+            }
+            _ => return Err(mlua::Error::runtime(format!("SetUnitState: cannot add unit '{unit}'"))),
+        },
+    }
+    Ok(true)
+}
+
+/// Remove `unit`; later party members shift down a slot like in the client.
+/// Returns whether the unit existed.
+fn remove_unit(state: &mut SimState, unit: &str) -> Result<bool> {
+    let existed = state.unit_stats(unit).is_some();
+    match unit {
+        "player" => return Err(mlua::Error::runtime("SetUnitState: the player cannot be removed")),
+        "target" => state.current_target = None,
+        "focus" => state.current_focus = None,
+        "pet" => state.pet = None,
+        u => match parse_party_index(u) {
+            Some(idx) if idx < state.party_members.len() => {
+                state.party_members.remove(idx);
+            }
+            Some(_) => {}
+            None => return Err(mlua::Error::runtime(format!("SetUnitState: unknown unit '{unit}'"))),
+        },
+    }
+    Ok(existed)
+}
+
+fn fire_events(lua: &Lua, events: &[PendingEvent]) -> Result<()> {
+    let fire: mlua::Function = lua.globals().get("FireEvent")?;
+    for (event, args) in events {
+        let mut call_args = vec![Value::String(lua.create_string(event)?)];
+        for arg in args {
+            call_args.push(Value::String(lua.create_string(arg)?));
+        }
+        fire.call::<()>(mlua::MultiValue::from_vec(call_args))?;
+    }
+    Ok(())
+}
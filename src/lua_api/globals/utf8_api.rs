@@ -0,0 +1,106 @@
+//! UTF-8 aware string helpers: `strlenutf8`, `string.utf8len`,
+//! `string.utf8sub` and a code-point aware `strtrim`.
+//!
+//! Lua 5.1 strings are byte strings, so `string.len`/`string.sub` count and
+//! cut bytes and can split a multibyte character in a player or item name.
+//! These helpers count code points by their lead bytes, which also keeps them
+//! total on strings that aren't valid UTF-8.
+
+use mlua::{Lua, Result, Value};
+
+/// Register the UTF-8 string helpers.
+pub fn register_utf8_api(lua: &Lua) -> Result<()> {
+    let globals = lua.globals();
+    let string: mlua::Table = globals.get("string")?;
+
+    let utf8len = lua.create_function(|_, s: mlua::String| Ok(utf8_len(&s.as_bytes()) as i64))?;
+    globals.set("strlenutf8", utf8len.clone())?;
+    string.set("utf8len", utf8len)?;
+
+    string.set(
+        "utf8sub",
+        lua.create_function(|lua, (s, i, j): (mlua::String, Option<i64>, Option<i64>)| {
+            let bytes = s.as_bytes();
+            lua.create_string(utf8_sub(&bytes, i.unwrap_or(1), j.unwrap_or(-1)))
+        })?,
+    )?;
+
+    globals.set(
+        "strtrim",
+        lua.create_function(|lua, (s, chars): (mlua::String, Option<mlua::String>)| {
+            let bytes = s.as_bytes();
+            let trimmed = match &chars {
+                Some(chars) => utf8_trim(&bytes, &chars.as_bytes()),
+                None => utf8_trim(&bytes, b" \t\r\n"),
+            };
+            Ok(Value::String(lua.create_string(trimmed)?))
+        })?,
+    )?;
+    Ok(())
+}
+
+/// Whether `b` continues a multibyte sequence rather than starting a character.
+fn is_continuation(b: u8) -> bool {
+    b & 0xC0 == 0x80
+}
+
+/// Number of code points in `bytes`.
+pub fn utf8_len(bytes: &[u8]) -> usize {
+    bytes.iter().filter(|&&b| !is_continuation(b)).count()
+}
+
+/// Byte offset where each code point starts.
+fn char_starts(bytes: &[u8]) -> Vec<usize> {
+    (0..bytes.len()).filter(|&i| !is_continuation(bytes[i])).collect()
+}
+
+/// Code points `i..=j` (1-based, negative counts from the end), with the same
+/// clamping as `string.sub`.
+pub fn utf8_sub(bytes: &[u8], i: i64, j: i64) -> &[u8] {
+    let starts = char_starts(bytes);
+    let len = starts.len() as i64;
+    let resolve = |n: i64| if n < 0 { len + n + 1 } else { n };
+    let first = resolve(i).max(1);
+    let last = resolve(j).min(len);
+    if first > last {
+        return &bytes[..0];
+    }
+    let start = starts[(first - 1) as usize];
+    let end = starts.get(last as usize).copied().unwrap_or(bytes.len());
+    &bytes[start..end]
+}
+
+/// Strip leading and trailing code points that appear in `chars`.
+pub fn utf8_trim<'a>(bytes: &'a [u8], chars: &[u8]) -> &'a [u8] {
+    let set: Vec<&[u8]> = {
+        let starts = char_starts(chars);
+        starts
+            .iter()
+            .enumerate()
+            .map(|(n, &s)| &chars[s..starts.get(n + 1).copied().unwrap_or(chars.len())])
+            .collect()
+    };
+    let starts = char_starts(bytes);
+    let char_at = |n: usize| &bytes[starts[n]..starts.get(n + 1).copied().unwrap_or(bytes.len())];
+    let mut first = 0;
+    while first < starts.len() && set.contains(&char_at(first)) {
+        first += 1;
+    }
+    let mut last = starts.len();
+    while last > first && set.contains(&char_at(last - 1)) {
+        last -= 1;
+    }
+    if first == last {
+        return &bytes[..0];
+    }
+    let end = starts.get(last).copied().unwrap_or(bytes.len());
+    &bytes[starts[first]..end]
+}
+
+/// The first `max_chars` characters of `s`.
+pub fn truncate_chars(s: &str, max_chars: usize) -> &str {
+    match s.char_indices().nth(max_chars) {
+        Some((end, _)) => &s[..end],
+        None => s,
+    }
+}
@@ -12,6 +12,7 @@ pub fn register_utility_api(lua: &Lua) -> Result<()> {
     register_global_access(lua)?;
     super::security_api::register_security_functions(lua)?;
     register_error_handlers(lua)?;
+    register_intrinsic_scripts(lua)?;
     register_misc_stubs(lua)?;
     register_lua_stdlib_aliases(lua)?;
     register_mixin_system(lua)?;
@@ -362,6 +363,92 @@ fn register_error_handlers(lua: &Lua) -> Result<()> {
         })?,
     )?;
 
+    // Message handler for generated `xpcall` sites: keeps the stack at the
+    // point of the error (trimmed unless --lua-traceback), which a plain
+    // `pcall` + `tostring(err)` loses. Errors coming through our `xpcall`
+    // already carry the traceback mlua captured where they were raised.
+    globals.set(
+        "__script_traceback",
+        lua.create_function(|lua, msg: Value| {
+            let msg: String = lua.globals().get::<mlua::Function>("tostring")?.call(msg)?;
+            if msg.contains("\nstack traceback:") {
+                return Ok(super::super::script_helpers::trim_traceback(&msg));
+            }
+            let traceback = lua.globals().get::<mlua::Table>("debug")
+                .and_then(|debug| debug.get::<mlua::Function>("traceback"));
+            // Level 2 skips `debug.traceback` and this handler.
+            let trace = match traceback {
+                Ok(tb) => tb.call::<String>((msg.as_str(), 2)).unwrap_or(msg),
+                Err(_) => msg,
+            };
+            Ok(super::super::script_helpers::trim_traceback(&trace))
+        })?,
+    )?;
+
+    Ok(())
+}
+
+/// Internal helpers used by generated XML script code for handlers declared
+/// with `intrinsicOrder="precall"`/`"postcall"`.
+///
+/// `__set_intrinsic_script(obj, handler, fn, order)` records the intrinsic
+/// handler; `__compose_intrinsic_script(obj, handler, fn)` returns `fn`
+/// wrapped so the recorded intrinsic runs before (precall) or after
+/// (postcall) it, or `fn` unchanged when there is none.
+fn register_intrinsic_scripts(lua: &Lua) -> Result<()> {
+    let globals = lua.globals();
+    let intrinsics = lua.create_table()?;
+
+    let store = intrinsics.clone();
+    globals.set(
+        "__set_intrinsic_script",
+        lua.create_function(
+            move |lua, (obj, handler, func, order): (Value, String, Option<mlua::Function>, String)| {
+                let Some(func) = func else { return Ok(()) };
+                let per_object = match store.get::<Option<mlua::Table>>(obj.clone())? {
+                    Some(t) => t,
+                    None => {
+                        let t = lua.create_table()?;
+                        store.set(obj, t.clone())?;
+                        t
+                    }
+                };
+                let entry = lua.create_table()?;
+                entry.set(1, func)?;
+                entry.set(2, order)?;
+                per_object.set(handler, entry)
+            },
+        )?,
+    )?;
+
+    globals.set(
+        "__compose_intrinsic_script",
+        lua.create_function(move |lua, (obj, handler, func): (Value, String, Value)| {
+            let Value::Function(func) = func else { return Ok(func) };
+            let entry = intrinsics
+                .get::<Option<mlua::Table>>(obj)?
+                .map(|t| t.get::<Option<mlua::Table>>(handler.as_str()))
+                .transpose()?
+                .flatten();
+            let Some(entry) = entry else { return Ok(Value::Function(func)) };
+            let intrinsic: mlua::Function = entry.get(1)?;
+            let order: String = entry.get(2)?;
+            let (first, second) = if order == "postcall" { (func, intrinsic) } else { (intrinsic, func) };
+            let composed = lua.create_function(move |lua, args: mlua::MultiValue| {
+                for f in [&first, &second] {
+                    if let Err(e) = f.call::<()>(args.clone()) {
+                        super::super::script_helpers::call_error_handler(
+                            lua,
+                            &format!("[script:{handler}] {e}"),
+                        );
+                    }
+                }
+                Ok(())
+            })?;
+            Ok(Value::Function(composed))
+        })?,
+    )?;
+
     Ok(())
 }
 
@@ -416,7 +503,6 @@ fn register_string_aliases(lua: &Lua) -> Result<()> {
         strrev = string.reverse
         strlower = string.lower
         strupper = string.upper
-        strtrim = function(s) return (s:gsub("^%s*(.-)%s*$", "%1")) end
         strsplittable = function(del, str) local t = {} for v in string.gmatch(str, "([^"..del.."]+)") do t[#t+1] = v end return t end
         strjoin = function(delimiter, ...) return table.concat({...}, delimiter) end
         string.join = strjoin
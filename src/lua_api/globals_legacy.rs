@@ -35,6 +35,7 @@ use super::globals::timer_api::register_timer_api;
 use super::globals::tooltip_api::register_tooltip_frames;
 use super::globals::unit_api::register_unit_api;
 use super::globals::utility_api::register_utility_api;
+use super::globals::utf8_api::register_utf8_api;
 use super::SimState;
 use mlua::{Lua, Result, Value};
 use std::cell::RefCell;
@@ -113,7 +114,7 @@ fn patch_string_format(lua: &Lua) -> Result<()> {
 fn register_print(lua: &Lua, state: Rc<RefCell<SimState>>) -> Result<()> {
     let print_func = lua.create_function(move |_lua, args: mlua::Variadic<Value>| {
         let output = format_print_args(&args);
-        state.borrow_mut().console_output.push(output);
+        state.borrow_mut().push_console(output);
         Ok(())
     })?;
     lua.globals().set("print", print_func)
@@ -266,7 +267,7 @@ const FRAME_BASE_METHODS: &[&str] = &[
     "SetPropagateKeyboardInput", "GetPropagateKeyboardInput", "SetIgnoreParentScale",
     "SetIgnoreParentAlpha", "SetFlattensRenderLayers", "GetFlattensRenderLayers",
     "SetDrawLayerEnabled", "GetDrawLayerEnabled", "GetTop", "GetBottom",
-    "GetLeft", "GetRight", "GetCenter", "GetBounds", "GetRect", "GetSize",
+    "GetLeft", "GetRight", "GetCenter", "GetBounds", "GetBoundsRect", "GetRect", "GetSize",
     "GetScaledRect", "SetClipsChildren", "DoesClipChildren",
     "EnableKeyboard", "IsKeyboardEnabled",
     "SetMouseClickEnabled", "IsMouseClickEnabled", "SetMouseMotionEnabled",
@@ -292,7 +293,7 @@ const TEXTURE_METHODS: &[&str] = &[
     "GetTexCoord", "SetVertexColor", "GetVertexColor", "SetDesaturated",
     "IsDesaturated", "SetBlendMode", "GetBlendMode", "SetRotation",
     "GetRotation", "SetAtlas", "GetAtlas", "SetColorTexture", "SetGradient",
-    "SetAllPoints",
+    "SetGradientAlpha", "SetAllPoints",
     "SetSnapToPixelGrid", "IsSnappingToPixelGrid", "SetTexelSnappingBias",
     "GetTexelSnappingBias", "ClearTextureSlice", "SetTextureSliceMode",
     "GetTextureSliceMode", "SetTextureSliceMargins", "GetTextureSliceMargins",
@@ -410,15 +411,16 @@ fn register_submodule_apis(lua: &Lua, state: &Rc<RefCell<SimState>>) -> Result<(
     register_enum_api(lua)?;
     register_constants_api(lua)?;
     register_c_map_api(lua)?;
-    register_c_quest_api(lua)?;
+    register_c_quest_api(lua, Rc::clone(state))?;
     register_c_collection_api(lua)?;
-    register_c_item_api(lua)?;
+    register_c_item_api(lua, Rc::clone(state))?;
     register_c_misc_api(lua)?;
     register_c_system_api(lua)?;
     register_c_stubs_api(lua, Rc::clone(state))?;
     register_c_editmode_api(lua)?;
     register_mixin_api(lua)?;
     register_utility_api(lua)?;
+    register_utf8_api(lua)?;
     register_settings_api(lua)?;
     register_spell_api(lua, Rc::clone(state))?;
     register_item_api(lua)?;
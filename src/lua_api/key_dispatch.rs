@@ -196,7 +196,7 @@ impl WowLuaEnv {
                     .map(|f| f.visible)
                     .unwrap_or(false);
                 if is_visible {
-                    self.state.borrow_mut().set_frame_visible(id, false);
+                    super::frame::hide_frame(&self.lua, id)?;
                     closed = true;
                 }
             }
@@ -224,23 +224,20 @@ impl WowLuaEnv {
             return Ok(());
         }
 
-        {
+        // Insert at the cursor; SetMaxLetters may drop some or all characters
+        let inserted = {
             let mut state = self.state.borrow_mut();
-            if let Some(frame) = state.widgets.get_mut_visual(fid) {
-                let current = frame.text.get_or_insert_with(String::new);
-                let char_pos = frame.editbox_cursor_pos as usize;
-                // Convert char position to byte position
-                let byte_pos = current.char_indices()
-                    .nth(char_pos)
-                    .map(|(i, _)| i)
-                    .unwrap_or(current.len());
-                current.insert_str(byte_pos, text);
-                frame.editbox_cursor_pos += text.chars().count() as i32;
+            match state.widgets.get_mut_visual(fid) {
+                Some(frame) => frame.editbox_insert(text),
+                None => String::new(),
             }
+        };
+        if inserted.is_empty() {
+            return Ok(());
         }
 
         // Fire OnChar with each character
-        for ch in text.chars() {
+        for ch in inserted.chars() {
             let char_val = Value::String(self.lua.create_string(ch.to_string())?);
             self.fire_script_handler(fid, "OnChar", vec![char_val])?;
         }
@@ -351,10 +348,9 @@ impl WowLuaEnv {
             .map(|f| f.visible)
             .unwrap_or(false);
         if is_visible {
-            self.state.borrow_mut().set_frame_visible(id, false);
+            super::frame::hide_frame(&self.lua, id)?;
         } else {
-            self.state.borrow_mut().set_frame_visible(id, true);
-            super::frame::fire_on_show_recursive(&self.lua, id)?;
+            super::frame::show_frame(&self.lua, id)?;
         }
         Ok(())
     }
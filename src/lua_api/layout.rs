@@ -1,5 +1,8 @@
 //! Layout computation helpers for frame positioning.
 
+use std::cell::RefCell;
+use std::collections::HashSet;
+
 use crate::widget::{AnchorPoint, WidgetRegistry};
 
 /// Simple layout rect for frame positioning.
@@ -44,17 +47,116 @@ fn parent_rect(
     }
 }
 
+/// Get the rect an anchor is relative to: its `relative_to_id`/`relative_to`
+/// target (name or `$parent.Key` path) when it resolves, else the parent rect.
+fn relative_rect(
+    registry: &WidgetRegistry,
+    id: u64,
+    anchor: &crate::widget::Anchor,
+    parent_rect: LayoutRect,
+    screen_width: f32,
+    screen_height: f32,
+) -> LayoutRect {
+    match registry.resolve_anchor_target(id, anchor) {
+        Some(target) if anchor.relative_to_id.is_some() || !registry.would_create_anchor_cycle(id, target) => {
+            compute_frame_rect(registry, target, screen_width, screen_height)
+        }
+        _ => parent_rect,
+    }
+}
+
+/// Frames currently being laid out on this thread (the recursion stack) and
+/// the anchor cycles already reported.
+#[derive(Default)]
+struct CycleGuard {
+    stack: Vec<u64>,
+    reported: HashSet<Vec<u64>>,
+    warnings: Vec<String>,
+}
+
+thread_local! {
+    static CYCLE_GUARD: RefCell<CycleGuard> = RefCell::new(CycleGuard::default());
+}
+
+/// Take the anchor cycle warnings reported on this thread since the last call.
+/// The addon loader moves them into each file's `LoadResult` warnings.
+pub fn take_anchor_cycle_warnings() -> Vec<String> {
+    CYCLE_GUARD.with(|g| std::mem::take(&mut g.borrow_mut().warnings))
+}
+
+/// Forget the cycles reported so far. Frame ids restart with each
+/// `WowLuaEnv`, so a new environment must be able to report them again.
+pub(crate) fn reset_anchor_cycle_guard() {
+    CYCLE_GUARD.with(|g| *g.borrow_mut() = CycleGuard::default());
+}
+
+/// Push `id` onto the layout stack. Returns false when `id` is already being
+/// laid out, i.e. its parents/anchors loop back to it; each distinct cycle is
+/// reported once.
+pub(crate) fn enter_layout(registry: &WidgetRegistry, id: u64) -> bool {
+    CYCLE_GUARD.with(|g| {
+        let mut g = g.borrow_mut();
+        let Some(pos) = g.stack.iter().position(|&f| f == id) else {
+            g.stack.push(id);
+            return true;
+        };
+        let cycle: Vec<u64> = g.stack[pos..].to_vec();
+        let mut key = cycle.clone();
+        key.sort_unstable();
+        if g.reported.insert(key) {
+            let path: Vec<String> = cycle.iter().chain(std::iter::once(&id))
+                .map(|&f| frame_label(registry, f))
+                .collect();
+            let msg = format!(
+                "anchor cycle {}: laying out {} as an empty rect at screen center",
+                path.join(" -> "),
+                frame_label(registry, id),
+            );
+            eprintln!("[layout] {msg}");
+            g.warnings.push(msg);
+        }
+        false
+    })
+}
+
+pub(crate) fn leave_layout() {
+    CYCLE_GUARD.with(|g| g.borrow_mut().stack.pop());
+}
+
+fn frame_label(registry: &WidgetRegistry, id: u64) -> String {
+    registry.get(id)
+        .and_then(|f| f.name.clone())
+        .unwrap_or_else(|| format!("<anonymous {id}>"))
+}
+
 /// Compute frame rect for debugging (same algorithm as renderer).
+///
+/// Parent/anchor cycles are broken like in the renderer's layout: the frame
+/// that closes the loop gets an empty rect at the screen center.
 pub fn compute_frame_rect(
     registry: &WidgetRegistry,
     id: u64,
     screen_width: f32,
     screen_height: f32,
 ) -> LayoutRect {
-    let frame = match registry.get(id) {
-        Some(f) => f,
-        None => return LayoutRect::default(),
-    };
+    if registry.get(id).is_none() {
+        return LayoutRect::default();
+    }
+    if !enter_layout(registry, id) {
+        return LayoutRect { x: screen_width / 2.0, y: screen_height / 2.0, width: 0.0, height: 0.0 };
+    }
+    let rect = frame_rect_uncached(registry, id, screen_width, screen_height);
+    leave_layout();
+    rect
+}
+
+fn frame_rect_uncached(
+    registry: &WidgetRegistry,
+    id: u64,
+    screen_width: f32,
+    screen_height: f32,
+) -> LayoutRect {
+    let Some(frame) = registry.get(id) else { return LayoutRect::default() };
 
     let width = frame.width;
     let height = frame.height;
@@ -69,8 +171,13 @@ pub fn compute_frame_rect(
         };
     }
 
+    if frame.anchors.len() >= 2 {
+        return multi_anchor_rect(registry, id, pr, screen_width, screen_height);
+    }
+
     let anchor = &frame.anchors[0];
-    let (pax, pay) = anchor_position(anchor.relative_point, pr.x, pr.y, pr.width, pr.height);
+    let rr = relative_rect(registry, id, anchor, pr, screen_width, screen_height);
+    let (pax, pay) = anchor_position(anchor.relative_point, rr.x, rr.y, rr.width, rr.height);
     let target_x = pax + anchor.x_offset;
     // WoW uses Y-up coordinate system, screen uses Y-down
     let target_y = pay - anchor.y_offset;
@@ -85,6 +192,69 @@ pub fn compute_frame_rect(
     }
 }
 
+/// Compute the rect of a frame with two or more anchors.
+///
+/// Each anchor pins one or two edges (or a center line). When opposing edges
+/// are both pinned (TOPLEFT+BOTTOMRIGHT, LEFT+RIGHT, TOP+BOTTOM) the span
+/// between them overrides the explicit size on that axis; otherwise the
+/// explicit width/height is used and positioned from whichever edge is pinned.
+fn multi_anchor_rect(
+    registry: &WidgetRegistry,
+    id: u64,
+    pr: LayoutRect,
+    screen_width: f32,
+    screen_height: f32,
+) -> LayoutRect {
+    let Some(frame) = registry.get(id) else { return LayoutRect::default() };
+    let (mut left, mut right, mut top, mut bottom) = (None, None, None, None);
+    let (mut center_x, mut center_y) = (None, None);
+
+    for anchor in &frame.anchors {
+        let rr = relative_rect(registry, id, anchor, pr, screen_width, screen_height);
+        let (ax, ay) = anchor_position(anchor.relative_point, rr.x, rr.y, rr.width, rr.height);
+        let x = ax + anchor.x_offset;
+        let y = ay - anchor.y_offset;
+        match anchor.point {
+            AnchorPoint::TopLeft => { left = Some(x); top = Some(y); }
+            AnchorPoint::TopRight => { right = Some(x); top = Some(y); }
+            AnchorPoint::BottomLeft => { left = Some(x); bottom = Some(y); }
+            AnchorPoint::BottomRight => { right = Some(x); bottom = Some(y); }
+            AnchorPoint::Top => { top = Some(y); center_x = Some(x); }
+            AnchorPoint::Bottom => { bottom = Some(y); center_x = Some(x); }
+            AnchorPoint::Left => { left = Some(x); center_y = Some(y); }
+            AnchorPoint::Right => { right = Some(x); center_y = Some(y); }
+            AnchorPoint::Center => { center_x = Some(x); center_y = Some(y); }
+        }
+    }
+
+    let (x, width) = resolve_axis(left, right, center_x, frame.width, pr.x, pr.width);
+    let (y, height) = resolve_axis(top, bottom, center_y, frame.height, pr.y, pr.height);
+    LayoutRect { x, y, width, height }
+}
+
+/// Resolve one axis from optional near/far/center constraints.
+///
+/// Returns (position, length). Inverted spans are swapped so the length is
+/// never negative; unconstrained axes are centered on the parent.
+fn resolve_axis(
+    near: Option<f32>,
+    far: Option<f32>,
+    center: Option<f32>,
+    size: f32,
+    parent_pos: f32,
+    parent_len: f32,
+) -> (f32, f32) {
+    match (near, far) {
+        (Some(n), Some(f)) => (n.min(f), (f - n).abs()),
+        (Some(n), None) => (n, size),
+        (None, Some(f)) => (f - size, size),
+        (None, None) => match center {
+            Some(c) => (c - size / 2.0, size),
+            None => (parent_pos + (parent_len - size) / 2.0, size),
+        },
+    }
+}
+
 /// Get the position of an anchor point on a rect.
 pub fn anchor_position(point: AnchorPoint, x: f32, y: f32, w: f32, h: f32) -> (f32, f32) {
     match point {
@@ -70,9 +70,11 @@ impl<'a> LoaderEnv<'a> {
         use super::script_helpers::{call_error_handler, get_frame_ref, get_script};
         use std::time::Instant;
 
+        let unit = super::script_helpers::event_unit_arg(args);
         let listeners = {
-            let state = self.state.borrow();
-            state.widgets.get_event_listeners(event)
+            let mut state = self.state.borrow_mut();
+            state.record_event(event, args);
+            state.widgets.get_unit_event_listeners(event, unit.as_deref())
         };
 
         for widget_id in listeners {
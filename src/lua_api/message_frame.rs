@@ -1,5 +1,7 @@
 //! MessageFrame / ScrollingMessageFrame state data structures.
 
+use std::collections::VecDeque;
+
 /// A single message in a MessageFrame.
 pub struct Message {
     pub text: String,
@@ -14,22 +16,26 @@ pub struct Message {
 
 /// State for a MessageFrame or ScrollingMessageFrame.
 pub struct MessageFrameData {
-    pub messages: Vec<Message>,
+    /// Scrollback, oldest first; capped at `max_lines`.
+    pub messages: VecDeque<Message>,
     pub max_lines: usize,
     pub fading: bool,
     pub time_visible: f64,
     pub fade_duration: f64,
     pub fade_power: f64,
     pub insert_mode: String, // "TOP" or "BOTTOM"
+    /// Messages scrolled back from the newest (0 = at the bottom).
     pub scroll_offset: i32,
     pub scroll_allowed: bool,
     pub text_copyable: bool,
+    /// Whether clicking a hyperlink fires OnHyperlinkClick.
+    pub hyperlinks_enabled: bool,
 }
 
 impl Default for MessageFrameData {
     fn default() -> Self {
         Self {
-            messages: Vec::new(),
+            messages: VecDeque::new(),
             max_lines: 120,
             fading: true,
             time_visible: 10.0,
@@ -39,6 +45,43 @@ impl Default for MessageFrameData {
             scroll_offset: 0,
             scroll_allowed: true,
             text_copyable: false,
+            hyperlinks_enabled: true,
+        }
+    }
+}
+
+impl MessageFrameData {
+    /// Drop messages beyond `max_lines`, oldest first.
+    pub fn trim_to_max_lines(&mut self) {
+        while self.messages.len() > self.max_lines {
+            if self.insert_mode == "TOP" {
+                self.messages.pop_back();
+            } else {
+                self.messages.pop_front();
+            }
         }
+        self.scroll_offset = self.scroll_offset.clamp(0, self.max_scroll_range());
+    }
+
+    /// Largest scroll offset: every message but the oldest scrolled away.
+    pub fn max_scroll_range(&self) -> i32 {
+        self.messages.len().saturating_sub(1) as i32
+    }
+
+    /// Scroll by `delta` messages (positive = back in history), clamped.
+    pub fn scroll_by(&mut self, delta: i32) {
+        self.scroll_offset = (self.scroll_offset + delta).clamp(0, self.max_scroll_range());
     }
 }
+
+/// One line of a rendered `|H<link>|h<text>|h` hyperlink in a MessageFrame
+/// or SimpleHTML frame.
+#[derive(Debug, Clone)]
+pub struct HyperlinkRegion {
+    /// Link payload, e.g. `item:19019::::::::80`.
+    pub link: String,
+    /// The whole hyperlink as written, passed as OnHyperlinkClick's `text`.
+    pub text: String,
+    /// Screen rect in UI units, like `Frame::layout_rect`.
+    pub rect: crate::LayoutRect,
+}
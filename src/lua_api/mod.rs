@@ -1,7 +1,9 @@
 //! Lua API bindings implementing WoW's addon API.
 
 pub mod animation;
+pub mod api_coverage;
 mod builtin_frames;
+mod clock;
 mod diagnostics;
 mod env;
 pub(crate) mod frame;
@@ -11,7 +13,8 @@ mod key_dispatch;
 mod frame_methods;
 pub mod globals;
 mod globals_legacy;
-mod layout;
+pub(crate) mod layout;
+mod mouse_dispatch;
 pub(crate) mod loader_env;
 pub mod message_frame;
 pub(crate) mod script_helpers;
@@ -24,12 +27,16 @@ pub(crate) mod workarounds_bags;
 pub(crate) mod workarounds_editmode;
 
 // Re-export public types
+pub use clock::SimClock;
 pub use env::WowLuaEnv;
+pub use game_data::set_random_seed;
 pub use layout::{
-    anchor_position, compute_frame_rect, frame_position_from_anchor, get_parent_depth, LayoutRect,
+    anchor_position, compute_frame_rect, frame_position_from_anchor, get_parent_depth,
+    take_anchor_cycle_warnings, LayoutRect,
 };
 pub use loader_env::LoaderEnv;
-pub use message_frame::MessageFrameData;
+pub use script_helpers::set_full_tracebacks;
+pub use message_frame::{HyperlinkRegion, MessageFrameData};
 pub use simple_html::SimpleHtmlData;
 pub use state::{AddonInfo, PendingTimer, SimState, tick_party_health};
 pub use tooltip::TooltipData;
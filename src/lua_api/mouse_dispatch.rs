@@ -0,0 +1,220 @@
+//! Headless mouse input: hit testing and OnEnter/OnLeave/OnMouseDown/OnMouseUp/OnClick/OnMouseWheel dispatch.
+//!
+//! Mirrors the GUI's mouse handling (iced_app/mouse.rs) for tests and
+//! headless runs. Coordinates are UI units with a top-left origin, the same
+//! space as `Frame::layout_rect`.
+//!
+//! Hyperlinks (OnHyperlinkClick/Enter/Leave) are hit against the link rects
+//! stored in `SimState::hyperlink_regions` from the last quad batch, so they
+//! only respond once the frame has been drawn.
+
+use crate::Result;
+use crate::widget::Frame;
+use mlua::Value;
+
+use super::env::WowLuaEnv;
+use super::HyperlinkRegion;
+
+impl WowLuaEnv {
+    /// Topmost visible frame at (x, y) whose flags pass `accepts`.
+    ///
+    /// Frames are scanned in reverse render order, so strata, frame level and
+    /// children-above-parents follow what is drawn. `SetHitRectInsets` shrinks
+    /// each frame's hit area.
+    pub fn frame_at(&self, x: f32, y: f32, accepts: fn(&Frame) -> bool) -> Option<u64> {
+        let mut state = self.state.borrow_mut();
+        state.ensure_layout_rects();
+        let buckets = state.get_strata_buckets()?.clone();
+        buckets.iter().rev().flat_map(|b| b.iter().rev()).copied().find(|&id| {
+            state.widgets.get(id).is_some_and(|f| {
+                !f.widget_type.is_region()
+                    && f.visible
+                    && f.effective_alpha > 0.0
+                    && accepts(f)
+                    && !f.name.as_deref().is_some_and(|n| {
+                        crate::iced_app::frame_collect::HIT_TEST_EXCLUDED.contains(&n)
+                    })
+                    && hit_rect_contains(f, x, y)
+            })
+        })
+    }
+
+    /// Move the cursor, firing OnLeave on the previously hovered frame and
+    /// OnEnter on the newly hovered one when the hovered frame changes. A
+    /// frame started with StartMoving follows the cursor, and one started
+    /// with StartSizing resizes and fires OnSizeChanged.
+    pub fn send_mouse_move(&self, x: f32, y: f32) -> Result<()> {
+        let resized = self.state.borrow_mut().set_mouse_position(x, y);
+        super::script_helpers::fire_size_changed(&self.lua, resized);
+        let new_hovered = self.frame_at(x, y, Frame::accepts_mouse_motion);
+        let old_hovered = {
+            let mut state = self.state.borrow_mut();
+            let old = state.hovered_frame;
+            // Update before firing so GetMouseFocus() is correct inside OnEnter.
+            state.hovered_frame = new_hovered;
+            old
+        };
+        if old_hovered != new_hovered {
+            self.update_hovered_hyperlink(None, x, y)?;
+            if let Some(old_id) = old_hovered {
+                self.fire_script_handler(old_id, "OnLeave", vec![Value::Boolean(true)])?;
+            }
+            if let Some(new_id) = new_hovered {
+                self.fire_script_handler(new_id, "OnEnter", vec![Value::Boolean(true)])?;
+            }
+        }
+        self.update_hovered_hyperlink(new_hovered, x, y)?;
+        Ok(())
+    }
+
+    /// The hyperlink drawn at (x, y) in frame `id`, if the frame has
+    /// hyperlinks enabled.
+    pub fn hyperlink_at(&self, id: u64, x: f32, y: f32) -> Option<HyperlinkRegion> {
+        let state = self.state.borrow();
+        if !state.hyperlinks_enabled(id)? {
+            return None;
+        }
+        state.hyperlink_at(id, x, y).cloned()
+    }
+
+    /// Fire OnHyperlinkClick(link, text, button) if a click on frame `id`
+    /// landed on one of its hyperlinks. Returns whether one was hit.
+    pub fn click_hyperlink(&self, id: u64, x: f32, y: f32, button: &str) -> Result<bool> {
+        let Some(region) = self.hyperlink_at(id, x, y) else { return Ok(false) };
+        let args = vec![
+            Value::String(self.lua.create_string(&region.link)?),
+            Value::String(self.lua.create_string(&region.text)?),
+            Value::String(self.lua.create_string(button)?),
+        ];
+        self.fire_script_handler(id, "OnHyperlinkClick", args)?;
+        Ok(true)
+    }
+
+    /// Track the hyperlink under the cursor in the `hovered` frame, firing
+    /// OnHyperlinkLeave on the old link and OnHyperlinkEnter(link, text) on
+    /// the new one. Returns whether the hovered link changed.
+    pub fn update_hovered_hyperlink(&self, hovered: Option<u64>, x: f32, y: f32) -> Result<bool> {
+        let new = hovered.and_then(|id| self.hyperlink_at(id, x, y).map(|region| (id, region)));
+        let old = {
+            let mut state = self.state.borrow_mut();
+            let key = new.as_ref().map(|(id, region)| (*id, region.link.clone()));
+            if state.hovered_hyperlink == key {
+                return Ok(false);
+            }
+            std::mem::replace(&mut state.hovered_hyperlink, key)
+        };
+        if let Some((old_id, _)) = old {
+            self.fire_script_handler(old_id, "OnHyperlinkLeave", vec![])?;
+        }
+        if let Some((id, region)) = new {
+            let args = vec![
+                Value::String(self.lua.create_string(&region.link)?),
+                Value::String(self.lua.create_string(&region.text)?),
+            ];
+            self.fire_script_handler(id, "OnHyperlinkEnter", args)?;
+        }
+        Ok(true)
+    }
+
+    /// Press `button` ("LeftButton", "RightButton", ...) at (x, y).
+    pub fn send_mouse_down(&self, x: f32, y: f32, button: &str) -> Result<()> {
+        let hit = self.frame_at(x, y, Frame::accepts_mouse_clicks);
+        {
+            let mut state = self.state.borrow_mut();
+            state.mouse_down_frame = hit;
+            if let Some(id) = hit {
+                state.raise_toplevel_ancestor(id);
+            }
+        }
+        if let Some(id) = hit {
+            let button_val = Value::String(self.lua.create_string(button)?);
+            self.fire_script_handler(id, "OnMouseDown", vec![button_val])?;
+        }
+        Ok(())
+    }
+
+    /// Release `button` at (x, y). OnClick, and OnHyperlinkClick over a
+    /// hyperlink, fire only when the release lands on the frame that received
+    /// the matching mouse-down.
+    pub fn send_mouse_up(&self, x: f32, y: f32, button: &str) -> Result<()> {
+        let hit = self.frame_at(x, y, Frame::accepts_mouse_clicks);
+        let pressed = self.state.borrow_mut().mouse_down_frame.take();
+        let Some(id) = hit else { return Ok(()) };
+        let button_val = Value::String(self.lua.create_string(button)?);
+        if pressed == Some(id) {
+            self.toggle_checkbutton_if_needed(id);
+            let down_val = Value::Boolean(false);
+            self.fire_script_handler(id, "OnClick", vec![button_val.clone(), down_val.clone()])?;
+            self.fire_script_handler(id, "PostClick", vec![button_val.clone(), down_val])?;
+            self.click_hyperlink(id, x, y, button)?;
+        }
+        self.fire_script_handler(id, "OnMouseUp", vec![button_val])
+    }
+
+    /// Toggle CheckButton checked state before OnClick (WoW behavior).
+    /// Skip action bar buttons — they manage checked state via UpdateState().
+    pub fn toggle_checkbutton_if_needed(&self, frame_id: u64) {
+        let mut state = self.state.borrow_mut();
+        let is_checkbutton = state
+            .widgets
+            .get(frame_id)
+            .map(|f| f.widget_type == crate::widget::WidgetType::CheckButton)
+            .unwrap_or(false);
+        if !is_checkbutton {
+            return;
+        }
+        // Action bar buttons registered via SetActionUIButton manage their own
+        // checked state through UpdateState() — don't auto-toggle them.
+        let is_action_button = state.action_ui_buttons.iter().any(|(id, _)| *id == frame_id);
+        if is_action_button {
+            return;
+        }
+
+        let old_checked = state
+            .widgets
+            .get(frame_id)
+            .and_then(|f| f.attributes.get("__checked"))
+            .and_then(|v| {
+                if let crate::widget::AttributeValue::Boolean(b) = v {
+                    Some(*b)
+                } else {
+                    None
+                }
+            })
+            .unwrap_or(false);
+        let new_checked = !old_checked;
+
+        if let Some(frame) = state.widgets.get_mut(frame_id) {
+            frame.attributes.insert(
+                "__checked".to_string(),
+                crate::widget::AttributeValue::Boolean(new_checked),
+            );
+        }
+        let tex_id = state.widgets.get(frame_id)
+            .and_then(|f| f.children_keys.get("CheckedTexture").copied());
+        if let Some(tex_id) = tex_id {
+            state.set_frame_visible(tex_id, new_checked);
+        }
+    }
+
+    /// Scroll the wheel by `delta` at (x, y). OnMouseWheel propagates up the
+    /// parent chain to the first frame with a handler; returns whether one ran.
+    pub fn send_mouse_wheel(&self, x: f32, y: f32, delta: f32) -> Result<bool> {
+        let mut current = self.frame_at(x, y, Frame::is_mouse_hittable);
+        while let Some(id) = current {
+            if self.has_script_handler(id, "OnMouseWheel") {
+                self.fire_script_handler(id, "OnMouseWheel", vec![Value::Number(delta as f64)])?;
+                return Ok(true);
+            }
+            current = self.state.borrow().widgets.get(id).and_then(|f| f.parent_id);
+        }
+        Ok(false)
+    }
+}
+
+/// Whether (x, y) falls inside the frame's layout rect shrunk by its hit rect insets.
+fn hit_rect_contains(f: &Frame, x: f32, y: f32) -> bool {
+    let Some(r) = f.layout_rect else { return false };
+    let (il, ir, it, ib) = f.hit_rect_insets;
+    x >= r.x + il && x <= r.x + r.width - ir && y >= r.y + it && y <= r.y + r.height - ib
+}
@@ -3,6 +3,8 @@
 //! All internal tables (__scripts, __script_hooks, __frame_fields) are stored
 //! in the Lua registry, invisible to addon Lua code.
 
+use std::sync::atomic::{AtomicBool, Ordering};
+
 use mlua::{Lua, Value};
 
 // ── __scripts table ──────────────────────────────────────────────────
@@ -34,6 +36,26 @@ pub fn get_script(lua: &Lua, widget_id: u64, handler_name: &str) -> Option<mlua:
     table.get(key.as_str()).ok()
 }
 
+/// Call `handler_name` on `widget_id` with the frame as `self` followed by
+/// `extra_args`. Lua errors go through `call_error_handler`.
+pub fn fire_script(lua: &Lua, widget_id: u64, handler_name: &str, extra_args: Vec<Value>) {
+    if let Some(handler) = get_script(lua, widget_id, handler_name) {
+        let mut call_args = vec![super::frame::frame_lud(widget_id)];
+        call_args.extend(extra_args);
+        if let Err(e) = handler.call::<()>(mlua::MultiValue::from_vec(call_args)) {
+            call_error_handler(lua, &e.to_string());
+        }
+    }
+}
+
+/// Fire OnSizeChanged for the frame `SimState::set_mouse_position` resized.
+pub fn fire_size_changed(lua: &Lua, resized: Option<(u64, f32, f32)>) {
+    if let Some((id, width, height)) = resized {
+        let size = vec![Value::Number(width as f64), Value::Number(height as f64)];
+        fire_script(lua, id, "OnSizeChanged", size);
+    }
+}
+
 /// Set a script handler for a given frame + handler name.
 pub fn set_script(lua: &Lua, widget_id: u64, handler_name: &str, func: mlua::Function) {
     let table = get_or_create_scripts_table(lua);
@@ -107,6 +129,14 @@ pub fn get_frame_ref(_lua: &Lua, widget_id: u64) -> Option<Value> {
 /// Call the WoW error handler (set via `seterrorhandler`) and always log to stderr.
 pub fn call_error_handler(lua: &Lua, error_msg: &str) {
     eprintln!("Lua error: {error_msg}");
+    if let Some(state) = lua.app_data_ref::<std::rc::Rc<std::cell::RefCell<super::SimState>>>()
+        && let Ok(mut state) = state.try_borrow_mut()
+    {
+        state.broadcast_console(&format!("Lua error: {error_msg}"));
+        if let Some(errors) = state.script_errors.as_mut() {
+            errors.push(error_msg.to_string());
+        }
+    }
     let handler: Option<mlua::Function> = lua.named_registry_value(ERROR_HANDLER_KEY).ok();
     if let Some(h) = handler
         && let Err(e) = h.call::<()>(error_msg.to_string()) {
@@ -114,3 +144,53 @@ pub fn call_error_handler(lua: &Lua, error_msg: &str) {
         }
 }
 
+// ── Tracebacks ──────────────────────────────────────────────────────
+
+static FULL_TRACEBACKS: AtomicBool = AtomicBool::new(false);
+
+/// Stack frames kept in a script error unless full tracebacks are on.
+const SHORT_TRACEBACK_FRAMES: usize = 4;
+
+/// Keep every stack frame in script error tracebacks (`--lua-traceback`).
+pub fn set_full_tracebacks(enabled: bool) {
+    FULL_TRACEBACKS.store(enabled, Ordering::Relaxed);
+}
+
+/// Shorten a `debug.traceback` string to the message and the innermost Lua
+/// frames, unless full tracebacks are on. C frames (`error`, `pcall`) are
+/// dropped from the short form since they never point at addon code.
+pub fn trim_traceback(trace: &str) -> String {
+    if FULL_TRACEBACKS.load(Ordering::Relaxed) {
+        return trace.to_string();
+    }
+    let Some((msg, stack)) = trace.split_once("\nstack traceback:") else {
+        return trace.to_string();
+    };
+    let frames: Vec<&str> = stack.lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty() && !l.starts_with("[C]"))
+        .collect();
+    let mut out = format!("{msg}\nstack traceback:");
+    for frame in frames.iter().take(SHORT_TRACEBACK_FRAMES) {
+        out.push_str("\n\t");
+        out.push_str(frame);
+    }
+    if frames.len() > SHORT_TRACEBACK_FRAMES {
+        out.push_str(&format!(
+            "\n\t(... {} more, use --lua-traceback for all)",
+            frames.len() - SHORT_TRACEBACK_FRAMES,
+        ));
+    }
+    out
+}
+
+// ── Event dispatch ───────────────────────────────────────────────────
+
+/// The unit token an event was fired for: its first argument, if a string.
+/// Used to apply RegisterUnitEvent filters during dispatch.
+pub fn event_unit_arg(args: &[Value]) -> Option<String> {
+    match args.first() {
+        Some(Value::String(s)) => s.to_str().ok().map(|s| s.to_string()),
+        _ => None,
+    }
+}
@@ -1,7 +1,15 @@
-//! SimpleHTML frame state data structures.
+//! SimpleHTML frame state data structures and block layout.
+//!
+//! `SetText` stores the HTML source here; it is parsed into top-level
+//! blocks (`<h1>`-`<h3>`, `<p>`, `<img>`), styled from the per-textType
+//! styles and written to `Frame::html_blocks` for the renderer, which stacks
+//! them vertically with `stack_html_blocks`.
 
 use std::collections::HashMap;
 
+use super::SimState;
+use crate::widget::{Color, Frame, HtmlBlock, TextJustify};
+
 /// Per-textType style (h1, h2, h3, p, etc.)
 pub struct TextStyle {
     pub font: Option<String>,
@@ -38,6 +46,8 @@ pub struct SimpleHtmlData {
     pub hyperlink_format: String,
     pub hyperlinks_enabled: bool,
     pub text_styles: HashMap<String, TextStyle>,
+    /// HTML source from the last `SetText` (GetText returns it tag-stripped).
+    pub source: String,
 }
 
 impl Default for SimpleHtmlData {
@@ -46,6 +56,231 @@ impl Default for SimpleHtmlData {
             hyperlink_format: "|H%s|h%s|h".to_string(),
             hyperlinks_enabled: true,
             text_styles: HashMap::new(),
+            source: String::new(),
+        }
+    }
+}
+
+impl SimpleHtmlData {
+    /// Parse the source and apply per-textType styles, falling back to the
+    /// "p" style and then the frame's own font and color.
+    pub fn resolve_blocks(&self, frame: &Frame) -> Vec<HtmlBlock> {
+        parse_html_blocks(&self.source)
+            .into_iter()
+            .map(|block| match block {
+                SourceBlock::Text { text_type, text } => self.styled_text_block(frame, text_type, text),
+                SourceBlock::Image { src, width, height, align } => HtmlBlock::Image {
+                    path: src,
+                    width,
+                    height,
+                    justify_h: TextJustify::from_wow_str(&align),
+                },
+            })
+            .collect()
+    }
+
+    fn styled_text_block(&self, frame: &Frame, text_type: &str, text: String) -> HtmlBlock {
+        let style = self.text_styles.get(text_type).or_else(|| self.text_styles.get("p"));
+        let Some(style) = style else {
+            return HtmlBlock::Text {
+                text,
+                font: frame.font.clone(),
+                font_size: frame.font_size,
+                color: frame.text_color,
+                justify_h: TextJustify::Left,
+                shadow_color: frame.shadow_color,
+                shadow_offset: frame.shadow_offset,
+            };
+        };
+        let (font, font_size) = match &style.font {
+            Some(font) => (Some(font.clone()), style.font_size),
+            None => (frame.font.clone(), frame.font_size),
+        };
+        let (r, g, b, a) = style.text_color;
+        let (sr, sg, sb, sa) = style.shadow_color;
+        HtmlBlock::Text {
+            text,
+            font,
+            font_size,
+            color: Color::new(r, g, b, a),
+            justify_h: TextJustify::from_wow_str(&style.justify_h),
+            shadow_color: Color::new(sr, sg, sb, sa),
+            shadow_offset: style.shadow_offset,
+        }
+    }
+}
+
+/// Re-resolve a SimpleHTML frame's rendered blocks after its source or
+/// text styles change.
+pub fn refresh_html_blocks(state: &mut SimState, id: u64) {
+    let Some(data) = state.simple_htmls.get(&id) else { return };
+    let Some(frame) = state.widgets.get(id) else { return };
+    let blocks = data.resolve_blocks(frame);
+    if let Some(frame) = state.widgets.get_mut_visual(id) {
+        frame.html_blocks = blocks;
+    }
+}
+
+/// Stack blocks top-to-bottom within `width`, returning each block's
+/// (top offset, height). `text_height` measures a text block's wrapped
+/// height: (text, font, font size, width).
+pub fn stack_html_blocks(
+    blocks: &[HtmlBlock],
+    width: f32,
+    mut text_height: impl FnMut(&str, Option<&str>, f32, f32) -> f32,
+) -> Vec<(f32, f32)> {
+    let mut y = 0.0;
+    blocks
+        .iter()
+        .map(|block| {
+            let height = match block {
+                HtmlBlock::Text { text, font, font_size, .. } => {
+                    text_height(text, font.as_deref(), *font_size, width)
+                }
+                HtmlBlock::Image { width: w, height: h, .. } => image_block_size(*w, *h, width).1,
+            };
+            let top = y;
+            y += height;
+            (top, height)
+        })
+        .collect()
+}
+
+/// Total height of stacked blocks (0 when empty).
+pub fn html_content_height(offsets: &[(f32, f32)]) -> f32 {
+    offsets.last().map_or(0.0, |&(top, height)| top + height)
+}
+
+/// Size of an `<img>` block: missing width fills the frame, missing height
+/// keeps it square.
+pub fn image_block_size(width: f32, height: f32, available_width: f32) -> (f32, f32) {
+    let w = if width > 0.0 { width } else { available_width };
+    let h = if height > 0.0 { height } else { w };
+    (w, h)
+}
+
+/// Rough wrapped text height for when no font system is loaded.
+pub fn estimate_text_height(text: &str, font_size: f32, width: f32) -> f32 {
+    let char_width = font_size * 0.6;
+    let chars_per_line = if width > 0.0 { (width / char_width).floor().max(1.0) } else { f32::MAX };
+    let lines: f32 = text
+        .split('\n')
+        .map(|line| (line.chars().count() as f32 / chars_per_line).ceil().max(1.0))
+        .sum();
+    lines * font_size * 1.2
+}
+
+/// A top-level element of SimpleHTML source, before styles are applied.
+#[derive(Debug, PartialEq)]
+enum SourceBlock {
+    Text { text_type: &'static str, text: String },
+    Image { src: String, width: f32, height: f32, align: String },
+}
+
+/// Split SimpleHTML source into text blocks and images.
+///
+/// Text outside any block element becomes a "p" block, `<br/>` becomes a
+/// line break, and unknown tags (`<html>`, `<body>`, `<a>`) are dropped
+/// while their content is kept. Plain text without markup is one block.
+fn parse_html_blocks(html: &str) -> Vec<SourceBlock> {
+    let mut blocks = Vec::new();
+    if !html.contains('<') {
+        if !html.trim().is_empty() {
+            blocks.push(SourceBlock::Text { text_type: "p", text: html.to_string() });
+        }
+        return blocks;
+    }
+
+    let mut text_type = "p";
+    let mut buf = String::new();
+    let mut rest = html;
+    while let Some(open) = rest.find('<') {
+        buf.push_str(&rest[..open]);
+        let Some(close) = rest[open..].find('>') else {
+            buf.push_str(&rest[open..]);
+            rest = "";
+            break;
+        };
+        let tag = &rest[open + 1..open + close];
+        rest = &rest[open + close + 1..];
+
+        let closing = tag.starts_with('/');
+        let tag = tag.trim_start_matches('/').trim_end_matches('/');
+        let name = tag.split_whitespace().next().unwrap_or("").to_ascii_lowercase();
+        match name.as_str() {
+            "h1" | "h2" | "h3" | "p" => {
+                flush_text_block(&mut blocks, text_type, &mut buf);
+                text_type = if closing { "p" } else { block_text_type(&name) };
+            }
+            "br" => buf.push('\n'),
+            "img" if !closing => {
+                flush_text_block(&mut blocks, text_type, &mut buf);
+                blocks.push(SourceBlock::Image {
+                    src: tag_attr(tag, "src").unwrap_or_default(),
+                    width: tag_attr(tag, "width").and_then(|v| v.parse().ok()).unwrap_or(0.0),
+                    height: tag_attr(tag, "height").and_then(|v| v.parse().ok()).unwrap_or(0.0),
+                    align: tag_attr(tag, "align").unwrap_or_else(|| "LEFT".to_string()),
+                });
+            }
+            _ => {}
+        }
+    }
+    buf.push_str(rest);
+    flush_text_block(&mut blocks, text_type, &mut buf);
+    blocks
+}
+
+fn block_text_type(name: &str) -> &'static str {
+    match name {
+        "h1" => "h1",
+        "h2" => "h2",
+        "h3" => "h3",
+        _ => "p",
+    }
+}
+
+/// Push the buffered text as a block, collapsing whitespace within each
+/// `<br/>`-separated line and decoding entities.
+fn flush_text_block(blocks: &mut Vec<SourceBlock>, text_type: &'static str, buf: &mut String) {
+    let lines: Vec<String> = buf
+        .split('\n')
+        .map(|line| decode_entities(&line.split_whitespace().collect::<Vec<_>>().join(" ")))
+        .collect();
+    buf.clear();
+    let text = lines.join("\n");
+    if !text.trim().is_empty() {
+        blocks.push(SourceBlock::Text { text_type, text: text.trim().to_string() });
+    }
+}
+
+fn decode_entities(s: &str) -> String {
+    s.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&nbsp;", " ")
+        .replace("&amp;", "&")
+}
+
+/// Value of `name="..."` (or single-quoted) in a tag's attribute list.
+fn tag_attr(tag: &str, name: &str) -> Option<String> {
+    let lower = tag.to_ascii_lowercase();
+    let mut search = 0;
+    while let Some(pos) = lower[search..].find(name) {
+        let start = search + pos;
+        search = start + name.len();
+        let preceded_by_space = lower[..start].ends_with(char::is_whitespace);
+        let after = lower[search..].trim_start();
+        if !preceded_by_space || !after.starts_with('=') {
+            continue;
         }
+        let value_start = tag.len() - after.len() + 1;
+        let value = tag[value_start..].trim_start();
+        let quote = value.chars().next()?;
+        return if quote == '"' || quote == '\'' {
+            value[1..].find(quote).map(|end| value[1..1 + end].to_string())
+        } else {
+            Some(value.split_whitespace().next().unwrap_or("").to_string())
+        };
     }
+    None
 }
@@ -1,25 +1,28 @@
 //! Shared state types for the WoW Lua API.
 
 use crate::cvars::CVarStorage;
-use crate::event::{EventQueue, ScriptRegistry};
+use crate::event::recorder::{EventRecorder, RecordedEvent};
+use crate::event::{EventArg, EventQueue, ScriptRegistry};
+use crate::loader::profile::LoadProfile;
 use crate::lua_api::animation::AnimGroupState;
-use crate::lua_api::message_frame::MessageFrameData;
+use crate::lua_api::message_frame::{HyperlinkRegion, MessageFrameData};
 use crate::lua_api::simple_html::SimpleHtmlData;
 use crate::lua_api::tooltip::TooltipData;
-use crate::sound::SoundManager;
+use crate::sound::SoundBackend;
 use crate::widget::WidgetRegistry;
 use mlua::RegistryKey;
 use std::collections::{HashMap, HashSet, VecDeque};
 use std::path::PathBuf;
-use std::time::Instant;
 
 // Re-export game data types so existing `crate::lua_api::state::X` imports keep working.
 pub use super::game_data::{
-    AuraInfo, CastingState, PartyMember, TargetInfo,
+    AuraInfo, CastingState, PartyMember, TargetInfo, UnitFieldsMut, UnitStats,
     CLASS_LABELS, RACE_DATA, ROT_DAMAGE_LEVELS, XP_LEVELS,
-    build_target_info, tick_party_health,
+    build_target_info, parse_party_index, tick_party_health,
 };
 pub use super::game_data::SpellCooldownState;
+pub use super::game_data::{QuestLog, QuestLogEntry, QuestObjective};
+pub use super::game_data::{Bags, ItemStack};
 
 /// What is currently held on the cursor (drag-and-drop state).
 #[derive(Debug, Clone)]
@@ -30,15 +33,30 @@ pub enum CursorInfo {
     Spell { spell_id: u32 },
 }
 use super::game_data::{
-    default_action_bars, default_party, default_player_buffs, random_player_name,
+    default_action_bars, default_bags, default_party, default_pet, default_player_buffs,
+    default_quest_log, random_player_name,
 };
 
+/// A frame being resized with StartSizing.
+#[derive(Debug, Clone, Copy)]
+pub struct SizingFrame {
+    pub id: u64,
+    /// Direction the dragged edges grow in: -1 for the left/top edge, 1 for
+    /// the right/bottom edge, 0 when that axis isn't being sized.
+    pub dir_x: f32,
+    pub dir_y: f32,
+    /// Cursor position when sizing started.
+    pub cursor: (f32, f32),
+    /// Frame rect (screen units) when sizing started.
+    pub start: crate::LayoutRect,
+}
+
 /// A pending timer callback.
 pub struct PendingTimer {
     /// Unique timer ID.
     pub id: u64,
-    /// When this timer should fire.
-    pub fire_at: Instant,
+    /// Simulated time (`SimClock` seconds) at which this timer should fire.
+    pub fire_at: f64,
     /// Lua function to call (stored in registry).
     pub callback_key: RegistryKey,
     /// For tickers: interval between firings.
@@ -127,15 +145,54 @@ pub struct AddonInfo {
     pub load_time_secs: f64,
     /// Runtime profiler metrics (updated per frame).
     pub runtime: AddonRuntimeMetrics,
+    /// Every `## Key: Value` line of the TOC, including `X-` fields.
+    pub metadata: HashMap<String, String>,
+}
+
+impl AddonInfo {
+    /// An enabled, not yet loaded entry built from the addon's TOC file.
+    /// Falls back to the folder name as title when the TOC can't be read.
+    pub fn from_toc(folder_name: &str, toc_path: &std::path::Path) -> Self {
+        let metadata = crate::toc::TocFile::from_file(toc_path)
+            .map(|t| t.metadata)
+            .unwrap_or_default();
+        let mut info = Self {
+            folder_name: folder_name.to_string(),
+            enabled: true,
+            metadata,
+            ..Default::default()
+        };
+        info.title = info.metadata_field("Title").unwrap_or(folder_name).to_string();
+        info.notes = info.metadata_field("Notes").unwrap_or_default().to_string();
+        info.load_on_demand = info.metadata_field("LoadOnDemand") == Some("1");
+        info
+    }
+
+    /// A TOC metadata value; keys match case-insensitively like in-game.
+    pub fn metadata_field(&self, key: &str) -> Option<&str> {
+        self.metadata
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(key))
+            .map(|(_, v)| v.as_str())
+    }
 }
 
 /// Shared simulator state accessible from Lua.
 pub struct SimState {
     pub widgets: WidgetRegistry,
     pub events: EventQueue,
+    /// Log of dispatched events, when recording is enabled.
+    pub event_recorder: Option<EventRecorder>,
+    /// Load cost collector, set by `--profile`.
+    pub load_profile: Option<LoadProfile>,
+    /// Missing API lookups, set by `--api-coverage`.
+    pub api_coverage: Option<super::api_coverage::ApiCoverage>,
     pub scripts: ScriptRegistry,
     /// Console output from Lua print() calls.
     pub console_output: Vec<String>,
+    /// Live listeners for console and error output (Lua server `Tail` clients).
+    /// Dropped receivers are pruned on the next broadcast.
+    pub console_subscribers: Vec<std::sync::mpsc::Sender<String>>,
     /// Pending timer callbacks.
     pub timers: VecDeque<PendingTimer>,
     /// Currently focused frame ID (for keyboard input).
@@ -150,6 +207,9 @@ pub struct SimState {
     pub simple_htmls: HashMap<u64, SimpleHtmlData>,
     /// MessageFrame state (keyed by frame ID).
     pub message_frames: HashMap<u64, MessageFrameData>,
+    /// Hyperlinks last drawn by MessageFrames and SimpleHTML frames (keyed
+    /// by frame ID), stored from the quad batch for hyperlink hit testing.
+    pub hyperlink_regions: HashMap<u64, Vec<HyperlinkRegion>>,
     /// Frame IDs with active OnUpdate script handlers.
     pub on_update_frames: HashSet<u64>,
     /// Cached subset of `on_update_frames` whose ancestors are all visible.
@@ -171,28 +231,55 @@ pub struct SimState {
     /// Screen dimensions in UI coordinates.
     pub screen_width: f32,
     pub screen_height: f32,
+    /// UIParent scale from the `useUiScale`/`uiScale` CVars.
+    pub ui_scale: f32,
     /// Action bar slots: slot (1-120) → spell ID.
     pub action_bars: HashMap<u32, u32>,
     /// Addon base paths for runtime on-demand loading (Blizzard UI + AddOns directories).
     pub addon_base_paths: Vec<PathBuf>,
+    /// Slash command index: uppercase token ("/TT") → `SlashCmdList` key.
+    /// Rebuilt from `SLASH_<KEY><n>` globals when a lookup misses.
+    pub slash_commands: HashMap<String, String>,
     /// Current mouse position in UI coordinates (for ANCHOR_CURSOR tooltip positioning).
     pub mouse_position: Option<(f32, f32)>,
     /// Currently hovered frame ID (for IsMouseMotionFocus / GetMouseFocus).
     pub hovered_frame: Option<u64>,
+    /// Frame and link of the hyperlink under the cursor (OnHyperlinkEnter/Leave).
+    pub hovered_hyperlink: Option<(u64, String)>,
+    /// Frame that received the last headless `send_mouse_down` (OnClick target).
+    pub mouse_down_frame: Option<u64>,
+    /// Frame following the cursor after StartMoving, with the cursor's offset
+    /// from its top-left corner in screen units.
+    pub moving_frame: Option<(u64, f32, f32)>,
+    /// Frame being resized after StartSizing.
+    pub sizing_frame: Option<SizingFrame>,
     /// Simulated party members (empty = not in group).
     pub party_members: Vec<PartyMember>,
     /// Current target (None = no target).
     pub current_target: Option<TargetInfo>,
     /// Current focus target (None = no focus).
     pub current_focus: Option<TargetInfo>,
+    /// The player's pet (None = no pet).
+    pub pet: Option<TargetInfo>,
     /// Audio playback manager (None when no audio device or WOW_SIM_NO_SOUND=1).
-    pub sound_manager: Option<SoundManager>,
+    pub sound_manager: Option<Box<dyn SoundBackend>>,
     /// Player character name (randomly chosen on startup).
     pub player_name: String,
+    /// Simulated player location: UiMapID and normalized x/y on that map.
+    /// Set with the sim-only `SimSetPlayerLocation(mapID, x, y)`.
+    pub player_location: (i32, f64, f64),
+    /// Player's realm as shown by `GetRealmName` (from the WTF config when set).
+    pub realm_name: String,
     /// Player current health.
     pub player_health: i32,
     /// Player maximum health.
     pub player_health_max: i32,
+    /// Player current primary power (mana).
+    pub player_power: i32,
+    /// Player maximum primary power.
+    pub player_power_max: i32,
+    /// Player level.
+    pub player_level: i32,
     /// Player class (1-based index matching CLASS_DATA in unit_api).
     pub player_class_index: i32,
     /// Player race (0-based index into RACE_DATA).
@@ -201,10 +288,14 @@ pub struct SimState {
     pub rot_damage_level: usize,
     /// Player buffs/debuffs (disabled by WOW_SIM_NO_BUFFS=1).
     pub player_buffs: Vec<AuraInfo>,
+    /// Quest log behind C_QuestLog and the quest leaderboard globals.
+    pub quest_log: QuestLog,
+    /// Bag contents and equipped items behind C_Container and GetInventoryItem*.
+    pub bags: Bags,
     /// Current framerate (FPS), updated by the app's FPS counter.
     pub fps: f32,
-    /// Instant at which the UI started (used by GetTime and message timestamps).
-    pub start_time: Instant,
+    /// Simulated clock (used by GetTime, timers and message timestamps).
+    pub clock: super::SimClock,
     /// Active spell cast (None = not casting).
     pub casting: Option<CastingState>,
     /// Counter for generating unique cast IDs.
@@ -221,6 +312,12 @@ pub struct SimState {
     /// Index of the addon currently being loaded (into `addons` vec).
     /// Set by the loader, read by CreateFrame to assign `owner_addon`.
     pub loading_addon_index: Option<u16>,
+    /// Script errors reported while an addon loads, collected by the loader
+    /// into its `LoadResult` warnings. `None` outside of addon loading.
+    pub script_errors: Option<Vec<String>>,
+    /// Unknown CreateFrame templates already warned about, so each is
+    /// reported once.
+    pub reported_missing_templates: HashSet<String>,
     /// Application-level frame metrics (total frame time for profiler ratios).
     pub app_frame_metrics: AppFrameMetrics,
     /// Talent tree interactive state (ranks, selections, currency mappings).
@@ -232,15 +329,20 @@ impl Default for SimState {
         Self {
             widgets: WidgetRegistry::default(),
             events: EventQueue::default(),
+            event_recorder: None,
+            load_profile: None,
+            api_coverage: None,
             scripts: ScriptRegistry::default(),
             console_output: Vec::new(),
+            console_subscribers: Vec::new(),
             timers: VecDeque::new(),
             focused_frame_id: None,
             addons: Vec::new(),
-            cvars: CVarStorage::new(),
+            cvars: CVarStorage::in_memory(),
             tooltips: HashMap::new(),
             simple_htmls: HashMap::new(),
             message_frames: HashMap::new(),
+            hyperlink_regions: HashMap::new(),
             on_update_frames: HashSet::new(),
             visible_on_update_cache: None,
             strata_buckets: None,
@@ -249,23 +351,38 @@ impl Default for SimState {
             next_anim_group_id: 1,
             screen_width: 1600.0,
             screen_height: 1200.0,
+            ui_scale: 1.0,
             action_bars: default_action_bars(),
             addon_base_paths: Vec::new(),
+            slash_commands: HashMap::new(),
             mouse_position: None,
             hovered_frame: None,
+            hovered_hyperlink: None,
+            mouse_down_frame: None,
+            moving_frame: None,
+            sizing_frame: None,
             party_members: default_party(),
             current_target: None,
             current_focus: None,
+            pet: Some(default_pet()),
             sound_manager: None,
             player_name: random_player_name(),
+            // Stormwind City, Trade District.
+            player_location: (84, 0.6115, 0.7076),
+            realm_name: "SimRealm".to_string(),
             player_health: 100_000,
             player_health_max: 100_000,
+            player_power: 50_000,
+            player_power_max: 100_000,
+            player_level: 80,
             player_class_index: 2,  // Paladin
             player_race_index: 0,   // Human
             rot_damage_level: 0,    // Off
             player_buffs: default_player_buffs(),
+            quest_log: default_quest_log(),
+            bags: default_bags(),
             fps: 0.0,
-            start_time: Instant::now(),
+            clock: super::SimClock::new(),
             casting: None,
             next_cast_id: 1,
             gcd: None,
@@ -273,6 +390,8 @@ impl Default for SimState {
             action_ui_buttons: Vec::new(),
             cursor_item: None,
             loading_addon_index: None,
+            script_errors: None,
+            reported_missing_templates: HashSet::new(),
             app_frame_metrics: AppFrameMetrics::default(),
             talents: super::talent_state::TalentState::new(),
         }
@@ -280,6 +399,74 @@ impl Default for SimState {
 }
 
 impl SimState {
+    /// Append a line to the console buffer and stream it to subscribers.
+    pub fn push_console(&mut self, line: String) {
+        self.broadcast_console(&line);
+        self.console_output.push(line);
+    }
+
+    /// Resize the screen. WorldFrame covers it at scale 1; UIParent keeps the
+    /// UI scale and is sized so that it still fills the screen.
+    pub fn set_screen_size(&mut self, width: f32, height: f32) {
+        self.screen_width = width;
+        self.screen_height = height;
+        self.apply_screen_frames();
+    }
+
+    /// Re-read `useUiScale`/`uiScale` and rescale UIParent. Returns whether
+    /// the scale changed.
+    pub fn apply_ui_scale_cvars(&mut self) -> bool {
+        let scale = if self.cvars.get_bool("useUiScale") {
+            self.cvars
+                .get("uiScale")
+                .and_then(|v| v.parse::<f32>().ok())
+                .filter(|s| *s > 0.0)
+                .unwrap_or(1.0)
+        } else {
+            1.0
+        };
+        if scale == self.ui_scale {
+            return false;
+        }
+        self.ui_scale = scale;
+        self.apply_screen_frames();
+        true
+    }
+
+    /// Size UIParent/WorldFrame for the current screen and UI scale.
+    /// Invalidates all cached layout rects and strata buckets.
+    fn apply_screen_frames(&mut self) {
+        let (w, h) = (self.screen_width, self.screen_height);
+        self.strata_buckets = None;
+        self.widgets.clear_all_layout_rects();
+        for (name, scale) in [("UIParent", self.ui_scale), ("WorldFrame", 1.0)] {
+            let Some(id) = self.widgets.get_id_by_name(name) else { continue };
+            if let Some(f) = self.widgets.get_mut_visual(id) {
+                f.scale = scale;
+                f.width = w / scale;
+                f.height = h / scale;
+            }
+            self.widgets.propagate_effective_scale(id, 1.0);
+        }
+    }
+
+    /// Log a dispatched event if recording is enabled.
+    pub fn record_event(&mut self, event: &str, args: &[mlua::Value]) {
+        let time = self.clock.now();
+        if let Some(recorder) = &mut self.event_recorder {
+            recorder.record(RecordedEvent {
+                time,
+                event: event.to_string(),
+                args: args.iter().map(EventArg::from_lua).collect(),
+            });
+        }
+    }
+
+    /// Stream a line to console subscribers only, dropping disconnected ones.
+    pub fn broadcast_console(&mut self, line: &str) {
+        self.console_subscribers.retain(|tx| tx.send(line.to_string()).is_ok());
+    }
+
     /// Return the per-strata buckets, building lazily if needed.
     pub fn get_strata_buckets(&mut self) -> Option<&Vec<Vec<u64>>> {
         if self.strata_buckets.is_none() {
@@ -331,12 +518,12 @@ impl SimState {
             };
             buckets[strata.as_index()].push(id);
         }
+        // Keys walk the ancestor chain (render_frame_level), so compute each once.
         for bucket in &mut buckets {
-            bucket.sort_by(|&a, &b| {
-                match (self.widgets.get(a), self.widgets.get(b)) {
-                    (Some(fa), Some(fb)) => intra_strata_sort_key(fa, a, &self.widgets).cmp(&intra_strata_sort_key(fb, b, &self.widgets)),
-                    _ => a.cmp(&b),
-                }
+            bucket.sort_by_cached_key(|&id| {
+                self.widgets.get(id)
+                    .map(|f| intra_strata_sort_key(f, id, &self.widgets))
+                    .unwrap_or_default()
             });
         }
         buckets
@@ -412,6 +599,7 @@ impl SimState {
     /// Computes missing rects using the same eager path as invalidate_layout.
     /// Called before quad rebuilds (acts as the "next frame" layout resolution).
     pub fn ensure_layout_rects(&mut self) {
+        self.bind_deferred_anchors();
         let pending = self.widgets.drain_pending_layout();
         if !pending.is_empty() {
             let sw = self.screen_width;
@@ -427,6 +615,34 @@ impl SimState {
         self.widgets.drain_rect_dirty();
     }
 
+    /// Bind anchors whose relativeKey/relativeTo target didn't exist when they
+    /// were set (e.g. `$parent.Header` declared after the anchored sibling),
+    /// then recompute the affected frames so they stop using the parent rect.
+    pub fn bind_deferred_anchors(&mut self) {
+        if !self.widgets.has_deferred_anchors() {
+            return;
+        }
+        let bound = self.widgets.bind_deferred_anchors();
+        self.relayout_bound_anchors(bound);
+    }
+
+    /// Bind deferred anchors whose path ends in `key`, after a frame was
+    /// assigned to that key. Cheaper than a full rescan on every `__newindex`.
+    pub fn bind_deferred_anchors_for_key(&mut self, key: &str) {
+        if !self.widgets.has_deferred_anchors() {
+            return;
+        }
+        let bound = self.widgets.bind_deferred_anchors_for_key(key);
+        self.relayout_bound_anchors(bound);
+    }
+
+    fn relayout_bound_anchors(&mut self, ids: Vec<u64>) {
+        for id in ids {
+            self.widgets.mark_rect_dirty(id);
+            self.invalidate_layout_with_dependents(id);
+        }
+    }
+
     /// Force layout resolution for a single frame, clearing its rect_dirty flag.
     /// Called by GetSize/GetWidth/GetHeight/IsRectValid to match WoW behavior
     /// where those methods force immediate rect resolution within the same frame.
@@ -464,6 +680,7 @@ impl SimState {
         if !visible {
             // Hide: remove subtree from buckets BEFORE propagating alpha to 0.
             self.remove_subtree_from_buckets(id);
+            self.clear_subtree_hyperlinks(id);
         }
         self.widgets.propagate_effective_alpha(id, parent_eff);
         if visible {
@@ -541,17 +758,17 @@ impl SimState {
     ///
     /// Finds the maximum frame_level among sibling frames (same parent, same
     /// strata) and sets this frame's level to max + 1. Propagates the new level
-    /// to all descendants.
-    pub fn raise_frame(&mut self, id: u64) {
+    /// to all descendants. Returns whether the level changed.
+    pub fn raise_frame(&mut self, id: u64) -> bool {
         let (parent_id, strata) = match self.widgets.get(id) {
             Some(f) => (f.parent_id, f.frame_strata),
-            None => return,
+            None => return false,
         };
         // Find max level among siblings in the same strata.
         let max_sibling_level = self.max_sibling_level(id, parent_id, strata);
         let current_level = self.widgets.get(id).map(|f| f.frame_level).unwrap_or(0);
         if current_level > max_sibling_level {
-            return; // Already on top
+            return false; // Already on top
         }
         let new_level = max_sibling_level + 1;
         if let Some(f) = self.widgets.get_mut_visual(id) {
@@ -562,10 +779,55 @@ impl SimState {
         );
         // Invalidate strata buckets since level changed (affects sort order).
         self.strata_buckets = None;
+        true
+    }
+
+    /// Lower a frame below all siblings in the same strata (never below 0).
+    pub fn lower_frame(&mut self, id: u64) {
+        let (parent_id, strata, current_level) = match self.widgets.get(id) {
+            Some(f) => (f.parent_id, f.frame_strata, f.frame_level),
+            None => return,
+        };
+        let min_sibling_level = self
+            .sibling_levels(id, parent_id, strata)
+            .into_iter()
+            .min()
+            .unwrap_or(current_level);
+        let new_level = (min_sibling_level - 1).max(0);
+        if current_level <= new_level {
+            return; // Already at the bottom
+        }
+        if let Some(f) = self.widgets.get_mut_visual(id) {
+            f.frame_level = new_level;
+        }
+        crate::lua_api::frame::propagate_strata_level_pub(
+            &mut self.widgets, id,
+        );
+        self.strata_buckets = None;
+    }
+
+    /// Raise the nearest toplevel frame at or above `id` (WoW raises a
+    /// toplevel frame when it or any of its descendants is clicked).
+    /// Returns whether a frame level changed.
+    pub fn raise_toplevel_ancestor(&mut self, id: u64) -> bool {
+        let mut current = Some(id);
+        while let Some(fid) = current {
+            let Some(f) = self.widgets.get(fid) else { return false };
+            if f.toplevel {
+                return self.raise_frame(fid);
+            }
+            current = f.parent_id;
+        }
+        false
     }
 
     /// Find the maximum frame_level among siblings of `id` in the given strata.
     fn max_sibling_level(&self, id: u64, parent_id: Option<u64>, strata: crate::widget::FrameStrata) -> i32 {
+        self.sibling_levels(id, parent_id, strata).into_iter().max().unwrap_or(0)
+    }
+
+    /// Frame levels of the siblings of `id` in the given strata.
+    fn sibling_levels(&self, id: u64, parent_id: Option<u64>, strata: crate::widget::FrameStrata) -> Vec<i32> {
         let sibling_ids: Vec<u64> = if let Some(pid) = parent_id {
             self.widgets.get(pid)
                 .map(|p| p.children.clone())
@@ -581,8 +843,7 @@ impl SimState {
             .filter_map(|&sid| self.widgets.get(sid))
             .filter(|f| f.frame_strata == strata)
             .map(|f| f.frame_level)
-            .max()
-            .unwrap_or(0)
+            .collect()
     }
 
     fn update_on_update_cache(&mut self, id: u64, visible: bool) {
@@ -625,3 +886,266 @@ impl SimState {
 
 }
 
+
+/// Unit model: the player, target, focus, pet and party1-4 as one lookup.
+impl SimState {
+    /// Current stats of `unit`, or None when the unit does not exist
+    /// (no target/focus/pet, empty party slot, unknown unit token).
+    pub fn unit_stats(&self, unit: &str) -> Option<UnitStats> {
+        match unit {
+            "player" => Some(UnitStats {
+                class_index: self.player_class_index,
+                level: self.player_level,
+                health: self.player_health,
+                health_max: self.player_health_max,
+                power: self.player_power,
+                power_max: self.player_power_max,
+                power_type: 0,
+                power_type_name: "MANA",
+            }),
+            "target" => self.current_target.as_ref().map(TargetInfo::stats),
+            "focus" => self.current_focus.as_ref().map(TargetInfo::stats),
+            "pet" => self.pet.as_ref().map(TargetInfo::stats),
+            u => self.party_member(u).map(PartyMember::stats),
+        }
+    }
+
+    /// Display name of `unit`, or None when the unit does not exist.
+    pub fn unit_name(&self, unit: &str) -> Option<&str> {
+        match unit {
+            "player" => Some(&self.player_name),
+            "target" => self.current_target.as_ref().map(|t| t.name.as_str()),
+            "focus" => self.current_focus.as_ref().map(|t| t.name.as_str()),
+            "pet" => self.pet.as_ref().map(|t| t.name.as_str()),
+            u => self.party_member(u).map(|m| m.name.as_str()),
+        }
+    }
+
+    /// Mutable fields of `unit`, or None when the unit does not exist.
+    pub fn unit_fields_mut(&mut self, unit: &str) -> Option<UnitFieldsMut<'_>> {
+        match unit {
+            "player" => Some(UnitFieldsMut {
+                name: &mut self.player_name,
+                class_index: &mut self.player_class_index,
+                level: &mut self.player_level,
+                health: &mut self.player_health,
+                health_max: &mut self.player_health_max,
+                power: &mut self.player_power,
+                power_max: &mut self.player_power_max,
+            }),
+            "target" => self.current_target.as_mut().map(TargetInfo::fields_mut),
+            "focus" => self.current_focus.as_mut().map(TargetInfo::fields_mut),
+            "pet" => self.pet.as_mut().map(TargetInfo::fields_mut),
+            u => {
+                let idx = parse_party_index(u)?;
+                self.party_members.get_mut(idx).map(PartyMember::fields_mut)
+            }
+        }
+    }
+
+    fn party_member(&self, unit: &str) -> Option<&PartyMember> {
+        let idx = parse_party_index(unit)?;
+        self.party_members.get(idx)
+    }
+}
+
+impl SimState {
+    /// SetHyperlinksEnabled state of a MessageFrame or SimpleHTML frame; `None`
+    /// for frames that don't render hyperlinks.
+    pub fn hyperlinks_enabled(&self, id: u64) -> Option<bool> {
+        self.message_frames
+            .get(&id)
+            .map(|d| d.hyperlinks_enabled)
+            .or_else(|| self.simple_htmls.get(&id).map(|d| d.hyperlinks_enabled))
+    }
+
+    /// Store the hyperlinks a quad batch drew, replacing each drawn frame's
+    /// previous regions.
+    pub fn set_hyperlink_regions(&mut self, drawn: Vec<(u64, Vec<HyperlinkRegion>)>) {
+        for (id, regions) in drawn {
+            if regions.is_empty() {
+                self.hyperlink_regions.remove(&id);
+            } else {
+                self.hyperlink_regions.insert(id, regions);
+            }
+        }
+    }
+
+    /// The hyperlink last drawn at (x, y) inside frame `id`, if any.
+    pub fn hyperlink_at(&self, id: u64, x: f32, y: f32) -> Option<&HyperlinkRegion> {
+        self.hyperlink_regions.get(&id)?.iter().find(|region| {
+            let r = region.rect;
+            x >= r.x && x <= r.x + r.width && y >= r.y && y <= r.y + r.height
+        })
+    }
+
+    /// Forget the hyperlinks drawn by `root_id` and its descendants, whose
+    /// text is no longer on screen.
+    fn clear_subtree_hyperlinks(&mut self, root_id: u64) {
+        if self.hyperlink_regions.is_empty() {
+            return;
+        }
+        let mut queue = vec![root_id];
+        while let Some(fid) = queue.pop() {
+            self.hyperlink_regions.remove(&fid);
+            if let Some(f) = self.widgets.get(fid) {
+                queue.extend(f.children.iter().copied());
+            }
+        }
+    }
+
+    /// Move the virtual cursor (UI units, top-left origin), dragging the frame
+    /// started with StartMoving along with it and resizing the one started
+    /// with StartSizing. Returns the resized frame and its new size, for the
+    /// caller to fire OnSizeChanged.
+    pub fn set_mouse_position(&mut self, x: f32, y: f32) -> Option<(u64, f32, f32)> {
+        self.mouse_position = Some((x, y));
+        if let Some((id, grab_x, grab_y)) = self.moving_frame {
+            self.move_frame_to(id, x - grab_x, y - grab_y);
+        }
+        self.resize_to_cursor(x, y)
+    }
+
+    /// Start dragging `id` with the cursor. The frame keeps its current spot
+    /// but is re-anchored by its top-left corner so later moves only shift
+    /// the offset. Returns false for frames that aren't movable.
+    pub fn start_moving(&mut self, id: u64) -> bool {
+        if !self.widgets.get(id).is_some_and(|f| f.movable) {
+            return false;
+        }
+        self.ensure_layout_rects();
+        self.resolve_rect_if_dirty(id);
+        let Some(rect) = self.widgets.get(id).and_then(|f| f.layout_rect) else {
+            return false;
+        };
+        let (cx, cy) = self
+            .mouse_position
+            .unwrap_or((self.screen_width / 2.0, self.screen_height / 2.0));
+        if let Some((prev, _, _)) = self.moving_frame
+            && let Some(f) = self.widgets.get_mut_visual(prev) {
+                f.is_moving = false;
+            }
+        if let Some(f) = self.widgets.get_mut_visual(id) {
+            f.is_moving = true;
+        }
+        self.moving_frame = Some((id, cx - rect.x, cy - rect.y));
+        self.move_frame_to(id, rect.x, rect.y);
+        true
+    }
+
+    /// Stop dragging `id`; its last anchor offset is kept.
+    pub fn stop_moving(&mut self, id: u64) {
+        if let Some(f) = self.widgets.get_mut_visual(id) {
+            f.is_moving = false;
+        }
+        if self.moving_frame.is_some_and(|(moving, _, _)| moving == id) {
+            self.moving_frame = None;
+        }
+    }
+
+    /// Start resizing `id` by its `point` edge or corner. Like start_moving,
+    /// the frame is re-anchored by its top-left corner. Returns false for
+    /// frames that aren't resizable.
+    pub fn start_sizing(&mut self, id: u64, point: crate::widget::AnchorPoint) -> bool {
+        use crate::widget::AnchorPoint as P;
+        if !self.widgets.get(id).is_some_and(|f| f.resizable) {
+            return false;
+        }
+        self.ensure_layout_rects();
+        self.resolve_rect_if_dirty(id);
+        let Some(rect) = self.widgets.get(id).and_then(|f| f.layout_rect) else {
+            return false;
+        };
+        let dir_x = match point {
+            P::Left | P::TopLeft | P::BottomLeft => -1.0,
+            P::Right | P::TopRight | P::BottomRight => 1.0,
+            _ => 0.0,
+        };
+        let dir_y = match point {
+            P::Top | P::TopLeft | P::TopRight => -1.0,
+            P::Bottom | P::BottomLeft | P::BottomRight => 1.0,
+            _ => 0.0,
+        };
+        let cursor = self
+            .mouse_position
+            .unwrap_or((self.screen_width / 2.0, self.screen_height / 2.0));
+        self.sizing_frame = Some(SizingFrame { id, dir_x, dir_y, cursor, start: rect });
+        self.move_frame_to(id, rect.x, rect.y);
+        true
+    }
+
+    /// Stop resizing `id`; it keeps its last size.
+    pub fn stop_sizing(&mut self, id: u64) {
+        if self.sizing_frame.is_some_and(|s| s.id == id) {
+            self.sizing_frame = None;
+        }
+    }
+
+    /// Size the frame being resized to follow the cursor, clamped to its
+    /// resize bounds. The edges opposite the dragged ones stay put.
+    fn resize_to_cursor(&mut self, x: f32, y: f32) -> Option<(u64, f32, f32)> {
+        let sizing = self.sizing_frame?;
+        let frame = self.widgets.get(sizing.id)?;
+        let scale = frame.effective_scale.max(f32::EPSILON);
+        let (min_w, min_h, max_w, max_h) = frame.resize_bounds;
+        let start = sizing.start;
+        let width = clamp_resize(
+            (start.width + sizing.dir_x * (x - sizing.cursor.0)) / scale, min_w, max_w,
+        );
+        let height = clamp_resize(
+            (start.height + sizing.dir_y * (y - sizing.cursor.1)) / scale, min_h, max_h,
+        );
+        if (width, height) == (frame.width, frame.height) {
+            return None;
+        }
+        let left = if sizing.dir_x < 0.0 { start.x + start.width - width * scale } else { start.x };
+        let top = if sizing.dir_y < 0.0 { start.y + start.height - height * scale } else { start.y };
+        if let Some(f) = self.widgets.get_mut_visual(sizing.id) {
+            f.set_size(width, height);
+        }
+        self.move_frame_to(sizing.id, left, top);
+        Some((sizing.id, width, height))
+    }
+
+    /// Anchor `id` so its top-left corner lands at (x, y) in screen units,
+    /// honouring SetClampedToScreen. A frame sized by several anchors keeps
+    /// its laid-out size as an explicit one, since a lone TOPLEFT anchor
+    /// cannot stretch it.
+    fn move_frame_to(&mut self, id: u64, x: f32, y: f32) {
+        let Some(frame) = self.widgets.get(id) else { return };
+        let Some(rect) = frame.layout_rect else { return };
+        let mut target = crate::LayoutRect { x, y, ..rect };
+        if frame.clamped_to_screen {
+            crate::iced_app::layout::clamp_rect_to_screen(
+                &mut target, self.screen_width, self.screen_height,
+            );
+        }
+        let scale = frame.effective_scale.max(f32::EPSILON);
+        let anchor_sized = frame.anchors.len() > 1;
+        let (px, py) = frame
+            .parent_id
+            .and_then(|p| self.widgets.get(p))
+            .and_then(|p| p.layout_rect)
+            .map(|p| (p.x, p.y))
+            .unwrap_or((0.0, 0.0));
+        let (x_ofs, y_ofs) = ((target.x - px) / scale, -(target.y - py) / scale);
+
+        self.widgets.remove_all_anchor_dependents_for(id);
+        if let Some(f) = self.widgets.get_mut_visual(id) {
+            if anchor_sized {
+                f.set_size(rect.width / scale, rect.height / scale);
+            }
+            f.clear_all_points();
+            let top_left = crate::widget::AnchorPoint::TopLeft;
+            f.set_point(top_left, None, top_left, x_ofs, y_ofs);
+        }
+        self.widgets.mark_rect_dirty(id);
+        self.invalidate_layout_with_dependents(id);
+    }
+}
+
+/// Clamp a StartSizing width or height to `min` and, when nonzero, `max`.
+fn clamp_resize(size: f32, min: f32, max: f32) -> f32 {
+    let size = size.max(min).max(0.0);
+    if max > 0.0 { size.min(max) } else { size }
+}
@@ -14,15 +14,40 @@ use std::thread;
 pub enum Request {
     /// Execute Lua code
     Exec { code: String },
+    /// Evaluate Lua code as an expression and return its values
+    Eval { code: String },
     /// Ping to check if server is alive
     Ping,
+    /// Stream subsequent print and error output as `Line` responses until disconnect
+    Tail,
     /// Dump the frame tree
     DumpTree {
         /// Filter by name (substring match)
         filter: Option<String>,
         /// Only show visible frames
         visible_only: bool,
+        /// Return the tree as JSON instead of text
+        #[serde(default)]
+        json: bool,
+        /// Only list frames matching this query (see `dump::FrameQuery`)
+        #[serde(default)]
+        query: Option<String>,
     },
+    /// Rects of all named frames, without rendering
+    Layout {
+        /// Return the rects as JSON instead of text
+        #[serde(default)]
+        json: bool,
+    },
+    /// Re-run an addon's TOC against the live environment
+    ReloadAddon {
+        /// Addon folder name
+        name: String,
+    },
+    /// Write all addons' SavedVariables back to storage
+    SaveVars,
+    /// Ranked report of missing APIs recorded with --api-coverage
+    ApiCoverage,
     /// Render a screenshot to a file
     Screenshot {
         /// Output file path
@@ -35,6 +60,9 @@ pub enum Request {
         filter: Option<String>,
         /// Crop the output image to WxH+X+Y (e.g., 700x150+400+650)
         crop: Option<String>,
+        /// Render only frames at or above this strata (e.g. DIALOG)
+        #[serde(default)]
+        min_strata: Option<String>,
     },
 }
 
@@ -49,6 +77,129 @@ pub enum Response {
     Pong,
     /// Frame tree dump
     Tree(String),
+    /// Return values of an `Eval` request
+    Values(Vec<EvalValue>),
+    /// One line of console output streamed to a `Tail` client
+    Line(String),
+}
+
+/// Maximum table nesting serialized by `Eval`; deeper tables become `Truncated`.
+pub const EVAL_MAX_DEPTH: usize = 4;
+/// Maximum entries serialized per table by `Eval`.
+pub const EVAL_MAX_ENTRIES: usize = 100;
+
+/// A Lua value returned by an `Eval` request.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum EvalValue {
+    Nil,
+    Boolean(bool),
+    Number(f64),
+    String(String),
+    /// Table entries in `pairs` order. `truncated` is set when entries past
+    /// `EVAL_MAX_ENTRIES` were dropped.
+    Table {
+        entries: Vec<(EvalValue, EvalValue)>,
+        truncated: bool,
+    },
+    /// A table nested deeper than `EVAL_MAX_DEPTH`.
+    Truncated,
+    /// A value with no data representation (function, userdata, thread), by type name.
+    Other(String),
+}
+
+impl EvalValue {
+    /// Convert a Lua value, capping table depth and size.
+    pub fn from_lua(value: &mlua::Value) -> Self {
+        Self::from_lua_at(value, 0)
+    }
+
+    fn from_lua_at(value: &mlua::Value, depth: usize) -> Self {
+        match value {
+            mlua::Value::Nil => Self::Nil,
+            mlua::Value::Boolean(b) => Self::Boolean(*b),
+            mlua::Value::Integer(n) => Self::Number(*n as f64),
+            mlua::Value::Number(n) => Self::Number(*n),
+            mlua::Value::String(s) => Self::String(s.to_string_lossy()),
+            mlua::Value::Table(_) if depth >= EVAL_MAX_DEPTH => Self::Truncated,
+            mlua::Value::Table(t) => {
+                let mut entries = Vec::new();
+                let mut truncated = false;
+                for (k, v) in t.clone().pairs::<mlua::Value, mlua::Value>().flatten() {
+                    if entries.len() >= EVAL_MAX_ENTRIES {
+                        truncated = true;
+                        break;
+                    }
+                    entries.push((Self::from_lua_at(&k, depth + 1), Self::from_lua_at(&v, depth + 1)));
+                }
+                Self::Table { entries, truncated }
+            }
+            other => Self::Other(other.type_name().to_string()),
+        }
+    }
+
+    /// JSON form for display: tables with keys 1..n become arrays, other
+    /// tables objects keyed by the stringified key. Truncation shows up as a
+    /// `"<truncated>"` element or key.
+    pub fn to_json(&self) -> serde_json::Value {
+        use serde_json::Value as Json;
+        match self {
+            Self::Nil => Json::Null,
+            Self::Boolean(b) => Json::Bool(*b),
+            Self::Number(n) if n.fract() == 0.0 && n.abs() < 1e15 => Json::from(*n as i64),
+            Self::Number(n) => serde_json::Number::from_f64(*n).map_or(Json::Null, Json::Number),
+            Self::String(s) => Json::String(s.clone()),
+            Self::Truncated => Json::String("<truncated>".into()),
+            Self::Other(ty) => Json::String(format!("<{}>", ty)),
+            Self::Table { entries, truncated } => {
+                let mut sorted: Vec<_> = entries.iter().collect();
+                let index = |k: &Self| match k {
+                    Self::Number(n) if n.fract() == 0.0 && *n >= 1.0 => Some(*n as usize),
+                    _ => None,
+                };
+                let n = sorted.len();
+                if n > 0 && sorted.iter().all(|(k, _)| index(k).is_some_and(|i| i <= n)) {
+                    sorted.sort_by_key(|(k, _)| index(k));
+                    let mut items: Vec<Json> = sorted.iter().map(|(_, v)| v.to_json()).collect();
+                    if *truncated {
+                        items.push(Json::String("<truncated>".into()));
+                    }
+                    return Json::Array(items);
+                }
+                sorted.sort_by_key(|(k, _)| k.key_string());
+                let mut map = serde_json::Map::new();
+                for (k, v) in sorted {
+                    map.insert(k.key_string(), v.to_json());
+                }
+                if *truncated {
+                    map.insert("<truncated>".into(), Json::Bool(true));
+                }
+                Json::Object(map)
+            }
+        }
+    }
+
+    fn key_string(&self) -> String {
+        match self {
+            Self::String(s) => s.clone(),
+            other => other.to_json().to_string(),
+        }
+    }
+}
+
+/// Evaluate `code` as an expression (`return <code>`), falling back to running
+/// it as a chunk so statements ending in `return ...` also work.
+pub fn eval_chunk(lua: &mlua::Lua, code: &str) -> Response {
+    let func = match lua.load(format!("return {}", code)).into_function() {
+        Ok(f) => f,
+        Err(_) => match lua.load(code).into_function() {
+            Ok(f) => f,
+            Err(e) => return Response::Error(e.to_string()),
+        },
+    };
+    match func.call::<mlua::MultiValue>(()) {
+        Ok(values) => Response::Values(values.iter().map(EvalValue::from_lua).collect()),
+        Err(e) => Response::Error(e.to_string()),
+    }
 }
 
 /// Command sent to the app from the Lua server.
@@ -57,9 +208,33 @@ pub enum LuaCommand {
         code: String,
         respond: mpsc::Sender<Response>,
     },
+    Eval {
+        code: String,
+        respond: mpsc::Sender<Response>,
+    },
+    /// Register a console subscriber (see `SimState::console_subscribers`).
+    Tail {
+        subscriber: mpsc::Sender<String>,
+    },
     DumpTree {
         filter: Option<String>,
         visible_only: bool,
+        json: bool,
+        query: Option<String>,
+        respond: mpsc::Sender<Response>,
+    },
+    Layout {
+        json: bool,
+        respond: mpsc::Sender<Response>,
+    },
+    ReloadAddon {
+        name: String,
+        respond: mpsc::Sender<Response>,
+    },
+    SaveVars {
+        respond: mpsc::Sender<Response>,
+    },
+    ApiCoverage {
         respond: mpsc::Sender<Response>,
     },
     Screenshot {
@@ -68,6 +243,7 @@ pub enum LuaCommand {
         height: u32,
         filter: Option<String>,
         crop: Option<String>,
+        min_strata: Option<String>,
         respond: mpsc::Sender<Response>,
     },
 }
@@ -145,9 +321,14 @@ fn run_server(cmd_tx: mpsc::Sender<LuaCommand>, path: PathBuf) {
     for stream in listener.incoming() {
         match stream {
             Ok(stream) => {
-                if let Err(e) = handle_connection(stream, &cmd_tx) {
-                    eprintln!("[wow-sim] Connection error: {}", e);
-                }
+                // One thread per connection so a streaming `Tail` client
+                // doesn't block other requests.
+                let cmd_tx = cmd_tx.clone();
+                thread::spawn(move || {
+                    if let Err(e) = handle_connection(stream, &cmd_tx) {
+                        eprintln!("[wow-sim] Connection error: {}", e);
+                    }
+                });
             }
             Err(e) => {
                 eprintln!("[wow-sim] Accept error: {}", e);
@@ -194,14 +375,24 @@ fn handle_connection(
 
         let response = match request {
             Request::Ping => Response::Pong,
+            Request::Tail => return stream_console(stream, cmd_tx),
             Request::Exec { code } => {
                 send_command(cmd_tx, |respond| LuaCommand::Exec { code, respond })
             }
-            Request::DumpTree { filter, visible_only } => {
-                send_command(cmd_tx, |respond| LuaCommand::DumpTree { filter, visible_only, respond })
+            Request::Eval { code } => {
+                send_command(cmd_tx, |respond| LuaCommand::Eval { code, respond })
             }
-            Request::Screenshot { output, width, height, filter, crop } => {
-                send_command(cmd_tx, |respond| LuaCommand::Screenshot { output, width, height, filter, crop, respond })
+            Request::DumpTree { filter, visible_only, json, query } => {
+                send_command(cmd_tx, |respond| LuaCommand::DumpTree { filter, visible_only, json, query, respond })
+            }
+            Request::Layout { json } => send_command(cmd_tx, |respond| LuaCommand::Layout { json, respond }),
+            Request::ReloadAddon { name } => {
+                send_command(cmd_tx, |respond| LuaCommand::ReloadAddon { name, respond })
+            }
+            Request::SaveVars => send_command(cmd_tx, |respond| LuaCommand::SaveVars { respond }),
+            Request::ApiCoverage => send_command(cmd_tx, |respond| LuaCommand::ApiCoverage { respond }),
+            Request::Screenshot { output, width, height, filter, crop, min_strata } => {
+                send_command(cmd_tx, |respond| LuaCommand::Screenshot { output, width, height, filter, crop, min_strata, respond })
             }
         };
 
@@ -211,6 +402,23 @@ fn handle_connection(
     Ok(())
 }
 
+/// Subscribe to console output and forward each line until the client
+/// disconnects. The app prunes the subscriber once the receiver is dropped.
+fn stream_console(mut stream: UnixStream, cmd_tx: &mpsc::Sender<LuaCommand>) -> std::io::Result<()> {
+    let (tx, rx) = mpsc::channel();
+    if cmd_tx.send(LuaCommand::Tail { subscriber: tx }).is_err() {
+        let resp = Response::Error("App closed".into());
+        return writeln!(stream, "{}", serde_json::to_string(&resp).unwrap());
+    }
+    for line in rx {
+        let resp = Response::Line(line);
+        if writeln!(stream, "{}", serde_json::to_string(&resp).unwrap()).is_err() {
+            break;
+        }
+    }
+    Ok(())
+}
+
 /// Client module for connecting to the Lua server.
 pub mod client {
     use super::*;
@@ -243,6 +451,57 @@ pub mod client {
             Response::Error(e) => Err(e),
             Response::Pong => Err("Unexpected pong".into()),
             Response::Tree(_) => Err("Unexpected tree".into()),
+            Response::Values(_) => Err("Unexpected values".into()),
+            Response::Line(_) => Err("Unexpected line".into()),
+        }
+    }
+
+    /// Stream console output, calling `on_line` for each line until the
+    /// server closes the connection.
+    pub fn tail<P: AsRef<Path>>(socket: P, mut on_line: impl FnMut(&str)) -> Result<(), String> {
+        let mut stream =
+            UnixStream::connect(socket).map_err(|e| format!("Connect failed: {}", e))?;
+
+        writeln!(stream, "{}", serde_json::to_string(&Request::Tail).unwrap())
+            .map_err(|e| format!("Write failed: {}", e))?;
+
+        for line in BufReader::new(stream).lines() {
+            let line = line.map_err(|e| format!("Read failed: {}", e))?;
+            let response: Response =
+                serde_json::from_str(&line).map_err(|e| format!("Invalid response: {}", e))?;
+            match response {
+                Response::Line(s) => on_line(&s),
+                Response::Error(e) => return Err(e),
+                _ => return Err("Unexpected response".into()),
+            }
+        }
+        Ok(())
+    }
+
+    /// Connect to a Lua server and evaluate an expression, returning its values.
+    pub fn eval<P: AsRef<Path>>(socket: P, code: &str) -> Result<Vec<EvalValue>, String> {
+        let mut stream =
+            UnixStream::connect(socket).map_err(|e| format!("Connect failed: {}", e))?;
+
+        let request = Request::Eval {
+            code: code.to_string(),
+        };
+        writeln!(stream, "{}", serde_json::to_string(&request).unwrap())
+            .map_err(|e| format!("Write failed: {}", e))?;
+
+        let mut reader = BufReader::new(stream);
+        let mut line = String::new();
+        reader
+            .read_line(&mut line)
+            .map_err(|e| format!("Read failed: {}", e))?;
+
+        let response: Response =
+            serde_json::from_str(&line).map_err(|e| format!("Invalid response: {}", e))?;
+
+        match response {
+            Response::Values(values) => Ok(values),
+            Response::Error(e) => Err(e),
+            _ => Err("Unexpected response".into()),
         }
     }
 
@@ -286,6 +545,7 @@ pub mod client {
         height: u32,
         filter: Option<String>,
         crop: Option<String>,
+        min_strata: Option<String>,
     ) -> Result<String, String> {
         let mut stream =
             UnixStream::connect(socket).map_err(|e| format!("Connect failed: {}", e))?;
@@ -296,6 +556,34 @@ pub mod client {
             height,
             filter,
             crop,
+            min_strata,
+        };
+        writeln!(stream, "{}", serde_json::to_string(&request).unwrap())
+            .map_err(|e| format!("Write failed: {}", e))?;
+
+        let mut reader = BufReader::new(stream);
+        let mut line = String::new();
+        reader
+            .read_line(&mut line)
+            .map_err(|e| format!("Read failed: {}", e))?;
+
+        let response: Response =
+            serde_json::from_str(&line).map_err(|e| format!("Invalid response: {}", e))?;
+
+        match response {
+            Response::Output(s) => Ok(s),
+            Response::Error(e) => Err(e),
+            _ => Err("Unexpected response".into()),
+        }
+    }
+
+    /// Reload an addon by folder name, returning the load report.
+    pub fn reload_addon<P: AsRef<Path>>(socket: P, name: &str) -> Result<String, String> {
+        let mut stream =
+            UnixStream::connect(socket).map_err(|e| format!("Connect failed: {}", e))?;
+
+        let request = Request::ReloadAddon {
+            name: name.to_string(),
         };
         writeln!(stream, "{}", serde_json::to_string(&request).unwrap())
             .map_err(|e| format!("Write failed: {}", e))?;
@@ -316,16 +604,66 @@ pub mod client {
         }
     }
 
+    /// Write all addons' SavedVariables back to storage.
+    pub fn save_vars<P: AsRef<Path>>(socket: P) -> Result<String, String> {
+        let mut stream =
+            UnixStream::connect(socket).map_err(|e| format!("Connect failed: {}", e))?;
+
+        writeln!(stream, "{}", serde_json::to_string(&Request::SaveVars).unwrap())
+            .map_err(|e| format!("Write failed: {}", e))?;
+
+        let mut reader = BufReader::new(stream);
+        let mut line = String::new();
+        reader
+            .read_line(&mut line)
+            .map_err(|e| format!("Read failed: {}", e))?;
+
+        let response: Response =
+            serde_json::from_str(&line).map_err(|e| format!("Invalid response: {}", e))?;
+
+        match response {
+            Response::Output(s) => Ok(s),
+            Response::Error(e) => Err(e),
+            _ => Err("Unexpected response".into()),
+        }
+    }
+
+    /// Fetch the missing-API report recorded with --api-coverage.
+    pub fn api_coverage<P: AsRef<Path>>(socket: P) -> Result<String, String> {
+        let mut stream =
+            UnixStream::connect(socket).map_err(|e| format!("Connect failed: {}", e))?;
+
+        writeln!(stream, "{}", serde_json::to_string(&Request::ApiCoverage).unwrap())
+            .map_err(|e| format!("Write failed: {}", e))?;
+
+        let mut reader = BufReader::new(stream);
+        let mut line = String::new();
+        reader
+            .read_line(&mut line)
+            .map_err(|e| format!("Read failed: {}", e))?;
+
+        let response: Response =
+            serde_json::from_str(&line).map_err(|e| format!("Invalid response: {}", e))?;
+
+        match response {
+            Response::Output(s) => Ok(s),
+            Response::Error(e) => Err(e),
+            _ => Err("Unexpected response".into()),
+        }
+    }
+
     /// Dump the frame tree.
     pub fn dump_tree<P: AsRef<Path>>(
         socket: P,
         filter: Option<String>,
         visible_only: bool,
+        json: bool,
+        query: Option<String>,
     ) -> Result<String, String> {
         let mut stream =
             UnixStream::connect(socket).map_err(|e| format!("Connect failed: {}", e))?;
 
-        let request = Request::DumpTree { filter, visible_only };
+        let request = Request::DumpTree { filter, visible_only, json, query };
         writeln!(stream, "{}", serde_json::to_string(&request).unwrap())
             .map_err(|e| format!("Write failed: {}", e))?;
 
@@ -344,4 +682,28 @@ pub mod client {
             _ => Err("Unexpected response".into()),
         }
     }
+
+    /// Fetch the rects of all named frames.
+    pub fn layout<P: AsRef<Path>>(socket: P, json: bool) -> Result<String, String> {
+        let mut stream =
+            UnixStream::connect(socket).map_err(|e| format!("Connect failed: {}", e))?;
+
+        writeln!(stream, "{}", serde_json::to_string(&Request::Layout { json }).unwrap())
+            .map_err(|e| format!("Write failed: {}", e))?;
+
+        let mut reader = BufReader::new(stream);
+        let mut line = String::new();
+        reader
+            .read_line(&mut line)
+            .map_err(|e| format!("Read failed: {}", e))?;
+
+        let response: Response =
+            serde_json::from_str(&line).map_err(|e| format!("Invalid response: {}", e))?;
+
+        match response {
+            Response::Tree(s) => Ok(s),
+            Response::Error(e) => Err(e),
+            _ => Err("Unexpected response".into()),
+        }
+    }
 }
@@ -3,11 +3,15 @@ use std::path::{Path, PathBuf};
 use std::rc::Rc;
 use clap::{Parser, Subcommand};
 use tracing_subscriber::EnvFilter;
+use wow_ui_sim::event::recorder::{events_after_startup, load_event_log, RecordedEvent};
+use wow_ui_sim::input::{parse_input_script, run_input_script, InputCommand};
 use wow_ui_sim::loader::{discover_blizzard_addons, load_addon, load_addon_with_saved_vars, LoadResult, LoadTiming};
+use wow_ui_sim::loader::profile::LoadProfile;
 use wow_ui_sim::lua_api::{AddonInfo, WowLuaEnv};
 use wow_ui_sim::render::WowFontSystem;
 use wow_ui_sim::saved_variables::{SavedVariablesManager, WtfConfig};
 use wow_ui_sim::toc::TocFile;
+use wow_ui_sim::widget::FrameStrata;
 
 #[derive(Parser)]
 #[command(name = "wow-sim", about = "WoW UI Simulator")]
@@ -16,6 +20,15 @@ struct Args {
     #[arg(long)]
     no_saved_vars: bool,
 
+    /// Write addons' SavedVariables back to storage when a headless command exits
+    /// (the GUI always saves on exit unless --no-save-vars-on-exit is given)
+    #[arg(long)]
+    save_vars_on_exit: bool,
+
+    /// Don't write SavedVariables back when the GUI exits
+    #[arg(long, conflicts_with = "save_vars_on_exit")]
+    no_save_vars_on_exit: bool,
+
     /// Skip loading third-party addons
     #[arg(long)]
     no_addons: bool,
@@ -41,6 +54,49 @@ struct Args {
     #[arg(long, value_name = "CODE")]
     exec_lua: Option<String>,
 
+    /// Simulate input after startup in screenshot/dump-tree: one command per line
+    /// (click/hover/key/type/scroll/wait, see `wow_ui_sim::input`).
+    /// Prefix with @ to load from file (e.g., --input-script @/tmp/open-bags.txt);
+    /// inline scripts separate commands with `;` (write `\;` for a literal one).
+    #[arg(long, value_name = "SCRIPT")]
+    input_script: Option<String>,
+
+    /// Record every dispatched event (name, args, GetTime()) to a JSON-lines file
+    #[arg(long, value_name = "PATH")]
+    record_events: Option<PathBuf>,
+
+    /// Replay a --record-events log after startup in screenshot/dump-tree
+    #[arg(long, value_name = "PATH")]
+    replay_events: Option<PathBuf>,
+
+    /// Seed Lua's math.random and the randomized player defaults (name, buffs,
+    /// XP) so repeated dump-tree/screenshot runs produce identical output
+    #[arg(long, value_name = "N")]
+    seed: Option<u64>,
+
+    /// Compile every Lua file from source instead of using .cache/lua-bytecode
+    #[arg(long)]
+    no_bytecode_cache: bool,
+
+    /// Print the whole Lua stack with script errors (OnLoad handlers, templates)
+    /// instead of the innermost few frames
+    #[arg(long)]
+    lua_traceback: bool,
+
+    /// Report the slowest addons, files and templates during load, with the
+    /// frames/textures/font strings each addon created
+    #[arg(long)]
+    profile: bool,
+
+    /// Also write the --profile data as JSON (implies --profile)
+    #[arg(long, value_name = "PATH")]
+    profile_json: Option<PathBuf>,
+
+    /// Record calls to globals, C_* functions and frame methods the simulator
+    /// doesn't implement, and print a ranked report on exit
+    #[arg(long)]
+    api_coverage: bool,
+
     #[command(subcommand)]
     command: Option<Commands>,
 }
@@ -60,6 +116,12 @@ enum Commands {
         /// Show only visible frames
         #[arg(long)]
         visible_only: bool,
+        /// Print the tree as JSON with absolute rects
+        #[arg(long)]
+        json: bool,
+        /// List frames matching all predicates, e.g. "type=Button visible=true under=ObjectiveTrackerFrame minwidth=100"
+        #[arg(long, conflicts_with_all = ["filter", "filter_key", "visible_only"])]
+        query: Option<String>,
         /// Screen width for layout computation
         #[arg(long, default_value_t = 1600)]
         width: u32,
@@ -82,6 +144,10 @@ enum Commands {
         #[arg(long, default_value_t = 1200)]
         height: u32,
 
+        /// Supersampling factor: render at N× the size, then downsample (1-4)
+        #[arg(long, default_value_t = 1, value_parser = clap::value_parser!(u32).range(1..=4))]
+        scale: u32,
+
         /// Render only this frame subtree (name substring match)
         #[arg(short, long)]
         filter: Option<String>,
@@ -89,9 +155,53 @@ enum Commands {
         /// Crop the output image to WxH+X+Y (e.g., 700x150+400+650)
         #[arg(long, value_name = "WxH+X+Y")]
         crop: Option<String>,
+        /// Render only frames at or above this strata (e.g. DIALOG, TOOLTIP)
+        #[arg(long, value_name = "STRATA", value_parser = parse_strata)]
+        min_strata: Option<FrameStrata>,
         /// Also dump frame tree before rendering (optional parentKey filter)
         #[arg(long, value_name = "FILTER")]
         dump_tree: Option<Option<String>>,
+        /// Print the --dump-tree output as JSON with absolute rects
+        #[arg(long, requires = "dump_tree")]
+        json: bool,
+    },
+
+    /// Reload an addon and re-render a screenshot whenever its .lua/.xml/.toc
+    /// files change (headless). Each reload loads the Blizzard UI and then only
+    /// this addon.
+    Watch {
+        /// Addon directory to watch (must contain a .toc)
+        addon: PathBuf,
+
+        /// Output file path (always lossy WebP at quality 15, extension forced to .webp)
+        #[arg(short, long, default_value = "screenshot.webp")]
+        screenshot: PathBuf,
+
+        /// Image width in pixels
+        #[arg(long, default_value_t = 1600)]
+        width: u32,
+
+        /// Image height in pixels
+        #[arg(long, default_value_t = 1200)]
+        height: u32,
+
+        /// Supersampling factor: render at N× the size, then downsample (1-4)
+        #[arg(long, default_value_t = 1, value_parser = clap::value_parser!(u32).range(1..=4))]
+        scale: u32,
+
+        /// Render only this frame subtree (name substring match)
+        #[arg(short, long)]
+        filter: Option<String>,
+
+        /// Crop the output image to WxH+X+Y (e.g., 700x150+400+650)
+        #[arg(long, value_name = "WxH+X+Y")]
+        crop: Option<String>,
+        /// Render only frames at or above this strata (e.g. DIALOG, TOOLTIP)
+        #[arg(long, value_name = "STRATA", value_parser = parse_strata)]
+        min_strata: Option<FrameStrata>,
+        /// Quiet time after the last file change before reloading
+        #[arg(long, value_name = "MS", default_value_t = 300)]
+        debounce: u64,
     },
 
     /// Dump textures used by frames to disk (for debugging atlas crops)
@@ -147,48 +257,55 @@ fn scan_addons(base_path: &PathBuf) -> Vec<(String, PathBuf)> {
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     let args = Args::parse();
     apply_resource_limits();
+    if args.no_bytecode_cache {
+        wow_ui_sim::loader::bytecode_cache::disable();
+    }
+    wow_ui_sim::lua_api::set_full_tracebacks(args.lua_traceback);
+    wow_ui_sim::lua_api::set_random_seed(args.seed);
 
     tracing_subscriber::fmt()
         .with_env_filter(EnvFilter::from_default_env())
         .init();
 
-    let env = WowLuaEnv::new()?;
     let font_system = Rc::new(RefCell::new(WowFontSystem::new(&PathBuf::from("./fonts"))));
-    env.set_font_system(Rc::clone(&font_system));
-
-    // Initialize sound manager (skip with WOW_SIM_NO_SOUND=1)
-    init_sound(&env);
+    let query = parse_query(&args.command);
 
-    // Set addon base paths for runtime on-demand loading (C_AddOns.LoadAddOn)
-    {
-        let mut state = env.state().borrow_mut();
-        state.addon_base_paths = vec![
-            PathBuf::from("./Interface/BlizzardUI"),
-            PathBuf::from("./Interface/AddOns"),
-        ];
+    // Watch builds a fresh environment per reload instead of the shared one below.
+    if let Some(Commands::Watch { addon, screenshot, width, height, scale, filter, crop, min_strata, debounce }) = &args.command {
+        let shot = WatchScreenshot {
+            output: screenshot.clone(), width: *width, height: *height, scale: *scale,
+            filter: filter.clone(), crop: crop.clone(), min_strata: *min_strata,
+        };
+        run_watch(&args, &font_system, addon, &shot, std::time::Duration::from_millis(*debounce));
+        return Ok(());
     }
 
-    // Register synthetic templates for C++ intrinsic frame types (WoWScrollBoxList, etc.)
-    // before any addons that reference them are loaded.
-    wow_ui_sim::xml::register_intrinsic_templates();
-
-    let mut saved_vars = configure_saved_vars(&args);
-    load_blizzard_addons(&env);
+    let (env, mut saved_vars) = create_standalone_env(&args, &font_system)?;
     load_third_party_addons(&args, &env, &mut saved_vars);
+    report_load_profile(&env, args.profile_json.as_deref());
     env.apply_post_load_workarounds();
 
     let exec_lua = resolve_exec_lua(&args.exec_lua);
+    let input_script = resolve_input_script(&args.input_script);
+    let replay = resolve_event_log(&args.replay_events);
 
     match args.command {
-        Some(Commands::DumpTree { filter, filter_key, visible_only, width, height }) => {
-            run_dump_tree(&env, filter, filter_key, visible_only, width, height, args.delay, exec_lua.as_deref());
+        Some(Commands::DumpTree { filter, filter_key, visible_only, json, width, height, .. }) => {
+            let selection = TreeSelection { filter, filter_key, query, visible_only, json };
+            run_dump_tree(&env, selection, width, height, args.delay, exec_lua.as_deref(), &input_script, &replay);
+            save_vars_on_exit(&env, saved_vars.as_ref());
+            report_api_coverage(&env);
         }
-        Some(Commands::Screenshot { output, width, height, filter, crop, dump_tree }) => {
-            run_screenshot(&env, &font_system, output, width, height, filter, crop, args.delay, exec_lua.as_deref(), dump_tree);
+        Some(Commands::Screenshot { output, width, height, scale, filter, crop, min_strata, dump_tree, json }) => {
+            run_screenshot(&env, &font_system, output, width, height, scale, filter, crop, min_strata, args.delay, exec_lua.as_deref(), &input_script, &replay, dump_tree, json);
+            save_vars_on_exit(&env, saved_vars.as_ref());
+            report_api_coverage(&env);
         }
         Some(Commands::DumpTexture { output, filter, frame_filter }) => {
             run_dump_texture(&env, &font_system, output, filter, frame_filter);
+            report_api_coverage(&env);
         }
+        Some(Commands::Watch { .. }) => unreachable!("watch returns before the shared environment is built"),
         None => {
             let debug = wow_ui_sim::DebugOptions {
                 borders: args.debug_borders || args.debug_elements,
@@ -201,6 +318,58 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
+/// Create a Lua environment with the Blizzard UI loaded: the setup shared by
+/// the GUI, the headless subcommands and every `watch` reload. Third-party
+/// addons and post-load workarounds are left to the caller.
+fn create_standalone_env(
+    args: &Args,
+    font_system: &Rc<RefCell<WowFontSystem>>,
+) -> Result<(WowLuaEnv, Option<SavedVariablesManager>), Box<dyn std::error::Error>> {
+    let env = WowLuaEnv::new()?;
+    env.use_persisted_cvars();
+    if let Some(path) = &args.record_events {
+        env.start_event_recording(Some(path.as_path()))?;
+        println!("Recording events to {}", path.display());
+    }
+    env.set_font_system(Rc::clone(font_system));
+
+    // Initialize sound manager (skip with WOW_SIM_NO_SOUND=1)
+    init_sound(&env);
+
+    // Set addon base paths for runtime on-demand loading (C_AddOns.LoadAddOn)
+    {
+        let mut state = env.state().borrow_mut();
+        state.addon_base_paths = vec![
+            PathBuf::from("./Interface/BlizzardUI"),
+            PathBuf::from("./Interface/AddOns"),
+        ];
+    }
+
+    // Register synthetic templates for C++ intrinsic frame types (WoWScrollBoxList, etc.)
+    // before any addons that reference them are loaded.
+    wow_ui_sim::xml::register_intrinsic_templates();
+
+    let saved_vars = configure_saved_vars(args);
+    if let Some(sv) = &saved_vars {
+        env.state().borrow_mut().realm_name = sv.realm_name().to_string();
+    }
+    if args.profile || args.profile_json.is_some() {
+        env.state().borrow_mut().load_profile = Some(LoadProfile::default());
+    }
+    if args.api_coverage {
+        env.enable_api_coverage()?;
+    }
+    load_blizzard_addons(&env);
+    Ok((env, saved_vars))
+}
+
+/// Parse a `--min-strata` value (case-insensitive strata name).
+fn parse_strata(s: &str) -> Result<FrameStrata, String> {
+    FrameStrata::from_str(s).ok_or_else(|| {
+        format!("unknown strata '{s}' (expected e.g. MEDIUM, DIALOG, FULLSCREEN_DIALOG, TOOLTIP)")
+    })
+}
+
 /// Resolve exec-lua argument: if prefixed with `@`, read the file contents.
 fn resolve_exec_lua(arg: &Option<String>) -> Option<String> {
     arg.as_ref().map(|s| {
@@ -215,12 +384,107 @@ fn resolve_exec_lua(arg: &Option<String>) -> Option<String> {
     })
 }
 
+/// Resolve and parse the input-script argument (`@file` or inline `;`-separated
+/// commands). Exits on unreadable files or parse errors.
+fn resolve_input_script(arg: &Option<String>) -> Vec<InputCommand> {
+    let Some(arg) = arg else { return Vec::new() };
+    let source = match arg.strip_prefix('@') {
+        Some(path) => std::fs::read_to_string(path).unwrap_or_else(|e| {
+            eprintln!("[input-script] Failed to read {path}: {e}");
+            std::process::exit(1);
+        }),
+        None => wow_ui_sim::input::inline_script_lines(arg),
+    };
+    parse_input_script(&source).unwrap_or_else(|e| {
+        eprintln!("[input-script] {e}");
+        std::process::exit(1);
+    })
+}
+
+/// Print the `--api-coverage` report, if recording was enabled.
+fn report_api_coverage(env: &WowLuaEnv) {
+    if let Some(report) = env.api_coverage_report() {
+        eprint!("{report}");
+    }
+}
+
+/// Print the `--profile` report and optionally write it as JSON. Profiling
+/// stops here so runtime template use is not counted.
+fn report_load_profile(env: &WowLuaEnv, json_path: Option<&Path>) {
+    let Some(profile) = env.state().borrow_mut().load_profile.take() else {
+        return;
+    };
+    print!("{}", profile.report(20));
+    let Some(path) = json_path else {
+        return;
+    };
+    let written = serde_json::to_string_pretty(&profile)
+        .map_err(|e| e.to_string())
+        .and_then(|json| std::fs::write(path, json).map_err(|e| e.to_string()));
+    match written {
+        Ok(()) => println!("Load profile written to {}", path.display()),
+        Err(e) => eprintln!("[profile] failed to write {}: {e}", path.display()),
+    }
+}
+
+/// Load the `--replay-events` log. Exits on unreadable or malformed files.
+fn resolve_event_log(path: &Option<PathBuf>) -> Vec<RecordedEvent> {
+    let Some(path) = path else { return Vec::new() };
+    load_event_log(path).unwrap_or_else(|e| {
+        eprintln!("[replay-events] {e}");
+        std::process::exit(1);
+    })
+}
+
+/// Parse `dump-tree --query` up front, so a typo exits before the UI loads.
+fn parse_query(command: &Option<Commands>) -> Option<wow_ui_sim::dump::FrameQuery> {
+    let Some(Commands::DumpTree { query: Some(query), .. }) = command else { return None };
+    match wow_ui_sim::dump::FrameQuery::parse(query) {
+        Ok(q) => Some(q),
+        Err(e) => {
+            eprintln!("Invalid query: {e}");
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Replay recorded events after headless startup, skipping the recorded
+/// login sequence that `run_headless_startup` has already fired.
+fn replay_event_log(env: &WowLuaEnv, events: &[RecordedEvent]) {
+    let events = events_after_startup(events);
+    if events.is_empty() {
+        return;
+    }
+    eprintln!("[replay-events] replaying {} event(s)", events.len());
+    if let Err(e) = env.replay_events(events) {
+        eprintln!("[replay-events] error: {e}");
+    }
+}
+
+/// Run headless input commands, then settle animations they started.
+fn run_input_commands(env: &WowLuaEnv, commands: &[InputCommand]) {
+    if commands.is_empty() {
+        return;
+    }
+    if let Err(e) = run_input_script(env, commands) {
+        eprintln!("[input-script] {e}");
+        std::process::exit(1);
+    }
+    settle_animations(env);
+}
+
 /// Configure SavedVariables from WTF directory based on args/env.
 fn configure_saved_vars(args: &Args) -> Option<SavedVariablesManager> {
     let skip = args.no_saved_vars
         || std::env::var("WOW_SIM_NO_SAVED_VARS").map(|v| v == "1").unwrap_or(false);
     if skip { println!("SavedVariables loading disabled"); return None; }
     let mut saved_vars = SavedVariablesManager::new();
+    // The GUI saves on exit unless opted out; headless commands only on request.
+    let save_on_exit = match args.command {
+        None => !args.no_save_vars_on_exit,
+        Some(_) => args.save_vars_on_exit,
+    };
+    saved_vars.set_save_on_exit(save_on_exit);
     let wtf_path = PathBuf::from("/syncthing/Sync/Projects/wow/WTF");
     if wtf_path.exists() {
         let wtf = WtfConfig::new(wtf_path, "50868465#2", "Burning Blade", "Haky");
@@ -230,6 +494,16 @@ fn configure_saved_vars(args: &Args) -> Option<SavedVariablesManager> {
     Some(saved_vars)
 }
 
+/// Headless runs: write SavedVariables back if `--save-vars-on-exit` was given.
+/// (The GUI does the same when the app is dropped.)
+fn save_vars_on_exit(env: &WowLuaEnv, saved_vars: Option<&SavedVariablesManager>) {
+    let Some(sv) = saved_vars.filter(|sv| sv.save_on_exit()) else { return };
+    match sv.save(env) {
+        Ok(count) => println!("SavedVariables saved for {} addon(s)", count),
+        Err(e) => eprintln!("SavedVariables save error: {}", e),
+    }
+}
+
 /// Initialize sound manager unless WOW_SIM_NO_SOUND=1 or --no-sound.
 fn init_sound(env: &WowLuaEnv) {
     let skip = std::env::var("WOW_SIM_NO_SOUND")
@@ -245,7 +519,7 @@ fn init_sound(env: &WowLuaEnv) {
     match wow_ui_sim::sound::SoundManager::new(sound_dir) {
         Some(mgr) => {
             println!("Sound initialized");
-            env.state().borrow_mut().sound_manager = Some(mgr);
+            env.state().borrow_mut().sound_manager = Some(Box::new(mgr));
         }
         None => {
             println!("Sound: no audio device available");
@@ -347,32 +621,17 @@ struct LoadStats {
 }
 
 /// Load a single third-party addon and update stats.
-/// Parse TOC metadata for an addon.
-fn parse_addon_metadata(name: &str, toc_path: &Path) -> (String, String, bool) {
-    let toc = TocFile::from_file(toc_path).ok();
-    toc.as_ref().map(|t| {
-        let title = t.metadata.get("Title").cloned().unwrap_or_else(|| name.to_string());
-        let notes = t.metadata.get("Notes").cloned().unwrap_or_default();
-        let lod = t.metadata.get("LoadOnDemand").map(|v| v == "1").unwrap_or(false);
-        (title, notes, lod)
-    }).unwrap_or_else(|| (name.to_string(), String::new(), false))
-}
-
-/// Load a single third-party addon and update stats.
+///
+/// Returns the addon's load warnings, or the load error as the only entry.
 fn load_single_addon(
     env: &WowLuaEnv,
     name: &str,
     toc_path: &Path,
     saved_vars: &mut Option<SavedVariablesManager>,
     stats: &mut LoadStats,
-) {
-    let (title, notes, load_on_demand) = parse_addon_metadata(name, toc_path);
-
+) -> Vec<String> {
     // Pre-register so loading_addon_index attributes frames to this addon.
-    env.register_addon(AddonInfo {
-        folder_name: name.to_string(), title, notes,
-        enabled: true, loaded: false, load_on_demand, ..Default::default()
-    });
+    env.register_addon(AddonInfo::from_toc(name, toc_path));
 
     let result = match saved_vars.as_mut() {
         Some(sv) => load_addon_with_saved_vars(&env.loader_env(), toc_path, sv),
@@ -388,8 +647,13 @@ fn load_single_addon(
             }
             drop(s);
             record_addon_success(name, &r, stats);
+            r.warnings
+        }
+        Err(e) => {
+            println!("✗ {} failed: {}", name, e);
+            stats.fail_count += 1;
+            vec![format!("load failed: {e}")]
         }
-        Err(e) => { println!("✗ {} failed: {}", name, e); stats.fail_count += 1; }
     }
 }
 
@@ -486,22 +750,7 @@ fn run_debug_script(env: &WowLuaEnv) {
     }
 }
 
-/// Process pending timers (deferred template wiring, addon callbacks, etc.).
-///
-/// In GUI mode timers fire every frame, but headless paths (screenshot, dump-tree)
-/// must explicitly drain them. Loops until no more timers fire (handles chaining).
-use wow_ui_sim::startup::{
-    apply_delay, fire_one_on_update_tick, fire_startup_events, process_pending_timers,
-};
-
-/// Fire extra OnUpdate ticks so deferred UI (talent frame, pool-created frames) can process.
-fn run_extra_update_ticks(env: &WowLuaEnv, n: usize) {
-    for _ in 0..n {
-        env.state().borrow_mut().ensure_layout_rects();
-        fire_one_on_update_tick(env);
-        process_pending_timers(env);
-    }
-}
+use wow_ui_sim::startup::{apply_delay, fire_headless_startup, settle_animations, settle_headless_startup};
 
 /// Debug: open game menu via micro button click for screenshot testing.
 fn debug_show_game_menu(env: &WowLuaEnv) {
@@ -589,17 +838,22 @@ fn apply_crop(img: image::RgbaImage, crop_str: &str) -> image::RgbaImage {
     img.view(cx, cy, cw, ch).to_image()
 }
 
-/// Build the quad batch and glyph atlas for headless rendering.
+/// Build the quad batch and glyph atlas for headless rendering. Glyphs are
+/// rasterized at `scale`× so they stay sharp in a supersampled render.
+#[allow(clippy::too_many_arguments)]
 fn build_screenshot_batch(
     env: &WowLuaEnv,
     font_system: &Rc<RefCell<WowFontSystem>>,
+    tex_mgr: &mut wow_ui_sim::texture::TextureManager,
     width: u32,
     height: u32,
+    scale: u32,
     filter: Option<&str>,
+    min_strata: Option<FrameStrata>,
 ) -> (wow_ui_sim::render::QuadBatch, wow_ui_sim::render::GlyphAtlas) {
-    use wow_ui_sim::iced_app::build_quad_batch_for_registry;
+    use wow_ui_sim::iced_app::build_quad_batch_at_time;
     use wow_ui_sim::render::GlyphAtlas;
-    let mut glyph_atlas = GlyphAtlas::new();
+    let mut glyph_atlas = GlyphAtlas::with_raster_scale(scale as f32);
     let batch = {
         let mut fs = font_system.borrow_mut();
         let buckets = {
@@ -607,52 +861,90 @@ fn build_screenshot_batch(
             state.ensure_layout_rects();
             wow_ui_sim::iced_app::tooltip::update_tooltip_sizes(&mut state, &mut fs);
             let _ = state.get_strata_buckets();
-            state.strata_buckets.as_ref().unwrap().clone()
+            let buckets = state.strata_buckets.as_ref().unwrap();
+            match min_strata {
+                Some(min) => wow_ui_sim::iced_app::strata_buckets_from(buckets, min),
+                None => buckets.clone(),
+            }
         };
         let state = env.state().borrow();
         let tooltip_data = wow_ui_sim::iced_app::tooltip::collect_tooltip_data(&state);
-        build_quad_batch_for_registry(
+        let mut batch = build_quad_batch_at_time(
             &state.widgets,
             (width as f32, height as f32),
             filter, None, None,
             Some((&mut fs, &mut glyph_atlas)),
+            Some(tex_mgr),
             Some(&state.message_frames),
             Some(&tooltip_data),
             &buckets,
-        )
+            state.clock.now(),
+        );
+        drop(state);
+        env.state().borrow_mut().set_hyperlink_regions(std::mem::take(&mut batch.hyperlinks));
+        batch
     };
     (batch, glyph_atlas)
 }
 
 /// Run startup events, timers, and settle the UI state for headless subcommands.
 fn run_headless_startup(env: &WowLuaEnv) {
-    fire_startup_events(env);
-    env.apply_post_event_workarounds();
-    env.state().borrow_mut().widgets.rebuild_anchor_index();
-    process_pending_timers(env);
-    fire_one_on_update_tick(env);
-    let _ = wow_ui_sim::lua_api::globals::global_frames::hide_runtime_hidden_frames(env.lua());
+    fire_headless_startup(env);
     run_debug_script(env);
-    std::thread::sleep(std::time::Duration::from_secs(2));
-    run_extra_update_ticks(env, 3);
+    settle_headless_startup(env);
+}
+
+/// Which frames `dump-tree` prints and how.
+struct TreeSelection {
+    filter: Option<String>,
+    filter_key: Option<String>,
+    query: Option<wow_ui_sim::dump::FrameQuery>,
+    visible_only: bool,
+    json: bool,
 }
 
 /// Load UI and dump the frame tree to stdout.
 #[allow(clippy::too_many_arguments)]
 fn run_dump_tree(
-    env: &WowLuaEnv, filter: Option<String>, filter_key: Option<String>,
-    visible_only: bool, width: u32, height: u32, delay: Option<u64>, exec_lua: Option<&str>,
+    env: &WowLuaEnv, selection: TreeSelection, width: u32, height: u32, delay: Option<u64>, exec_lua: Option<&str>,
+    input_script: &[InputCommand], replay: &[RecordedEvent],
 ) {
+    let TreeSelection { filter, filter_key, query, visible_only, json } = selection;
     run_headless_startup(env);
+    replay_event_log(env, replay);
     if let Some(code) = exec_lua
         && let Err(e) = env.exec(code) {
             eprintln!("[exec-lua] error: {e}");
         }
-    apply_delay(delay);
+    run_input_commands(env, input_script);
+    apply_delay(env, delay);
     let state = env.state().borrow();
+    if let Some(query) = &query {
+        print_query_results(&state.widgets, query, json, width as f32, height as f32);
+        return;
+    }
+    if json {
+        let tree = wow_ui_sim::dump::frame_tree_json(&state.widgets, filter.as_deref(), filter_key.as_deref(), visible_only, width as f32, height as f32);
+        println!("{}", serde_json::to_string_pretty(&tree).unwrap());
+        return;
+    }
     wow_ui_sim::dump::print_frame_tree(&state.widgets, filter.as_deref(), filter_key.as_deref(), visible_only, width as f32, height as f32);
 }
 
+/// Print the frames matching a `dump-tree --query` string.
+fn print_query_results(
+    widgets: &wow_ui_sim::widget::WidgetRegistry, query: &wow_ui_sim::dump::FrameQuery, json: bool, width: f32, height: f32,
+) {
+    if json {
+        let result = wow_ui_sim::dump::query_results_json(widgets, query, width, height);
+        println!("{}", serde_json::to_string_pretty(&result).unwrap());
+        return;
+    }
+    for line in wow_ui_sim::dump::build_query_results(widgets, query, width, height) {
+        println!("{line}");
+    }
+}
+
 /// Render a headless screenshot.
 #[allow(clippy::too_many_arguments)]
 fn run_screenshot(
@@ -661,31 +953,44 @@ fn run_screenshot(
     output: PathBuf,
     width: u32,
     height: u32,
+    scale: u32,
     filter: Option<String>,
     crop: Option<String>,
+    min_strata: Option<FrameStrata>,
     delay: Option<u64>,
     exec_lua: Option<&str>,
+    input_script: &[InputCommand],
+    replay: &[RecordedEvent],
     dump_tree: Option<Option<String>>,
+    dump_json: bool,
 ) {
-    use wow_ui_sim::render::headless::render_to_image;
+    use wow_ui_sim::render::headless::render_scene_scaled;
 
     env.set_screen_size(width as f32, height as f32);
     run_headless_startup(env);
+    replay_event_log(env, replay);
     debug_show_game_menu(env);
     if let Some(code) = exec_lua
         && let Err(e) = env.exec(code) {
             eprintln!("[exec-lua] error: {e}");
         }
-    apply_delay(delay);
-    let (batch, glyph_atlas) = build_screenshot_batch(env, font_system, width, height, filter.as_deref());
+    run_input_commands(env, input_script);
+    apply_delay(env, delay);
+    let mut tex_mgr = create_texture_manager();
+    let (batch, glyph_atlas) =
+        build_screenshot_batch(env, font_system, &mut tex_mgr, width, height, scale, filter.as_deref(), min_strata);
     if let Some(dump_filter) = &dump_tree {
         let state = env.state().borrow();
         let fk = dump_filter.as_deref();
-        wow_ui_sim::dump::print_frame_tree(&state.widgets, None, fk, false, width as f32, height as f32);
+        if dump_json {
+            let tree = wow_ui_sim::dump::frame_tree_json(&state.widgets, None, fk, false, width as f32, height as f32);
+            println!("{}", serde_json::to_string_pretty(&tree).unwrap());
+        } else {
+            wow_ui_sim::dump::print_frame_tree(&state.widgets, None, fk, false, width as f32, height as f32);
+        }
     }
     eprintln!("QuadBatch: {} quads, {} texture requests", batch.quad_count(), batch.texture_requests.len());
 
-    let mut tex_mgr = create_texture_manager();
     let glyph_data = if glyph_atlas.is_dirty() {
         let (data, size, _) = glyph_atlas.texture_data();
         Some((data, size))
@@ -693,7 +998,21 @@ fn run_screenshot(
         None
     };
 
-    let img = render_to_image(&batch, &mut tex_mgr, width, height, glyph_data);
+    let rendered = render_scene_scaled(&batch, &mut tex_mgr, width, height, scale, glyph_data);
+    let misses = &rendered.stats.texture_misses;
+    if !misses.is_empty() {
+        eprintln!(
+            "Missing {} of {} textures (set WOW_SIM_VERBOSE=1 to list them)",
+            misses.len(),
+            rendered.stats.textures_loaded + misses.len(),
+        );
+        if std::env::var("WOW_SIM_VERBOSE").is_ok() {
+            for path in misses {
+                eprintln!("  [missing] {path}");
+            }
+        }
+    }
+    let img = rendered.image;
     let img = match crop.as_deref() {
         Some(crop_str) => apply_crop(img, crop_str),
         None => img,
@@ -702,6 +1021,11 @@ fn run_screenshot(
     let output = output.with_extension("webp");
     save_screenshot(&img, &output);
     eprintln!("Saved {}x{} screenshot to {}", img.width(), img.height(), output.with_extension("webp").display());
+    let cache = tex_mgr.cache_stats();
+    eprintln!(
+        "Texture cache: {} hits, {} misses, {} evictions ({} textures, {:.1} MB)",
+        cache.hits, cache.misses, cache.evictions, cache.entries, cache.bytes as f64 / (1024.0 * 1024.0)
+    );
 }
 
 fn save_screenshot(img: &image::RgbaImage, output: &std::path::Path) {
@@ -714,6 +1038,107 @@ fn save_screenshot(img: &image::RgbaImage, output: &std::path::Path) {
     }
 }
 
+/// Screenshot settings for `watch`, applied on every reload.
+struct WatchScreenshot {
+    output: PathBuf,
+    width: u32,
+    height: u32,
+    scale: u32,
+    filter: Option<String>,
+    crop: Option<String>,
+    min_strata: Option<FrameStrata>,
+}
+
+/// Reload `addon_dir` and re-render the screenshot every time one of its
+/// .lua/.xml/.toc files changes. Runs until interrupted.
+fn run_watch(
+    args: &Args,
+    font_system: &Rc<RefCell<WowFontSystem>>,
+    addon_dir: &Path,
+    shot: &WatchScreenshot,
+    debounce: std::time::Duration,
+) {
+    use wow_ui_sim::watch::{wait_for_change, Snapshot};
+
+    let Some(toc_path) = wow_ui_sim::loader::find_toc_file(addon_dir) else {
+        eprintln!("[watch] No .toc file in {}", addon_dir.display());
+        std::process::exit(1);
+    };
+    let name = addon_dir.file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_else(|| addon_dir.display().to_string());
+
+    let mut snapshot = Snapshot::scan(addon_dir);
+    let mut previous: Option<Vec<String>> = None;
+    loop {
+        let warnings = reload_watched_addon(args, font_system, &name, &toc_path, shot);
+        report_warning_changes(previous.as_deref(), &warnings);
+        previous = Some(warnings);
+
+        eprintln!("[watch] Watching {} files in {} (Ctrl-C to stop)", snapshot.len(), addon_dir.display());
+        let (next, changed) = wait_for_change(addon_dir, &snapshot, debounce);
+        for path in &changed {
+            eprintln!("[watch] Changed: {}", path.display());
+        }
+        snapshot = next;
+    }
+}
+
+/// Build a fresh environment with the Blizzard UI and the watched addon,
+/// then save a screenshot. Returns the addon's load warnings.
+fn reload_watched_addon(
+    args: &Args,
+    font_system: &Rc<RefCell<WowFontSystem>>,
+    name: &str,
+    toc_path: &Path,
+    shot: &WatchScreenshot,
+) -> Vec<String> {
+    let start = std::time::Instant::now();
+    let (env, mut saved_vars) = match create_standalone_env(args, font_system) {
+        Ok(loaded) => loaded,
+        Err(e) => {
+            eprintln!("[watch] Failed to create environment: {e}");
+            return Vec::new();
+        }
+    };
+    let warnings = load_single_addon(&env, name, toc_path, &mut saved_vars, &mut LoadStats::default());
+    env.apply_post_load_workarounds();
+
+    // Re-read @file arguments so edits to them are picked up too.
+    let exec_lua = resolve_exec_lua(&args.exec_lua);
+    let input_script = resolve_input_script(&args.input_script);
+    let replay = resolve_event_log(&args.replay_events);
+    run_screenshot(
+        &env, font_system, shot.output.clone(), shot.width, shot.height, shot.scale, shot.filter.clone(),
+        shot.crop.clone(), shot.min_strata, args.delay, exec_lua.as_deref(), &input_script, &replay, None, false,
+    );
+    eprintln!("[watch] Reloaded {name} in {:.2?}", start.elapsed());
+    warnings
+}
+
+/// Print how the watched addon's warnings changed since the last reload.
+fn report_warning_changes(previous: Option<&[String]>, current: &[String]) {
+    let Some(previous) = previous else {
+        eprintln!("[watch] {} warnings", current.len());
+        for w in current {
+            eprintln!("  [!] {w}");
+        }
+        return;
+    };
+    let diff = wow_ui_sim::watch::WarningDiff::between(previous, current);
+    if diff.is_empty() {
+        eprintln!("[watch] Warnings unchanged ({})", current.len());
+        return;
+    }
+    eprintln!("[watch] Warnings: {} new, {} gone ({} total)", diff.added.len(), diff.removed.len(), current.len());
+    for w in &diff.added {
+        eprintln!("  + {w}");
+    }
+    for w in &diff.removed {
+        eprintln!("  - {w}");
+    }
+}
+
 /// Dump textures used by frames to disk.
 fn run_dump_texture(
     env: &WowLuaEnv, font_system: &Rc<RefCell<WowFontSystem>>,
@@ -721,9 +1146,9 @@ fn run_dump_texture(
 ) {
     env.set_screen_size(1600.0, 1200.0);
     run_headless_startup(env);
-    let (batch, _) = build_screenshot_batch(env, font_system, 1600, 1200, frame_filter.as_deref());
-    eprintln!("QuadBatch: {} quads, {} tex requests", batch.quad_count(), batch.texture_requests.len());
     let mut tex_mgr = create_texture_manager();
+    let (batch, _) = build_screenshot_batch(env, font_system, &mut tex_mgr, 1600, 1200, 1, frame_filter.as_deref(), None);
+    eprintln!("QuadBatch: {} quads, {} tex requests", batch.quad_count(), batch.texture_requests.len());
     wow_ui_sim::dump_texture::dump_batch_textures(&batch, &mut tex_mgr, &output, filter.as_deref());
 }
 
@@ -0,0 +1,139 @@
+//! Screenshot comparison: per-pixel difference between a baseline and a
+//! candidate image, rendered as a heatmap.
+//!
+//! Used by `wow-cli compare` for visual regression checks.
+
+use image::{Rgba, RgbaImage};
+
+/// Outcome of comparing two images of the same size.
+pub struct DiffResult {
+    /// Heatmap: unchanged pixels are a dimmed grayscale of the baseline,
+    /// differing pixels are red, brighter for larger differences.
+    pub diff_image: RgbaImage,
+    /// Pixels whose largest channel difference exceeds the tolerance.
+    pub differing_pixels: u64,
+    pub total_pixels: u64,
+    /// Largest channel difference seen anywhere in the image.
+    pub max_delta: u8,
+}
+
+impl DiffResult {
+    /// Fraction of pixels that differ, in 0.0..=1.0.
+    pub fn fraction(&self) -> f64 {
+        if self.total_pixels == 0 {
+            return 0.0;
+        }
+        self.differing_pixels as f64 / self.total_pixels as f64
+    }
+}
+
+/// Compare two images pixel by pixel.
+///
+/// A pixel differs when any RGBA channel changes by more than `tolerance`
+/// (0 = exact match required). Images must have the same dimensions.
+pub fn diff_images(
+    baseline: &RgbaImage,
+    candidate: &RgbaImage,
+    tolerance: u8,
+) -> Result<DiffResult, String> {
+    if baseline.dimensions() != candidate.dimensions() {
+        let (bw, bh) = baseline.dimensions();
+        let (cw, ch) = candidate.dimensions();
+        return Err(format!(
+            "Image dimensions differ: baseline is {}x{}, candidate is {}x{}",
+            bw, bh, cw, ch
+        ));
+    }
+
+    let mut diff_image = RgbaImage::new(baseline.width(), baseline.height());
+    let mut differing_pixels = 0u64;
+    let mut max_delta = 0u8;
+
+    for (x, y, base) in baseline.enumerate_pixels() {
+        let delta = pixel_delta(base, candidate.get_pixel(x, y));
+        max_delta = max_delta.max(delta);
+        let out = if delta > tolerance {
+            differing_pixels += 1;
+            heat_color(delta)
+        } else {
+            dimmed_gray(base)
+        };
+        diff_image.put_pixel(x, y, out);
+    }
+
+    Ok(DiffResult {
+        diff_image,
+        differing_pixels,
+        total_pixels: baseline.width() as u64 * baseline.height() as u64,
+        max_delta,
+    })
+}
+
+/// Largest absolute per-channel difference between two pixels.
+fn pixel_delta(a: &Rgba<u8>, b: &Rgba<u8>) -> u8 {
+    a.0.iter().zip(b.0.iter()).map(|(x, y)| x.abs_diff(*y)).max().unwrap_or(0)
+}
+
+/// Red ramp from dark (small delta) to bright (max delta), always opaque.
+fn heat_color(delta: u8) -> Rgba<u8> {
+    let red = 128 + delta / 2;
+    Rgba([red, 0, 0, 255])
+}
+
+/// Baseline pixel as faint grayscale so differences stand out.
+fn dimmed_gray(p: &Rgba<u8>) -> Rgba<u8> {
+    let [r, g, b, _] = p.0;
+    let luma = (r as u32 * 299 + g as u32 * 587 + b as u32 * 114) / 1000;
+    let v = (luma / 4) as u8;
+    Rgba([v, v, v, 255])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn solid(w: u32, h: u32, color: [u8; 4]) -> RgbaImage {
+        RgbaImage::from_pixel(w, h, Rgba(color))
+    }
+
+    #[test]
+    fn identical_images_have_no_differences() {
+        let img = solid(4, 4, [10, 20, 30, 255]);
+        let result = diff_images(&img, &img, 0).unwrap();
+        assert_eq!(result.differing_pixels, 0);
+        assert_eq!(result.total_pixels, 16);
+        assert_eq!(result.max_delta, 0);
+        assert_eq!(result.fraction(), 0.0);
+    }
+
+    #[test]
+    fn changed_pixels_are_counted_and_highlighted() {
+        let baseline = solid(4, 4, [0, 0, 0, 255]);
+        let mut candidate = baseline.clone();
+        candidate.put_pixel(1, 2, Rgba([200, 0, 0, 255]));
+        candidate.put_pixel(3, 3, Rgba([0, 0, 0, 100]));
+
+        let result = diff_images(&baseline, &candidate, 0).unwrap();
+        assert_eq!(result.differing_pixels, 2);
+        assert_eq!(result.max_delta, 200);
+        assert_eq!(result.fraction(), 2.0 / 16.0);
+        assert_eq!(*result.diff_image.get_pixel(1, 2), heat_color(200));
+        assert_eq!(*result.diff_image.get_pixel(0, 0), Rgba([0, 0, 0, 255]));
+    }
+
+    #[test]
+    fn tolerance_ignores_small_deltas() {
+        let baseline = solid(2, 2, [100, 100, 100, 255]);
+        let candidate = solid(2, 2, [103, 98, 100, 255]);
+        assert_eq!(diff_images(&baseline, &candidate, 3).unwrap().differing_pixels, 0);
+        assert_eq!(diff_images(&baseline, &candidate, 2).unwrap().differing_pixels, 4);
+    }
+
+    #[test]
+    fn mismatched_dimensions_are_an_error() {
+        let err = diff_images(&solid(4, 4, [0; 4]), &solid(4, 5, [0; 4]), 0)
+            .err()
+            .unwrap();
+        assert!(err.contains("4x4") && err.contains("4x5"), "{err}");
+    }
+}
@@ -3,12 +3,19 @@
 //! Loads WoW TTF fonts into a cosmic-text FontSystem for text shaping,
 //! measurement, and glyph rasterization. Provides mapping from WoW font
 //! paths (e.g. `Fonts\\FRIZQT__.TTF`) to fontdb family names.
+//!
+//! Text is split into runs by glyph coverage: each character uses the
+//! requested font when it has the glyph, otherwise the first face in the
+//! fallback chain that does (Cyrillic, Hangul, Han), so non-Latin strings
+//! don't render as tofu.
 
 use std::collections::HashMap;
 use std::path::Path;
 
 use cosmic_text::fontdb;
 
+use super::text::{InlineTexture, MarkupSegment, parse_wow_markup};
+
 /// WoW font path constants (as they appear in Lua/XML).
 const WOW_FONT_FRIZ: &str = "Fonts\\FRIZQT__.TTF";
 const WOW_FONT_ARIAL_NARROW: &str = "Fonts\\ARIALN.TTF";
@@ -16,6 +23,17 @@ const WOW_FONT_ARIAL_NARROW: &str = "Fonts\\ARIALN.TTF";
 /// Default WoW font (Friz Quadrata).
 pub const DEFAULT_WOW_FONT: &str = WOW_FONT_FRIZ;
 
+/// Fallback faces by script, tried in order for characters the requested
+/// font lacks. File names are the locale fonts the client ships (ruRU, koKR,
+/// zhCN, zhTW) plus Noto CJK, matched case-insensitively in the fonts directory.
+const FALLBACK_FONT_FILES: &[(&str, &str)] = &[
+    ("Cyrillic", "frizqt___cyr.ttf"),
+    ("Hangul", "2002.ttf"),
+    ("Han (Simplified)", "ARKai_T.ttf"),
+    ("Han (Traditional)", "bLEI00D.ttf"),
+    ("CJK", "NotoSansCJK-Regular.ttc"),
+];
+
 /// Font entry mapping a WoW path to a fontdb family name.
 #[derive(Debug, Clone)]
 struct FontEntry {
@@ -32,12 +50,46 @@ pub struct WowFontSystem {
     pub swash_cache: cosmic_text::SwashCache,
     /// Map from normalized WoW font path (uppercase) to family name.
     font_map: HashMap<String, FontEntry>,
+    /// Fallback family names, in the order they are tried.
+    fallback_families: Vec<String>,
+    /// First loaded face for each family name, for glyph coverage checks.
+    face_ids: HashMap<String, fontdb::ID>,
+    /// Memoized glyph coverage per (face, character).
+    coverage: HashMap<(fontdb::ID, char), bool>,
+    /// Memoized inline-texture placeholder advance per (font path, size bits).
+    placeholder_widths: HashMap<(String, u32), f32>,
+}
+
+/// Character standing in for inline textures while shaping; no-break so a
+/// texture is never split across lines.
+const INLINE_TEXTURE_PLACEHOLDER: char = '\u{a0}';
+
+/// Markup-free text ready for shaping, plus the inline textures it stands in
+/// for (byte offset of the first placeholder, texture), its color changes
+/// (byte offset, color in effect from there or `None` for the base color) and
+/// its hyperlinks.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PreparedMarkup {
+    pub text: String,
+    pub textures: Vec<(usize, InlineTexture)>,
+    pub colors: Vec<(usize, Option<[f32; 3]>)>,
+    pub links: Vec<MarkupLink>,
+}
+
+/// A `|H<link>|h<text>|h` hyperlink in prepared text.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MarkupLink {
+    /// Link payload, e.g. `item:19019::::::::80`.
+    pub link: String,
+    /// Byte range of the linked text in the shaped string.
+    pub range: std::ops::Range<usize>,
 }
 
 impl std::fmt::Debug for WowFontSystem {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("WowFontSystem")
             .field("fonts", &self.font_map.keys().collect::<Vec<_>>())
+            .field("fallbacks", &self.fallback_families)
             .finish()
     }
 }
@@ -90,6 +142,15 @@ impl WowFontSystem {
             tracing::debug!("Loaded font {} -> family '{}'", filename, family_name);
         }
 
+        let fallback_families = load_fallback_fonts(&mut db, fonts_dir);
+
+        let mut face_ids = HashMap::new();
+        for face in db.faces() {
+            if let Some((family, _)) = face.families.first() {
+                face_ids.entry(family.clone()).or_insert(face.id);
+            }
+        }
+
         let font_system =
             cosmic_text::FontSystem::new_with_locale_and_db("en-US".to_string(), db);
         let swash_cache = cosmic_text::SwashCache::new();
@@ -98,9 +159,88 @@ impl WowFontSystem {
             font_system,
             swash_cache,
             font_map,
+            fallback_families,
+            face_ids,
+            coverage: HashMap::new(),
+            placeholder_widths: HashMap::new(),
         }
     }
 
+    /// Whether the family's face has a glyph for `ch`.
+    fn has_glyph(&mut self, family: &str, ch: char) -> bool {
+        let Some(&id) = self.face_ids.get(family) else {
+            return false;
+        };
+        let db = self.font_system.db();
+        *self.coverage.entry((id, ch)).or_insert_with(|| {
+            db.with_face_data(id, |data, index| {
+                ttf_parser::Face::parse(data, index)
+                    .is_ok_and(|face| face.glyph_index(ch).is_some())
+            })
+            .unwrap_or(false)
+        })
+    }
+
+    /// Family to render `ch` with: `primary` if it has the glyph, else the
+    /// first fallback that does, else `primary` (tofu).
+    pub fn family_for_char(&mut self, primary: &str, ch: char) -> String {
+        if ch.is_whitespace() || ch.is_control() || self.has_glyph(primary, ch) {
+            return primary.to_string();
+        }
+        let fallbacks = self.fallback_families.clone();
+        fallbacks
+            .into_iter()
+            .find(|family| self.has_glyph(family, ch))
+            .unwrap_or_else(|| primary.to_string())
+    }
+
+    /// Split `text` into byte ranges that share a family, applying the
+    /// fallback chain per character.
+    fn fallback_spans(&mut self, text: &str, primary: &str) -> Vec<(std::ops::Range<usize>, String)> {
+        let mut spans: Vec<(std::ops::Range<usize>, String)> = Vec::new();
+        for (i, ch) in text.char_indices() {
+            let end = i + ch.len_utf8();
+            // Whitespace joins the current run rather than splitting it.
+            if (ch.is_whitespace() || ch.is_control())
+                && let Some((range, _)) = spans.last_mut()
+            {
+                range.end = end;
+                continue;
+            }
+            let family = self.family_for_char(primary, ch);
+            match spans.last_mut() {
+                Some((range, last)) if *last == family => range.end = end,
+                _ => spans.push((i..end, family)),
+            }
+        }
+        spans
+    }
+
+    /// Set `text` on a buffer using the WoW font with per-character fallback.
+    pub fn set_buffer_text(
+        &mut self,
+        buffer: &mut cosmic_text::Buffer,
+        text: &str,
+        font_path: Option<&str>,
+    ) {
+        let attrs = self.attrs_owned(font_path);
+        let Some(primary) = self.family_name(font_path).map(str::to_string) else {
+            buffer.set_text(&mut self.font_system, text, &attrs.as_attrs(), cosmic_text::Shaping::Advanced, None);
+            return;
+        };
+        let spans = self.fallback_spans(text, &primary);
+        let rich = spans.iter().map(|(range, family)| {
+            (&text[range.clone()], attrs.as_attrs().family(cosmic_text::Family::Name(family)))
+        });
+        buffer.set_rich_text(
+            &mut self.font_system,
+            rich,
+            &attrs.as_attrs(),
+            cosmic_text::Shaping::Advanced,
+            None,
+        );
+    }
+
     /// Get the fontdb family name for a WoW font path.
     ///
     /// Falls back to the default WoW font (Friz Quadrata) if the path is
@@ -138,23 +278,41 @@ impl WowFontSystem {
     /// Measure the pixel width of a text string using cosmic-text shaping.
     ///
     /// `font_path` is the WoW font path (e.g. `Fonts\\FRIZQT__.TTF`).
-    /// Returns the width of the first layout line.
+    /// Returns the width of the first layout line. WoW markup is measured as
+    /// displayed: color codes and hyperlink wrappers take no space, and inline
+    /// textures (`|T...|t`) add their width.
     pub fn measure_text_width(&mut self, text: &str, font_path: Option<&str>, font_size: f32) -> f32 {
+        if !text.contains('|') {
+            return self.measure_plain_width(text, font_path, font_size);
+        }
+        let mut plain = String::new();
+        let mut textures_width = 0.0;
+        for segment in parse_wow_markup(text, font_size) {
+            match segment {
+                MarkupSegment::Text(t) => match t.split_once('\n') {
+                    Some((first_line, _)) => {
+                        plain.push_str(first_line);
+                        break;
+                    }
+                    None => plain.push_str(&t),
+                },
+                MarkupSegment::Texture(tex) => textures_width += tex.width,
+                MarkupSegment::Color(_) | MarkupSegment::Link(_) => {}
+            }
+        }
+        self.measure_plain_width(&plain, font_path, font_size) + textures_width
+    }
+
+    /// Width of the first line of markup-free text.
+    fn measure_plain_width(&mut self, text: &str, font_path: Option<&str>, font_size: f32) -> f32 {
         if text.is_empty() {
             return 0.0;
         }
         let line_height = (font_size * 1.2).ceil();
         let metrics = cosmic_text::Metrics::new(font_size, line_height);
-        let attrs = self.attrs_owned(font_path);
         let mut buffer = cosmic_text::Buffer::new(&mut self.font_system, metrics);
         buffer.set_size(&mut self.font_system, Some(10000.0), Some(line_height));
-        buffer.set_text(
-            &mut self.font_system,
-            text,
-            &attrs.as_attrs(),
-            cosmic_text::Shaping::Advanced,
-            None,
-        );
+        self.set_buffer_text(&mut buffer, text, font_path);
         buffer.shape_until_scroll(&mut self.font_system, true);
 
         buffer
@@ -164,6 +322,67 @@ impl WowFontSystem {
             .unwrap_or(0.0)
     }
 
+    /// Prepare WoW markup for shaping: strip it, and stand each inline texture
+    /// in with no-break spaces of about its width so line layout and wrapping
+    /// leave room for it. Textures are returned with the byte offset of their
+    /// first placeholder in the shaped text.
+    pub fn prepare_markup(&mut self, text: &str, font_path: Option<&str>, font_size: f32) -> PreparedMarkup {
+        let mut prepared = PreparedMarkup {
+            text: String::with_capacity(text.len()),
+            textures: Vec::new(),
+            colors: Vec::new(),
+            links: Vec::new(),
+        };
+        if !text.contains('|') {
+            prepared.text.push_str(text);
+            return prepared;
+        }
+        let mut open_link: Option<(String, usize)> = None;
+        // `|c` colors nest: `|r` returns to the enclosing `|c`, not the base color.
+        let mut color_stack: Vec<[f32; 3]> = Vec::new();
+        for segment in parse_wow_markup(text, font_size) {
+            match segment {
+                MarkupSegment::Text(t) => prepared.text.push_str(&t),
+                MarkupSegment::Color(Some(color)) => {
+                    color_stack.push(color);
+                    prepared.colors.push((prepared.text.len(), Some(color)));
+                }
+                MarkupSegment::Color(None) => {
+                    color_stack.pop();
+                    prepared.colors.push((prepared.text.len(), color_stack.last().copied()));
+                }
+                MarkupSegment::Link(Some(link)) => open_link = Some((link, prepared.text.len())),
+                MarkupSegment::Link(None) => {
+                    if let Some((link, start)) = open_link.take() {
+                        prepared.links.push(MarkupLink { link, range: start..prepared.text.len() });
+                    }
+                }
+                MarkupSegment::Texture(tex) => {
+                    let space = self.placeholder_width(font_path, font_size);
+                    let count = ((tex.width / space).round() as usize).max(1);
+                    prepared.textures.push((prepared.text.len(), tex));
+                    prepared.text.extend(std::iter::repeat_n(INLINE_TEXTURE_PLACEHOLDER, count));
+                }
+            }
+        }
+        prepared
+    }
+
+    /// Advance of one placeholder character, memoized per font and size.
+    fn placeholder_width(&mut self, font_path: Option<&str>, font_size: f32) -> f32 {
+        let key = (font_path.unwrap_or("").to_string(), font_size.to_bits());
+        if let Some(&width) = self.placeholder_widths.get(&key) {
+            return width;
+        }
+        // Measured between two letters: trailing whitespace has no line width.
+        let spaced = format!("x{}x", INLINE_TEXTURE_PLACEHOLDER);
+        let width = (self.measure_plain_width(&spaced, font_path, font_size)
+            - self.measure_plain_width("xx", font_path, font_size))
+        .max(1.0);
+        self.placeholder_widths.insert(key, width);
+        width
+    }
+
     /// Measure the pixel height of text, accounting for word wrapping.
     ///
     /// If `wrap_width` is Some and > 0, text wraps at that width.
@@ -175,34 +394,37 @@ impl WowFontSystem {
         font_size: f32,
         wrap_width: Option<f32>,
     ) -> f32 {
+        self.measure_text_wrapped(text, font_path, font_size, wrap_width.unwrap_or(0.0)).1
+    }
+
+    /// Measure text wrapped at `max_width` (no wrapping when `max_width <= 0`).
+    ///
+    /// Returns `(width, height, line_count)`: the widest line, and
+    /// `line_count * line_height`. Empty text measures as zero lines.
+    pub fn measure_text_wrapped(
+        &mut self,
+        text: &str,
+        font_path: Option<&str>,
+        font_size: f32,
+        max_width: f32,
+    ) -> (f32, f32, usize) {
         if text.is_empty() {
-            return 0.0;
+            return (0.0, 0.0, 0);
         }
         let line_height = (font_size * 1.2).ceil();
         let metrics = cosmic_text::Metrics::new(font_size, line_height);
-        let attrs = self.attrs_owned(font_path);
-        let shape_width = match wrap_width {
-            Some(w) if w > 0.0 => w,
-            _ => 10000.0,
-        };
+        let shape_width = if max_width > 0.0 { max_width } else { 10000.0 };
+        let prepared = self.prepare_markup(text, font_path, font_size);
         let mut buffer = cosmic_text::Buffer::new(&mut self.font_system, metrics);
         buffer.set_size(&mut self.font_system, Some(shape_width), Some(10000.0));
-        buffer.set_text(
-            &mut self.font_system,
-            text,
-            &attrs.as_attrs(),
-            cosmic_text::Shaping::Advanced,
-            None,
-        );
+        self.set_buffer_text(&mut buffer, &prepared.text, font_path);
         buffer.shape_until_scroll(&mut self.font_system, true);
 
-        let runs: Vec<_> = buffer.layout_runs().collect();
-        let num_lines = runs.len();
-        if num_lines <= 1 {
-            line_height
-        } else {
-            runs.last().map(|run| run.line_y + line_height).unwrap_or(line_height)
-        }
+        let (width, lines) = buffer
+            .layout_runs()
+            .fold((0.0_f32, 0_usize), |(w, n), run| (w.max(run.line_w), n + 1));
+        let lines = lines.max(1);
+        (width, lines as f32 * line_height, lines)
     }
 }
 
@@ -211,6 +433,40 @@ fn normalize_wow_path(path: &str) -> String {
     path.replace('/', "\\").to_uppercase()
 }
 
+/// Load the fallback faces present in `fonts_dir`, returning their family
+/// names in chain order. Faces already loaded as WoW fonts are not loaded twice.
+fn load_fallback_fonts(db: &mut fontdb::Database, fonts_dir: &Path) -> Vec<String> {
+    let mut families: Vec<String> = Vec::new();
+    for (script, filename) in FALLBACK_FONT_FILES {
+        let Some(path) = find_file_case_insensitive(fonts_dir, filename) else {
+            continue;
+        };
+        let Ok(data) = std::fs::read(&path) else {
+            tracing::warn!("Failed to read fallback font {}", path.display());
+            continue;
+        };
+        let family = fontdb_family_name(&data).unwrap_or_else(|| filename.to_string());
+        let loaded = db.faces().any(|f| f.families.iter().any(|(name, _)| *name == family));
+        if !loaded {
+            db.load_font_data(data);
+        }
+        if !families.contains(&family) {
+            tracing::debug!("Fallback font for {}: {} -> family '{}'", script, filename, family);
+            families.push(family);
+        }
+    }
+    families
+}
+
+/// Find a file in `dir` by case-insensitive name.
+fn find_file_case_insensitive(dir: &Path, name: &str) -> Option<std::path::PathBuf> {
+    std::fs::read_dir(dir)
+        .ok()?
+        .flatten()
+        .find(|entry| entry.file_name().to_string_lossy().eq_ignore_ascii_case(name))
+        .map(|entry| entry.path())
+}
+
 /// Extract the font family name from raw TTF data using fontdb.
 fn fontdb_family_name(data: &[u8]) -> Option<String> {
     // Parse the font to get its family name
@@ -322,6 +578,75 @@ mod tests {
         assert!(long > short, "Longer text should be wider: {long} > {short}");
     }
 
+    #[test]
+    fn cyrillic_falls_back_from_friz_quadrata() {
+        let mut fs = WowFontSystem::new(&fonts_dir());
+        let friz = fs.family_name(Some(WOW_FONT_FRIZ)).unwrap().to_string();
+        assert_eq!(fs.family_for_char(&friz, 'A'), friz);
+        // The western Friz Quadrata has no Cyrillic; frizqt___cyr.ttf does.
+        let cyr = fs.family_for_char(&friz, 'П');
+        assert_ne!(cyr, friz, "expected a Cyrillic fallback family");
+        assert!(fs.measure_text_width("Привет", Some(WOW_FONT_FRIZ), 14.0) > 0.0);
+    }
+
+    #[test]
+    #[ignore = "needs a system Noto Sans CJK font; run with --ignored where one is installed"]
+    fn hangul_measures_with_cjk_fallback() {
+        let cjk_candidates = [
+            "/usr/share/fonts/opentype/noto/NotoSansCJK-Regular.ttc",
+            "/usr/share/fonts/noto-cjk/NotoSansCJK-Regular.ttc",
+            "/usr/share/fonts/google-noto-cjk/NotoSansCJK-Regular.ttc",
+        ];
+        let cjk = cjk_candidates
+            .iter()
+            .map(PathBuf::from)
+            .find(|p| p.exists())
+            .expect("no Noto Sans CJK font installed");
+        let dir = tempfile::TempDir::new().unwrap();
+        for entry in std::fs::read_dir(fonts_dir()).unwrap().flatten() {
+            std::fs::copy(entry.path(), dir.path().join(entry.file_name())).unwrap();
+        }
+        std::fs::copy(&cjk, dir.path().join("NotoSansCJK-Regular.ttc")).unwrap();
+
+        let mut fs = WowFontSystem::new(dir.path());
+        let friz = fs.family_name(Some(WOW_FONT_FRIZ)).unwrap().to_string();
+        assert_ne!(fs.family_for_char(&friz, '봉'), friz);
+        let latin = fs.measure_text_width("Realm", Some(WOW_FONT_FRIZ), 14.0);
+        let mixed = fs.measure_text_width("Realm 봉은바람", Some(WOW_FONT_FRIZ), 14.0);
+        assert!(mixed > latin + 4.0 * 10.0, "Hangul should add real glyph widths: {mixed} vs {latin}");
+    }
+
+    #[test]
+    fn prepare_markup_reset_pops_to_enclosing_color() {
+        let mut fs = WowFontSystem::new(&fonts_dir());
+        let prepared = fs.prepare_markup("|cffff0000a|cff00ff00b|rc|rd", Some(WOW_FONT_FRIZ), 14.0);
+        assert_eq!(prepared.text, "abcd");
+        let red = [1.0, 0.0, 0.0];
+        assert_eq!(
+            prepared.colors,
+            vec![(0, Some(red)), (1, Some([0.0, 1.0, 0.0])), (2, Some(red)), (3, None)]
+        );
+    }
+
+    #[test]
+    fn measure_text_width_ignores_color_codes_and_links() {
+        let mut fs = WowFontSystem::new(&fonts_dir());
+        let plain = fs.measure_text_width("Hello", Some(WOW_FONT_FRIZ), 14.0);
+        let colored = fs.measure_text_width("|cffff0000Hello|r", Some(WOW_FONT_FRIZ), 14.0);
+        assert_eq!(colored, plain);
+        let link = fs.measure_text_width("|Hitem:1|h[Hello]|h", Some(WOW_FONT_FRIZ), 14.0);
+        assert_eq!(link, fs.measure_text_width("[Hello]", Some(WOW_FONT_FRIZ), 14.0));
+    }
+
+    #[test]
+    fn measure_text_width_adds_inline_texture_advance() {
+        let mut fs = WowFontSystem::new(&fonts_dir());
+        let plain = fs.measure_text_width("AB", Some(WOW_FONT_FRIZ), 14.0);
+        let with_icon =
+            fs.measure_text_width("A|TInterface\\Icons\\X:16|tB", Some(WOW_FONT_FRIZ), 14.0);
+        assert_eq!(with_icon, plain + 16.0);
+    }
+
     #[test]
     fn measure_text_height_single_line() {
         let mut fs = WowFontSystem::new(&fonts_dir());
@@ -342,6 +667,19 @@ mod tests {
         );
     }
 
+    #[test]
+    fn measure_text_wrapped_counts_lines() {
+        let mut fs = WowFontSystem::new(&fonts_dir());
+        let text = "This is a fairly long sentence that should wrap when given a narrow width constraint";
+        let line_height = (14.0_f32 * 1.2).ceil();
+        let (w, h, lines) = fs.measure_text_wrapped(text, Some(WOW_FONT_FRIZ), 14.0, 100.0);
+        assert!(lines > 1, "expected wrapping, got {lines} line(s)");
+        assert!(w <= 100.0, "wrapped lines should fit the width: {w}");
+        assert_eq!(h, lines as f32 * line_height);
+        let (_, h, lines) = fs.measure_text_wrapped("Hi", Some(WOW_FONT_FRIZ), 14.0, 100.0);
+        assert_eq!((h, lines), (line_height, 1));
+    }
+
     #[test]
     fn measure_text_height_empty_is_zero() {
         let mut fs = WowFontSystem::new(&fonts_dir());
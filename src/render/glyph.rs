@@ -8,10 +8,11 @@
 
 use std::collections::HashMap;
 
-use cosmic_text::{Buffer, CacheKey, Metrics, Shaping, SwashContent};
+use cosmic_text::{Buffer, CacheKey, Metrics, SwashContent};
 use iced::Rectangle;
 
-use super::font::WowFontSystem;
+use super::font::{MarkupLink, PreparedMarkup, WowFontSystem};
+use super::text::InlineTexture;
 use super::shader::{BlendMode, QuadBatch};
 use crate::widget::TextJustify;
 
@@ -42,20 +43,39 @@ struct ShapeCacheKey {
     shape_width_bits: u32,   // effective width
     bounds_height_bits: u32,
     max_lines: u32,
+    raster_scale_bits: u32,
 }
 
 /// A single glyph position extracted from a layout run.
 #[derive(Clone)]
 struct CachedGlyph {
     cache_key: CacheKey,
+    /// Pen position in raster pixels (logical pixels × `raster_scale`).
     x: i32,
     y: i32,
+    /// Byte offset of the glyph's text in the shaped string, to place inline
+    /// textures and color spans.
+    start: usize,
+    /// Advance width in logical pixels.
+    width: f32,
+}
+
+/// Where one line of a hyperlink's text was drawn, returned by
+/// `emit_text_quads` for hyperlink hit testing.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextLinkRect {
+    /// Link payload, e.g. `item:19019::::::::80`.
+    pub link: String,
+    /// The whole hyperlink as written: `|H<link>|h<text>|h`.
+    pub text: String,
+    pub rect: Rectangle,
 }
 
 /// Extracted layout run data for cache replay.
 #[derive(Clone)]
 struct CachedLayoutRun {
     line_y: f32,
+    line_top: f32,
     line_w: f32,
     glyphs: Vec<CachedGlyph>,
 }
@@ -88,6 +108,9 @@ pub struct GlyphAtlas {
     shape_cache_generation: u64,
     /// Unique path used to register this atlas in the GpuTextureAtlas.
     atlas_path: String,
+    /// Glyphs are rasterized at this multiple of their font size and their
+    /// quads shrunk back to logical size, for supersampled screenshots.
+    raster_scale: f32,
 }
 
 impl std::fmt::Debug for GlyphAtlas {
@@ -117,9 +140,21 @@ impl GlyphAtlas {
             shape_cache: HashMap::new(),
             shape_cache_generation: 0,
             atlas_path: "__glyph_atlas__".to_string(),
+            raster_scale: 1.0,
         }
     }
 
+    /// An atlas whose glyphs are rasterized at `scale`× for a render target
+    /// `scale` times larger than the logical layout.
+    pub fn with_raster_scale(scale: f32) -> Self {
+        Self { raster_scale: scale.max(1.0), ..Self::new() }
+    }
+
+    /// Rasterization multiple set by [`Self::with_raster_scale`].
+    pub fn raster_scale(&self) -> f32 {
+        self.raster_scale
+    }
+
     /// The unique texture path used to identify this atlas in the GPU texture system.
     pub fn atlas_path(&self) -> &str {
         &self.atlas_path
@@ -284,19 +319,11 @@ fn shape_text_to_runs(
 ) -> (Buffer, f32) {
     let line_height = (font_size * 1.2).ceil();
     let metrics = Metrics::new(font_size, line_height);
-    let attrs = font_system.attrs_owned(font_path);
-
     let shape_width = if word_wrap && bounds_width > 0.0 { bounds_width } else { 10000.0 };
 
     let mut buffer = Buffer::new(&mut font_system.font_system, metrics);
     buffer.set_size(&mut font_system.font_system, Some(shape_width), Some(bounds_height));
-    buffer.set_text(
-        &mut font_system.font_system,
-        text,
-        &attrs.as_attrs(),
-        Shaping::Advanced,
-        None,
-    );
+    font_system.set_buffer_text(&mut buffer, text, font_path);
     buffer.shape_until_scroll(&mut font_system.font_system, true);
 
     // Calculate total text height (for vertical justification).
@@ -318,7 +345,13 @@ fn shape_text_to_runs(
 }
 
 /// Extract glyph positions from layout runs into cacheable data.
-fn extract_layout_runs(buffer: &Buffer, max_lines: u32) -> Vec<CachedLayoutRun> {
+///
+/// cosmic-text reports glyph offsets within their paragraph; `text` (the
+/// shaped string) turns them back into offsets within the whole string.
+fn extract_layout_runs(buffer: &Buffer, text: &str, max_lines: u32, raster_scale: f32) -> Vec<CachedLayoutRun> {
+    let line_starts: Vec<usize> = std::iter::once(0)
+        .chain(text.match_indices('\n').map(|(i, _)| i + 1))
+        .collect();
     let runs: Vec<_> = buffer.layout_runs().collect();
     let runs_slice = if max_lines > 0 {
         &runs[..runs.len().min(max_lines as usize)]
@@ -328,20 +361,24 @@ fn extract_layout_runs(buffer: &Buffer, max_lines: u32) -> Vec<CachedLayoutRun>
     runs_slice
         .iter()
         .map(|run| {
+            let line_start = line_starts.get(run.line_i).copied().unwrap_or(0);
             let glyphs = run
                 .glyphs
                 .iter()
                 .map(|g| {
-                    let pg = g.physical((0.0, 0.0), 1.0);
-                    CachedGlyph { cache_key: pg.cache_key, x: pg.x, y: pg.y }
+                    let pg = g.physical((0.0, 0.0), raster_scale);
+                    CachedGlyph { cache_key: pg.cache_key, x: pg.x, y: pg.y, start: line_start + g.start, width: g.w }
                 })
                 .collect();
-            CachedLayoutRun { line_y: run.line_y, line_w: run.line_w, glyphs }
+            CachedLayoutRun { line_y: run.line_y, line_top: run.line_top, line_w: run.line_w, glyphs }
         })
         .collect()
 }
 
 /// Emit glyph quads from cached layout runs with a given color and offset.
+///
+/// `colors` are `|c`/`|r` changes by byte offset; glyphs inside a `|c` span
+/// take its RGB with `glyph_color`'s alpha.
 #[allow(clippy::too_many_arguments)]
 fn emit_glyphs_from_cache(
     batch: &mut QuadBatch,
@@ -352,29 +389,23 @@ fn emit_glyphs_from_cache(
     y_offset: f32,
     justify_h: TextJustify,
     glyph_color: [f32; 4],
+    colors: &[(usize, Option<[f32; 3]>)],
     offset_x: f32,
     offset_y: f32,
     glyph_tex_index: i32,
 ) {
+    let scale = glyph_atlas.raster_scale;
     for run in runs {
-        let x_offset = if bounds.width > 0.0 {
-            match justify_h {
-                TextJustify::Left => 0.0,
-                TextJustify::Center => (bounds.width - run.line_w) / 2.0,
-                TextJustify::Right => bounds.width - run.line_w,
-            }
-        } else {
-            0.0
-        };
-
+        let x_offset = justify_offset(run, bounds, justify_h);
         for glyph in &run.glyphs {
+            let glyph_color = span_color(colors, glyph.start, glyph_color);
             if let Some(entry) = glyph_atlas.ensure_glyph(font_system, glyph.cache_key) {
-                let glyph_x = bounds.x + x_offset + glyph.x as f32 + offset_x;
-                let glyph_y =
-                    bounds.y + y_offset + run.line_y + glyph.y as f32 - entry.top as f32 + offset_y;
+                let glyph_x = bounds.x + x_offset + glyph.x as f32 / scale + offset_x;
+                let glyph_y = bounds.y + y_offset + run.line_y
+                    + (glyph.y - entry.top) as f32 / scale + offset_y;
                 let glyph_bounds = Rectangle::new(
                     iced::Point::new(glyph_x, glyph_y),
-                    iced::Size::new(entry.width as f32, entry.height as f32),
+                    iced::Size::new(entry.width as f32 / scale, entry.height as f32 / scale),
                 );
                 let uv = Rectangle::new(
                     iced::Point::new(entry.uv_x, entry.uv_y),
@@ -386,6 +417,87 @@ fn emit_glyphs_from_cache(
     }
 }
 
+/// Color of the glyph at byte `start`: the last color change at or before it.
+fn span_color(colors: &[(usize, Option<[f32; 3]>)], start: usize, base: [f32; 4]) -> [f32; 4] {
+    let idx = colors.partition_point(|&(offset, _)| offset <= start);
+    match idx.checked_sub(1).and_then(|i| colors[i].1) {
+        Some([r, g, b]) => [r, g, b, base[3]],
+        None => base,
+    }
+}
+
+/// Horizontal offset of a line within `bounds` for the justification.
+fn justify_offset(run: &CachedLayoutRun, bounds: Rectangle, justify_h: TextJustify) -> f32 {
+    if bounds.width <= 0.0 {
+        return 0.0;
+    }
+    match justify_h {
+        TextJustify::Left => 0.0,
+        TextJustify::Center => (bounds.width - run.line_w) / 2.0,
+        TextJustify::Right => bounds.width - run.line_w,
+    }
+}
+
+/// Emit inline textures (`|T...|t`) at their placeholder glyphs, vertically
+/// centered on the line.
+#[allow(clippy::too_many_arguments)]
+fn emit_inline_textures(
+    batch: &mut QuadBatch,
+    runs: &[CachedLayoutRun],
+    textures: &[(usize, InlineTexture)],
+    bounds: Rectangle,
+    y_offset: f32,
+    line_height: f32,
+    justify_h: TextJustify,
+    alpha: f32,
+    raster_scale: f32,
+) {
+    for (offset, tex) in textures {
+        let placed = runs.iter().find_map(|run| {
+            let glyph = run.glyphs.iter().find(|g| g.start == *offset)?;
+            Some((run, glyph))
+        });
+        let Some((run, glyph)) = placed else { continue };
+        let x = bounds.x + justify_offset(run, bounds, justify_h) + glyph.x as f32 / raster_scale;
+        let y = bounds.y + y_offset + run.line_top + (line_height - tex.height) / 2.0;
+        let tex_bounds = Rectangle::new(iced::Point::new(x, y), iced::Size::new(tex.width, tex.height));
+        batch.push_textured_path(tex_bounds, &tex.path, [1.0, 1.0, 1.0, alpha], BlendMode::Alpha);
+    }
+}
+
+/// Rects covering each hyperlink's glyphs, one per line the link spans.
+#[allow(clippy::too_many_arguments)]
+fn link_rects(
+    runs: &[CachedLayoutRun],
+    links: &[MarkupLink],
+    stripped: &str,
+    bounds: Rectangle,
+    y_offset: f32,
+    line_height: f32,
+    justify_h: TextJustify,
+    raster_scale: f32,
+) -> Vec<TextLinkRect> {
+    let mut rects = Vec::new();
+    for link in links {
+        let text = format!("|H{}|h{}|h", link.link, stripped.get(link.range.clone()).unwrap_or(""));
+        for run in runs {
+            let span = run.glyphs.iter()
+                .filter(|g| link.range.contains(&g.start))
+                .map(|g| (g.x as f32 / raster_scale, g.x as f32 / raster_scale + g.width))
+                .reduce(|(l0, r0), (l1, r1)| (l0.min(l1), r0.max(r1)));
+            let Some((left, right)) = span else { continue };
+            let x = bounds.x + justify_offset(run, bounds, justify_h) + left;
+            let y = bounds.y + y_offset + run.line_top;
+            rects.push(TextLinkRect {
+                link: link.link.clone(),
+                text: text.clone(),
+                rect: Rectangle::new(iced::Point::new(x, y), iced::Size::new(right - left, line_height)),
+            });
+        }
+    }
+    rects
+}
+
 /// Measure the height of text after word-wrapping within the given width.
 ///
 /// Returns the total pixel height the text would occupy when rendered with
@@ -400,7 +512,7 @@ pub fn measure_text_height(
     bounds_width: f32,
     word_wrap: bool,
 ) -> f32 {
-    let stripped = crate::render::text::strip_wow_markup(text);
+    let stripped = font_system.prepare_markup(text, font_path, font_size).text;
     if stripped.is_empty() {
         return 0.0;
     }
@@ -412,6 +524,7 @@ pub fn measure_text_height(
         shape_width_bits: shape_width.to_bits(),
         bounds_height_bits: 10000.0_f32.to_bits(),
         max_lines: 0,
+        raster_scale_bits: glyph_atlas.raster_scale.to_bits(),
     };
     if let Some(entry) = glyph_atlas.shape_cache.get_mut(&key) {
         entry.last_used = glyph_atlas.shape_cache_generation;
@@ -421,7 +534,7 @@ pub fn measure_text_height(
         font_system, &stripped, font_path, font_size,
         bounds_width, 10000.0, word_wrap, 0,
     );
-    let runs = extract_layout_runs(&buffer, 0);
+    let runs = extract_layout_runs(&buffer, &stripped, 0, glyph_atlas.raster_scale);
     let generation = glyph_atlas.shape_cache_generation;
     glyph_atlas.shape_cache.insert(key, ShapeCacheEntry { runs, total_height, last_used: generation });
     total_height
@@ -431,6 +544,7 @@ pub fn measure_text_height(
 ///
 /// Shapes the text, rasterizes glyphs into the atlas, and pushes textured quads.
 /// The glyph atlas texture must be uploaded separately via `GlyphAtlas::texture_data()`.
+/// Returns where the text's hyperlinks were drawn.
 #[allow(clippy::too_many_arguments)]
 pub fn emit_text_quads(
     batch: &mut QuadBatch,
@@ -449,14 +563,15 @@ pub fn emit_text_quads(
     outline: crate::widget::TextOutline,
     word_wrap: bool,
     max_lines: u32,
-) {
+) -> Vec<TextLinkRect> {
     if text.is_empty() || bounds.height <= 0.0 {
-        return;
+        return Vec::new();
     }
 
-    let stripped = crate::render::text::strip_wow_markup(text);
+    let PreparedMarkup { text: stripped, textures: inline_textures, colors, links } =
+        font_system.prepare_markup(text, font_path, font_size);
     if stripped.is_empty() {
-        return;
+        return Vec::new();
     }
 
     // Phase 1: Populate cache if miss, extract runs + total_height.
@@ -468,6 +583,7 @@ pub fn emit_text_quads(
         shape_width_bits: shape_width.to_bits(),
         bounds_height_bits: bounds.height.to_bits(),
         max_lines,
+        raster_scale_bits: glyph_atlas.raster_scale.to_bits(),
     };
     let generation = glyph_atlas.shape_cache_generation;
     if !glyph_atlas.shape_cache.contains_key(&key) {
@@ -475,7 +591,7 @@ pub fn emit_text_quads(
             font_system, &stripped, font_path, font_size,
             bounds.width, bounds.height, word_wrap, max_lines,
         );
-        let runs = extract_layout_runs(&buffer, max_lines);
+        let runs = extract_layout_runs(&buffer, &stripped, max_lines, glyph_atlas.raster_scale);
         glyph_atlas.shape_cache.insert(
             key.clone(),
             ShapeCacheEntry { runs, total_height, last_used: generation },
@@ -494,8 +610,8 @@ pub fn emit_text_quads(
     };
 
     let emit = |batch: &mut QuadBatch, ga: &mut GlyphAtlas, fs: &mut WowFontSystem,
-                c: [f32; 4], ox: f32, oy: f32| {
-        emit_glyphs_from_cache(batch, ga, fs, &runs, bounds, y_offset, justify_h, c, ox, oy, glyph_tex_index);
+                c: [f32; 4], spans: &[(usize, Option<[f32; 3]>)], ox: f32, oy: f32| {
+        emit_glyphs_from_cache(batch, ga, fs, &runs, bounds, y_offset, justify_h, c, spans, ox, oy, glyph_tex_index);
     };
 
     // Render outline first (behind everything)
@@ -507,16 +623,25 @@ pub fn emit_text_quads(
             crate::widget::TextOutline::None => unreachable!(),
         };
         for &(dx, dy) in &[(-d, 0.0), (d, 0.0), (0.0, -d), (0.0, d), (-d, -d), (d, -d), (-d, d), (d, d)] {
-            emit(batch, glyph_atlas, font_system, outline_color, dx, dy);
+            emit(batch, glyph_atlas, font_system, outline_color, &[], dx, dy);
         }
     }
 
     // Render shadow (behind main text, in front of outline)
     if let Some(sc) = shadow_color
         && sc[3] > 0.0 {
-            emit(batch, glyph_atlas, font_system, sc, shadow_offset.0, shadow_offset.1);
+            emit(batch, glyph_atlas, font_system, sc, &[], shadow_offset.0, shadow_offset.1);
         }
 
-    // Render main text
-    emit(batch, glyph_atlas, font_system, color, 0.0, 0.0);
+    // Render main text, honoring |c color spans
+    emit(batch, glyph_atlas, font_system, color, &colors, 0.0, 0.0);
+
+    let line_height = (font_size * 1.2).ceil();
+    if !inline_textures.is_empty() {
+        emit_inline_textures(
+            batch, &runs, &inline_textures, bounds, y_offset, line_height, justify_h, color[3],
+            glyph_atlas.raster_scale,
+        );
+    }
+    link_rects(&runs, &links, &stripped, bounds, y_offset, line_height, justify_h, glyph_atlas.raster_scale)
 }
@@ -9,23 +9,42 @@ use image::RgbaImage;
 use super::shader::{GpuTextureData, QuadBatch, WowUiPrimitive};
 use crate::texture::TextureManager;
 
+/// Pixels and bookkeeping from one offscreen render.
+pub struct RenderOutput {
+    pub image: RgbaImage,
+    pub stats: RenderStats,
+}
+
+/// What went into a rendered image.
+#[derive(Debug, Clone, Default)]
+pub struct RenderStats {
+    pub quad_count: usize,
+    /// Unique texture paths uploaded for the batch.
+    pub textures_loaded: usize,
+    /// Requested texture paths that could not be loaded, in request order.
+    pub texture_misses: Vec<String>,
+}
+
 /// Load unique textures for all batch texture requests.
+///
+/// Returns the loaded textures and the paths that failed to load.
 fn load_batch_textures(
     batch: &QuadBatch,
     tex_mgr: &mut TextureManager,
-) -> Vec<GpuTextureData> {
+) -> (Vec<GpuTextureData>, Vec<String>) {
     let mut textures = Vec::new();
+    let mut misses = Vec::new();
     let mut seen = std::collections::HashSet::new();
     for request in batch.texture_requests.iter().chain(&batch.mask_texture_requests) {
-        if seen.contains(&request.path) {
+        if !seen.insert(request.path.clone()) {
             continue;
         }
-        if let Some(gpu_data) = super::shader::load_texture_or_crop(tex_mgr, &request.path) {
-            seen.insert(request.path.clone());
-            textures.push(gpu_data);
+        match super::shader::load_texture_or_crop(tex_mgr, &request.path) {
+            Some(gpu_data) => textures.push(gpu_data),
+            None => misses.push(request.path.clone()),
         }
     }
-    textures
+    (textures, misses)
 }
 
 /// Create a headless wgpu device and queue.
@@ -140,18 +159,43 @@ fn read_back_pixels(
 ///
 /// Creates a headless GPU device, sets up the same WowUiPipeline used by
 /// the iced GUI, and renders to an offscreen texture. The result is read
-/// back to CPU memory as an RgbaImage.
+/// back to CPU memory along with stats about the batch; nothing touches disk.
 ///
 /// When `glyph_atlas_data` is provided, text glyphs are rendered using the
 /// glyph atlas texture.
-pub fn render_to_image(
+pub fn render_scene(
     batch: &QuadBatch,
     tex_mgr: &mut TextureManager,
     width: u32,
     height: u32,
     glyph_atlas_data: Option<(&[u8], u32)>,
-) -> RgbaImage {
-    let textures = load_batch_textures(batch, tex_mgr);
+) -> RenderOutput {
+    render_scene_scaled(batch, tex_mgr, width, height, 1, glyph_atlas_data)
+}
+
+/// Like [`render_scene`], but supersampled: the batch (laid out at
+/// `width`×`height`) is drawn into a target `scale` times larger, then
+/// box-filtered back down to `width`×`height`.
+///
+/// Build the batch with a [`GlyphAtlas::with_raster_scale`] of the same
+/// `scale` so text is rasterized at the higher resolution too.
+///
+/// [`GlyphAtlas::with_raster_scale`]: super::GlyphAtlas::with_raster_scale
+pub fn render_scene_scaled(
+    batch: &QuadBatch,
+    tex_mgr: &mut TextureManager,
+    width: u32,
+    height: u32,
+    scale: u32,
+    glyph_atlas_data: Option<(&[u8], u32)>,
+) -> RenderOutput {
+    let scale = scale.max(1);
+    let (textures, texture_misses) = load_batch_textures(batch, tex_mgr);
+    let stats = RenderStats {
+        quad_count: batch.quad_count(),
+        textures_loaded: textures.len(),
+        texture_misses,
+    };
     let mut primitive = WowUiPrimitive::new_merged_with_textures(std::sync::Arc::new(batch.clone()), textures);
 
     if let Some((data, size)) = glyph_atlas_data {
@@ -161,22 +205,79 @@ pub fn render_to_image(
 
     let (device, queue) = create_headless_device();
     let format = wgpu::TextureFormat::Rgba8UnormSrgb;
+    let (target_w, target_h) = (width * scale, height * scale);
 
     use iced::widget::shader::Pipeline;
     let mut pipeline = super::shader::WowUiPipeline::new(&device, &queue, format);
-    let (render_texture, render_view) = create_render_target(&device, width, height, format);
+    let (render_texture, render_view) = create_render_target(&device, target_w, target_h, format);
 
-    // Prepare (uploads textures, resolves tex_index, uploads buffers)
+    // Prepare (uploads textures, resolves tex_index, uploads buffers).
+    // The viewport scale factor multiplies vertex positions up to the target.
     let bounds = iced::Rectangle::new(iced::Point::ORIGIN, iced::Size::new(width as f32, height as f32));
-    let viewport = iced::widget::shader::Viewport::with_physical_size(iced::Size::new(width, height), 1.0);
+    let viewport = iced::widget::shader::Viewport::with_physical_size(
+        iced::Size::new(target_w, target_h),
+        scale as f32,
+    );
     primitive.prepare(&mut pipeline, &device, &queue, &bounds, &viewport);
 
     // Render
     let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
         label: Some("Screenshot Encoder"),
     });
-    let clip_bounds_u32 = iced::Rectangle { x: 0u32, y: 0u32, width, height };
+    let clip_bounds_u32 = iced::Rectangle { x: 0u32, y: 0u32, width: target_w, height: target_h };
     pipeline.render_clear(&mut encoder, &render_view, &clip_bounds_u32, [0.05, 0.05, 0.08, 1.0]);
 
-    read_back_pixels(&device, &queue, encoder, &render_texture, width, height)
+    let image = read_back_pixels(&device, &queue, encoder, &render_texture, target_w, target_h);
+    let image = if scale > 1 { downsample_box(&image, scale) } else { image };
+    RenderOutput { image, stats }
+}
+
+/// Average each `factor`×`factor` block of `img` into one pixel. Color
+/// channels are averaged in linear light, so an edge half covered by white
+/// on black comes out at half the brightness rather than a darker sRGB 128.
+pub fn downsample_box(img: &RgbaImage, factor: u32) -> RgbaImage {
+    let to_linear: [f32; 256] = std::array::from_fn(|c| srgb_to_linear(c as f32 / 255.0));
+    let (w, h) = (img.width() / factor, img.height() / factor);
+    let samples = (factor * factor) as f32;
+    RgbaImage::from_fn(w, h, |x, y| {
+        let mut sum = [0f32; 4];
+        for dy in 0..factor {
+            for dx in 0..factor {
+                let p = img.get_pixel(x * factor + dx, y * factor + dy).0;
+                for (acc, c) in sum[..3].iter_mut().zip(&p[..3]) {
+                    *acc += to_linear[*c as usize];
+                }
+                sum[3] += p[3] as f32 / 255.0;
+            }
+        }
+        let [r, g, b, a] = sum.map(|c| c / samples);
+        let encode = |c: f32| (c.clamp(0.0, 1.0) * 255.0).round() as u8;
+        image::Rgba([
+            encode(linear_to_srgb(r)),
+            encode(linear_to_srgb(g)),
+            encode(linear_to_srgb(b)),
+            encode(a),
+        ])
+    })
+}
+
+fn srgb_to_linear(c: f32) -> f32 {
+    if c <= 0.04045 { c / 12.92 } else { ((c + 0.055) / 1.055).powf(2.4) }
+}
+
+fn linear_to_srgb(c: f32) -> f32 {
+    if c <= 0.0031308 { c * 12.92 } else { 1.055 * c.powf(1.0 / 2.4) - 0.055 }
+}
+
+/// Render a QuadBatch to an RGBA image, dropping the stats.
+///
+/// See [`render_scene`].
+pub fn render_to_image(
+    batch: &QuadBatch,
+    tex_mgr: &mut TextureManager,
+    width: u32,
+    height: u32,
+    glyph_atlas_data: Option<(&[u8], u32)>,
+) -> RgbaImage {
+    render_scene(batch, tex_mgr, width, height, glyph_atlas_data).image
 }
@@ -2,6 +2,7 @@
 //!
 //! Provides both canvas-based (CPU) and shader-based (GPU) rendering.
 
+pub mod diff;
 pub mod font;
 pub mod glyph;
 pub mod shader;
@@ -15,6 +15,7 @@ pub use pipeline::WowUiPipeline;
 pub use primitive::{GpuTextureData, WowUiPrimitive, load_texture_or_crop};
 pub use program::WowUiProgram;
 pub use quad::FLAG_CIRCLE_CLIP;
+pub use quad::FLAG_COOLDOWN_SWIPE;
 pub use quad::FLAG_DESATURATE;
 pub use quad::{BlendMode, QuadBatch, QuadVertex, TextureRequest};
 pub use quad_nine_slice::NineSliceTextures;
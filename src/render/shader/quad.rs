@@ -97,6 +97,9 @@ pub struct QuadBatch {
     pub texture_requests: Vec<TextureRequest>,
     /// Mask texture requests — resolved into mask_tex_index/mask_tex_coords during prepare.
     pub mask_texture_requests: Vec<TextureRequest>,
+    /// Hyperlinks drawn per MessageFrame/SimpleHTML frame, for the caller to
+    /// store with `SimState::set_hyperlink_regions`.
+    pub hyperlinks: Vec<(u64, Vec<crate::lua_api::HyperlinkRegion>)>,
 }
 
 impl QuadBatch {
@@ -112,6 +115,7 @@ impl QuadBatch {
             indices: Vec::with_capacity(quad_count * 6),
             texture_requests: Vec::new(),
             mask_texture_requests: Vec::new(),
+            hyperlinks: Vec::new(),
         }
     }
 
@@ -121,6 +125,7 @@ impl QuadBatch {
         self.indices.clear();
         self.texture_requests.clear();
         self.mask_texture_requests.clear();
+        self.hyperlinks.clear();
     }
 
     /// Number of quads in the batch.
@@ -620,6 +625,54 @@ impl QuadBatch {
         );
     }
 
+    /// Push the 8 edge/corner pieces of a legacy backdrop edge file.
+    ///
+    /// The edge texture is a strip of 8 equal segments: left, right, top,
+    /// bottom, top-left, top-right, bottom-left, bottom-right. Top and bottom
+    /// segments are stored rotated, so they are drawn with rotated UVs.
+    ///
+    /// # Arguments
+    /// * `bounds` - Outer bounds of the backdrop
+    /// * `edge_size` - Edge/corner thickness in pixels
+    /// * `path` - Edge texture path
+    /// * `color` - Border tint
+    pub fn push_backdrop_edges(&mut self, bounds: Rectangle, edge_size: f32, path: &str, color: [f32; 4]) {
+        let e = edge_size.min(bounds.width / 2.0).min(bounds.height / 2.0);
+        if e <= 0.0 {
+            return;
+        }
+        let seg = |i: f32| (i / 8.0, (i + 1.0) / 8.0);
+        let rect = |x: f32, y: f32, w: f32, h: f32| {
+            Rectangle::new(iced::Point::new(x, y), iced::Size::new(w, h))
+        };
+        let (left, top) = (bounds.x, bounds.y);
+        let (right, bottom) = (bounds.x + bounds.width - e, bounds.y + bounds.height - e);
+        let inner_w = bounds.width - 2.0 * e;
+        let inner_h = bounds.height - 2.0 * e;
+
+        // Left/right edges and corners map their segment directly.
+        let straight = [
+            (0.0, rect(left, top + e, e, inner_h)),
+            (1.0, rect(right, top + e, e, inner_h)),
+            (4.0, rect(left, top, e, e)),
+            (5.0, rect(right, top, e, e)),
+            (6.0, rect(left, bottom, e, e)),
+            (7.0, rect(right, bottom, e, e)),
+        ];
+        for (i, piece) in straight {
+            let (u0, u1) = seg(i);
+            let uvs = rect(u0, 0.0, u1 - u0, 1.0);
+            self.push_textured_path_uv(piece, uvs, path, color, BlendMode::Alpha);
+        }
+
+        // Top/bottom edges: the segment's V axis runs along screen X.
+        for (i, piece) in [(2.0, rect(left + e, top, inner_w, e)), (3.0, rect(left + e, bottom, inner_w, e))] {
+            let (u0, u1) = seg(i);
+            let uvs = [[u0, 1.0], [u0, 0.0], [u1, 0.0], [u1, 1.0]];
+            self.push_textured_path_uv4(piece, uvs, path, color, BlendMode::Alpha);
+        }
+    }
+
     /// Append all quads from another batch, adjusting indices.
     pub fn append(&mut self, other: &QuadBatch) {
         let base = self.vertices.len() as u32;
@@ -112,63 +112,191 @@ pub fn wow_font_to_iced(font_path: Option<&str>) -> Font {
     }
 }
 
-/// Strip WoW markup from text: textures (`|T...|t`), atlases (`|A...|a`),
-/// colors (`|cXXXXXXXX`/`|r`), and hyperlinks (`|H...|h`/`|h`).
-/// Preserves plain text content visible to the player.
-pub fn strip_wow_markup(text: &str) -> String {
-    let mut result = String::with_capacity(text.len());
+/// An inline texture escape: `|Tpath:height[:width[:offsetX:offsetY...]]|t`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct InlineTexture {
+    pub path: String,
+    pub width: f32,
+    pub height: f32,
+}
+
+impl InlineTexture {
+    /// Parse the inside of a `|T...|t` escape. A height of 0 (or none) means
+    /// the line's font size; a missing or 0 width means square.
+    pub fn parse(spec: &str, font_size: f32) -> Self {
+        let mut parts = spec.split(':');
+        let path = parts.next().unwrap_or_default().to_string();
+        let mut size = || parts.next().and_then(|p| p.trim().parse::<f32>().ok()).filter(|v| *v > 0.0);
+        let height = size().unwrap_or(font_size);
+        let width = size().unwrap_or(height);
+        Self { path, width, height }
+    }
+}
+
+/// A piece of WoW-markup text: visible characters, an inline texture, a
+/// color change (`Some` for `|cAARRGGBB`, `None` for `|r`), or a hyperlink
+/// boundary (`Some(link)` for `|H<link>|h`, `None` for the closing `|h`).
+#[derive(Debug, Clone, PartialEq)]
+pub enum MarkupSegment {
+    Text(String),
+    Texture(InlineTexture),
+    Color(Option<[f32; 3]>),
+    Link(Option<String>),
+}
+
+/// Parse WoW markup into visible text, inline textures, color changes and
+/// hyperlink boundaries, in order.
+///
+/// The linked text of a hyperlink stays visible between its `Link` segments;
+/// atlases (`|A...|a`) are dropped. The alpha byte of `|c` is ignored, as in
+/// the client.
+pub fn parse_wow_markup(text: &str, font_size: f32) -> Vec<MarkupSegment> {
+    let mut segments = Vec::new();
+    let mut current = String::with_capacity(text.len());
     let mut chars = text.chars().peekable();
 
+    // Consume up to and including `|<marker>`, returning what came before.
+    fn take_until(chars: &mut std::iter::Peekable<std::str::Chars<'_>>, marker: char) -> String {
+        let mut inner = String::new();
+        while let Some(ch) = chars.next() {
+            if ch == '|' && chars.peek() == Some(&marker) {
+                chars.next();
+                break;
+            }
+            inner.push(ch);
+        }
+        inner
+    }
+
+    let push = |segments: &mut Vec<MarkupSegment>, current: &mut String, segment| {
+        if !current.is_empty() {
+            segments.push(MarkupSegment::Text(std::mem::take(current)));
+        }
+        segments.push(segment);
+    };
+
     while let Some(c) = chars.next() {
-        if c == '|' {
-            if let Some(&next) = chars.peek() {
-                // |T...|t or |A...|a — inline texture/atlas
-                if next == 'T' || next == 'A' {
-                    let end_marker = if next == 'T' { 't' } else { 'a' };
+        if c == '|'
+            && let Some(&next) = chars.peek()
+        {
+            match next {
+                // |T...|t — inline texture
+                'T' => {
                     chars.next();
-                    while let Some(ch) = chars.next() {
-                        if ch == '|'
-                            && let Some(&marker) = chars.peek()
-                                && marker == end_marker {
-                                    chars.next();
-                                    break;
-                                }
-                    }
+                    let spec = take_until(&mut chars, 't');
+                    push(&mut segments, &mut current, MarkupSegment::Texture(InlineTexture::parse(&spec, font_size)));
                     continue;
                 }
-                // |H...|h — hyperlink open tag (skip tag, keep linked text)
-                if next == 'H' {
+                // |A...|a — atlas
+                'A' => {
                     chars.next();
-                    while let Some(ch) = chars.next() {
-                        if ch == '|' && chars.peek() == Some(&'h') {
-                            chars.next();
-                            break;
-                        }
-                    }
+                    take_until(&mut chars, 'a');
                     continue;
                 }
-                // |h — hyperlink close tag
-                if next == 'h' {
+                // |H...|h — hyperlink open tag (the linked text follows)
+                'H' => {
                     chars.next();
+                    let link = take_until(&mut chars, 'h');
+                    push(&mut segments, &mut current, MarkupSegment::Link(Some(link)));
                     continue;
                 }
-                // |cXXXXXXXX — color start
-                if next == 'c' {
+                // |h — hyperlink close tag
+                'h' => {
                     chars.next();
-                    for _ in 0..8 {
-                        chars.next();
-                    }
+                    push(&mut segments, &mut current, MarkupSegment::Link(None));
                     continue;
                 }
                 // |r — color reset
-                if next == 'r' {
+                'r' => {
+                    chars.next();
+                    push(&mut segments, &mut current, MarkupSegment::Color(None));
+                    continue;
+                }
+                // |cAARRGGBB — color start
+                'c' => {
                     chars.next();
+                    let hex: String = chars.by_ref().take(8).collect();
+                    if let Some(rgb) = parse_markup_color(&hex) {
+                        push(&mut segments, &mut current, MarkupSegment::Color(Some(rgb)));
+                    }
                     continue;
                 }
+                _ => {}
             }
         }
-        result.push(c);
+        current.push(c);
+    }
+    if !current.is_empty() {
+        segments.push(MarkupSegment::Text(current));
+    }
+    segments
+}
+
+/// RGB from the `AARRGGBB` digits of a `|c` escape.
+fn parse_markup_color(hex: &str) -> Option<[f32; 3]> {
+    if hex.len() != 8 || !hex.is_ascii() {
+        return None;
     }
+    let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok().map(|v| v as f32 / 255.0);
+    Some([channel(2)?, channel(4)?, channel(6)?])
+}
 
-    result
+/// Strip WoW markup from text: textures (`|T...|t`), atlases (`|A...|a`),
+/// colors (`|cXXXXXXXX`/`|r`), and hyperlinks (`|H...|h`/`|h`).
+/// Preserves plain text content visible to the player.
+pub fn strip_wow_markup(text: &str) -> String {
+    parse_wow_markup(text, 0.0)
+        .into_iter()
+        .filter_map(|seg| match seg {
+            MarkupSegment::Text(t) => Some(t),
+            MarkupSegment::Texture(_) | MarkupSegment::Color(_) | MarkupSegment::Link(_) => None,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_colors_links_and_textures() {
+        let segments = parse_wow_markup(
+            "|cffff0000Hi|r |Hitem:19019|h[Thunderfury]|h|TInterface\\Icons\\X:16|t!",
+            12.0,
+        );
+        assert_eq!(
+            segments,
+            vec![
+                MarkupSegment::Color(Some([1.0, 0.0, 0.0])),
+                MarkupSegment::Text("Hi".into()),
+                MarkupSegment::Color(None),
+                MarkupSegment::Text(" ".into()),
+                MarkupSegment::Link(Some("item:19019".into())),
+                MarkupSegment::Text("[Thunderfury]".into()),
+                MarkupSegment::Link(None),
+                MarkupSegment::Texture(InlineTexture {
+                    path: "Interface\\Icons\\X".into(),
+                    width: 16.0,
+                    height: 16.0,
+                }),
+                MarkupSegment::Text("!".into()),
+            ]
+        );
+    }
+
+    #[test]
+    fn inline_texture_sizes_default_to_font_size() {
+        assert_eq!(InlineTexture::parse("Foo:0", 14.0).height, 14.0);
+        let t = InlineTexture::parse("Foo:16:32:0:0", 14.0);
+        assert_eq!((t.width, t.height), (32.0, 16.0));
+        assert_eq!(strip_wow_markup("a|A atlas:12|ab"), "ab");
+    }
+
+    #[test]
+    fn malformed_color_escape_is_dropped() {
+        assert_eq!(
+            parse_wow_markup("|cffzz0000Hi", 12.0),
+            vec![MarkupSegment::Text("Hi".into())]
+        );
+    }
 }
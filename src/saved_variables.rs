@@ -11,7 +11,8 @@
 //! 2. Simulator storage (~/.local/share/wow-sim/SavedVariables/)
 
 use mlua::{Lua, Result, Table, Value};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::ffi::c_void;
 use std::fmt::Write;
 use std::fs;
 use std::path::PathBuf;
@@ -86,6 +87,8 @@ pub struct SavedVariablesManager {
     wtf_config: Option<WtfConfig>,
     /// Track which addons have had WTF variables loaded.
     wtf_loaded: HashMap<String, bool>,
+    /// Write all variables back to storage when the simulator exits.
+    save_on_exit: bool,
 }
 
 impl SavedVariablesManager {
@@ -104,6 +107,7 @@ impl SavedVariablesManager {
             registered_per_char: HashMap::new(),
             wtf_config: None,
             wtf_loaded: HashMap::new(),
+            save_on_exit: false,
         }
     }
 
@@ -117,6 +121,7 @@ impl SavedVariablesManager {
             registered_per_char: HashMap::new(),
             wtf_config: None,
             wtf_loaded: HashMap::new(),
+            save_on_exit: false,
         }
     }
 
@@ -134,6 +139,27 @@ impl SavedVariablesManager {
         self.wtf_config = Some(config);
     }
 
+    /// Enable writing SavedVariables back to storage on exit (on by default in
+    /// the GUI, `--save-vars-on-exit` for headless commands).
+    pub fn set_save_on_exit(&mut self, enabled: bool) {
+        self.save_on_exit = enabled;
+    }
+
+    /// Realm used for per-character variables (the WTF config's realm when set).
+    pub fn realm_name(&self) -> &str {
+        &self.realm_name
+    }
+
+    /// Whether SavedVariables should be written back on exit.
+    pub fn save_on_exit(&self) -> bool {
+        self.save_on_exit
+    }
+
+    /// Directory SavedVariables are written to.
+    pub fn storage_dir(&self) -> &std::path::Path {
+        &self.storage_dir
+    }
+
     /// Get a reference to the WTF configuration.
     pub fn wtf_config(&self) -> Option<&WtfConfig> {
         self.wtf_config.as_ref()
@@ -141,62 +167,46 @@ impl SavedVariablesManager {
 
     /// Load WTF saved variables for an addon from the real WoW installation.
     /// This executes the Lua files to set global variables.
-    /// Returns the number of files loaded (0, 1, or 2 for account + character).
-    pub fn load_wtf_for_addon(&mut self, lua: &Lua, addon_name: &str) -> Result<usize> {
+    /// Returns the number of files loaded (0, 1, or 2 for account + character)
+    /// and a warning for each file that failed to load.
+    pub fn load_wtf_for_addon(&mut self, lua: &Lua, addon_name: &str) -> (usize, Vec<String>) {
+        let mut warnings = Vec::new();
         let config = match &self.wtf_config {
             Some(c) => c.clone(),
-            None => return Ok(0), // No WTF config, skip
+            None => return (0, warnings), // No WTF config, skip
         };
 
         // Skip if already loaded
         if self.wtf_loaded.contains_key(addon_name) {
-            return Ok(0);
+            return (0, warnings);
         }
 
         let mut loaded = 0;
-
-        // Load account-level SavedVariables
-        let account_file = config.account_saved_vars_file(addon_name);
-        if account_file.exists() {
-            match self.load_wtf_lua_file(lua, &account_file) {
+        let files = [
+            config.account_saved_vars_file(addon_name),
+            config.character_saved_vars_file(addon_name),
+        ];
+        for file in files.iter().filter(|f| f.exists()) {
+            match self.load_lua_file(lua, file) {
                 Ok(()) => loaded += 1,
-                Err(e) => {
-                    tracing::warn!(
-                        "Failed to load account SavedVariables for {}: {}",
-                        addon_name,
-                        e
-                    );
-                }
-            }
-        }
-
-        // Load character-level SavedVariables
-        let char_file = config.character_saved_vars_file(addon_name);
-        if char_file.exists() {
-            match self.load_wtf_lua_file(lua, &char_file) {
-                Ok(()) => loaded += 1,
-                Err(e) => {
-                    tracing::warn!(
-                        "Failed to load character SavedVariables for {}: {}",
-                        addon_name,
-                        e
-                    );
-                }
+                Err(e) => warnings.push(describe_load_error(addon_name, file, &e)),
             }
         }
 
         self.wtf_loaded.insert(addon_name.to_string(), loaded > 0);
-        Ok(loaded)
+        (loaded, warnings)
     }
 
-    /// Load a WTF Lua file, executing it to set global variables.
-    fn load_wtf_lua_file(&self, lua: &Lua, path: &std::path::Path) -> Result<()> {
+    /// Execute a SavedVariables Lua file to set global variables.
+    ///
+    /// The chunk is named after the full path, so syntax and runtime errors
+    /// read `<path>:<line>: <message>`.
+    fn load_lua_file(&self, lua: &Lua, path: &std::path::Path) -> Result<()> {
         let content = fs::read_to_string(path).map_err(mlua::Error::external)?;
         // Strip UTF-8 BOM if present
         let content = content.strip_prefix('\u{feff}').unwrap_or(&content);
 
-        // Execute the Lua file - it will set global variables
-        let chunk_name = format!("@WTF/{}", path.file_name().unwrap_or_default().to_string_lossy());
+        let chunk_name = format!("@{}", path.display());
         lua.load(content).set_name(&chunk_name).exec()?;
         Ok(())
     }
@@ -215,34 +225,22 @@ impl SavedVariablesManager {
     }
 
     /// Initialize saved variables for an addon before it loads.
-    /// This creates empty tables in Lua globals for each declared variable,
-    /// then loads any existing saved data into them.
+    ///
+    /// Variables that are still nil are loaded from simulator storage; any
+    /// left unset (no file, or the file failed to load) become empty tables so
+    /// the addon can still run. Returns a warning per storage file that failed.
     pub fn init_for_addon(
         &mut self,
         lua: &Lua,
         addon_name: &str,
         saved_vars: &[String],
         saved_vars_per_char: &[String],
-    ) -> Result<()> {
-        let globals = lua.globals();
-
-        // Initialize account-wide variables
-        for var_name in saved_vars {
-            // Only initialize if not already set
-            if globals.get::<Value>(var_name.as_str())?.is_nil() {
-                // Load from storage or create empty table
-                let table = self.load_variable(lua, addon_name, var_name, false)?;
-                globals.set(var_name.as_str(), table)?;
-            }
-        }
-
-        // Initialize per-character variables
-        for var_name in saved_vars_per_char {
-            if globals.get::<Value>(var_name.as_str())?.is_nil() {
-                let table = self.load_variable(lua, addon_name, var_name, true)?;
-                globals.set(var_name.as_str(), table)?;
-            }
-        }
+    ) -> Result<Vec<String>> {
+        let mut warnings = Vec::new();
+        let account_path = self.account_path(addon_name);
+        let character_path = self.character_path(addon_name);
+        self.init_variables(lua, addon_name, saved_vars, &account_path, &mut warnings)?;
+        self.init_variables(lua, addon_name, saved_vars_per_char, &character_path, &mut warnings)?;
 
         // Track registered variables
         if !saved_vars.is_empty() {
@@ -254,34 +252,39 @@ impl SavedVariablesManager {
                 .insert(addon_name.to_string(), saved_vars_per_char.to_vec());
         }
 
-        Ok(())
+        Ok(warnings)
     }
 
-    /// Load a single variable from storage by executing the saved .lua file.
-    fn load_variable(
+    /// Load `vars` from the storage file at `path` (executed once), then
+    /// default any still-nil variable to an empty table.
+    fn init_variables(
         &self,
         lua: &Lua,
         addon_name: &str,
-        var_name: &str,
-        per_character: bool,
-    ) -> Result<Table> {
-        let path = if per_character {
-            self.character_path(addon_name)
-        } else {
-            self.account_path(addon_name)
-        };
+        vars: &[String],
+        path: &std::path::Path,
+        warnings: &mut Vec<String>,
+    ) -> Result<()> {
+        let globals = lua.globals();
+        let is_unset = |var: &String| globals.get::<Value>(var.as_str()).map(|v| v.is_nil());
 
-        if path.exists() {
-            // Execute the Lua file to set globals, then retrieve the variable
-            self.load_wtf_lua_file(lua, &path)?;
-            let val: Value = lua.globals().get(var_name)?;
-            if let Value::Table(t) = val {
-                return Ok(t);
-            }
+        let mut any_unset = false;
+        for var in vars {
+            any_unset |= is_unset(var)?;
+        }
+        if any_unset
+            && path.exists()
+            && let Err(e) = self.load_lua_file(lua, path)
+        {
+            warnings.push(describe_load_error(addon_name, path, &e));
         }
 
-        // No existing data, return empty table
-        lua.create_table()
+        for var in vars {
+            if is_unset(var)? {
+                globals.set(var.as_str(), lua.create_table()?)?;
+            }
+        }
+        Ok(())
     }
 
     /// Save all registered variables for an addon in WoW-compatible Lua format.
@@ -294,7 +297,8 @@ impl SavedVariablesManager {
                 &globals,
                 vars,
                 &self.account_path(addon_name),
-            );
+            )
+            .map_err(mlua::Error::external)?;
         }
 
         // Save per-character variables
@@ -303,15 +307,15 @@ impl SavedVariablesManager {
                 &globals,
                 vars,
                 &self.character_path(addon_name),
-            );
+            )
+            .map_err(mlua::Error::external)?;
         }
 
-        let _ = lua;
         Ok(())
     }
 
     /// Write variable values to a .lua file in WoW SavedVariables format.
-    fn write_vars_file(&self, globals: &Table, vars: &[String], path: &PathBuf) {
+    fn write_vars_file(&self, globals: &Table, vars: &[String], path: &PathBuf) -> std::io::Result<()> {
         let mut output = String::from("\n");
         let mut has_data = false;
 
@@ -320,42 +324,66 @@ impl SavedVariablesManager {
                 Ok(v) => v,
                 Err(_) => continue,
             };
-            serialize_assignment(&mut output, var_name, &val);
+            let cycles = serialize_assignment(&mut output, var_name, &val);
+            if cycles > 0 {
+                tracing::warn!(
+                    "{}: {} contains {} reference cycle(s), written as nil",
+                    path.display(),
+                    var_name,
+                    cycles
+                );
+            }
             has_data = true;
         }
 
         if has_data {
             if let Some(parent) = path.parent() {
-                let _ = fs::create_dir_all(parent);
+                fs::create_dir_all(parent)?;
             }
-            let _ = fs::write(path, output);
+            fs::write(path, output)?;
         }
+        Ok(())
     }
 
     /// Save all registered variables for all addons.
     pub fn save_all(&self, lua: &Lua) -> Result<()> {
-        let addon_names: Vec<String> = self
-            .registered
-            .keys()
-            .chain(self.registered_per_char.keys())
-            .cloned()
-            .collect();
-
-        for addon_name in addon_names {
-            self.save_addon(lua, &addon_name)?;
+        for addon_name in self.registered_addons() {
+            self.save_addon(lua, addon_name)?;
         }
         Ok(())
     }
 
+    /// Save every addon's SavedVariables from a live environment.
+    /// Returns the number of addons written.
+    pub fn save(&self, env: &crate::lua_api::WowLuaEnv) -> Result<usize> {
+        self.save_all(env.lua())?;
+        Ok(self.registered_addons().len())
+    }
+
     /// Get list of registered addons.
     pub fn registered_addons(&self) -> Vec<&String> {
-        self.registered
+        let mut names: Vec<&String> = self
+            .registered
             .keys()
             .chain(self.registered_per_char.keys())
-            .collect()
+            .collect();
+        names.sort();
+        names.dedup();
+        names
     }
 }
 
+/// Describe a SavedVariables file that failed to load. Lua errors already
+/// carry `<path>:<line>:`; the addon's variables fall back to empty tables.
+fn describe_load_error(addon_name: &str, path: &std::path::Path, err: &mlua::Error) -> String {
+    let msg = match err {
+        mlua::Error::SyntaxError { message, .. } => message.clone(),
+        mlua::Error::RuntimeError(message) => message.clone(),
+        other => format!("{}: {}", path.display(), other),
+    };
+    format!("SavedVariables for {} not loaded (using empty defaults): {}", addon_name, msg)
+}
+
 impl Default for SavedVariablesManager {
     fn default() -> Self {
         Self::new()
@@ -363,114 +391,186 @@ impl Default for SavedVariablesManager {
 }
 
 /// Serialize a top-level `VarName = value` assignment in WoW SavedVariables format.
-fn serialize_assignment(out: &mut String, name: &str, value: &Value) {
+/// Returns the number of reference cycles that were cut.
+fn serialize_assignment(out: &mut String, name: &str, value: &Value) -> usize {
     let _ = write!(out, "{} = ", name);
-    serialize_value(out, value, 0);
+    let mut visit = CycleGuard::default();
+    serialize_value(out, value, 0, &mut visit);
     out.push('\n');
+    visit.cycles
+}
+
+/// Tables on the path currently being serialized. A table reached again
+/// through one of its own descendants is a cycle and is written as `nil`.
+#[derive(Default)]
+struct CycleGuard {
+    ancestors: HashSet<*const c_void>,
+    cycles: usize,
 }
 
 /// Serialize a Lua value to WoW SavedVariables format.
-fn serialize_value(out: &mut String, value: &Value, depth: usize) {
+fn serialize_value(out: &mut String, value: &Value, depth: usize, visit: &mut CycleGuard) {
     match value {
         Value::Nil => out.push_str("nil"),
         Value::Boolean(b) => out.push_str(if *b { "true" } else { "false" }),
         Value::Integer(i) => {
             let _ = write!(out, "{}", i);
         }
-        Value::Number(n) => {
-            // Match WoW's format: use integer notation when possible
-            if n.fract() == 0.0 && n.abs() < i64::MAX as f64 {
-                let _ = write!(out, "{}", *n as i64);
-            } else {
-                let _ = write!(out, "{}", n);
+        Value::Number(n) => write_number(out, *n),
+        Value::String(s) => write_lua_string(out, &s.as_bytes()),
+        Value::Table(t) => {
+            let ptr = t.to_pointer();
+            if !visit.ancestors.insert(ptr) {
+                visit.cycles += 1;
+                out.push_str("nil");
+                return;
             }
+            serialize_table(out, t, depth, visit);
+            visit.ancestors.remove(&ptr);
         }
-        Value::String(s) => {
-            let Ok(s) = s.to_str() else { out.push_str("\"\""); return };
-            out.push('"');
-            for ch in s.chars() {
+        // Functions, userdata, threads etc. are not serializable
+        _ => out.push_str("nil"),
+    }
+}
+
+/// Write a number, using integer notation when exact (as WoW does).
+fn write_number(out: &mut String, n: f64) {
+    if n.is_nan() {
+        out.push_str("0/0");
+    } else if n.is_infinite() {
+        out.push_str(if n > 0.0 { "math.huge" } else { "-math.huge" });
+    } else if n.fract() == 0.0 && n.abs() < i64::MAX as f64 {
+        let _ = write!(out, "{}", n as i64);
+    } else {
+        let _ = write!(out, "{:?}", n);
+    }
+}
+
+/// Write a double-quoted Lua string literal.
+///
+/// Control bytes use 3-digit `\ddd` escapes so a following digit can't be
+/// read as part of the escape. Strings that aren't valid UTF-8 have every
+/// non-ASCII byte escaped the same way, so the file stays valid UTF-8 and
+/// the bytes round-trip exactly.
+fn write_lua_string(out: &mut String, bytes: &[u8]) {
+    let utf8 = std::str::from_utf8(bytes).ok();
+    out.push('"');
+    match utf8 {
+        Some(text) => {
+            for ch in text.chars() {
                 match ch {
-                    '"' => out.push_str("\\\""),
-                    '\\' => out.push_str("\\\\"),
-                    '\n' => out.push_str("\\n"),
-                    '\r' => out.push_str("\\r"),
-                    '\t' => out.push_str("\\t"),
-                    '\0' => out.push_str("\\0"),
+                    c if c.is_ascii() => write_escaped_byte(out, c as u8),
                     c => out.push(c),
                 }
             }
-            out.push('"');
         }
-        Value::Table(t) => serialize_table(out, t, depth),
-        // Functions, userdata, threads etc. are not serializable
-        _ => out.push_str("nil"),
+        None => {
+            for &b in bytes {
+                if b.is_ascii() {
+                    write_escaped_byte(out, b);
+                } else {
+                    let _ = write!(out, "\\{:03}", b);
+                }
+            }
+        }
     }
+    out.push('"');
 }
 
-/// Collect non-array entries from a table, sorted by key for deterministic output.
-fn collect_hash_entries(table: &Table, array_len: usize) -> Vec<(String, Value)> {
-    let mut entries = Vec::new();
-    let Ok(pairs) = table.clone().pairs::<Value, Value>().collect::<std::result::Result<Vec<_>, _>>() else {
-        return entries;
-    };
-    for (k, v) in pairs {
-        match &k {
-            Value::Integer(i) if *i >= 1 && *i <= array_len as i64 => continue,
-            Value::String(s) => {
-                if let Ok(key) = s.to_str() {
-                    entries.push((key.to_string(), v));
-                }
-            }
-            Value::Integer(i) => entries.push((i.to_string(), v)),
-            Value::Number(n) => entries.push((n.to_string(), v)),
-            _ => {}
+fn write_escaped_byte(out: &mut String, b: u8) {
+    match b {
+        b'"' => out.push_str("\\\""),
+        b'\\' => out.push_str("\\\\"),
+        b'\n' => out.push_str("\\n"),
+        b'\r' => out.push_str("\\r"),
+        b'\t' => out.push_str("\\t"),
+        b if b < 0x20 || b == 0x7f => {
+            let _ = write!(out, "\\{:03}", b);
         }
+        b => out.push(b as char),
     }
-    entries.sort_by(|a, b| a.0.cmp(&b.0));
+}
+
+/// A non-array table key, ordered numbers first, then strings, then booleans.
+#[derive(PartialEq, PartialOrd)]
+enum TableKey {
+    Number(f64),
+    String(Vec<u8>),
+    Boolean(bool),
+}
+
+/// The integer value of a key, whether Lua handed it over as an integer or
+/// an integral float.
+fn integer_key(value: &Value) -> Option<i64> {
+    match value {
+        Value::Integer(i) => Some(*i),
+        Value::Number(n) if n.fract() == 0.0 && n.abs() < i64::MAX as f64 => Some(*n as i64),
+        _ => None,
+    }
+}
+
+/// Length of the contiguous array part `t[1]..t[n]` (stops at the first nil).
+fn array_prefix_len(table: &Table) -> i64 {
+    let mut n = 0;
+    while table.raw_get::<Value>(n + 1).is_ok_and(|v| !v.is_nil()) {
+        n += 1;
+    }
+    n
+}
+
+/// Collect entries outside the array prefix, sorted by key for deterministic
+/// output. Integer keys past a nil gap land here and keep their numeric type.
+fn collect_hash_entries(table: &Table, array_len: i64) -> Vec<(TableKey, Value)> {
+    let mut entries = Vec::new();
+    for (k, v) in table.clone().pairs::<Value, Value>().flatten() {
+        let key = match &k {
+            k if integer_key(k).is_some_and(|i| i >= 1 && i <= array_len) => continue,
+            Value::Integer(i) => TableKey::Number(*i as f64),
+            Value::Number(n) if !n.is_nan() => TableKey::Number(*n),
+            Value::String(s) => TableKey::String(s.as_bytes().to_vec()),
+            Value::Boolean(b) => TableKey::Boolean(*b),
+            _ => continue,
+        };
+        entries.push((key, v));
+    }
+    entries.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal));
     entries
 }
 
-/// Write a Lua-escaped key string (for `["key"]` syntax).
-fn write_escaped_key(out: &mut String, key: &str) {
-    for ch in key.chars() {
-        match ch {
-            '"' => out.push_str("\\\""),
-            '\\' => out.push_str("\\\\"),
-            '\n' => out.push_str("\\n"),
-            c => out.push(c),
-        }
+/// Write a table key in `[key]` syntax.
+fn write_key(out: &mut String, key: &TableKey) {
+    out.push('[');
+    match key {
+        TableKey::Number(n) => write_number(out, *n),
+        TableKey::String(bytes) => write_lua_string(out, bytes),
+        TableKey::Boolean(b) => out.push_str(if *b { "true" } else { "false" }),
     }
+    out.push(']');
 }
 
 /// Serialize a Lua table in WoW SavedVariables format.
 ///
 /// WoW uses a specific format:
 /// - Array entries (sequential integer keys 1..N) are written without explicit keys
-/// - String/other keys use `["key"] = value` syntax
+/// - Other keys use `[key] = value` syntax (`["name"]`, `[7]`, `[true]`)
 /// - Tables are indented with tabs
-fn serialize_table(out: &mut String, table: &Table, depth: usize) {
+fn serialize_table(out: &mut String, table: &Table, depth: usize, visit: &mut CycleGuard) {
     out.push_str("{\n");
     let indent = "\t".repeat(depth + 1);
-    let array_len = table.raw_len();
+    let array_len = array_prefix_len(table);
 
     for i in 1..=array_len {
-        let val: Value = match table.get(i as i64) {
-            Ok(v) => v,
-            Err(_) => break,
-        };
-        if val.is_nil() {
-            break;
-        }
+        let val: Value = table.raw_get(i).unwrap_or(Value::Nil);
         let _ = write!(out, "{}", indent);
-        serialize_value(out, &val, depth + 1);
+        serialize_value(out, &val, depth + 1, visit);
         let _ = writeln!(out, ", -- [{}]", i);
     }
 
     for (key, val) in &collect_hash_entries(table, array_len) {
-        let _ = write!(out, "{}[\"", indent);
-        write_escaped_key(out, key);
-        out.push_str("\"] = ");
-        serialize_value(out, val, depth + 1);
+        out.push_str(&indent);
+        write_key(out, key);
+        out.push_str(" = ");
+        serialize_value(out, val, depth + 1, visit);
         out.push_str(",\n");
     }
 
@@ -738,4 +838,66 @@ mod tests {
         // String keys should use ["key"] syntax
         assert!(output.contains("[\"setting\"] = \"hello\""));
     }
+    #[test]
+    fn test_round_trip_keeps_key_types_gaps_and_escapes() {
+        let dir = tempdir().unwrap();
+        let lua = Lua::new();
+        let mut mgr = SavedVariablesManager::with_storage_dir(dir.path().to_path_buf());
+        mgr.init_for_addon(&lua, "TestAddon", &["TestDB".to_string()], &[])
+            .unwrap();
+
+        lua.load(r#"
+            TestDB.sparse = { "a", "b", nil, "d", [10] = "ten" }
+            TestDB.byId = { [12345] = true, ["12345"] = "str" }
+            TestDB.flags = { [true] = "yes", [false] = "no" }
+            TestDB.ratio = 0.1
+            TestDB.ctrl = "\0011\127|cffff0000red|r"
+            TestDB.self = TestDB
+        "#)
+        .exec()
+        .unwrap();
+        mgr.save_addon(&lua, "TestAddon").unwrap();
+
+        let lua2 = Lua::new();
+        let mut mgr2 = SavedVariablesManager::with_storage_dir(dir.path().to_path_buf());
+        mgr2.init_for_addon(&lua2, "TestAddon", &["TestDB".to_string()], &[])
+            .unwrap();
+
+        let check: bool = lua2
+            .load(r#"
+                local s = TestDB.sparse
+                return s[1] == "a" and s[2] == "b" and s[3] == nil and s[4] == "d"
+                    and s[10] == "ten"
+                    and TestDB.byId[12345] == true and TestDB.byId["12345"] == "str"
+                    and TestDB.flags[true] == "yes" and TestDB.flags[false] == "no"
+                    and TestDB.ratio == 0.1
+                    and TestDB.ctrl == "\0011\127|cffff0000red|r"
+                    and TestDB.self == nil
+            "#)
+            .eval()
+            .unwrap();
+        assert!(check, "{}", fs::read_to_string(dir.path().join("TestAddon.lua")).unwrap());
+    }
+
+    #[test]
+    fn test_save_env_round_trip() {
+        use crate::lua_api::WowLuaEnv;
+
+        let dir = tempdir().unwrap();
+        let vars = ["SettingsDB".to_string()];
+
+        let env = WowLuaEnv::new().unwrap();
+        let mut mgr = SavedVariablesManager::with_storage_dir(dir.path().to_path_buf());
+        mgr.init_for_addon(env.lua(), "Settings", &vars, &[]).unwrap();
+        env.exec("SettingsDB.scale = 1.25; SettingsDB.profile = { name = 'Main' }").unwrap();
+        assert_eq!(mgr.save(&env).unwrap(), 1);
+
+        let env2 = WowLuaEnv::new().unwrap();
+        let mut mgr2 = SavedVariablesManager::with_storage_dir(dir.path().to_path_buf());
+        mgr2.init_for_addon(env2.lua(), "Settings", &vars, &[]).unwrap();
+        let scale: f64 = env2.eval("return SettingsDB.scale").unwrap();
+        let name: String = env2.eval("return SettingsDB.profile.name").unwrap();
+        assert_eq!(scale, 1.25);
+        assert_eq!(name, "Main");
+    }
 }
@@ -14,3 +14,28 @@ mod stub;
 
 #[cfg(not(feature = "sound"))]
 pub use stub::SoundManager;
+
+/// Playback behind the Lua sound API: the `SoundManager` in the simulator,
+/// a recording stand-in in tests.
+pub trait SoundBackend {
+    /// Play a sound file by path. Returns a handle on success.
+    fn play_sound_file(&mut self, path: &str) -> Option<u32>;
+    /// Stop a playing sound by handle.
+    fn stop_sound(&mut self, handle: u32);
+    /// Check if a sound handle is still playing.
+    fn is_playing(&self, handle: u32) -> bool;
+}
+
+impl SoundBackend for SoundManager {
+    fn play_sound_file(&mut self, path: &str) -> Option<u32> {
+        SoundManager::play_sound_file(self, path)
+    }
+
+    fn stop_sound(&mut self, handle: u32) {
+        SoundManager::stop_sound(self, handle)
+    }
+
+    fn is_playing(&self, handle: u32) -> bool {
+        SoundManager::is_playing(self, handle)
+    }
+}
@@ -13,8 +13,6 @@ pub struct SoundManager {
     stream: OutputStream,
     active_sounds: HashMap<u32, Sink>,
     sound_dir: PathBuf,
-    /// SoundKit ID -> relative file path (e.g. 850 -> "igmainmenuopen.ogg").
-    soundkit_map: HashMap<u32, &'static str>,
 }
 
 impl SoundManager {
@@ -28,15 +26,12 @@ impl SoundManager {
             stream,
             active_sounds: HashMap::new(),
             sound_dir,
-            soundkit_map: build_soundkit_map(),
         })
     }
 
     /// Play a sound by SoundKit ID. Returns a handle on success.
     pub fn play_sound(&mut self, soundkit_id: u32) -> Option<u32> {
-        let rel_path = self.soundkit_map.get(&soundkit_id)?;
-        let full_path = self.sound_dir.join(rel_path);
-        self.play_file(&full_path)
+        self.play_sound_file(crate::soundkits::soundkit_file(soundkit_id)?)
     }
 
     /// Play a sound file by path. Returns a handle on success.
@@ -79,22 +74,3 @@ impl SoundManager {
         Some(handle)
     }
 }
-
-/// Build the SoundKit ID -> filename mapping for common UI sounds.
-fn build_soundkit_map() -> HashMap<u32, &'static str> {
-    HashMap::from([
-        (829, "igspellbookopen.ogg"),
-        (830, "igspellbookclose.ogg"),
-        (836, "igabilitypageturn.ogg"),
-        (839, "igcharacterinfotab.ogg"),
-        (841, "igcharacterinfoopen.ogg"),
-        (850, "igmainmenuopen.ogg"),
-        (851, "igmainmenuclose.ogg"),
-        (856, "igmainmenuoption.ogg"),
-        (857, "igmainmenuoptioncheckboxon.ogg"),
-        (858, "igmainmenuoptioncheckboxoff.ogg"),
-        // UI_CLASS_TALENT_OPEN/CLOSE_WINDOW — fallback to classic spellbook sounds
-        (207757, "igspellbookopen.ogg"),
-        (207758, "igspellbookclose.ogg"),
-    ])
-}
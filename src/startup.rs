@@ -3,9 +3,15 @@
 //! Fires the WoW login event sequence, processes pending timers,
 //! and runs one OnUpdate tick so OnUpdate-dependent state (e.g. buff
 //! durations) is populated even without a GUI loop.
+//!
+//! Headless runs never sleep: time only passes through the virtual
+//! `SimClock`, so output is identical regardless of machine speed.
 
 use crate::lua_api::WowLuaEnv;
 
+/// Simulated seconds that pass per headless OnUpdate tick.
+pub const HEADLESS_TICK_SECS: f64 = 0.1;
+
 /// Process any C_Timer callbacks that became ready during startup.
 pub fn process_pending_timers(env: &WowLuaEnv) {
     for _ in 0..10 {
@@ -20,22 +26,79 @@ pub fn process_pending_timers(env: &WowLuaEnv) {
     }
 }
 
-/// Sleep for the given number of milliseconds (if specified).
-pub fn apply_delay(delay: Option<u64>) {
+/// Advance the virtual clock by `secs` without firing OnUpdate.
+pub fn advance_clock(env: &WowLuaEnv, secs: f64) {
+    env.state().borrow_mut().clock.advance(secs);
+}
+
+/// Let the given number of simulated milliseconds pass (if specified),
+/// then drain any timers that became due.
+pub fn apply_delay(env: &WowLuaEnv, delay: Option<u64>) {
     if let Some(ms) = delay {
-        eprintln!("[Startup] Delaying {}ms", ms);
-        std::thread::sleep(std::time::Duration::from_millis(ms));
+        eprintln!("[Startup] Advancing sim clock {}ms", ms);
+        advance_clock(env, ms as f64 / 1000.0);
+        process_pending_timers(env);
     }
 }
 
 /// Fire a single OnUpdate tick so OnUpdate-dependent state (e.g. buff
 /// durations) is populated in headless modes where the GUI loop never runs.
+/// Advances the virtual clock by `HEADLESS_TICK_SECS`.
 pub fn fire_one_on_update_tick(env: &WowLuaEnv) {
-    if let Err(e) = env.fire_on_update(0.016) {
+    if let Err(e) = env.fire_on_update(HEADLESS_TICK_SECS) {
         eprintln!("[OnUpdate tick] error: {e}");
     }
 }
 
+/// Advance playing, non-looping animation groups to their end.
+///
+/// Headless runs only fire a handful of ticks, so fade-ins and slide-ins
+/// started during startup would otherwise be captured mid-animation.
+pub fn settle_animations(env: &WowLuaEnv) {
+    use crate::lua_api::animation::LoopType;
+    let remaining = {
+        let state = env.state().borrow();
+        state.animation_groups.values()
+            .filter(|g| g.playing && !g.paused && g.looping == LoopType::None && g.speed_multiplier > 0.0)
+            .map(|g| (g.total_duration() - g.elapsed) / g.speed_multiplier)
+            .fold(0.0_f64, f64::max)
+    };
+    if remaining <= 0.0 {
+        return;
+    }
+    if let Err(e) = crate::lua_api::animation::tick_animation_groups(env.state(), env.lua(), remaining) {
+        eprintln!("[Animations] error: {e}");
+    }
+}
+
+/// Fire extra OnUpdate ticks so deferred UI (talent frame, pool-created frames) can process.
+pub fn run_extra_update_ticks(env: &WowLuaEnv, n: usize) {
+    for _ in 0..n {
+        env.state().borrow_mut().ensure_layout_rects();
+        fire_one_on_update_tick(env);
+        process_pending_timers(env);
+    }
+}
+
+/// First half of the headless startup: login events, post-event workarounds,
+/// pending timers and one OnUpdate tick.
+pub fn fire_headless_startup(env: &WowLuaEnv) {
+    fire_startup_events(env);
+    env.apply_post_event_workarounds();
+    env.state().borrow_mut().widgets.rebuild_anchor_index();
+    process_pending_timers(env);
+    fire_one_on_update_tick(env);
+    let _ = crate::lua_api::hide_runtime_hidden_frames(env.lua());
+}
+
+/// Second half of the headless startup: let two simulated seconds pass,
+/// run a few more ticks and finish startup animations.
+pub fn settle_headless_startup(env: &WowLuaEnv) {
+    advance_clock(env, 2.0);
+    run_extra_update_ticks(env, 3);
+    settle_animations(env);
+}
+
 /// Fire startup events to simulate WoW login sequence.
 pub fn fire_startup_events(env: &WowLuaEnv) {
     let fire = |name| {
@@ -15,14 +15,127 @@ pub struct TextureManager {
     interface_path: Option<PathBuf>,
     /// Base path to addons directory (for addon textures).
     addons_path: Option<PathBuf>,
-    /// Cache of loaded texture data (path -> RGBA pixels).
-    cache: HashMap<String, TextureData>,
+    /// Normalized WoW path -> resolved file path.
+    resolved: HashMap<String, PathBuf>,
+    /// Decoded textures keyed by resolved file path, bounded by a byte budget.
+    cache: TextureCache,
     /// Cache of sub-region textures (path#region -> RGBA pixels).
     sub_cache: HashMap<String, TextureData>,
     /// Paths that failed to load (logged once, then silenced).
     not_found: HashSet<String>,
 }
 
+/// Default byte budget for decoded texture pixels (256 MB).
+pub const DEFAULT_CACHE_BUDGET: usize = 256 * 1024 * 1024;
+
+/// Texture cache counters, reported in the screenshot summary.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    pub hits: u64,
+    pub misses: u64,
+    pub evictions: u64,
+    /// Pixel bytes currently held.
+    pub bytes: usize,
+    pub entries: usize,
+}
+
+/// LRU cache of decoded textures. Once the pixel bytes exceed the budget the
+/// least-recently-used entries are evicted (a texture larger than the whole
+/// budget is still kept, alone).
+#[derive(Debug)]
+struct TextureCache {
+    entries: HashMap<PathBuf, CacheEntry>,
+    budget: usize,
+    bytes: usize,
+    /// Monotonic use counter; an entry's `last_used` orders eviction.
+    tick: u64,
+    hits: u64,
+    misses: u64,
+    evictions: u64,
+}
+
+#[derive(Debug)]
+struct CacheEntry {
+    data: TextureData,
+    last_used: u64,
+}
+
+impl TextureCache {
+    fn new(budget: usize) -> Self {
+        Self {
+            entries: HashMap::new(),
+            budget,
+            bytes: 0,
+            tick: 0,
+            hits: 0,
+            misses: 0,
+            evictions: 0,
+        }
+    }
+
+    /// Mark `path` as used, counting a hit or a miss. Returns whether it is cached.
+    fn touch(&mut self, path: &Path) -> bool {
+        self.tick += 1;
+        match self.entries.get_mut(path) {
+            Some(entry) => {
+                entry.last_used = self.tick;
+                self.hits += 1;
+                true
+            }
+            None => {
+                self.misses += 1;
+                false
+            }
+        }
+    }
+
+    /// Look up without affecting recency or counters.
+    fn peek(&self, path: &Path) -> Option<&TextureData> {
+        self.entries.get(path).map(|e| &e.data)
+    }
+
+    fn insert(&mut self, path: PathBuf, data: TextureData) -> &TextureData {
+        let size = data.pixels.len();
+        while self.bytes + size > self.budget && self.evict_oldest() {}
+        self.tick += 1;
+        self.bytes += size;
+        let entry = CacheEntry { data, last_used: self.tick };
+        self.entries.insert(path.clone(), entry);
+        &self.entries[&path].data
+    }
+
+    fn evict_oldest(&mut self) -> bool {
+        let Some(oldest) = self
+            .entries
+            .iter()
+            .min_by_key(|(_, e)| e.last_used)
+            .map(|(path, _)| path.clone())
+        else {
+            return false;
+        };
+        if let Some(entry) = self.entries.remove(&oldest) {
+            self.bytes -= entry.data.pixels.len();
+            self.evictions += 1;
+        }
+        true
+    }
+
+    fn set_budget(&mut self, budget: usize) {
+        self.budget = budget;
+        while self.bytes > self.budget && self.evict_oldest() {}
+    }
+
+    fn stats(&self) -> CacheStats {
+        CacheStats {
+            hits: self.hits,
+            misses: self.misses,
+            evictions: self.evictions,
+            bytes: self.bytes,
+            entries: self.entries.len(),
+        }
+    }
+}
+
 /// Loaded texture data.
 #[derive(Debug, Clone)]
 pub struct TextureData {
@@ -38,7 +151,8 @@ impl TextureManager {
             textures_path: textures_path.into(),
             interface_path: None,
             addons_path: None,
-            cache: HashMap::new(),
+            resolved: HashMap::new(),
+            cache: TextureCache::new(DEFAULT_CACHE_BUDGET),
             sub_cache: HashMap::new(),
             not_found: HashSet::new(),
         }
@@ -56,48 +170,68 @@ impl TextureManager {
         self
     }
 
+    /// Set the byte budget for decoded textures (default [`DEFAULT_CACHE_BUDGET`]).
+    pub fn with_cache_budget(mut self, bytes: usize) -> Self {
+        self.cache.set_budget(bytes);
+        self
+    }
+
+    /// Cache hit/miss counters and current size.
+    pub fn cache_stats(&self) -> CacheStats {
+        self.cache.stats()
+    }
+
     /// Load a texture by its WoW path (e.g., "Interface\\DialogFrame\\UI-DialogBox-Background").
     pub fn load(&mut self, wow_path: &str) -> Option<&TextureData> {
-        // Normalize the path
         let normalized = normalize_wow_path(wow_path);
-
-        // Check cache first
-        if self.cache.contains_key(&normalized) {
-            return self.cache.get(&normalized);
-        }
         if self.not_found.contains(&normalized) {
             return None;
         }
 
-        // Try to load from disk
-        if let Some(file_path) = self.resolve_path(&normalized) {
-            match load_texture_file(&file_path) {
-                Ok(data) => {
-                    self.cache.insert(normalized.clone(), data);
-                    return self.cache.get(&normalized);
+        let file_path = match self.resolved.get(&normalized) {
+            Some(path) => path.clone(),
+            None => match self.resolve_path(&normalized) {
+                Some(path) => {
+                    self.resolved.insert(normalized, path.clone());
+                    path
                 }
-                Err(e) => {
-                    eprintln!("[TexMgr] Load error: {} -> {}: {}", wow_path, file_path.display(), e);
+                None => {
+                    eprintln!("[TexMgr] Not found: {}", wow_path);
+                    self.not_found.insert(normalized);
+                    return None;
                 }
+            },
+        };
+
+        if self.cache.touch(&file_path) {
+            return self.cache.peek(&file_path);
+        }
+        match load_texture_file(&file_path) {
+            Ok(data) => Some(self.cache.insert(file_path, data)),
+            Err(e) => {
+                eprintln!("[TexMgr] Load error: {} -> {}: {}", wow_path, file_path.display(), e);
+                None
             }
-        } else {
-            eprintln!("[TexMgr] Not found: {}", wow_path);
-            self.not_found.insert(normalized);
         }
+    }
 
-        None
+    /// Pixel size of the texture file at `wow_path`, loading it if needed.
+    /// Tiled regions repeat at this size.
+    pub fn native_size(&mut self, wow_path: &str) -> Option<(u32, u32)> {
+        self.load(wow_path).map(|data| (data.width, data.height))
     }
 
     /// Get a cached texture without loading.
     pub fn get(&self, wow_path: &str) -> Option<&TextureData> {
         let normalized = normalize_wow_path(wow_path);
-        self.cache.get(&normalized)
+        self.resolved
+            .get(&normalized)
+            .and_then(|path| self.cache.peek(path))
     }
 
     /// Get the dimensions of a cached texture.
     pub fn get_texture_size(&self, wow_path: &str) -> Option<(u32, u32)> {
-        let normalized = normalize_wow_path(wow_path);
-        self.cache.get(&normalized).map(|d| (d.width, d.height))
+        self.get(wow_path).map(|d| (d.width, d.height))
     }
 
     /// Load a sub-region of a texture (for texture atlases).
@@ -118,17 +252,10 @@ impl TextureManager {
             return self.sub_cache.get(&key);
         }
 
-        // Load the full texture first
-        if let Some(file_path) = self.resolve_path(&normalized)
-            && let Ok(full_data) = load_texture_file(&file_path) {
-                // Extract sub-region
-                if let Some(sub_data) = extract_sub_region(&full_data, x, y, width, height) {
-                    self.sub_cache.insert(key.clone(), sub_data);
-                    return self.sub_cache.get(&key);
-                }
-            }
-
-        None
+        // Cut it out of the (cached) full texture
+        let sub_data = extract_sub_region(self.load(wow_path)?, x, y, width, height)?;
+        self.sub_cache.insert(key.clone(), sub_data);
+        self.sub_cache.get(&key)
     }
 
     /// Resolve a WoW texture path to a file system path.
@@ -268,28 +395,73 @@ pub fn fix_1bit_alpha(pixels: &mut [u8]) {
     }
 }
 
+/// Rescale alpha decoded by image-blp according to the BLP's alpha depth.
+///
+/// image-blp leaves palettized alpha at its stored precision: 1-bit alpha
+/// comes out as 0/1 and 4-bit alpha as 0..=15, which renders as nearly
+/// invisible. `alpha_bits` is the header's alpha depth:
+/// - 0: no alpha channel, force opaque
+/// - 1: 0/1 -> 0/255 (DXT1 already decoded to 0/255 is left alone)
+/// - 4: 0..=15 -> 0..=255
+/// - 8 (and anything else): untouched
+pub fn normalize_blp_alpha(pixels: &mut [u8], alpha_bits: u32) {
+    match alpha_bits {
+        0 => pixels.iter_mut().skip(3).step_by(4).for_each(|a| *a = 255),
+        1 => fix_1bit_alpha(pixels),
+        4 => {
+            let max_alpha = pixels.iter().skip(3).step_by(4).copied().max().unwrap_or(0);
+            if max_alpha <= 15 {
+                pixels.iter_mut().skip(3).step_by(4).for_each(|a| *a *= 17);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Number of mip levels in a BLP file, including the full-size image.
+pub fn blp_mip_count(path: &Path) -> Result<usize, Box<dyn std::error::Error + Send + Sync>> {
+    Ok(load_blp(path)?.header.mipmaps_count() + 1)
+}
+
+/// Decode one mip level of a BLP file (0 = full size, each level halves).
+///
+/// Errors if `mip` is beyond the mips stored in the file.
+pub fn convert_blp_mip(
+    path: &Path,
+    mip: usize,
+) -> Result<image::RgbaImage, Box<dyn std::error::Error + Send + Sync>> {
+    let blp = load_blp(path)?;
+    let count = blp.header.mipmaps_count() + 1;
+    if mip >= count {
+        return Err(format!(
+            "{}: mip {} out of range (file has {} mip level(s), 0..={})",
+            path.display(),
+            mip,
+            count,
+            count - 1
+        )
+        .into());
+    }
+    // image-blp uses image 0.24, we use 0.25, so extract raw pixels directly
+    let rgba = blp_to_image(&blp, mip)?.to_rgba8();
+    let (width, height) = rgba.dimensions();
+    let mut pixels = rgba.into_raw();
+    normalize_blp_alpha(&mut pixels, blp.header.flags.alpha_bits());
+    Ok(image::RgbaImage::from_raw(width, height, pixels).expect("BLP mip dimensions match pixels"))
+}
+
 /// Load texture data from a file.
 fn load_texture_file(path: &Path) -> Result<TextureData, Box<dyn std::error::Error + Send + Sync>> {
     // Check if it's a BLP file
     let ext = path.extension().and_then(|e| e.to_str()).unwrap_or("");
 
     if ext.eq_ignore_ascii_case("blp") {
-        // Use image-blp for BLP files
-        // Note: image-blp uses image 0.24, we use 0.25, so extract raw pixels directly
-        let blp = load_blp(path)?;
-        let blp_img = blp_to_image(&blp, 0)?;
-        // Get dimensions and convert to RGBA8 bytes
-        let rgba = blp_img.to_rgba8();
-        let width = rgba.width();
-        let height = rgba.height();
-        let mut pixels = rgba.into_raw();
-        // Fix 1-bit alpha: image-blp decodes 1-bit alpha as literal 0/1 byte values
-        // instead of 0/255. Remap any alpha > 0 to 255 for correct rendering.
-        fix_1bit_alpha(&mut pixels);
+        let rgba = convert_blp_mip(path, 0)?;
+        let (width, height) = rgba.dimensions();
         Ok(TextureData {
             width,
             height,
-            pixels,
+            pixels: rgba.into_raw(),
         })
     } else {
         // Use standard image crate for other formats
@@ -408,6 +580,120 @@ mod tests {
         assert_eq!(data.pixels[2], 0, "B should be 0 (webp loaded)");
     }
 
+    /// BLP2 file with the given compression (1 = palettized, 3 = raw BGRA),
+    /// alpha depth and per-mip data. The palette is all red.
+    fn write_blp(path: &Path, compression: u8, alpha_bits: u8, size: u32, mips: &[Vec<u8>]) {
+        let mut data = Vec::new();
+        data.extend_from_slice(b"BLP2");
+        data.extend_from_slice(&1u32.to_le_bytes()); // type: BLP color
+        let has_mips = u8::from(mips.len() > 1);
+        data.extend_from_slice(&[compression, alpha_bits, 0, has_mips]);
+        data.extend_from_slice(&size.to_le_bytes());
+        data.extend_from_slice(&size.to_le_bytes());
+        let header_len = 20 + 16 * 4 * 2 + 256 * 4;
+        let mut offsets = [0u32; 16];
+        let mut lengths = [0u32; 16];
+        let mut offset = header_len as u32;
+        for (i, mip) in mips.iter().enumerate() {
+            offsets[i] = offset;
+            lengths[i] = mip.len() as u32;
+            offset += mip.len() as u32;
+        }
+        offsets.iter().for_each(|o| data.extend_from_slice(&o.to_le_bytes()));
+        lengths.iter().for_each(|l| data.extend_from_slice(&l.to_le_bytes()));
+        data.extend(std::iter::repeat_n([0, 0, 255, 255], 256).flatten()); // BGRA palette
+        mips.iter().for_each(|mip| data.extend_from_slice(mip));
+        fs::write(path, data).unwrap();
+    }
+
+    /// Uncompressed (BGRA) 4x4 BLP2 with a full mip chain: 4x4, 2x2, 1x1.
+    fn write_raw_blp(path: &Path) {
+        let mips: Vec<Vec<u8>> = [16, 4, 1]
+            .iter()
+            .map(|&n| std::iter::repeat_n([0, 0, 255, 255], n).flatten().collect())
+            .collect();
+        write_blp(path, 3, 8, 4, &mips);
+    }
+
+    /// Sorted distinct alpha values of a decoded image.
+    fn alpha_values(img: &image::RgbaImage) -> Vec<u8> {
+        let mut values: Vec<u8> = img.pixels().map(|p| p.0[3]).collect();
+        values.sort_unstable();
+        values.dedup();
+        values
+    }
+
+    #[test]
+    fn test_palettized_blp_alpha_depths_are_rescaled() {
+        let temp_dir = TempDir::new().unwrap();
+        let indexes = vec![0u8; 16];
+
+        // 1-bit: every other pixel opaque.
+        let path = temp_dir.path().join("alpha1.blp");
+        write_blp(&path, 1, 1, 4, &[[indexes.clone(), vec![0x55, 0x55]].concat()]);
+        let img = convert_blp_mip(&path, 0).unwrap();
+        assert_eq!(alpha_values(&img), vec![0, 255]);
+        assert_eq!(img.pixels().filter(|p| p.0[3] == 255).count(), 8);
+
+        // 4-bit: each of the 16 levels once.
+        let path = temp_dir.path().join("alpha4.blp");
+        let nibbles: Vec<u8> = (0..8).map(|i| (2 * i) | ((2 * i + 1) << 4)).collect();
+        write_blp(&path, 1, 4, 4, &[[indexes.clone(), nibbles].concat()]);
+        let img = convert_blp_mip(&path, 0).unwrap();
+        let expected: Vec<u8> = (0..16).map(|i| i * 17).collect();
+        assert_eq!(alpha_values(&img), expected);
+
+        // 8-bit: stored values pass through untouched.
+        let path = temp_dir.path().join("alpha8.blp");
+        let alpha: Vec<u8> = (0..16).map(|i| i * 16).collect();
+        write_blp(&path, 1, 8, 4, &[[indexes, alpha.clone()].concat()]);
+        let img = convert_blp_mip(&path, 0).unwrap();
+        assert_eq!(alpha_values(&img), alpha);
+    }
+
+    #[test]
+    fn test_convert_blp_mip_levels() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("mips.blp");
+        write_raw_blp(&path);
+
+        assert_eq!(blp_mip_count(&path).unwrap(), 3);
+        assert_eq!(convert_blp_mip(&path, 0).unwrap().dimensions(), (4, 4));
+        let mip1 = convert_blp_mip(&path, 1).unwrap();
+        assert_eq!(mip1.dimensions(), (2, 2));
+        assert_eq!(mip1.get_pixel(0, 0).0, [255, 0, 0, 255]);
+
+        let err = convert_blp_mip(&path, 3).unwrap_err().to_string();
+        assert!(err.contains("mip 3 out of range"), "{err}");
+    }
+
+    #[test]
+    fn test_cache_hits_and_lru_eviction() {
+        let temp_dir = TempDir::new().unwrap();
+        let base = temp_dir.path();
+        for name in ["a", "b", "c"] {
+            image::RgbaImage::from_pixel(2, 2, image::Rgba([0, 0, 0, 255]))
+                .save(base.join(format!("{name}.png")))
+                .unwrap();
+        }
+
+        // Room for two 2x2 RGBA textures (16 bytes each).
+        let mut mgr = TextureManager::new(base).with_cache_budget(40);
+        assert!(mgr.load("a").is_some());
+        assert!(mgr.load("a").is_some());
+        let stats = mgr.cache_stats();
+        assert_eq!((stats.hits, stats.misses, stats.entries), (1, 1, 1));
+
+        mgr.load("b");
+        mgr.load("a"); // b is now least recently used
+        mgr.load("c");
+        let stats = mgr.cache_stats();
+        assert_eq!((stats.evictions, stats.entries, stats.bytes), (1, 2, 32));
+        assert!(mgr.get("a").is_some());
+        assert!(mgr.get("b").is_none(), "LRU entry should be evicted");
+        assert!(mgr.get("c").is_some());
+    }
+
     #[test]
     fn test_extension_priority_order() {
         // Verify the extension order in try_resolve_in_dir is webp first
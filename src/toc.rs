@@ -16,6 +16,113 @@ pub struct TocFile {
     pub metadata: HashMap<String, String>,
     /// Files to load in order (relative paths)
     pub files: Vec<PathBuf>,
+    /// Account-wide SavedVariables globals (`## SavedVariables` plus
+    /// `## SavedVariablesMachine`)
+    pub saved_variables: Vec<String>,
+    /// Per-character SavedVariables globals (`## SavedVariablesPerCharacter`)
+    pub saved_variables_per_character: Vec<String>,
+}
+
+/// Game flavor a TOC variant targets, selected by its file-name suffix
+/// (`MyAddon_Mainline.toc`, `MyAddon-Classic.toc`, ...).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TocFlavor {
+    Mainline,
+    Vanilla,
+    Tbc,
+    Wrath,
+    Cata,
+    Mists,
+}
+
+impl TocFlavor {
+    pub const ALL: [TocFlavor; 6] = [
+        TocFlavor::Mainline,
+        TocFlavor::Vanilla,
+        TocFlavor::Tbc,
+        TocFlavor::Wrath,
+        TocFlavor::Cata,
+        TocFlavor::Mists,
+    ];
+
+    /// File-name suffixes the client accepts for this flavor, in preference order.
+    pub fn suffixes(self) -> &'static [&'static str] {
+        match self {
+            TocFlavor::Mainline => &["Mainline"],
+            TocFlavor::Vanilla => &["Vanilla", "Classic"],
+            TocFlavor::Tbc => &["TBC", "BCC"],
+            TocFlavor::Wrath => &["Wrath", "WOTLKC"],
+            TocFlavor::Cata => &["Cata"],
+            TocFlavor::Mists => &["Mists"],
+        }
+    }
+
+    /// Flavor of the simulated client, from [`client_interface`].
+    pub fn current() -> TocFlavor {
+        Self::from_interface(client_interface())
+    }
+
+    /// Flavor whose client reports `interface` (by its major version).
+    pub fn from_interface(interface: u32) -> TocFlavor {
+        match interface / 10000 {
+            1 => TocFlavor::Vanilla,
+            2 => TocFlavor::Tbc,
+            3 => TocFlavor::Wrath,
+            4 => TocFlavor::Cata,
+            5 => TocFlavor::Mists,
+            _ => TocFlavor::Mainline,
+        }
+    }
+
+    /// `WOW_PROJECT_ID` the client reports for this flavor.
+    pub fn project_id(self) -> i32 {
+        match self {
+            TocFlavor::Mainline => 1,
+            TocFlavor::Vanilla => 2,
+            TocFlavor::Tbc => 5,
+            TocFlavor::Wrath => 11,
+            TocFlavor::Cata => 14,
+            TocFlavor::Mists => 19,
+        }
+    }
+
+    /// Expansion level (`LE_EXPANSION_*`) the flavor's client runs.
+    pub fn expansion_level(self) -> i32 {
+        match self {
+            TocFlavor::Mainline => 10,
+            TocFlavor::Vanilla => 0,
+            TocFlavor::Tbc => 1,
+            TocFlavor::Wrath => 2,
+            TocFlavor::Cata => 3,
+            TocFlavor::Mists => 4,
+        }
+    }
+
+    /// Flavor named by a TOC file stem's `_Suffix`/`-Suffix`, if any
+    /// (case-insensitive, as on the client).
+    pub fn from_toc_stem(stem: &str) -> Option<TocFlavor> {
+        let (_, suffix) = stem.rsplit_once(['_', '-'])?;
+        Self::ALL
+            .into_iter()
+            .find(|f| f.suffixes().iter().any(|s| s.eq_ignore_ascii_case(suffix)))
+    }
+}
+
+/// Interface number of the simulated client when `WOW_SIM_INTERFACE` is unset.
+pub const MAINLINE_INTERFACE: u32 = 110007;
+
+/// Interface number of the simulated client (`select(4, GetBuildInfo())`).
+///
+/// Set `WOW_SIM_INTERFACE` (e.g. `11507` for Classic Era) to simulate another
+/// flavor: it also decides which TOC variant addons load.
+pub fn client_interface() -> u32 {
+    static INTERFACE: std::sync::OnceLock<u32> = std::sync::OnceLock::new();
+    *INTERFACE.get_or_init(|| {
+        std::env::var("WOW_SIM_INTERFACE")
+            .ok()
+            .and_then(|v| v.trim().parse().ok())
+            .unwrap_or(MAINLINE_INTERFACE)
+    })
 }
 
 /// Strip inline annotations like `[AllowLoadEnvironment Global]` from a TOC line.
@@ -49,6 +156,16 @@ fn is_allowed_game_type(line: &str) -> bool {
         .any(|t| matches!(t.trim(), "mainline" | "standard"))
 }
 
+/// Comma-separated names from the given metadata keys, in key order.
+fn metadata_list(metadata: &HashMap<String, String>, keys: &[&str]) -> Vec<String> {
+    keys.iter()
+        .filter_map(|key| metadata.get(*key))
+        .flat_map(|s| s.split(','))
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+        .collect()
+}
+
 /// Resolve addon name from Title metadata or directory name.
 fn resolve_addon_name(metadata: &HashMap<String, String>, addon_dir: &Path) -> String {
     metadata.get("Title").cloned().unwrap_or_else(|| {
@@ -107,6 +224,8 @@ impl TocFile {
         TocFile {
             addon_dir: addon_dir.to_path_buf(),
             name: resolve_addon_name(&metadata, addon_dir),
+            saved_variables: metadata_list(&metadata, &["SavedVariables", "SavedVariablesMachine"]),
+            saved_variables_per_character: metadata_list(&metadata, &["SavedVariablesPerCharacter"]),
             metadata,
             files,
         }
@@ -199,34 +318,6 @@ impl TocFile {
             .unwrap_or(false)
     }
 
-    /// Get saved variables names (account-wide + machine-specific).
-    pub fn saved_variables(&self) -> Vec<String> {
-        let mut vars: Vec<String> = Vec::new();
-        for key in ["SavedVariables", "SavedVariablesMachine"] {
-            if let Some(s) = self.metadata.get(key) {
-                vars.extend(
-                    s.split(',')
-                        .map(|v| v.trim().to_string())
-                        .filter(|v| !v.is_empty()),
-                );
-            }
-        }
-        vars
-    }
-
-    /// Get saved variables per character names.
-    pub fn saved_variables_per_character(&self) -> Vec<String> {
-        self.metadata
-            .get("SavedVariablesPerCharacter")
-            .map(|s| {
-                s.split(',')
-                    .map(|v| v.trim().to_string())
-                    .filter(|v| !v.is_empty())
-                    .collect()
-            })
-            .unwrap_or_default()
-    }
-
     /// Get absolute paths for all files to load.
     /// Uses case-insensitive matching for compatibility with WoW (Windows/macOS).
     pub fn file_paths(&self) -> Vec<PathBuf> {
@@ -380,7 +471,7 @@ Core.lua
         let toc = TocFile::parse(Path::new("/addons/TestAddon"), contents);
 
         assert_eq!(
-            toc.saved_variables(),
+            toc.saved_variables,
             vec!["TestAddonDB", "TestAddonPerCharDB"]
         );
     }
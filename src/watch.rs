@@ -0,0 +1,118 @@
+//! Polling file watcher for `wow-sim watch`: notices edits to an addon's
+//! .lua/.xml/.toc files and waits for them to settle before a reload.
+//!
+//! Polls modification times and sizes instead of subscribing to OS events,
+//! so an editor's atomic save (write a temp file, rename it over the
+//! original) shows up as a plain change to the original path.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime};
+
+/// How often the watched tree is rescanned.
+pub const POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+/// File extensions whose changes trigger a reload.
+const WATCHED_EXTENSIONS: &[&str] = &["lua", "xml", "toc"];
+
+/// Modification time and size of every watched file under a directory.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Snapshot {
+    files: BTreeMap<PathBuf, (Option<SystemTime>, u64)>,
+}
+
+impl Snapshot {
+    /// Scan `root` recursively. Symlinks are not followed, which also skips
+    /// editor lock files such as Emacs' `.#name.lua`.
+    pub fn scan(root: &Path) -> Self {
+        let files = walkdir::WalkDir::new(root)
+            .into_iter()
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_type().is_file() && is_watched(e.path()))
+            .filter_map(|e| {
+                let meta = e.metadata().ok()?;
+                Some((e.into_path(), (meta.modified().ok(), meta.len())))
+            })
+            .collect();
+        Self { files }
+    }
+
+    /// Number of watched files.
+    pub fn len(&self) -> usize {
+        self.files.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+
+    /// Paths added, removed or modified going from `self` to `newer`, sorted.
+    pub fn changed_paths(&self, newer: &Snapshot) -> Vec<PathBuf> {
+        let mut changed: Vec<PathBuf> = newer.files.iter()
+            .filter(|&(path, stamp)| self.files.get(path) != Some(stamp))
+            .map(|(path, _)| path.clone())
+            .collect();
+        changed.extend(self.files.keys().filter(|p| !newer.files.contains_key(*p)).cloned());
+        changed.sort();
+        changed
+    }
+}
+
+/// Whether edits to `path` should trigger a reload.
+fn is_watched(path: &Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|ext| WATCHED_EXTENSIONS.iter().any(|w| ext.eq_ignore_ascii_case(w)))
+}
+
+/// Block until a watched file under `root` differs from `last`, then until
+/// the tree has been quiet for `debounce`, so a burst of saves reloads once.
+///
+/// Returns the settled snapshot and the paths that changed.
+pub fn wait_for_change(root: &Path, last: &Snapshot, debounce: Duration) -> (Snapshot, Vec<PathBuf>) {
+    let mut current = loop {
+        std::thread::sleep(POLL_INTERVAL);
+        let scan = Snapshot::scan(root);
+        if scan != *last {
+            break scan;
+        }
+    };
+    let mut quiet_since = Instant::now();
+    while quiet_since.elapsed() < debounce {
+        std::thread::sleep(POLL_INTERVAL.min(debounce));
+        let scan = Snapshot::scan(root);
+        if scan != current {
+            current = scan;
+            quiet_since = Instant::now();
+        }
+    }
+    let changed = last.changed_paths(&current);
+    (current, changed)
+}
+
+/// Warnings that appeared and disappeared between two reloads.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct WarningDiff {
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+}
+
+impl WarningDiff {
+    /// Compare warning lists, ignoring order; repeated warnings count once.
+    pub fn between(previous: &[String], current: &[String]) -> Self {
+        let missing_from = |list: &[String], other: &[String]| {
+            let mut out: Vec<String> = list.iter().filter(|w| !other.contains(*w)).cloned().collect();
+            out.sort();
+            out.dedup();
+            out
+        };
+        Self {
+            added: missing_from(current, previous),
+            removed: missing_from(previous, current),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty()
+    }
+}
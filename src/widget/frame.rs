@@ -33,6 +33,80 @@ impl Color {
     }
 }
 
+/// Convert HSV (hue in degrees, saturation and value in 0..1) to RGB.
+pub fn hsv_to_rgb(h: f64, s: f64, v: f64) -> (f64, f64, f64) {
+    let h = h.rem_euclid(360.0);
+    let c = v * s;
+    let x = c * (1.0 - ((h / 60.0) % 2.0 - 1.0).abs());
+    let m = v - c;
+
+    let (r1, g1, b1) = if h < 60.0 {
+        (c, x, 0.0)
+    } else if h < 120.0 {
+        (x, c, 0.0)
+    } else if h < 180.0 {
+        (0.0, c, x)
+    } else if h < 240.0 {
+        (0.0, x, c)
+    } else if h < 300.0 {
+        (x, 0.0, c)
+    } else {
+        (c, 0.0, x)
+    };
+
+    (r1 + m, g1 + m, b1 + m)
+}
+
+/// Convert RGB to HSV.
+pub fn rgb_to_hsv(r: f64, g: f64, b: f64) -> (f64, f64, f64) {
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+    let delta = max - min;
+
+    let v = max;
+    let s = if max == 0.0 { 0.0 } else { delta / max };
+    let h = if delta == 0.0 {
+        0.0
+    } else if max == r {
+        60.0 * (((g - b) / delta) % 6.0)
+    } else if max == g {
+        60.0 * ((b - r) / delta + 2.0)
+    } else {
+        60.0 * ((r - g) / delta + 4.0)
+    };
+    let h = if h < 0.0 { h + 360.0 } else { h };
+
+    (h, s, v)
+}
+
+/// Gradient direction for `Texture:SetGradient`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GradientOrientation {
+    /// `minColor` on the left edge, `maxColor` on the right.
+    Horizontal,
+    /// `minColor` on the bottom edge, `maxColor` on the top.
+    Vertical,
+}
+
+impl GradientOrientation {
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s.to_ascii_uppercase().as_str() {
+            "HORIZONTAL" => Some(Self::Horizontal),
+            "VERTICAL" => Some(Self::Vertical),
+            _ => None,
+        }
+    }
+}
+
+/// Two-color gradient applied across a texture's vertices.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Gradient {
+    pub orientation: GradientOrientation,
+    pub min_color: Color,
+    pub max_color: Color,
+}
+
 /// Text justification for FontStrings.
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 pub enum TextJustify {
@@ -97,8 +171,12 @@ pub struct Backdrop {
     pub border_color: Color,
     /// Edge size (border thickness).
     pub edge_size: f32,
-    /// Insets from frame edges.
-    pub insets: f32,
+    /// Whether the background texture repeats instead of stretching.
+    pub tile: bool,
+    /// Size of one background tile (0 = texture's natural size).
+    pub tile_size: f32,
+    /// Background insets (left, right, top, bottom) from the frame edges.
+    pub insets: (f32, f32, f32, f32),
 }
 
 /// Anchor for Line widget start/end points.
@@ -110,6 +188,28 @@ pub struct LineAnchor {
     pub y_offset: f32,
 }
 
+/// A styled top-level block of SimpleHTML content, stacked vertically when rendered.
+#[derive(Debug, Clone, PartialEq)]
+pub enum HtmlBlock {
+    /// A heading or paragraph (`<h1>`-`<h3>`, `<p>`), word-wrapped to the frame width.
+    Text {
+        text: String,
+        font: Option<String>,
+        font_size: f32,
+        color: Color,
+        justify_h: TextJustify,
+        shadow_color: Color,
+        shadow_offset: (f32, f32),
+    },
+    /// An `<img>`; zero width/height means unspecified.
+    Image {
+        path: String,
+        width: f32,
+        height: f32,
+        justify_h: TextJustify,
+    },
+}
+
 /// A Frame is the base widget type in WoW's UI system.
 #[derive(Debug)]
 pub struct Frame {
@@ -133,6 +233,9 @@ pub struct Frame {
     pub visible: bool,
     /// Events this frame is registered to receive.
     pub registered_events: HashSet<String>,
+    /// Unit filters from RegisterUnitEvent: event name -> accepted unit tokens.
+    /// Events without an entry are delivered for every unit.
+    pub unit_event_filters: HashMap<String, Vec<String>>,
     /// Frame level (draw order within strata).
     pub frame_level: i32,
     /// Whether frame level was explicitly set (not inherited from parent).
@@ -141,6 +244,12 @@ pub struct Frame {
     pub frame_strata: FrameStrata,
     /// Whether frame strata was explicitly set (not inherited from parent).
     pub has_fixed_frame_strata: bool,
+    /// `hooksecurefunc` wrapped one of this frame's methods, so `__index`
+    /// prefers the wrapper stored in its fields over the shared method.
+    pub has_method_hooks: bool,
+    /// Whether height was explicitly set (SetHeight/SetSize/`<Size>`), so
+    /// word-wrapped FontStrings keep it instead of growing to their text.
+    pub has_fixed_height: bool,
     /// Top-level frame: auto-raised above siblings when shown.
     pub toplevel: bool,
     /// Alpha transparency (0.0 - 1.0).
@@ -152,12 +261,16 @@ pub struct Frame {
     pub anim_offset_x: f32,
     /// Additive animation translation offset (from Animation Translation, not anchors).
     pub anim_offset_y: f32,
+    /// Animation scale factor around the frame's center (from Animation Scale).
+    pub anim_scale_x: f32,
+    /// Animation scale factor around the frame's center (from Animation Scale).
+    pub anim_scale_y: f32,
     /// Scale factor (affects visible size; default 1.0).
     pub scale: f32,
     /// Effective scale (product of all ancestor scales × own scale).
     /// Updated eagerly when scale changes or frame is reparented.
     pub effective_scale: f32,
-    /// Whether mouse is enabled.
+    /// Whether mouse clicks are enabled (EnableMouse / SetMouseClickEnabled).
     pub mouse_enabled: bool,
     /// Hit rect insets (left, right, top, bottom) — shrinks the clickable area.
     pub hit_rect_insets: (f32, f32, f32, f32),
@@ -171,8 +284,12 @@ pub struct Frame {
     pub color_texture: Option<Color>,
     /// Vertex color for textures (tinting).
     pub vertex_color: Option<Color>,
+    /// Per-vertex gradient (from SetGradient), multiplied with the tint.
+    pub gradient: Option<Gradient>,
     /// Text content (for FontString widgets).
     pub text: Option<String>,
+    /// Styled content blocks (for SimpleHTML widgets).
+    pub html_blocks: Vec<HtmlBlock>,
     /// Title text (for DefaultPanelTemplate frames).
     pub title: Option<String>,
     /// Text color for FontStrings.
@@ -201,6 +318,9 @@ pub struct Frame {
     pub movable: bool,
     /// Whether the frame can be resized by the user.
     pub resizable: bool,
+    /// (minWidth, minHeight, maxWidth, maxHeight) from SetResizeBounds; a
+    /// max of 0 means unbounded.
+    pub resize_bounds: (f32, f32, f32, f32),
     /// Whether the frame is clamped to screen bounds.
     pub clamped_to_screen: bool,
     /// Whether the frame is currently being moved/dragged.
@@ -252,8 +372,9 @@ pub struct Frame {
     /// Texture coordinates (left, right, top, bottom) — final UV coords used for rendering.
     pub tex_coords: Option<(f32, f32, f32, f32)>,
     /// Raw 8-arg SetTexCoord values: [ULx, ULy, LLx, LLy, URx, URy, LRx, LRy].
-    /// Stored when 8-arg SetTexCoord is called, used by tiling code to detect
-    /// UV-based repeat tiling (BackdropTemplateMixin) where values >1.0 encode repeat counts.
+    /// Stored when 8-arg SetTexCoord is called. The renderer maps each corner
+    /// independently (rotated/flipped art); tiling code uses it to detect UV-based
+    /// repeat tiling (BackdropTemplateMixin) where values >1.0 encode repeat counts.
     pub tex_coords_quad: Option<[f32; 8]>,
     /// Atlas base texture coordinates — the sub-region on the texture file.
     /// SetTexCoord remaps relative to these when an atlas is active.
@@ -277,8 +398,12 @@ pub struct Frame {
     pub is_mask: bool,
     /// Mask texture IDs applied to this texture (for circular clipping etc.).
     pub mask_textures: Vec<u64>,
+    /// Mask file set with `SetMask` (or `mask="..."`), stretched over this texture.
+    pub mask_file: Option<String>,
     /// Texture rotation in radians (for SetRotation on Texture widgets).
     pub rotation: f32,
+    /// Rotation pivot in normalized frame coordinates (0,0 = top-left).
+    pub rotation_pivot: (f32, f32),
     /// Whether mouse motion events are enabled.
     pub mouse_motion_enabled: bool,
     /// User-set frame ID (from XML `id` attribute or SetID()).
@@ -301,7 +426,7 @@ pub struct Frame {
     pub slider_min: f64,
     /// Slider maximum value.
     pub slider_max: f64,
-    /// Slider step size.
+    /// Slider step size; SetValue rounds to it. 0 (the default) means continuous.
     pub slider_step: f64,
     /// Slider orientation ("HORIZONTAL" or "VERTICAL").
     pub slider_orientation: String,
@@ -309,6 +434,8 @@ pub struct Frame {
     pub slider_obey_step_on_drag: bool,
     /// Number of steps per page for slider.
     pub slider_steps_per_page: i32,
+    /// Thumb texture, placed along the track by layout according to the value.
+    pub slider_thumb_id: Option<u64>,
 
     // --- StatusBar fields ---
     /// Current statusbar value.
@@ -382,6 +509,14 @@ pub struct Frame {
     /// Whether cooldown is paused.
     pub cooldown_paused: bool,
 
+    // --- ColorSelect fields ---
+    /// Selected color as (hue in degrees, saturation, value).
+    pub color_hsv: (f64, f64, f64),
+    /// Texture whose rect holds the hue/saturation wheel (SetColorWheelTexture).
+    pub color_wheel_texture: Option<u64>,
+    /// Texture whose rect holds the value bar (SetColorValueTexture).
+    pub color_value_texture: Option<u64>,
+
     // --- Line fields ---
     /// Line start anchor (for Line widgets).
     pub line_start: Option<LineAnchor>,
@@ -413,15 +548,20 @@ macro_rules! frame_defaults {
             anchors: Vec::new(),
             visible: true,
             registered_events: HashSet::new(),
+            unit_event_filters: HashMap::new(),
             frame_level: 0,
             has_fixed_frame_level: false,
             frame_strata: FrameStrata::Medium,
             has_fixed_frame_strata: false,
+            has_fixed_height: false,
+            has_method_hooks: false,
             toplevel: false,
             alpha: 1.0,
             effective_alpha: 1.0,
             anim_offset_x: 0.0,
             anim_offset_y: 0.0,
+            anim_scale_x: 1.0,
+            anim_scale_y: 1.0,
             scale: 1.0,
             effective_scale: 1.0,
             mouse_enabled: false,
@@ -431,7 +571,9 @@ macro_rules! frame_defaults {
             texture: None,
             color_texture: None,
             vertex_color: None,
+            gradient: None,
             text: None,
+            html_blocks: Vec::new(),
             title: None,
             text_color: Color::new(1.0, 0.8, 0.2, 1.0),
             shadow_color: Color::new(0.0, 0.0, 0.0, 0.0),
@@ -446,6 +588,7 @@ macro_rules! frame_defaults {
             children_keys: HashMap::new(),
             movable: false,
             resizable: false,
+            resize_bounds: (0.0, 0.0, 0.0, 0.0),
             clamped_to_screen: false,
             is_moving: false,
             word_wrap: true,
@@ -482,7 +625,9 @@ macro_rules! frame_defaults {
             clips_children: false,
             is_mask: false,
             mask_textures: Vec::new(),
+            mask_file: None,
             rotation: 0.0,
+            rotation_pivot: (0.5, 0.5),
             mouse_motion_enabled: false,
             user_id: 0,
             button_state: 0,
@@ -493,10 +638,11 @@ macro_rules! frame_defaults {
             slider_value: 0.0,
             slider_min: 0.0,
             slider_max: 100.0,
-            slider_step: 1.0,
+            slider_step: 0.0,
             slider_orientation: "HORIZONTAL".to_string(),
             slider_obey_step_on_drag: false,
             slider_steps_per_page: 1,
+            slider_thumb_id: None,
 
             // StatusBar
             statusbar_value: 0.0,
@@ -538,6 +684,11 @@ macro_rules! frame_defaults {
             cooldown_hide_countdown: false,
             cooldown_paused: false,
 
+            // ColorSelect
+            color_hsv: (0.0, 0.0, 1.0),
+            color_wheel_texture: None,
+            color_value_texture: None,
+
             // Line
             line_start: None,
             line_end: None,
@@ -571,6 +722,7 @@ impl Frame {
     pub fn set_size(&mut self, width: f32, height: f32) {
         self.width = width;
         self.height = height;
+        self.has_fixed_height = height > 0.0;
     }
 
     pub fn set_point(
@@ -627,15 +779,95 @@ impl Frame {
 
     pub fn register_event(&mut self, event: &str) {
         self.registered_events.insert(event.to_string());
+        self.unit_event_filters.remove(event);
+    }
+
+    /// Register for `event` only when its first argument is one of `units`.
+    pub fn register_unit_event(&mut self, event: &str, units: Vec<String>) {
+        self.registered_events.insert(event.to_string());
+        self.unit_event_filters.insert(event.to_string(), units);
     }
 
     pub fn unregister_event(&mut self, event: &str) {
         self.registered_events.remove(event);
+        self.unit_event_filters.remove(event);
+    }
+
+    /// Drop every registration, including a RegisterAllEvents.
+    pub fn unregister_all_events(&mut self) {
+        self.registered_events.clear();
+        self.unit_event_filters.clear();
+        self.register_all_events = false;
+    }
+
+    /// Whether OnMouseDown/OnMouseUp/OnClick are delivered to this frame.
+    pub fn accepts_mouse_clicks(&self) -> bool {
+        self.mouse_enabled
+    }
+
+    /// Whether OnEnter/OnLeave are delivered to this frame.
+    pub fn accepts_mouse_motion(&self) -> bool {
+        self.mouse_motion_enabled
+    }
+
+    /// Whether the frame takes part in mouse hit testing at all.
+    pub fn is_mouse_hittable(&self) -> bool {
+        self.mouse_enabled || self.mouse_motion_enabled
     }
 
     pub fn is_registered_for_event(&self, event: &str) -> bool {
         self.register_all_events || self.registered_events.contains(event)
     }
+
+    /// Like `is_registered_for_event`, but also applies any RegisterUnitEvent
+    /// filter to the event's unit argument. A filtered event fired without a
+    /// unit argument is still delivered.
+    pub fn accepts_unit_event(&self, event: &str, unit: Option<&str>) -> bool {
+        if !self.is_registered_for_event(event) {
+            return false;
+        }
+        match (self.unit_event_filters.get(event), unit) {
+            (Some(units), Some(unit)) => units.iter().any(|u| u.eq_ignore_ascii_case(unit)),
+            _ => true,
+        }
+    }
+
+    /// Insert text at the EditBox cursor (a char index), truncated to
+    /// `editbox_max_letters`. Returns the text actually inserted.
+    pub fn editbox_insert(&mut self, text: &str) -> String {
+        let current = self.text.get_or_insert_with(String::new);
+        let len = current.chars().count();
+        let room = if self.editbox_max_letters > 0 {
+            (self.editbox_max_letters as usize).saturating_sub(len)
+        } else {
+            usize::MAX
+        };
+        let inserted: String = text.chars().take(room).collect();
+        if inserted.is_empty() {
+            return inserted;
+        }
+        let char_pos = (self.editbox_cursor_pos.max(0) as usize).min(len);
+        let byte_pos = current
+            .char_indices()
+            .nth(char_pos)
+            .map(|(i, _)| i)
+            .unwrap_or(current.len());
+        current.insert_str(byte_pos, &inserted);
+        self.editbox_cursor_pos = (char_pos + inserted.chars().count()) as i32;
+        inserted
+    }
+
+    /// Replace EditBox text as SetText does: truncate to
+    /// `editbox_max_letters` and move the cursor to the end.
+    pub fn editbox_set_text(&mut self, text: &str) {
+        let text: String = if self.editbox_max_letters > 0 {
+            text.chars().take(self.editbox_max_letters as usize).collect()
+        } else {
+            text.to_string()
+        };
+        self.editbox_cursor_pos = text.chars().count() as i32;
+        self.text = Some(text);
+    }
 }
 
 /// Frame strata (draw order).
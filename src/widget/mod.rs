@@ -5,7 +5,7 @@ mod frame;
 mod registry;
 
 pub use anchor::{Anchor, AnchorPoint};
-pub use frame::{AttributeValue, Backdrop, Color, DrawLayer, Frame, FrameStrata, LineAnchor, TextJustify, TextOutline};
+pub use frame::{hsv_to_rgb, rgb_to_hsv, AttributeValue, Backdrop, Color, DrawLayer, Frame, FrameStrata, Gradient, GradientOrientation, HtmlBlock, LineAnchor, TextJustify, TextOutline};
 pub use crate::atlas::NineSliceAtlasInfo;
 pub use registry::WidgetRegistry;
 
@@ -18,6 +18,44 @@ pub fn next_widget_id() -> u64 {
     NEXT_WIDGET_ID.fetch_add(1, Ordering::Relaxed)
 }
 
+/// WoW object type hierarchy as (type, parent type) pairs, used by
+/// `IsObjectType`. Abstract bases (Region, TextureBase, ...) have no
+/// `WidgetType` of their own. Frames are treated as Regions, as in the
+/// classic API.
+pub const OBJECT_TYPE_PARENTS: &[(&str, &str)] = &[
+    ("Object", "FrameScriptObject"),
+    ("ScriptRegion", "Object"),
+    ("Region", "ScriptRegion"),
+    ("TextureBase", "Region"),
+    ("Texture", "TextureBase"),
+    ("Line", "Texture"),
+    ("FontString", "Region"),
+    ("Frame", "Region"),
+    ("Button", "Frame"),
+    ("CheckButton", "Button"),
+    ("EditBox", "Frame"),
+    ("ScrollFrame", "Frame"),
+    ("Slider", "Frame"),
+    ("StatusBar", "Frame"),
+    ("Cooldown", "Frame"),
+    ("Model", "Frame"),
+    ("PlayerModel", "Model"),
+    ("ModelScene", "Frame"),
+    ("ColorSelect", "Frame"),
+    ("MessageFrame", "Frame"),
+    ("SimpleHTML", "Frame"),
+    ("GameTooltip", "Frame"),
+    ("Minimap", "Frame"),
+];
+
+/// Parent of a WoW object type in `OBJECT_TYPE_PARENTS`.
+pub fn object_type_parent(type_name: &str) -> Option<&'static str> {
+    OBJECT_TYPE_PARENTS
+        .iter()
+        .find(|(name, _)| *name == type_name)
+        .map(|&(_, parent)| parent)
+}
+
 /// Widget types supported by the simulator.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum WidgetType {
@@ -72,6 +110,26 @@ impl WidgetType {
         }
     }
 
+    /// Regions (textures, fontstrings, lines) are drawn by their parent frame
+    /// and are not frames themselves: GetChildren skips them, GetRegions
+    /// returns only them.
+    pub fn is_region(&self) -> bool {
+        matches!(self, Self::Texture | Self::FontString | Self::Line)
+    }
+
+    /// Whether this type is, or inherits from, the named WoW type
+    /// (case-insensitive): a CheckButton is a Button, Frame and Region.
+    pub fn is_object_type(&self, type_name: &str) -> bool {
+        let mut current = Some(self.as_str());
+        while let Some(name) = current {
+            if name.eq_ignore_ascii_case(type_name) {
+                return true;
+            }
+            current = object_type_parent(name);
+        }
+        false
+    }
+
     pub fn as_str(&self) -> &'static str {
         match self {
             Self::Frame => "Frame",
@@ -1,6 +1,6 @@
 //! Global widget registry for tracking all widgets.
 
-use super::Frame;
+use super::{Anchor, Frame};
 use std::cell::RefCell;
 use std::collections::{HashMap, HashSet, VecDeque};
 
@@ -20,6 +20,9 @@ pub struct WidgetRegistry {
     rect_dirty_ids: HashSet<u64>,
     /// Frames with `layout_rect = None` that need layout computation.
     pending_layout_ids: HashSet<u64>,
+    /// Frames with anchors whose `relative_to` target (name or relativeKey
+    /// path) did not exist yet when the anchor was set.
+    deferred_anchor_ids: HashSet<u64>,
 }
 
 impl WidgetRegistry {
@@ -117,6 +120,16 @@ impl WidgetRegistry {
             .collect()
     }
 
+    /// Get all widgets that should receive `event` fired for `unit`,
+    /// honoring RegisterUnitEvent filters.
+    pub fn get_unit_event_listeners(&self, event: &str, unit: Option<&str>) -> Vec<u64> {
+        self.widgets
+            .values()
+            .filter(|w| w.accepts_unit_event(event, unit))
+            .map(|w| w.id)
+            .collect()
+    }
+
     /// Add a child to a parent widget.
     pub fn add_child(&mut self, parent_id: u64, child_id: u64) {
         if let Some(parent) = self.widgets.get_mut(&parent_id) {
@@ -221,6 +234,19 @@ impl WidgetRegistry {
         }
     }
 
+    /// Product of `alpha` over the frame and its ancestors, regardless of
+    /// visibility. This is what `GetEffectiveAlpha()` reports; the cached
+    /// `effective_alpha` is 0 for hidden frames.
+    pub fn alpha_through_parents(&self, id: u64) -> f32 {
+        let mut alpha = 1.0;
+        let mut current = Some(id);
+        while let Some(f) = current.and_then(|cid| self.widgets.get(&cid)) {
+            alpha *= f.alpha;
+            current = f.parent_id;
+        }
+        alpha
+    }
+
     /// Propagate effective_alpha for ALL frames from root. Called once at startup
     /// to initialize effective_alpha after all frames are created and parented.
     pub fn propagate_all_effective_alpha(&mut self) {
@@ -400,6 +426,127 @@ impl WidgetRegistry {
         }
     }
 
+    /// Resolve a relativeKey path like `$parent.Header` or `$parent.$parent.ScrollBox`
+    /// starting at `frame_id`. `$parent` segments walk `parent_id`; other segments
+    /// look up `children_keys`. A `$parentFoo` segment names the global frame
+    /// `<parent name>Foo`, like `$parent` in XML frame names.
+    pub fn resolve_relative_key(&self, frame_id: u64, key: &str) -> Option<u64> {
+        let mut current = frame_id;
+        for part in key.split('.') {
+            if part.is_empty() {
+                continue;
+            }
+            if matches!(part, "$parent" | "$Parent" | "$parentKey") {
+                current = self.widgets.get(&current)?.parent_id?;
+            } else if let Some(suffix) = part.strip_prefix("$parent").or_else(|| part.strip_prefix("$Parent")) {
+                let parent_id = self.widgets.get(&current)?.parent_id?;
+                let parent_name = self.widgets.get(&parent_id)?.name.as_deref()?;
+                current = self.get_id_by_name(&format!("{parent_name}{suffix}"))?;
+            } else {
+                current = *self.widgets.get(&current)?.children_keys.get(part)?;
+            }
+        }
+        (current != frame_id).then_some(current)
+    }
+
+    /// Resolve the frame an anchor is relative to.
+    ///
+    /// Prefers the bound `relative_to_id`; otherwise resolves `relative_to` as a
+    /// relativeKey path (contains `$parent` or `.`) or a global frame name.
+    /// Returns `None` when the anchor targets the implicit parent or the named
+    /// target doesn't exist (yet).
+    pub fn resolve_anchor_target(&self, frame_id: u64, anchor: &Anchor) -> Option<u64> {
+        if let Some(id) = anchor.relative_to_id {
+            return Some(id as u64);
+        }
+        let rel = anchor.relative_to.as_deref()?;
+        if rel.starts_with('$') || rel.contains('.') {
+            self.resolve_relative_key(frame_id, rel)
+        } else {
+            self.get_id_by_name(rel)
+        }
+    }
+
+    /// Record that `frame_id` has an anchor whose target couldn't be resolved yet.
+    pub fn defer_anchor_resolution(&mut self, frame_id: u64) {
+        self.deferred_anchor_ids.insert(frame_id);
+    }
+
+    /// Whether any frames are waiting on deferred anchor targets.
+    pub fn has_deferred_anchors(&self) -> bool {
+        !self.deferred_anchor_ids.is_empty()
+    }
+
+    /// Bind deferred anchors whose targets now exist to concrete frame IDs.
+    ///
+    /// Sets `relative_to_id` and registers the anchor dependent. Frames whose
+    /// targets are still missing stay deferred (and keep laying out relative to
+    /// their parent). Returns the IDs whose anchors were bound.
+    pub fn bind_deferred_anchors(&mut self) -> Vec<u64> {
+        let ids: Vec<u64> = self.deferred_anchor_ids.iter().copied().collect();
+        self.bind_anchors_of(ids)
+    }
+
+    /// Like `bind_deferred_anchors`, but only for frames with a pending anchor
+    /// path ending in `key` (e.g. `$parent.Header` when `Header` is assigned).
+    pub fn bind_deferred_anchors_for_key(&mut self, key: &str) -> Vec<u64> {
+        let ids: Vec<u64> = self
+            .deferred_anchor_ids
+            .iter()
+            .copied()
+            .filter(|id| {
+                self.widgets.get(id).is_none_or(|f| {
+                    f.anchors.iter().any(|a| {
+                        a.relative_to_id.is_none()
+                            && a.relative_to.as_deref().is_some_and(|rel| rel.rsplit('.').next() == Some(key))
+                    })
+                })
+            })
+            .collect();
+        self.bind_anchors_of(ids)
+    }
+
+    /// Bind the pending anchors of `ids`, dropping frames that are gone or
+    /// have nothing left to resolve from the deferred set.
+    fn bind_anchors_of(&mut self, ids: Vec<u64>) -> Vec<u64> {
+        let mut bound_ids = Vec::new();
+        for id in ids {
+            let Some(frame) = self.widgets.get(&id) else {
+                self.deferred_anchor_ids.remove(&id);
+                continue;
+            };
+            let mut bindings = Vec::new();
+            let mut still_pending = false;
+            for (idx, anchor) in frame.anchors.iter().enumerate() {
+                if anchor.relative_to_id.is_some() || anchor.relative_to.is_none() {
+                    continue;
+                }
+                match self.resolve_anchor_target(id, anchor) {
+                    Some(target) if !self.would_create_anchor_cycle(id, target) => {
+                        bindings.push((idx, target));
+                    }
+                    _ => still_pending = true,
+                }
+            }
+            if !still_pending {
+                self.deferred_anchor_ids.remove(&id);
+            }
+            if bindings.is_empty() {
+                continue;
+            }
+            for &(_, target) in &bindings {
+                self.add_anchor_dependent(target, id);
+            }
+            if let Some(f) = self.widgets.get_mut(&id) {
+                for (idx, target) in bindings {
+                    f.anchors[idx].relative_to_id = Some(target as usize);
+                }
+            }
+            bound_ids.push(id);
+        }
+        bound_ids
+    }
+
     /// Get frame IDs anchored to `target_id`.
     pub fn get_anchor_dependents(&self, target_id: u64) -> Option<&HashSet<u64>> {
         self.anchor_dependents.get(&target_id)
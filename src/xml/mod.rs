@@ -9,20 +9,21 @@ mod types_elements;
 pub use parse::{parse_xml, parse_xml_file, XmlLoadError};
 pub use template::{
     anim_group_template_registry_read, clear_templates, collect_anim_group_mixins,
-    collect_texture_mixins, get_template, get_template_chain, get_template_info,
-    register_anim_group_template, register_intrinsic_templates, register_template,
-    register_texture_template, TemplateEntry, TemplateInfo,
+    collect_texture_mixins, find_missing_templates, get_template, get_template_chain,
+    get_template_info, register_anim_group_template, register_intrinsic_templates, register_template,
+    register_texture_template, MissingTemplate, TemplateEntry, TemplateInfo,
 };
 pub use types::{
-    AbsDimensionXml, AnchorXml, AnchorsXml, AnimationsXml, AttributeXml, AttributesXml,
-    BackdropXml, ColorXml, FontRefXml, FrameChildElement, FrameXml, ScopedModifierXml,
+    AbsDimensionXml, AbsValueXml, AnchorXml, AnchorsXml, AnimationsXml, AttributeXml,
+    AttributesXml, BackdropInsetsXml, BackdropValueXml, BackdropXml, ColorXml, FontRefXml,
+    FrameChildElement, FrameXml, ScopedModifierXml,
     InsetsXml, KeyValueXml, KeyValuesXml, OffsetXml,
     ResizeBoundsXml, ScriptBodyXml, ScriptsXml, ScrollChildXml, SizeXml,
     UiXml, XmlElement,
 };
 pub use types_elements::{
-    ActorXml, ActorsXml, AnimationElement, AnimationGroupXml, AnimationXml, FontFamilyXml,
-    FontStringXml, FontXml, FrameElement, FramesXml, IncludeXml, LayerElement, LayerXml,
+    ActorXml, ActorsXml, AnimOffsetXml, AnimationElement, AnimationGroupXml, AnimationXml,
+    FontFamilyXml, FontStringXml, FontXml, FrameElement, FramesXml, IncludeXml, LayerElement, LayerXml,
     LayersXml, ScriptXml, TextureXml,
 };
 
@@ -139,6 +139,52 @@ fn collect_template_chain(name: &str, chain: &mut Vec<TemplateEntry>, visited: &
     }
 }
 
+/// A template named by CreateFrame or an `inherits` list that isn't registered.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MissingTemplate {
+    pub name: String,
+    /// Template whose `inherits` names it; `None` when it was requested directly.
+    pub required_by: Option<String>,
+}
+
+/// Templates in the inheritance chain of `names` that aren't registered.
+/// Registered templates count even when they define nothing.
+pub fn find_missing_templates(names: &str) -> Vec<MissingTemplate> {
+    let mut missing = Vec::new();
+    let mut visited = HashSet::new();
+    for name in names.split(',').map(|s| s.trim()) {
+        if !name.is_empty() {
+            collect_missing_templates(name, None, &mut missing, &mut visited);
+        }
+    }
+    missing
+}
+
+fn collect_missing_templates(
+    name: &str,
+    required_by: Option<&str>,
+    missing: &mut Vec<MissingTemplate>,
+    visited: &mut HashSet<String>,
+) {
+    if !visited.insert(name.to_string()) {
+        return;
+    }
+    let Some(entry) = get_template(name) else {
+        missing.push(MissingTemplate {
+            name: name.to_string(),
+            required_by: required_by.map(str::to_string),
+        });
+        return;
+    };
+    if let Some(ref inherits) = entry.frame.inherits {
+        for parent in inherits.split(',').map(|s| s.trim()) {
+            if !parent.is_empty() {
+                collect_missing_templates(parent, Some(&entry.name), missing, visited);
+            }
+        }
+    }
+}
+
 /// Register synthetic templates for C++ intrinsic frame types.
 ///
 /// WoW has several frame types built into the C++ engine that don't have XML
@@ -126,6 +126,8 @@ pub struct FrameXml {
     pub enable_mouse: Option<bool>,
     #[serde(rename = "@clampedToScreen")]
     pub clamped_to_screen: Option<bool>,
+    #[serde(rename = "@resizable")]
+    pub resizable: Option<bool>,
     /// Button text attribute (localization key or literal text).
     #[serde(rename = "@text")]
     pub text: Option<String>,
@@ -260,6 +262,22 @@ impl FrameXml {
         })
     }
 
+    /// Get the legacy Backdrop element if present.
+    pub fn backdrop(&self) -> Option<&BackdropXml> {
+        self.children.iter().find_map(|c| match c {
+            FrameChildElement::Backdrop(b) => Some(b),
+            _ => None,
+        })
+    }
+
+    /// Get the ResizeBounds element if present.
+    pub fn resize_bounds(&self) -> Option<&ResizeBoundsXml> {
+        self.children.iter().find_map(|c| match c {
+            FrameChildElement::ResizeBounds(r) => Some(r),
+            _ => None,
+        })
+    }
+
     /// Get the HitRectInsets element if present.
     pub fn hit_rect_insets(&self) -> Option<&InsetsXml> {
         self.children.iter().find_map(|c| match c {
@@ -492,6 +510,73 @@ pub struct BackdropXml {
     pub edge_file: Option<String>,
     #[serde(rename = "@tile")]
     pub tile: Option<bool>,
+    #[serde(rename = "EdgeSize")]
+    pub edge_size: Option<BackdropValueXml>,
+    #[serde(rename = "TileSize")]
+    pub tile_size: Option<BackdropValueXml>,
+    #[serde(rename = "BackgroundInsets")]
+    pub background_insets: Option<BackdropInsetsXml>,
+    #[serde(rename = "Color")]
+    pub color: Option<ColorXml>,
+    #[serde(rename = "BorderColor")]
+    pub border_color: Option<ColorXml>,
+}
+
+/// Backdrop size value: `<EdgeSize val="16"/>` or `<EdgeSize><AbsValue val="16"/></EdgeSize>`.
+#[derive(Debug, Deserialize, Default, Clone)]
+pub struct BackdropValueXml {
+    #[serde(rename = "@val")]
+    pub val: Option<f32>,
+    #[serde(rename = "AbsValue")]
+    pub abs_value: Option<AbsValueXml>,
+}
+
+impl BackdropValueXml {
+    pub fn value(&self) -> Option<f32> {
+        self.val.or_else(|| self.abs_value.as_ref().and_then(|v| v.val))
+    }
+}
+
+/// `<AbsValue val="..."/>`.
+#[derive(Debug, Deserialize, Default, Clone)]
+pub struct AbsValueXml {
+    #[serde(rename = "@val")]
+    pub val: Option<f32>,
+}
+
+/// Backdrop background insets: attributes directly or an `<AbsInset>` child.
+#[derive(Debug, Deserialize, Default, Clone)]
+pub struct BackdropInsetsXml {
+    #[serde(rename = "@left")]
+    pub left: Option<f32>,
+    #[serde(rename = "@right")]
+    pub right: Option<f32>,
+    #[serde(rename = "@top")]
+    pub top: Option<f32>,
+    #[serde(rename = "@bottom")]
+    pub bottom: Option<f32>,
+    #[serde(rename = "AbsInset")]
+    pub abs_inset: Option<InsetsXml>,
+}
+
+impl BackdropInsetsXml {
+    /// Resolved (left, right, top, bottom), preferring the `<AbsInset>` child.
+    pub fn values(&self) -> (f32, f32, f32, f32) {
+        match &self.abs_inset {
+            Some(i) => (
+                i.left.unwrap_or(0.0),
+                i.right.unwrap_or(0.0),
+                i.top.unwrap_or(0.0),
+                i.bottom.unwrap_or(0.0),
+            ),
+            None => (
+                self.left.unwrap_or(0.0),
+                self.right.unwrap_or(0.0),
+                self.top.unwrap_or(0.0),
+                self.bottom.unwrap_or(0.0),
+            ),
+        }
+    }
 }
 
 /// ResizeBounds element.
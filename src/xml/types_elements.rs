@@ -90,6 +90,9 @@ pub struct TextureXml {
     pub alpha: Option<f32>,
     #[serde(rename = "@alphaMode")]
     pub alpha_mode: Option<String>,
+    /// Mask file applied with `SetMask` (e.g. a circular portrait mask).
+    #[serde(rename = "@mask")]
+    pub mask: Option<String>,
     #[serde(rename = "@setAllPoints")]
     pub set_all_points: Option<bool>,
     #[serde(rename = "@parentArray")]
@@ -347,6 +350,9 @@ pub struct AnimationXml {
     pub target: Option<String>,
     #[serde(rename = "@targetKey")]
     pub target_key: Option<String>,
+    /// Animation type for generic `<Animation type="Alpha">` elements.
+    #[serde(rename = "@type")]
+    pub anim_type: Option<String>,
     #[serde(rename = "@duration")]
     pub duration: Option<f32>,
     #[serde(rename = "@order")]
@@ -367,6 +373,9 @@ pub struct AnimationXml {
     pub offset_x: Option<f32>,
     #[serde(rename = "@offsetY")]
     pub offset_y: Option<f32>,
+    /// `<Offset x="" y=""/>` child, an alternative to offsetX/offsetY.
+    #[serde(rename = "Offset")]
+    pub offset: Option<AnimOffsetXml>,
     // Scale
     #[serde(rename = "@scaleX")]
     pub scale_x: Option<f32>,
@@ -402,6 +411,15 @@ pub struct AnimationXml {
     pub curve: Option<String>,
 }
 
+/// Translation offset child element of an animation.
+#[derive(Debug, Deserialize, Default, Clone)]
+pub struct AnimOffsetXml {
+    #[serde(rename = "@x")]
+    pub x: Option<f32>,
+    #[serde(rename = "@y")]
+    pub y: Option<f32>,
+}
+
 /// Actors container for ModelScene.
 #[derive(Debug, Deserialize, Default, Clone)]
 pub struct ActorsXml {
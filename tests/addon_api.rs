@@ -223,6 +223,56 @@ fn test_get_addon_metadata_unknown_addon() {
     assert_eq!(title, "Unknown");
 }
 
+#[test]
+fn test_get_addon_metadata_reads_toc_fields() {
+    let dir = tempfile::tempdir().unwrap();
+    let addon_dir = dir.path().join("TomTom");
+    std::fs::create_dir(&addon_dir).unwrap();
+    std::fs::write(
+        addon_dir.join("TomTom.toc"),
+        "## Interface: 110207\n## Title: TomTom\n## Version: v4.0.1-release\n\
+         ## Author: jnwhiteh\n## LoadOnDemand: 1\n## X-Website: https://example.invalid/tomtom\n\
+         TomTom.lua\n",
+    )
+    .unwrap();
+    std::fs::write(addon_dir.join("TomTom.lua"), "TomTomLoaded = true").unwrap();
+
+    let env = WowLuaEnv::new().unwrap();
+    env.state().borrow_mut().addon_base_paths = vec![dir.path().to_path_buf()];
+    env.scan_and_register_addons(dir.path());
+
+    let (version, author, website, missing): (String, String, String, Option<String>) = env
+        .eval(
+            r#"return C_AddOns.GetAddOnMetadata("TomTom", "Version"),
+                GetAddOnMetadata("tomtom", "author"),
+                C_AddOns.GetAddOnMetadata("TomTom", "X-Website"),
+                C_AddOns.GetAddOnMetadata("TomTom", "X-Missing")"#,
+        )
+        .unwrap();
+    assert_eq!(version, "v4.0.1-release");
+    assert_eq!(author, "jnwhiteh");
+    assert_eq!(website, "https://example.invalid/tomtom");
+    assert_eq!(missing, None);
+
+    let (name, title, _notes, loadable, reason): (String, String, String, bool, Option<String>) =
+        env.eval(r#"return C_AddOns.GetAddOnInfo("TomTom")"#).unwrap();
+    assert_eq!((name.as_str(), title.as_str()), ("TomTom", "TomTom"));
+    assert!(loadable);
+    assert_eq!(reason, None);
+
+    let before: bool = env.eval(r#"return C_AddOns.IsAddOnLoaded("TomTom")"#).unwrap();
+    assert!(!before);
+    let (loaded, _): (bool, Option<String>) =
+        env.eval(r#"return C_AddOns.LoadAddOn("TomTom")"#).unwrap();
+    assert!(loaded);
+    let after: bool = env
+        .eval(r#"return C_AddOns.IsAddOnLoaded("TomTom") and IsAddOnLoaded("tomtom")"#)
+        .unwrap();
+    assert!(after);
+    let version: String = env.eval(r#"return C_AddOns.GetAddOnMetadata("TomTom", "Version")"#).unwrap();
+    assert_eq!(version, "v4.0.1-release");
+}
+
 // ============================================================================
 // C_AddOns.DoesAddOnExist
 // ============================================================================
@@ -419,3 +469,110 @@ fn test_profiler_app_vs_overall_metric_differ() {
     let pct = overall_val / app_val * 100.0;
     assert!(pct < 100.0, "Addon CPU percentage should be < 100%, got {pct:.1}%");
 }
+
+// ============================================================================
+// reload_addon (Lua server hot reload)
+// ============================================================================
+
+#[test]
+fn test_reload_addon_reruns_files_and_detaches_old_frames() {
+    use wow_ui_sim::lua_api::globals::addon_api::reload_addon;
+
+    let dir = tempfile::tempdir().unwrap();
+    let addon_dir = dir.path().join("HotAddon");
+    std::fs::create_dir(&addon_dir).unwrap();
+    std::fs::write(addon_dir.join("HotAddon.toc"), "## Interface: 110207\n## Title: Hot\nHotAddon.lua\n").unwrap();
+    std::fs::write(
+        addon_dir.join("HotAddon.lua"),
+        r#"
+        HotLoads = (HotLoads or 0) + 1
+        HotEvents = HotEvents or {}
+        local f = CreateFrame("Frame", "HotFrame" .. HotLoads, UIParent)
+        f:RegisterEvent("PLAYER_ENTERING_WORLD")
+        f:SetScript("OnEvent", function() table.insert(HotEvents, HotLoads) end)
+        "#,
+    )
+    .unwrap();
+
+    let env = WowLuaEnv::new().unwrap();
+    env.state().borrow_mut().addon_base_paths = vec![dir.path().to_path_buf()];
+    env.scan_and_register_addons(dir.path());
+    env.exec(r#"C_AddOns.LoadAddOn("HotAddon")"#).unwrap();
+
+    let result = reload_addon(env.lua(), env.state(), "HotAddon").unwrap();
+    assert_eq!(result.lua_files, 1);
+    assert!(result.warnings.is_empty(), "{:?}", result.warnings);
+
+    env.fire_event("PLAYER_ENTERING_WORLD").unwrap();
+    let events: String = env.eval("return table.concat(HotEvents, ',')").unwrap();
+    assert_eq!(events, "2", "only the reloaded frame should still receive events");
+    let old_shown: bool = env.eval("return HotFrame1:IsShown()").unwrap();
+    assert!(!old_shown);
+
+    let result = reload_addon(env.lua(), env.state(), "hotaddon").unwrap();
+    assert_eq!(result.lua_files, 1, "addon names are case-insensitive");
+    let shown: bool = env.eval("return HotFrame2:IsShown()").unwrap();
+    assert!(!shown, "a case-mismatched reload still detaches the previous load");
+
+    assert!(reload_addon(env.lua(), env.state(), "NoSuchAddon").is_err());
+}
+
+// ============================================================================
+// LoadAddOn (on-demand loading)
+// ============================================================================
+
+#[test]
+fn test_load_addon_loads_load_on_demand_addon_from_disk() {
+    let dir = tempfile::tempdir().unwrap();
+    let addon_dir = dir.path().join("LodPanel");
+    std::fs::create_dir(&addon_dir).unwrap();
+    std::fs::write(
+        addon_dir.join("LodPanel.toc"),
+        "## Interface: 110207\n## Title: LOD Panel\n## LoadOnDemand: 1\nLodPanel.lua\n",
+    )
+    .unwrap();
+    std::fs::write(
+        addon_dir.join("LodPanel.lua"),
+        r#"LodPanelLoads = (LodPanelLoads or 0) + 1
+        CreateFrame("Frame", "LodPanelFrame", UIParent)"#,
+    )
+    .unwrap();
+
+    let env = WowLuaEnv::new().unwrap();
+    env.state().borrow_mut().addon_base_paths = vec![dir.path().to_path_buf()];
+    env.scan_and_register_addons(dir.path());
+    env.exec(
+        r#"
+        LoadedEvents = {}
+        local watcher = CreateFrame("Frame")
+        watcher:RegisterEvent("ADDON_LOADED")
+        watcher:SetScript("OnEvent", function(_, _, name) table.insert(LoadedEvents, name) end)
+        "#,
+    )
+    .unwrap();
+
+    let before: bool = env.eval("return LodPanelFrame == nil and not C_AddOns.IsAddOnLoaded('LodPanel')").unwrap();
+    assert!(before, "LoadOnDemand addon must not load at startup");
+
+    let (loaded, reason): (bool, Option<String>) =
+        env.eval(r#"return C_AddOns.LoadAddOn("lodpanel")"#).unwrap();
+    assert!(loaded, "{reason:?}");
+    assert_eq!(reason, None);
+    let frame_exists: bool = env.eval("return LodPanelFrame ~= nil").unwrap();
+    assert!(frame_exists);
+    let is_loaded: bool = env.eval("return C_AddOns.IsAddOnLoaded('LodPanel')").unwrap();
+    assert!(is_loaded);
+
+    // Loading again (legacy global) is a no-op success.
+    let (again, _): (bool, Option<String>) = env.eval(r#"return LoadAddOn("LodPanel")"#).unwrap();
+    assert!(again);
+    let loads: i32 = env.eval("return LodPanelLoads").unwrap();
+    assert_eq!(loads, 1);
+    let events: String = env.eval("return table.concat(LoadedEvents, ',')").unwrap();
+    assert_eq!(events, "LodPanel");
+
+    let (missing, reason): (bool, Option<String>) =
+        env.eval(r#"return C_AddOns.LoadAddOn("NoSuchAddon")"#).unwrap();
+    assert!(!missing);
+    assert_eq!(reason.as_deref(), Some("MISSING"));
+}
@@ -1,6 +1,9 @@
-//! Tests for AnimationGroup lifecycle: Play, Stop, Pause, tick, OnFinished, looping, alpha.
+//! Tests for AnimationGroup lifecycle: Play, Stop, Pause, tick, OnFinished, looping, alpha;
+//! and animation groups parsed from XML <Animations>.
 
+use wow_ui_sim::loader::create_frame_from_xml;
 use wow_ui_sim::lua_api::WowLuaEnv;
+use wow_ui_sim::xml::{parse_xml, XmlElement};
 
 fn setup() -> WowLuaEnv {
     WowLuaEnv::new().expect("Failed to create Lua environment")
@@ -838,3 +841,229 @@ fn animation_delays() {
         assert(ag:GetDuration() == 0.8, "Duration with delays should be 0.8, got " .. ag:GetDuration())
     "#).unwrap();
 }
+
+#[test]
+fn tick_scale_animation_scales_layout_rect() {
+    let env = setup();
+    env.exec(r#"
+        local f = CreateFrame("Frame", "TestAnimFrameScale", UIParent)
+        f:SetSize(100, 50)
+        f:SetPoint("CENTER")
+        local ag = f:CreateAnimationGroup()
+        local anim = ag:CreateAnimation("Scale")
+        anim:SetDuration(1.0)
+        anim:SetScaleFrom(1, 1)
+        anim:SetScaleTo(2, 2)
+        ag:Play()
+    "#).unwrap();
+
+    let before = {
+        let mut state = env.state().borrow_mut();
+        state.ensure_layout_rects();
+        let id = state.widgets.get_id_by_name("TestAnimFrameScale").unwrap();
+        state.widgets.get(id).unwrap().layout_rect.unwrap()
+    };
+
+    env.fire_on_update(0.5).unwrap();
+
+    let mut state = env.state().borrow_mut();
+    state.ensure_layout_rects();
+    let id = state.widgets.get_id_by_name("TestAnimFrameScale").unwrap();
+    let rect = state.widgets.get(id).unwrap().layout_rect.unwrap();
+    assert!((rect.width - 150.0).abs() < 0.5, "width at 1.5x should be 150, got {}", rect.width);
+    assert!((rect.height - 75.0).abs() < 0.5, "height at 1.5x should be 75, got {}", rect.height);
+    // Scaling is around the center, so the center stays put.
+    let center = |r: &wow_ui_sim::LayoutRect| (r.x + r.width / 2.0, r.y + r.height / 2.0);
+    let (cx, cy) = center(&rect);
+    let (bx, by) = center(&before);
+    assert!((cx - bx).abs() < 0.5 && (cy - by).abs() < 0.5);
+}
+
+#[test]
+fn settle_animations_reaches_final_alpha() {
+    let env = setup();
+    env.exec(r#"
+        local f = CreateFrame("Frame", "TestAnimFrameSettle", UIParent)
+        local ag = f:CreateAnimationGroup()
+        ag:SetToFinalAlpha(true)
+        local anim = ag:CreateAnimation("Alpha")
+        anim:SetDuration(1.0)
+        anim:SetSmoothing("OUT")
+        anim:SetFromAlpha(0)
+        anim:SetToAlpha(1)
+        TestAnimSettleGroup = ag
+        ag:Play()
+    "#).unwrap();
+
+    // A few headless ticks leave the fade partway through.
+    for _ in 0..3 {
+        wow_ui_sim::startup::fire_one_on_update_tick(&env);
+    }
+    let mid: f64 = env.eval("return TestAnimFrameSettle:GetAlpha()").unwrap();
+    assert!(mid < 1.0, "fade should still be in progress, got {mid}");
+
+    wow_ui_sim::startup::settle_animations(&env);
+    let alpha: f64 = env.eval("return TestAnimFrameSettle:GetAlpha()").unwrap();
+    assert!((alpha - 1.0).abs() < 1e-6, "settled alpha should be 1, got {alpha}");
+    let playing: bool = env.eval("return TestAnimSettleGroup:IsPlaying()").unwrap();
+    assert!(!playing);
+}
+
+// ============================================================================
+// XML <Animations>
+// ============================================================================
+
+/// Env with "XmlAnimFrame" (holding a "Glow" texture) created from XML with
+/// the given `<AnimationGroup parentKey="Anim">` children.
+fn setup_xml_anim(animations: &str) -> WowLuaEnv {
+    setup_xml_anim_group("", animations)
+}
+
+/// Like `setup_xml_anim`, with extra attributes on the `<AnimationGroup>`.
+fn setup_xml_anim_group(group_attrs: &str, animations: &str) -> WowLuaEnv {
+    let env = setup();
+    let xml = format!(r#"
+        <Ui>
+            <Frame name="XmlAnimFrame" parent="UIParent">
+                <Size x="100" y="100"/>
+                <Anchors><Anchor point="CENTER"/></Anchors>
+                <Layers>
+                    <Layer level="ARTWORK">
+                        <Texture parentKey="Glow"><Size x="10" y="10"/></Texture>
+                    </Layer>
+                </Layers>
+                <Animations>
+                    <AnimationGroup parentKey="Anim" {group_attrs}>
+                        {animations}
+                    </AnimationGroup>
+                </Animations>
+            </Frame>
+        </Ui>
+    "#);
+    let ui = parse_xml(&xml).unwrap();
+    if let XmlElement::Frame(frame) = &ui.elements[0] {
+        create_frame_from_xml(&env.loader_env(), frame, "Frame", None, None).unwrap();
+    }
+    env
+}
+
+#[test]
+fn xml_fade_group_drives_alpha_over_duration() {
+    let env = setup_xml_anim(r#"<Alpha fromAlpha="0" toAlpha="1" duration="1"/>"#);
+    env.exec("XmlAnimFrame.Anim:Play()").unwrap();
+
+    env.fire_on_update(0.25).unwrap();
+    let alpha: f64 = env.eval("return XmlAnimFrame:GetAlpha()").unwrap();
+    assert!((alpha - 0.25).abs() < 0.05, "alpha at 0.25s should be ~0.25, got {alpha}");
+
+    env.fire_on_update(1.0).unwrap();
+    let alpha: f64 = env.eval("return XmlAnimFrame:GetAlpha()").unwrap();
+    assert!((alpha - 1.0).abs() < 1e-6, "alpha after the duration should be 1, got {alpha}");
+}
+
+#[test]
+fn xml_typed_animation_targets_parent_relative_key() {
+    let env = setup_xml_anim(
+        r#"<Animation type="Alpha" targetKey="$parent.Glow" fromAlpha="0" toAlpha="1" duration="1"/>"#,
+    );
+    let same_target: bool = env
+        .eval("return XmlAnimFrame.Anim:GetAnimations():GetTarget() == XmlAnimFrame.Glow")
+        .unwrap();
+    assert!(same_target);
+
+    env.exec("XmlAnimFrame.Anim:Play()").unwrap();
+    env.fire_on_update(0.5).unwrap();
+    let (glow, owner): (f64, f64) = env
+        .eval("return XmlAnimFrame.Glow:GetAlpha(), XmlAnimFrame:GetAlpha()")
+        .unwrap();
+    assert!((glow - 0.5).abs() < 0.05, "Glow alpha should be ~0.5, got {glow}");
+    assert_eq!(owner, 1.0);
+}
+
+#[test]
+fn xml_translation_offset_child_moves_frame() {
+    let env = setup_xml_anim(r#"<Translation duration="1"><Offset x="100" y="0"/></Translation>"#);
+    env.exec("XmlAnimFrame.Anim:Play()").unwrap();
+    env.fire_on_update(0.5).unwrap();
+
+    let state = env.state().borrow();
+    let id = state.widgets.get_id_by_name("XmlAnimFrame").unwrap();
+    let offset_x = state.widgets.get(id).unwrap().anim_offset_x;
+    assert!((offset_x - 50.0).abs() < 5.0, "offset should be ~50 halfway, got {offset_x}");
+}
+
+fn xml_anim_alpha(env: &WowLuaEnv) -> f64 {
+    env.eval("return XmlAnimFrame:GetAlpha()").unwrap()
+}
+
+#[test]
+fn xml_repeat_group_keeps_advancing_past_duration() {
+    let env = setup_xml_anim_group(
+        r#"looping="REPEAT""#,
+        r#"<Alpha fromAlpha="0" toAlpha="1" duration="1"/>"#,
+    );
+    assert_eq!(env.eval::<String>("return XmlAnimFrame.Anim:GetLooping()").unwrap(), "REPEAT");
+    env.exec("XmlAnimFrame.Anim:Play()").unwrap();
+
+    let mut samples = Vec::new();
+    for _ in 0..10 {
+        env.fire_on_update(0.25).unwrap();
+        samples.push(xml_anim_alpha(&env));
+    }
+    let expected = [0.25, 0.5, 0.75, 0.0, 0.25, 0.5, 0.75, 0.0, 0.25, 0.5];
+    for (i, (got, want)) in samples.iter().zip(expected).enumerate() {
+        assert!((got - want).abs() < 1e-3, "tick {i}: alpha {got}, expected {want}");
+    }
+    let playing: bool = env.eval("return XmlAnimFrame.Anim:IsPlaying()").unwrap();
+    assert!(playing, "a repeating group should still be playing");
+}
+
+#[test]
+fn xml_bounce_group_reverses_at_the_ends() {
+    let env = setup_xml_anim_group(
+        r#"looping="BOUNCE""#,
+        r#"<Alpha fromAlpha="0" toAlpha="1" duration="1"/>"#,
+    );
+    env.exec("XmlAnimFrame.Anim:Play()").unwrap();
+
+    let mut samples = Vec::new();
+    for _ in 0..6 {
+        env.fire_on_update(0.5).unwrap();
+        samples.push(xml_anim_alpha(&env));
+    }
+    // Up to 1, back down to 0, then up again.
+    let expected = [0.5, 1.0, 0.5, 0.0, 0.5, 1.0];
+    for (i, (got, want)) in samples.iter().zip(expected).enumerate() {
+        assert!((got - want).abs() < 1e-3, "tick {i}: alpha {got}, expected {want}");
+    }
+
+    // A tick spanning several passes lands on the right one.
+    env.fire_on_update(2.25).unwrap();
+    let alpha = xml_anim_alpha(&env);
+    assert!((alpha - 0.75).abs() < 1e-3, "alpha after a long tick should be 0.75, got {alpha}");
+}
+
+#[test]
+fn xml_set_to_final_alpha_snaps_on_stop_and_finish() {
+    let fade_out = r#"<Alpha fromAlpha="1" toAlpha="0" duration="1"/>"#;
+
+    let env = setup_xml_anim_group(r#"setToFinalAlpha="true""#, fade_out);
+    env.exec("XmlAnimFrame.Anim:Play()").unwrap();
+    env.fire_on_update(0.25).unwrap();
+    env.exec("XmlAnimFrame.Anim:Stop()").unwrap();
+    assert_eq!(xml_anim_alpha(&env), 0.0, "Stop should snap to the final alpha");
+
+    let env = setup_xml_anim_group(r#"setToFinalAlpha="true""#, fade_out);
+    env.exec("XmlAnimFrame.Anim:Play()").unwrap();
+    env.exec("XmlAnimFrame.Anim:Finish()").unwrap();
+    assert_eq!(xml_anim_alpha(&env), 0.0, "Finish should snap to the final alpha");
+    let playing: bool = env.eval("return XmlAnimFrame.Anim:IsPlaying()").unwrap();
+    assert!(!playing);
+
+    // Without setToFinalAlpha the pre-animation alpha comes back.
+    let env = setup_xml_anim(fade_out);
+    env.exec("XmlAnimFrame.Anim:Play()").unwrap();
+    env.fire_on_update(0.25).unwrap();
+    env.exec("XmlAnimFrame.Anim:Stop()").unwrap();
+    assert_eq!(xml_anim_alpha(&env), 1.0);
+}
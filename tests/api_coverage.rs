@@ -0,0 +1,55 @@
+//! Tests for `--api-coverage` missing-API recording.
+
+use wow_ui_sim::lua_api::api_coverage::MissingApi;
+use wow_ui_sim::lua_api::WowLuaEnv;
+
+fn env() -> WowLuaEnv {
+    let env = WowLuaEnv::new().expect("Failed to create Lua environment");
+    env.enable_api_coverage().unwrap();
+    env
+}
+
+fn missing(env: &WowLuaEnv, name: &str) -> Option<MissingApi> {
+    env.state().borrow().api_coverage.as_ref().unwrap().get(name).cloned()
+}
+
+#[test]
+fn test_unknown_namespace_call_is_recorded_with_addon() {
+    let env = env();
+    env.exec_named("C_FooBar.Baz()", "@Interface/AddOns/TomTom/TomTom.lua").unwrap();
+
+    assert_eq!(
+        missing(&env, "C_FooBar.Baz"),
+        Some(MissingApi { count: 1, example_addon: "TomTom".to_string() })
+    );
+    assert!(missing(&env, "C_FooBar").is_none(), "the namespace proxy itself is not a miss");
+}
+
+#[test]
+fn test_missing_globals_and_frame_methods_are_counted() {
+    let env = env();
+    env.exec_named(
+        r#"
+        local f = CreateFrame("Button")
+        for _ = 1, 3 do
+            local _ = NotARealGlobal
+            local _ = f.NotARealMethod
+        end
+        local _ = f.someAddonField
+        local _ = C_Timer.NotARealMember
+        "#,
+        "@Interface/AddOns/Details/Core.lua",
+    )
+    .unwrap();
+
+    assert_eq!(missing(&env, "NotARealGlobal").map(|m| m.count), Some(3));
+    assert_eq!(missing(&env, "Button:NotARealMethod").map(|m| m.count), Some(3));
+    assert_eq!(missing(&env, "C_Timer.NotARealMember").map(|m| m.count), Some(1));
+    assert!(missing(&env, "Button:someAddonField").is_none());
+
+    // Real APIs are untouched.
+    let known: bool = env.eval("return C_Timer.After ~= nil and CreateFrame ~= nil").unwrap();
+    assert!(known);
+    let report = env.api_coverage_report().unwrap();
+    assert!(report.contains("       3  Button:NotARealMethod  (Details)"), "{report}");
+}
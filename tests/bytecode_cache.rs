@@ -0,0 +1,42 @@
+//! Tests for the Lua bytecode cache (loader/bytecode_cache.rs) through the
+//! addon loader.
+
+use wow_ui_sim::loader::{bytecode_cache, load_addon, LoadTiming};
+use wow_ui_sim::lua_api::WowLuaEnv;
+
+/// Load the addon in a fresh env; returns its timing and `CacheProbe`.
+fn load(toc: &std::path::Path) -> (LoadTiming, i64) {
+    let env = WowLuaEnv::new().unwrap();
+    let result = load_addon(&env.loader_env(), toc).unwrap();
+    assert!(result.warnings.is_empty(), "{:?}", result.warnings);
+    let probe: i64 = env.eval("return CacheProbe").unwrap();
+    (result.timing, probe)
+}
+
+#[test]
+fn test_edited_file_misses_cache_and_runs_new_code() {
+    if bytecode_cache::is_disabled() {
+        return;
+    }
+    let addons = tempfile::tempdir().unwrap();
+    let dir = addons.path().join("CacheProbe");
+    std::fs::create_dir(&dir).unwrap();
+    let toc = dir.join("CacheProbe.toc");
+    std::fs::write(&toc, "## Interface: 110207\nCacheProbe.lua\n").unwrap();
+    let lua = dir.join("CacheProbe.lua");
+
+    std::fs::write(&lua, "CacheProbe = 1\n").unwrap();
+    let (timing, probe) = load(&toc);
+    assert_eq!((timing.cache_hits, timing.cache_misses, probe), (0, 1, 1));
+
+    let (timing, probe) = load(&toc);
+    assert_eq!((timing.cache_hits, timing.cache_misses, probe), (1, 0, 1));
+
+    // Same length, different content: must not reuse the old bytecode.
+    std::fs::write(&lua, "CacheProbe = 2\n").unwrap();
+    let (timing, probe) = load(&toc);
+    assert_eq!((timing.cache_hits, timing.cache_misses, probe), (0, 1, 2));
+
+    let (timing, probe) = load(&toc);
+    assert_eq!((timing.cache_hits, timing.cache_misses, probe), (1, 0, 2));
+}
@@ -0,0 +1,81 @@
+//! Tests for C_EditMode layouts and the placeholder EditModeManagerFrame.
+
+use wow_ui_sim::lua_api::WowLuaEnv;
+
+fn env() -> WowLuaEnv {
+    WowLuaEnv::new().expect("Failed to create Lua environment")
+}
+
+#[test]
+fn test_set_active_layout_fires_layouts_updated_once() {
+    let env = env();
+    env.exec(
+        r#"
+        LayoutEvents = {}
+        local watcher = CreateFrame("Frame")
+        watcher:RegisterEvent("EDIT_MODE_LAYOUTS_UPDATED")
+        watcher:SetScript("OnEvent", function(_, _, info, reconcile)
+            table.insert(LayoutEvents, info.activeLayout .. ":" .. tostring(reconcile))
+        end)
+        "#,
+    )
+    .unwrap();
+
+    let initial: i32 = env.eval("return C_EditMode.GetLayouts().activeLayout").unwrap();
+    assert_eq!(initial, 1);
+
+    env.exec("C_EditMode.SetActiveLayout(3); C_EditMode.SetActiveLayout(3)").unwrap();
+    let events: String = env.eval("return table.concat(LayoutEvents, ',')").unwrap();
+    assert_eq!(events, "3:false", "only an actual change fires the event");
+    let active: i32 = env.eval("return C_EditMode.GetLayouts().activeLayout").unwrap();
+    assert_eq!(active, 3);
+}
+
+#[test]
+fn test_save_layouts_keeps_only_custom_layouts() {
+    let env = env();
+    env.exec(
+        r#"
+        C_EditMode.SaveLayouts({
+            activeLayout = 4,
+            layouts = {
+                { layoutName = "Modern", layoutType = 0, systems = {} },
+                { layoutName = "Mine", layoutType = 1, systems = { { system = 0, anchorInfo = { point = "TOP" } } } },
+            },
+        })
+        -- GetLayouts hands out copies; editing one must not touch the store.
+        C_EditMode.GetLayouts().layouts[1].layoutName = "Edited"
+        "#,
+    )
+    .unwrap();
+
+    let (active, count, name, point): (i32, i32, String, String) = env
+        .eval(
+            r#"local info = C_EditMode.GetLayouts()
+            local mine = info.layouts[1]
+            return info.activeLayout, #info.layouts, mine.layoutName, mine.systems[1].anchorInfo.point"#,
+        )
+        .unwrap();
+    assert_eq!((active, count), (4, 1));
+    assert_eq!(name, "Mine");
+    assert_eq!(point, "TOP");
+}
+
+#[test]
+fn test_edit_mode_system_registers_without_blizzard_edit_mode() {
+    let env = env();
+    let found: bool = env
+        .eval(
+            r#"
+            local f = CreateFrame("Frame", "EditModeProbeFrame", UIParent)
+            Mixin(f, EditModeSystemMixin)
+            f.system = 42
+            f.systemIndex = 1
+            f:OnSystemLoad()
+            return EditModeManagerFrame:GetRegisteredSystemFrame(42, 1) == f
+                and not EditModeManagerFrame:IsEditModeActive()
+            "#,
+        )
+        .unwrap();
+    assert!(found);
+}
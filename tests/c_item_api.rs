@@ -24,10 +24,11 @@ fn test_c_item_get_item_info_returns_nil() {
 fn test_c_item_get_item_info_returns_data() {
     let env = env();
     // Item 6948 (Hearthstone) exists in the DB
-    let name: String = env
-        .eval("local info = C_Item.GetItemInfo(6948); return info.itemName")
+    let (name, count): (String, i32) = env
+        .eval("return C_Item.GetItemInfo(6948), select('#', C_Item.GetItemInfo(6948))")
         .unwrap();
     assert_eq!(name, "Hearthstone");
+    assert_eq!(count, 17);
 }
 
 // ============================================================================
@@ -49,9 +50,29 @@ fn test_c_item_get_item_info_instant_by_id() {
 fn test_c_item_get_item_info_instant_by_link() {
     let env = env();
     let item_id: i64 = env
-        .eval(r#"return C_Item.GetItemInfoInstant("|cffffffff|Hitem:54321::::::::80:::::|h[Test]|h|r")"#)
+        .eval(r#"return C_Item.GetItemInfoInstant("|cffffffff|Hitem:19019::::::::80:::::|h[Test]|h|r")"#)
+        .unwrap();
+    assert_eq!(item_id, 19019);
+}
+
+#[test]
+fn test_c_item_get_item_info_instant_weapon_class() {
+    let env = env();
+    let (item_type, equip_loc, class_id, subclass_id): (String, String, i32, i32) = env
+        .eval("local _, t, _, loc, _, c, s = C_Item.GetItemInfoInstant(19019); return t, loc, c, s")
         .unwrap();
-    assert_eq!(item_id, 54321);
+    assert_eq!(item_type, "Weapon");
+    assert_eq!(equip_loc, "INVTYPE_WEAPON");
+    assert_eq!((class_id, subclass_id), (2, 14));
+}
+
+#[test]
+fn test_c_item_get_item_info_instant_unknown_item() {
+    let env = env();
+    let count: i32 = env
+        .eval("return select('#', C_Item.GetItemInfoInstant(54321))")
+        .unwrap();
+    assert_eq!(count, 0);
 }
 
 #[test]
@@ -306,6 +327,73 @@ fn test_legacy_get_item_info() {
     assert!(is_nil);
 }
 
+#[test]
+fn test_legacy_get_item_info_weapon() {
+    let env = env();
+    env.exec(
+        "Info = { GetItemInfo(19019) }
+         Count = select('#', GetItemInfo(19019))",
+    )
+    .unwrap();
+    let count: i32 = env.eval("return Count").unwrap();
+    assert_eq!(count, 17);
+    let (name, link, quality, ilvl, req_level): (String, String, i32, i32, i32) =
+        env.eval("return Info[1], Info[2], Info[3], Info[4], Info[5]").unwrap();
+    assert_eq!(name, "Thunderfury, Blessed Blade of the Windseeker");
+    assert!(link.starts_with("|cffff8000|Hitem:19019:"), "link: {link}");
+    assert_eq!((quality, ilvl, req_level), (5, 29, 25));
+    let (item_type, max_stack, equip_loc, sell_price, class_id, bind_type): (
+        String,
+        i32,
+        String,
+        i64,
+        i32,
+        i32,
+    ) = env
+        .eval("return Info[6], Info[8], Info[9], Info[11], Info[12], Info[14]")
+        .unwrap();
+    assert_eq!(item_type, "Weapon");
+    assert_eq!(max_stack, 1);
+    assert_eq!(equip_loc, "INVTYPE_WEAPON");
+    assert_eq!(sell_price, 32455);
+    assert_eq!(class_id, 2);
+    assert_eq!(bind_type, 1);
+}
+
+#[test]
+fn test_legacy_get_item_info_consumable() {
+    let env = env();
+    let (name, quality, max_stack, equip_loc, sell_price, bind_type): (
+        String,
+        i32,
+        i32,
+        String,
+        i64,
+        i32,
+    ) = env
+        .eval(
+            "local name, _, quality, _, _, _, _, stack, loc, _, price, _, _, bind = GetItemInfo(13446)
+             return name, quality, stack, loc, price, bind",
+        )
+        .unwrap();
+    assert_eq!(name, "Major Healing Potion");
+    assert_eq!(quality, 1);
+    assert_eq!(max_stack, 200);
+    assert_eq!(equip_loc, "");
+    assert_eq!(sell_price, 1000);
+    assert_eq!(bind_type, 0);
+}
+
+#[test]
+fn test_legacy_get_item_icon() {
+    let env = env();
+    let (icon, missing): (i64, bool) = env
+        .eval("return GetItemIcon(13446), GetItemIcon(1) == nil")
+        .unwrap();
+    assert_eq!(icon, 134400);
+    assert!(missing);
+}
+
 #[test]
 fn test_legacy_get_item_id() {
     let env = env();
@@ -336,6 +424,64 @@ fn test_legacy_get_container_num_slots() {
     assert_eq!(slots, 16);
 }
 
+#[test]
+fn test_set_container_item_fills_backpack() {
+    let env = env();
+    env.exec(
+        r#"
+        BagUpdates = {}
+        local watcher = CreateFrame("Frame")
+        watcher:RegisterEvent("BAG_UPDATE")
+        watcher:SetScript("OnEvent", function(_, _, bag) table.insert(BagUpdates, bag) end)
+        ClearContainers()
+        BagUpdates = {}
+        SetContainerItem(0, 1, 6948)
+        SetContainerItem(0, 2, 13446, 5)
+        SetContainerItem(0, 16, 19019)
+        "#,
+    )
+    .unwrap();
+
+    let (slots, items, potions, free): (i32, String, i32, i32) = env
+        .eval(
+            r#"
+            local ids = {}
+            for slot = 1, C_Container.GetContainerNumSlots(0) do
+                local info = C_Container.GetContainerItemInfo(0, slot)
+                if info then table.insert(ids, slot .. "=" .. info.itemID) end
+            end
+            return C_Container.GetContainerNumSlots(0), table.concat(ids, ","),
+                C_Container.GetContainerItemInfo(0, 2).stackCount,
+                (C_Container.GetContainerNumFreeSlots(0))
+            "#,
+        )
+        .unwrap();
+    assert_eq!(slots, 16);
+    assert_eq!(items, "1=6948,2=13446,16=19019");
+    assert_eq!(potions, 5);
+    assert_eq!(free, 13);
+    let updates: String = env.eval("return table.concat(BagUpdates, ',')").unwrap();
+    assert_eq!(updates, "0,0,0");
+
+    env.exec("SetContainerItem(0, 2, nil)").unwrap();
+    assert!(env.eval::<bool>("return C_Container.GetContainerItemID(0, 2) == nil").unwrap());
+    assert!(env.exec("SetContainerItem(0, 17, 6948)").is_err(), "backpack has 16 slots");
+}
+
+#[test]
+fn test_get_inventory_item_link_reads_equipped_item() {
+    let env = env();
+    assert!(env.eval::<bool>("return GetInventoryItemLink('player', 16) == nil").unwrap());
+
+    env.exec("SetInventoryItem(16, 19019)").unwrap();
+    let (link, id): (String, i64) = env
+        .eval("return GetInventoryItemLink('player', 16), GetInventoryItemID('player', 16)")
+        .unwrap();
+    assert!(link.contains("|Hitem:19019:"), "{link}");
+    assert_eq!(id, 19019);
+    assert!(env.eval::<bool>("return GetInventoryItemLink('target', 16) == nil").unwrap());
+}
+
 // ============================================================================
 // Inventory slot functions
 // ============================================================================
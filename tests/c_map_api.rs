@@ -31,21 +31,75 @@ fn test_get_map_info_has_name_field() {
     assert!(!name.is_empty());
 }
 
+#[test]
+fn test_get_map_info_unknown_map_is_nil() {
+    let env = env();
+    let is_nil: bool = env.eval("return C_Map.GetMapInfo(999999) == nil").unwrap();
+    assert!(is_nil);
+}
+
+#[test]
+fn test_get_map_info_parent_chain_reaches_cosmic() {
+    let env = env();
+    let chain: String = env.eval(r#"
+        local names = {}
+        local mapID = C_Map.GetBestMapForUnit("player")
+        while mapID and mapID ~= 0 do
+            local info = C_Map.GetMapInfo(mapID)
+            table.insert(names, info.name)
+            mapID = info.parentMapID
+        end
+        return table.concat(names, " > ")
+    "#).unwrap();
+    assert_eq!(chain, "Stormwind City > Eastern Kingdoms > Azeroth > Cosmic");
+}
+
 #[test]
 fn test_get_best_map_for_unit() {
     let env = env();
     let map_id: i32 = env.eval(r#"return C_Map.GetBestMapForUnit("player")"#).unwrap();
-    assert_eq!(map_id, 1);
+    assert_eq!(map_id, 84);
+    let none: bool = env.eval(r#"return C_Map.GetBestMapForUnit("target") == nil"#).unwrap();
+    assert!(none);
 }
 
 #[test]
 fn test_get_player_map_position() {
     let env = env();
-    let is_table: bool = env.eval(r#"
-        local pos = C_Map.GetPlayerMapPosition(1, "player")
-        return type(pos) == "table" or type(pos) == "userdata"
+    let (x, y): (f64, f64) = env.eval(r#"
+        local pos = C_Map.GetPlayerMapPosition(C_Map.GetBestMapForUnit("player"), "player")
+        return pos:GetXY()
     "#).unwrap();
-    assert!(is_table);
+    assert!(x > 0.0 && x < 1.0 && y > 0.0 && y < 1.0);
+    let off_map: bool = env.eval(r#"return C_Map.GetPlayerMapPosition(1, "player") == nil"#).unwrap();
+    assert!(off_map, "player is not on Durotar");
+}
+
+#[test]
+fn test_sim_set_player_location_moves_player() {
+    let env = env();
+    env.exec("SimSetPlayerLocation(1, 0.25, 0.75)").unwrap();
+    let (map_id, x, y): (i32, f64, f64) = env.eval(r#"
+        local mapID = C_Map.GetBestMapForUnit("player")
+        return mapID, C_Map.GetPlayerMapPosition(mapID, "player"):GetXY()
+    "#).unwrap();
+    assert_eq!((map_id, x, y), (1, 0.25, 0.75));
+    assert!(env.exec("SimSetPlayerLocation(999999)").is_err(), "unknown map");
+    // The location lives in this env only.
+    let fresh: i32 = env().eval(r#"return C_Map.GetBestMapForUnit("player")"#).unwrap();
+    assert_eq!(fresh, 84);
+}
+
+#[test]
+fn test_get_map_children_info_lists_zones() {
+    let env = env();
+    let has_stormwind: bool = env.eval(r#"
+        for _, info in ipairs(C_Map.GetMapChildrenInfo(13)) do
+            if info.mapID == 84 then return true end
+        end
+        return false
+    "#).unwrap();
+    assert!(has_stormwind);
 }
 
 #[test]
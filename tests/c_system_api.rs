@@ -261,6 +261,22 @@ fn test_c_texture_get_atlas_info_unknown_nil() {
     assert!(is_nil);
 }
 
+#[test]
+fn test_c_texture_get_atlas_info_fields() {
+    let env = env();
+    let (width, height, left, right, tiles_h, filename): (i32, i32, f64, f64, bool, String) = env
+        .eval(
+            r#"local info = C_Texture.GetAtlasInfo("QuestNormal")
+            return info.width, info.height, info.leftTexCoord, info.rightTexCoord,
+                info.tilesHorizontally, info.filename"#,
+        )
+        .unwrap();
+    assert_eq!((width, height), (32, 32));
+    assert!(left < right && left > 0.8);
+    assert!(!tiles_h);
+    assert_eq!(filename, r"Interface\minimap\objecticonsatlas");
+}
+
 #[test]
 fn test_c_texture_get_atlas_info_is_function() {
     let env = env();
@@ -0,0 +1,157 @@
+//! Tests for C_Timer scheduling on the virtual SimClock.
+
+use wow_ui_sim::lua_api::WowLuaEnv;
+use wow_ui_sim::startup::{fire_one_on_update_tick, process_pending_timers, HEADLESS_TICK_SECS};
+
+fn env() -> WowLuaEnv {
+    WowLuaEnv::new().expect("Failed to create Lua environment")
+}
+
+fn fired(env: &WowLuaEnv) -> String {
+    env.eval("return table.concat(__fired, ',')").unwrap()
+}
+
+#[test]
+fn test_get_time_follows_virtual_clock() {
+    let env = env();
+    let t0: f64 = env.eval("return GetTime()").unwrap();
+    std::thread::sleep(std::time::Duration::from_millis(20));
+    let t1: f64 = env.eval("return GetTime()").unwrap();
+    assert_eq!(t0, t1, "wall-clock time must not leak into GetTime()");
+
+    fire_one_on_update_tick(&env);
+    let t2: f64 = env.eval("return GetTime()").unwrap();
+    assert!((t2 - t0 - HEADLESS_TICK_SECS).abs() < 1e-9);
+}
+
+#[test]
+fn test_timers_fire_in_order_after_ticks() {
+    let env = env();
+    // Scheduled out of order on purpose: firing order follows due time.
+    env.exec(
+        r#"
+        __fired = {}
+        C_Timer.After(0.2, function() table.insert(__fired, "b") end)
+        C_Timer.After(0.1, function() table.insert(__fired, "a") end)
+    "#,
+    )
+    .unwrap();
+
+    process_pending_timers(&env);
+    assert_eq!(fired(&env), "", "nothing is due before the clock advances");
+
+    fire_one_on_update_tick(&env);
+    process_pending_timers(&env);
+    assert_eq!(fired(&env), "a");
+
+    fire_one_on_update_tick(&env);
+    process_pending_timers(&env);
+    assert_eq!(fired(&env), "a,b");
+}
+
+#[test]
+fn test_due_timers_in_one_pass_fire_by_due_time() {
+    let env = env();
+    env.exec(
+        r#"
+        __fired = {}
+        C_Timer.After(0.3, function() table.insert(__fired, "c") end)
+        C_Timer.After(0.2, function() table.insert(__fired, "b") end)
+        C_Timer.After(0.1, function() table.insert(__fired, "a") end)
+    "#,
+    )
+    .unwrap();
+
+    env.fire_on_update(1.0).unwrap();
+    process_pending_timers(&env);
+    assert_eq!(fired(&env), "a,b,c");
+}
+
+#[test]
+fn test_ticker_fires_once_per_interval() {
+    let env = env();
+    env.exec(
+        r#"
+        __fired = {}
+        C_Timer.NewTicker(0.1, function() table.insert(__fired, "t") end, 3)
+    "#,
+    )
+    .unwrap();
+
+    for _ in 0..5 {
+        fire_one_on_update_tick(&env);
+        process_pending_timers(&env);
+    }
+    assert_eq!(fired(&env), "t,t,t");
+}
+
+#[test]
+fn test_cancelled_ticker_stops_firing() {
+    let env = env();
+    env.exec(
+        r#"
+        __fired = {}
+        Ticker = C_Timer.NewTicker(0.1, function() table.insert(__fired, "t") end)
+    "#,
+    )
+    .unwrap();
+
+    fire_one_on_update_tick(&env);
+    process_pending_timers(&env);
+    assert_eq!(fired(&env), "t");
+
+    env.exec("Ticker:Cancel()").unwrap();
+    for _ in 0..5 {
+        fire_one_on_update_tick(&env);
+        process_pending_timers(&env);
+    }
+    assert_eq!(fired(&env), "t");
+    let cancelled: bool = env.eval("return Ticker:IsCancelled()").unwrap();
+    assert!(cancelled);
+    assert!(!env.has_pending_timers());
+}
+
+#[test]
+fn test_ticker_cancelling_itself_in_callback_stops_firing() {
+    let env = env();
+    env.exec(
+        r#"
+        __fired = {}
+        C_Timer.NewTicker(0.1, function(self)
+            table.insert(__fired, "t")
+            if #__fired == 2 then self:Cancel() end
+        end)
+    "#,
+    )
+    .unwrap();
+
+    for _ in 0..6 {
+        fire_one_on_update_tick(&env);
+        process_pending_timers(&env);
+    }
+    assert_eq!(fired(&env), "t,t");
+    assert!(!env.has_pending_timers());
+}
+
+#[test]
+fn test_self_rescheduling_zero_delay_timer_fires_once_per_pass() {
+    let env = env();
+    env.exec(
+        r#"
+        __fired = {}
+        local function again()
+            table.insert(__fired, "z")
+            C_Timer.After(0, again)
+        end
+        C_Timer.After(0, again)
+    "#,
+    )
+    .unwrap();
+
+    assert_eq!(env.process_timers().unwrap(), 1);
+    assert_eq!(fired(&env), "z");
+    assert!(env.has_pending_timers(), "the rescheduled timer waits for the next pass");
+
+    assert_eq!(env.process_timers().unwrap(), 1);
+    assert_eq!(fired(&env), "z,z");
+}
@@ -0,0 +1,96 @@
+//! Tests for Cooldown frame state and radial swipe rendering.
+
+use wow_ui_sim::iced_app::build_quad_batch_at_time;
+use wow_ui_sim::lua_api::WowLuaEnv;
+use wow_ui_sim::render::shader::FLAG_COOLDOWN_SWIPE;
+
+/// Build a quad batch for `root` at `elapsed` seconds and return the
+/// swipe progress values (encoded in `tex_coords.x`) of every swipe quad.
+fn swipe_progress(env: &WowLuaEnv, root: &str, elapsed: f64) -> Vec<f32> {
+    let buckets = {
+        let mut state = env.state().borrow_mut();
+        state.ensure_layout_rects();
+        let _ = state.get_strata_buckets();
+        state.strata_buckets.as_ref().unwrap().clone()
+    };
+    let state = env.state().borrow();
+    let batch = build_quad_batch_at_time(
+        &state.widgets,
+        (1024.0, 768.0),
+        Some(root),
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        &buckets,
+        elapsed,
+    );
+    batch
+        .vertices
+        .chunks(4)
+        .filter(|quad| quad[0].flags & FLAG_COOLDOWN_SWIPE != 0)
+        .map(|quad| quad[0].tex_coords[0])
+        .collect()
+}
+
+fn create_cooldown(env: &WowLuaEnv, name: &str) {
+    env.exec(&format!(
+        r#"
+        local parent = CreateFrame("Frame", "{name}Parent", UIParent)
+        parent:SetSize(40, 40)
+        parent:SetPoint("CENTER")
+        local cd = CreateFrame("Cooldown", "{name}", parent)
+        cd:SetAllPoints(parent)
+        "#
+    ))
+    .unwrap();
+}
+
+#[test]
+fn test_set_cooldown_round_trips_times() {
+    let env = WowLuaEnv::new().unwrap();
+    create_cooldown(&env, "TestCdTimes");
+    env.exec("TestCdTimes:SetCooldown(100, 30)").unwrap();
+    let (start, duration): (f64, f64) = env.eval("return TestCdTimes:GetCooldownTimes()").unwrap();
+    assert_eq!(start, 100.0);
+    assert_eq!(duration, 30.0);
+}
+
+#[test]
+fn test_cooldown_swipe_progress_follows_clock() {
+    let env = WowLuaEnv::new().unwrap();
+    create_cooldown(&env, "TestCdSwipe");
+    env.exec("TestCdSwipe:SetCooldown(10, 20)").unwrap();
+
+    let swipes = swipe_progress(&env, "TestCdSwipeParent", 15.0);
+    assert_eq!(swipes.len(), 1, "active cooldown should emit one swipe quad");
+    assert!((swipes[0] - 0.25).abs() < 1e-4, "expected 25% progress, got {}", swipes[0]);
+
+    let finished = swipe_progress(&env, "TestCdSwipeParent", 31.0);
+    assert!(finished.is_empty(), "finished cooldown should not draw a swipe");
+}
+
+#[test]
+fn test_cooldown_reverse_inverts_swipe() {
+    let env = WowLuaEnv::new().unwrap();
+    create_cooldown(&env, "TestCdReverse");
+    env.exec("TestCdReverse:SetReverse(true); TestCdReverse:SetCooldown(0, 10)").unwrap();
+
+    let swipes = swipe_progress(&env, "TestCdReverseParent", 2.0);
+    assert_eq!(swipes.len(), 1);
+    assert!((swipes[0] - 0.8).abs() < 1e-4, "expected 80% reversed progress, got {}", swipes[0]);
+}
+
+#[test]
+fn test_set_cooldown_zero_clears_swipe() {
+    let env = WowLuaEnv::new().unwrap();
+    create_cooldown(&env, "TestCdClear");
+    env.exec("TestCdClear:SetCooldown(10, 20)").unwrap();
+    env.exec("TestCdClear:SetCooldown(0, 0)").unwrap();
+
+    let (start, duration): (f64, f64) = env.eval("return TestCdClear:GetCooldownTimes()").unwrap();
+    assert_eq!((start, duration), (0.0, 0.0));
+    assert!(swipe_progress(&env, "TestCdClearParent", 15.0).is_empty());
+}
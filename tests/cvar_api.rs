@@ -0,0 +1,146 @@
+//! Tests for the CVar API (cvar_api.rs): registry defaults, overrides,
+//! unknown CVars, C_CVar/global parity and CVAR_UPDATE.
+
+use wow_ui_sim::lua_api::WowLuaEnv;
+
+fn env() -> WowLuaEnv {
+    WowLuaEnv::new().expect("create env")
+}
+
+/// `GetCVar(name)` in a brand-new env.
+fn env_value(name: &str) -> Option<String> {
+    env().eval(&format!("return GetCVar({name:?})")).unwrap()
+}
+
+#[test]
+fn test_known_cvar_has_registered_default() {
+    let env = env();
+    let default: String = env.eval(r#"return GetCVarDefault("cameraSmoothStyle")"#).unwrap();
+    assert_eq!(default, "4");
+    let c_default: String = env.eval(r#"return C_CVar.GetCVarDefault("nameplateMaxDistance")"#).unwrap();
+    assert_eq!(c_default, "60.000000");
+    let is_bool: bool = env
+        .eval(r#"return type(GetCVarBool("ActionButtonUseKeyDown")) == "boolean""#)
+        .unwrap();
+    assert!(is_bool);
+}
+
+#[test]
+fn test_overridden_cvar_keeps_default_and_fires_update() {
+    let env = env();
+    env.exec(
+        r#"
+        CVarLog = {}
+        local f = CreateFrame("Frame")
+        f:RegisterEvent("CVAR_UPDATE")
+        f:SetScript("OnEvent", function(_, event, name, value)
+            table.insert(CVarLog, name .. "=" .. value)
+        end)
+        RegisterCVar("wowSimTestFlag", "0")
+        SetCVar("wowSimTestFlag", true)
+    "#,
+    )
+    .unwrap();
+
+    let value: String = env.eval(r#"return GetCVar("wowSimTestFlag")"#).unwrap();
+    assert_eq!(value, "1");
+    let enabled: bool = env.eval(r#"return C_CVar.GetCVarBool("wowSimTestFlag")"#).unwrap();
+    assert!(enabled);
+    let default: String = env.eval(r#"return GetCVarDefault("wowSimTestFlag")"#).unwrap();
+    assert_eq!(default, "0");
+    let log: String = env.eval("return table.concat(CVarLog, ',')").unwrap();
+    assert_eq!(log, "wowSimTestFlag=1");
+    // The override stays in this env's in-memory storage: it never reaches
+    // the user's cvars.json, so the next env doesn't load it back.
+    assert_eq!(env_value("wowSimTestFlag"), None);
+}
+
+#[test]
+fn test_c_cvar_and_globals_share_one_store() {
+    let env = env();
+    env.exec(
+        r#"
+        C_CVar.RegisterCVar("wowSimParityVar", "7")
+        SetCVar("wowSimParityVar", "legacy")
+    "#,
+    )
+    .unwrap();
+    let (via_c, default): (String, String) = env
+        .eval(r#"return C_CVar.GetCVar("wowSimParityVar"), GetCVarDefault("wowSimParityVar")"#)
+        .unwrap();
+    assert_eq!((via_c.as_str(), default.as_str()), ("legacy", "7"));
+
+    env.exec(r#"C_CVar.SetCVar("wowSimParityVar", 0)"#).unwrap();
+    let (via_global, enabled): (String, bool) = env
+        .eval(r#"return GetCVar("wowSimParityVar"), GetCVarBool("wowSimParityVar")"#)
+        .unwrap();
+    assert_eq!(via_global, "0");
+    assert!(!enabled);
+    // Both writes stayed in this env; a new env doesn't load them from disk.
+    assert_eq!(env_value("wowSimParityVar"), None);
+}
+
+#[test]
+fn test_unknown_cvar_returns_nil() {
+    let env = env();
+    let all_nil: bool = env
+        .eval(
+            r#"return GetCVar("noSuchCVarAnywhere") == nil
+                and GetCVarDefault("noSuchCVarAnywhere") == nil
+                and GetCVarBool("noSuchCVarAnywhere") == nil
+                and C_CVar.GetCVar("noSuchCVarAnywhere") == nil"#,
+        )
+        .unwrap();
+    assert!(all_nil);
+}
+
+#[test]
+fn test_ui_scale_cvar_rescales_uiparent() {
+    let env = env();
+    env.set_screen_size(1024.0, 768.0);
+    env.exec(
+        r#"
+        ScaleEvents = 0
+        local f = CreateFrame("Frame")
+        f:RegisterEvent("UI_SCALE_CHANGED")
+        f:SetScript("OnEvent", function() ScaleEvents = ScaleEvents + 1 end)
+        local child = CreateFrame("Frame", "ScaledChild", UIParent)
+        child:SetSize(100, 50)
+        child:SetPoint("TOPLEFT", 100, -100)
+        SetCVar("useUiScale", "1")
+        SetCVar("uiScale", "0.8")
+    "#,
+    )
+    .unwrap();
+
+    let (scale, width, height): (f64, f64, f64) = env
+        .eval("return UIParent:GetEffectiveScale(), UIParent:GetWidth(), UIParent:GetHeight()")
+        .unwrap();
+    let (screen_w, screen_h, phys_w, phys_h): (f64, f64, f64, f64) = env
+        .eval("local pw, ph = GetPhysicalScreenSize(); return GetScreenWidth(), GetScreenHeight(), pw, ph")
+        .unwrap();
+    let child_scale: f64 = env.eval("return ScaledChild:GetEffectiveScale()").unwrap();
+    // PixelUtil: one physical pixel expressed in UIParent units.
+    let pixel: f64 = env
+        .eval(
+            r#"local _, ph = GetPhysicalScreenSize()
+               return 1 * (768.0 / ph) / UIParent:GetEffectiveScale()"#,
+        )
+        .unwrap();
+    let events: i32 = env.eval("return ScaleEvents").unwrap();
+    let rect = {
+        let mut state = env.state().borrow_mut();
+        state.ensure_layout_rects();
+        let id = state.widgets.get_id_by_name("ScaledChild").unwrap();
+        state.widgets.get(id).and_then(|f| f.layout_rect).unwrap()
+    };
+
+
+    assert!((scale - 0.8).abs() < 1e-4, "UIParent scale {scale}");
+    assert!((child_scale - 0.8).abs() < 1e-4, "child scale {child_scale}");
+    assert!((width - 1280.0).abs() < 0.01 && (height - 960.0).abs() < 0.01, "{width}x{height}");
+    assert_eq!((screen_w, screen_h, phys_w, phys_h), (1024.0, 768.0, 1024.0, 768.0));
+    assert!((pixel - 1.25).abs() < 1e-4, "pixel size {pixel}");
+    assert!((rect.x - 80.0).abs() < 0.01 && (rect.width - 80.0).abs() < 0.01, "{rect:?}");
+    assert_eq!(events, 1);
+}
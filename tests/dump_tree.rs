@@ -1,4 +1,4 @@
-use wow_ui_sim::dump::{build_tree, build_warning_dump, strip_wow_escapes};
+use wow_ui_sim::dump::{build_query_results, build_tree, build_warning_dump, frame_tree_json, query_frames, strip_wow_escapes, FrameQuery};
 use wow_ui_sim::widget::{Anchor, AnchorPoint, Frame, WidgetRegistry, WidgetType};
 
 fn make_frame(id: u64, parent: Option<u64>, w: f32, h: f32) -> Frame {
@@ -109,6 +109,21 @@ fn test_build_tree_shows_texture_path() {
     assert!(lines.iter().any(|l| l.contains("[texture] Interface/Icons/foo")));
 }
 
+#[test]
+fn test_build_tree_truncates_multibyte_text_preview_on_char_boundary() {
+    let mut reg = build_basic_registry();
+    let mut label = make_frame(30, Some(10), 100.0, 12.0);
+    label.widget_type = WidgetType::FontString;
+    label.visible = true;
+    label.text = Some("é".repeat(25));
+    reg.register(label);
+    reg.add_child(10, 30);
+
+    let lines = build_tree(&reg, None, None, false, 1024.0, 768.0);
+    let preview = format!("\"{}...\"", "é".repeat(17));
+    assert!(lines.iter().any(|l| l.contains(&preview)), "{lines:#?}");
+}
+
 #[test]
 fn test_build_tree_shows_anchor_lines() {
     let reg = build_basic_registry();
@@ -123,3 +138,124 @@ fn test_build_warning_dump_includes_header() {
     assert!(lines[0].contains("Frame Dump"));
     assert!(lines[1].contains("1024x768"));
 }
+
+// ── frame_tree_json ─────────────────────────────────────────
+
+#[test]
+fn test_json_tree_has_screen_and_nested_frames() {
+    let reg = build_basic_registry();
+    let json = frame_tree_json(&reg, None, None, false, 1024.0, 768.0);
+    assert_eq!(json["screen"]["width"], 1024.0);
+    assert_eq!(json["screen"]["height"], 768.0);
+
+    let uiparent = &json["frames"][0];
+    assert_eq!(uiparent["name"], "UIParent");
+    let button = uiparent["children"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|c| c["name"] == "MyButton")
+        .expect("MyButton should be a child of UIParent");
+    assert_eq!(button["type"], "Frame");
+    assert_eq!(button["rect"]["x"], 412.0);
+    assert_eq!(button["rect"]["y"], 366.0);
+    assert_eq!(button["rect"]["width"], 200.0);
+    assert_eq!(button["anchors"][0]["point"], "CENTER");
+}
+
+#[test]
+fn test_json_tree_anonymous_frames_have_null_name() {
+    let reg = build_basic_registry();
+    let json = frame_tree_json(&reg, Some("MyButton"), None, false, 1024.0, 768.0);
+    let frames = json["frames"].as_array().unwrap();
+    assert_eq!(frames.len(), 1);
+    let icon = &frames[0]["children"][0];
+    assert!(icon["name"].is_null(), "anonymous texture should have a null name");
+    assert_eq!(icon["parentKey"], "Icon");
+    assert_eq!(icon["type"], "Texture");
+}
+
+#[test]
+fn test_json_tree_visible_only_drops_hidden() {
+    let reg = build_basic_registry();
+    let json = frame_tree_json(&reg, Some("HiddenFrame"), None, true, 1024.0, 768.0);
+    assert!(json["frames"].as_array().unwrap().is_empty());
+}
+
+// ── frame queries ───────────────────────────────────────────
+
+/// UIParent > ObjectiveTrackerFrame with buttons of varying width and
+/// visibility, plus a wide button outside the tracker.
+fn build_query_registry() -> WidgetRegistry {
+    let mut reg = WidgetRegistry::new();
+    let mut add = |id: u64, parent: Option<u64>, name: &str, ty: WidgetType, w: f32, visible: bool, children: Vec<u64>| {
+        let mut f = make_frame(id, parent, w, 30.0);
+        f.name = Some(name.to_string());
+        f.widget_type = ty;
+        f.visible = visible;
+        f.children = children;
+        reg.register(f);
+    };
+    add(1, None, "UIParent", WidgetType::Frame, 1024.0, true, vec![10, 30]);
+    add(10, Some(1), "ObjectiveTrackerFrame", WidgetType::Frame, 300.0, true, vec![20, 21, 22, 23, 24]);
+    add(20, Some(10), "TrackerBlock1", WidgetType::Button, 150.0, true, vec![]);
+    add(21, Some(10), "TrackerNarrow", WidgetType::Button, 80.0, true, vec![]);
+    add(22, Some(10), "TrackerHidden", WidgetType::Button, 150.0, false, vec![]);
+    add(23, Some(10), "TrackerWideFrame", WidgetType::Frame, 150.0, true, vec![]);
+    add(24, Some(10), "TrackerCollapsed", WidgetType::Frame, 150.0, false, vec![25]);
+    add(25, Some(24), "TrackerInsideCollapsed", WidgetType::Button, 200.0, true, vec![]);
+    add(30, Some(1), "OutsideButton", WidgetType::Button, 150.0, true, vec![]);
+    reg.get_mut(21).unwrap().anchors = vec![anchor(AnchorPoint::TopLeft, Some(20), AnchorPoint::BottomLeft)];
+    reg
+}
+
+fn query_names(reg: &WidgetRegistry, query: &str) -> Vec<String> {
+    let query = FrameQuery::parse(query).unwrap();
+    query_frames(reg, &query, 1024.0, 768.0).into_iter()
+        .map(|id| reg.get(id).unwrap().name.clone().unwrap())
+        .collect()
+}
+
+#[test]
+fn test_query_requires_all_predicates() {
+    let reg = build_query_registry();
+    assert_eq!(
+        query_names(&reg, "type=Button visible=true under=ObjectiveTrackerFrame minwidth=100"),
+        vec!["TrackerBlock1"],
+    );
+    // Dropping a predicate widens the result; hidden ancestors hide descendants.
+    assert_eq!(
+        query_names(&reg, "type=button under=ObjectiveTrackerFrame visible=false"),
+        vec!["TrackerHidden", "TrackerInsideCollapsed"],
+    );
+    assert_eq!(
+        query_names(&reg, "type=Button minwidth=100 maxwidth=160"),
+        vec!["TrackerBlock1", "TrackerHidden", "OutsideButton"],
+    );
+}
+
+#[test]
+fn test_query_by_anchor_target_and_name() {
+    let reg = build_query_registry();
+    assert_eq!(query_names(&reg, "anchor=TrackerBlock1"), vec!["TrackerNarrow"]);
+    assert_eq!(query_names(&reg, "name=^tracker.*frame$"), vec!["TrackerWideFrame"]);
+    assert_eq!(query_names(&reg, "").len(), 9, "an empty query matches every frame");
+}
+
+#[test]
+fn test_query_results_include_rects() {
+    let reg = build_query_registry();
+    let query = FrameQuery::parse("type=Button under=ObjectiveTrackerFrame maxwidth=100").unwrap();
+    let lines = build_query_results(&reg, &query, 1024.0, 768.0);
+    assert_eq!(lines.len(), 1);
+    assert!(lines[0].starts_with("TrackerNarrow [Button]"), "{}", lines[0]);
+    assert!(lines[0].contains("80x30"), "{}", lines[0]);
+}
+
+#[test]
+fn test_query_parse_errors() {
+    assert!(FrameQuery::parse("type").is_err());
+    assert!(FrameQuery::parse("minwidth=wide").is_err());
+    assert!(FrameQuery::parse("visible=maybe").is_err());
+    assert!(FrameQuery::parse("color=red").is_err());
+}
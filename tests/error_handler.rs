@@ -287,3 +287,33 @@ fn test_error_handler_can_be_replaced() {
     assert_eq!(first, 0, "first handler should not be called after replacement");
     assert_eq!(second, 1, "second handler should receive the error");
 }
+
+// ── Tracebacks in load warnings ──────────────────────────────────────
+
+#[test]
+fn test_erroring_onload_warning_has_source_line_and_traceback() {
+    let addons = tempfile::tempdir().unwrap();
+    let dir = addons.path().join("ErrProbe");
+    std::fs::create_dir(&dir).unwrap();
+    let toc = dir.join("ErrProbe.toc");
+    std::fs::write(&toc, "## Interface: 110207\nErrProbe.lua\nErrProbe.xml\n").unwrap();
+    std::fs::write(dir.join("ErrProbe.lua"), r#"ErrProbeMixin = {}
+function ErrProbeMixin:Explode() local t = nil; return t.field end
+function ErrProbeMixin:OnLoad() self:Explode() end
+"#).unwrap();
+    std::fs::write(dir.join("ErrProbe.xml"), r#"<Ui>
+    <Frame name="ErrProbeFrame" mixin="ErrProbeMixin">
+        <Scripts><OnLoad method="OnLoad"/></Scripts>
+    </Frame>
+</Ui>"#).unwrap();
+
+    let env = env();
+    let result = wow_ui_sim::loader::load_addon(&env.loader_env(), &toc).unwrap();
+    let warning = result.warnings.iter()
+        .find(|w| w.contains("attempt to index"))
+        .unwrap_or_else(|| panic!("no OnLoad error in warnings: {:?}", result.warnings));
+    assert!(warning.contains("ErrProbeFrame"), "names the frame: {warning}");
+    assert!(warning.contains("ErrProbe.lua:2"), "has the source line: {warning}");
+    assert!(warning.contains("stack traceback:"), "has a traceback: {warning}");
+    assert!(warning.contains("Explode"), "traceback names the function: {warning}");
+}
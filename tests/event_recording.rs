@@ -0,0 +1,98 @@
+//! Tests for event recording and replay (event/recorder.rs).
+
+use wow_ui_sim::event::recorder::load_event_log;
+use wow_ui_sim::lua_api::WowLuaEnv;
+
+/// Env with a listener counting ADDON_LOADED/PLAYER_LOGIN and logging args.
+fn env_with_listener() -> WowLuaEnv {
+    let env = WowLuaEnv::new().expect("create env");
+    env.exec(
+        r#"
+        Counts = { ADDON_LOADED = 0, PLAYER_LOGIN = 0 }
+        Seen = {}
+        local f = CreateFrame("Frame")
+        f:RegisterEvent("ADDON_LOADED")
+        f:RegisterEvent("PLAYER_LOGIN")
+        f:SetScript("OnEvent", function(_, event, arg1)
+            Counts[event] = Counts[event] + 1
+            table.insert(Seen, event .. ":" .. tostring(arg1))
+        end)
+    "#,
+    )
+    .unwrap();
+    env
+}
+
+fn summary(env: &WowLuaEnv) -> String {
+    env.eval(
+        "return Counts.ADDON_LOADED .. '/' .. Counts.PLAYER_LOGIN .. ' ' .. table.concat(Seen, ',')",
+    )
+    .unwrap()
+}
+
+fn fire_startup(env: &WowLuaEnv) {
+    for addon in ["MyAddon", "OtherAddon"] {
+        let name = mlua::Value::String(env.lua().create_string(addon).unwrap());
+        env.fire_event_with_args("ADDON_LOADED", &[name]).unwrap();
+    }
+    env.fire_event("PLAYER_LOGIN").unwrap();
+}
+
+#[test]
+fn test_replay_reproduces_listener_invocations() {
+    let recorded_env = env_with_listener();
+    recorded_env.start_event_recording(None).unwrap();
+    fire_startup(&recorded_env);
+    let events = recorded_env
+        .state()
+        .borrow()
+        .event_recorder
+        .as_ref()
+        .unwrap()
+        .events()
+        .to_vec();
+    assert_eq!(events.len(), 3);
+
+    let replayed_env = env_with_listener();
+    replayed_env.replay_events(&events).unwrap();
+    assert_eq!(summary(&replayed_env), summary(&recorded_env));
+    assert_eq!(
+        summary(&replayed_env),
+        "2/1 ADDON_LOADED:MyAddon,ADDON_LOADED:OtherAddon,PLAYER_LOGIN:nil"
+    );
+}
+
+#[test]
+fn test_get_event_log_lists_lua_fired_events() {
+    let env = env_with_listener();
+    let empty: i32 = env.eval("return #GetEventLog()").unwrap();
+    assert_eq!(empty, 0);
+
+    env.start_event_recording(None).unwrap();
+    env.exec(r#"FireEvent("ADDON_LOADED", "LuaAddon", nil, 3)"#).unwrap();
+    let (count, event, arg1, n, arg3): (i32, String, String, i32, i32) = env
+        .eval(
+            r#"local log = GetEventLog()
+               local e = log[1]
+               return #log, e.event, e.args[1], e.n, e.args[3]"#,
+        )
+        .unwrap();
+    assert_eq!((count, event.as_str(), arg1.as_str(), n, arg3), (1, "ADDON_LOADED", "LuaAddon", 3, 3));
+}
+
+#[test]
+fn test_recording_to_file_round_trips() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("events.jsonl");
+    let recorded_env = env_with_listener();
+    recorded_env.start_event_recording(Some(path.as_path())).unwrap();
+    fire_startup(&recorded_env);
+
+    let events = load_event_log(&path).unwrap();
+    let names: Vec<&str> = events.iter().map(|e| e.event.as_str()).collect();
+    assert_eq!(names, ["ADDON_LOADED", "ADDON_LOADED", "PLAYER_LOGIN"]);
+
+    let replayed_env = env_with_listener();
+    replayed_env.replay_events(&events).unwrap();
+    assert_eq!(summary(&replayed_env), summary(&recorded_env));
+}
@@ -0,0 +1,87 @@
+//! Tests for RegisterEvent/UnregisterEvent/UnregisterAllEvents bookkeeping
+//! (methods_event.rs) and the listener lists event dispatch uses.
+
+use wow_ui_sim::lua_api::WowLuaEnv;
+
+/// Env with a frame `EventFrame` that counts OnEvent calls in `EventCalls`.
+fn env() -> WowLuaEnv {
+    let env = WowLuaEnv::new().expect("create env");
+    env.exec(
+        r#"
+        EventCalls = 0
+        local f = CreateFrame("Frame", "EventFrame")
+        f:SetScript("OnEvent", function() EventCalls = EventCalls + 1 end)
+    "#,
+    )
+    .unwrap();
+    env
+}
+
+/// Fire `event` and return how many OnEvent calls it caused.
+fn fire_count(env: &WowLuaEnv, event: &str) -> i32 {
+    env.exec("EventCalls = 0").unwrap();
+    env.fire_event(event).unwrap();
+    env.eval("return EventCalls").unwrap()
+}
+
+fn listens(env: &WowLuaEnv, event: &str) -> bool {
+    let state = env.state().borrow();
+    let id = state.widgets.get_id_by_name("EventFrame").unwrap();
+    state.widgets.get_event_listeners(event).contains(&id)
+}
+
+#[test]
+fn test_double_register_dispatches_once() {
+    let env = env();
+    env.exec(r#"EventFrame:RegisterEvent("PLAYER_LOGIN"); EventFrame:RegisterEvent("PLAYER_LOGIN")"#)
+        .unwrap();
+    assert_eq!(fire_count(&env, "PLAYER_LOGIN"), 1);
+}
+
+#[test]
+fn test_unregister_stops_dispatch_and_listener_entry() {
+    let env = env();
+    env.exec(r#"EventFrame:RegisterEvent("PLAYER_LOGIN")"#).unwrap();
+    assert!(listens(&env, "PLAYER_LOGIN"));
+    let registered: bool = env.eval(r#"return EventFrame:IsEventRegistered("PLAYER_LOGIN")"#).unwrap();
+    assert!(registered);
+
+    env.exec(r#"EventFrame:UnregisterEvent("PLAYER_LOGIN")"#).unwrap();
+    assert_eq!(fire_count(&env, "PLAYER_LOGIN"), 0);
+    assert!(!listens(&env, "PLAYER_LOGIN"));
+    let registered: bool = env.eval(r#"return EventFrame:IsEventRegistered("PLAYER_LOGIN")"#).unwrap();
+    assert!(!registered);
+}
+
+#[test]
+fn test_is_event_registered_returns_unit_filter() {
+    let env = env();
+    env.exec(r#"EventFrame:RegisterUnitEvent("UNIT_HEALTH", "player", "target")"#).unwrap();
+    let result: String = env
+        .eval(
+            r#"local ok, u1, u2 = EventFrame:IsEventRegistered("UNIT_HEALTH")
+               return tostring(ok) .. "," .. u1 .. "," .. u2"#,
+        )
+        .unwrap();
+    assert_eq!(result, "true,player,target");
+}
+
+#[test]
+fn test_unregister_all_events_clears_everything() {
+    let env = env();
+    env.exec(
+        r#"
+        EventFrame:RegisterEvent("PLAYER_LOGIN")
+        EventFrame:RegisterUnitEvent("UNIT_HEALTH", "player")
+        EventFrame:RegisterAllEvents()
+        EventFrame:UnregisterAllEvents()
+    "#,
+    )
+    .unwrap();
+    for event in ["PLAYER_LOGIN", "UNIT_HEALTH", "BAG_UPDATE"] {
+        assert_eq!(fire_count(&env, event), 0, "{event}");
+        assert!(!listens(&env, event), "{event}");
+    }
+    let registered: bool = env.eval(r#"return EventFrame:IsEventRegistered("PLAYER_LOGIN")"#).unwrap();
+    assert!(!registered);
+}
@@ -0,0 +1,49 @@
+//! Tests for texture extraction (extract_textures.rs).
+
+use std::path::Path;
+use wow_ui_sim::extract_textures::{
+    build_file_index, convert_texture_references, find_texture_references,
+};
+
+/// Addon referencing 12 present and 5 missing textures, plus an interface
+/// directory holding the present ones as small PNGs.
+fn write_fixture(root: &Path) {
+    let addons = root.join("AddOns/Fixture");
+    std::fs::create_dir_all(&addons).unwrap();
+    let interface = root.join("Interface/Buttons");
+    std::fs::create_dir_all(&interface).unwrap();
+
+    let mut lua = String::new();
+    for i in 0..12 {
+        lua.push_str(&format!("tex:SetTexture(\"Interface\\\\Buttons\\\\Fixture-{i}\")\n"));
+        image::RgbaImage::from_pixel(4, 4, image::Rgba([i * 20, 0, 0, 255]))
+            .save(interface.join(format!("Fixture-{i}.png")))
+            .unwrap();
+    }
+    for i in 0..5 {
+        lua.push_str(&format!("tex:SetTexture(\"Interface\\\\Buttons\\\\Absent-{i}\")\n"));
+    }
+    std::fs::write(addons.join("Fixture.lua"), lua).unwrap();
+}
+
+#[test]
+fn test_parallel_conversion_matches_serial_totals() {
+    let dir = tempfile::tempdir().unwrap();
+    write_fixture(dir.path());
+
+    let mut textures: Vec<String> =
+        find_texture_references(&dir.path().join("AddOns")).into_iter().collect();
+    textures.sort();
+    let index = build_file_index(&dir.path().join("Interface"));
+
+    let serial_out = dir.path().join("serial");
+    let parallel_out = dir.path().join("parallel");
+    let serial = convert_texture_references(&textures, &index, &serial_out, 1);
+    let parallel = convert_texture_references(&textures, &index, &parallel_out, 4);
+
+    assert_eq!(serial, (12, 5));
+    assert_eq!(parallel, serial);
+    for i in 0..12 {
+        assert!(parallel_out.join(format!("buttons/fixture-{i}.webp")).exists());
+    }
+}
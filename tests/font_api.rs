@@ -305,3 +305,61 @@ fn test_standard_font_gold_color() {
     assert_eq!(r, 1.0);
     assert!((g - 0.82).abs() < 0.01);
 }
+
+// ============================================================================
+// FontString word wrap
+// ============================================================================
+
+#[test]
+fn test_wrapped_fontstring_reports_multiline_height() {
+    use std::cell::RefCell;
+    use std::path::Path;
+    use std::rc::Rc;
+    use wow_ui_sim::render::font::WowFontSystem;
+
+    let env = env();
+    env.set_font_system(Rc::new(RefCell::new(WowFontSystem::new(Path::new("./fonts")))));
+    let (lines, height, string_height, line_height, width): (i32, f64, f64, f64, f64) = env
+        .eval(
+            r#"
+            local f = CreateFrame("Frame", nil, UIParent)
+            local fs = f:CreateFontString(nil, "OVERLAY")
+            fs:SetFont("Fonts\\FRIZQT__.TTF", 12)
+            fs:SetWidth(100)
+            fs:SetWordWrap(true)
+            fs:SetText("This is a fairly long sentence that should wrap inside a narrow font string")
+            return fs:GetNumLines(), fs:GetHeight(), fs:GetStringHeight(), fs:GetLineHeight(), fs:GetWidth()
+        "#,
+        )
+        .unwrap();
+    assert!(lines > 1, "expected wrapped text, got {lines} line(s)");
+    assert_eq!(height, lines as f64 * line_height);
+    assert_eq!(string_height, height);
+    assert_eq!(width, 100.0, "wrapped FontString keeps its fixed width");
+}
+
+#[test]
+fn test_wrapped_fontstring_keeps_explicit_height() {
+    use std::cell::RefCell;
+    use std::path::Path;
+    use std::rc::Rc;
+    use wow_ui_sim::render::font::WowFontSystem;
+
+    let env = env();
+    env.set_font_system(Rc::new(RefCell::new(WowFontSystem::new(Path::new("./fonts")))));
+    let (lines, height): (i32, f64) = env
+        .eval(
+            r#"
+            local f = CreateFrame("Frame", nil, UIParent)
+            local fs = f:CreateFontString(nil, "OVERLAY")
+            fs:SetFont("Fonts\\FRIZQT__.TTF", 12)
+            fs:SetSize(100, 20)
+            fs:SetWordWrap(true)
+            fs:SetText("This is a fairly long sentence that should wrap inside a narrow font string")
+            return fs:GetNumLines(), fs:GetHeight()
+        "#,
+        )
+        .unwrap();
+    assert!(lines > 1, "expected wrapped text, got {lines} line(s)");
+    assert_eq!(height, 20.0, "SetSize height is not overridden by the wrapped text");
+}
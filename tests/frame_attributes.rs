@@ -0,0 +1,120 @@
+//! Tests for frame attributes (methods_attribute.rs):
+//! SetAttribute/GetAttribute storage, OnAttributeChanged, ClearAttributes.
+
+use wow_ui_sim::lua_api::WowLuaEnv;
+
+fn env() -> WowLuaEnv {
+    WowLuaEnv::new().expect("create env")
+}
+
+#[test]
+fn test_set_attribute_fires_on_attribute_changed() {
+    let env = env();
+    env.exec(
+        r#"
+        AttrLog = {}
+        local f = CreateFrame("Frame", "AttrFrame", UIParent)
+        f:SetScript("OnAttributeChanged", function(self, name, value)
+            table.insert(AttrLog, name .. "=" .. tostring(value))
+        end)
+        f:SetAttribute("unit", "player")
+        f:SetAttribute("unit", "target")
+    "#,
+    )
+    .unwrap();
+
+    let log: String = env.eval("return table.concat(AttrLog, ',')").unwrap();
+    assert_eq!(log, "unit=player,unit=target");
+    let unit: String = env.eval(r#"return AttrFrame:GetAttribute("unit")"#).unwrap();
+    assert_eq!(unit, "target");
+}
+
+#[test]
+fn test_set_attribute_same_value_does_not_fire() {
+    let env = env();
+    env.exec(
+        r#"
+        AttrCount = 0
+        local f = CreateFrame("Frame", "AttrSameFrame", UIParent)
+        f:SetScript("OnAttributeChanged", function() AttrCount = AttrCount + 1 end)
+        f:SetAttribute("statehidden", true)
+        f:SetAttribute("statehidden", true)
+        f:SetAttribute("index", 3)
+        f:SetAttribute("index", 3)
+        f:SetAttribute("missing", nil)
+    "#,
+    )
+    .unwrap();
+
+    let count: i32 = env.eval("return AttrCount").unwrap();
+    assert_eq!(count, 2, "only actual changes should fire OnAttributeChanged");
+}
+
+#[test]
+fn test_set_attribute_no_handler_skips_script() {
+    let env = env();
+    env.exec(
+        r#"
+        AttrCount = 0
+        local f = CreateFrame("Frame", "AttrNoHandlerFrame", UIParent)
+        f:SetScript("OnAttributeChanged", function() AttrCount = AttrCount + 1 end)
+        f:SetAttributeNoHandler("unit", "focus")
+    "#,
+    )
+    .unwrap();
+
+    let count: i32 = env.eval("return AttrCount").unwrap();
+    assert_eq!(count, 0);
+    let unit: String = env.eval(r#"return AttrNoHandlerFrame:GetAttribute("unit")"#).unwrap();
+    assert_eq!(unit, "focus");
+}
+
+#[test]
+fn test_function_attribute_round_trips_and_is_replaced_by_string() {
+    let env = env();
+    env.exec(
+        r#"
+        local btn = CreateFrame("Button", "AttrFuncBtn", UIParent)
+        btn:SetAttribute("type", "custom")
+        btn:SetAttribute("_custom", function(self) return "ran " .. self:GetName() end)
+    "#,
+    )
+    .unwrap();
+
+    // The SecureActionButtonTemplate read path: look up "type", then "_" .. type.
+    let result: String = env
+        .eval(
+            r#"
+            local t = AttrFuncBtn:GetAttribute("type")
+            return AttrFuncBtn:GetAttribute("_" .. t)(AttrFuncBtn)
+        "#,
+        )
+        .unwrap();
+    assert_eq!(result, "ran AttrFuncBtn");
+
+    env.exec(r#"AttrFuncBtn:SetAttribute("_custom", "snippet")"#).unwrap();
+    let value: String = env.eval(r#"return AttrFuncBtn:GetAttribute("_custom")"#).unwrap();
+    assert_eq!(value, "snippet", "string value should replace the stored function");
+}
+
+#[test]
+fn test_clear_attributes_removes_simple_and_table_values() {
+    let env = env();
+    env.exec(
+        r#"
+        local f = CreateFrame("Frame", "AttrClearFrame", UIParent)
+        f:SetAttribute("unit", "player")
+        f:SetAttribute("_onclick", function() end)
+        f:ClearAttributes()
+    "#,
+    )
+    .unwrap();
+
+    let cleared: bool = env
+        .eval(
+            r#"return AttrClearFrame:GetAttribute("unit") == nil
+                and AttrClearFrame:GetAttribute("_onclick") == nil"#,
+        )
+        .unwrap();
+    assert!(cleared);
+}
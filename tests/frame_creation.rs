@@ -151,6 +151,54 @@ fn test_create_frame_default_parent() {
     assert_eq!(parent_name, "UIParent");
 }
 
+#[test]
+fn test_get_children_excludes_regions() {
+    let env = WowLuaEnv::new().unwrap();
+
+    env.exec(
+        r#"
+        local parent = CreateFrame("Frame", "TestRegionsParent", UIParent)
+        CreateFrame("Button", "TestRegionsButton1", parent)
+        local label = parent:CreateFontString("TestRegionsLabel", "OVERLAY")
+        CreateFrame("Button", "TestRegionsButton2", parent)
+        parent:CreateTexture("TestRegionsBg", "BACKGROUND")
+    "#,
+    )
+    .unwrap();
+
+    let (num_children, num_regions): (i32, i32) = env
+        .eval("return TestRegionsParent:GetNumChildren(), TestRegionsParent:GetNumRegions()")
+        .unwrap();
+    assert_eq!((num_children, num_regions), (2, 2));
+
+    let children: String = env
+        .eval(
+            r#"
+            local names = {}
+            for _, c in ipairs({ TestRegionsParent:GetChildren() }) do
+                table.insert(names, c:GetName())
+            end
+            return table.concat(names, ",")
+        "#,
+        )
+        .unwrap();
+    assert_eq!(children, "TestRegionsButton1,TestRegionsButton2");
+
+    // Regions come back in draw order: BACKGROUND before OVERLAY.
+    let regions: String = env
+        .eval(
+            r#"
+            local names = {}
+            for _, r in ipairs({ TestRegionsParent:GetRegions() }) do
+                table.insert(names, r:GetName())
+            end
+            return table.concat(names, ",")
+        "#,
+        )
+        .unwrap();
+    assert_eq!(regions, "TestRegionsBg,TestRegionsLabel");
+}
+
 // ============================================================================
 // $parent Name Substitution Tests
 // ============================================================================
@@ -675,3 +723,78 @@ fn test_checkbutton_text_from_global_string() {
     assert_eq!(label, "Load out of date AddOns");
 }
 
+
+// ============================================================================
+// GetObjectType / IsObjectType
+// ============================================================================
+
+#[test]
+fn test_is_object_type_follows_hierarchy() {
+    let env = WowLuaEnv::new().unwrap();
+    env.exec(r#"
+        TypeBar = CreateFrame("StatusBar", nil, UIParent)
+        TypeCheck = CreateFrame("CheckButton", nil, UIParent)
+        TypeTex = TypeBar:CreateTexture()
+    "#).unwrap();
+
+    let (bar_type, is_frame, is_button): (String, bool, bool) = env
+        .eval("return TypeBar:GetObjectType(), TypeBar:IsObjectType('Frame'), TypeBar:IsObjectType('Button')")
+        .unwrap();
+    assert_eq!(bar_type, "StatusBar");
+    assert!(is_frame);
+    assert!(!is_button);
+
+    let check_is_button: bool = env.eval("return TypeCheck:IsObjectType('button')").unwrap();
+    assert!(check_is_button);
+
+    let (tex_is_region, tex_is_frame): (bool, bool) = env
+        .eval("return TypeTex:IsObjectType('Region'), TypeTex:IsObjectType('Frame')")
+        .unwrap();
+    assert!(tex_is_region);
+    assert!(!tex_is_frame);
+}
+
+#[test]
+fn test_region_get_parent_and_parent_key_back_reference() {
+    let env = WowLuaEnv::new().unwrap();
+
+    env.exec(
+        r#"
+        local frame = CreateFrame("Frame", "TestParentKeyFrame", UIParent)
+        local tex = frame:CreateTexture(nil, "ARTWORK")
+        frame.Icon = tex
+        local text = frame:CreateFontString(nil, "OVERLAY")
+        text:SetParentKey("Label")
+
+        assert(tex:GetParent() == frame, "texture:GetParent() should be the frame")
+        assert(frame.Icon == tex, "frame.Icon should be the texture")
+        assert(text:GetParent() == frame, "fontstring:GetParent() should be the frame")
+        assert(frame.Label == text, "SetParentKey should set frame.Label")
+        assert(text:GetParentKey() == "Label")
+        assert(frame.Icon:GetParent().Label == text, "walk via parent and back")
+
+        assert(UIParent:GetParent() == nil, "UIParent has no parent")
+        assert(WorldFrame:GetParent() == nil, "WorldFrame has no parent")
+    "#,
+    )
+    .unwrap();
+}
+
+#[test]
+fn test_set_parent_key_clear_other_keys_clears_old_lua_field() {
+    let env = WowLuaEnv::new().unwrap();
+
+    env.exec(
+        r#"
+        local frame = CreateFrame("Frame", "TestReKeyFrame", UIParent)
+        local text = frame:CreateFontString(nil, "OVERLAY")
+        text:SetParentKey("Label")
+        text:SetParentKey("Title", true)
+
+        assert(frame.Title == text, "the new key is set")
+        assert(frame.Label == nil, "clearOtherKeys removes the old field")
+        assert(text:GetParentKey() == "Title")
+    "#,
+    )
+    .unwrap();
+}
@@ -0,0 +1,207 @@
+//! Tests for StartMoving/StartSizing/StopMovingOrSizing dragging and
+//! resizing frames.
+
+use wow_ui_sim::lua_api::WowLuaEnv;
+
+/// `Mover` (100x80, movable) anchored TOPLEFT of UIParent at (100, -100).
+fn env() -> WowLuaEnv {
+    let env = WowLuaEnv::new().unwrap();
+    env.set_screen_size(1024.0, 768.0);
+    env.exec(
+        r#"
+        local f = CreateFrame("Frame", "Mover", UIParent)
+        f:SetSize(100, 80)
+        f:SetPoint("TOPLEFT", 100, -100)
+        f:SetMovable(true)
+        f:EnableMouse(true)
+        f:RegisterForDrag("LeftButton")
+        f:SetScript("OnDragStart", f.StartMoving)
+        f:SetScript("OnDragStop", f.StopMovingOrSizing)
+    "#,
+    )
+    .unwrap();
+    env
+}
+
+fn point(env: &WowLuaEnv) -> (String, bool, String, f64, f64) {
+    env.eval(
+        r#"local p, rel, rp, x, y = Mover:GetPoint(1)
+        return p, rel == UIParent, rp, x, y"#,
+    )
+    .unwrap()
+}
+
+#[test]
+fn test_start_moving_follows_cursor_until_stopped() {
+    let env = env();
+    let movable: bool = env.eval("return Mover:IsMovable()").unwrap();
+    assert!(movable);
+
+    env.send_mouse_move(150.0, 140.0).unwrap();
+    env.exec("Mover:StartMoving()").unwrap();
+    // Cursor moves by (50, -30) in WoW coordinates: right and down.
+    env.send_mouse_move(200.0, 170.0).unwrap();
+    env.exec("Mover:StopMovingOrSizing()").unwrap();
+    assert_eq!(point(&env), ("TOPLEFT".into(), true, "TOPLEFT".into(), 150.0, -130.0));
+    let (left, top): (f64, f64) = env.eval("return Mover:GetLeft(), Mover:GetTop()").unwrap();
+    assert_eq!((left, top), (150.0, 768.0 - 130.0));
+
+    // Once stopped, the frame stays put.
+    env.send_mouse_move(400.0, 400.0).unwrap();
+    assert_eq!(point(&env), ("TOPLEFT".into(), true, "TOPLEFT".into(), 150.0, -130.0));
+}
+
+#[test]
+fn test_start_moving_ignores_non_movable_frames() {
+    let env = env();
+    env.exec("Mover:SetMovable(false)").unwrap();
+    env.send_mouse_move(150.0, 140.0).unwrap();
+    env.exec("Mover:StartMoving()").unwrap();
+    env.send_mouse_move(300.0, 300.0).unwrap();
+    assert_eq!(point(&env), ("TOPLEFT".into(), true, "TOPLEFT".into(), 100.0, -100.0));
+}
+
+#[test]
+fn test_moving_respects_clamped_to_screen() {
+    let env = env();
+    env.exec("Mover:SetClampedToScreen(true)").unwrap();
+    env.send_mouse_move(150.0, 140.0).unwrap();
+    env.exec("Mover:StartMoving()").unwrap();
+    env.send_mouse_move(-200.0, 900.0).unwrap();
+    env.exec("Mover:StopMovingOrSizing()").unwrap();
+    assert_eq!(point(&env), ("TOPLEFT".into(), true, "TOPLEFT".into(), 0.0, -(768.0 - 80.0)));
+}
+
+#[test]
+fn test_moving_a_frame_sized_by_two_anchors_keeps_its_size() {
+    let env = env();
+    env.exec(
+        r#"
+        Mover:ClearAllPoints()
+        Mover:SetSize(0, 0)
+        Mover:SetPoint("TOPLEFT", 100, -100)
+        Mover:SetPoint("BOTTOMRIGHT", UIParent, "TOPLEFT", 220, -160)
+    "#,
+    )
+    .unwrap();
+    env.send_mouse_move(150.0, 140.0).unwrap();
+    env.exec("Mover:StartMoving()").unwrap();
+    env.send_mouse_move(200.0, 170.0).unwrap();
+    env.exec("Mover:StopMovingOrSizing()").unwrap();
+    assert_eq!(point(&env), ("TOPLEFT".into(), true, "TOPLEFT".into(), 150.0, -130.0));
+    let size: (f64, f64) = env.eval("return Mover:GetWidth(), Mover:GetHeight()").unwrap();
+    assert_eq!(size, (120.0, 60.0));
+}
+
+#[test]
+fn test_start_moving_another_frame_clears_the_previous_moving_flag() {
+    let env = env();
+    env.exec(
+        r#"
+        local other = CreateFrame("Frame", "OtherMover", UIParent)
+        other:SetSize(50, 50)
+        other:SetPoint("TOPLEFT", 400, -100)
+        other:SetMovable(true)
+        Mover:StartMoving()
+        OtherMover:StartMoving()
+    "#,
+    )
+    .unwrap();
+    let state = env.state().borrow();
+    let moving = |name| state.widgets.get(state.widgets.get_id_by_name(name).unwrap()).unwrap().is_moving;
+    assert!(!moving("Mover"), "only one frame moves with the cursor");
+    assert!(moving("OtherMover"));
+}
+
+/// Make `Mover` resizable within 50x50..180x120 and log OnSizeChanged.
+fn resizable_env() -> WowLuaEnv {
+    let env = env();
+    env.exec(
+        r#"
+        SizeLog = {}
+        Mover:SetResizable(true)
+        Mover:SetResizeBounds(50, 50, 180, 120)
+        Mover:SetScript("OnSizeChanged", function(_, w, h) table.insert(SizeLog, w .. "x" .. h) end)
+    "#,
+    )
+    .unwrap();
+    env
+}
+
+fn size(env: &WowLuaEnv) -> (f64, f64) {
+    env.eval("return Mover:GetWidth(), Mover:GetHeight()").unwrap()
+}
+
+#[test]
+fn test_start_sizing_grows_frame_and_clamps_to_max_bounds() {
+    let env = resizable_env();
+    let bounds: (f64, f64, f64, f64) = env.eval("return Mover:GetResizeBounds()").unwrap();
+    assert_eq!(bounds, (50.0, 50.0, 180.0, 120.0));
+
+    // Grab the bottom-right corner and drag it right 50, down 20.
+    env.send_mouse_move(200.0, 180.0).unwrap();
+    env.exec("Mover:StartSizing('BOTTOMRIGHT')").unwrap();
+    env.send_mouse_move(250.0, 200.0).unwrap();
+    assert_eq!(size(&env), (150.0, 100.0));
+
+    // Far past the max bounds.
+    env.send_mouse_move(600.0, 600.0).unwrap();
+    assert_eq!(size(&env), (180.0, 120.0));
+    env.exec("Mover:StopMovingOrSizing()").unwrap();
+    env.send_mouse_move(300.0, 300.0).unwrap();
+    assert_eq!(size(&env), (180.0, 120.0), "sizing stops with StopMovingOrSizing");
+
+    let log: String = env.eval("return table.concat(SizeLog, ',')").unwrap();
+    assert_eq!(log, "150x100,180x120");
+    // The top-left corner never moved.
+    assert_eq!(point(&env), ("TOPLEFT".into(), true, "TOPLEFT".into(), 100.0, -100.0));
+}
+
+#[test]
+fn test_sizing_from_top_left_keeps_bottom_right_corner() {
+    let env = resizable_env();
+    env.send_mouse_move(100.0, 100.0).unwrap();
+    env.exec("Mover:StartSizing('TOPLEFT')").unwrap();
+    // Drag the corner up-left by (20, 10), then far enough to hit the minimum.
+    env.send_mouse_move(80.0, 90.0).unwrap();
+    assert_eq!(size(&env), (120.0, 90.0));
+    assert_eq!(point(&env), ("TOPLEFT".into(), true, "TOPLEFT".into(), 80.0, -90.0));
+    env.send_mouse_move(500.0, 500.0).unwrap();
+    assert_eq!(size(&env), (50.0, 50.0));
+    let (right, bottom): (f64, f64) = env.eval("return Mover:GetRight(), Mover:GetBottom()").unwrap();
+    assert_eq!((right, bottom), (200.0, 768.0 - 180.0));
+}
+
+#[test]
+fn test_start_sizing_ignores_non_resizable_frames() {
+    let env = resizable_env();
+    env.exec("Mover:SetResizable(false)").unwrap();
+    env.send_mouse_move(200.0, 180.0).unwrap();
+    env.exec("Mover:StartSizing()").unwrap();
+    env.send_mouse_move(300.0, 300.0).unwrap();
+    assert_eq!(size(&env), (100.0, 80.0));
+    let log: String = env.eval("return table.concat(SizeLog, ',')").unwrap();
+    assert_eq!(log, "");
+}
+
+#[test]
+fn test_sizing_a_frame_sized_by_two_anchors_starts_from_its_laid_out_size() {
+    let env = resizable_env();
+    env.exec(
+        r#"
+        Mover:ClearAllPoints()
+        Mover:SetSize(0, 0)
+        Mover:SetPoint("TOPLEFT", 100, -100)
+        Mover:SetPoint("BOTTOMRIGHT", UIParent, "TOPLEFT", 220, -160)
+    "#,
+    )
+    .unwrap();
+    env.send_mouse_move(220.0, 160.0).unwrap();
+    env.exec("Mover:StartSizing('BOTTOMRIGHT')").unwrap();
+    assert_eq!(size(&env), (120.0, 60.0), "re-anchoring keeps the anchored size");
+    env.send_mouse_move(230.0, 170.0).unwrap();
+    assert_eq!(size(&env), (130.0, 70.0));
+    assert_eq!(point(&env), ("TOPLEFT".into(), true, "TOPLEFT".into(), 100.0, -100.0));
+    let log: String = env.eval("return table.concat(SizeLog, ',')").unwrap();
+    assert_eq!(log, "130x70");
+}
@@ -7,7 +7,9 @@
 use std::path::PathBuf;
 use wow_ui_sim::loader::{discover_blizzard_addons, load_addon};
 use wow_ui_sim::lua_api::WowLuaEnv;
-use wow_ui_sim::startup::{fire_one_on_update_tick, fire_startup_events, process_pending_timers};
+use wow_ui_sim::startup::{
+    advance_clock, fire_one_on_update_tick, fire_startup_events, process_pending_timers,
+};
 
 fn blizzard_ui_dir() -> PathBuf {
     PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("Interface/BlizzardUI")
@@ -40,8 +42,8 @@ fn setup_env() -> WowLuaEnv {
     fire_one_on_update_tick(&env);
     let _ = wow_ui_sim::lua_api::globals::global_frames::hide_runtime_hidden_frames(env.lua());
 
-    // Allow timer-driven layout callbacks to become due (real wall clock via Instant)
-    std::thread::sleep(std::time::Duration::from_secs(2));
+    // Allow timer-driven layout callbacks to become due (virtual SimClock)
+    advance_clock(&env, 2.0);
 
     // Extra update ticks — drain timers and fire OnUpdate (same as main.rs)
     for _ in 0..3 {
@@ -0,0 +1,108 @@
+//! Tests for SetToplevel, Raise/Lower and raising toplevel frames on click.
+
+use wow_ui_sim::lua_api::WowLuaEnv;
+use wow_ui_sim::widget::Frame;
+
+/// `TopBack` (toplevel, level 5) at (0,0) partly covered by `TopFront`
+/// (level 10) at (100,100); both 200x200 and mouse-enabled.
+fn env() -> WowLuaEnv {
+    let env = WowLuaEnv::new().unwrap();
+    env.set_screen_size(1024.0, 768.0);
+    env.exec(
+        r#"
+        local back = CreateFrame("Frame", "TopBack", UIParent)
+        back:SetSize(200, 200)
+        back:SetPoint("TOPLEFT", 0, 0)
+        back:SetFrameLevel(5)
+        back:SetToplevel(true)
+        back:EnableMouse(true)
+        local child = CreateFrame("Button", "TopBackChild", back)
+        child:SetSize(20, 20)
+        child:SetPoint("TOPLEFT", 10, -10)
+
+        local front = CreateFrame("Frame", "TopFront", UIParent)
+        front:SetSize(200, 200)
+        front:SetPoint("TOPLEFT", 100, -100)
+        front:SetFrameLevel(10)
+        front:EnableMouse(true)
+    "#,
+    )
+    .unwrap();
+    env
+}
+
+fn id(env: &WowLuaEnv, name: &str) -> u64 {
+    env.state().borrow().widgets.get_id_by_name(name).unwrap()
+}
+
+/// Position of `name` within its strata bucket (render order).
+fn render_index(env: &WowLuaEnv, name: &str) -> usize {
+    let id = id(env, name);
+    let mut state = env.state().borrow_mut();
+    state.ensure_layout_rects();
+    let buckets = state.get_strata_buckets().unwrap();
+    buckets.iter().find_map(|b| b.iter().position(|&f| f == id)).unwrap()
+}
+
+fn click(env: &WowLuaEnv, x: f32, y: f32) {
+    env.send_mouse_down(x, y, "LeftButton").unwrap();
+    env.send_mouse_up(x, y, "LeftButton").unwrap();
+}
+
+#[test]
+fn test_clicking_toplevel_frame_brings_it_to_front() {
+    let env = env();
+    let toplevel: bool = env.eval("return TopBack:IsToplevel()").unwrap();
+    assert!(toplevel);
+    assert_eq!(env.frame_at(150.0, 150.0, Frame::accepts_mouse_clicks), Some(id(&env, "TopFront")));
+    assert!(render_index(&env, "TopBack") < render_index(&env, "TopFront"));
+
+    // Click the uncovered corner of the back frame.
+    click(&env, 50.0, 150.0);
+
+    let (back, front): (i32, i32) =
+        env.eval("return TopBack:GetFrameLevel(), TopFront:GetFrameLevel()").unwrap();
+    assert!(back > front, "back {back}, front {front}");
+    assert!(render_index(&env, "TopBack") > render_index(&env, "TopFront"));
+    assert_eq!(env.frame_at(150.0, 150.0, Frame::accepts_mouse_clicks), Some(id(&env, "TopBack")));
+    // Children follow their parent's new level.
+    let child: i32 = env.eval("return TopBackChild:GetFrameLevel()").unwrap();
+    assert_eq!(child, back + 1);
+}
+
+#[test]
+fn test_clicking_child_raises_toplevel_ancestor_only() {
+    let env = env();
+    env.exec("TopBackChild:EnableMouse(true)").unwrap();
+    click(&env, 15.0, 15.0);
+    assert!(render_index(&env, "TopBack") > render_index(&env, "TopFront"));
+
+    // A non-toplevel frame stays where it is when clicked.
+    env.exec("TopBack:SetFrameLevel(5); TopBack:SetToplevel(false)").unwrap();
+    click(&env, 50.0, 150.0);
+    let back: i32 = env.eval("return TopBack:GetFrameLevel()").unwrap();
+    assert_eq!(back, 5);
+    assert!(render_index(&env, "TopBack") < render_index(&env, "TopFront"));
+}
+
+#[test]
+fn test_raise_and_lower_reorder_siblings() {
+    let env = env();
+    env.exec("TopBack:Raise()").unwrap();
+    assert!(render_index(&env, "TopBack") > render_index(&env, "TopFront"));
+
+    env.exec("TopBack:Lower()").unwrap();
+    let (back, front): (i32, i32) =
+        env.eval("return TopBack:GetFrameLevel(), TopFront:GetFrameLevel()").unwrap();
+    assert!(back < front, "back {back}, front {front}");
+    assert!(render_index(&env, "TopBack") < render_index(&env, "TopFront"));
+    assert_eq!(env.frame_at(150.0, 150.0, Frame::accepts_mouse_clicks), Some(id(&env, "TopFront")));
+}
+
+#[test]
+fn test_set_frame_level_reorders_render_buckets() {
+    let env = env();
+    assert!(render_index(&env, "TopBack") < render_index(&env, "TopFront"));
+    env.exec("TopBack:SetFrameLevel(20)").unwrap();
+    assert!(render_index(&env, "TopBack") > render_index(&env, "TopFront"));
+}
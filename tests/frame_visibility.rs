@@ -0,0 +1,141 @@
+//! Tests for Show/Hide/IsShown/IsVisible (methods_core.rs): the parent chain
+//! and OnShow/OnHide firing only on effective visibility transitions.
+
+use wow_ui_sim::lua_api::WowLuaEnv;
+
+/// Env with a shown "VisParent" holding a shown "VisChild". Both log
+/// "<Name>:<Handler>" to `VisLog` from OnShow/OnHide.
+fn env_with_parent_and_child() -> WowLuaEnv {
+    let env = WowLuaEnv::new().expect("create env");
+    env.exec(
+        r#"
+        VisLog = {}
+        local function track(frame)
+            for _, h in ipairs({ "OnShow", "OnHide" }) do
+                frame:SetScript(h, function(self)
+                    table.insert(VisLog, self:GetName() .. ":" .. h)
+                end)
+            end
+        end
+        local parent = CreateFrame("Frame", "VisParent", UIParent)
+        local child = CreateFrame("Frame", "VisChild", parent)
+        track(parent)
+        track(child)
+    "#,
+    )
+    .unwrap();
+    env
+}
+
+fn take_log(env: &WowLuaEnv) -> String {
+    env.eval("local s = table.concat(VisLog, ','); VisLog = {}; return s")
+        .unwrap()
+}
+
+#[test]
+fn test_hidden_parent_makes_child_invisible_but_shown() {
+    let env = env_with_parent_and_child();
+    env.exec("VisParent:Hide()").unwrap();
+
+    let (shown, visible): (bool, bool) = env
+        .eval("return VisChild:IsShown(), VisChild:IsVisible()")
+        .unwrap();
+    assert!(shown);
+    assert!(!visible);
+}
+
+#[test]
+fn test_hide_and_show_propagate_to_shown_children() {
+    let env = env_with_parent_and_child();
+
+    env.exec("VisParent:Hide()").unwrap();
+    assert_eq!(take_log(&env), "VisParent:OnHide,VisChild:OnHide");
+
+    env.exec("VisParent:Show()").unwrap();
+    assert_eq!(take_log(&env), "VisParent:OnShow,VisChild:OnShow");
+}
+
+#[test]
+fn test_scripts_fire_only_on_transitions() {
+    let env = env_with_parent_and_child();
+
+    env.exec("VisParent:Show(); VisChild:SetShown(true)").unwrap();
+    assert_eq!(take_log(&env), "");
+
+    env.exec("VisParent:Hide(); VisParent:Hide()").unwrap();
+    assert_eq!(take_log(&env), "VisParent:OnHide,VisChild:OnHide");
+
+    // Child toggles under a hidden parent never become visible.
+    env.exec("VisChild:Hide(); VisChild:Show()").unwrap();
+    assert_eq!(take_log(&env), "");
+}
+
+#[test]
+fn test_hidden_child_stays_quiet_when_parent_shows() {
+    let env = env_with_parent_and_child();
+    env.exec("VisChild:Hide()").unwrap();
+    assert_eq!(take_log(&env), "VisChild:OnHide");
+
+    env.exec("VisParent:Hide(); VisParent:Show()").unwrap();
+    assert_eq!(take_log(&env), "VisParent:OnHide,VisParent:OnShow");
+}
+
+#[test]
+fn test_showing_parent_fires_children_top_down_once() {
+    let env = env_with_parent_and_child();
+    env.exec(
+        r#"
+        local second = CreateFrame("Frame", "VisChild2", VisParent)
+        local grandchild = CreateFrame("Frame", "VisGrandchild", VisChild)
+        for _, frame in ipairs({ second, grandchild }) do
+            frame:SetScript("OnShow", function(self)
+                table.insert(VisLog, self:GetName() .. ":OnShow")
+            end)
+        end
+        VisParent:Hide()
+        VisLog = {}
+    "#,
+    )
+    .unwrap();
+
+    env.exec("VisParent:SetShown(true)").unwrap();
+    assert_eq!(take_log(&env), "VisParent:OnShow,VisChild:OnShow,VisGrandchild:OnShow,VisChild2:OnShow");
+    env.exec("VisParent:SetShown(true)").unwrap();
+    assert_eq!(take_log(&env), "");
+}
+
+#[test]
+fn test_child_shown_from_parent_on_show_fires_once() {
+    let env = env_with_parent_and_child();
+    // Tab-style parent: OnShow selects (shows) the child itself.
+    env.exec(
+        r#"
+        VisChild:Hide()
+        VisParent:Hide()
+        VisParent:SetScript("OnShow", function(self)
+            table.insert(VisLog, "VisParent:OnShow")
+            VisChild:Show()
+        end)
+        VisLog = {}
+    "#,
+    )
+    .unwrap();
+
+    env.exec("VisParent:Show()").unwrap();
+    assert_eq!(take_log(&env), "VisParent:OnShow,VisChild:OnShow");
+
+    // A child hidden by the parent's OnShow gets OnHide and no OnShow.
+    env.exec(
+        r#"
+        VisParent:Hide()
+        VisParent:SetScript("OnShow", function(self)
+            table.insert(VisLog, "VisParent:OnShow")
+            VisChild:Hide()
+        end)
+        VisLog = {}
+        VisParent:Show()
+    "#,
+    )
+    .unwrap();
+    assert_eq!(take_log(&env), "VisParent:OnShow,VisChild:OnHide");
+}
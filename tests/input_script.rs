@@ -0,0 +1,83 @@
+//! Tests for headless input scripts (input.rs): commands run against the
+//! layout through hit testing.
+
+use wow_ui_sim::input::{inline_script_lines, parse_input_script, run_input_script};
+use wow_ui_sim::lua_api::WowLuaEnv;
+
+/// Env with a clickable "ScriptButton" partly covered by "ScriptCover" in a
+/// higher strata, and a focused "ScriptEdit" EditBox. Scripts log to `InputLog`.
+fn env_with_widgets() -> WowLuaEnv {
+    let env = WowLuaEnv::new().expect("create env");
+    env.set_screen_size(1024.0, 768.0);
+    env.exec(
+        r#"
+        InputLog = {}
+        local function log(s) table.insert(InputLog, s) end
+        local btn = CreateFrame("Button", "ScriptButton", UIParent)
+        btn:SetSize(100, 40)
+        btn:SetPoint("TOPLEFT", 50, -50)
+        btn:SetScript("OnEnter", function() log("enter") end)
+        btn:SetScript("OnClick", function(_, button) log("click:" .. button) end)
+        btn:SetScript("OnMouseWheel", function(_, delta) log("wheel:" .. delta) end)
+
+        local cover = CreateFrame("Button", "ScriptCover", UIParent)
+        cover:SetSize(100, 40)
+        cover:SetPoint("TOPLEFT", 300, -50)
+        cover:SetFrameStrata("HIGH")
+        local hidden = CreateFrame("Button", "ScriptCovered", UIParent)
+        hidden:SetAllPoints(cover)
+        hidden:SetScript("OnClick", function() log("covered") end)
+
+        local edit = CreateFrame("EditBox", "ScriptEdit", UIParent)
+        edit:SetSize(200, 20)
+        edit:SetPoint("BOTTOM")
+        edit:SetScript("OnEnterPressed", function(self) log("submit:" .. self:GetText()) end)
+        edit:SetFocus()
+    "#,
+    )
+    .unwrap();
+    env
+}
+
+fn run(env: &WowLuaEnv, script: &str) {
+    let commands = parse_input_script(script).unwrap();
+    run_input_script(env, &commands).unwrap();
+}
+
+fn take_log(env: &WowLuaEnv) -> String {
+    env.eval("local s = table.concat(InputLog, ','); InputLog = {}; return s")
+        .unwrap()
+}
+
+#[test]
+fn test_mouse_commands_hit_test_frame_centers() {
+    let env = env_with_widgets();
+    run(&env, "hover ScriptButton\nclick ScriptButton RightButton\nscroll ScriptButton -1\n");
+    assert_eq!(take_log(&env), "enter,click:RightButton,wheel:-1");
+
+    // The covering frame takes the click.
+    run(&env, "click ScriptCovered");
+    assert_eq!(take_log(&env), "");
+}
+
+#[test]
+fn test_key_and_type_commands_reach_focused_editbox() {
+    let env = env_with_widgets();
+    run(&env, "type hi there\nkey RETURN");
+    assert_eq!(take_log(&env), "submit:hi there");
+}
+
+#[test]
+fn test_inline_script_keeps_escaped_semicolons_in_typed_text() {
+    let env = env_with_widgets();
+    run(&env, &inline_script_lines(r"type a\;b;key RETURN"));
+    assert_eq!(take_log(&env), "submit:a;b");
+}
+
+#[test]
+fn test_unknown_frame_is_an_error() {
+    let env = env_with_widgets();
+    let commands = parse_input_script("click NoSuchFrame").unwrap();
+    let err = run_input_script(&env, &commands).unwrap_err();
+    assert!(err.contains("NoSuchFrame"), "{err}");
+}
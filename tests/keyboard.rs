@@ -455,6 +455,105 @@ fn test_click_editbox_type_message_and_submit() {
     assert_eq!(text_after, "", "EditBox should be cleared after submit");
 }
 
+fn focused_editbox(env: &WowLuaEnv, name: &str) {
+    env.exec(&format!(
+        r#"
+        local eb = CreateFrame("EditBox", "{name}", UIParent)
+        eb:SetSize(200, 30)
+        eb:SetFocus()
+        "#
+    ))
+    .unwrap();
+}
+
+#[test]
+fn test_editbox_typing_fires_on_text_changed_with_running_value() {
+    let env = WowLuaEnv::new().unwrap();
+    focused_editbox(&env, "SearchEditBox");
+    env.exec(
+        r#"
+        _G.seen = {}
+        SearchEditBox:SetScript("OnTextChanged", function(self, userInput)
+            table.insert(_G.seen, self:GetText() .. ":" .. tostring(userInput))
+        end)
+    "#,
+    )
+    .unwrap();
+
+    for ch in "ore".chars() {
+        let s = ch.to_string();
+        env.send_key_press(&s.to_uppercase(), Some(&s)).unwrap();
+    }
+
+    let seen: String = env.eval("return table.concat(_G.seen, ',')").unwrap();
+    assert_eq!(seen, "o:true,or:true,ore:true");
+    let cursor: i32 = env.eval("return SearchEditBox:GetCursorPosition()").unwrap();
+    assert_eq!(cursor, 3);
+}
+
+#[test]
+fn test_editbox_max_letters_limits_typing_and_set_text() {
+    let env = WowLuaEnv::new().unwrap();
+    focused_editbox(&env, "ShortEditBox");
+    env.exec("ShortEditBox:SetMaxLetters(3)").unwrap();
+
+    for ch in "abcd".chars() {
+        let s = ch.to_string();
+        env.send_key_press(&s.to_uppercase(), Some(&s)).unwrap();
+    }
+    let typed: String = env.eval("return ShortEditBox:GetText()").unwrap();
+    assert_eq!(typed, "abc", "typing past SetMaxLetters should be dropped");
+
+    env.exec("ShortEditBox:SetText('wxyz')").unwrap();
+    let (text, cursor): (String, i32) = env
+        .eval("return ShortEditBox:GetText(), ShortEditBox:GetCursorPosition()")
+        .unwrap();
+    assert_eq!((text.as_str(), cursor), ("wxy", 3));
+}
+
+#[test]
+fn test_editbox_numeric_rejects_letters() {
+    let env = WowLuaEnv::new().unwrap();
+    focused_editbox(&env, "NumEditBox");
+    env.exec("NumEditBox:SetNumeric(true)").unwrap();
+
+    env.send_key_press("A", Some("a")).unwrap();
+    env.send_key_press("4", Some("4")).unwrap();
+    env.send_key_press("2", Some("2")).unwrap();
+
+    let text: String = env.eval("return NumEditBox:GetText()").unwrap();
+    assert_eq!(text, "42");
+}
+
+#[test]
+fn test_editbox_insert_at_cursor_uses_char_positions() {
+    let env = WowLuaEnv::new().unwrap();
+    focused_editbox(&env, "InsertEditBox");
+    env.exec(
+        r#"
+        _G.changes = 0
+        InsertEditBox:SetText("héllo")
+        InsertEditBox:SetScript("OnTextChanged", function(self, userInput)
+            _G.changes = _G.changes + 1
+            _G.lastUserInput = userInput
+        end)
+        InsertEditBox:SetCursorPosition(2)
+        InsertEditBox:Insert("XY")
+    "#,
+    )
+    .unwrap();
+
+    let (text, cursor): (String, i32) = env
+        .eval("return InsertEditBox:GetText(), InsertEditBox:GetCursorPosition()")
+        .unwrap();
+    assert_eq!(text, "héXYllo");
+    assert_eq!(cursor, 4);
+    let (changes, user_input): (i32, bool) =
+        env.eval("return _G.changes, _G.lastUserInput").unwrap();
+    assert_eq!(changes, 1);
+    assert!(!user_input, "Insert from Lua is not user input");
+}
+
 // --- Targeting system tests ---
 
 #[test]
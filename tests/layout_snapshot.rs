@@ -0,0 +1,66 @@
+//! Tests for the layout-only headless path (`compute_layout`).
+
+use wow_ui_sim::iced_app::layout::compute_frame_rect;
+use wow_ui_sim::layout_snapshot::compute_layout;
+use wow_ui_sim::lua_api::WowLuaEnv;
+
+/// A small addon: a centered panel, a child anchored to its corner and a
+/// frame that only gets its size once PLAYER_LOGIN fires.
+fn load_fixture(env: &WowLuaEnv) {
+    env.exec(
+        r#"
+        local panel = CreateFrame("Frame", "LayoutFixturePanel", UIParent)
+        panel:SetSize(300, 200)
+        panel:SetPoint("CENTER", UIParent, "CENTER", 50, -20)
+
+        local child = CreateFrame("Button", "LayoutFixtureChild", panel)
+        child:SetSize(40, 20)
+        child:SetPoint("TOPLEFT", panel, "TOPLEFT", 10, -10)
+
+        local late = CreateFrame("Frame", "LayoutFixtureLate", UIParent)
+        late:RegisterEvent("PLAYER_LOGIN")
+        late:SetScript("OnEvent", function(self)
+            self:SetSize(120, 30)
+            self:SetPoint("TOP", panel, "BOTTOM", 0, -5)
+        end)
+        "#,
+    )
+    .unwrap();
+}
+
+#[test]
+fn test_compute_layout_matches_dump_tree_rects() {
+    let env = WowLuaEnv::new().unwrap();
+    load_fixture(&env);
+
+    let snapshot = compute_layout(&env, 1024, 768);
+
+    let state = env.state().borrow();
+    for name in ["LayoutFixturePanel", "LayoutFixtureChild", "LayoutFixtureLate"] {
+        let id = state.widgets.get_id_by_name(name).unwrap();
+        let expected = compute_frame_rect(&state.widgets, id, 1024.0, 768.0);
+        let got = snapshot.get(name).unwrap_or_else(|| panic!("{name} missing from snapshot"));
+        assert_eq!(
+            (got.x, got.y, got.width, got.height),
+            (expected.x, expected.y, expected.width, expected.height),
+            "{name}"
+        );
+    }
+    let panel = snapshot.get("LayoutFixturePanel").unwrap();
+    assert_eq!((panel.x, panel.y, panel.width, panel.height), (412.0, 304.0, 300.0, 200.0));
+    let late = snapshot.get("LayoutFixtureLate").unwrap();
+    assert_eq!((late.width, late.height), (120.0, 30.0), "PLAYER_LOGIN handler ran");
+}
+
+#[test]
+fn test_layout_snapshot_json_skips_anonymous_frames() {
+    let env = WowLuaEnv::new().unwrap();
+    load_fixture(&env);
+    env.exec("CreateFrame('Frame', nil, UIParent):SetSize(10, 10)").unwrap();
+
+    let json = compute_layout(&env, 1024, 768).to_json();
+    assert_eq!(json["screen"]["width"], 1024.0);
+    assert_eq!(json["frames"]["LayoutFixtureChild"]["width"], 40.0);
+    let frames = json["frames"].as_object().unwrap();
+    assert!(frames.keys().all(|name| !name.starts_with("__")), "generated names leaked");
+}
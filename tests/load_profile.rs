@@ -0,0 +1,82 @@
+//! Tests for the `--profile` load profiler (loader/profile.rs).
+
+use wow_ui_sim::loader::load_addon;
+use wow_ui_sim::loader::profile::{LoadProfile, WidgetCounts};
+use wow_ui_sim::lua_api::WowLuaEnv;
+
+/// Addon with a virtual XML template (one texture, one font string) that its
+/// Lua file instantiates twice through CreateFrame.
+fn write_addon(root: &std::path::Path) -> std::path::PathBuf {
+    let dir = root.join("ProfAddon");
+    std::fs::create_dir(&dir).unwrap();
+    std::fs::write(dir.join("ProfAddon.toc"), "## Interface: 110207\nTemplates.xml\nProfAddon.lua\n").unwrap();
+    std::fs::write(
+        dir.join("Templates.xml"),
+        r#"<Ui>
+    <Frame name="ProfRowTemplate" virtual="true">
+        <Size x="100" y="20"/>
+        <Layers>
+            <Layer level="BACKGROUND">
+                <Texture parentKey="Bg"/>
+            </Layer>
+            <Layer level="OVERLAY">
+                <FontString parentKey="Label" inherits="GameFontNormal"/>
+            </Layer>
+        </Layers>
+    </Frame>
+</Ui>"#,
+    )
+    .unwrap();
+    std::fs::write(
+        dir.join("ProfAddon.lua"),
+        r#"
+        for i = 1, 2 do
+            CreateFrame("Frame", "ProfRow" .. i, UIParent, "ProfRowTemplate")
+        end
+    "#,
+    )
+    .unwrap();
+    dir.join("ProfAddon.toc")
+}
+
+#[test]
+fn test_profile_records_files_templates_and_created_widgets() {
+    let addons = tempfile::tempdir().unwrap();
+    let toc = write_addon(addons.path());
+    let env = WowLuaEnv::new().unwrap();
+    env.state().borrow_mut().load_profile = Some(LoadProfile::default());
+
+    load_addon(&env.loader_env(), &toc).unwrap();
+
+    let profile = env.state().borrow_mut().load_profile.take().unwrap();
+    let files: Vec<&str> = profile.files.iter().map(|f| f.path.as_str()).collect();
+    assert_eq!(files.len(), 2, "{files:?}");
+    assert!(files[0].ends_with("Templates.xml") && files[1].ends_with("ProfAddon.lua"), "{files:?}");
+    assert!(profile.files.iter().all(|f| f.addon == "ProfAddon"));
+
+    assert_eq!(profile.templates["ProfRowTemplate"].applications, 2);
+
+    assert_eq!(profile.addons.len(), 1);
+    assert_eq!(profile.addons[0].name, "ProfAddon");
+    assert_eq!(
+        profile.addons[0].created,
+        WidgetCounts { frames: 2, textures: 2, font_strings: 2 }
+    );
+
+    let report = profile.report(10);
+    assert!(report.contains("ProfAddon  (2 frames, 2 textures, 2 font strings)"), "{report}");
+    assert!(report.contains("x2     ProfRowTemplate"), "{report}");
+}
+
+#[test]
+fn test_profile_is_off_by_default() {
+    let addons = tempfile::tempdir().unwrap();
+    let toc = write_addon(addons.path());
+    let env = WowLuaEnv::new().unwrap();
+
+    load_addon(&env.loader_env(), &toc).unwrap();
+
+    assert!(env.state().borrow().load_profile.is_none());
+    let created: bool = env.eval("return ProfRow2 ~= nil and ProfRow2.Label ~= nil").unwrap();
+    assert!(created);
+}
@@ -0,0 +1,98 @@
+//! Tests for the Lua server's `Eval` request (lua_server::eval_chunk):
+//! typed return values and table truncation; and the console subscribers
+//! behind `Tail`.
+
+use wow_ui_sim::lua_api::WowLuaEnv;
+use wow_ui_sim::lua_server::{eval_chunk, EvalValue, Response, EVAL_MAX_ENTRIES};
+
+fn eval(code: &str) -> Vec<EvalValue> {
+    let env = WowLuaEnv::new().expect("create env");
+    match eval_chunk(env.lua(), code) {
+        Response::Values(values) => values,
+        Response::Error(e) => panic!("eval failed: {e}"),
+        other => panic!("unexpected response: {other:?}"),
+    }
+}
+
+#[test]
+fn test_eval_returns_typed_multi_values() {
+    let values = eval(r#"1.5, "hi", nil, true"#);
+    assert_eq!(
+        values,
+        vec![
+            EvalValue::Number(1.5),
+            EvalValue::String("hi".into()),
+            EvalValue::Nil,
+            EvalValue::Boolean(true),
+        ]
+    );
+}
+
+#[test]
+fn test_eval_accepts_statement_chunks() {
+    let values = eval("local x = GetTime() return type(x)");
+    assert_eq!(values, vec![EvalValue::String("number".into())]);
+}
+
+#[test]
+fn test_eval_tables_render_as_json() {
+    let values = eval("{ 10, 20, 30 }, { name = \"UIParent\", shown = true }");
+    let json: Vec<String> = values.iter().map(|v| v.to_json().to_string()).collect();
+    assert_eq!(json, vec![r#"[10,20,30]"#, r#"{"name":"UIParent","shown":true}"#]);
+}
+
+#[test]
+fn test_eval_caps_table_depth_and_size() {
+    let values = eval(
+        r#"(function()
+            local big = {}
+            for i = 1, 500 do big[i] = i end
+            local deep = { { { { { "bottom" } } } } }
+            return big, deep
+        end)()"#,
+    );
+    let EvalValue::Table { entries, truncated } = &values[0] else {
+        panic!("expected table, got {:?}", values[0]);
+    };
+    assert_eq!(entries.len(), EVAL_MAX_ENTRIES);
+    assert!(truncated);
+    assert!(values[1].to_json().to_string().contains("<truncated>"));
+}
+
+#[test]
+fn test_eval_reports_errors() {
+    let env = WowLuaEnv::new().expect("create env");
+    assert!(matches!(eval_chunk(env.lua(), "error('boom')"), Response::Error(e) if e.contains("boom")));
+}
+
+#[test]
+fn test_console_subscribers_receive_print_and_errors() {
+    let env = WowLuaEnv::new().expect("create env");
+    let (tx, rx) = std::sync::mpsc::channel();
+    env.state().borrow_mut().console_subscribers.push(tx);
+
+    env.exec(r#"print("hello", 42)"#).unwrap();
+    env.exec(r#"
+        local f = CreateFrame("Frame")
+        f:SetScript("OnEvent", function() error("tail boom") end)
+        f:RegisterEvent("PLAYER_LOGIN")
+    "#)
+    .unwrap();
+    let _ = env.fire_event("PLAYER_LOGIN");
+
+    let lines: Vec<String> = rx.try_iter().collect();
+    assert_eq!(lines[0], "hello\t42");
+    assert!(lines[1..].iter().any(|l| l.contains("tail boom")), "lines: {lines:?}");
+}
+
+#[test]
+fn test_dropped_console_subscriber_is_pruned() {
+    let env = WowLuaEnv::new().expect("create env");
+    let (tx, rx) = std::sync::mpsc::channel();
+    env.state().borrow_mut().console_subscribers.push(tx);
+    drop(rx);
+
+    env.exec(r#"print("nobody listening")"#).unwrap();
+
+    assert!(env.state().borrow().console_subscribers.is_empty());
+}
@@ -390,3 +390,183 @@ fn test_max_lines_truncates_existing() {
     let count: i32 = env.eval("return TestMFTrunc:GetNumMessages()").unwrap();
     assert_eq!(count, 2, "SetMaxLines should truncate existing messages");
 }
+
+#[test]
+fn test_max_lines_keeps_newest_messages() {
+    let env = WowLuaEnv::new().unwrap();
+
+    let (count, oldest, newest): (i32, String, String) = env
+        .eval(
+            r#"
+        local f = CreateFrame("ScrollingMessageFrame", "TestMFRing", UIParent)
+        f:SetMaxLines(3)
+        for i = 1, 5 do f:AddMessage("Line " .. i) end
+        local count = f:GetNumMessages()
+        f:SetMaxLines(2)
+        return count, f:GetMessageInfo(1), f:GetMessageInfo(2)
+    "#,
+        )
+        .unwrap();
+    assert_eq!(count, 3);
+    assert_eq!(oldest, "Line 4");
+    assert_eq!(newest, "Line 5");
+}
+
+#[test]
+fn test_scrolling_through_scrollback() {
+    let env = WowLuaEnv::new().unwrap();
+
+    let (range, offset, at_bottom, at_top): (i32, i32, bool, bool) = env
+        .eval(
+            r#"
+        local f = CreateFrame("ScrollingMessageFrame", "TestMFScroll", UIParent)
+        for i = 1, 4 do f:AddMessage("Line " .. i) end
+        f:ScrollUp()
+        f:ScrollUp()
+        local offset, atBottom = f:GetScrollOffset(), f:AtBottom()
+        f:ScrollToTop()
+        return f:GetMaxScrollRange(), offset, atBottom, f:AtTop()
+    "#,
+        )
+        .unwrap();
+    assert_eq!(range, 3);
+    assert_eq!(offset, 2);
+    assert!(!at_bottom);
+    assert!(at_top);
+}
+
+#[test]
+fn test_color_codes_render_as_colored_glyphs() {
+    use std::path::Path;
+    use wow_ui_sim::iced_app::build_quad_batch_for_registry;
+    use wow_ui_sim::render::font::WowFontSystem;
+    use wow_ui_sim::render::glyph::GlyphAtlas;
+    use wow_ui_sim::render::shader::GLYPH_ATLAS_TEX_INDEX;
+
+    let env = WowLuaEnv::new().unwrap();
+    env.exec(
+        r#"
+        DEFAULT_CHAT_FRAME:ClearAllPoints()
+        DEFAULT_CHAT_FRAME:SetPoint("BOTTOMLEFT", 20, 20)
+        DEFAULT_CHAT_FRAME:SetSize(400, 120)
+        DEFAULT_CHAT_FRAME:Show()
+        DEFAULT_CHAT_FRAME:AddMessage("|cffff0000Hi|r there")
+    "#,
+    )
+    .unwrap();
+
+    let buckets = {
+        let mut state = env.state().borrow_mut();
+        state.ensure_layout_rects();
+        let _ = state.get_strata_buckets();
+        state.strata_buckets.as_ref().unwrap().clone()
+    };
+    let mut fs = WowFontSystem::new(Path::new("./fonts"));
+    let mut ga = GlyphAtlas::new();
+    let state = env.state().borrow();
+    let batch = build_quad_batch_for_registry(
+        &state.widgets,
+        (1024.0, 768.0),
+        Some("DEFAULT_CHAT_FRAME"),
+        None,
+        None,
+        Some((&mut fs, &mut ga)),
+        Some(&state.message_frames),
+        None,
+        &buckets,
+    );
+
+    let glyph_colors: Vec<[f32; 4]> = batch
+        .vertices
+        .chunks(4)
+        .filter(|q| q[0].tex_index == GLYPH_ATLAS_TEX_INDEX)
+        .map(|q| q[0].color)
+        .collect();
+    let red = glyph_colors.iter().filter(|c| **c == [1.0, 0.0, 0.0, 1.0]).count();
+    let white = glyph_colors.iter().filter(|c| **c == [1.0, 1.0, 1.0, 1.0]).count();
+    assert_eq!(red, 2, "'Hi' should be red: {glyph_colors:?}");
+    assert_eq!(white, 5, "'there' keeps the message color: {glyph_colors:?}");
+}
+
+#[test]
+fn test_click_on_rendered_item_link_fires_on_hyperlink_click() {
+    use std::path::Path;
+    use wow_ui_sim::iced_app::build_quad_batch_for_registry;
+    use wow_ui_sim::render::font::WowFontSystem;
+    use wow_ui_sim::render::glyph::GlyphAtlas;
+
+    let env = WowLuaEnv::new().unwrap();
+    env.exec(
+        r#"
+        local f = CreateFrame("ScrollingMessageFrame", "LinkTestMF", UIParent)
+        f:SetPoint("BOTTOMLEFT", 20, 20)
+        f:SetSize(400, 120)
+        f:EnableMouse(true)
+        f:AddMessage("Loot: |cffa335ee|Hitem:19019::::::::80:::::|h[Thunderfury]|h|r")
+        HyperlinkEvents = {}
+        f:SetScript("OnHyperlinkEnter", function(_, link)
+            table.insert(HyperlinkEvents, "enter " .. link)
+        end)
+        f:SetScript("OnHyperlinkLeave", function()
+            table.insert(HyperlinkEvents, "leave")
+        end)
+        f:SetScript("OnHyperlinkClick", function(_, link, text, button)
+            table.insert(HyperlinkEvents, "click " .. link .. " " .. text .. " " .. button)
+        end)
+    "#,
+    )
+    .unwrap();
+
+    let buckets = {
+        let mut state = env.state().borrow_mut();
+        state.ensure_layout_rects();
+        let _ = state.get_strata_buckets();
+        state.strata_buckets.as_ref().unwrap().clone()
+    };
+    let mut fs = WowFontSystem::new(Path::new("./fonts"));
+    let mut ga = GlyphAtlas::new();
+    let (id, frame, regions) = {
+        let mut state = env.state().borrow_mut();
+        let batch = build_quad_batch_for_registry(
+            &state.widgets,
+            (1024.0, 768.0),
+            Some("LinkTestMF"),
+            None,
+            None,
+            Some((&mut fs, &mut ga)),
+            Some(&state.message_frames),
+            None,
+            &buckets,
+        );
+        state.set_hyperlink_regions(batch.hyperlinks);
+        let id = state.widgets.get_id_by_name("LinkTestMF").unwrap();
+        let regions = state.hyperlink_regions.get(&id).cloned().unwrap_or_default();
+        (id, state.widgets.get(id).unwrap().layout_rect.unwrap(), regions)
+    };
+    assert_eq!(regions.len(), 1, "one link on one line: {regions:?}");
+    let link = regions[0].rect;
+    assert!(link.x > frame.x && link.width > 0.0, "link starts after 'Loot: ': {link:?}");
+
+    // A click on the plain text before the link hits the frame but no link.
+    let y = link.y + link.height / 2.0;
+    env.send_mouse_down(frame.x + 1.0, y, "LeftButton").unwrap();
+    env.send_mouse_up(frame.x + 1.0, y, "LeftButton").unwrap();
+
+    let x = link.x + link.width / 2.0;
+    env.send_mouse_move(x, y).unwrap();
+    env.send_mouse_down(x, y, "LeftButton").unwrap();
+    env.send_mouse_up(x, y, "LeftButton").unwrap();
+    env.send_mouse_move(frame.x + 1.0, y).unwrap();
+
+    let events: String = env.eval("return table.concat(HyperlinkEvents, '; ')").unwrap();
+    assert_eq!(
+        events,
+        "enter item:19019::::::::80:::::; \
+         click item:19019::::::::80::::: |Hitem:19019::::::::80:::::|h[Thunderfury]|h LeftButton; \
+         leave"
+    );
+    // A new message scrolls the drawn lines, so the old link rects go away
+    // until the frame is drawn again.
+    env.exec("LinkTestMF:AddMessage('Next line')").unwrap();
+    assert!(env.hyperlink_at(id, x, y).is_none());
+}
@@ -1,5 +1,6 @@
 //! Tests for methods_anchor.rs: SetPoint, ClearAllPoints, GetPoint, GetNumPoints,
-//! SetAllPoints, AdjustPointsOffset, GetPointByName.
+//! SetAllPoints, AdjustPointsOffset, GetPointByName; and the layout queries
+//! GetRect/GetCenter/GetBoundsRect.
 
 use wow_ui_sim::lua_api::WowLuaEnv;
 
@@ -94,6 +95,37 @@ fn test_clear_all_points() {
     assert_eq!(num, 0);
 }
 
+#[test]
+fn test_clear_then_set_point_starts_fresh() {
+    let env = env();
+    let (before, after, point, width): (i32, i32, String, f64) = env.eval(r#"
+        local f = CreateFrame("Frame", "AnchorReclear", UIParent)
+        f:SetSize(100, 50)
+        f:SetPoint("TOPLEFT", UIParent, "TOPLEFT", 0, 0)
+        f:SetPoint("BOTTOMRIGHT", UIParent, "BOTTOMRIGHT", 0, 0)
+        local before = f:GetNumPoints()
+        f:ClearAllPoints()
+        f:SetPoint("CENTER", UIParent, "CENTER", 5, 5)
+        local point = f:GetPoint(1)
+        return before, f:GetNumPoints(), point, f:GetWidth()
+    "#).unwrap();
+    assert_eq!((before, after, point.as_str()), (2, 1, "CENTER"));
+    assert_eq!(width, 100.0, "the cleared TOPLEFT/BOTTOMRIGHT pair must not stretch the frame");
+}
+
+#[test]
+fn test_set_point_relative_frame_with_offsets() {
+    let env = env();
+    let (point, rel_is_parent, rel_point, x, y): (String, bool, String, f64, f64) = env.eval(r#"
+        local f = CreateFrame("Frame", "AnchorShortForm", UIParent)
+        f:SetPoint("TOPLEFT", UIParent, 7, -3)
+        local point, relTo, relPoint, x, y = f:GetPoint(1)
+        return point, relTo == UIParent, relPoint, x, y
+    "#).unwrap();
+    assert_eq!((point.as_str(), rel_is_parent, rel_point.as_str()), ("TOPLEFT", true, "TOPLEFT"));
+    assert_eq!((x, y), (7.0, -3.0));
+}
+
 // ============================================================================
 // SetAllPoints
 // ============================================================================
@@ -122,6 +154,28 @@ fn test_set_all_points_offsets_zero() {
     assert_eq!(y1, 0.0);
 }
 
+#[test]
+fn test_set_all_points_replaces_existing_anchors() {
+    let env = env();
+    let summary: String = env.eval(r#"
+        local target = CreateFrame("Frame", "AnchorAllTarget", UIParent)
+        local f = CreateFrame("Frame", "AnchorAllReplace", UIParent)
+        f:SetPoint("CENTER")
+        f:SetPoint("LEFT", UIParent, "LEFT", 3, 0)
+        f:SetAllPoints("AnchorAllTarget")
+        local parts = { f:GetNumPoints() }
+        for i = 1, f:GetNumPoints() do
+            local point, relTo, relPoint = f:GetPoint(i)
+            table.insert(parts, point .. ">" .. relTo:GetName() .. "." .. relPoint)
+        end
+        return table.concat(parts, " ")
+    "#).unwrap();
+    assert_eq!(
+        summary,
+        "2 TOPLEFT>AnchorAllTarget.TOPLEFT BOTTOMRIGHT>AnchorAllTarget.BOTTOMRIGHT"
+    );
+}
+
 // ============================================================================
 // AdjustPointsOffset
 // ============================================================================
@@ -213,3 +267,188 @@ fn test_get_num_points_default_zero() {
     "#).unwrap();
     assert_eq!(num, 0);
 }
+
+// ============================================================================
+// Multi-anchor layout
+// ============================================================================
+
+#[test]
+fn test_left_right_anchors_derive_width_only() {
+    let env = env();
+    let (left, width, height): (f64, f64, f64) = env.eval(r#"
+        local f = CreateFrame("Frame", "AnchorSpanLR", UIParent)
+        f:SetHeight(30)
+        f:SetPoint("LEFT", UIParent, "LEFT", 100, 0)
+        f:SetPoint("RIGHT", UIParent, "RIGHT", -100, 0)
+        local l, _, w, h = f:GetRect()
+        return l, w, h
+    "#).unwrap();
+    assert_eq!(left, 100.0);
+    assert_eq!(width, 1400.0);
+    assert_eq!(height, 30.0);
+}
+
+#[test]
+fn test_top_bottom_anchors_derive_height_only() {
+    let env = env();
+    let (bottom, width, height): (f64, f64, f64) = env.eval(r#"
+        local f = CreateFrame("Frame", "AnchorSpanTB", UIParent)
+        f:SetWidth(50)
+        f:SetPoint("TOP", UIParent, "TOP", 0, -200)
+        f:SetPoint("BOTTOM", UIParent, "BOTTOM", 0, 100)
+        local _, b, w, h = f:GetRect()
+        return b, w, h
+    "#).unwrap();
+    assert_eq!(bottom, 100.0);
+    assert_eq!(width, 50.0);
+    assert_eq!(height, 900.0);
+}
+
+// ============================================================================
+// GetRect / GetCenter / GetBoundsRect
+// ============================================================================
+
+#[test]
+fn test_get_center_of_centered_frame() {
+    let env = env();
+    env.set_screen_size(1600.0, 1200.0);
+    let (cx, cy): (f64, f64) = env.eval(r#"
+        local f = CreateFrame("Frame", "CenteredQuery", UIParent)
+        f:SetSize(200, 100)
+        f:SetPoint("CENTER")
+        return f:GetCenter()
+    "#).unwrap();
+    assert_eq!((cx, cy), (800.0, 600.0));
+}
+
+#[test]
+fn test_get_rect_follows_moved_anchor() {
+    let env = env();
+    env.set_screen_size(1600.0, 1200.0);
+    let rect: (f64, f64, f64, f64) = env.eval(r#"
+        local f = CreateFrame("Frame", "MovedQuery", UIParent)
+        f:SetSize(200, 100)
+        f:SetPoint("CENTER")
+        f:GetRect()
+        f:ClearAllPoints()
+        f:SetPoint("BOTTOMLEFT", 10, 20)
+        return f:GetRect()
+    "#).unwrap();
+    assert_eq!(rect, (10.0, 20.0, 200.0, 100.0));
+}
+
+#[test]
+fn test_get_bounds_rect_includes_shown_children() {
+    let env = env();
+    env.set_screen_size(1600.0, 1200.0);
+    let rect: (f64, f64, f64, f64) = env.eval(r#"
+        local f = CreateFrame("Frame", "BoundsQuery", UIParent)
+        f:SetSize(100, 100)
+        f:SetPoint("BOTTOMLEFT", 100, 100)
+        local child = CreateFrame("Frame", nil, f)
+        child:SetSize(50, 50)
+        child:SetPoint("TOPLEFT", f, "TOPRIGHT", 0, 0)
+        local hidden = CreateFrame("Frame", nil, f)
+        hidden:SetSize(500, 500)
+        hidden:SetPoint("CENTER")
+        hidden:Hide()
+        return f:GetBoundsRect()
+    "#).unwrap();
+    assert_eq!(rect, (100.0, 100.0, 150.0, 100.0));
+}
+
+// ============================================================================
+// Anchor cycles
+// ============================================================================
+
+#[test]
+fn test_two_frame_anchor_cycle_lays_out_with_warning() {
+    use wow_ui_sim::iced_app::compute_frame_rect;
+    use wow_ui_sim::lua_api::take_anchor_cycle_warnings;
+    use wow_ui_sim::widget::{Anchor, AnchorPoint};
+
+    let env = env();
+    env.exec(r#"
+        local a = CreateFrame("Frame", "CycleA", UIParent)
+        a:SetSize(40, 40)
+        local b = CreateFrame("Frame", "CycleB", UIParent)
+        b:SetSize(40, 40)
+        b:SetPoint("TOPLEFT", a, "BOTTOMLEFT")
+    "#).unwrap();
+    take_anchor_cycle_warnings();
+
+    // Close the loop behind SetPoint's cycle check, as a malformed layout can.
+    let mut state = env.state().borrow_mut();
+    let a = state.widgets.get_id_by_name("CycleA").unwrap();
+    let b = state.widgets.get_id_by_name("CycleB").unwrap();
+    state.widgets.get_mut(a).unwrap().anchors.push(Anchor {
+        point: AnchorPoint::TopLeft,
+        relative_to_id: Some(b as usize),
+        relative_point: AnchorPoint::BottomLeft,
+        ..Anchor::default()
+    });
+
+    let rect = compute_frame_rect(&state.widgets, a, 1024.0, 768.0);
+    assert!(rect.x.is_finite() && rect.y.is_finite());
+    state.invalidate_layout_with_dependents(a);
+    state.ensure_layout_rects();
+    drop(state);
+
+    let warnings = take_anchor_cycle_warnings();
+    assert_eq!(warnings.len(), 1, "each cycle is reported once: {warnings:?}");
+    assert!(warnings[0].contains("CycleA") && warnings[0].contains("CycleB"), "{warnings:?}");
+}
+
+#[test]
+fn test_anchoring_a_frame_to_its_own_child_does_not_recurse() {
+    use wow_ui_sim::lua_api::take_anchor_cycle_warnings;
+
+    let env = env();
+    take_anchor_cycle_warnings();
+    // The child's rect depends on its parent, so this loops through the parent link.
+    let width: f64 = env.eval(r#"
+        local parent = CreateFrame("Frame", "CycleParent", UIParent)
+        parent:SetSize(80, 30)
+        local child = CreateFrame("Frame", "CycleChild", parent)
+        child:SetSize(10, 10)
+        child:SetPoint("CENTER")
+        parent:SetPoint("CENTER", child, "CENTER")
+        return parent:GetWidth()
+    "#).unwrap();
+    assert_eq!(width, 80.0);
+    let warnings = take_anchor_cycle_warnings();
+    assert!(
+        warnings.iter().any(|w| w.contains("CycleParent") && w.contains("CycleChild")),
+        "{warnings:?}"
+    );
+}
+
+#[test]
+fn test_anchor_cycle_during_load_is_a_load_warning() {
+    let dir = tempfile::tempdir().unwrap();
+    let addon_dir = dir.path().join("CycleAddon");
+    std::fs::create_dir(&addon_dir).unwrap();
+    std::fs::write(addon_dir.join("CycleAddon.toc"), "## Interface: 110000\nCycleAddon.lua\n").unwrap();
+    std::fs::write(
+        addon_dir.join("CycleAddon.lua"),
+        r#"
+        local parent = CreateFrame("Frame", "LoadCycleParent", UIParent)
+        parent:SetSize(80, 30)
+        local child = CreateFrame("Frame", "LoadCycleChild", parent)
+        child:SetSize(10, 10)
+        child:SetPoint("CENTER")
+        parent:SetPoint("CENTER", child, "CENTER")
+        parent:GetWidth()
+        "#,
+    )
+    .unwrap();
+
+    let env = env();
+    let result = wow_ui_sim::loader::load_addon(&env.loader_env(), &addon_dir.join("CycleAddon.toc")).unwrap();
+    assert!(
+        result.warnings.iter().any(|w| w.contains("anchor cycle") && w.contains("LoadCycleParent")),
+        "{:?}",
+        result.warnings
+    );
+    assert!(wow_ui_sim::lua_api::take_anchor_cycle_warnings().is_empty(), "drained by the loader");
+}
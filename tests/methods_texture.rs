@@ -3,8 +3,8 @@
 //! Covers: SetTexture, GetTexture, SetTexCoord, SetVertexColor, GetVertexColor,
 //! SetColorTexture, SetAtlas, GetAtlas, SetBlendMode, GetBlendMode,
 //! SetHorizTile, GetHorizTile, SetVertTile, GetVertTile, SetDrawLayer, GetDrawLayer,
-//! SetDesaturated, IsDesaturated, mask textures, pixel grid, texel snapping,
-//! and nine-slice stub methods.
+//! SetDesaturated, IsDesaturated, SetGradient, SetGradientAlpha, mask textures,
+//! pixel grid, texel snapping, and nine-slice stub methods.
 
 use wow_ui_sim::lua_api::WowLuaEnv;
 
@@ -271,6 +271,28 @@ fn test_set_tex_coord_with_atlas_partial() {
     assert!((coords.3 - 1.0).abs() < 0.001);
 }
 
+#[test]
+fn test_set_tex_coord_8_args_round_trips() {
+    let env = env();
+    env.exec(
+        r#"
+        local frame = CreateFrame("Frame", "TC8Frame", UIParent)
+        local tex = frame:CreateTexture("TC8Tex", "BACKGROUND")
+        tex:SetTexCoord(0, 1, 1, 1, 0, 0, 1, 0)
+    "#,
+    )
+    .unwrap();
+
+    let coords: (f32, f32, f32, f32, f32, f32, f32, f32) =
+        env.eval("return TC8Tex:GetTexCoord()").unwrap();
+    assert_eq!(coords, (0.0, 1.0, 1.0, 1.0, 0.0, 0.0, 1.0, 0.0));
+
+    // Bounding box is still recorded for code paths that need a plain rect.
+    let state = env.state().borrow();
+    let id = state.widgets.get_id_by_name("TC8Tex").unwrap();
+    assert_eq!(state.widgets.get(id).unwrap().tex_coords, Some((0.0, 1.0, 0.0, 1.0)));
+}
+
 // ============================================================================
 // SetHorizTile / GetHorizTile / SetVertTile / GetVertTile
 // ============================================================================
@@ -440,6 +462,58 @@ fn test_set_atlas_unknown() {
     );
 }
 
+#[test]
+fn test_set_atlas_use_atlas_size() {
+    let env = env();
+    env.exec(
+        r#"
+        local frame = CreateFrame("Frame", "AtlasSizeFrame", UIParent)
+        local tex = frame:CreateTexture("AtlasSizeTex", "BACKGROUND")
+        tex:SetSize(1, 1)
+        tex:SetAtlas("checkbox-minimal", true)
+    "#,
+    )
+    .unwrap();
+
+    let lookup = wow_ui_sim::atlas::get_atlas_info("checkbox-minimal").unwrap();
+    let (w, h): (f32, f32) = env.eval("return AtlasSizeTex:GetSize()").unwrap();
+    assert_eq!((w, h), (lookup.width() as f32, lookup.height() as f32));
+}
+
+#[test]
+fn test_set_atlas_resets_8_arg_tex_coord() {
+    let env = env();
+    env.exec(
+        r#"
+        local frame = CreateFrame("Frame", "AtlasResetFrame", UIParent)
+        local tex = frame:CreateTexture("AtlasResetTex", "BACKGROUND")
+        tex:SetTexCoord(1, 0, 0, 0, 1, 1, 0, 1)
+        tex:SetAtlas("checkbox-minimal")
+    "#,
+    )
+    .unwrap();
+
+    let state = env.state().borrow();
+    let id = state.widgets.get_id_by_name("AtlasResetTex").unwrap();
+    assert!(state.widgets.get(id).unwrap().tex_coords_quad.is_none());
+}
+
+#[test]
+fn test_set_status_bar_atlas_applies_to_bar_texture() {
+    let env = env();
+    env.exec(
+        r#"
+        local bar = CreateFrame("StatusBar", "AtlasStatusBar", UIParent)
+        bar:SetSize(100, 10)
+        bar:SetStatusBarAtlas("checkbox-minimal")
+    "#,
+    )
+    .unwrap();
+
+    let atlas: String = env.eval("return AtlasStatusBar:GetStatusBarTexture():GetAtlas()").unwrap();
+    assert_eq!(atlas, "checkbox-minimal");
+}
+
 #[test]
 fn test_get_atlas_default_nil() {
     let env = env();
@@ -471,6 +545,24 @@ fn test_get_atlas_returns_name() {
     assert_eq!(name, "checkbox-minimal");
 }
 
+#[test]
+fn test_set_texture_clears_atlas() {
+    let env = env();
+    let (is_nil, texture): (bool, String) = env
+        .eval(
+            r#"
+            local frame = CreateFrame("Frame", "AtlasClearFrame", UIParent)
+            local tex = frame:CreateTexture("AtlasClearTex", "BACKGROUND")
+            tex:SetAtlas("QuestNormal")
+            tex:SetTexture("Interface\\Buttons\\WHITE8X8")
+            return tex:GetAtlas() == nil, tex:GetTexture()
+            "#,
+        )
+        .unwrap();
+    assert!(is_nil, "SetTexture should replace the atlas");
+    assert_eq!(texture, r"Interface\Buttons\WHITE8X8");
+}
+
 // ============================================================================
 // SetAtlas - button parent propagation
 // ============================================================================
@@ -646,6 +738,46 @@ fn test_get_mask_texture_nil() {
     assert!(is_nil);
 }
 
+#[test]
+fn test_get_mask_texture_returns_added_mask() {
+    let env = env();
+    let (first, second): (bool, bool) = env
+        .eval(
+            r#"
+            local frame = CreateFrame("Frame", "MaskGetFrame", UIParent)
+            local tex = frame:CreateTexture("MaskGetTex", "BACKGROUND")
+            local mask = frame:CreateMaskTexture("MaskGetMask", "BACKGROUND")
+            tex:AddMaskTexture(mask)
+            return tex:GetMaskTexture(1) == mask, tex:GetMaskTexture(2) == nil
+            "#,
+        )
+        .unwrap();
+    assert!(first);
+    assert!(second);
+}
+
+#[test]
+fn test_set_mask_stores_and_clears_mask_file() {
+    let env = env();
+    env.exec(
+        r#"
+        local frame = CreateFrame("Frame", "SetMaskFrame", UIParent)
+        SetMaskTex = frame:CreateTexture("SetMaskTex", "ARTWORK")
+        SetMaskTex:SetMask("Interface\\CharacterFrame\\TempPortraitAlphaMask")
+        "#,
+    )
+    .unwrap();
+    let mask_file = |env: &WowLuaEnv| {
+        let state = env.state().borrow();
+        let id = state.widgets.get_id_by_name("SetMaskTex").unwrap();
+        state.widgets.get(id).unwrap().mask_file.clone()
+    };
+    assert_eq!(mask_file(&env).as_deref(), Some("Interface\\CharacterFrame\\TempPortraitAlphaMask"));
+
+    env.exec("SetMaskTex:SetMask(nil)").unwrap();
+    assert_eq!(mask_file(&env), None);
+}
+
 // ============================================================================
 // SetDrawLayer / GetDrawLayer
 // ============================================================================
@@ -681,20 +813,69 @@ fn test_set_draw_layer_no_error() {
 }
 
 // ============================================================================
-// SetGradient / SetCenterColor stubs
+// SetGradient / SetGradientAlpha / SetCenterColor
 // ============================================================================
 
+fn gradient_of(env: &WowLuaEnv, name: &str) -> wow_ui_sim::widget::Gradient {
+    let state = env.state().borrow();
+    let id = state.widgets.get_id_by_name(name).unwrap();
+    state.widgets.get(id).unwrap().gradient.expect("gradient should be set")
+}
+
 #[test]
-fn test_set_gradient_no_error() {
+fn test_set_gradient_color_tables() {
+    use wow_ui_sim::widget::{Color, GradientOrientation};
     let env = env();
     env.exec(
         r#"
         local frame = CreateFrame("Frame", "GradFrame", UIParent)
         local tex = frame:CreateTexture("GradTex", "BACKGROUND")
-        tex:SetGradient("HORIZONTAL", {r=1, g=0, b=0, a=1}, {r=0, g=0, b=1, a=1})
+        tex:SetGradient("HORIZONTAL", {r=1, g=0, b=0, a=1}, {r=0, g=0, b=1, a=0.5})
     "#,
     )
     .unwrap();
+
+    let g = gradient_of(&env, "GradTex");
+    assert_eq!(g.orientation, GradientOrientation::Horizontal);
+    assert_eq!(g.min_color, Color::new(1.0, 0.0, 0.0, 1.0));
+    assert_eq!(g.max_color, Color::new(0.0, 0.0, 1.0, 0.5));
+}
+
+#[test]
+fn test_set_gradient_legacy_components() {
+    use wow_ui_sim::widget::{Color, GradientOrientation};
+    let env = env();
+    env.exec(
+        r#"
+        local frame = CreateFrame("Frame", "GradLegacyFrame", UIParent)
+        local tex = frame:CreateTexture("GradLegacyTex", "BACKGROUND")
+        tex:SetGradient("VERTICAL", 0.1, 0.2, 0.3, 0.4, 0.5, 0.6)
+    "#,
+    )
+    .unwrap();
+
+    let g = gradient_of(&env, "GradLegacyTex");
+    assert_eq!(g.orientation, GradientOrientation::Vertical);
+    assert_eq!(g.min_color, Color::new(0.1, 0.2, 0.3, 1.0));
+    assert_eq!(g.max_color, Color::new(0.4, 0.5, 0.6, 1.0));
+}
+
+#[test]
+fn test_set_gradient_alpha() {
+    use wow_ui_sim::widget::Color;
+    let env = env();
+    env.exec(
+        r#"
+        local frame = CreateFrame("Frame", "GradAlphaFrame", UIParent)
+        local tex = frame:CreateTexture("GradAlphaTex", "BACKGROUND")
+        tex:SetGradientAlpha("VERTICAL", 0, 0, 0, 0, 1, 1, 1, 0.75)
+    "#,
+    )
+    .unwrap();
+
+    let g = gradient_of(&env, "GradAlphaTex");
+    assert_eq!(g.min_color, Color::new(0.0, 0.0, 0.0, 0.0));
+    assert_eq!(g.max_color, Color::new(1.0, 1.0, 1.0, 0.75));
 }
 
 #[test]
@@ -159,6 +159,18 @@ fn micro_menu_game_menu_button_opens_game_menu() {
     );
 }
 
+#[test]
+fn micro_menu_input_script_click_opens_game_menu() {
+    use wow_ui_sim::input::{parse_input_script, run_input_script};
+    let env = setup_env();
+    let commands = parse_input_script("click MainMenuMicroButton").unwrap();
+    run_input_script(&env, &commands).expect("input script failed");
+    assert!(
+        frame_is_shown(&env, "GameMenuFrame"),
+        "GameMenuFrame should be shown after an input-script click on MainMenuMicroButton"
+    );
+}
+
 #[test]
 fn micro_menu_professions_button_loads_and_opens_panel() {
     let env = setup_env();
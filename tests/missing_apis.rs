@@ -23,7 +23,6 @@ fn test_strsplit() {
 
 /// SlashCmdList is a global table for registering slash commands.
 #[test]
-#[ignore = "SlashCmdList not implemented"]
 fn test_slash_command_registration() {
     let env = WowLuaEnv::new().unwrap();
 
@@ -39,8 +38,9 @@ fn test_slash_command_registration() {
     )
     .unwrap();
 
-    // Would need a way to execute slash commands
-    // env.execute_slash_command("/mycommand test");
+    assert!(env.dispatch_slash_command("/mycommand test").unwrap());
+    let msg: String = env.eval("return _G.cmdExecuted and _G.cmdMsg").unwrap();
+    assert_eq!(msg, "test");
 }
 
 /// C_Timer.After is used for delayed execution.
@@ -239,6 +239,34 @@ fn test_get_build_info() {
     assert!(!version.is_empty());
 }
 
+/// The interface number and WOW_PROJECT_ID agree with the flavor TOCs load for.
+#[test]
+fn test_build_info_matches_toc_flavor() {
+    use wow_ui_sim::toc::{client_interface, TocFlavor};
+    let env = WowLuaEnv::new().unwrap();
+
+    let (version, interface, project, expansion): (String, u32, i32, i32) = env
+        .eval(
+            r#"
+        local version, _, _, interface = GetBuildInfo()
+        return version, interface, WOW_PROJECT_ID, GetClassicExpansionLevel()
+        "#,
+        )
+        .unwrap();
+
+    assert_eq!(interface, client_interface());
+    let flavor = TocFlavor::from_interface(interface);
+    assert_eq!(flavor, TocFlavor::current());
+    assert_eq!(project, flavor.project_id());
+    assert_eq!(expansion, flavor.expansion_level());
+    let major: u32 = version.split('.').next().unwrap().parse().unwrap();
+    assert_eq!(major, interface / 10000);
+    if std::env::var("WOW_SIM_INTERFACE").is_err() {
+        let is_mainline: bool = env.eval("return WOW_PROJECT_ID == WOW_PROJECT_MAINLINE").unwrap();
+        assert!(is_mainline);
+    }
+}
+
 /// Settings API for modern addon options.
 #[test]
 #[ignore = "Settings API not implemented"]
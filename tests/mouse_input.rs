@@ -0,0 +1,242 @@
+//! Tests for headless mouse input (mouse_dispatch.rs): hit testing and
+//! OnEnter/OnLeave/OnMouseDown/OnMouseUp/OnClick dispatch.
+
+use wow_ui_sim::lua_api::WowLuaEnv;
+use wow_ui_sim::widget::Frame;
+
+/// Env with a mouse-enabled button "Back" and a smaller "Front" button in a
+/// higher strata overlapping its center. Every mouse script appends
+/// "<Name>:<Handler>" to `MouseLog`.
+fn env_with_buttons() -> WowLuaEnv {
+    let env = WowLuaEnv::new().expect("create env");
+    env.set_screen_size(1024.0, 768.0);
+    env.exec(
+        r#"
+        MouseLog = {}
+        local function track(frame)
+            for _, h in ipairs({ "OnEnter", "OnLeave", "OnMouseDown", "OnMouseUp", "OnClick" }) do
+                frame:SetScript(h, function(self)
+                    table.insert(MouseLog, self:GetName() .. ":" .. h)
+                end)
+            end
+        end
+        local back = CreateFrame("Button", "Back", UIParent)
+        back:SetSize(200, 200)
+        back:SetPoint("TOPLEFT", 100, -100)
+        track(back)
+        local front = CreateFrame("Button", "Front", UIParent)
+        front:SetSize(50, 50)
+        front:SetPoint("CENTER", back, "CENTER")
+        front:SetFrameStrata("HIGH")
+        track(front)
+    "#,
+    )
+    .unwrap();
+    env
+}
+
+fn center_of(env: &WowLuaEnv, name: &str) -> (f32, f32) {
+    let mut state = env.state().borrow_mut();
+    state.ensure_layout_rects();
+    let id = state.widgets.get_id_by_name(name).unwrap();
+    let r = state.widgets.get(id).unwrap().layout_rect.unwrap();
+    (r.x + r.width / 2.0, r.y + r.height / 2.0)
+}
+
+fn take_log(env: &WowLuaEnv) -> String {
+    env.eval("local s = table.concat(MouseLog, ','); MouseLog = {}; return s")
+        .unwrap()
+}
+
+fn click(env: &WowLuaEnv, (x, y): (f32, f32)) {
+    env.send_mouse_down(x, y, "LeftButton").unwrap();
+    env.send_mouse_up(x, y, "LeftButton").unwrap();
+}
+
+#[test]
+fn test_hover_fires_enter_and_leave_on_transitions() {
+    let env = env_with_buttons();
+    let (cx, cy) = center_of(&env, "Back");
+
+    env.send_mouse_move(cx - 80.0, cy).unwrap();
+    env.send_mouse_move(cx - 70.0, cy).unwrap();
+    assert_eq!(take_log(&env), "Back:OnEnter");
+
+    env.send_mouse_move(cx, cy).unwrap();
+    assert_eq!(take_log(&env), "Back:OnLeave,Front:OnEnter");
+
+    env.send_mouse_move(-10.0, -10.0).unwrap();
+    assert_eq!(take_log(&env), "Front:OnLeave");
+    assert_eq!(env.state().borrow().hovered_frame, None);
+}
+
+#[test]
+fn test_click_goes_to_topmost_frame() {
+    let env = env_with_buttons();
+
+    click(&env, center_of(&env, "Front"));
+
+    assert_eq!(take_log(&env), "Front:OnMouseDown,Front:OnClick,Front:OnMouseUp");
+}
+
+#[test]
+fn test_click_disabled_frame_passes_clicks_but_keeps_hover() {
+    let env = env_with_buttons();
+    env.exec("Front:SetMouseClickEnabled(false)").unwrap();
+    let center = center_of(&env, "Front");
+
+    click(&env, center);
+    assert_eq!(take_log(&env), "Back:OnMouseDown,Back:OnClick,Back:OnMouseUp");
+
+    env.send_mouse_move(center.0, center.1).unwrap();
+    assert_eq!(take_log(&env), "Front:OnEnter");
+}
+
+#[test]
+fn test_enable_mouse_false_ignores_frame() {
+    let env = env_with_buttons();
+    env.exec("Front:EnableMouse(false)").unwrap();
+    let (x, y) = center_of(&env, "Front");
+
+    let motion = env.frame_at(x, y, Frame::accepts_mouse_motion);
+    let back = env.state().borrow().widgets.get_id_by_name("Back");
+    assert_eq!(motion, back);
+}
+
+#[test]
+fn test_hit_rect_insets_shrink_hit_area() {
+    let env = env_with_buttons();
+    env.exec("Back:SetHitRectInsets(20, 0, 0, 0)").unwrap();
+    let (cx, cy) = center_of(&env, "Back");
+
+    // 10 units in from Back's left edge: inside the rect, but inside the inset.
+    assert_eq!(env.frame_at(cx - 90.0, cy, Frame::accepts_mouse_clicks), None);
+    assert!(env.frame_at(cx - 70.0, cy, Frame::accepts_mouse_clicks).is_some());
+}
+
+#[test]
+fn test_click_requires_release_on_pressed_frame() {
+    let env = env_with_buttons();
+    let (bx, by) = center_of(&env, "Back");
+    let (fx, fy) = center_of(&env, "Front");
+
+    env.send_mouse_down(bx - 80.0, by, "LeftButton").unwrap();
+    env.send_mouse_up(fx, fy, "LeftButton").unwrap();
+
+    assert_eq!(take_log(&env), "Back:OnMouseDown,Front:OnMouseUp");
+}
+
+#[test]
+fn test_mouse_focus_and_is_mouse_over_follow_cursor() {
+    let env = env_with_buttons();
+    let no_cursor: bool = env.eval("return Back:IsMouseOver()").unwrap();
+    assert!(!no_cursor, "no cursor position yet");
+
+    let (cx, cy) = center_of(&env, "Back");
+    env.send_mouse_move(cx - 80.0, cy).unwrap();
+
+    let focus: String = env.eval("return GetMouseFocus():GetName()").unwrap();
+    assert_eq!(focus, "Back");
+    let foci: String = env.eval("return GetMouseFoci()[1]:GetName()").unwrap();
+    assert_eq!(foci, "Back");
+    let (back, front): (bool, bool) =
+        env.eval("return Back:IsMouseOver(), Front:IsMouseOver()").unwrap();
+    assert!(back);
+    assert!(!front, "cursor is left of Front");
+
+    // A negative left offset extends Front's rect leftwards over the cursor.
+    let tolerant: bool = env.eval("return Front:IsMouseOver(0, 0, -60, 0)").unwrap();
+    assert!(tolerant);
+}
+
+#[test]
+fn test_click_toggles_check_button_before_on_click() {
+    let env = env_with_buttons();
+    env.exec(
+        r#"
+        local check = CreateFrame("CheckButton", "Check", UIParent)
+        check:SetSize(24, 24)
+        check:SetPoint("TOPLEFT", 400, -400)
+        check:SetScript("OnClick", function(self)
+            table.insert(MouseLog, "OnClick:" .. tostring(self:GetChecked()))
+        end)
+    "#,
+    )
+    .unwrap();
+    let center = center_of(&env, "Check");
+
+    click(&env, center);
+    assert_eq!(take_log(&env), "OnClick:true");
+    click(&env, center);
+    assert_eq!(take_log(&env), "OnClick:false");
+}
+
+#[test]
+fn test_hit_rect_insets_on_all_sides() {
+    let env = env_with_buttons();
+    env.exec(
+        r#"
+        local b = CreateFrame("Button", "Padded", UIParent)
+        b:SetSize(100, 100)
+        b:SetPoint("TOPLEFT", 500, -400)
+        b:SetHitRectInsets(10, 10, 10, 10)
+    "#,
+    )
+    .unwrap();
+    let padded = env.state().borrow().widgets.get_id_by_name("Padded");
+    let (cx, cy) = center_of(&env, "Padded");
+    let (left, top) = (cx - 50.0, cy - 50.0);
+
+    assert_eq!(env.frame_at(left + 5.0, top + 5.0, Frame::accepts_mouse_clicks), None);
+    assert_eq!(env.frame_at(cx, cy, Frame::accepts_mouse_clicks), padded);
+
+    // Negative insets enlarge the hit area past the frame's edges.
+    env.exec("Padded:SetHitRectInsets(-10, -10, -10, -10)").unwrap();
+    assert_eq!(env.frame_at(left - 5.0, top - 5.0, Frame::accepts_mouse_clicks), padded);
+}
+
+#[test]
+fn test_get_cursor_position_uses_bottom_left_origin() {
+    let env = env_with_buttons();
+    env.exec("SetCursorPosition(400, 300)").unwrap();
+    let pos: (f64, f64) = env.eval("return GetCursorPosition()").unwrap();
+    assert_eq!(pos, (400.0, 300.0));
+
+    // Headless moves use top-left UI coordinates; GetCursorPosition flips y.
+    env.send_mouse_move(400.0, 468.0).unwrap();
+    let pos: (f64, f64) = env.eval("return GetCursorPosition()").unwrap();
+    assert_eq!(pos, (400.0, 300.0));
+}
+
+#[test]
+fn test_drag_handler_tracks_cursor_delta() {
+    let env = env_with_buttons();
+    env.exec(
+        r#"
+        Back:SetScript("OnMouseDown", function(self)
+            local x, y = GetCursorPosition()
+            local scale = self:GetEffectiveScale()
+            DragStart = { x / scale, y / scale }
+        end)
+        function DragDelta()
+            local x, y = GetCursorPosition()
+            local scale = Back:GetEffectiveScale()
+            return x / scale - DragStart[1], y / scale - DragStart[2]
+        end
+    "#,
+    )
+    .unwrap();
+    // Press on Back outside Front, then drag in two steps.
+    let (x, y) = (120.0, 120.0);
+    env.send_mouse_move(x, y).unwrap();
+    env.send_mouse_down(x, y, "LeftButton").unwrap();
+
+    env.send_mouse_move(x + 30.0, y + 20.0).unwrap();
+    let delta: (f64, f64) = env.eval("return DragDelta()").unwrap();
+    assert_eq!(delta, (30.0, -20.0), "moving down the screen lowers WoW's y");
+
+    env.send_mouse_move(x + 50.0, y - 60.0).unwrap();
+    let delta: (f64, f64) = env.eval("return DragDelta()").unwrap();
+    assert_eq!(delta, (50.0, 60.0));
+    env.send_mouse_up(x + 50.0, y - 60.0, "LeftButton").unwrap();
+}
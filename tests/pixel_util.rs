@@ -0,0 +1,69 @@
+//! Tests for PixelUtil snapping against the simulated screen.
+//!
+//! PixelUtil is Blizzard's own Lua (PixelUtil.lua in Blizzard_SharedXML); it
+//! derives the pixel grid from GetPhysicalScreenSize and GetEffectiveScale,
+//! so these tests pin that those APIs agree with the layout engine.
+
+mod common;
+
+use common::env_with_shared_xml;
+use wow_ui_sim::lua_api::WowLuaEnv;
+
+/// 1024x768 screen (one UI unit per pixel at scale 1) with `PixelFrame` at scale 0.8.
+fn env() -> WowLuaEnv {
+    let env = env_with_shared_xml();
+    env.set_screen_size(1024.0, 768.0);
+    env.exec(
+        r#"
+        local f = CreateFrame("Frame", "PixelFrame", UIParent)
+        f:SetScale(0.8)
+        f:SetHeight(10)
+        f:SetPoint("TOPLEFT")
+    "#,
+    )
+    .unwrap();
+    env
+}
+
+fn layout_rect(env: &WowLuaEnv, name: &str) -> wow_ui_sim::LayoutRect {
+    let mut state = env.state().borrow_mut();
+    state.ensure_layout_rects();
+    let id = state.widgets.get_id_by_name(name).unwrap();
+    state.widgets.get(id).and_then(|f| f.layout_rect).unwrap()
+}
+
+#[test]
+fn test_set_width_snaps_to_whole_pixels() {
+    let env = env();
+    let factor: f64 = env.eval("return PixelUtil.GetPixelToUIUnitFactor()").unwrap();
+    assert_eq!(factor, 1.0);
+
+    // 20.3 units * 0.8 = 16.24 pixels -> 16 pixels -> 20 units.
+    env.exec("PixelUtil.SetWidth(PixelFrame, 20.3)").unwrap();
+    let width: f64 = env.eval("return PixelFrame:GetWidth()").unwrap();
+    assert!((width - 20.0).abs() < 1e-4, "width {width}");
+    let rect = layout_rect(&env, "PixelFrame");
+    assert!((rect.width - 16.0).abs() < 1e-3, "{rect:?}");
+
+    // minPixels wins over rounding towards zero.
+    env.exec("PixelUtil.SetWidth(PixelFrame, 0.2, 2)").unwrap();
+    let rect = layout_rect(&env, "PixelFrame");
+    assert!((rect.width - 2.0).abs() < 1e-3, "{rect:?}");
+}
+
+#[test]
+fn test_set_point_snaps_offsets_to_whole_pixels() {
+    let env = env();
+    env.exec(
+        r#"
+        PixelUtil.SetSize(PixelFrame, 12.6, 7.4)
+        PixelUtil.SetPoint(PixelFrame, "TOPLEFT", UIParent, "TOPLEFT", 10.6, -5.1)
+    "#,
+    )
+    .unwrap();
+    let rect = layout_rect(&env, "PixelFrame");
+    // 10.6 * 0.8 = 8.48 -> 8 px, 5.1 * 0.8 = 4.08 -> 4 px; sizes 10.08 -> 10, 5.92 -> 6.
+    for (got, want) in [(rect.x, 8.0), (rect.y, 4.0), (rect.width, 10.0), (rect.height, 6.0)] {
+        assert!((got - want).abs() < 1e-3, "{rect:?}");
+    }
+}
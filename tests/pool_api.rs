@@ -69,16 +69,15 @@ fn test_frame_pool_get_num_active() {
 #[test]
 fn test_frame_pool_release_moves_to_inactive() {
     let env = env();
-    // Release moves a frame to inactive but the current implementation
-    // doesn't remove it from active. Test the behavior that does work:
-    // after release + acquire, we should get the released frame back.
     env.exec(r#"
-        local pool = CreateFramePool("Frame", UIParent)
+        local pool = CreateFramePool("Button", UIParent)
         local f1 = pool:Acquire()
-        pool:Release(f1)
-        -- After release, acquiring again should reuse from inactive
-        local f2 = pool:Acquire()
-        assert(f2 ~= nil, "Should reuse released frame")
+        f1:Show()
+        assert(pool:Release(f1), "Release of an active frame should succeed")
+        assert(not pool:IsActive(f1), "Released frame should leave the active set")
+        assert(pool:DoesObjectBelongToPool(f1), "Released frame still belongs to the pool")
+        assert(not f1:IsShown(), "Default reset hides the released frame")
+        assert(pool:GetNumActive() == 0)
     "#).unwrap();
 }
 
@@ -104,9 +103,35 @@ fn test_frame_pool_reuses_released() {
         local f1 = pool:Acquire()
         pool:Release(f1)
         local f2 = pool:Acquire()
-        -- f2 should reuse f1
-        assert(f2 ~= nil, "Should be able to acquire after release")
+        assert(f2 == f1, "Acquire should reuse the released frame")
+    "#).unwrap();
+}
+
+#[test]
+fn test_frame_pool_release_one_of_three() {
+    let env = env();
+    let (enumerated, walked, kind): (i32, i32, String) = env.eval(r#"
+        local pool = CreateFramePool("Button", UIParent)
+        local b1, b2, b3 = pool:Acquire(), pool:Acquire(), pool:Acquire()
+        pool:Release(b2)
+
+        local n = 0
+        for button in pool:EnumerateActive() do
+            assert(button ~= b2, "Released button must not be enumerated")
+            n = n + 1
+        end
+
+        local walked = 0
+        local current = pool:GetNextActive()
+        while current do
+            walked = walked + 1
+            current = pool:GetNextActive(current)
+        end
+        return n, walked, b1:GetObjectType()
     "#).unwrap();
+    assert_eq!(enumerated, 2);
+    assert_eq!(walked, 2);
+    assert_eq!(kind, "Button");
 }
 
 #[test]
@@ -214,33 +239,38 @@ fn test_object_pool_get_num_active() {
 #[test]
 fn test_object_pool_release_all_no_error() {
     let env = env();
-    // ObjectPool.ReleaseAll is currently a stub (no-op)
-    env.exec(r#"
+    let (active, resets): (i32, i32) = env.eval(r#"
+        local resets = 0
         local pool = CreateObjectPool(
             function() return {} end,
-            function(obj) end
+            function(pool, obj, new) if not new then resets = resets + 1 end end
         )
         pool:Acquire()
         pool:Acquire()
         pool:ReleaseAll()
+        return pool:GetNumActive(), resets
     "#).unwrap();
+    assert_eq!(active, 0);
+    assert_eq!(resets, 2);
 }
 
 #[test]
 fn test_object_pool_enumerate_active_stub() {
     let env = env();
-    // ObjectPool.EnumerateActive is currently a stub that returns a no-op iterator
-    env.exec(r#"
+    let count: i32 = env.eval(r#"
         local pool = CreateObjectPool(
             function() return {} end,
             function(obj) end
         )
         pool:Acquire()
         pool:Acquire()
+        local n = 0
         for obj in pool:EnumerateActive() do
-            -- stub iterator returns nil immediately
+            n = n + 1
         end
+        return n
     "#).unwrap();
+    assert_eq!(count, 2);
 }
 
 // ============================================================================
@@ -0,0 +1,131 @@
+//! Tests for the simulated quest log (C_QuestLog + AddQuestToLog/SetQuestComplete).
+
+use wow_ui_sim::lua_api::WowLuaEnv;
+
+/// Env with two injected quests; `QuestLogUpdates` counts QUEST_LOG_UPDATE.
+fn env_with_quests() -> WowLuaEnv {
+    let env = WowLuaEnv::new().unwrap();
+    env.exec(
+        r#"
+        QuestLogUpdates = 0
+        local f = CreateFrame("Frame")
+        f:RegisterEvent("QUEST_LOG_UPDATE")
+        f:SetScript("OnEvent", function() QuestLogUpdates = QuestLogUpdates + 1 end)
+
+        AddQuestToLog({
+            questID = 81001,
+            title = "Boars of the Valley",
+            objectives = {
+                { text = "3/8 Valley Boar slain", type = "monster", numFulfilled = 3, numRequired = 8 },
+                { text = "Speak to the Ranger", type = "event" },
+            },
+        })
+        AddQuestToLog({ questID = 81002, title = "Letter Home", level = 12, watched = false })
+    "#,
+    )
+    .unwrap();
+    env
+}
+
+#[test]
+fn test_injected_quests_replace_mocks_and_are_listed() {
+    let env = WowLuaEnv::new().unwrap();
+    let (shown, watched): (i32, i32) = env
+        .eval("return C_QuestLog.GetNumQuestLogEntries(), C_QuestLog.GetNumQuestWatches()")
+        .unwrap();
+    assert_eq!((shown, watched), (0, 3), "mock quests are watched but not listed");
+
+    let env = env_with_quests();
+    let (shown, total): (i32, i32) = env.eval("return C_QuestLog.GetNumQuestLogEntries()").unwrap();
+    assert_eq!((shown, total), (2, 2));
+    let on_mock: bool = env.eval("return C_QuestLog.IsOnQuest(80000)").unwrap();
+    assert!(!on_mock);
+    let updates: i32 = env.eval("return QuestLogUpdates").unwrap();
+    assert_eq!(updates, 2);
+
+    let (title, level, id, log_index): (String, i32, i32, i32) = env
+        .eval(
+            r#"local info = C_QuestLog.GetInfo(2)
+            return info.title, info.level, info.questID, C_QuestLog.GetLogIndexForQuestID(81002)"#,
+        )
+        .unwrap();
+    assert_eq!((title.as_str(), level, id, log_index), ("Letter Home", 12, 81002, 2));
+    let title: String = env.eval("return C_QuestLog.GetTitleForQuestID(81001)").unwrap();
+    assert_eq!(title, "Boars of the Valley");
+
+    // Only the first quest is watched.
+    let (watches, first): (i32, i32) = env
+        .eval("return C_QuestLog.GetNumQuestWatches(), C_QuestLog.GetQuestIDForQuestWatchIndex(1)")
+        .unwrap();
+    assert_eq!((watches, first), (1, 81001));
+    env.exec("C_QuestLog.AddQuestWatch(81002)").unwrap();
+    let watches: i32 = env.eval("return C_QuestLog.GetNumQuestWatches()").unwrap();
+    assert_eq!(watches, 2);
+}
+
+#[test]
+fn test_objectives_are_shaped_like_the_client() {
+    let env = env_with_quests();
+    let (text, kind, fulfilled, required, finished): (String, String, i32, i32, bool) = env
+        .eval(
+            r#"local o = C_QuestLog.GetQuestObjectives(81001)[1]
+            return o.text, o.type, o.numFulfilled, o.numRequired, o.finished"#,
+        )
+        .unwrap();
+    assert_eq!(
+        (text.as_str(), kind.as_str(), fulfilled, required, finished),
+        ("3/8 Valley Boar slain", "monster", 3, 8, false)
+    );
+
+    let (count, text, kind, finished): (i32, String, String, bool) = env
+        .eval(
+            r#"local n = GetNumQuestLeaderBoards(1)
+            return n, GetQuestLogLeaderBoard(2, 1)"#,
+        )
+        .unwrap();
+    assert_eq!((count, text.as_str(), kind.as_str(), finished), (2, "Speak to the Ranger", "event", false));
+    let missing: bool = env.eval("return GetQuestLogLeaderBoard(3, 1) == nil").unwrap();
+    assert!(missing);
+
+    let (fulfilled, required): (i32, i32) = env
+        .eval("local _, _, _, f, r = GetQuestObjectiveInfo(81001, 1, false); return f, r")
+        .unwrap();
+    assert_eq!((fulfilled, required), (3, 8));
+}
+
+#[test]
+fn test_set_quest_complete_fulfills_objectives() {
+    let env = env_with_quests();
+    let before: bool = env.eval("return C_QuestLog.IsComplete(81001)").unwrap();
+    assert!(!before);
+
+    let found: bool = env.eval("return SetQuestComplete(81001)").unwrap();
+    assert!(found);
+    let (complete, turn_in, finished, fulfilled): (bool, bool, bool, i32) = env
+        .eval(
+            r#"local o = C_QuestLog.GetQuestObjectives(81001)[1]
+            return C_QuestLog.IsComplete(81001), C_QuestLog.ReadyForTurnIn(81001), o.finished, o.numFulfilled"#,
+        )
+        .unwrap();
+    assert_eq!((complete, turn_in, finished, fulfilled), (true, true, true, 8));
+    let updates: i32 = env.eval("return QuestLogUpdates").unwrap();
+    assert_eq!(updates, 3);
+
+    env.exec("SetQuestComplete(81001, false)").unwrap();
+    let complete: bool = env.eval("return C_QuestLog.IsComplete(81001)").unwrap();
+    assert!(!complete);
+    let unknown: bool = env.eval("return SetQuestComplete(99999)").unwrap();
+    assert!(!unknown);
+}
+
+#[test]
+fn test_clear_quest_log_empties_listing_and_watches() {
+    let env = WowLuaEnv::new().unwrap();
+    env.exec("ClearQuestLog()").unwrap();
+    let (shown, watched, info): (i32, i32, bool) = env
+        .eval(
+            "return C_QuestLog.GetNumQuestLogEntries(), C_QuestLog.GetNumQuestWatches(), C_QuestLog.GetInfo(1) == nil",
+        )
+        .unwrap();
+    assert_eq!((shown, watched, info), (0, 0, true));
+}
@@ -0,0 +1,114 @@
+//! Tests for `--seed`: seeded environments produce identical output.
+//!
+//! The seed is process-wide, so this lives in its own test binary and runs
+//! as a single test.
+
+use std::path::Path;
+use wow_ui_sim::dump::build_tree;
+use wow_ui_sim::loader::load_addon;
+use wow_ui_sim::lua_api::{set_random_seed, WowLuaEnv};
+use wow_ui_sim::startup::{fire_headless_startup, settle_headless_startup};
+
+/// Create an environment and lay out a frame whose size and text depend on
+/// `math.random`, then dump its subtree.
+fn seeded_dump() -> (Vec<String>, String) {
+    let env = WowLuaEnv::new().unwrap();
+    env.exec(
+        r#"
+        local f = CreateFrame("Frame", "SeedProbeFrame", UIParent)
+        f:SetSize(math.random(50, 400), math.random(50, 400))
+        f:SetPoint("TOPLEFT", math.random(0, 100), -math.random(0, 100))
+        local label = f:CreateFontString("SeedProbeLabel", "OVERLAY", "GameFontNormal")
+        label:SetText(tostring(math.random(1, 1000000)))
+        label:SetPoint("CENTER")
+        "#,
+    )
+    .unwrap();
+    let tree = {
+        let state = env.state().borrow();
+        build_tree(&state.widgets, Some("SeedProbe"), None, false, 1024.0, 768.0)
+    };
+    let name: String = env.eval("return UnitName('player')").unwrap();
+    let xp: i32 = env.eval("return UnitXP('player')").unwrap();
+    (tree, format!("{name}:{xp}"))
+}
+
+/// Write an addon that lays out frames from `math.random` and `GetTime`
+/// during login, in a timer and in OnUpdate.
+fn write_fixture_addon(dir: &Path) -> std::path::PathBuf {
+    let addon_dir = dir.join("SeedFixture");
+    std::fs::create_dir_all(&addon_dir).unwrap();
+    std::fs::write(addon_dir.join("SeedFixture.toc"), "## Interface: 110000\nSeedFixture.lua\n").unwrap();
+    std::fs::write(
+        addon_dir.join("SeedFixture.lua"),
+        r#"
+        local f = CreateFrame("Frame", "SeedFixtureFrame", UIParent)
+        f:RegisterEvent("PLAYER_LOGIN")
+        f:SetScript("OnEvent", function(self)
+            self:SetSize(math.random(50, 400), math.random(50, 400))
+            self:SetPoint("TOPLEFT", math.random(0, 100), -math.random(0, 100))
+            local label = self:CreateFontString("SeedFixtureLabel", "OVERLAY", "GameFontNormal")
+            label:SetPoint("CENTER")
+            label:SetText(string.format("%d@%.3f", math.random(1, 1000000), GetTime()))
+            C_Timer.After(0.5, function()
+                local late = CreateFrame("Frame", "SeedFixtureLate", self)
+                late:SetSize(math.random(10, 90), 20)
+                late:SetPoint("BOTTOM")
+            end)
+        end)
+        f:SetScript("OnUpdate", function(self)
+            self.ticks = (self.ticks or 0) + 1
+            if self.ticks == 2 then
+                self:SetAlpha(math.random(1, 100) / 100)
+            end
+        end)
+        "#,
+    )
+    .unwrap();
+    addon_dir.join("SeedFixture.toc")
+}
+
+/// Load the fixture addon, run the headless startup like `dump-tree` does
+/// and return the dumped tree as bytes.
+fn seeded_startup_dump(toc: &Path) -> Vec<u8> {
+    let env = WowLuaEnv::new().unwrap();
+    env.set_screen_size(1024.0, 768.0);
+    load_addon(&env.loader_env(), toc).unwrap();
+    env.apply_post_load_workarounds();
+    fire_headless_startup(&env);
+    settle_headless_startup(&env);
+    let state = env.state().borrow();
+    build_tree(&state.widgets, None, None, false, 1024.0, 768.0).join("\n").into_bytes()
+}
+
+#[test]
+fn seeded_runs_are_reproducible() {
+    set_random_seed(Some(42));
+    let first = seeded_dump();
+    let second = seeded_dump();
+
+    assert!(!first.0.is_empty(), "probe frame missing from dump");
+    assert_eq!(first.0.join("\n"), second.0.join("\n"));
+    assert_eq!(first.1, second.1);
+
+    // The full headless startup over an addon gives byte-identical dumps.
+    let dir = tempfile::tempdir().unwrap();
+    let toc = write_fixture_addon(dir.path());
+    let first = seeded_startup_dump(&toc);
+    let second = seeded_startup_dump(&toc);
+    let text = String::from_utf8_lossy(&first);
+    assert!(text.contains("SeedFixtureLabel") && text.contains("SeedFixtureLate"), "fixture frames missing");
+    assert!(first == second, "seeded startup dumps differ");
+
+    // A different seed gives a different math.random sequence.
+    set_random_seed(Some(1));
+    let a: Vec<i32> = WowLuaEnv::new().unwrap()
+        .eval("return {math.random(1, 1000000), math.random(1, 1000000)}")
+        .unwrap();
+    set_random_seed(Some(2));
+    let b: Vec<i32> = WowLuaEnv::new().unwrap()
+        .eval("return {math.random(1, 1000000), math.random(1, 1000000)}")
+        .unwrap();
+    set_random_seed(None);
+    assert_ne!(a, b);
+}
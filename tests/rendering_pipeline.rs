@@ -5,7 +5,7 @@
 //! 2. Texture path resolution (backslash, case-insensitive, atlas DB)
 //! 3. Layout verification (positions from FauxScrollFrameTemplate)
 //! 4. Quad batch generation
-//! 5. GPU atlas upload
+//! 5. GPU atlas upload and headless rendering
 
 mod common;
 
@@ -13,9 +13,13 @@ use std::path::PathBuf;
 
 use common::env_with_shared_xml;
 use wow_ui_sim::atlas::{get_atlas_info, ATLAS_DB};
-use wow_ui_sim::iced_app::{build_quad_batch_for_registry, compute_frame_rect};
+use wow_ui_sim::iced_app::{
+    build_quad_batch_at_time, build_quad_batch_for_registry, compute_frame_rect, strata_buckets_from,
+};
+use wow_ui_sim::render::headless::render_scene;
 use wow_ui_sim::render::{GpuTextureAtlas, QuadBatch};
 use wow_ui_sim::texture::TextureManager;
+use wow_ui_sim::widget::FrameStrata;
 
 // ============================================================================
 // Helpers
@@ -48,6 +52,25 @@ fn build_strata_buckets(env: &wow_ui_sim::lua_api::WowLuaEnv) -> Vec<Vec<u64>> {
     state.strata_buckets.as_ref().unwrap().clone()
 }
 
+/// Lay out `env` and build the quad batch for the subtree of the frame
+/// `name`, without text, on a screen of size `screen`.
+fn batch_for(env: &wow_ui_sim::lua_api::WowLuaEnv, name: &str, screen: (f32, f32)) -> QuadBatch {
+    env.state().borrow_mut().ensure_layout_rects();
+    let buckets = build_strata_buckets(env);
+    let state = env.state().borrow();
+    build_quad_batch_for_registry(
+        &state.widgets,
+        screen,
+        Some(name),
+        None,
+        None,
+        None,
+        None,
+        None,
+        &buckets,
+    )
+}
+
 /// Scroll bar texture paths used by the classic FauxScrollFrameTemplate.
 const SCROLL_UP_BUTTON: &str = "Interface/Buttons/UI-ScrollBar-ScrollUpButton-Up";
 const SCROLL_DOWN_BUTTON: &str = "Interface/Buttons/UI-ScrollBar-ScrollDownButton-Up";
@@ -279,19 +302,7 @@ fn layer4_quad_batch_has_quads_for_scroll_widgets() {
     )
     .unwrap();
 
-    let buckets = build_strata_buckets(&env);
-    let state = env.state().borrow();
-    let batch = build_quad_batch_for_registry(
-        &state.widgets,
-        (1024.0, 768.0),
-        Some("TestSFQuads"),
-        None,
-        None,
-        None,
-        None,
-        None,
-        &buckets,
-    );
+    let batch = batch_for(&env, "TestSFQuads", (1024.0, 768.0));
 
     // Should have at least the background quad + some widget quads
     assert!(
@@ -415,6 +426,792 @@ fn find_vertex_near_rect(batch: &QuadBatch, rect: &wow_ui_sim::LayoutRect) -> bo
     false
 }
 
+#[test]
+fn layer4_vertical_gradient_colors_top_and_bottom() {
+    let env = env_with_shared_xml();
+
+    env.exec(
+        r#"
+        local f = CreateFrame("Frame", "TestGradientFrame", UIParent)
+        f:SetSize(100, 60)
+        f:SetPoint("CENTER")
+        local bg = f:CreateTexture(nil, "BACKGROUND")
+        bg:SetAllPoints(f)
+        bg:SetColorTexture(1, 1, 1, 1)
+        bg:SetGradient("VERTICAL", CreateColor(0, 0, 0, 1), CreateColor(1, 0.5, 0, 1))
+    "#,
+    )
+    .unwrap();
+
+    let batch = batch_for(&env, "TestGradientFrame", (1024.0, 768.0));
+
+    // The marble background is tiled (tex_index -2); the gradient is the only solid quad.
+    let quad = batch.vertices
+        .chunks(4)
+        .find(|q| q[0].tex_index == -1)
+        .expect("gradient texture should emit a solid quad");
+    let (top, bottom) = (&quad[0], &quad[3]); // TL, BL
+    assert_eq!(top.color, [1.0, 0.5, 0.0, 1.0], "top edge should use maxColor");
+    assert_eq!(bottom.color, [0.0, 0.0, 0.0, 1.0], "bottom edge should use minColor");
+}
+
+#[test]
+fn layer4_vertex_color_tints_and_desaturates_quads() {
+    use wow_ui_sim::render::shader::FLAG_DESATURATE;
+
+    let env = env_with_shared_xml();
+    env.exec(
+        r#"
+        local f = CreateFrame("Frame", "TestTintFrame", UIParent)
+        f:SetSize(100, 60)
+        f:SetPoint("CENTER")
+        local icon = f:CreateTexture(nil, "ARTWORK")
+        icon:SetAllPoints(f)
+        icon:SetTexture("Interface\\Icons\\INV_Misc_QuestionMark")
+        icon:SetVertexColor(1, 0.5, 0, 0.5)
+        icon:SetDesaturated(true)
+        local fill = f:CreateTexture(nil, "BACKGROUND")
+        fill:SetAllPoints(f)
+        fill:SetColorTexture(1, 1, 1, 1)
+        fill:SetVertexColor(0.25, 0.5, 1, 0.5)
+    "#,
+    )
+    .unwrap();
+
+    let batch = batch_for(&env, "TestTintFrame", (1024.0, 768.0));
+
+    let icon = batch
+        .texture_requests
+        .iter()
+        .find(|r| r.path.contains("INV_Misc_QuestionMark"))
+        .expect("icon texture request");
+    let icon_quad = &batch.vertices[icon.vertex_start as usize..][..4];
+    assert!(icon_quad.iter().all(|v| v.color == [1.0, 0.5, 0.0, 0.5]), "vertex color tints the icon");
+    assert!(icon_quad.iter().all(|v| v.flags & FLAG_DESATURATE != 0), "icon is drawn greyscale");
+
+    let fill = batch
+        .vertices
+        .chunks(4)
+        .find(|q| q[0].tex_index == -1)
+        .expect("color texture should emit a solid quad");
+    assert_eq!(fill[0].color, [0.25, 0.5, 1.0, 0.5], "vertex color multiplies the solid fill");
+    assert_eq!(fill[0].flags & FLAG_DESATURATE, 0);
+}
+
+#[test]
+fn layer4_parent_alpha_multiplies_into_child_quads() {
+    let env = env_with_shared_xml();
+    env.exec(
+        r#"
+        local outer = CreateFrame("Frame", "TestAlphaOuter", UIParent)
+        outer:SetSize(200, 100)
+        outer:SetPoint("CENTER")
+        outer:SetAlpha(0.5)
+        local fill = outer:CreateTexture(nil, "BACKGROUND")
+        fill:SetSize(40, 40)
+        fill:SetPoint("LEFT")
+        fill:SetColorTexture(1, 0, 0, 1)
+        local inner = CreateFrame("Frame", nil, outer)
+        inner:SetSize(40, 40)
+        inner:SetPoint("RIGHT")
+        inner:SetAlpha(0.5)
+        local nested = inner:CreateTexture(nil, "BACKGROUND")
+        nested:SetAllPoints(inner)
+        nested:SetColorTexture(0, 0, 1, 1)
+    "#,
+    )
+    .unwrap();
+
+    let (outer_eff, inner_eff, clamped): (f32, f32, f32) = env
+        .eval(
+            r#"local inner = select(1, TestAlphaOuter:GetChildren())
+            local probe = CreateFrame("Frame")
+            probe:SetAlpha(3)
+            return TestAlphaOuter:GetEffectiveAlpha(), inner:GetEffectiveAlpha(), probe:GetAlpha()"#,
+        )
+        .unwrap();
+    assert_eq!((outer_eff, inner_eff, clamped), (0.5, 0.25, 1.0));
+
+    let batch = batch_for(&env, "TestAlphaOuter", (1024.0, 768.0));
+
+    let solid_color = |rgb: [f32; 3]| {
+        batch
+            .vertices
+            .chunks(4)
+            .find(|q| q[0].tex_index == -1 && q[0].color[..3] == rgb)
+            .map(|q| q[0].color[3])
+    };
+    assert_eq!(solid_color([1.0, 0.0, 0.0]), Some(0.5), "child of a 0.5 container");
+    assert_eq!(solid_color([0.0, 0.0, 1.0]), Some(0.25), "0.5 inside 0.5");
+}
+
+#[test]
+fn layer4_hidden_frame_keeps_effective_alpha() {
+    let env = env_with_shared_xml();
+    let eff: f32 = env
+        .eval(
+            r#"local f = CreateFrame("Frame", nil, UIParent)
+            f:SetAlpha(0.4)
+            f:Hide()
+            return f:GetEffectiveAlpha()"#,
+        )
+        .unwrap();
+    assert!((eff - 0.4).abs() < 1e-6, "GetEffectiveAlpha ignores visibility, got {eff}");
+}
+
+/// Build a 200x20 StatusBar at `value` out of 0..100 and return the
+/// (min_x, max_x, min_y, max_y) bounds of its bar texture quad, plus the
+/// StatusBar's layout rect.
+fn statusbar_fill_bounds(setup: &str) -> ([f32; 4], wow_ui_sim::LayoutRect) {
+    let env = env_with_shared_xml();
+    env.exec(&format!(
+        r#"
+        local bar = CreateFrame("StatusBar", "TestFillBar", UIParent)
+        bar:SetSize(200, 20)
+        bar:SetPoint("CENTER")
+        bar:SetStatusBarTexture("Interface\\TargetingFrame\\UI-StatusBar")
+        bar:SetMinMaxValues(0, 100)
+        {setup}
+    "#
+    ))
+    .unwrap();
+
+    let batch = batch_for(&env, "TestFillBar", (1024.0, 768.0));
+    let state = env.state().borrow();
+    let id = state.widgets.get_id_by_name("TestFillBar").unwrap();
+    let rect = compute_frame_rect(&state.widgets, id, 1024.0, 768.0);
+    let req = batch
+        .texture_requests
+        .iter()
+        .find(|r| r.path.to_lowercase().contains("ui-statusbar"))
+        .expect("bar texture should emit a textured quad");
+    let start = req.vertex_start as usize;
+    let verts = &batch.vertices[start..start + 4];
+    let xs = verts.iter().map(|v| v.position[0]);
+    let ys = verts.iter().map(|v| v.position[1]);
+    let bounds = [
+        xs.clone().fold(f32::MAX, f32::min),
+        xs.fold(f32::MIN, f32::max),
+        ys.clone().fold(f32::MAX, f32::min),
+        ys.fold(f32::MIN, f32::max),
+    ];
+    (bounds, rect)
+}
+
+#[test]
+fn layer4_statusbar_half_full_horizontal_crops_width() {
+    let ([min_x, max_x, min_y, max_y], rect) = statusbar_fill_bounds("bar:SetValue(50)");
+    assert!((max_x - min_x - 100.0).abs() < 0.5, "half-full bar should be 100px wide, got {}", max_x - min_x);
+    assert!((min_x - rect.x).abs() < 0.5, "fill should start at the left edge");
+    assert!((max_y - min_y - 20.0).abs() < 0.5, "height should be untouched");
+}
+
+#[test]
+fn layer4_statusbar_reverse_fill_anchors_right() {
+    let ([min_x, max_x, _, _], rect) =
+        statusbar_fill_bounds("bar:SetReverseFill(true); bar:SetValue(60)");
+    assert!((max_x - min_x - 120.0).abs() < 0.5, "60% bar should be 120px wide");
+    assert!((max_x - (rect.x + rect.width)).abs() < 0.5, "reverse fill should end at the right edge");
+}
+
+#[test]
+fn layer4_statusbar_vertical_crops_height_from_bottom() {
+    let ([min_x, max_x, min_y, max_y], rect) =
+        statusbar_fill_bounds("bar:SetOrientation('VERTICAL'); bar:SetValue(25)");
+    assert!((max_x - min_x - 200.0).abs() < 0.5, "width should be untouched");
+    assert!((max_y - min_y - 5.0).abs() < 0.5, "25% vertical bar should be 5px tall, got {}", max_y - min_y);
+    assert!((max_y - (rect.y + rect.height)).abs() < 0.5, "vertical fill should grow from the bottom");
+}
+
+/// Solid-color quads (tex_index -1) emitted for `root`, as (min_x, max_x, min_y, max_y).
+fn solid_quad_bounds(env: &wow_ui_sim::lua_api::WowLuaEnv, root: &str) -> Vec<[f32; 4]> {
+    solid_quad_bounds_from(env, root, None)
+}
+
+/// Like [`solid_quad_bounds`], rendering only strata at or above `min_strata`.
+fn solid_quad_bounds_from(
+    env: &wow_ui_sim::lua_api::WowLuaEnv,
+    root: &str,
+    min_strata: Option<FrameStrata>,
+) -> Vec<[f32; 4]> {
+    env.state().borrow_mut().ensure_layout_rects();
+    let mut buckets = build_strata_buckets(env);
+    if let Some(min) = min_strata {
+        buckets = strata_buckets_from(&buckets, min);
+    }
+    let state = env.state().borrow();
+    let batch = build_quad_batch_for_registry(
+        &state.widgets,
+        (1024.0, 768.0),
+        Some(root),
+        None,
+        None,
+        None,
+        None,
+        None,
+        &buckets,
+    );
+    batch
+        .vertices
+        .chunks(4)
+        .filter(|q| q[0].tex_index == -1)
+        .map(|q| {
+            let xs = q.iter().map(|v| v.position[0]);
+            let ys = q.iter().map(|v| v.position[1]);
+            [
+                xs.clone().fold(f32::MAX, f32::min),
+                xs.fold(f32::MIN, f32::max),
+                ys.clone().fold(f32::MAX, f32::min),
+                ys.fold(f32::MIN, f32::max),
+            ]
+        })
+        .collect()
+}
+
+#[test]
+fn layer4_clips_children_culls_child_outside_parent() {
+    let env = env_with_shared_xml();
+    env.exec(
+        r#"
+        local clip = CreateFrame("Frame", "TestClipParent", UIParent)
+        clip:SetSize(100, 100)
+        clip:SetPoint("CENTER")
+        clip:SetClipsChildren(true)
+        local child = CreateFrame("Frame", nil, clip)
+        child:SetSize(40, 40)
+        child:SetPoint("TOPLEFT", clip, "BOTTOMRIGHT", 10, -10)
+        local tex = child:CreateTexture(nil, "ARTWORK")
+        tex:SetAllPoints(child)
+        tex:SetColorTexture(1, 0, 0, 1)
+    "#,
+    )
+    .unwrap();
+
+    assert!(env.eval::<bool>("return TestClipParent:DoesClipChildren()").unwrap());
+    assert!(
+        solid_quad_bounds(&env, "TestClipParent").is_empty(),
+        "child anchored outside a clipping parent should be culled"
+    );
+
+    env.exec("TestClipParent:SetClipsChildren(false)").unwrap();
+    assert_eq!(solid_quad_bounds(&env, "TestClipParent").len(), 1, "unclipped child should render");
+}
+
+#[test]
+fn layer4_clips_children_crops_straddling_child() {
+    let env = env_with_shared_xml();
+    env.exec(
+        r#"
+        local clip = CreateFrame("Frame", "TestCropParent", UIParent)
+        clip:SetSize(100, 100)
+        clip:SetPoint("CENTER")
+        clip:SetClipsChildren(true)
+        local tex = clip:CreateTexture(nil, "ARTWORK")
+        tex:SetSize(100, 60)
+        tex:SetPoint("TOPLEFT", clip, "BOTTOMLEFT", 0, 30)
+        tex:SetColorTexture(0, 1, 0, 1)
+    "#,
+    )
+    .unwrap();
+
+    let quads = solid_quad_bounds(&env, "TestCropParent");
+    assert_eq!(quads.len(), 1);
+    let [_, _, min_y, max_y] = quads[0];
+    assert!((max_y - min_y - 30.0).abs() < 0.5, "only the 30px inside the parent should remain, got {}", max_y - min_y);
+}
+
+#[test]
+fn layer4_min_strata_renders_only_dialog_and_tooltip() {
+    let env = env_with_shared_xml();
+    env.exec(
+        r#"
+        local root = CreateFrame("Frame", "TestStrataRoot", UIParent)
+        root:SetSize(400, 400)
+        root:SetPoint("TOPLEFT", 0, 0)
+        local bg = root:CreateTexture(nil, "BACKGROUND")
+        bg:SetAllPoints(root)
+        bg:SetColorTexture(0, 0, 0, 1)
+        local function box(strata, x, size)
+            local f = CreateFrame("Frame", nil, root)
+            f:SetFrameStrata(strata)
+            f:SetSize(size, size)
+            f:SetPoint("TOPLEFT", root, "TOPLEFT", x, -10)
+            local t = f:CreateTexture(nil, "ARTWORK")
+            t:SetAllPoints(f)
+            t:SetColorTexture(1, 1, 1, 1)
+        end
+        box("HIGH", 10, 20)
+        box("DIALOG", 100, 50)
+        box("TOOLTIP", 200, 30)
+    "#,
+    )
+    .unwrap();
+    // Same strata outside the --filter subtree must stay excluded.
+    env.exec(
+        r#"
+        local other = CreateFrame("Frame", nil, UIParent)
+        other:SetFrameStrata("DIALOG")
+        other:SetSize(10, 10)
+        other:SetPoint("BOTTOMRIGHT")
+        other:CreateTexture(nil, "ARTWORK"):SetColorTexture(1, 0, 0, 1)
+    "#,
+    )
+    .unwrap();
+
+    assert_eq!(solid_quad_bounds(&env, "TestStrataRoot").len(), 4);
+
+    let mut widths: Vec<f32> = solid_quad_bounds_from(&env, "TestStrataRoot", Some(FrameStrata::Dialog))
+        .iter()
+        .map(|[min_x, max_x, _, _]| (max_x - min_x).round())
+        .collect();
+    widths.sort_by(f32::total_cmp);
+    assert_eq!(widths, vec![30.0, 50.0], "only the DIALOG and TOOLTIP boxes should render");
+}
+
+#[test]
+fn layer4_set_scale_shrinks_child_regions_and_offsets() {
+    let env = env_with_shared_xml();
+    env.exec(
+        r#"
+        local parent = CreateFrame("Frame", "TestScaleParent", UIParent)
+        parent:SetSize(400, 400)
+        parent:SetPoint("TOPLEFT", 100, -100)
+        parent:SetScale(0.5)
+        local tex = parent:CreateTexture(nil, "ARTWORK")
+        tex:SetSize(200, 50)
+        tex:SetPoint("TOPLEFT", parent, "TOPLEFT", 40, 0)
+        tex:SetColorTexture(1, 0, 0, 1)
+    "#,
+    )
+    .unwrap();
+
+    let quads = solid_quad_bounds(&env, "TestScaleParent");
+    assert_eq!(quads.len(), 1);
+    let [min_x, max_x, min_y, max_y] = quads[0];
+    assert!((max_x - min_x - 100.0).abs() < 0.5, "200px child at scale 0.5 should be 100 wide, got {}", max_x - min_x);
+    assert!((max_y - min_y - 25.0).abs() < 0.5, "50px child at scale 0.5 should be 25 tall, got {}", max_y - min_y);
+    // Offsets are in the parent's scaled space: (100 + 40) * 0.5.
+    assert!((min_x - 70.0).abs() < 0.5, "scaled offset should place the child at x=70, got {min_x}");
+
+    let eff: f32 = env.eval("return TestScaleParent:GetEffectiveScale()").unwrap();
+    assert!((eff - 0.5).abs() < 1e-6);
+}
+
+#[test]
+fn layer4_simple_html_img_emits_textured_quad_below_text() {
+    let env = env_with_shared_xml();
+    env.exec(
+        r#"
+        local html = CreateFrame("SimpleHTML", "TestHtmlRender", UIParent)
+        html:SetSize(300, 200)
+        html:SetPoint("TOPLEFT", 100, -100)
+        html:SetText('<html><body><h1>Title</h1><img src="Interface\\Icons\\INV_Misc_QuestionMark" width="32" height="32"/></body></html>')
+    "#,
+    )
+    .unwrap();
+
+    let batch = batch_for(&env, "TestHtmlRender", (1024.0, 768.0));
+
+    let request = batch
+        .texture_requests
+        .iter()
+        .find(|r| r.path.contains("INV_Misc_QuestionMark"))
+        .expect("<img> should emit a textured quad");
+    let quad = &batch.vertices[request.vertex_start as usize..][..4];
+    let min_x = quad.iter().map(|v| v.position[0]).fold(f32::MAX, f32::min);
+    let max_x = quad.iter().map(|v| v.position[0]).fold(f32::MIN, f32::max);
+    let min_y = quad.iter().map(|v| v.position[1]).fold(f32::MAX, f32::min);
+    assert!((min_x - 100.0).abs() < 0.5, "image should start at the frame's left edge, got {min_x}");
+    assert!((max_x - min_x - 32.0).abs() < 0.5);
+    assert!(min_y > 100.5, "image should sit below the h1 block, got y={min_y}");
+}
+
+#[test]
+fn layer4_child_with_lower_level_draws_above_parent_regions() {
+    let env = env_with_shared_xml();
+    env.exec(
+        r#"
+        local parent = CreateFrame("Frame", "TestLevelParent", UIParent)
+        parent:SetSize(100, 100)
+        parent:SetPoint("CENTER")
+        parent:SetFrameLevel(10)
+        local art = parent:CreateTexture(nil, "ARTWORK")
+        art:SetAllPoints()
+        art:SetColorTexture(1, 0, 0, 1)
+        local overlay = parent:CreateTexture(nil, "OVERLAY")
+        overlay:SetAllPoints()
+        overlay:SetColorTexture(0, 0, 1, 1)
+
+        local child = CreateFrame("Frame", "TestLevelChild", parent)
+        child:SetAllPoints()
+        child:SetFrameLevel(3)
+        local childTex = child:CreateTexture(nil, "BACKGROUND")
+        childTex:SetAllPoints()
+        childTex:SetColorTexture(0, 1, 0, 1)
+    "#,
+    )
+    .unwrap();
+
+    let batch = batch_for(&env, "TestLevelParent", (1024.0, 768.0));
+
+    let quad_index = |rgb: [f32; 3]| {
+        batch
+            .vertices
+            .chunks(4)
+            .position(|q| q[0].tex_index == -1 && q[0].color[..3] == rgb)
+            .unwrap_or_else(|| panic!("no solid quad with color {rgb:?}"))
+    };
+    let art = quad_index([1.0, 0.0, 0.0]);
+    let overlay = quad_index([0.0, 0.0, 1.0]);
+    let child = quad_index([0.0, 1.0, 0.0]);
+    assert!(art < overlay, "parent ARTWORK ({art}) should precede OVERLAY ({overlay})");
+    assert!(overlay < child, "child quad ({child}) should draw after parent OVERLAY ({overlay})");
+
+    // The explicit level is still reported as set.
+    let level: i32 = env.eval("return TestLevelChild:GetFrameLevel()").unwrap();
+    assert_eq!(level, 3);
+}
+
+#[test]
+fn layer4_set_atlas_emits_cropped_atlas_file() {
+    let env = env_with_shared_xml();
+    env.exec(
+        r#"
+        local f = CreateFrame("Frame", "TestAtlasQuadFrame", UIParent)
+        f:SetSize(32, 32)
+        f:SetPoint("CENTER")
+        local tex = f:CreateTexture(nil, "ARTWORK")
+        tex:SetAllPoints(f)
+        tex:SetAtlas("checkbox-minimal")
+    "#,
+    )
+    .unwrap();
+
+    let batch = batch_for(&env, "TestAtlasQuadFrame", (1024.0, 768.0));
+
+    let info = get_atlas_info("checkbox-minimal").unwrap().info;
+    let req = batch
+        .texture_requests
+        .iter()
+        .find(|r| r.path.contains("@crop:"))
+        .expect("atlas sub-region should be requested as a cropped texture");
+    assert!(req.path.starts_with(info.file), "expected {} crop, got {}", info.file, req.path);
+    // UVs are remapped into the cropped region, so the quad spans it fully.
+    let quad = &batch.vertices[req.vertex_start as usize..req.vertex_start as usize + 4];
+    for (got, want) in [(quad[0].tex_coords, [0.0, 0.0]), (quad[2].tex_coords, [1.0, 1.0])] {
+        assert!((got[0] - want[0]).abs() < 1e-4 && (got[1] - want[1]).abs() < 1e-4, "uv {got:?}");
+    }
+}
+
+#[test]
+fn layer4_rotated_tex_coord_corners() {
+    let env = env_with_shared_xml();
+
+    // 90° clockwise: UL=(0,1), LL=(1,1), UR=(0,0), LR=(1,0).
+    env.exec(
+        r#"
+        local f = CreateFrame("Frame", "TestRotatedTexFrame", UIParent)
+        f:SetSize(32, 32)
+        f:SetPoint("CENTER")
+        local arrow = f:CreateTexture(nil, "ARTWORK")
+        arrow:SetAllPoints(f)
+        arrow:SetTexture("Interface\\Buttons\\UI-ScrollBar-ScrollUpButton-Up")
+        arrow:SetTexCoord(0, 1, 1, 1, 0, 0, 1, 0)
+    "#,
+    )
+    .unwrap();
+
+    let batch = batch_for(&env, "TestRotatedTexFrame", (1024.0, 768.0));
+
+    let req = batch
+        .texture_requests
+        .iter()
+        .find(|r| r.path.to_lowercase().contains("scrollupbutton"))
+        .expect("rotated texture should emit a texture request");
+    let start = req.vertex_start as usize;
+    let uvs: Vec<[f32; 2]> = batch.vertices[start..start + 4].iter().map(|v| v.tex_coords).collect();
+    // Vertex order is TL, TR, BR, BL.
+    assert_eq!(uvs, vec![[0.0, 1.0], [0.0, 0.0], [1.0, 0.0], [1.0, 1.0]]);
+}
+
+#[test]
+fn layer4_set_rotation_rotates_quad_corners() {
+    let env = env_with_shared_xml();
+
+    env.exec(
+        r#"
+        local f = CreateFrame("Frame", "TestSpinFrame", UIParent)
+        f:SetSize(100, 50)
+        f:SetPoint("CENTER")
+        local spinner = f:CreateTexture("TestSpinTexture", "ARTWORK")
+        spinner:SetAllPoints(f)
+        spinner:SetTexture("Interface\\Buttons\\UI-ScrollBar-ScrollUpButton-Up")
+        spinner:SetRotation(math.pi / 2)
+    "#,
+    )
+    .unwrap();
+
+    let batch = batch_for(&env, "TestSpinFrame", (1024.0, 768.0));
+    let state = env.state().borrow();
+    let tex_id = state.widgets.get_id_by_name("TestSpinTexture").unwrap();
+    let rect = compute_frame_rect(&state.widgets, tex_id, 1024.0, 768.0);
+
+    let req = batch
+        .texture_requests
+        .iter()
+        .find(|r| r.path.to_lowercase().contains("scrollupbutton"))
+        .expect("rotated texture should emit a texture request");
+    let start = req.vertex_start as usize;
+    let (cx, cy) = (rect.x + rect.width / 2.0, rect.y + rect.height / 2.0);
+    let (hw, hh) = (rect.width / 2.0, rect.height / 2.0);
+    // A quarter turn counter-clockwise: TL lands bottom-left, TR top-left,
+    // BR top-right and BL bottom-right; width and height swap.
+    let expected = [[cx - hh, cy + hw], [cx - hh, cy - hw], [cx + hh, cy - hw], [cx + hh, cy + hw]];
+    for (v, want) in batch.vertices[start..start + 4].iter().zip(expected) {
+        let got = v.position;
+        assert!((got[0] - want[0]).abs() < 1e-3 && (got[1] - want[1]).abs() < 1e-3, "corner {got:?}, want {want:?}");
+    }
+    // UVs are untouched: the whole image turns with the quad.
+    assert_eq!(batch.vertices[start].tex_coords, [0.0, 0.0]);
+}
+
+#[test]
+fn layer4_color_select_draws_wheel_with_selector_at_color() {
+    let env = env_with_shared_xml();
+
+    env.exec(
+        r#"
+        local cs = CreateFrame("ColorSelect", "TestColorWheel", UIParent)
+        cs:SetSize(200, 128)
+        cs:SetPoint("CENTER")
+        cs:SetColorRGB(1, 0, 0)
+    "#,
+    )
+    .unwrap();
+
+    let batch = batch_for(&env, "TestColorWheel", (1024.0, 768.0));
+    let state = env.state().borrow();
+    let id = state.widgets.get_id_by_name("TestColorWheel").unwrap();
+    let rect = compute_frame_rect(&state.widgets, id, 1024.0, 768.0);
+
+    // Without wheel/value textures the wheel fills the left 128x128 square.
+    // Red sits at hue 0 on the rim: straight right of the center.
+    let (cx, cy) = (rect.x + 64.0, rect.y + 64.0);
+    let red_rim = batch.vertices.iter().any(|v| {
+        (v.position[0] - (cx + 64.0)).abs() < 1e-3
+            && (v.position[1] - cy).abs() < 1e-3
+            && v.color == [1.0, 0.0, 0.0, 1.0]
+    });
+    assert!(red_rim, "wheel should have a red rim vertex right of center");
+
+    let selector = batch.vertices.chunks(4).find(|q| {
+        let (tl, br) = (q[0].position, q[2].position);
+        q[0].color == [1.0, 1.0, 1.0, 1.0]
+            && (br[0] - tl[0] - 6.0).abs() < 1e-3
+            && (br[1] - tl[1] - 6.0).abs() < 1e-3
+    });
+    let selector = selector.expect("wheel selector quad");
+    let center = ((selector[0].position[0] + selector[2].position[0]) / 2.0, (selector[0].position[1] + selector[2].position[1]) / 2.0);
+    assert!((center.0 - (cx + 64.0)).abs() < 1e-3 && (center.1 - cy).abs() < 1e-3, "selector at {center:?}");
+}
+
+#[test]
+fn layer4_set_backdrop_emits_inset_background_and_eight_edge_pieces() {
+    let env = env_with_shared_xml();
+
+    env.exec(
+        r#"
+        local f = CreateFrame("Frame", "TestBackdropFrame", UIParent)
+        f:SetSize(200, 100)
+        f:SetPoint("CENTER")
+        f:SetBackdrop({
+            bgFile = "Interface\\Tooltips\\UI-Tooltip-Background",
+            edgeFile = "Interface\\Tooltips\\UI-Tooltip-Border",
+            tile = true, tileSize = 16, edgeSize = 16,
+            insets = { left = 5, right = 5, top = 5, bottom = 5 },
+        })
+        f:SetBackdropColor(0, 0, 0, 0.8)
+    "#,
+    )
+    .unwrap();
+
+    let batch = batch_for(&env, "TestBackdropFrame", (1024.0, 768.0));
+
+    let edges: Vec<_> = batch.texture_requests.iter().filter(|r| r.path.contains("UI-Tooltip-Border")).collect();
+    assert_eq!(edges.len(), 8, "edge file should emit 4 edges + 4 corners");
+
+    // Tiled background: 190x90 inside the insets, 16px tiles.
+    let bg = batch
+        .texture_requests
+        .iter()
+        .find(|r| r.path.contains("UI-Tooltip-Background"))
+        .expect("background texture request");
+    assert_eq!(bg.vertex_count, 12 * 6 * 4);
+    let first = &batch.vertices[bg.vertex_start as usize];
+    assert_eq!(first.position, [412.0 + 5.0, 334.0 + 5.0]);
+    assert_eq!(first.color, [0.0, 0.0, 0.0, 0.8]);
+
+    // Top-left corner is segment 4 of the edge strip, at the frame's corner.
+    let corner = &batch.vertices[edges[2].vertex_start as usize];
+    assert_eq!(corner.position, [412.0, 334.0]);
+    assert_eq!(corner.tex_coords, [0.5, 0.0]);
+}
+
+#[test]
+fn layer4_horiz_tile_repeats_texture_at_native_size() {
+    // A 64x64 texture the texture manager decodes while building the batch.
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::create_dir_all(dir.path().join("TileTest")).unwrap();
+    image::RgbaImage::new(64, 64).save(dir.path().join("TileTest/Parchment64.png")).unwrap();
+    let mut tex_mgr = TextureManager::new(dir.path());
+
+    let env = env_with_shared_xml();
+    env.exec(
+        r#"
+        local f = CreateFrame("Frame", "TestHorizTileFrame", UIParent)
+        f:SetSize(400, 64)
+        f:SetPoint("TOPLEFT", 0, 0)
+        local tex = f:CreateTexture(nil, "BACKGROUND")
+        tex:SetAllPoints(f)
+        tex:SetTexture("Interface\\TileTest\\Parchment64", true, false)
+    "#,
+    )
+    .unwrap();
+
+    env.state().borrow_mut().ensure_layout_rects();
+    let buckets = build_strata_buckets(&env);
+    let state = env.state().borrow();
+    let batch = build_quad_batch_at_time(
+        &state.widgets,
+        (1024.0, 768.0),
+        Some("TestHorizTileFrame"),
+        None,
+        None,
+        None,
+        Some(&mut tex_mgr),
+        None,
+        None,
+        &buckets,
+        0.0,
+    );
+
+    let tiles: Vec<_> = batch.texture_requests.iter().filter(|r| r.path.contains("Parchment64")).collect();
+    // 400 / 64 = 6 full tiles plus a 16px remainder.
+    assert_eq!(tiles.len(), 7);
+    let last = &batch.vertices[tiles[6].vertex_start as usize..tiles[6].vertex_start as usize + 4];
+    assert_eq!(batch.vertices[tiles[1].vertex_start as usize].position, [64.0, 0.0]);
+    assert_eq!(last[0].position, [384.0, 0.0]);
+    assert_eq!(last[2].position, [400.0, 64.0]);
+    assert!((last[2].tex_coords[0] - 0.25).abs() < 1e-4, "remainder shows a quarter of the texture: {:?}", last[2].tex_coords);
+}
+
+#[test]
+fn backdrop_mixin_methods_shadow_rust_fallbacks() {
+    let env = env_with_shared_xml();
+    let (plain, mixed): (bool, String) = env
+        .eval(
+            r#"
+            local plain = CreateFrame("Frame")
+            plain:SetBackdrop({ edgeFile = "Interface\\Tooltips\\UI-Tooltip-Border", edgeSize = 16 })
+            plain:SetBackdropBorderColor(1, 0, 0)
+            local r, g = plain:GetBackdropBorderColor()
+
+            local mixed = CreateFrame("Frame")
+            mixed.SetBackdrop = function() return "mixin" end
+            return r == 1 and g == 0 and plain:GetBackdrop().edgeSize == 16, mixed:SetBackdrop({})
+            "#,
+        )
+        .unwrap();
+    assert!(plain);
+    assert_eq!(mixed, "mixin");
+}
+
+#[test]
+fn layer4_inline_texture_escape_emits_quad_after_text() {
+    use iced::{Point, Rectangle, Size};
+    use wow_ui_sim::render::font::WowFontSystem;
+    use wow_ui_sim::render::glyph::{emit_text_quads, GlyphAtlas};
+    use wow_ui_sim::widget::{TextJustify, TextOutline};
+
+    let mut fs = WowFontSystem::new(std::path::Path::new("./fonts"));
+    let mut ga = GlyphAtlas::new();
+    let mut batch = QuadBatch::new();
+    let bounds = Rectangle::new(Point::new(10.0, 20.0), Size::new(300.0, 30.0));
+    emit_text_quads(
+        &mut batch,
+        &mut fs,
+        &mut ga,
+        "|cffffd100Gold|r|TInterface\\Icons\\X:16|t",
+        bounds,
+        None,
+        12.0,
+        [1.0; 4],
+        TextJustify::Left,
+        TextJustify::Center,
+        0,
+        None,
+        (0.0, 0.0),
+        TextOutline::None,
+        false,
+        0,
+    );
+
+    let req = batch
+        .texture_requests
+        .iter()
+        .find(|r| r.path == "Interface\\Icons\\X")
+        .expect("inline texture should be requested");
+    let tl = batch.vertices[req.vertex_start as usize].position;
+    let br = batch.vertices[req.vertex_start as usize + 2].position;
+    assert_eq!(br[1] - tl[1], 16.0, "16px tall");
+    let text_right = 10.0 + fs.measure_text_width("Gold", None, 12.0);
+    assert!((tl[0] - text_right).abs() <= 1.0, "icon at {} should follow text ending at {text_right}", tl[0]);
+    assert!(tl[1] >= 20.0 && br[1] <= 50.0, "icon inside the text bounds: {tl:?}..{br:?}");
+}
+
+/// Glyph quads for `text` laid out with `glyph_atlas`, and their bounding box.
+fn text_batch(
+    fs: &mut wow_ui_sim::render::WowFontSystem,
+    glyph_atlas: &mut wow_ui_sim::render::GlyphAtlas,
+    text: &str,
+) -> (QuadBatch, [f32; 4]) {
+    use iced::{Point, Rectangle, Size};
+    use wow_ui_sim::render::emit_text_quads;
+    use wow_ui_sim::render::shader::GLYPH_ATLAS_TEX_INDEX;
+    use wow_ui_sim::widget::{TextJustify, TextOutline};
+
+    let mut batch = QuadBatch::new();
+    let bounds = Rectangle::new(Point::new(4.0, 4.0), Size::new(120.0, 24.0));
+    emit_text_quads(
+        &mut batch, fs, glyph_atlas, text, bounds, None, 14.0, [1.0; 4],
+        TextJustify::Left, TextJustify::Center, GLYPH_ATLAS_TEX_INDEX,
+        None, (0.0, 0.0), TextOutline::None, false, 0,
+    );
+    let mut extent = [f32::MAX, f32::MAX, f32::MIN, f32::MIN];
+    for v in &batch.vertices {
+        extent[0] = extent[0].min(v.position[0]);
+        extent[1] = extent[1].min(v.position[1]);
+        extent[2] = extent[2].max(v.position[0]);
+        extent[3] = extent[3].max(v.position[1]);
+    }
+    (batch, extent)
+}
+
+#[test]
+fn layer4_raster_scale_keeps_glyph_quads_at_logical_size() {
+    use wow_ui_sim::render::{GlyphAtlas, WowFontSystem};
+
+    let mut fs = WowFontSystem::new(std::path::Path::new("./fonts"));
+    let (plain, plain_extent) = text_batch(&mut fs, &mut GlyphAtlas::new(), "Quest Log");
+    let (scaled, scaled_extent) = text_batch(&mut fs, &mut GlyphAtlas::with_raster_scale(2.0), "Quest Log");
+
+    assert!(plain.quad_count() > 0, "text should emit glyph quads");
+    assert_eq!(plain.quad_count(), scaled.quad_count());
+    for (a, b) in plain_extent.iter().zip(scaled_extent) {
+        assert!((a - b).abs() <= 1.0, "2x glyphs should cover the same area: {plain_extent:?} vs {scaled_extent:?}");
+    }
+}
+
 // ============================================================================
 // Layer 5: GPU atlas upload
 // ============================================================================
@@ -549,3 +1346,160 @@ fn layer5_gpu_atlas_real_scroll_textures() {
         }
     }
 }
+
+#[test]
+fn layer5_render_scene_returns_pixels_and_stats() {
+    if common::try_create_gpu_device().is_none() {
+        eprintln!("Skipping GPU test: no adapter available");
+        return;
+    }
+
+    use iced::{Point, Rectangle, Size};
+
+    let mut batch = QuadBatch::new();
+    batch.push_solid(Rectangle::new(Point::new(16.0, 16.0), Size::new(32.0, 32.0)), [1.0, 0.0, 0.0, 1.0]);
+    let mut tex_mgr = TextureManager::new(LOCAL_TEXTURES);
+    let output = render_scene(&batch, &mut tex_mgr, 64, 64, None);
+
+    assert_eq!(output.image.dimensions(), (64, 64));
+    assert_eq!(output.image.get_pixel(32, 32).0, [255, 0, 0, 255], "center should be the frame color");
+    assert_ne!(output.image.get_pixel(2, 2).0, [255, 0, 0, 255], "corner should be background");
+    assert_eq!(output.stats.quad_count, 1);
+    assert_eq!(output.stats.textures_loaded, 0);
+    assert!(output.stats.texture_misses.is_empty());
+}
+
+/// A 64x64 red square at the screen's top-left with a circular `SetMask`.
+fn circle_masked_square_batch() -> QuadBatch {
+    let env = env_with_shared_xml();
+    env.exec(
+        r#"
+        local f = CreateFrame("Frame", "TestCircleMaskFrame", UIParent)
+        f:SetSize(64, 64)
+        f:SetPoint("TOPLEFT", 0, 0)
+        local tex = f:CreateTexture(nil, "ARTWORK")
+        tex:SetAllPoints(f)
+        tex:SetColorTexture(1, 0, 0, 1)
+        tex:SetMask("Interface\\CharacterFrame\\TempPortraitAlphaMask")
+    "#,
+    )
+    .unwrap();
+    batch_for(&env, "TestCircleMaskFrame", (64.0, 64.0))
+}
+
+#[test]
+fn layer4_set_mask_tags_quads_with_full_mask_uvs() {
+    let batch = circle_masked_square_batch();
+    let req = batch
+        .mask_texture_requests
+        .iter()
+        .find(|r| r.path.contains("TempPortraitAlphaMask"))
+        .expect("mask file should be requested");
+    let quad = &batch.vertices[req.vertex_start as usize..req.vertex_start as usize + 4];
+    assert!(quad.iter().all(|v| v.mask_tex_index == -2), "quads await mask resolution");
+    assert_eq!(quad[0].mask_tex_coords, [0.0, 0.0]);
+    assert_eq!(quad[2].mask_tex_coords, [1.0, 1.0]);
+}
+
+#[test]
+fn layer5_circular_mask_clears_square_corners() {
+    if common::try_create_gpu_device().is_none() {
+        eprintln!("Skipping GPU test: no adapter available");
+        return;
+    }
+    let Some(mut tex_mgr) = make_texture_manager() else {
+        eprintln!("Skipping: no textures directory");
+        return;
+    };
+
+    let batch = circle_masked_square_batch();
+    let output = render_scene(&batch, &mut tex_mgr, 64, 64, None);
+    assert!(output.stats.texture_misses.is_empty(), "mask missing: {:?}", output.stats.texture_misses);
+
+    let center = output.image.get_pixel(32, 32).0;
+    let corner = output.image.get_pixel(1, 1).0;
+    assert!(center[0] > 200, "center keeps the texture: {center:?}");
+    assert!(corner[0] < 40, "corner is masked out to the background: {corner:?}");
+}
+
+#[test]
+fn layer5_set_rotation_renders_rotated_quad() {
+    if common::try_create_gpu_device().is_none() {
+        eprintln!("Skipping GPU test: no adapter available");
+        return;
+    }
+
+    // A 64x16 horizontal red bar centered at (32, 32), turned a quarter.
+    let env = env_with_shared_xml();
+    env.exec(
+        r#"
+        local f = CreateFrame("Frame", "TestSpinBarFrame", UIParent)
+        f:SetSize(64, 16)
+        f:SetPoint("TOPLEFT", 0, -24)
+        local bar = f:CreateTexture(nil, "ARTWORK")
+        bar:SetAllPoints(f)
+        bar:SetColorTexture(1, 0, 0, 1)
+        bar:SetRotation(math.pi / 2)
+    "#,
+    )
+    .unwrap();
+    let batch = batch_for(&env, "TestSpinBarFrame", (64.0, 64.0));
+    let mut tex_mgr = TextureManager::new(LOCAL_TEXTURES);
+    let output = render_scene(&batch, &mut tex_mgr, 64, 64, None);
+
+    let red = [255, 0, 0, 255];
+    assert_eq!(output.image.get_pixel(32, 32).0, red, "center stays covered");
+    assert_eq!(output.image.get_pixel(32, 4).0, red, "the bar now runs vertically");
+    assert_ne!(output.image.get_pixel(4, 32).0, red, "its old horizontal extent is background");
+}
+
+#[test]
+fn layer5_downsample_box_averages_blocks() {
+    use wow_ui_sim::render::headless::downsample_box;
+
+    let img = image::RgbaImage::from_fn(4, 2, |x, _| {
+        if x % 2 == 0 { image::Rgba([255, 0, 0, 255]) } else { image::Rgba([0, 0, 255, 255]) }
+    });
+    let small = downsample_box(&img, 2);
+    assert_eq!(small.dimensions(), (2, 1));
+    // 50% linear light in each channel, encoded back to sRGB.
+    assert_eq!(small.get_pixel(0, 0).0, [188, 0, 188, 255]);
+}
+
+#[test]
+fn layer5_render_scene_scaled_antialiases_edges_at_requested_size() {
+    if common::try_create_gpu_device().is_none() {
+        eprintln!("Skipping GPU test: no adapter available");
+        return;
+    }
+
+    use iced::{Point, Rectangle, Size};
+    use wow_ui_sim::render::headless::render_scene_scaled;
+    use wow_ui_sim::render::{GlyphAtlas, WowFontSystem};
+
+    // A red quad whose left edge sits on a half pixel.
+    let mut batch = QuadBatch::new();
+    batch.push_solid(Rectangle::new(Point::new(10.5, 16.0), Size::new(32.0, 32.0)), [1.0, 0.0, 0.0, 1.0]);
+    let mut tex_mgr = TextureManager::new(LOCAL_TEXTURES);
+    let plain = render_scene_scaled(&batch, &mut tex_mgr, 64, 64, 1, None).image;
+    let scaled = render_scene_scaled(&batch, &mut tex_mgr, 64, 64, 2, None).image;
+
+    assert_eq!(plain.dimensions(), (64, 64));
+    assert_eq!(scaled.dimensions(), (64, 64), "output stays at the requested size");
+    let plain_edge = plain.get_pixel(10, 32).0[0];
+    let scaled_edge = scaled.get_pixel(10, 32).0[0];
+    let background = scaled.get_pixel(2, 32).0[0];
+    assert!(plain_edge == 255 || plain_edge == background, "1x edge is hard: {plain_edge}");
+    assert!(scaled_edge > background && scaled_edge < 255, "2x edge is blended: {scaled_edge}");
+    assert_eq!(scaled.get_pixel(32, 32).0, [255, 0, 0, 255], "interior is unchanged");
+
+    // Text rasterized at 2x still lands in its 1x box.
+    let mut fs = WowFontSystem::new(std::path::Path::new("./fonts"));
+    let mut glyphs = GlyphAtlas::with_raster_scale(2.0);
+    let (text, extent) = text_batch(&mut fs, &mut glyphs, "Quest Log");
+    let (data, size, _) = glyphs.texture_data();
+    let out = render_scene_scaled(&text, &mut tex_mgr, 128, 32, 2, Some((data, size))).image;
+    assert_eq!(out.dimensions(), (128, 32));
+    let lit = out.enumerate_pixels().filter(|(_, _, p)| p.0[0] > 128).count();
+    assert!(lit > 0, "text should be drawn inside {extent:?}");
+}
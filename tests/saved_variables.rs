@@ -0,0 +1,82 @@
+//! Tests for SavedVariables loading through the addon loader
+//! (loader::load_addon_with_saved_vars + saved_variables.rs).
+
+use wow_ui_sim::loader::load_addon_with_saved_vars;
+use wow_ui_sim::lua_api::WowLuaEnv;
+use wow_ui_sim::saved_variables::SavedVariablesManager;
+
+#[test]
+fn test_broken_saved_variables_warns_and_addon_still_loads() {
+    let addons = tempfile::tempdir().unwrap();
+    let storage = tempfile::tempdir().unwrap();
+
+    let addon_dir = addons.path().join("BrokenVars");
+    std::fs::create_dir(&addon_dir).unwrap();
+    std::fs::write(
+        addon_dir.join("BrokenVars.toc"),
+        "## Interface: 110207\n## SavedVariables: BrokenVarsDB\nBrokenVars.lua\n",
+    )
+    .unwrap();
+    std::fs::write(
+        addon_dir.join("BrokenVars.lua"),
+        "BrokenVarsDB.loaded = true\nBrokenVarsRan = true\n",
+    )
+    .unwrap();
+    // Missing the closing brace.
+    std::fs::write(
+        storage.path().join("BrokenVars.lua"),
+        "\nBrokenVarsDB = {\n\t[\"scale\"] = 1,\n",
+    )
+    .unwrap();
+
+    let env = WowLuaEnv::new().unwrap();
+    let mut mgr = SavedVariablesManager::with_storage_dir(storage.path().to_path_buf());
+    let result =
+        load_addon_with_saved_vars(&env.loader_env(), &addon_dir.join("BrokenVars.toc"), &mut mgr)
+            .expect("addon should load despite broken SavedVariables");
+
+    let warning = result
+        .warnings
+        .iter()
+        .find(|w| w.contains("SavedVariables for BrokenVars"))
+        .unwrap_or_else(|| panic!("no SavedVariables warning in {:?}", result.warnings));
+    assert!(warning.contains("BrokenVars.lua:"), "warning should name the file: {warning}");
+    assert!(warning.contains("'}' expected"), "warning should carry the Lua error: {warning}");
+
+    let ran: bool = env.eval("return BrokenVarsRan == true and BrokenVarsDB.loaded == true").unwrap();
+    assert!(ran, "addon code should run against empty default tables");
+}
+
+#[test]
+fn test_toc_declared_saved_variables_are_loaded_before_first_file() {
+    let addons = tempfile::tempdir().unwrap();
+    let storage = tempfile::tempdir().unwrap();
+
+    let addon_dir = addons.path().join("TwoVars");
+    std::fs::create_dir(&addon_dir).unwrap();
+    std::fs::write(
+        addon_dir.join("TwoVars.toc"),
+        "## Interface: 110207\n## SavedVariables: TwoVarsDB\n\
+         ## SavedVariablesPerCharacter: TwoVarsCharDB\nTwoVars.lua\n",
+    )
+    .unwrap();
+    // Snapshot what the first file sees, before any addon code could set them.
+    std::fs::write(
+        addon_dir.join("TwoVars.lua"),
+        "SeenScale = TwoVarsDB and TwoVarsDB.scale\nSeenSpec = TwoVarsCharDB and TwoVarsCharDB.spec\n",
+    )
+    .unwrap();
+    std::fs::write(storage.path().join("TwoVars.lua"), "TwoVarsDB = { scale = 2 }\n").unwrap();
+    let char_dir = storage.path().join("SimRealm").join("SimPlayer");
+    std::fs::create_dir_all(&char_dir).unwrap();
+    std::fs::write(char_dir.join("TwoVars.lua"), "TwoVarsCharDB = { spec = \"frost\" }\n").unwrap();
+
+    let env = WowLuaEnv::new().unwrap();
+    let mut mgr = SavedVariablesManager::with_storage_dir(storage.path().to_path_buf());
+    load_addon_with_saved_vars(&env.loader_env(), &addon_dir.join("TwoVars.toc"), &mut mgr).unwrap();
+
+    let (scale, spec): (i64, String) = env.eval("return SeenScale, SeenSpec").unwrap();
+    assert_eq!(scale, 2);
+    assert_eq!(spec, "frost");
+    assert_eq!(mgr.registered_addons(), vec!["TwoVars"]);
+}
@@ -29,6 +29,72 @@ fn test_create_scrollframe_basic() {
     assert_eq!(obj_type, "ScrollFrame");
 }
 
+fn layout_rect_of(env: &WowLuaEnv, name: &str) -> wow_ui_sim::LayoutRect {
+    let mut state = env.state().borrow_mut();
+    state.ensure_layout_rects();
+    let id = state.widgets.get_id_by_name(name).unwrap();
+    state.widgets.get(id).unwrap().layout_rect.unwrap()
+}
+
+#[test]
+fn test_vertical_scroll_shifts_scroll_child_subtree() {
+    let env = WowLuaEnv::new().unwrap();
+    env.exec(
+        r#"
+        local sf = CreateFrame("ScrollFrame", "TestScrollOffset", UIParent)
+        sf:SetSize(200, 100)
+        sf:SetPoint("CENTER")
+        local content = CreateFrame("Frame", "TestScrollOffsetContent", sf)
+        content:SetSize(200, 400)
+        content:SetPoint("TOPLEFT")
+        local row = CreateFrame("Frame", "TestScrollOffsetRow", content)
+        row:SetSize(200, 20)
+        row:SetPoint("TOPLEFT", 0, -150)
+        sf:SetScrollChild(content)
+    "#,
+    )
+    .unwrap();
+
+    let before = layout_rect_of(&env, "TestScrollOffsetRow");
+    env.exec("TestScrollOffset:SetVerticalScroll(100)").unwrap();
+    let after = layout_rect_of(&env, "TestScrollOffsetRow");
+    assert!((before.y - after.y - 100.0).abs() < 0.01, "row should move up by 100px");
+    assert_eq!(before.x, after.x);
+
+    let scroll: f64 = env.eval("return TestScrollOffset:GetVerticalScroll()").unwrap();
+    assert_eq!(scroll, 100.0);
+    let range: f64 = env.eval("return TestScrollOffset:GetVerticalScrollRange()").unwrap();
+    assert_eq!(range, 300.0, "range is child height minus viewport height");
+    let child_is_content: bool = env
+        .eval("return TestScrollOffset:GetScrollChild() == TestScrollOffsetContent")
+        .unwrap();
+    assert!(child_is_content);
+}
+
+#[test]
+fn test_horizontal_scroll_shifts_scroll_child() {
+    let env = WowLuaEnv::new().unwrap();
+    env.exec(
+        r#"
+        local sf = CreateFrame("ScrollFrame", "TestHScroll", UIParent)
+        sf:SetSize(100, 100)
+        sf:SetPoint("CENTER")
+        local content = CreateFrame("Frame", "TestHScrollContent", sf)
+        content:SetSize(300, 100)
+        content:SetPoint("TOPLEFT")
+        sf:SetScrollChild(content)
+    "#,
+    )
+    .unwrap();
+
+    let frame = layout_rect_of(&env, "TestHScroll");
+    env.exec("TestHScroll:SetHorizontalScroll(40)").unwrap();
+    let content = layout_rect_of(&env, "TestHScrollContent");
+    assert!((frame.x - content.x - 40.0).abs() < 0.01, "content should move left by 40px");
+    let range: f64 = env.eval("return TestHScroll:GetHorizontalScrollRange()").unwrap();
+    assert_eq!(range, 200.0);
+}
+
 // ============================================================================
 // FauxScrollFrameTemplate Tests (requires SharedXML)
 // ============================================================================
@@ -173,6 +239,68 @@ fn test_slider_has_fontstrings() {
     assert!(has_text, "Slider should have Text FontString");
 }
 
+#[test]
+fn test_slider_thumb_follows_value() {
+    let env = WowLuaEnv::new().unwrap();
+    env.set_screen_size(1024.0, 768.0);
+
+    env.exec(
+        r#"
+        local slider = CreateFrame("Slider", "TestSliderThumb", UIParent)
+        slider:SetSize(200, 20)
+        slider:SetPoint("TOPLEFT", 100, -100)
+        slider:SetMinMaxValues(0, 100)
+        local thumb = slider:CreateTexture(nil, "ARTWORK")
+        thumb:SetSize(32, 32)
+        slider:SetThumbTexture(thumb)
+        slider:SetValue(50)
+    "#,
+    )
+    .unwrap();
+
+    // The thumb travels the 200 - 32 px left inside the track: 50% is centered.
+    let (left, top): (f32, f32) = env
+        .eval("local t = TestSliderThumb:GetThumbTexture(); return t:GetLeft(), t:GetTop()")
+        .unwrap();
+    assert_eq!((left, top), (184.0, 768.0 - 94.0));
+
+    env.exec("TestSliderThumb:SetValue(25)").unwrap();
+    let left: f32 = env.eval("return TestSliderThumb:GetThumbTexture():GetLeft()").unwrap();
+    assert_eq!(left, 142.0);
+
+    env.exec("TestSliderThumb:SetValue(100)").unwrap();
+    let right: f32 = env.eval("return TestSliderThumb:GetThumbTexture():GetRight()").unwrap();
+    assert_eq!(right, 300.0);
+}
+
+#[test]
+fn test_slider_value_snaps_to_step_and_fires_on_value_changed() {
+    let env = WowLuaEnv::new().unwrap();
+
+    env.exec(
+        r#"
+        Changes = {}
+        local slider = CreateFrame("Slider", "TestSliderStep", UIParent)
+        slider:SetMinMaxValues(0, 100)
+        slider:SetValueStep(10)
+        slider:SetScript("OnValueChanged", function(self, value)
+            table.insert(Changes, value)
+        end)
+        slider:SetValue(47)
+        slider:SetValue(52)
+        slider:SetValue(250)
+        slider:SetMinMaxValues(0, 80)
+    "#,
+    )
+    .unwrap();
+
+    // 52 snaps back to 50, so it doesn't fire; narrowing the range clamps 100 to 80.
+    let changes: String = env.eval("return table.concat(Changes, ',')").unwrap();
+    assert_eq!(changes, "50,100,80");
+    let value: f64 = env.eval("return TestSliderStep:GetValue()").unwrap();
+    assert_eq!(value, 80.0);
+}
+
 // ============================================================================
 // Button Texture Tests (requires SharedXML)
 // ============================================================================
@@ -236,3 +236,101 @@ fn test_fontstring_settext_no_html_stripping() {
     let text: String = env.eval("return TestFontStr:GetText()").unwrap();
     assert_eq!(text, "<h1>Title</h1>", "FontString should store HTML tags as-is");
 }
+
+#[test]
+fn test_content_height_stacks_blocks_and_grows_when_narrowed() {
+    let env = WowLuaEnv::new().unwrap();
+
+    env.exec(
+        r#"
+        local f = CreateFrame("SimpleHTML", "TestHTMLBlocks", UIParent)
+        f:SetSize(400, 300)
+        f:SetFont("h1", "Fonts\\FRIZQT__.TTF", 24)
+        f:SetText("<html><body><h1>Patch Notes</h1>"
+            .. "<p>The first paragraph talks about the new zone and the quests found there.</p>"
+            .. "<p>The second paragraph lists class changes and a few bug fixes.</p>"
+            .. "</body></html>")
+    "#,
+    )
+    .unwrap();
+
+    let blocks = {
+        let state = env.state().borrow();
+        let id = state.widgets.get_id_by_name("TestHTMLBlocks").unwrap();
+        state.widgets.get(id).unwrap().html_blocks.clone()
+    };
+    assert_eq!(blocks.len(), 3, "h1 and two paragraphs should be separate blocks");
+    match &blocks[0] {
+        wow_ui_sim::widget::HtmlBlock::Text { text, font_size, .. } => {
+            assert_eq!(text, "Patch Notes");
+            assert_eq!(*font_size, 24.0);
+        }
+        other => panic!("expected a text block, got {other:?}"),
+    }
+
+    let wide: f64 = env.eval("return TestHTMLBlocks:GetContentHeight()").unwrap();
+    env.exec("TestHTMLBlocks:SetWidth(120)").unwrap();
+    let narrow: f64 = env.eval("return TestHTMLBlocks:GetContentHeight()").unwrap();
+    assert!(wide > 0.0);
+    assert!(narrow > wide, "narrowing should wrap paragraphs onto more lines ({narrow} <= {wide})");
+}
+
+#[test]
+fn test_content_height_wraps_to_anchored_width() {
+    let env = WowLuaEnv::new().unwrap();
+
+    env.exec(
+        r#"
+        local html = "<html><body>"
+            .. "<p>The first paragraph talks about the new zone and the quests found there.</p>"
+            .. "<p>The second paragraph lists class changes and a few bug fixes.</p>"
+            .. "</body></html>"
+        local sized = CreateFrame("SimpleHTML", "TestHTMLSized", UIParent)
+        sized:SetSize(150, 300)
+        sized:SetPoint("TOPLEFT")
+        sized:SetText(html)
+        local holder = CreateFrame("Frame", nil, UIParent)
+        holder:SetSize(150, 300)
+        holder:SetPoint("CENTER")
+        local anchored = CreateFrame("SimpleHTML", "TestHTMLAnchored", holder)
+        anchored:SetPoint("TOPLEFT")
+        anchored:SetPoint("BOTTOMRIGHT")
+        anchored:SetText(html)
+    "#,
+    )
+    .unwrap();
+
+    let sized: f64 = env.eval("return TestHTMLSized:GetContentHeight()").unwrap();
+    let anchored: f64 = env.eval("return TestHTMLAnchored:GetContentHeight()").unwrap();
+    assert!(sized > 0.0);
+    assert_eq!(anchored, sized, "a frame sized by its anchors wraps like an explicit width");
+}
+
+#[test]
+fn test_img_becomes_image_block_between_paragraphs() {
+    let env = WowLuaEnv::new().unwrap();
+
+    env.exec(
+        r#"
+        local f = CreateFrame("SimpleHTML", "TestHTMLImg", UIParent)
+        f:SetSize(300, 300)
+        f:SetText('<html><body><p>Above</p><img src="Interface\\Icons\\INV_Misc_QuestionMark" width="32" height="40" align="center"/><p>Below &amp; after</p></body></html>')
+    "#,
+    )
+    .unwrap();
+
+    let state = env.state().borrow();
+    let id = state.widgets.get_id_by_name("TestHTMLImg").unwrap();
+    let blocks = &state.widgets.get(id).unwrap().html_blocks;
+    assert_eq!(blocks.len(), 3);
+    assert_eq!(
+        blocks[1],
+        wow_ui_sim::widget::HtmlBlock::Image {
+            path: "Interface\\Icons\\INV_Misc_QuestionMark".to_string(),
+            width: 32.0,
+            height: 40.0,
+            justify_h: wow_ui_sim::widget::TextJustify::Center,
+        }
+    );
+    assert!(matches!(&blocks[2], wow_ui_sim::widget::HtmlBlock::Text { text, .. } if text == "Below & after"));
+}
@@ -0,0 +1,83 @@
+//! Tests for slash command dispatch (WowLuaEnv::dispatch_slash_command):
+//! SLASH_* aliases, the token index and Blizzard's hash_SlashCmdList.
+
+use wow_ui_sim::lua_api::WowLuaEnv;
+
+fn env() -> WowLuaEnv {
+    WowLuaEnv::new().expect("create env")
+}
+
+/// Register a TomTom-style command with two aliases, logging to `TomTomMsg`.
+fn register_tomtom(env: &WowLuaEnv) {
+    env.exec(
+        r#"
+        SLASH_TOMTOM1 = "/tomtom"
+        SLASH_TOMTOM2 = "/tt"
+        SlashCmdList["TOMTOM"] = function(msg) TomTomMsg = msg end
+    "#,
+    )
+    .unwrap();
+}
+
+#[test]
+fn test_alias_routes_to_handler_registered_after_first_dispatch() {
+    let env = env();
+    assert!(!env.dispatch_slash_command("/tt 10 20").unwrap());
+
+    register_tomtom(&env);
+    assert!(env.dispatch_slash_command("/TT 10 20").unwrap());
+    let msg: String = env.eval("return TomTomMsg").unwrap();
+    assert_eq!(msg, "10 20");
+    assert!(env.dispatch_slash_command("/tomtom").unwrap());
+    let msg: String = env.eval("return TomTomMsg").unwrap();
+    assert_eq!(msg, "");
+}
+
+#[test]
+fn test_indexed_dispatch_does_not_probe_slash_globals() {
+    let env = env();
+    register_tomtom(&env);
+    // Count lookups of missing SLASH_* globals, i.e. index rebuilds.
+    env.exec(
+        r#"
+        SlashProbes = 0
+        setmetatable(_G, { __index = function(_, k)
+            if type(k) == "string" and k:sub(1, 6) == "SLASH_" then
+                SlashProbes = SlashProbes + 1
+            end
+        end })
+    "#,
+    )
+    .unwrap();
+
+    assert!(env.dispatch_slash_command("/tt").unwrap());
+    let probes: i32 = env.eval("local n = SlashProbes; SlashProbes = 0; return n").unwrap();
+    assert!(probes > 0, "first dispatch builds the index");
+
+    assert!(env.dispatch_slash_command("/tt again").unwrap());
+    let probes: i32 = env.eval("return SlashProbes").unwrap();
+    assert_eq!(probes, 0);
+}
+
+#[test]
+fn test_unregistered_handler_no_longer_dispatches() {
+    let env = env();
+    register_tomtom(&env);
+    assert!(env.dispatch_slash_command("/tt").unwrap());
+    env.exec(r#"SlashCmdList["TOMTOM"] = nil"#).unwrap();
+    assert!(!env.dispatch_slash_command("/tt").unwrap());
+}
+
+#[test]
+fn test_hash_slash_cmd_list_is_consulted_first() {
+    let env = env();
+    env.exec(
+        r#"
+        hash_SlashCmdList = { ["/HASHED"] = function(msg) HashedMsg = msg end }
+    "#,
+    )
+    .unwrap();
+    assert!(env.dispatch_slash_command("/hashed hi").unwrap());
+    let msg: String = env.eval("return HashedMsg").unwrap();
+    assert_eq!(msg, "hi");
+}
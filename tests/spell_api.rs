@@ -163,6 +163,43 @@ fn test_spell_get_spell_cooldown() {
     assert!(is_table);
 }
 
+#[test]
+fn test_spell_get_spell_info_unknown_is_nil() {
+    let env = env();
+    let is_nil: bool = env.eval("return C_Spell.GetSpellInfo(999999999) == nil").unwrap();
+    assert!(is_nil);
+}
+
+#[test]
+fn test_legacy_get_spell_info_fireball() {
+    let env = env();
+    let (name, icon, spell_id): (String, u32, i32) = env
+        .eval("local name, _, icon, _, _, _, id = GetSpellInfo(133); return name, icon, id")
+        .unwrap();
+    assert_eq!(name, "Fireball");
+    assert_eq!(spell_id, 133);
+    assert!(
+        wow_ui_sim::manifest_interface_data::get_texture_path(icon).is_some(),
+        "icon {icon} should resolve to a texture path"
+    );
+    let none: i32 = env.eval("return select('#', GetSpellInfo(999999999))").unwrap();
+    assert_eq!(none, 0);
+}
+
+#[test]
+fn test_set_spell_cooldown_drives_both_cooldown_apis() {
+    let env = env();
+    env.exec("SetSpellCooldown(133, GetTime(), 8)").unwrap();
+    let (legacy, modern): (f64, f64) = env
+        .eval("local _, d = GetSpellCooldown(133); return d, C_Spell.GetSpellCooldown(133).duration")
+        .unwrap();
+    assert_eq!((legacy, modern), (8.0, 8.0));
+
+    env.exec("SetSpellCooldown(133, 0, 0)").unwrap();
+    let (start, duration): (f64, f64) = env.eval("return GetSpellCooldown(133)").unwrap();
+    assert_eq!((start, duration), (0.0, 0.0));
+}
+
 #[test]
 fn test_spell_does_spell_exist() {
     let env = env();
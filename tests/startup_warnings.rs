@@ -1,8 +1,13 @@
 //! Test that loading Blizzard addons and firing startup events produces no warnings.
 
+mod common;
+
 use std::path::PathBuf;
+use wow_ui_sim::iced_app::{build_quad_batch_for_registry, compute_frame_rect};
 use wow_ui_sim::loader::{discover_blizzard_addons, load_addon};
 use wow_ui_sim::lua_api::WowLuaEnv;
+use wow_ui_sim::render::headless::render_scene;
+use wow_ui_sim::texture::TextureManager;
 
 fn blizzard_ui_dir() -> PathBuf {
     PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("Interface/BlizzardUI")
@@ -218,3 +223,104 @@ fn test_widget_container_mixin_applied() {
     assert_lua(&env, "return type(ObjectiveTrackerUIWidgetContainer.GetNumWidgetsShowing) == 'function'",
         "GetNumWidgetsShowing should still be available after startup events and timer processing");
 }
+
+/// Injected quests reach the ObjectiveTracker: QUEST_LOG_UPDATE rebuilds the
+/// quest module with a block per watched quest and no Lua errors.
+#[test]
+fn test_objective_tracker_shows_injected_quests() {
+    let env = load_all_addons();
+    install_test_error_handler(&env);
+    fire_events_and_timers(&env);
+    drain_test_errors(&env);
+
+    env.exec(
+        r#"
+        AddQuestToLog({ questID = 81001, title = "Boars of the Valley", objectives = {
+            { text = "3/8 Valley Boar slain", type = "monster", numFulfilled = 3, numRequired = 8 },
+        } })
+        AddQuestToLog({ questID = 81002, title = "Letter Home" })
+        SetQuestComplete(81002)
+    "#,
+    )
+    .expect("injecting quests should not error");
+    for _ in 0..3 {
+        let _ = env.fire_on_update(0.016);
+        let _ = env.process_timers();
+    }
+
+    let errors = drain_test_errors(&env);
+    assert!(errors.is_empty(), "tracker errors: {errors:#?}");
+    assert_lua(&env, "return C_QuestLog.GetNumQuestLogEntries() == 2",
+        "both injected quests should be listed");
+    assert_lua(&env, "return QuestObjectiveTracker:GetExistingBlock(81001) ~= nil",
+        "the quest tracker should have a block for the injected quest");
+}
+
+#[test]
+fn test_player_frame_health_bar_follows_unit_model() {
+    let env = load_all_addons();
+    install_test_error_handler(&env);
+    fire_events_and_timers(&env);
+    drain_test_errors(&env);
+
+    env.exec(
+        r#"
+        SetUnitState("player", { healthMax = 120000, health = 60000 })
+        UnitFrameHealthBar_Update(PlayerFrame_GetHealthBar(), "player")
+    "#,
+    )
+    .expect("updating the player unit should not error");
+    let _ = env.fire_on_update(0.016);
+
+    let errors = drain_test_errors(&env);
+    assert!(errors.is_empty(), "unit frame errors: {errors:#?}");
+    let (value, max): (f64, f64) = env
+        .eval(
+            r#"local bar = PlayerFrame_GetHealthBar()
+            local _, max = bar:GetMinMaxValues()
+            return bar:GetValue(), max"#,
+        )
+        .unwrap();
+    assert_eq!((value, max), (60000.0, 120000.0), "PlayerFrame health bar should be half full");
+
+    // Screenshot check: the bar's fill quad covers the left half of the bar.
+    env.state().borrow_mut().ensure_layout_rects();
+    let buckets = {
+        let mut state = env.state().borrow_mut();
+        let _ = state.get_strata_buckets();
+        state.strata_buckets.as_ref().unwrap().clone()
+    };
+    let (batch, bar) = {
+        let state = env.state().borrow();
+        let path = ["PlayerFrameContent", "PlayerFrameContentMain", "HealthBarsContainer", "HealthBar"];
+        let bar_id = path.iter().try_fold(state.widgets.get_id_by_name("PlayerFrame").unwrap(), |id, key| {
+            state.widgets.get(id).and_then(|f| f.children_keys.get(*key).copied())
+        });
+        let bar = compute_frame_rect(&state.widgets, bar_id.expect("PlayerFrame health bar"), 1024.0, 768.0);
+        let batch = build_quad_batch_for_registry(
+            &state.widgets, (1024.0, 768.0), Some("PlayerFrame"), None, None, None, None, None, &buckets,
+        );
+        (batch, bar)
+    };
+    let half_fill = batch.vertices.chunks(4).any(|q| {
+        let min_x = q.iter().map(|v| v.position[0]).fold(f32::MAX, f32::min);
+        let max_x = q.iter().map(|v| v.position[0]).fold(f32::MIN, f32::max);
+        let mid_y = q.iter().map(|v| v.position[1]).sum::<f32>() / 4.0;
+        (min_x - bar.x).abs() < 1.0
+            && (max_x - min_x - bar.width / 2.0).abs() < 1.0
+            && mid_y > bar.y
+            && mid_y < bar.y + bar.height
+    });
+    assert!(half_fill, "no half-width fill quad inside the health bar at {bar:?}");
+
+    if common::try_create_gpu_device().is_none() || !PathBuf::from("./textures").exists() {
+        eprintln!("Skipping health bar render: no GPU adapter or textures");
+        return;
+    }
+    let mut tex_mgr = TextureManager::new("./textures");
+    let image = render_scene(&batch, &mut tex_mgr, 1024, 768, None).image;
+    let y = (bar.y + bar.height / 2.0) as u32;
+    let filled = image.get_pixel((bar.x + bar.width * 0.25) as u32, y).0;
+    let empty = image.get_pixel((bar.x + bar.width * 0.75) as u32, y).0;
+    assert_ne!(filled, empty, "the filled half should render differently from the empty half");
+}
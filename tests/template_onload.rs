@@ -1,7 +1,8 @@
 //! Regression tests for template OnLoad firing.
 //!
 //! Verifies that fire_on_load does NOT fire the mixin's OnLoad on child frames
-//! that share the parent's mixin but have no <Scripts> section.
+//! that share the parent's mixin but have no <Scripts> section, and that
+//! inherited scripts compose per `inherit` and `intrinsicOrder`.
 
 use wow_ui_sim::loader::create_frame_from_xml;
 use wow_ui_sim::lua_api::WowLuaEnv;
@@ -101,3 +102,65 @@ fn template_child_shared_mixin_no_onload_xml() {
     let result = check_onload_only_on_parent(&env, "SpellBtnXml");
     assert_eq!(result, "ok", "XML loading path: {}", result);
 }
+
+/// Register every virtual frame in `xml` as a template, then create the
+/// non-virtual ones through the XML loader.
+fn load_xml_frames(env: &WowLuaEnv, xml: &str) {
+    let ui = parse_xml(xml).unwrap();
+    for element in &ui.elements {
+        let XmlElement::Frame(frame) = element else { continue };
+        if frame.is_virtual == Some(true) {
+            register_template(frame.name.as_deref().unwrap(), "Frame", frame.clone());
+        } else {
+            create_frame_from_xml(&env.loader_env(), frame, "Frame", None, None).unwrap();
+        }
+    }
+}
+
+/// inherit="append": the instance handler runs, then the inherited one.
+#[test]
+fn template_onload_inherit_append_runs_both_in_order() {
+    let env = WowLuaEnv::new().unwrap();
+    env.exec("__script_order = {}").unwrap();
+    load_xml_frames(&env, r#"
+        <Ui>
+            <Frame name="TestAppendBaseTpl" virtual="true">
+                <Scripts>
+                    <OnLoad>table.insert(__script_order, "base")</OnLoad>
+                </Scripts>
+            </Frame>
+            <Frame name="TestAppendDerived" inherits="TestAppendBaseTpl" parent="UIParent">
+                <Scripts>
+                    <OnLoad inherit="append">table.insert(__script_order, "derived")</OnLoad>
+                </Scripts>
+            </Frame>
+        </Ui>
+    "#);
+
+    let order: String = env.eval("return table.concat(__script_order, ',')").unwrap();
+    assert_eq!(order, "derived,base");
+}
+
+/// intrinsicOrder="precall" survives a plain override and runs first.
+#[test]
+fn template_intrinsic_order_precall_runs_before_override() {
+    let env = WowLuaEnv::new().unwrap();
+    env.exec("__script_order = {}").unwrap();
+    load_xml_frames(&env, r#"
+        <Ui>
+            <Frame name="TestPrecallBaseTpl" virtual="true">
+                <Scripts>
+                    <OnLoad intrinsicOrder="precall">table.insert(__script_order, "intrinsic")</OnLoad>
+                </Scripts>
+            </Frame>
+            <Frame name="TestPrecallDerived" inherits="TestPrecallBaseTpl" parent="UIParent">
+                <Scripts>
+                    <OnLoad>table.insert(__script_order, "derived")</OnLoad>
+                </Scripts>
+            </Frame>
+        </Ui>
+    "#);
+
+    let order: String = env.eval("return table.concat(__script_order, ',')").unwrap();
+    assert_eq!(order, "intrinsic,derived");
+}
@@ -1,5 +1,6 @@
 use std::path::{Path, PathBuf};
-use wow_ui_sim::toc::TocFile;
+use wow_ui_sim::loader::{find_toc_file, find_toc_file_for_flavor};
+use wow_ui_sim::toc::{TocFile, TocFlavor};
 
 fn blizzard_shared_xml_base_toc() -> PathBuf {
     PathBuf::from(env!("CARGO_MANIFEST_DIR"))
@@ -85,3 +86,59 @@ fn test_lua_and_xml_files() {
     assert!(lua_count > 0, "Expected Lua files");
     assert!(xml_count > 0, "Expected XML files");
 }
+
+/// Addon folder "Multi" shipping the given TOC file names.
+fn addon_with_tocs(names: &[&str]) -> tempfile::TempDir {
+    let root = tempfile::tempdir().unwrap();
+    let dir = root.path().join("Multi");
+    std::fs::create_dir(&dir).unwrap();
+    for name in names {
+        std::fs::write(dir.join(name), "## Interface: 110207\nCore.lua\n").unwrap();
+    }
+    root
+}
+
+fn found_name(root: &tempfile::TempDir, flavor: TocFlavor) -> Option<String> {
+    find_toc_file_for_flavor(&root.path().join("Multi"), flavor)
+        .map(|p| p.file_name().unwrap().to_string_lossy().into_owned())
+}
+
+#[test]
+fn test_find_toc_prefers_flavor_suffix() {
+    let root = addon_with_tocs(&["Multi.toc", "Multi_Vanilla.toc", "Multi_Mainline.toc", "Multi-Cata.toc"]);
+
+    assert_eq!(found_name(&root, TocFlavor::Mainline).as_deref(), Some("Multi_Mainline.toc"));
+    assert_eq!(found_name(&root, TocFlavor::Vanilla).as_deref(), Some("Multi_Vanilla.toc"));
+    assert_eq!(found_name(&root, TocFlavor::Cata).as_deref(), Some("Multi-Cata.toc"));
+    // No Wrath variant: the unsuffixed TOC is the fallback.
+    assert_eq!(found_name(&root, TocFlavor::Wrath).as_deref(), Some("Multi.toc"));
+    assert_eq!(
+        find_toc_file(&root.path().join("Multi")),
+        find_toc_file_for_flavor(&root.path().join("Multi"), TocFlavor::Mainline),
+    );
+}
+
+#[test]
+fn test_flavor_from_interface_number() {
+    assert_eq!(TocFlavor::from_interface(110007), TocFlavor::Mainline);
+    assert_eq!(TocFlavor::from_interface(11507), TocFlavor::Vanilla);
+    assert_eq!(TocFlavor::from_interface(20504), TocFlavor::Tbc);
+    assert_eq!(TocFlavor::from_interface(38000), TocFlavor::Wrath);
+    assert_eq!(TocFlavor::from_interface(40402), TocFlavor::Cata);
+    assert_eq!(TocFlavor::from_interface(50500), TocFlavor::Mists);
+    assert_eq!(TocFlavor::Vanilla.project_id(), 2);
+    assert_eq!(TocFlavor::Mists.expansion_level(), 4);
+}
+
+#[test]
+fn test_find_toc_skips_other_flavors_without_base_toc() {
+    let root = addon_with_tocs(&["Multi_Classic.toc", "Multi_Wrath.toc"]);
+    assert_eq!(found_name(&root, TocFlavor::Mainline), None);
+    assert_eq!(found_name(&root, TocFlavor::Vanilla).as_deref(), Some("Multi_Classic.toc"));
+}
+
+#[test]
+fn test_interface_versions_parse_each_listed_build() {
+    let toc = TocFile::parse(Path::new("/addons/Multi"), "## Interface: 110207, 11507 ,bogus, 50500\n");
+    assert_eq!(toc.interface_versions(), vec![110207, 11507, 50500]);
+}
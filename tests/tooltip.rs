@@ -89,6 +89,70 @@ fn test_settext_clears_and_sets_first_line() {
     assert_eq!(count, 1, "SetText should clear existing lines and add one");
 }
 
+/// Lines of GameTooltip as (left text, left color).
+fn game_tooltip_lines(env: &WowLuaEnv) -> Vec<(String, (f32, f32, f32))> {
+    let state = env.state().borrow();
+    let id = state.widgets.get_id_by_name("GameTooltip").unwrap();
+    state.tooltips[&id]
+        .lines
+        .iter()
+        .map(|l| (l.left_text.clone(), l.left_color))
+        .collect()
+}
+
+#[test]
+fn test_set_item_by_id_fills_name_in_quality_color() {
+    let env = WowLuaEnv::new().unwrap();
+
+    env.exec(
+        r#"
+        GameTooltip:SetOwner(UIParent, "ANCHOR_RIGHT")
+        GameTooltip:SetItemByID(19019)
+    "#,
+    )
+    .unwrap();
+
+    let lines = game_tooltip_lines(&env);
+    let (name, color) = &lines[0];
+    assert_eq!(name, "Thunderfury, Blessed Blade of the Windseeker");
+    assert_eq!(*color, (1.0, 0.5, 0.0), "legendary items are orange");
+    assert!(lines.iter().any(|(t, _)| t == "Item Level 29"), "lines: {lines:?}");
+    assert!(lines.iter().any(|(t, _)| t == "Binds when picked up"), "lines: {lines:?}");
+
+    let (item_name, item_id): (String, u32) = env
+        .eval("local name, _, id = GameTooltip:GetItem(); return name, id")
+        .unwrap();
+    assert_eq!(item_name, "Thunderfury, Blessed Blade of the Windseeker");
+    assert_eq!(item_id, 19019);
+}
+
+#[test]
+fn test_set_hyperlink_parses_item_links() {
+    let env = WowLuaEnv::new().unwrap();
+
+    env.exec(r#"GameTooltip:SetHyperlink("item:19019:0:0:0")"#).unwrap();
+    assert_eq!(game_tooltip_lines(&env)[0].0, "Thunderfury, Blessed Blade of the Windseeker");
+
+    env.exec(
+        r#"
+        local _, link = GameTooltip:GetItem()
+        GameTooltip:ClearLines()
+        GameTooltip:SetHyperlink(link)
+    "#,
+    )
+    .unwrap();
+    assert_eq!(game_tooltip_lines(&env)[0].0, "Thunderfury, Blessed Blade of the Windseeker");
+
+    // The ID may end at the `|h` with no trailing fields.
+    env.exec(r#"GameTooltip:ClearLines() GameTooltip:SetHyperlink("|Hitem:19019|h[Thunderfury]|h")"#).unwrap();
+    assert_eq!(game_tooltip_lines(&env)[0].0, "Thunderfury, Blessed Blade of the Windseeker");
+
+    env.exec(r#"GameTooltip:SetHyperlink("spell:19750")"#).unwrap();
+    assert!(game_tooltip_lines(&env).is_empty());
+    let has_item: bool = env.eval("return GameTooltip:GetItem() ~= nil").unwrap();
+    assert!(!has_item);
+}
+
 #[test]
 fn test_setowner_and_isowned_and_getowner() {
     let env = WowLuaEnv::new().unwrap();
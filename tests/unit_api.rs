@@ -262,6 +262,37 @@ fn test_unit_full_name_no_target() {
     assert_eq!(realm, "SimRealm");
 }
 
+#[test]
+fn test_realm_name_follows_configured_realm() {
+    let env = env();
+    env.state().borrow_mut().realm_name = "Burning Blade".to_string();
+    let (realm, normalized, full_realm): (String, String, String) = env
+        .eval("return GetRealmName(), GetNormalizedRealmName(), select(2, UnitFullName('player'))")
+        .unwrap();
+    assert_eq!(realm, "Burning Blade");
+    assert_eq!(normalized, "BurningBlade");
+    assert_eq!(full_realm, "BurningBlade");
+}
+
+#[test]
+fn test_ambiguate_modes() {
+    let env = env();
+    env.state().borrow_mut().realm_name = "Burning Blade".to_string();
+    let names: Vec<String> = env
+        .eval(
+            r#"return {
+                Ambiguate("Haky-BurningBlade", "short"),
+                Ambiguate("Haky-BurningBlade", "none"),
+                Ambiguate("Haky-Stormrage", "none"),
+                Ambiguate("Haky-Stormrage", "short"),
+                Ambiguate("Haky-Stormrage", "guild"),
+                Ambiguate("Haky", "none"),
+            }"#,
+        )
+        .unwrap();
+    assert_eq!(names, ["Haky", "Haky", "Haky-Stormrage", "Haky", "Haky-Stormrage", "Haky"]);
+}
+
 // ============================================================================
 // GetUnitName
 // ============================================================================
@@ -722,3 +753,108 @@ fn test_get_weapon_enchant_info_offhand() {
     assert_eq!(oh_charges, 0);
     assert_eq!(oh_id, 0);
 }
+
+// ============================================================================
+// SetUnitState (unit model)
+// ============================================================================
+
+/// Log every unit event as "EVENT:arg1:arg2" into `UnitLog`.
+fn log_unit_events(env: &WowLuaEnv) {
+    env.exec(
+        r#"
+        UnitLog = {}
+        local f = CreateFrame("Frame")
+        for _, e in ipairs({ "UNIT_HEALTH", "UNIT_MAXHEALTH", "UNIT_POWER_UPDATE", "UNIT_MAXPOWER",
+                "UNIT_NAME_UPDATE", "UNIT_LEVEL", "UNIT_PET", "PLAYER_TARGET_CHANGED",
+                "GROUP_ROSTER_UPDATE" }) do
+            f:RegisterEvent(e)
+        end
+        f:SetScript("OnEvent", function(_, event, ...)
+            table.insert(UnitLog, table.concat({ event, ... }, ":"))
+        end)
+    "#,
+    )
+    .unwrap();
+}
+
+fn unit_log(env: &WowLuaEnv) -> String {
+    env.eval("local s = table.concat(UnitLog, ','); UnitLog = {}; return s").unwrap()
+}
+
+#[test]
+fn test_set_unit_state_player_health_and_power() {
+    let env = env();
+    log_unit_events(&env);
+    env.exec("SetUnitState('player', { health = 50000, power = 20000, powerMax = 40000 })").unwrap();
+    let (hp, power, power_max): (i32, i32, i32) = env
+        .eval("return UnitHealth('player'), UnitPower('player'), UnitPowerMax('player')")
+        .unwrap();
+    assert_eq!((hp, power, power_max), (50000, 20000, 40000));
+    assert_eq!(
+        unit_log(&env),
+        "UNIT_HEALTH:player,UNIT_MAXPOWER:player:MANA,UNIT_POWER_UPDATE:player:MANA"
+    );
+
+    // Unchanged values fire nothing; health is clamped to the new maximum.
+    env.exec("SetUnitState('player', { health = 50000 })").unwrap();
+    assert_eq!(unit_log(&env), "");
+    env.exec("SetUnitState('player', { healthMax = 30000 })").unwrap();
+    let hp: i32 = env.eval("return UnitHealth('player')").unwrap();
+    assert_eq!(hp, 30000);
+    assert_eq!(unit_log(&env), "UNIT_MAXHEALTH:player,UNIT_HEALTH:player");
+}
+
+#[test]
+fn test_set_unit_state_name_class_level() {
+    let env = env();
+    log_unit_events(&env);
+    env.exec("SetUnitState('party1', { name = 'Anduin', class = 'PRIEST', level = 70 })").unwrap();
+    let (name, class_file, level): (String, String, i32) = env
+        .eval("local _, file = UnitClass('party1'); return UnitName('party1'), file, UnitLevel('party1')")
+        .unwrap();
+    assert_eq!((name.as_str(), class_file.as_str(), level), ("Anduin", "PRIEST", 70));
+    assert_eq!(unit_log(&env), "UNIT_LEVEL:party1,UNIT_NAME_UPDATE:party1");
+
+    env.exec("SetUnitState('player', { class = 8 })").unwrap();
+    let class_file: String = env.eval("return UnitClassBase('player')").unwrap();
+    assert_eq!(class_file, "MAGE");
+}
+
+#[test]
+fn test_set_unit_state_target_and_pet_presence() {
+    let env = env();
+    log_unit_events(&env);
+    let err = env.exec("SetUnitState('target', { health = 1 })").unwrap_err();
+    assert!(err.to_string().contains("does not exist"), "{err}");
+
+    env.exec("SetUnitState('target', { exists = true, health = 9000 })").unwrap();
+    let (exists, hp, name): (bool, i32, String) =
+        env.eval("return UnitExists('target'), UnitHealth('target'), UnitName('target')").unwrap();
+    assert_eq!((exists, hp, name.as_str()), (true, 9000, "Hogger"));
+    assert_eq!(unit_log(&env), "PLAYER_TARGET_CHANGED,UNIT_HEALTH:target");
+
+    let (pet_power, pet_token): (i32, String) =
+        env.eval("local _, token = UnitPowerType('pet'); return UnitPower('pet'), token").unwrap();
+    assert_eq!((pet_power, pet_token.as_str()), (100, "FOCUS"));
+    env.exec("SetUnitState('pet', { exists = false })").unwrap();
+    let (exists, hp): (bool, i32) = env.eval("return UnitExists('pet'), UnitHealth('pet')").unwrap();
+    assert_eq!((exists, hp), (false, 0));
+    assert_eq!(unit_log(&env), "UNIT_PET:player");
+}
+
+#[test]
+fn test_set_unit_state_party_roster() {
+    let env = env();
+    log_unit_events(&env);
+    env.exec("SetUnitState('party1', { exists = false })").unwrap();
+    // Later members shift down a slot.
+    let (party1, party4): (String, bool) =
+        env.eval("return UnitName('party1'), UnitExists('party4')").unwrap();
+    assert_eq!((party1.as_str(), party4), ("Kazzara", false));
+    assert_eq!(unit_log(&env), "GROUP_ROSTER_UPDATE");
+
+    env.exec("SetUnitState('party4', { exists = true, name = 'Varian' })").unwrap();
+    let name: String = env.eval("return UnitName('party4')").unwrap();
+    assert_eq!(name, "Varian");
+    assert!(env.exec("SetUnitState('party6', { exists = true })").is_err());
+}
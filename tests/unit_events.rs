@@ -0,0 +1,87 @@
+//! Tests for RegisterUnitEvent and unit-filtered event dispatch.
+
+use wow_ui_sim::lua_api::WowLuaEnv;
+
+fn env() -> WowLuaEnv {
+    WowLuaEnv::new().expect("create env")
+}
+
+fn fire_unit_event(env: &WowLuaEnv, event: &str, unit: &str) {
+    let unit = mlua::Value::String(env.lua().create_string(unit).unwrap());
+    env.fire_event_with_args(event, &[unit]).unwrap();
+}
+
+/// Create a frame that appends "<tag>:<unit>" to `UnitEventLog` on every event.
+fn logging_frame(env: &WowLuaEnv, tag: &str, register: &str) {
+    env.exec(&format!(
+        r#"
+        UnitEventLog = UnitEventLog or {{}}
+        local f = CreateFrame("Frame")
+        f:SetScript("OnEvent", function(self, event, unit)
+            table.insert(UnitEventLog, "{tag}:" .. tostring(unit))
+        end)
+        {register}
+        "#
+    ))
+    .unwrap();
+}
+
+fn event_log(env: &WowLuaEnv) -> String {
+    env.eval("return table.concat(UnitEventLog or {}, ',')").unwrap()
+}
+
+#[test]
+fn test_unit_event_delivered_only_to_matching_unit() {
+    let env = env();
+    logging_frame(&env, "p", r#"f:RegisterUnitEvent("UNIT_HEALTH", "player")"#);
+    logging_frame(&env, "t", r#"f:RegisterUnitEvent("UNIT_HEALTH", "target")"#);
+
+    fire_unit_event(&env, "UNIT_HEALTH", "player");
+
+    assert_eq!(event_log(&env), "p:player");
+}
+
+#[test]
+fn test_unit_event_accepts_any_listed_unit() {
+    let env = env();
+    logging_frame(&env, "pt", r#"f:RegisterUnitEvent("UNIT_POWER_UPDATE", "player", "target")"#);
+
+    fire_unit_event(&env, "UNIT_POWER_UPDATE", "target");
+    fire_unit_event(&env, "UNIT_POWER_UPDATE", "focus");
+    env.exec(r#"FireEvent("UNIT_POWER_UPDATE", "player")"#).unwrap();
+
+    assert_eq!(event_log(&env), "pt:target,pt:player");
+}
+
+#[test]
+fn test_register_event_clears_unit_filter() {
+    let env = env();
+    logging_frame(
+        &env,
+        "all",
+        r#"f:RegisterUnitEvent("UNIT_HEALTH", "player")
+        f:RegisterEvent("UNIT_HEALTH")"#,
+    );
+
+    fire_unit_event(&env, "UNIT_HEALTH", "party1");
+
+    assert_eq!(event_log(&env), "all:party1");
+}
+
+#[test]
+fn test_unregister_event_drops_unit_filter() {
+    let env = env();
+    logging_frame(
+        &env,
+        "u",
+        r#"f:RegisterUnitEvent("UNIT_HEALTH", "player")
+        f:UnregisterEvent("UNIT_HEALTH")
+        UnitEventRegistered = f:IsEventRegistered("UNIT_HEALTH")"#,
+    );
+
+    fire_unit_event(&env, "UNIT_HEALTH", "player");
+
+    assert_eq!(event_log(&env), "");
+    let registered: bool = env.eval("return UnitEventRegistered").unwrap();
+    assert!(!registered);
+}
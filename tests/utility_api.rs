@@ -1,6 +1,10 @@
 //! Tests for utility API functions (utility_api.rs).
 
+use std::cell::RefCell;
+use std::rc::Rc;
 use wow_ui_sim::lua_api::WowLuaEnv;
+use wow_ui_sim::sound::SoundBackend;
+use wow_ui_sim::soundkits::soundkit_file;
 
 fn env() -> WowLuaEnv {
     WowLuaEnv::new().expect("Failed to create Lua environment")
@@ -416,6 +420,65 @@ fn test_hooksecurefunc_table() {
     assert!(hook_ran);
 }
 
+#[test]
+fn test_hooksecurefunc_hook_sees_args_after_original() {
+    let env = env();
+    let (log, a, b): (String, i32, i32) = env
+        .eval(
+            r#"
+            local log = {}
+            function HookedAdd(x, y)
+                table.insert(log, "orig:" .. x .. "," .. y)
+                return x + y, x * y
+            end
+            hooksecurefunc("HookedAdd", function(x, y)
+                table.insert(log, "hook:" .. x .. "," .. y)
+            end)
+            local sum, product = HookedAdd(3, 4)
+            return table.concat(log, " "), sum, product
+            "#,
+        )
+        .unwrap();
+    assert_eq!(log, "orig:3,4 hook:3,4");
+    assert_eq!((a, b), (7, 12));
+}
+
+#[test]
+fn test_hooksecurefunc_frame_method() {
+    let env = env();
+    let (shown, calls): (bool, String) = env
+        .eval(
+            r#"
+            local f = CreateFrame("Frame")
+            local other = CreateFrame("Frame")
+            f:Hide()
+            local calls = {}
+            hooksecurefunc(f, "Show", function(self) table.insert(calls, self == f and "f" or "?") end)
+            f:Show()
+            other:Show()
+            return f:IsShown(), table.concat(calls, ",")
+            "#,
+        )
+        .unwrap();
+    assert!(shown);
+    assert_eq!(calls, "f");
+}
+
+#[test]
+fn test_hook_error_does_not_break_original() {
+    let env = env();
+    let result: i32 = env
+        .eval(
+            r#"
+            function HookedDouble(x) return x * 2 end
+            hooksecurefunc("HookedDouble", function() error("hook failed") end)
+            return HookedDouble(21)
+            "#,
+        )
+        .unwrap();
+    assert_eq!(result, 42);
+}
+
 // ============================================================================
 // securecallfunction
 // ============================================================================
@@ -491,6 +554,55 @@ fn test_sound_stubs_exist() {
     }
 }
 
+/// Sound backend that records the files it was asked to play.
+struct RecordingSounds(Rc<RefCell<Vec<String>>>);
+
+impl SoundBackend for RecordingSounds {
+    fn play_sound_file(&mut self, path: &str) -> Option<u32> {
+        let mut played = self.0.borrow_mut();
+        played.push(path.to_string());
+        Some(played.len() as u32)
+    }
+
+    fn stop_sound(&mut self, _handle: u32) {}
+
+    fn is_playing(&self, _handle: u32) -> bool {
+        false
+    }
+}
+
+#[test]
+fn test_play_sound_resolves_soundkit_to_file() {
+    // SOUNDKIT.IG_MAINMENU_OPEN
+    assert_eq!(soundkit_file(850), Some("igmainmenuopen.ogg"));
+    assert_eq!(soundkit_file(858), Some("igmainmenuoptioncheckboxoff.ogg"));
+    assert_eq!(soundkit_file(1), None);
+
+    let env = env();
+    let (will_play, is_handle): (bool, bool) = env
+        .eval("local ok, h = PlaySound(850, 'sfx'); return ok, type(h) == 'number'")
+        .unwrap();
+    assert!(will_play && is_handle, "known kit should report willPlay with a handle");
+
+    let results: (bool, bool, bool) = env
+        .eval("return (PlaySound(1)), (PlaySound(850, 'Bogus')), (C_Sound.PlaySound(852, 'Master'))")
+        .unwrap();
+    assert_eq!(results, (false, false, true));
+}
+
+#[test]
+fn test_play_sound_hands_resolved_file_to_sound_backend() {
+    let env = env();
+    let played = Rc::new(RefCell::new(Vec::new()));
+    env.state().borrow_mut().sound_manager = Some(Box::new(RecordingSounds(Rc::clone(&played))));
+
+    let handle: i64 = env.eval("local _, h = PlaySound(850); return h").unwrap();
+    env.exec("PlaySound(1); PlaySoundFile('Interface/custom.ogg')").unwrap();
+
+    assert_eq!(handle, 1, "handle comes from the backend");
+    assert_eq!(*played.borrow(), ["igmainmenuopen.ogg", "Interface/custom.ogg"]);
+}
+
 // ============================================================================
 // String library aliases
 // ============================================================================
@@ -509,6 +621,58 @@ fn test_strtrim() {
     assert_eq!(result, "hello");
 }
 
+#[test]
+fn test_strtrim_custom_chars_respects_code_points() {
+    let env = env();
+    // "é" is C3 A9 and "è" is C3 A8: a byte-wise trim of "é" would strip
+    // the shared C3 lead byte off "è".
+    let result: String = env.eval("return strtrim('éèxé', 'é')").unwrap();
+    assert_eq!(result, "èx");
+    let dashes: String = env.eval("return strtrim('--x-y--', '-')").unwrap();
+    assert_eq!(dashes, "x-y");
+}
+
+#[test]
+fn test_utf8_length_and_sub() {
+    let env = env();
+    let (bytes, chars, alias): (i32, i32, i32) = env
+        .eval("local s = 'Añ€b'; return #s, string.utf8len(s), strlenutf8(s)")
+        .unwrap();
+    assert_eq!((bytes, chars, alias), (7, 4, 4));
+
+    let (head, tail, middle, empty): (String, String, String, String) = env
+        .eval(
+            "local s = 'Añ€b'
+            return string.utf8sub(s, 1, 3), string.utf8sub(s, -2), string.utf8sub(s, 2, 2), string.utf8sub(s, 5)",
+        )
+        .unwrap();
+    assert_eq!(head, "Añ€");
+    assert_eq!(tail, "€b");
+    assert_eq!(middle, "ñ");
+    assert_eq!(empty, "");
+}
+
+#[test]
+fn test_utf8sub_truncation_measures_truncated_text() {
+    let env = env();
+    let (text, width, full_width): (String, f32, f32) = env
+        .eval(
+            r#"local fs = UIParent:CreateFontString(nil, "OVERLAY")
+            local name = "Ägir€Lichtträger"
+            fs:SetText(name)
+            local full = fs:GetStringWidth()
+            fs:SetText(string.utf8sub(name, 1, 5))
+            return fs:GetText(), fs:GetStringWidth(), full"#,
+        )
+        .unwrap();
+    assert_eq!(text, "Ägir€", "the 3-byte character stays whole");
+    assert!(width > 0.0 && width < full_width);
+    let single: f32 = env
+        .eval(r#"local fs = UIParent:CreateFontString(nil, "OVERLAY"); fs:SetText("Ägir€"); return fs:GetStringWidth()"#)
+        .unwrap();
+    assert_eq!(width, single, "width matches the truncated text on its own");
+}
+
 #[test]
 fn test_strjoin() {
     let env = env();
@@ -776,6 +940,22 @@ fn test_securecall() {
     assert_eq!(result, 10);
 }
 
+#[test]
+fn test_securecall_by_name_swallows_errors() {
+    let env = env();
+    let (count, after): (i32, bool) = env
+        .eval(
+            r#"
+            function SecureCallBoom() error("boom") end
+            local n = select("#", securecall("SecureCallBoom"))
+            return n, true
+            "#,
+        )
+        .unwrap();
+    assert_eq!(count, 0);
+    assert!(after);
+}
+
 // ============================================================================
 // forceinsecure
 // ============================================================================
@@ -0,0 +1,63 @@
+//! Tests for the `watch` file snapshots and warning diffs.
+
+use std::fs;
+
+use wow_ui_sim::watch::{Snapshot, WarningDiff};
+
+#[test]
+fn snapshot_tracks_only_addon_sources() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::create_dir(dir.path().join("Modules")).unwrap();
+    fs::write(dir.path().join("MyAddon.toc"), "## Interface: 110007\nCore.lua\n").unwrap();
+    fs::write(dir.path().join("Core.lua"), "print('hi')").unwrap();
+    fs::write(dir.path().join("Modules/Frame.XML"), "<Ui/>").unwrap();
+    fs::write(dir.path().join("Core.lua.swp"), "junk").unwrap();
+    fs::write(dir.path().join("notes.txt"), "junk").unwrap();
+
+    assert_eq!(Snapshot::scan(dir.path()).len(), 3);
+}
+
+#[test]
+fn snapshot_reports_edits_additions_and_removals() {
+    let dir = tempfile::tempdir().unwrap();
+    let core = dir.path().join("Core.lua");
+    let old = dir.path().join("Old.lua");
+    fs::write(&core, "local a = 1").unwrap();
+    fs::write(&old, "").unwrap();
+    let before = Snapshot::scan(dir.path());
+
+    fs::write(&core, "local a = 12").unwrap();
+    fs::remove_file(&old).unwrap();
+    let new = dir.path().join("New.xml");
+    fs::write(&new, "<Ui/>").unwrap();
+    let after = Snapshot::scan(dir.path());
+
+    assert_ne!(before, after);
+    assert_eq!(before.changed_paths(&after), vec![core, new, old]);
+}
+
+#[test]
+fn snapshot_sees_atomic_save_by_rename() {
+    let dir = tempfile::tempdir().unwrap();
+    let core = dir.path().join("Core.lua");
+    fs::write(&core, "local a = 1").unwrap();
+    let before = Snapshot::scan(dir.path());
+
+    // Editors write a temp file and rename it over the original.
+    let tmp = dir.path().join(".Core.lua.tmp123");
+    fs::write(&tmp, "local a = 1 -- edited").unwrap();
+    fs::rename(&tmp, &core).unwrap();
+
+    assert_eq!(before.changed_paths(&Snapshot::scan(dir.path())), vec![core]);
+}
+
+#[test]
+fn warning_diff_lists_new_and_fixed_warnings() {
+    let previous = vec!["a.lua:1: bad".to_string(), "b.xml: missing".to_string()];
+    let current = vec!["b.xml: missing".to_string(), "c.lua:3: new".to_string(), "c.lua:3: new".to_string()];
+
+    let diff = WarningDiff::between(&previous, &current);
+    assert_eq!(diff.added, vec!["c.lua:3: new"]);
+    assert_eq!(diff.removed, vec!["a.lua:1: bad"]);
+    assert!(WarningDiff::between(&current, &current).is_empty());
+}
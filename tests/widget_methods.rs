@@ -207,6 +207,13 @@ fn test_colorselect_hsv_to_rgb_green() {
     assert!(b.abs() < 0.01);
 }
 
+#[test]
+fn test_hsv_to_rgb_wraps_negative_hue() {
+    // -30° is the same hue as 330°: full red, half blue.
+    let (r, g, b) = wow_ui_sim::widget::hsv_to_rgb(-30.0, 1.0, 1.0);
+    assert!((r - 1.0).abs() < 0.01 && g.abs() < 0.01 && (b - 0.5).abs() < 0.01, "{r} {g} {b}");
+}
+
 #[test]
 fn test_colorselect_rgb_to_hsv_conversion() {
     let env = WowLuaEnv::new().unwrap();
@@ -229,6 +236,65 @@ fn test_colorselect_rgb_to_hsv_conversion() {
     assert!((v - 1.0).abs() < 0.01, "Value for red should be 1, got {}", v);
 }
 
+#[test]
+fn test_colorselect_hue_survives_zero_saturation() {
+    let env = WowLuaEnv::new().unwrap();
+
+    env.exec(
+        r#"
+        local cs = CreateFrame("ColorSelect", "TestCSGrey", UIParent)
+        cs:SetColorHSV(200, 0, 0.5)
+    "#,
+    )
+    .unwrap();
+
+    // Grey has no hue in RGB, but the selector remembers where it was.
+    let (h, s, v): (f64, f64, f64) = env.eval("return TestCSGrey:GetColorHSV()").unwrap();
+    assert_eq!((h, s, v), (200.0, 0.0, 0.5));
+}
+
+#[test]
+fn test_colorselect_fires_on_color_select_once_per_change() {
+    let env = WowLuaEnv::new().unwrap();
+
+    env.exec(
+        r#"
+        Selected = {}
+        local cs = CreateFrame("ColorSelect", "TestCSEvents", UIParent)
+        cs:SetScript("OnColorSelect", function(self, r, g, b)
+            table.insert(Selected, string.format("%.1f,%.1f,%.1f", r, g, b))
+        end)
+        cs:SetColorRGB(1, 0, 0)
+        cs:SetColorRGB(1, 0, 0)
+        cs:SetColorHSV(120, 1, 1)
+    "#,
+    )
+    .unwrap();
+
+    let selected: String = env.eval("return table.concat(Selected, ' ')").unwrap();
+    assert_eq!(selected, "1.0,0.0,0.0 0.0,1.0,0.0");
+}
+
+#[test]
+fn test_colorselect_wheel_and_value_textures() {
+    let env = WowLuaEnv::new().unwrap();
+
+    let (wheel, value, unset): (bool, bool, bool) = env
+        .eval(
+            r#"
+            local cs = CreateFrame("ColorSelect", "TestCSTex", UIParent)
+            local wheel = cs:CreateTexture()
+            local value = cs:CreateTexture()
+            local unset = cs:GetColorWheelTexture() == nil
+            cs:SetColorWheelTexture(wheel)
+            cs:SetColorValueTexture(value)
+            return cs:GetColorWheelTexture() == wheel, cs:GetColorValueTexture() == value, unset
+        "#,
+        )
+        .unwrap();
+    assert!(wheel && value && unset);
+}
+
 // ============================================================================
 // SimpleHTML: SetHyperlinkFormat / GetHyperlinkFormat
 // ============================================================================
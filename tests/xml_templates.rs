@@ -104,6 +104,63 @@ fn test_create_frame_finds_xml_template() {
     assert!(env.eval::<bool>("return TestWithTemplate ~= nil").unwrap());
 }
 
+#[test]
+fn test_template_relative_key_parent_prefix_uses_parent_name() {
+    clear_templates();
+    let env = WowLuaEnv::new().unwrap();
+    register_first_template(
+        r#"<Ui><Frame name="RelNameTemplate" virtual="true">
+            <Size x="200" y="100"/>
+            <Frames>
+                <Frame name="$parentHeader"><Size x="200" y="20"/>
+                    <Anchors><Anchor point="TOPLEFT"/></Anchors>
+                </Frame>
+                <Frame parentKey="Header"><Size x="10" y="10"/></Frame>
+                <Frame parentKey="Body"><Size x="200" y="50"/>
+                    <Anchors><Anchor point="TOPLEFT" relativeKey="$parentHeader" relativePoint="BOTTOMLEFT"/></Anchors>
+                </Frame>
+            </Frames>
+        </Frame></Ui>"#,
+        "RelNameTemplate", "Frame",
+    );
+    env.exec(r#"CreateFrame("Frame", "RelNameBox", UIParent, "RelNameTemplate")"#).unwrap();
+    // `$parentHeader` is the global RelNameBoxHeader, not the `Header` parentKey.
+    assert!(env.eval::<bool>("return select(2, RelNameBox.Body:GetPoint(1)) == RelNameBoxHeader").unwrap());
+}
+
+#[test]
+fn test_create_frame_reports_unknown_templates_once() {
+    let env = WowLuaEnv::new().unwrap();
+    register_first_template(
+        r#"<Ui><Frame name="DiagEmptyTemplate" virtual="true"/></Ui>"#,
+        "DiagEmptyTemplate", "Frame",
+    );
+    register_first_template(
+        r#"<Ui><Frame name="DiagDerivedTemplate" virtual="true" inherits="DiagMissingBaseTemplate"/></Ui>"#,
+        "DiagDerivedTemplate", "Frame",
+    );
+    env.state().borrow_mut().script_errors = Some(Vec::new());
+    env.exec_named(
+        r#"
+        CreateFrame("Frame", "DiagBogusFrame", UIParent, "DiagBogusTemplate")
+        CreateFrame("Frame", nil, UIParent, "DiagBogusTemplate")
+        CreateFrame("Frame", nil, UIParent, "DiagEmptyTemplate, DiagDerivedTemplate")
+        "#,
+        "@Interface/AddOns/MyAddon/MyAddon.lua",
+    )
+    .unwrap();
+
+    let errors = env.state().borrow_mut().script_errors.take().unwrap();
+    assert_eq!(
+        errors,
+        [
+            r#"CreateFrame(): Couldn't find inherited node "DiagBogusTemplate" (from MyAddon)"#,
+            r#"CreateFrame(): Couldn't find inherited node "DiagMissingBaseTemplate" (inherited by "DiagDerivedTemplate", from MyAddon)"#,
+        ]
+    );
+    assert!(env.eval::<bool>("return DiagBogusFrame ~= nil").unwrap(), "the frame is still created");
+}
+
 // ============================================================================
 // Frame Creation from XML Tests
 // ============================================================================